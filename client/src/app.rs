@@ -210,6 +210,23 @@ pub fn run_game_frame(
             InputCommand::ShopBuy { npc_id, item_id, quantity } => ClientMessage::ShopBuy { npc_id: npc_id.clone(), item_id: item_id.clone(), quantity: *quantity },
             InputCommand::ShopSell { npc_id, item_id, quantity } => ClientMessage::ShopSell { npc_id: npc_id.clone(), item_id: item_id.clone(), quantity: *quantity },
             InputCommand::EnterPortal { portal_id } => ClientMessage::EnterPortal { portal_id: portal_id.clone() },
+            InputCommand::TradeRespond { requester_id, accept } => ClientMessage::TradeRespond { requester_id: requester_id.clone(), accept: *accept },
+            InputCommand::TradeOfferItem { slot_index, quantity } => ClientMessage::TradeOfferItem { slot_index: *slot_index, quantity: *quantity },
+            InputCommand::TradeRemoveItem { offer_index } => ClientMessage::TradeRemoveItem { offer_index: *offer_index },
+            InputCommand::TradeSetGold { amount } => ClientMessage::TradeSetGold { amount: *amount },
+            InputCommand::TradeAccept => ClientMessage::TradeAccept,
+            InputCommand::TradeConfirm => ClientMessage::TradeConfirm,
+            InputCommand::TradeCancel => ClientMessage::TradeCancel,
+            InputCommand::PartyRespond { inviter_id, accept } => ClientMessage::PartyRespond { inviter_id: inviter_id.clone(), accept: *accept },
+            InputCommand::PartyLeave => ClientMessage::PartyLeave,
+            InputCommand::PartyKick { member_id } => ClientMessage::PartyKick { member_id: member_id.clone() },
+            InputCommand::PartyPromote { member_id } => ClientMessage::PartyPromote { member_id: member_id.clone() },
+            InputCommand::SetTitle { achievement_id } => ClientMessage::SetTitle { achievement_id: achievement_id.clone() },
+            InputCommand::StorageOpen { furniture_id } => ClientMessage::StorageOpen { furniture_id: *furniture_id },
+            InputCommand::StorageDeposit { furniture_id, slot_index, quantity } => ClientMessage::StorageDeposit { furniture_id: *furniture_id, slot_index: *slot_index, quantity: *quantity },
+            InputCommand::StorageWithdraw { furniture_id, storage_slot } => ClientMessage::StorageWithdraw { furniture_id: *furniture_id, storage_slot: *storage_slot },
+            InputCommand::GravestoneOpen { gravestone_id } => ClientMessage::GravestoneOpen { gravestone_id: gravestone_id.clone() },
+            InputCommand::GravestoneRecover { gravestone_id } => ClientMessage::GravestoneRecover { gravestone_id: gravestone_id.clone() },
         };
         network.send(&msg);
     }
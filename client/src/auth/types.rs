@@ -44,6 +44,9 @@ pub struct CharacterInfo {
     pub hair_color: Option<i32>,
     #[serde(rename = "playedTime")]
     pub played_time: i64,
+    /// Died under a hardcore death rule; can no longer be played
+    #[serde(default)]
+    pub perished: bool,
 }
 
 #[derive(Deserialize)]
@@ -209,6 +209,8 @@ pub struct ChunkManager {
     view_radius: i32,
     /// Interior mode - if Some, we're in an interior with (width, height)
     interior_size: Option<(u32, u32)>,
+    /// Number of objects the interior map itself places (player furniture follows them)
+    interior_object_count: usize,
 }
 
 impl ChunkManager {
@@ -219,6 +221,7 @@ impl ChunkManager {
             current_chunk: ChunkCoord::new(0, 0),
             view_radius: 2, // Load 5x5 chunks around player
             interior_size: None,
+            interior_object_count: 0,
         }
     }
 
@@ -433,6 +436,7 @@ impl ChunkManager {
         }).collect();
 
         let collision_data = Chunk::unpack_collision_sized(collision, (width * height) as usize);
+        self.interior_object_count = objects.len();
 
         let chunk = Chunk {
             coord,
@@ -452,5 +456,17 @@ impl ChunkManager {
         self.chunks.clear();
         self.pending_requests.clear();
         self.interior_size = None;
+        self.interior_object_count = 0;
+    }
+
+    /// Replace the furniture placed in a house interior, keeping the map's own objects
+    pub fn set_interior_furniture(&mut self, furniture: Vec<MapObject>) {
+        if self.interior_size.is_none() {
+            return;
+        }
+        if let Some(chunk) = self.chunks.get_mut(&ChunkCoord::new(0, 0)) {
+            chunk.objects.truncate(self.interior_object_count);
+            chunk.objects.extend(furniture);
+        }
     }
 }
@@ -60,6 +60,14 @@ pub const TILES_PER_SECOND: f32 = 4.0;
 // Server: 250ms per tile = 4 tiles per second
 const VISUAL_SPEED: f32 = 4.0;
 
+/// A status effect on a player (poison, stun, slow or bleed)
+#[derive(Debug, Clone)]
+pub struct StatusEffect {
+    pub kind: String,
+    pub expires_at: f64, // Game time when it wears off
+    pub stacks: u32,
+}
+
 #[derive(Debug, Clone)]
 pub struct Player {
     pub id: String,
@@ -115,6 +123,19 @@ pub struct Player {
     // Admin status
     pub is_admin: bool,
 
+    // Guild tag shown before the name (e.g. "<IW> Name")
+    pub guild_tag: Option<String>,
+
+    // Achievement title shown before the name (e.g. "Hunter Name")
+    pub title: Option<String>,
+
+    // PvP opt-in (red name) and skull from attacking outside a PvP zone
+    pub pvp_enabled: bool,
+    pub skulled: bool,
+
+    // Active status effects from monster attacks
+    pub status_effects: Vec<StatusEffect>,
+
     // Animation
     pub animation: PlayerAnimation,
 
@@ -158,6 +179,11 @@ impl Player {
             equipped_necklace: None,
             equipped_belt: None,
             is_admin: false,
+            guild_tag: None,
+            title: None,
+            pvp_enabled: false,
+            skulled: false,
+            status_effects: Vec::new(),
             animation: PlayerAnimation::new(),
             last_damage_time: 0.0,
         }
@@ -167,6 +193,29 @@ impl Player {
         self.is_dead = true;
         self.death_time = macroquad::time::get_time();
         self.hp = 0;
+        self.status_effects.clear();
+    }
+
+    /// Status effects that haven't worn off yet
+    pub fn active_status_effects(&self) -> impl Iterator<Item = &StatusEffect> {
+        let now = macroquad::time::get_time();
+        self.status_effects.iter().filter(move |e| e.expires_at > now)
+    }
+
+    /// Sprite tint for the most severe active status effect
+    pub fn status_tint(&self) -> Option<(u8, u8, u8)> {
+        let has = |kind: &str| self.active_status_effects().any(|e| e.kind == kind);
+        if has("stun") {
+            Some((255, 240, 140))
+        } else if has("poison") {
+            Some((170, 255, 150))
+        } else if has("bleed") {
+            Some((255, 160, 160))
+        } else if has("slow") {
+            Some((160, 190, 255))
+        } else {
+            None
+        }
     }
 
     /// Get the combat level (calculated from skills)
@@ -234,6 +283,7 @@ impl Player {
     pub fn respawn(&mut self, x: f32, y: f32, hp: i32) {
         self.is_dead = false;
         self.death_time = 0.0;
+        self.status_effects.clear();
         self.hp = hp;
         self.max_hp = hp;
         self.x = x;
@@ -9,8 +9,8 @@ pub mod pathfinding;
 pub mod shop;
 pub mod skills;
 
-pub use state::{GameState, Camera, ConnectionStatus, ChatChannel, ChatMessage, ChatBubble, UiState, DamageEvent, LevelUpEvent, SkillXpEvent, DialogueChoice, ActiveDialogue, QuestObjective, ActiveQuest, QuestCompletedEvent, ContextMenu, ContextMenuTarget, GoldDropDialog, DragState, DragSource, DoubleClickState, Announcement, FrameTimings, Projectile, TransitionState, MapTransition};
-pub use entities::{Player, Direction};
+pub use state::{GameState, Camera, ConnectionStatus, ChatChannel, ChatMessage, ChatBubble, UiState, DamageEvent, LevelUpEvent, SkillXpEvent, DialogueChoice, ActiveDialogue, QuestObjective, ActiveQuest, QuestCompletedEvent, ContextMenu, ContextMenuTarget, GoldDropDialog, TradeItem, TradeWindow, TradeRequestPrompt, HouseFurniture, StorageWindow, Gravestone, DeathPenaltyNotice, GravestoneWindow, PartyMember, PartyState, PartyInvitePrompt, AchievementEntry, ChallengeEntry, FactionStanding, WorldEventEntry, UpcomingEventEntry, BossBar, BossTelegraph, DragState, DragSource, DoubleClickState, Announcement, FrameTimings, Projectile, TransitionState, MapTransition};
+pub use entities::{Player, Direction, StatusEffect};
 pub use tilemap::{Tilemap, TilemapLayer, LayerType};
 pub use npc::{Npc, NpcState};
 pub use item::{GroundItem, Inventory, InventorySlot, INVENTORY_SIZE, RecipeDefinition, RecipeIngredient, RecipeResult};
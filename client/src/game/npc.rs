@@ -67,6 +67,12 @@ pub struct Npc {
     pub death_timer: Option<f32>,
     /// NPC will die after reaching target position
     pub pending_death: bool,
+    /// Owning player if this NPC is a summoned pet
+    pub owner_id: Option<String>,
+    /// Elite rank ("elite", "champion") if this monster rolled as a stronger variant
+    pub elite_rank: Option<String>,
+    /// Affix prefixes shown before the name
+    pub elite_prefixes: Vec<String>,
 }
 
 impl Npc {
@@ -94,6 +100,9 @@ impl Npc {
             last_damage_time: 0.0,
             death_timer: None,
             pending_death: false,
+            owner_id: None,
+            elite_rank: None,
+            elite_prefixes: Vec::new(),
         }
     }
 
@@ -101,6 +110,8 @@ impl Npc {
         // Don't show level for friendly NPCs (quest givers and merchants)
         if self.is_quest_giver || self.is_merchant {
             self.display_name.clone()
+        } else if !self.elite_prefixes.is_empty() {
+            format!("{} {} Lv.{}", self.elite_prefixes.join(" "), self.display_name, self.level)
         } else {
             format!("{} Lv.{}", self.display_name, self.level)
         }
@@ -110,6 +121,11 @@ impl Npc {
         self.hostile
     }
 
+    /// Pets can't be targeted, interacted with or block movement
+    pub fn is_pet(&self) -> bool {
+        self.owner_id.is_some()
+    }
+
     pub fn is_alive(&self) -> bool {
         self.state != NpcState::Dead
     }
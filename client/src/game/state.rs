@@ -194,9 +194,8 @@ pub enum ChatChannel {
     Local,      // Nearby players only (current default)
     Global,     // Server-wide player chat
     System,     // XP gains, quest completions, shop transactions
-    // Future:
-    // Party,
-    // Guild,
+    Party,      // Party members only
+    Guild,      // Guild members only
 }
 
 pub struct ChatMessage {
@@ -374,6 +373,207 @@ pub struct ContextMenu {
 pub struct GoldDropDialog {
     pub input: String,
     pub cursor: usize,
+    /// When true the amount is offered in the open trade instead of dropped
+    pub for_trade: bool,
+}
+
+/// One stack in a trade offer
+#[derive(Debug, Clone)]
+pub struct TradeItem {
+    pub item_id: String,
+    pub quantity: i32,
+}
+
+/// Open trade window with another player
+#[derive(Debug, Clone)]
+pub struct TradeWindow {
+    pub trade_id: String,
+    pub partner_name: String,
+    pub stage: String, // "offering" or "confirming"
+    pub my_items: Vec<TradeItem>,
+    pub my_gold: i32,
+    pub my_accepted: bool,
+    pub my_confirmed: bool,
+    pub their_items: Vec<TradeItem>,
+    pub their_gold: i32,
+    pub their_accepted: bool,
+    pub their_confirmed: bool,
+}
+
+impl TradeWindow {
+    pub fn is_confirming(&self) -> bool {
+        self.stage == "confirming"
+    }
+}
+
+/// A piece of furniture placed in the house the player is standing in
+#[derive(Debug, Clone)]
+pub struct HouseFurniture {
+    pub id: i32,
+    pub item_id: String,
+    pub x: i32,
+    pub y: i32,
+    pub storage: bool,
+}
+
+/// Open storage chest in a house
+#[derive(Debug, Clone)]
+pub struct StorageWindow {
+    pub furniture_id: i32,
+    pub name: String,
+    pub slots: Vec<Option<(String, i32)>>,
+}
+
+/// Gravestone holding the items a dead player left behind
+#[derive(Debug, Clone)]
+pub struct Gravestone {
+    pub id: String,
+    pub owner_id: String,
+    pub owner_name: String,
+    pub x: f32,
+    pub y: f32,
+    /// Local time (get_time) the gravestone crumbles
+    pub expires_at: f64,
+}
+
+/// What the last death cost the local player (shown on the death overlay)
+#[derive(Debug, Clone)]
+pub struct DeathPenaltyNotice {
+    pub penalty: String,
+    pub gold_lost: i32,
+    pub gravestone_id: Option<String>,
+    pub expires_at: f64,
+}
+
+impl DeathPenaltyNotice {
+    pub fn is_hardcore(&self) -> bool {
+        self.penalty == "hardcore"
+    }
+}
+
+/// Open recovery window for the local player's gravestone
+#[derive(Debug, Clone)]
+pub struct GravestoneWindow {
+    pub id: String,
+    pub items: Vec<(String, i32)>,
+    pub expires_at: f64,
+}
+
+/// A member shown in the party frames
+#[derive(Debug, Clone)]
+pub struct PartyMember {
+    pub id: String,
+    pub name: String,
+    pub hp: i32,
+    pub max_hp: i32,
+    pub level: i32,
+    pub online: bool,
+}
+
+/// Current party roster
+#[derive(Debug, Clone)]
+pub struct PartyState {
+    pub leader_id: String,
+    pub loot_rule: String,
+    pub members: Vec<PartyMember>,
+}
+
+/// One achievement and the local player's progress towards it
+#[derive(Debug, Clone)]
+pub struct AchievementEntry {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub title: Option<String>,
+    pub progress: i64,
+    pub target: i64,
+    pub unlocked: bool,
+}
+
+/// One active daily or weekly challenge and the local player's progress
+#[derive(Debug, Clone)]
+pub struct ChallengeEntry {
+    pub name: String,
+    pub description: String,
+    pub progress: i32,
+    pub target: i32,
+    pub completed: bool,
+    pub reward_gold: i32,
+}
+
+/// A running world event as listed on a bounty board
+#[derive(Debug, Clone)]
+pub struct WorldEventEntry {
+    pub name: String,
+    pub description: String,
+    pub kind: String,
+    pub x: i32,
+    pub y: i32,
+    pub killed: u32,
+    pub total: u32,
+    pub ends_at: f64, // get_time() seconds
+    pub contribution: i32,
+    pub min_contribution: i32,
+    pub reward_gold: i32,
+}
+
+/// A scheduled world event that has not started yet
+#[derive(Debug, Clone)]
+pub struct UpcomingEventEntry {
+    pub name: String,
+    pub kind: String,
+    pub starts_at: f64, // get_time() seconds
+}
+
+/// Health bar and damage leaderboard of the boss being fought
+#[derive(Debug, Clone)]
+pub struct BossBar {
+    pub boss_id: String,
+    pub name: String,
+    pub hp: i32,
+    pub max_hp: i32,
+    pub phase: i32,
+    pub phase_name: Option<String>,
+    pub enraged: bool,
+    /// (player name, damage), highest first
+    pub leaderboard: Vec<(String, i32)>,
+}
+
+/// Tiles a boss area attack is about to hit
+#[derive(Debug, Clone)]
+pub struct BossTelegraph {
+    pub boss_id: String,
+    pub tiles: Vec<(i32, i32)>,
+    pub start_time: f64,
+    pub hits_at: f64, // get_time() seconds
+}
+
+/// Standing with one faction, shown in the reputation panel
+#[derive(Debug, Clone)]
+pub struct FactionStanding {
+    pub name: String,
+    pub standing: i32,
+    pub level: i32,
+    pub level_name: String,
+    pub level_min: i32,
+    /// None at the highest level
+    pub level_max: Option<i32>,
+}
+
+/// Incoming party invite waiting for an answer
+#[derive(Debug, Clone)]
+pub struct PartyInvitePrompt {
+    pub inviter_id: String,
+    pub inviter_name: String,
+    pub time: f64,
+}
+
+/// Incoming trade request waiting for an answer
+#[derive(Debug, Clone)]
+pub struct TradeRequestPrompt {
+    pub requester_id: String,
+    pub requester_name: String,
+    pub time: f64,
 }
 
 /// Source of a drag operation
@@ -448,6 +648,39 @@ pub struct UiState {
     pub context_menu: Option<ContextMenu>,
     // Gold drop dialog state
     pub gold_drop_dialog: Option<GoldDropDialog>,
+    // Player trading state
+    pub trade: Option<TradeWindow>,
+    pub trade_request: Option<TradeRequestPrompt>,
+    // Open house storage chest
+    pub storage: Option<StorageWindow>,
+    // Death penalty of the last death and the open gravestone
+    pub death_penalty: Option<DeathPenaltyNotice>,
+    pub gravestone: Option<GravestoneWindow>,
+    // Party state
+    pub party: Option<PartyState>,
+    pub party_invite: Option<PartyInvitePrompt>,
+    // Faction reputation
+    pub reputation: Vec<FactionStanding>,
+    pub reputation_open: bool,
+    // Achievements and selected title (achievement ID)
+    pub achievements: Vec<AchievementEntry>,
+    pub active_title: Option<String>,
+    pub achievements_open: bool,
+    pub achievements_scroll: usize, // Index of the first visible row
+    // Daily/weekly challenges (reset times are in get_time() seconds)
+    pub daily_challenges: Vec<ChallengeEntry>,
+    pub weekly_challenges: Vec<ChallengeEntry>,
+    pub daily_reset_time: f64,
+    pub weekly_reset_time: f64,
+    pub daily_streak: i32,
+    pub weekly_streak: i32,
+    pub challenges_open: bool,
+    // Bounty board (world events and bounties)
+    pub bounty_board_open: bool,
+    pub bounty_events: Vec<WorldEventEntry>,
+    pub upcoming_events: Vec<UpcomingEventEntry>,
+    // Boss encounter in progress (shown as a health bar)
+    pub boss_bar: Option<BossBar>,
     // Drag state for inventory slot rearrangement
     pub drag_state: Option<DragState>,
     // Double-click tracking for equipping items
@@ -519,6 +752,30 @@ impl Default for UiState {
             hovered_element: None,
             context_menu: None,
             gold_drop_dialog: None,
+            trade: None,
+            storage: None,
+            death_penalty: None,
+            gravestone: None,
+            trade_request: None,
+            party: None,
+            party_invite: None,
+            reputation: Vec::new(),
+            reputation_open: false,
+            achievements: Vec::new(),
+            active_title: None,
+            achievements_open: false,
+            achievements_scroll: 0,
+            daily_challenges: Vec::new(),
+            weekly_challenges: Vec::new(),
+            daily_reset_time: 0.0,
+            weekly_reset_time: 0.0,
+            daily_streak: 0,
+            weekly_streak: 0,
+            challenges_open: false,
+            bounty_board_open: false,
+            bounty_events: Vec::new(),
+            upcoming_events: Vec::new(),
+            boss_bar: None,
             drag_state: None,
             double_click_state: DoubleClickState {
                 last_click_slot: None,
@@ -574,6 +831,8 @@ pub struct GameState {
     pub ground_items: HashMap<String, GroundItem>,
     /// Items waiting to spawn (with spawn time) - delays loot appearance until after death animation
     pub pending_ground_items: Vec<(GroundItem, f64)>,
+    /// Gravestones in the current zone
+    pub gravestones: HashMap<String, Gravestone>,
 
     // Targeting
     pub selected_entity_id: Option<String>,
@@ -584,6 +843,7 @@ pub struct GameState {
     pub skill_xp_events: Vec<SkillXpEvent>,
     pub xp_globes: XpGlobesManager,
     pub projectiles: Vec<Projectile>,
+    pub boss_telegraphs: Vec<BossTelegraph>,
 
     // Chat bubbles above players
     pub chat_bubbles: Vec<ChatBubble>,
@@ -626,6 +886,8 @@ pub struct GameState {
     pub current_interior: Option<String>,
     /// Current instance ID if in an instance
     pub current_instance: Option<String>,
+    /// Furniture of the house the player is in (empty outside houses)
+    pub house_furniture: Vec<HouseFurniture>,
     /// Pending portal to enter (set when player walks onto a portal)
     pub pending_portal_id: Option<String>,
     /// Last tile position checked for portal (to avoid triggering on spawn)
@@ -651,6 +913,7 @@ impl GameState {
             players: HashMap::new(),
             npcs: HashMap::new(),
             ground_items: HashMap::new(),
+            gravestones: HashMap::new(),
             pending_ground_items: Vec::new(),
             selected_entity_id: None,
             damage_events: Vec::new(),
@@ -658,6 +921,7 @@ impl GameState {
             skill_xp_events: Vec::new(),
             xp_globes: XpGlobesManager::new(),
             projectiles: Vec::new(),
+            boss_telegraphs: Vec::new(),
             chat_bubbles: Vec::new(),
             inventory: Inventory::new(),
             item_registry: ItemRegistry::new(),
@@ -673,6 +937,7 @@ impl GameState {
             map_transition: MapTransition::default(),
             current_interior: None,
             current_instance: None,
+            house_furniture: Vec::new(),
             pending_portal_id: None,
             last_portal_check_pos: None,
             area_banner: AreaBanner::default(),
@@ -753,6 +1018,9 @@ impl GameState {
         // Clean up completed projectiles
         self.projectiles.retain(|p| !p.is_complete(current_time));
 
+        // Clean up boss telegraphs once they have landed
+        self.boss_telegraphs.retain(|t| current_time < t.hits_at);
+
         // Clean up old quest completion events (older than 4 seconds)
         self.ui_state.quest_completed_events.retain(|event| current_time - event.time < 4.0);
 
@@ -3,6 +3,7 @@ use std::collections::HashSet;
 use crate::game::{GameState, ContextMenu, ContextMenuTarget, DragState, DragSource, GoldDropDialog, PathState, pathfinding};
 use crate::render::animation::AnimationState;
 use crate::render::isometric::screen_to_world;
+use crate::render::ACHIEVEMENT_ROWS_VISIBLE;
 use crate::ui::{UiElementId, UiLayout};
 use crate::network::messages::ClientMessage;
 use crate::audio::AudioManager;
@@ -38,9 +39,9 @@ fn build_occupied_set(state: &GameState) -> HashSet<(i32, i32)> {
         }
     }
 
-    // Add all alive NPCs
+    // Add all alive NPCs (pets don't block)
     for npc in state.npcs.values() {
-        if npc.is_alive() {
+        if npc.is_alive() && !npc.is_pet() {
             occupied.insert((npc.x.round() as i32, npc.y.round() as i32));
         }
     }
@@ -48,6 +49,17 @@ fn build_occupied_set(state: &GameState) -> HashSet<(i32, i32)> {
     occupied
 }
 
+/// Build the command for a confirmed gold dialog (drop, or set trade gold)
+fn gold_dialog_command(dialog: &GoldDropDialog, player_gold: i32) -> Option<InputCommand> {
+    let amount = dialog.input.parse::<i32>().ok()?;
+    if dialog.for_trade {
+        // Zero is valid in a trade - it clears the offered gold
+        (0..=player_gold).contains(&amount).then_some(InputCommand::TradeSetGold { amount })
+    } else {
+        (amount > 0 && amount <= player_gold).then_some(InputCommand::DropGold { amount })
+    }
+}
+
 /// Input commands that can be sent to the server
 #[derive(Debug, Clone)]
 pub enum InputCommand {
@@ -77,6 +89,28 @@ pub enum InputCommand {
     ShopSell { npc_id: String, item_id: String, quantity: u32 },
     // Portal commands
     EnterPortal { portal_id: String },
+    // Trade commands
+    TradeRespond { requester_id: String, accept: bool },
+    TradeOfferItem { slot_index: u8, quantity: i32 },
+    TradeRemoveItem { offer_index: u8 },
+    TradeSetGold { amount: i32 },
+    TradeAccept,
+    TradeConfirm,
+    TradeCancel,
+    // Party commands
+    PartyRespond { inviter_id: String, accept: bool },
+    PartyLeave,
+    PartyKick { member_id: String },
+    PartyPromote { member_id: String },
+    // Achievements
+    SetTitle { achievement_id: Option<String> },
+    // House storage
+    StorageOpen { furniture_id: i32 },
+    StorageDeposit { furniture_id: i32, slot_index: u8, quantity: i32 },
+    StorageWithdraw { furniture_id: i32, storage_slot: u8 },
+    // Gravestones
+    GravestoneOpen { gravestone_id: String },
+    GravestoneRecover { gravestone_id: String },
 }
 
 /// Cardinal directions for isometric movement (no diagonals)
@@ -381,6 +415,217 @@ impl InputHandler {
             }
         }
 
+        // Handle incoming trade request prompt
+        if mouse_clicked {
+            if let Some(prompt) = state.ui_state.trade_request.clone() {
+                let accept = match &clicked_element {
+                    Some(UiElementId::TradeRequestAccept) => Some(true),
+                    Some(UiElementId::TradeRequestDecline) => Some(false),
+                    _ => None,
+                };
+                if let Some(accept) = accept {
+                    commands.push(InputCommand::TradeRespond {
+                        requester_id: prompt.requester_id,
+                        accept,
+                    });
+                    state.ui_state.trade_request = None;
+                    audio.play_sfx("enter");
+                    return commands;
+                }
+            }
+        }
+
+        // Handle party invite prompt and party frame buttons
+        if mouse_clicked {
+            if let Some(invite) = state.ui_state.party_invite.clone() {
+                let accept = match &clicked_element {
+                    Some(UiElementId::PartyInviteAccept) => Some(true),
+                    Some(UiElementId::PartyInviteDecline) => Some(false),
+                    _ => None,
+                };
+                if let Some(accept) = accept {
+                    commands.push(InputCommand::PartyRespond {
+                        inviter_id: invite.inviter_id,
+                        accept,
+                    });
+                    state.ui_state.party_invite = None;
+                    audio.play_sfx("enter");
+                    return commands;
+                }
+            }
+
+            if let Some(party) = &state.ui_state.party {
+                let member_id = |idx: &usize| party.members.get(*idx).map(|m| m.id.clone());
+                let command = match &clicked_element {
+                    Some(UiElementId::PartyLeaveButton) => Some(InputCommand::PartyLeave),
+                    Some(UiElementId::PartyKickButton(idx)) => member_id(idx).map(|member_id| InputCommand::PartyKick { member_id }),
+                    Some(UiElementId::PartyPromoteButton(idx)) => member_id(idx).map(|member_id| InputCommand::PartyPromote { member_id }),
+                    _ => None,
+                };
+                if let Some(command) = command {
+                    commands.push(command);
+                    audio.play_sfx("enter");
+                    return commands;
+                }
+            }
+        }
+
+        // Achievements panel: select a title (clicking the active one clears it) and scroll
+        if state.ui_state.achievements_open {
+            if mouse_clicked {
+                if let Some(UiElementId::AchievementTitleButton(idx)) = &clicked_element {
+                    if let Some(entry) = state.ui_state.achievements.get(*idx) {
+                        let achievement_id = if state.ui_state.active_title.as_ref() == Some(&entry.id) {
+                            None
+                        } else {
+                            Some(entry.id.clone())
+                        };
+                        commands.push(InputCommand::SetTitle { achievement_id });
+                        audio.play_sfx("enter");
+                        return commands;
+                    }
+                }
+            }
+
+            let (_wheel_x, wheel_y) = mouse_wheel();
+            if wheel_y != 0.0 {
+                let max_scroll = state.ui_state.achievements.len().saturating_sub(ACHIEVEMENT_ROWS_VISIBLE);
+                state.ui_state.achievements_scroll = if wheel_y > 0.0 {
+                    state.ui_state.achievements_scroll.saturating_sub(1)
+                } else {
+                    (state.ui_state.achievements_scroll + 1).min(max_scroll)
+                };
+            }
+        }
+
+        // Handle trade window - inventory clicks add items to the offer instead of dragging
+        if state.ui_state.trade.is_some() && state.ui_state.gold_drop_dialog.is_none() {
+            if is_key_pressed(KeyCode::Escape) {
+                commands.push(InputCommand::TradeCancel);
+                return commands;
+            }
+
+            if mouse_clicked {
+                if let Some(ref element) = clicked_element {
+                    let trade = state.ui_state.trade.as_ref().unwrap();
+                    match element {
+                        UiElementId::InventorySlot(idx) | UiElementId::QuickSlot(idx) => {
+                            if let Some(Some(slot)) = state.inventory.slots.get(*idx) {
+                                commands.push(InputCommand::TradeOfferItem {
+                                    slot_index: *idx as u8,
+                                    quantity: slot.quantity,
+                                });
+                                audio.play_sfx("item_put");
+                            }
+                            return commands;
+                        }
+                        UiElementId::TradeOfferSlot(offer_idx) => {
+                            commands.push(InputCommand::TradeRemoveItem { offer_index: *offer_idx as u8 });
+                            return commands;
+                        }
+                        UiElementId::TradeGoldButton => {
+                            state.ui_state.gold_drop_dialog = Some(GoldDropDialog {
+                                input: String::new(),
+                                cursor: 0,
+                                for_trade: true,
+                            });
+                            return commands;
+                        }
+                        UiElementId::TradeAcceptButton => {
+                            if trade.is_confirming() {
+                                if !trade.my_confirmed {
+                                    commands.push(InputCommand::TradeConfirm);
+                                }
+                            } else if !trade.my_accepted {
+                                commands.push(InputCommand::TradeAccept);
+                            }
+                            audio.play_sfx("enter");
+                            return commands;
+                        }
+                        UiElementId::TradeCancelButton => {
+                            commands.push(InputCommand::TradeCancel);
+                            audio.play_sfx("enter");
+                            return commands;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        // Handle house storage - inventory clicks deposit, storage clicks withdraw
+        if let Some(furniture_id) = state.ui_state.storage.as_ref().map(|s| s.furniture_id) {
+            // Walking away from the chest closes it
+            let in_reach = state.local_player_id.as_ref()
+                .and_then(|id| state.players.get(id))
+                .zip(state.house_furniture.iter().find(|f| f.id == furniture_id))
+                .is_some_and(|(player, f)| {
+                    (player.x.floor() as i32 - f.x).abs() <= 1 && (player.y.floor() as i32 - f.y).abs() <= 1
+                });
+            if !in_reach || is_key_pressed(KeyCode::Escape) {
+                state.ui_state.storage = None;
+                if in_reach {
+                    audio.play_sfx("enter");
+                    return commands;
+                }
+            } else if mouse_clicked && state.ui_state.trade.is_none() {
+                match &clicked_element {
+                    Some(UiElementId::InventorySlot(idx)) | Some(UiElementId::QuickSlot(idx)) => {
+                        if let Some(Some(slot)) = state.inventory.slots.get(*idx) {
+                            commands.push(InputCommand::StorageDeposit {
+                                furniture_id,
+                                slot_index: *idx as u8,
+                                quantity: slot.quantity,
+                            });
+                            audio.play_sfx("item_put");
+                        }
+                        return commands;
+                    }
+                    Some(UiElementId::StorageSlot(storage_idx)) => {
+                        commands.push(InputCommand::StorageWithdraw {
+                            furniture_id,
+                            storage_slot: *storage_idx as u8,
+                        });
+                        audio.play_sfx("item_put");
+                        return commands;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // Handle gravestone recovery window
+        if let Some(gravestone_id) = state.ui_state.gravestone.as_ref().map(|g| g.id.clone()) {
+            // Walking away from the gravestone closes it
+            let in_reach = state.local_player_id.as_ref()
+                .and_then(|id| state.players.get(id))
+                .zip(state.gravestones.get(&gravestone_id))
+                .is_some_and(|(player, grave)| {
+                    (player.x.floor() - grave.x).abs() <= 1.0 && (player.y.floor() - grave.y).abs() <= 1.0
+                });
+            if !in_reach || is_key_pressed(KeyCode::Escape) {
+                state.ui_state.gravestone = None;
+                if in_reach {
+                    audio.play_sfx("enter");
+                    return commands;
+                }
+            } else if mouse_clicked {
+                match &clicked_element {
+                    Some(UiElementId::GravestoneRecoverButton) => {
+                        commands.push(InputCommand::GravestoneRecover { gravestone_id });
+                        audio.play_sfx("item_put");
+                        return commands;
+                    }
+                    Some(UiElementId::GravestoneCloseButton) => {
+                        state.ui_state.gravestone = None;
+                        audio.play_sfx("enter");
+                        return commands;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
         // Double-click detection threshold (300ms)
         const DOUBLE_CLICK_THRESHOLD: f64 = 0.3;
 
@@ -551,6 +796,7 @@ impl InputHandler {
                                         state.ui_state.gold_drop_dialog = Some(GoldDropDialog {
                                             input: String::new(),
                                             cursor: 0,
+                                            for_trade: false,
                                         });
                                     }
                                 }
@@ -787,11 +1033,9 @@ impl InputHandler {
                         UiElementId::GoldDropConfirm => {
                             // Parse amount and validate
                             let dialog = state.ui_state.gold_drop_dialog.as_ref().unwrap();
-                            if let Ok(amount) = dialog.input.parse::<i32>() {
-                                if amount > 0 && amount <= state.inventory.gold {
-                                    commands.push(InputCommand::DropGold { amount });
-                                    state.ui_state.gold_drop_dialog = None;
-                                }
+                            if let Some(cmd) = gold_dialog_command(dialog, state.inventory.gold) {
+                                commands.push(cmd);
+                                state.ui_state.gold_drop_dialog = None;
                             }
                             return commands;
                         }
@@ -813,11 +1057,9 @@ impl InputHandler {
             if is_key_pressed(KeyCode::Enter) {
                 // Confirm with Enter key
                 let dialog = state.ui_state.gold_drop_dialog.as_ref().unwrap();
-                if let Ok(amount) = dialog.input.parse::<i32>() {
-                    if amount > 0 && amount <= state.inventory.gold {
-                        commands.push(InputCommand::DropGold { amount });
-                        state.ui_state.gold_drop_dialog = None;
-                    }
+                if let Some(cmd) = gold_dialog_command(dialog, state.inventory.gold) {
+                    commands.push(cmd);
+                    state.ui_state.gold_drop_dialog = None;
                 }
                 return commands;
             }
@@ -1993,8 +2235,8 @@ impl InputHandler {
 
             // Check NPCs - must be on the exact clicked tile
             for (id, npc) in &state.npcs {
-                // Only allow interacting with alive NPCs
-                if !npc.is_alive() {
+                // Only allow interacting with alive NPCs (pets just follow their owner)
+                if !npc.is_alive() || npc.is_pet() {
                     continue;
                 }
 
@@ -2109,12 +2351,14 @@ impl InputHandler {
         if is_key_pressed(KeyCode::Escape) {
             // Check if any panel is open and close it
             if state.ui_state.inventory_open || state.ui_state.character_panel_open
-                || state.ui_state.social_open || state.ui_state.skills_open {
+                || state.ui_state.social_open || state.ui_state.skills_open
+                || state.ui_state.bounty_board_open {
                 audio.play_sfx("enter");
                 state.ui_state.inventory_open = false;
                 state.ui_state.character_panel_open = false;
                                 state.ui_state.social_open = false;
                 state.ui_state.skills_open = false;
+                state.ui_state.bounty_board_open = false;
             } else if state.selected_entity_id.is_some() {
                 commands.push(InputCommand::ClearTarget);
             } else {
@@ -2293,8 +2537,8 @@ impl InputHandler {
                     let mut nearest_npc: Option<(String, f32)> = None;
 
                     for (id, npc) in &state.npcs {
-                        // Only interact with alive NPCs
-                        if !npc.is_alive() {
+                        // Only interact with alive NPCs (not pets)
+                        if !npc.is_alive() || npc.is_pet() {
                             continue;
                         }
 
@@ -2309,9 +2553,23 @@ impl InputHandler {
                         }
                     }
 
+                    // Storage furniture right next to the player (house chests)
+                    let tile_x = player.x.floor() as i32;
+                    let tile_y = player.y.floor() as i32;
+                    let nearby_storage = state.house_furniture.iter()
+                        .find(|f| f.storage && (f.x - tile_x).abs() <= 1 && (f.y - tile_y).abs() <= 1);
+                    // The local player's own gravestone right next to them
+                    let nearby_gravestone = state.gravestones.values()
+                        .find(|g| &g.owner_id == local_id
+                            && (g.x as i32 - tile_x).abs() <= 1 && (g.y as i32 - tile_y).abs() <= 1);
+
                     if let Some((npc_id, _)) = nearest_npc {
                         log::info!("Interacting with NPC: {}", npc_id);
                         commands.push(InputCommand::Interact { npc_id });
+                    } else if let Some(grave) = nearby_gravestone {
+                        commands.push(InputCommand::GravestoneOpen { gravestone_id: grave.id.clone() });
+                    } else if let Some(furniture) = nearby_storage {
+                        commands.push(InputCommand::StorageOpen { furniture_id: furniture.id });
                     } else if self.touch_controls.interact_pressed() {
                         // Touch interact fallback: pickup item if no NPC nearby
                         const PICKUP_RANGE: f32 = 2.0;
@@ -2339,6 +2597,21 @@ impl InputHandler {
             state.ui_state.quest_log_open = !state.ui_state.quest_log_open;
         }
 
+        // Toggle reputation panel (R key)
+        if is_key_pressed(KeyCode::R) {
+            state.ui_state.reputation_open = !state.ui_state.reputation_open;
+        }
+
+        // Toggle achievements panel (Y key)
+        if is_key_pressed(KeyCode::Y) {
+            state.ui_state.achievements_open = !state.ui_state.achievements_open;
+        }
+
+        // Toggle challenges panel (J key)
+        if is_key_pressed(KeyCode::J) {
+            state.ui_state.challenges_open = !state.ui_state.challenges_open;
+        }
+
         commands
     }
 
@@ -1,4 +1,4 @@
-use crate::game::{GameState, ConnectionStatus, Player, Direction, ChatChannel, ChatMessage, ChatBubble, DamageEvent, LevelUpEvent, SkillXpEvent, GroundItem, InventorySlot, ActiveDialogue, DialogueChoice, ActiveQuest, QuestObjective, QuestCompletedEvent, RecipeDefinition, RecipeIngredient, RecipeResult, ItemDefinition, EquipmentStats, MapObject, ShopData, ShopStockItem, SkillType, Wall, WallEdge, Portal, TransitionState};
+use crate::game::{GameState, ConnectionStatus, Player, Direction, StatusEffect, ChatChannel, ChatMessage, ChatBubble, DamageEvent, LevelUpEvent, SkillXpEvent, GroundItem, InventorySlot, ActiveDialogue, DialogueChoice, ActiveQuest, QuestObjective, QuestCompletedEvent, RecipeDefinition, RecipeIngredient, RecipeResult, ItemDefinition, EquipmentStats, MapObject, ShopData, ShopStockItem, TradeItem, TradeWindow, TradeRequestPrompt, HouseFurniture, StorageWindow, Gravestone, DeathPenaltyNotice, GravestoneWindow, PartyMember, PartyState, PartyInvitePrompt, AchievementEntry, ChallengeEntry, FactionStanding, WorldEventEntry, UpcomingEventEntry, BossBar, BossTelegraph, SkillType, Wall, WallEdge, Portal, TransitionState};
 use crate::game::npc::{Npc, NpcState};
 use crate::render::OVERWORLD_NAME;
 use super::protocol::{extract_string, extract_f32, extract_i32, extract_u32, extract_u64, extract_array, extract_u8, extract_bool};
@@ -39,6 +39,8 @@ pub fn handle_room_data(msg_type: &str, data: Option<&rmpv::Value>, state: &mut
                 let equipped_belt = extract_string(value, "equipped_belt").filter(|s| !s.is_empty());
                 // Admin status
                 let is_admin = extract_bool(value, "is_admin").unwrap_or(false);
+                let guild_tag = extract_string(value, "guild_tag").filter(|s| !s.is_empty());
+                let title = extract_string(value, "title").filter(|s| !s.is_empty());
 
                 log::info!("Player joined: {} at ({}, {}) [{}/{}]", name, x, y, gender, skin);
                 let mut player = Player::new(id.clone(), name, x, y, gender, skin);
@@ -54,6 +56,8 @@ pub fn handle_room_data(msg_type: &str, data: Option<&rmpv::Value>, state: &mut
                 player.equipped_necklace = equipped_necklace;
                 player.equipped_belt = equipped_belt;
                 player.is_admin = is_admin;
+                player.guild_tag = guild_tag;
+                player.title = title;
                 state.players.insert(id, player);
             }
         }
@@ -106,6 +110,10 @@ pub fn handle_room_data(msg_type: &str, data: Option<&rmpv::Value>, state: &mut
                         let equipped_necklace = extract_string(player_value, "equipped_necklace").filter(|s| !s.is_empty());
                         let equipped_belt = extract_string(player_value, "equipped_belt").filter(|s| !s.is_empty());
                         let is_admin = extract_bool(player_value, "is_admin").unwrap_or(false);
+                        let guild_tag = extract_string(player_value, "guild_tag").filter(|s| !s.is_empty());
+                        let title = extract_string(player_value, "title").filter(|s| !s.is_empty());
+                        let pvp_enabled = extract_bool(player_value, "pvp_enabled").unwrap_or(false);
+                        let skulled = extract_bool(player_value, "skulled").unwrap_or(false);
 
                         let is_local_player = state.local_player_id.as_ref() == Some(&id);
 
@@ -160,6 +168,10 @@ pub fn handle_room_data(msg_type: &str, data: Option<&rmpv::Value>, state: &mut
                             player.equipped_belt = equipped_belt.clone();
                             // Update admin status
                             player.is_admin = is_admin;
+                            player.guild_tag = guild_tag;
+                            player.title = title;
+                            player.pvp_enabled = pvp_enabled;
+                            player.skulled = skulled;
                         } else if state.local_player_id.as_ref() != Some(&id) && !id.is_empty() {
                             // Player not in our map - create them from stateSync data
                             // This handles players re-appearing after map transitions
@@ -185,6 +197,10 @@ pub fn handle_room_data(msg_type: &str, data: Option<&rmpv::Value>, state: &mut
                                 new_player.equipped_necklace = equipped_necklace;
                                 new_player.equipped_belt = equipped_belt;
                                 new_player.is_admin = is_admin;
+                                new_player.guild_tag = guild_tag;
+                                new_player.title = title;
+                                new_player.pvp_enabled = pvp_enabled;
+                                new_player.skulled = skulled;
                                 if let Some(hp_val) = hp {
                                     new_player.hp = hp_val;
                                 }
@@ -266,6 +282,11 @@ pub fn handle_room_data(msg_type: &str, data: Option<&rmpv::Value>, state: &mut
                         let is_quest_giver = extract_bool(npc_value, "is_quest_giver").unwrap_or(false);
                         let is_merchant = extract_bool(npc_value, "is_merchant").unwrap_or(false);
                         let move_speed = extract_f32(npc_value, "move_speed").unwrap_or(2.0);
+                        let owner_id = extract_string(npc_value, "owner_id");
+                        let elite_rank = extract_string(npc_value, "elite_rank");
+                        let elite_prefixes: Vec<String> = extract_array(npc_value, "elite_prefixes")
+                            .map(|prefixes| prefixes.iter().filter_map(|p| p.as_str().map(String::from)).collect())
+                            .unwrap_or_default();
 
                         if let Some(npc) = state.npcs.get_mut(&id) {
                             // Update existing NPC - interpolate toward new grid position
@@ -281,6 +302,8 @@ pub fn handle_room_data(msg_type: &str, data: Option<&rmpv::Value>, state: &mut
                             }
                             npc.hp = hp;
                             npc.max_hp = max_hp;
+                            // Pets level up while alive
+                            npc.level = level;
                             // Handle state transitions
                             let new_state = NpcState::from_u8(npc_state);
                             if new_state != NpcState::Dead {
@@ -299,6 +322,10 @@ pub fn handle_room_data(msg_type: &str, data: Option<&rmpv::Value>, state: &mut
                             npc.is_quest_giver = is_quest_giver;
                             npc.is_merchant = is_merchant;
                             npc.move_speed = move_speed;
+                            npc.owner_id = owner_id;
+                            // Respawns roll a new variant
+                            npc.elite_rank = elite_rank;
+                            npc.elite_prefixes = elite_prefixes;
                         } else {
                             // New NPC - add to state
                             let mut npc = Npc::new(id.clone(), entity_type, x, y);
@@ -312,6 +339,9 @@ pub fn handle_room_data(msg_type: &str, data: Option<&rmpv::Value>, state: &mut
                             npc.is_quest_giver = is_quest_giver;
                             npc.is_merchant = is_merchant;
                             npc.move_speed = move_speed;
+                            npc.owner_id = owner_id;
+                            npc.elite_rank = elite_rank;
+                            npc.elite_prefixes = elite_prefixes;
                             state.npcs.insert(id, npc);
                         }
                     }
@@ -337,13 +367,18 @@ pub fn handle_room_data(msg_type: &str, data: Option<&rmpv::Value>, state: &mut
                 let sender_name = extract_string(value, "senderName").unwrap_or_default();
                 let text = extract_string(value, "text").unwrap_or_default();
                 let timestamp = extract_u64(value, "timestamp").unwrap_or(0) as f64;
+                let channel = match extract_string(value, "channel").as_deref() {
+                    Some("party") => ChatChannel::Party,
+                    Some("guild") => ChatChannel::Guild,
+                    _ => ChatChannel::Local,
+                };
 
                 // Add to chat log
                 state.ui_state.chat_messages.push(ChatMessage {
                     sender_name: sender_name.clone(),
                     text: text.clone(),
                     timestamp,
-                    channel: ChatChannel::Local,
+                    channel,
                 });
 
                 if state.ui_state.chat_messages.len() > 100 {
@@ -446,7 +481,7 @@ pub fn handle_room_data(msg_type: &str, data: Option<&rmpv::Value>, state: &mut
                         let source_pos = if let Some(player) = state.players.get(source_id) {
                             Some((player.x.round(), player.y.round()))
                         } else {
-                            None
+                            state.npcs.get(source_id).map(|npc| (npc.x.round(), npc.y.round()))
                         };
 
                         if let Some((src_x, src_y)) = source_pos {
@@ -461,7 +496,7 @@ pub fn handle_room_data(msg_type: &str, data: Option<&rmpv::Value>, state: &mut
                                 end_x,
                                 end_y,
                                 start_time: current_time,
-                                duration: 0.15, // Fast arrow travel
+                                duration: if projectile_type == "arrow" { 0.15 } else { 0.3 }, // Arrows fly faster than spells
                             });
                         }
                     }
@@ -493,6 +528,18 @@ pub fn handle_room_data(msg_type: &str, data: Option<&rmpv::Value>, state: &mut
             }
         }
 
+        "npcDespawned" => {
+            if let Some(value) = data {
+                let npc_id = extract_string(value, "id").unwrap_or_default();
+                log::debug!("NPC despawned: {}", npc_id);
+                state.npcs.remove(&npc_id);
+
+                if state.selected_entity_id.as_ref() == Some(&npc_id) {
+                    state.selected_entity_id = None;
+                }
+            }
+        }
+
         "npcRespawned" => {
             if let Some(value) = data {
                 let npc_id = extract_string(value, "id").unwrap_or_default();
@@ -543,6 +590,9 @@ pub fn handle_room_data(msg_type: &str, data: Option<&rmpv::Value>, state: &mut
                 if let Some(player) = state.players.get_mut(&player_id) {
                     player.respawn(x, y, hp);
                 }
+                if state.local_player_id.as_ref() == Some(&player_id) {
+                    state.ui_state.death_penalty = None;
+                }
             }
         }
 
@@ -1302,6 +1352,273 @@ pub fn handle_room_data(msg_type: &str, data: Option<&rmpv::Value>, state: &mut
             }
         }
 
+        // ========== Trade System Messages ==========
+
+        "tradeRequested" => {
+            if let Some(value) = data {
+                let requester_id = extract_string(value, "requesterId").unwrap_or_default();
+                let requester_name = extract_string(value, "requesterName").unwrap_or_default();
+                state.ui_state.chat_messages.push(ChatMessage::system(
+                    format!("{} wants to trade with you.", requester_name)
+                ));
+                state.ui_state.trade_request = Some(TradeRequestPrompt {
+                    requester_id,
+                    requester_name,
+                    time: macroquad::time::get_time(),
+                });
+            }
+        }
+
+        "tradeOpened" => {
+            if let Some(value) = data {
+                let trade_id = extract_string(value, "tradeId").unwrap_or_default();
+                let partner_name = extract_string(value, "partnerName").unwrap_or_default();
+                log::info!("Trade {} opened with {}", trade_id, partner_name);
+
+                // Trading needs the inventory visible to pick items
+                state.ui_state.inventory_open = true;
+                state.ui_state.trade_request = None;
+                state.ui_state.trade = Some(TradeWindow {
+                    trade_id,
+                    partner_name,
+                    stage: "offering".to_string(),
+                    my_items: Vec::new(),
+                    my_gold: 0,
+                    my_accepted: false,
+                    my_confirmed: false,
+                    their_items: Vec::new(),
+                    their_gold: 0,
+                    their_accepted: false,
+                    their_confirmed: false,
+                });
+            }
+        }
+
+        "tradeUpdate" => {
+            if let Some(value) = data {
+                let trade_id = extract_string(value, "tradeId").unwrap_or_default();
+                let parse_items = |key: &str| -> Vec<TradeItem> {
+                    extract_array(value, key)
+                        .map(|arr| arr.iter().map(|item| TradeItem {
+                            item_id: extract_string(item, "itemId").unwrap_or_default(),
+                            quantity: extract_i32(item, "quantity").unwrap_or(0),
+                        }).collect())
+                        .unwrap_or_default()
+                };
+
+                if let Some(trade) = state.ui_state.trade.as_mut() {
+                    if trade.trade_id == trade_id {
+                        trade.stage = extract_string(value, "stage").unwrap_or_else(|| "offering".to_string());
+                        trade.my_items = parse_items("myItems");
+                        trade.my_gold = extract_i32(value, "myGold").unwrap_or(0);
+                        trade.my_accepted = extract_bool(value, "myAccepted").unwrap_or(false);
+                        trade.my_confirmed = extract_bool(value, "myConfirmed").unwrap_or(false);
+                        trade.their_items = parse_items("theirItems");
+                        trade.their_gold = extract_i32(value, "theirGold").unwrap_or(0);
+                        trade.their_accepted = extract_bool(value, "theirAccepted").unwrap_or(false);
+                        trade.their_confirmed = extract_bool(value, "theirConfirmed").unwrap_or(false);
+                    }
+                }
+            }
+        }
+
+        "tradeClosed" => {
+            if let Some(value) = data {
+                let completed = extract_bool(value, "completed").unwrap_or(false);
+                let reason = extract_string(value, "reason");
+
+                let text = if completed {
+                    "Trade completed.".to_string()
+                } else {
+                    reason.unwrap_or_else(|| "Trade cancelled.".to_string())
+                };
+                state.ui_state.chat_messages.push(ChatMessage::system(text));
+                state.ui_state.trade = None;
+                // Close a gold dialog that was opened for the trade
+                if state.ui_state.gold_drop_dialog.as_ref().is_some_and(|d| d.for_trade) {
+                    state.ui_state.gold_drop_dialog = None;
+                }
+            }
+        }
+
+        // ========== Party System Messages ==========
+
+        "partyInvite" => {
+            if let Some(value) = data {
+                let inviter_id = extract_string(value, "inviterId").unwrap_or_default();
+                let inviter_name = extract_string(value, "inviterName").unwrap_or_default();
+                state.ui_state.chat_messages.push(ChatMessage::system(
+                    format!("{} invited you to a party.", inviter_name)
+                ));
+                state.ui_state.party_invite = Some(PartyInvitePrompt {
+                    inviter_id,
+                    inviter_name,
+                    time: macroquad::time::get_time(),
+                });
+            }
+        }
+
+        "partyUpdate" => {
+            if let Some(value) = data {
+                let leader_id = extract_string(value, "leaderId").unwrap_or_default();
+                let loot_rule = extract_string(value, "lootRule").unwrap_or_else(|| "shared".to_string());
+                let members = extract_array(value, "members")
+                    .map(|arr| arr.iter().map(|m| PartyMember {
+                        id: extract_string(m, "id").unwrap_or_default(),
+                        name: extract_string(m, "name").unwrap_or_default(),
+                        hp: extract_i32(m, "hp").unwrap_or(0),
+                        max_hp: extract_i32(m, "maxHp").unwrap_or(0),
+                        level: extract_i32(m, "level").unwrap_or(0),
+                        online: extract_bool(m, "online").unwrap_or(false),
+                    }).collect())
+                    .unwrap_or_default();
+
+                state.ui_state.party_invite = None;
+                state.ui_state.party = Some(PartyState {
+                    leader_id,
+                    loot_rule,
+                    members,
+                });
+            }
+        }
+
+        "partyLeft" => {
+            state.ui_state.party = None;
+        }
+
+        "achievementsUpdate" => {
+            if let Some(value) = data {
+                state.ui_state.achievements = extract_array(value, "achievements")
+                    .map(|arr| arr.iter().map(|a| AchievementEntry {
+                        id: extract_string(a, "id").unwrap_or_default(),
+                        name: extract_string(a, "name").unwrap_or_default(),
+                        description: extract_string(a, "description").unwrap_or_default(),
+                        title: extract_string(a, "title"),
+                        progress: extract_u64(a, "progress").unwrap_or(0) as i64,
+                        target: extract_u64(a, "target").unwrap_or(1) as i64,
+                        unlocked: extract_u64(a, "unlockedAt").is_some(),
+                    }).collect())
+                    .unwrap_or_default();
+                state.ui_state.active_title = extract_string(value, "activeTitle");
+            }
+        }
+
+        "challengesUpdate" => {
+            if let Some(value) = data {
+                let parse_challenges = |key: &str| -> Vec<ChallengeEntry> {
+                    extract_array(value, key)
+                        .map(|arr| arr.iter().map(|c| ChallengeEntry {
+                            name: extract_string(c, "name").unwrap_or_default(),
+                            description: extract_string(c, "description").unwrap_or_default(),
+                            progress: extract_i32(c, "progress").unwrap_or(0),
+                            target: extract_i32(c, "target").unwrap_or(1),
+                            completed: extract_bool(c, "completed").unwrap_or(false),
+                            reward_gold: extract_i32(c, "rewardGold").unwrap_or(0),
+                        }).collect())
+                        .unwrap_or_default()
+                };
+                let now = macroquad::time::get_time();
+                state.ui_state.daily_challenges = parse_challenges("daily");
+                state.ui_state.weekly_challenges = parse_challenges("weekly");
+                state.ui_state.daily_reset_time = now + extract_u64(value, "dailyResetsIn").unwrap_or(0) as f64 / 1000.0;
+                state.ui_state.weekly_reset_time = now + extract_u64(value, "weeklyResetsIn").unwrap_or(0) as f64 / 1000.0;
+                state.ui_state.daily_streak = extract_i32(value, "dailyStreak").unwrap_or(0);
+                state.ui_state.weekly_streak = extract_i32(value, "weeklyStreak").unwrap_or(0);
+            }
+        }
+
+        "bountyBoard" => {
+            if let Some(value) = data {
+                let now = macroquad::time::get_time();
+                state.ui_state.bounty_events = extract_array(value, "events")
+                    .map(|arr| arr.iter().map(|e| WorldEventEntry {
+                        name: extract_string(e, "name").unwrap_or_default(),
+                        description: extract_string(e, "description").unwrap_or_default(),
+                        kind: extract_string(e, "kind").unwrap_or_default(),
+                        x: extract_i32(e, "x").unwrap_or(0),
+                        y: extract_i32(e, "y").unwrap_or(0),
+                        killed: extract_u32(e, "killed").unwrap_or(0),
+                        total: extract_u32(e, "total").unwrap_or(0),
+                        ends_at: now + extract_u64(e, "endsIn").unwrap_or(0) as f64 / 1000.0,
+                        contribution: extract_i32(e, "contribution").unwrap_or(0),
+                        min_contribution: extract_i32(e, "minContribution").unwrap_or(0),
+                        reward_gold: extract_i32(e, "rewardGold").unwrap_or(0),
+                    }).collect())
+                    .unwrap_or_default();
+                state.ui_state.upcoming_events = extract_array(value, "upcoming")
+                    .map(|arr| arr.iter().map(|u| UpcomingEventEntry {
+                        name: extract_string(u, "name").unwrap_or_default(),
+                        kind: extract_string(u, "kind").unwrap_or_default(),
+                        starts_at: now + extract_u64(u, "startsIn").unwrap_or(0) as f64 / 1000.0,
+                    }).collect())
+                    .unwrap_or_default();
+                state.ui_state.bounty_board_open = true;
+            }
+        }
+
+        "bossUpdate" => {
+            if let Some(value) = data {
+                state.ui_state.boss_bar = Some(BossBar {
+                    boss_id: extract_string(value, "bossId").unwrap_or_default(),
+                    name: extract_string(value, "name").unwrap_or_default(),
+                    hp: extract_i32(value, "hp").unwrap_or(0),
+                    max_hp: extract_i32(value, "maxHp").unwrap_or(1),
+                    phase: extract_i32(value, "phase").unwrap_or(1),
+                    phase_name: extract_string(value, "phaseName"),
+                    enraged: extract_bool(value, "enraged").unwrap_or(false),
+                    leaderboard: extract_array(value, "leaderboard")
+                        .map(|arr| arr.iter().map(|entry| (
+                            extract_string(entry, "name").unwrap_or_default(),
+                            extract_i32(entry, "damage").unwrap_or(0),
+                        )).collect())
+                        .unwrap_or_default(),
+                });
+            }
+        }
+
+        "bossTelegraph" => {
+            if let Some(value) = data {
+                let now = macroquad::time::get_time();
+                let tiles = extract_array(value, "tiles")
+                    .map(|arr| arr.iter().filter_map(|tile| {
+                        let pos = tile.as_array()?;
+                        Some((pos.first()?.as_i64()? as i32, pos.get(1)?.as_i64()? as i32))
+                    }).collect())
+                    .unwrap_or_default();
+                state.boss_telegraphs.push(BossTelegraph {
+                    boss_id: extract_string(value, "bossId").unwrap_or_default(),
+                    tiles,
+                    start_time: now,
+                    hits_at: now + extract_u64(value, "delayMs").unwrap_or(0) as f64 / 1000.0,
+                });
+            }
+        }
+
+        "bossEnded" => {
+            if let Some(value) = data {
+                let boss_id = extract_string(value, "bossId").unwrap_or_default();
+                if state.ui_state.boss_bar.as_ref().is_some_and(|bar| bar.boss_id == boss_id) {
+                    state.ui_state.boss_bar = None;
+                }
+                state.boss_telegraphs.retain(|t| t.boss_id != boss_id);
+            }
+        }
+
+        "reputationUpdate" => {
+            if let Some(value) = data {
+                state.ui_state.reputation = extract_array(value, "factions")
+                    .map(|arr| arr.iter().map(|f| FactionStanding {
+                        name: extract_string(f, "name").unwrap_or_default(),
+                        standing: extract_i32(f, "standing").unwrap_or(0),
+                        level: extract_i32(f, "level").unwrap_or(0),
+                        level_name: extract_string(f, "levelName").unwrap_or_default(),
+                        level_min: extract_i32(f, "levelMin").unwrap_or(0),
+                        level_max: extract_i32(f, "levelMax"),
+                    }).collect())
+                    .unwrap_or_default();
+            }
+        }
+
         "mapTransition" => {
             if let Some(value) = data {
                 let map_type = extract_string(value, "mapType").unwrap_or_default();
@@ -1320,6 +1637,10 @@ pub fn handle_room_data(msg_type: &str, data: Option<&rmpv::Value>, state: &mut
                     state.chunk_manager.clear_interior();
                     state.current_interior = None;
                     state.current_instance = None;
+                    state.house_furniture.clear();
+                    state.ui_state.storage = None;
+                    state.gravestones.clear();
+                    state.ui_state.gravestone = None;
 
                     // Clear interior NPCs and ground items (will be repopulated by stateSync)
                     state.npcs.clear();
@@ -1445,6 +1766,10 @@ pub fn handle_room_data(msg_type: &str, data: Option<&rmpv::Value>, state: &mut
                 state.chunk_manager.load_interior(width, height, layers, &collision, portals, objects, walls);
                 state.current_interior = Some(map_id.clone());
                 state.current_instance = Some(instance_id);
+                state.house_furniture.clear();
+                state.ui_state.storage = None;
+                state.gravestones.clear();
+                state.ui_state.gravestone = None;
 
                 // Reset portal check position to prevent immediate re-trigger
                 state.last_portal_check_pos = None;
@@ -1473,6 +1798,152 @@ pub fn handle_room_data(msg_type: &str, data: Option<&rmpv::Value>, state: &mut
             }
         }
 
+        "houseFurniture" => {
+            if let Some(value) = data {
+                let mut furniture = Vec::new();
+                let mut objects = Vec::new();
+                if let Some(arr) = extract_array(value, "furniture") {
+                    for f in arr {
+                        let x = extract_i32(f, "x").unwrap_or(0);
+                        let y = extract_i32(f, "y").unwrap_or(0);
+                        objects.push(MapObject {
+                            gid: extract_u32(f, "gid").unwrap_or(0),
+                            tile_x: x,
+                            tile_y: y,
+                            width: extract_u32(f, "width").unwrap_or(32),
+                            height: extract_u32(f, "height").unwrap_or(32),
+                        });
+                        furniture.push(HouseFurniture {
+                            id: extract_i32(f, "id").unwrap_or(0),
+                            item_id: extract_string(f, "itemId").unwrap_or_default(),
+                            x,
+                            y,
+                            storage: extract_bool(f, "storage").unwrap_or(false),
+                        });
+                    }
+                }
+
+                // A chest that was picked up closes its storage window
+                if let Some(storage) = &state.ui_state.storage {
+                    if !furniture.iter().any(|f| f.id == storage.furniture_id) {
+                        state.ui_state.storage = None;
+                    }
+                }
+                state.chunk_manager.set_interior_furniture(objects);
+                state.house_furniture = furniture;
+            }
+        }
+
+        "storageContents" => {
+            if let Some(value) = data {
+                let furniture_id = extract_i32(value, "furnitureId").unwrap_or(0);
+                let name = extract_string(value, "name").unwrap_or_default();
+                let capacity = extract_i32(value, "capacity").unwrap_or(0).max(0) as usize;
+
+                let mut slots = vec![None; capacity];
+                if let Some(arr) = extract_array(value, "slots") {
+                    for slot in arr {
+                        let idx = extract_u8(slot, "slot").unwrap_or(0) as usize;
+                        let item_id = extract_string(slot, "itemId").unwrap_or_default();
+                        let quantity = extract_i32(slot, "quantity").unwrap_or(0);
+                        if idx < capacity && !item_id.is_empty() && quantity > 0 {
+                            slots[idx] = Some((item_id, quantity));
+                        }
+                    }
+                }
+
+                // Depositing needs the inventory visible to pick items
+                state.ui_state.inventory_open = true;
+                state.ui_state.storage = Some(StorageWindow { furniture_id, name, slots });
+            }
+        }
+
+        "deathPenalty" => {
+            if let Some(value) = data {
+                let penalty = extract_string(value, "penalty").unwrap_or_default();
+                let gold_lost = extract_i32(value, "goldLost").unwrap_or(0);
+                let gravestone_id = extract_string(value, "gravestoneId");
+                let expires_in = extract_u64(value, "expiresIn").unwrap_or(0);
+
+                let text = match penalty.as_str() {
+                    "gravestone" if gravestone_id.is_some() => format!(
+                        "Your items were left in a gravestone. Recover them within {} minutes.",
+                        expires_in / 60
+                    ),
+                    "gold_loss" if gold_lost > 0 => format!("You lost {} gold.", gold_lost),
+                    "hardcore" => "Your character has perished and cannot be revived.".to_string(),
+                    _ => "You lost nothing.".to_string(),
+                };
+                state.ui_state.chat_messages.push(ChatMessage::system(text));
+                state.ui_state.death_penalty = Some(DeathPenaltyNotice {
+                    penalty,
+                    gold_lost,
+                    gravestone_id,
+                    expires_at: macroquad::time::get_time() + expires_in as f64,
+                });
+            }
+        }
+
+        "gravestoneSpawned" => {
+            if let Some(value) = data {
+                let id = extract_string(value, "id").unwrap_or_default();
+                let expires_in = extract_u64(value, "expiresIn").unwrap_or(0);
+                state.gravestones.insert(id.clone(), Gravestone {
+                    id,
+                    owner_id: extract_string(value, "ownerId").unwrap_or_default(),
+                    owner_name: extract_string(value, "ownerName").unwrap_or_default(),
+                    x: extract_i32(value, "x").unwrap_or(0) as f32,
+                    y: extract_i32(value, "y").unwrap_or(0) as f32,
+                    expires_at: macroquad::time::get_time() + expires_in as f64,
+                });
+            }
+        }
+
+        "gravestoneRemoved" => {
+            if let Some(value) = data {
+                let id = extract_string(value, "id").unwrap_or_default();
+                state.gravestones.remove(&id);
+                if state.ui_state.gravestone.as_ref().is_some_and(|g| g.id == id) {
+                    state.ui_state.gravestone = None;
+                }
+            }
+        }
+
+        "gravestoneContents" => {
+            if let Some(value) = data {
+                let id = extract_string(value, "id").unwrap_or_default();
+                let expires_in = extract_u64(value, "expiresIn").unwrap_or(0);
+                let items = extract_array(value, "items")
+                    .map(|arr| arr.iter().map(|item| (
+                        extract_string(item, "itemId").unwrap_or_default(),
+                        extract_i32(item, "quantity").unwrap_or(0),
+                    )).collect())
+                    .unwrap_or_default();
+                state.ui_state.gravestone = Some(GravestoneWindow {
+                    id,
+                    items,
+                    expires_at: macroquad::time::get_time() + expires_in as f64,
+                });
+            }
+        }
+
+        "statusEffects" => {
+            if let Some(value) = data {
+                let player_id = extract_string(value, "playerId").unwrap_or_default();
+                let now = macroquad::time::get_time();
+                let effects = extract_array(value, "effects")
+                    .map(|arr| arr.iter().map(|effect| StatusEffect {
+                        kind: extract_string(effect, "kind").unwrap_or_default(),
+                        expires_at: now + extract_u64(effect, "remainingMs").unwrap_or(0) as f64 / 1000.0,
+                        stacks: extract_u32(effect, "stacks").unwrap_or(1),
+                    }).collect())
+                    .unwrap_or_default();
+                if let Some(player) = state.players.get_mut(&player_id) {
+                    player.status_effects = effects;
+                }
+            }
+        }
+
         _ => {
             log::debug!("Unhandled message type: {}", msg_type);
         }
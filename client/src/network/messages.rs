@@ -78,6 +78,57 @@ pub enum ClientMessage {
 
     #[serde(rename = "enterPortal")]
     EnterPortal { portal_id: String },
+
+    #[serde(rename = "tradeRespond")]
+    TradeRespond { requester_id: String, accept: bool },
+
+    #[serde(rename = "tradeOfferItem")]
+    TradeOfferItem { slot_index: u8, quantity: i32 },
+
+    #[serde(rename = "tradeRemoveItem")]
+    TradeRemoveItem { offer_index: u8 },
+
+    #[serde(rename = "tradeSetGold")]
+    TradeSetGold { amount: i32 },
+
+    #[serde(rename = "tradeAccept")]
+    TradeAccept,
+
+    #[serde(rename = "tradeConfirm")]
+    TradeConfirm,
+
+    #[serde(rename = "tradeCancel")]
+    TradeCancel,
+
+    #[serde(rename = "partyRespond")]
+    PartyRespond { inviter_id: String, accept: bool },
+
+    #[serde(rename = "partyLeave")]
+    PartyLeave,
+
+    #[serde(rename = "partyKick")]
+    PartyKick { member_id: String },
+
+    #[serde(rename = "partyPromote")]
+    PartyPromote { member_id: String },
+
+    #[serde(rename = "setTitle")]
+    SetTitle { achievement_id: Option<String> },
+
+    #[serde(rename = "storageOpen")]
+    StorageOpen { furniture_id: i32 },
+
+    #[serde(rename = "storageDeposit")]
+    StorageDeposit { furniture_id: i32, slot_index: u8, quantity: i32 },
+
+    #[serde(rename = "storageWithdraw")]
+    StorageWithdraw { furniture_id: i32, storage_slot: u8 },
+
+    #[serde(rename = "gravestoneOpen")]
+    GravestoneOpen { gravestone_id: String },
+
+    #[serde(rename = "gravestoneRecover")]
+    GravestoneRecover { gravestone_id: String },
 }
 
 impl ClientMessage {
@@ -202,6 +253,70 @@ impl ClientMessage {
                 data.insert("portalId".into(), Value::String(portal_id.clone().into()));
                 "enterPortal"
             }
+            ClientMessage::TradeRespond { requester_id, accept } => {
+                data.insert("requesterId".into(), Value::String(requester_id.clone().into()));
+                data.insert("accept".into(), Value::Boolean(*accept));
+                "tradeRespond"
+            }
+            ClientMessage::TradeOfferItem { slot_index, quantity } => {
+                data.insert("slotIndex".into(), Value::Integer((*slot_index as i64).into()));
+                data.insert("quantity".into(), Value::Integer((*quantity as i64).into()));
+                "tradeOfferItem"
+            }
+            ClientMessage::TradeRemoveItem { offer_index } => {
+                data.insert("offerIndex".into(), Value::Integer((*offer_index as i64).into()));
+                "tradeRemoveItem"
+            }
+            ClientMessage::TradeSetGold { amount } => {
+                data.insert("amount".into(), Value::Integer((*amount as i64).into()));
+                "tradeSetGold"
+            }
+            ClientMessage::TradeAccept => "tradeAccept",
+            ClientMessage::TradeConfirm => "tradeConfirm",
+            ClientMessage::TradeCancel => "tradeCancel",
+            ClientMessage::PartyRespond { inviter_id, accept } => {
+                data.insert("inviterId".into(), Value::String(inviter_id.clone().into()));
+                data.insert("accept".into(), Value::Boolean(*accept));
+                "partyRespond"
+            }
+            ClientMessage::PartyLeave => "partyLeave",
+            ClientMessage::PartyKick { member_id } => {
+                data.insert("memberId".into(), Value::String(member_id.clone().into()));
+                "partyKick"
+            }
+            ClientMessage::PartyPromote { member_id } => {
+                data.insert("memberId".into(), Value::String(member_id.clone().into()));
+                "partyPromote"
+            }
+            ClientMessage::SetTitle { achievement_id } => {
+                if let Some(id) = achievement_id {
+                    data.insert("achievementId".into(), Value::String(id.clone().into()));
+                }
+                "setTitle"
+            }
+            ClientMessage::StorageOpen { furniture_id } => {
+                data.insert("furnitureId".into(), Value::Integer((*furniture_id as i64).into()));
+                "storageOpen"
+            }
+            ClientMessage::StorageDeposit { furniture_id, slot_index, quantity } => {
+                data.insert("furnitureId".into(), Value::Integer((*furniture_id as i64).into()));
+                data.insert("slotIndex".into(), Value::Integer((*slot_index as i64).into()));
+                data.insert("quantity".into(), Value::Integer((*quantity as i64).into()));
+                "storageDeposit"
+            }
+            ClientMessage::StorageWithdraw { furniture_id, storage_slot } => {
+                data.insert("furnitureId".into(), Value::Integer((*furniture_id as i64).into()));
+                data.insert("storageSlot".into(), Value::Integer((*storage_slot as i64).into()));
+                "storageWithdraw"
+            }
+            ClientMessage::GravestoneOpen { gravestone_id } => {
+                data.insert("gravestoneId".into(), Value::String(gravestone_id.clone().into()));
+                "gravestoneOpen"
+            }
+            ClientMessage::GravestoneRecover { gravestone_id } => {
+                data.insert("gravestoneId".into(), Value::String(gravestone_id.clone().into()));
+                "gravestoneRecover"
+            }
         };
 
         (msg_type, data)
@@ -8,5 +8,6 @@ mod ui;
 pub use renderer::{Renderer, RenderTimings};
 pub use animation::{AnimationState, PlayerAnimation};
 pub use font::BitmapFont;
+pub use ui::achievements::ACHIEVEMENT_ROWS_VISIBLE;
 pub use ui::area_banner::{AreaBanner, OVERWORLD_NAME};
 pub use ui::xp_globes::XpGlobesManager;
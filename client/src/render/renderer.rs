@@ -5,11 +5,12 @@ use macroquad::miniquad::UniformDesc;
 use macroquad::miniquad::ShaderSource;
 use std::collections::HashMap;
 use crate::util::{asset_path, SpriteManifest, SpriteAtlasInfo, virtual_screen_size};
-use crate::game::{GameState, Player, Camera, ConnectionStatus, LayerType, GroundItem, ChunkLayerType, CHUNK_SIZE, MapObject, ChatChannel, Direction, DragSource, Wall, WallEdge};
+use crate::game::{GameState, Player, Camera, ConnectionStatus, LayerType, GroundItem, Gravestone, ChunkLayerType, CHUNK_SIZE, MapObject, ChatChannel, Direction, DragSource, Wall, WallEdge};
 use crate::game::npc::{Npc, NpcState};
 use crate::game::tilemap::get_tile_color;
 use crate::ui::UiLayout;
 use super::ui::common::{SlotState, CORNER_ACCENT_SIZE};
+use super::ui::status_effects::status_icons_width;
 use super::isometric::{world_to_screen, world_to_screen_exact, TILE_WIDTH, TILE_HEIGHT, calculate_depth};
 use super::animation::{SPRITE_WIDTH, SPRITE_HEIGHT, WEAPON_SPRITE_WIDTH, WEAPON_SPRITE_HEIGHT, BOOT_SPRITE_WIDTH, BOOT_SPRITE_HEIGHT, BODY_ARMOR_SPRITE_WIDTH, BODY_ARMOR_SPRITE_HEIGHT, HEAD_SPRITE_WIDTH, HEAD_SPRITE_HEIGHT, BACK_STATIC_SPRITE_WIDTH, BACK_STATIC_SPRITE_HEIGHT, OFFHAND_SPRITE_WIDTH, OFFHAND_SPRITE_HEIGHT, NpcAnimation, get_weapon_frame, get_weapon_offset, get_boot_frame, get_boot_offset, get_body_armor_frame, get_body_armor_offset, get_head_frame, get_head_offset, get_back_static_frame, get_back_static_offset, get_offhand_frame, get_offhand_offset, AnimationState};
 use super::font::BitmapFont;
@@ -107,6 +108,24 @@ const HEALTH_RED_DARK: Color = Color::new(0.55, 0.12, 0.12, 1.0);        // Ruby
 const HEALTH_RED_MID: Color = Color::new(0.75, 0.18, 0.18, 1.0);         // Ruby bright
 const HEALTH_RED_LIGHT: Color = Color::new(0.90, 0.35, 0.35, 1.0);       // Ruby highlight
 
+/// Glow color for spell projectiles (arrows are drawn as arrows)
+fn projectile_color(sprite: &str) -> Color {
+    match sprite {
+        "shadow_bolt" => Color::new(0.55, 0.25, 0.85, 1.0),
+        "fireball" => Color::new(0.95, 0.50, 0.15, 1.0),
+        "venom" => Color::new(0.40, 0.80, 0.25, 1.0),
+        _ => Color::new(0.60, 0.80, 1.0, 1.0),
+    }
+}
+
+/// Name and aura color for elite monster ranks
+fn elite_color(rank: &str) -> Color {
+    match rank {
+        "champion" => Color::new(1.0, 0.65, 0.15, 1.0),
+        _ => Color::new(0.45, 0.65, 1.0, 1.0),
+    }
+}
+
 /// A sprite atlas: one texture containing many sprites, with rect lookups
 pub struct SpriteAtlas {
     pub texture: Texture2D,
@@ -747,6 +766,14 @@ impl Renderer {
                 }
             }
         }
+
+        // 1.7. Render boss area attack warnings
+        for telegraph in &state.boss_telegraphs {
+            let progress = ((get_time() - telegraph.start_time) / (telegraph.hits_at - telegraph.start_time).max(0.001)) as f32;
+            for &(tile_x, tile_y) in &telegraph.tiles {
+                self.render_telegraph_tile(tile_x, tile_y, &state.camera, progress.clamp(0.0, 1.0));
+            }
+        }
         timings.ground_ms = (get_time() - t0) * 1000.0;
 
         // 2. Collect renderable items (players + NPCs + items + object tiles + map objects) for depth sorting
@@ -756,6 +783,7 @@ impl Renderer {
             Player(&'a Player, bool),
             Npc(&'a Npc),
             Item(&'a GroundItem),
+            Gravestone(&'a Gravestone),
             Tile { x: u32, y: u32, tile_id: u32 },
             ChunkObject(&'a MapObject),
             ChunkWall(&'a Wall),
@@ -771,6 +799,12 @@ impl Renderer {
             renderables.push((depth, Renderable::Item(item)));
         }
 
+        // Add gravestones (stand upright like entities)
+        for grave in state.gravestones.values() {
+            let depth = calculate_depth(grave.x, grave.y, 1);
+            renderables.push((depth, Renderable::Gravestone(grave)));
+        }
+
         // Add players
         for player in state.players.values() {
             let is_local = state.local_player_id.as_ref() == Some(&player.id);
@@ -823,6 +857,10 @@ impl Renderer {
                 Renderable::Item(item) => {
                     self.render_ground_item(item, &state.camera, state);
                 }
+                Renderable::Gravestone(grave) => {
+                    let own = state.local_player_id.as_ref() == Some(&grave.owner_id);
+                    self.render_gravestone(grave, own, &state.camera);
+                }
                 Renderable::Player(player, is_local) => {
                     let is_selected = state.selected_entity_id.as_ref() == Some(&player.id);
                     let is_hovered = state.hovered_entity_id.as_ref() == Some(&player.id);
@@ -1189,6 +1227,15 @@ impl Renderer {
             let arrow_y_offset = -40.0 * state.camera.zoom;
             let screen_y = screen_y_raw + arrow_y_offset;
 
+            // Anything that isn't an arrow is a spell: a glowing orb
+            if projectile.sprite != "arrow" {
+                let color = projectile_color(&projectile.sprite);
+                draw_circle(screen_x, screen_y, 7.0, Color::new(color.r, color.g, color.b, 0.35));
+                draw_circle(screen_x, screen_y, 4.0, color);
+                draw_circle(screen_x, screen_y, 1.5, Color::new(1.0, 1.0, 1.0, 0.9));
+                continue;
+            }
+
             // Calculate direction in SCREEN space (accounts for isometric transform)
             let (start_screen_x, start_screen_y) = world_to_screen(projectile.start_x, projectile.start_y, &state.camera);
             let (end_screen_x, end_screen_y) = world_to_screen(projectile.end_x, projectile.end_y, &state.camera);
@@ -1630,6 +1677,30 @@ impl Renderer {
     }
 
     /// Draw a green drop zone indicator for a tile (when dragging items)
+    /// Render a red warning diamond on a tile a boss attack will hit (brighter as it lands)
+    fn render_telegraph_tile(&self, tile_x: i32, tile_y: i32, camera: &Camera, progress: f32) {
+        let (center_x, center_y) = world_to_screen(tile_x as f32 + 0.5, tile_y as f32 + 0.5, camera);
+        let center_y = center_y - TILE_HEIGHT * camera.zoom / 2.0;
+        let half_w = TILE_WIDTH * camera.zoom / 2.0;
+        let half_h = TILE_HEIGHT * camera.zoom / 2.0;
+
+        let top = Vec2::new(center_x, center_y - half_h);
+        let right = Vec2::new(center_x + half_w, center_y);
+        let bottom = Vec2::new(center_x, center_y + half_h);
+        let left = Vec2::new(center_x - half_w, center_y);
+
+        let color = Color::from_rgba(220, 40, 30, (50.0 + 110.0 * progress) as u8);
+        draw_triangle(top, right, bottom, color);
+        draw_triangle(top, bottom, left, color);
+
+        let border_color = Color::from_rgba(255, 90, 60, 200);
+        let line_width = 1.0 * camera.zoom;
+        draw_line(top.x, top.y, right.x, right.y, line_width, border_color);
+        draw_line(right.x, right.y, bottom.x, bottom.y, line_width, border_color);
+        draw_line(bottom.x, bottom.y, left.x, left.y, line_width, border_color);
+        draw_line(left.x, left.y, top.x, top.y, line_width, border_color);
+    }
+
     pub(crate) fn render_drop_zone(&self, tile_x: i32, tile_y: i32, camera: &Camera, is_hovered: bool) {
         // Get the center of the tile in screen space
         let (center_x, center_y) = world_to_screen(tile_x as f32 + 0.5, tile_y as f32 + 0.5, camera);
@@ -1701,8 +1772,10 @@ impl Renderer {
             let coords = player.animation.get_sprite_coords();
             let (src_x, src_y, src_w, src_h) = coords.to_source_rect();
 
-            // Tint for local player distinction (slight green tint)
-            let tint = if is_local {
+            // Tint for status effects, otherwise local player distinction (slight green tint)
+            let tint = if let Some((r, g, b)) = player.status_tint() {
+                Color::from_rgba(r, g, b, alpha)
+            } else if is_local {
                 Color::from_rgba(220, 255, 220, alpha)
             } else {
                 Color::from_rgba(255, 255, 255, alpha)
@@ -2300,30 +2373,59 @@ impl Renderer {
 
         let show_name = is_selected || is_hovered;
         if show_name {
-            // Build display name with optional (GM) suffix
+            // Build display name with optional <TAG> and title prefixes and (GM) suffix
+            let guild_prefix = player.guild_tag.as_ref().map(|tag| format!("<{}> ", tag));
+            let title_prefix = player.title.as_ref().map(|title| format!("{} ", title));
+            let tag_width = guild_prefix.as_ref().map(|p| self.measure_text_sharp(p, 16.0).width).unwrap_or(0.0);
+            let title_width = title_prefix.as_ref().map(|p| self.measure_text_sharp(p, 16.0).width).unwrap_or(0.0);
             let name_width = self.measure_text_sharp(&player.name, 16.0).width;
             let gm_width = if player.is_admin { self.measure_text_sharp(" (GM)", 16.0).width - 2.0 } else { 0.0 };
-            let total_width = name_width + gm_width;
-            let name_x = screen_x - total_width / 2.0;
+            let total_width = tag_width + title_width + name_width + gm_width;
+            let tag_x = screen_x - total_width / 2.0;
+            let title_x = tag_x + tag_width;
+            let name_x = title_x + title_width;
             let name_y = screen_y - name_y_offset + 2.0;
 
             // Background for readability
             let padding = 4.0;
             draw_rectangle(
-                name_x - padding,
+                tag_x - padding,
                 name_y - 14.0,
                 total_width + padding * 2.0,
                 18.0,
                 Color::from_rgba(0, 0, 0, 180),
             );
 
-            // Draw player name in white
+            // Draw guild tag in green
+            if let Some(prefix) = &guild_prefix {
+                self.draw_text_sharp(
+                    prefix,
+                    tag_x,
+                    name_y,
+                    16.0,
+                    Color::from_rgba(120, 230, 130, 255),
+                );
+            }
+
+            // Draw achievement title in light purple
+            if let Some(prefix) = &title_prefix {
+                self.draw_text_sharp(
+                    prefix,
+                    title_x,
+                    name_y,
+                    16.0,
+                    Color::from_rgba(200, 160, 255, 255),
+                );
+            }
+
+            // Draw player name in white, or red while opted in to PvP
+            let name_color = if player.pvp_enabled { Color::from_rgba(255, 110, 100, 255) } else { WHITE };
             self.draw_text_sharp(
                 &player.name,
                 name_x,
                 name_y,
                 16.0,
-                WHITE,
+                name_color,
             );
 
             // Draw (GM) suffix in gold if admin
@@ -2358,6 +2460,33 @@ impl Renderer {
 
             self.draw_entity_health_bar(bar_x, bar_y, bar_width, bar_height, hp_ratio, 1.0);
         }
+
+        // Status effect icons above the head of other players (the local player's are on the HUD)
+        let mut overhead_y = screen_y - name_y_offset;
+        if show_name {
+            overhead_y -= 16.0;
+        }
+        if show_health_bar {
+            overhead_y -= 10.0;
+        }
+        let effect_count = player.active_status_effects().count();
+        if !is_local && effect_count > 0 {
+            let icon_size = 12.0;
+            let icons_x = screen_x - status_icons_width(effect_count, icon_size) / 2.0;
+            overhead_y -= icon_size + 2.0;
+            self.render_status_icons(player, icons_x, overhead_y, icon_size, false);
+        }
+
+        // Skull above the head of players who attacked outside a PvP zone (always visible)
+        if player.skulled {
+            let skull_y = overhead_y - 10.0;
+            let r = 6.0;
+            draw_circle(screen_x, skull_y, r, Color::from_rgba(235, 230, 215, 255));
+            draw_rectangle(screen_x - r * 0.6, skull_y + r * 0.4, r * 1.2, r * 0.9, Color::from_rgba(235, 230, 215, 255));
+            draw_circle(screen_x - r * 0.4, skull_y, r * 0.3, Color::from_rgba(40, 20, 20, 255));
+            draw_circle(screen_x + r * 0.4, skull_y, r * 0.3, Color::from_rgba(40, 20, 20, 255));
+            draw_line(screen_x, skull_y + r * 0.6, screen_x, skull_y + r * 1.3, 1.0, Color::from_rgba(40, 20, 20, 255));
+        }
     }
 
     /// Renders a semi-transparent silhouette of the player that's always visible
@@ -2609,8 +2738,21 @@ impl Renderer {
             self.render_tile_selection(npc.x, npc.y, camera);
         }
 
+        // Elite aura: a pulsing glow at the feet in the rank's color
+        if let Some(rank) = npc.elite_rank.as_deref().filter(|_| npc.death_timer.is_none()) {
+            let color = elite_color(rank);
+            let phase_offset = (npc.x + npc.y * 1.7) as f64;
+            let pulse = ((macroquad::time::get_time() * 3.0 + phase_offset).sin() * 0.5 + 0.5) as f32;
+            draw_ellipse(screen_x, screen_y, 24.0 * zoom, 10.0 * zoom, 0.0, Color::new(color.r, color.g, color.b, 0.15 + pulse * 0.15));
+            draw_ellipse(screen_x, screen_y, 18.0 * zoom, 7.0 * zoom, 0.0, Color::new(color.r, color.g, color.b, 0.25 + pulse * 0.2));
+        }
+
         // Name color based on NPC type
-        let name_color = if npc.is_hostile() {
+        let name_color = if let Some(rank) = &npc.elite_rank {
+            elite_color(rank)
+        } else if npc.is_pet() {
+            Color::from_rgba(255, 230, 150, 255) // Pale gold for pets
+        } else if npc.is_hostile() {
             Color::from_rgba(255, 150, 150, 255) // Red for hostile
         } else if npc.is_quest_giver {
             Color::from_rgba(150, 220, 255, 255) // Cyan for quest givers
@@ -2945,6 +3087,37 @@ impl Renderer {
         }
     }
 
+    /// Render a gravestone with its owner's name; the local player's own one is outlined in gold
+    fn render_gravestone(&self, grave: &Gravestone, own: bool, camera: &Camera) {
+        let (screen_x, screen_y) = world_to_screen(grave.x, grave.y, camera);
+        let zoom = camera.zoom;
+
+        let stone_w = 12.0 * zoom;
+        let stone_h = 16.0 * zoom;
+        let stone_x = screen_x - stone_w / 2.0;
+        let stone_y = screen_y - stone_h;
+        let outline = if own { Color::from_rgba(220, 180, 80, 255) } else { Color::from_rgba(40, 40, 45, 255) };
+
+        draw_ellipse(screen_x, screen_y, 11.0 * zoom, 4.0 * zoom, 0.0, Color::from_rgba(0, 0, 0, 60));
+        // Rounded top: a circle over the upper edge of the slab
+        draw_circle(screen_x, stone_y + stone_w / 2.0, stone_w / 2.0 + zoom, outline);
+        draw_rectangle(stone_x - zoom, stone_y + stone_w / 2.0, stone_w + 2.0 * zoom, stone_h - stone_w / 2.0 + zoom, outline);
+        draw_circle(screen_x, stone_y + stone_w / 2.0, stone_w / 2.0, Color::from_rgba(130, 130, 140, 255));
+        draw_rectangle(stone_x, stone_y + stone_w / 2.0, stone_w, stone_h - stone_w / 2.0, Color::from_rgba(130, 130, 140, 255));
+
+        // Engraved cross
+        let cross = Color::from_rgba(80, 80, 90, 255);
+        draw_rectangle(screen_x - 0.5 * zoom, stone_y + 3.0 * zoom, zoom, 9.0 * zoom, cross);
+        draw_rectangle(screen_x - 3.0 * zoom, stone_y + 6.0 * zoom, 6.0 * zoom, zoom, cross);
+
+        let label = format!("{}'s grave", grave.owner_name);
+        let label_w = self.measure_text_sharp(&label, 16.0).width;
+        let label_x = (screen_x - label_w / 2.0).round();
+        let label_y = (stone_y - 6.0 * zoom).round();
+        self.draw_text_sharp(&label, label_x + 1.0, label_y + 1.0, 16.0, BLACK);
+        self.draw_text_sharp(&label, label_x, label_y, 16.0, if own { Color::from_rgba(255, 215, 0, 255) } else { LIGHTGRAY });
+    }
+
     /// Render a gold pile with multiple animated nuggets
     fn render_gold_pile(&self, item: &GroundItem, camera: &Camera) {
         let (screen_x, screen_y) = world_to_screen(item.x, item.y, camera);
@@ -3255,10 +3428,26 @@ impl Renderer {
                 }
                 self.draw_text_sharp(text, text_x, text_y, font_size, RED);
 
-                // Respawn countdown (5 seconds)
+                // What this death cost, from the zone's death rule
+                let notice = state.ui_state.death_penalty.as_ref();
+                let penalty_text = notice.and_then(|n| match n.penalty.as_str() {
+                    "gravestone" if n.gravestone_id.is_some() => {
+                        let minutes = ((n.expires_at - macroquad::time::get_time()).max(0.0) / 60.0).ceil();
+                        Some(format!("Your items lie in a gravestone for {} minutes", minutes))
+                    }
+                    "gold_loss" if n.gold_lost > 0 => Some(format!("You lost {} gold", n.gold_lost)),
+                    "hardcore" => Some("Your character has perished forever".to_string()),
+                    _ => None,
+                });
+                if let Some(text) = penalty_text {
+                    let dims = self.measure_text_sharp(&text, 16.0);
+                    self.draw_text_sharp(&text, (sw - dims.width) / 2.0, text_y + 80.0, 16.0, LIGHTGRAY);
+                }
+
+                // Respawn countdown (5 seconds), never for a hardcore death
                 let time_since_death = macroquad::time::get_time() - player.death_time;
                 let respawn_time = 5.0 - time_since_death;
-                if respawn_time > 0.0 {
+                if respawn_time > 0.0 && !notice.is_some_and(|n| n.is_hardcore()) {
                     let countdown_text = format!("Respawning in {:.1}s", respawn_time);
                     let countdown_dims = self.measure_text_sharp(&countdown_text, 16.0);
                     self.draw_text_sharp(
@@ -3288,6 +3477,8 @@ impl Renderer {
                     ChatChannel::Local => (WHITE, format!("{}: {}", msg.sender_name, msg.text)),
                     ChatChannel::Global => (SKYBLUE, format!("[G] {}: {}", msg.sender_name, msg.text)),
                     ChatChannel::System => (YELLOW, format!("{} {}", msg.sender_name, msg.text)),
+                    ChatChannel::Party => (Color::from_rgba(120, 200, 255, 255), format!("[P] {}: {}", msg.sender_name, msg.text)),
+                    ChatChannel::Guild => (Color::from_rgba(120, 230, 130, 255), format!("[Guild] {}: {}", msg.sender_name, msg.text)),
                 };
                 let wrapped_lines = self.wrap_text(&text, max_chat_width, font_size);
 
@@ -3363,6 +3554,14 @@ impl Renderer {
             let hp_text_w = self.measure_text_sharp(&hp_text, font_size).width;
             self.draw_text_sharp(&hp_text, (hp_bar_x + (bar_width - hp_text_w) / 2.0).floor(), (hp_bar_y + 14.0).floor(), font_size, TEXT_NORMAL);
 
+            // Status effects (below the HP bar, right-aligned)
+            let effect_count = player.active_status_effects().count();
+            if effect_count > 0 {
+                let icon_size = 20.0;
+                let icons_x = bar_x + bar_width - status_icons_width(effect_count, icon_size);
+                self.render_status_icons(player, icons_x, hp_bar_y + bar_height + 4.0, icon_size, true);
+            }
+
             // XP Globes (to the left of player stats)
             let globe_stats_y = tag_y + tag_height / 2.0 + 8.0; // Slightly below name tag center
             self.render_xp_globes(&state.xp_globes, bar_x, globe_stats_y);
@@ -3371,6 +3570,9 @@ impl Renderer {
         // Note: Interactive UI (inventory, crafting, dialogue, quick slots) is rendered
         // by render_interactive_ui() which is called by the main render loop
 
+        // Boss health bar (while a boss fight is nearby)
+        self.render_boss_bar(state);
+
         // Area banner (location name during transitions)
         if state.area_banner.is_visible() {
             self.render_area_banner(&state.area_banner.text, state.area_banner.opacity());
@@ -3495,6 +3697,26 @@ impl Renderer {
             self.render_quest_log(state, hovered, &mut layout);
         }
 
+        // Reputation panel (when open)
+        if state.ui_state.reputation_open {
+            self.render_reputation_panel(state);
+        }
+
+        // Achievements panel (when open)
+        if state.ui_state.achievements_open {
+            self.render_achievements_panel(state, hovered, &mut layout);
+        }
+
+        // Challenges panel (when open)
+        if state.ui_state.challenges_open {
+            self.render_challenges_panel(state);
+        }
+
+        // Bounty board (when open)
+        if state.ui_state.bounty_board_open {
+            self.render_bounty_board(state);
+        }
+
         // Crafting UI (when open)
         if state.ui_state.crafting_open {
             self.render_crafting(state, hovered, &mut layout);
@@ -3515,6 +3737,9 @@ impl Renderer {
         // Quest objective tracker (top-left)
         self.render_quest_tracker(state);
 
+        // Party frames (left side, below quest tracker)
+        self.render_party_frames(state, hovered, &mut layout);
+
         // Quest completion notifications
         self.render_quest_completed(state);
 
@@ -3523,6 +3748,31 @@ impl Renderer {
             self.render_dialogue(dialogue, hovered, &mut layout, state.ui_state.dialogue_scroll_offset, state.ui_state.dialogue_scrollbar_dragging);
         }
 
+        // Trade window (when trading)
+        if let Some(ref trade) = state.ui_state.trade {
+            self.render_trade_window(trade, state, hovered, &mut layout);
+        }
+
+        // House storage chest (when open)
+        if let Some(ref storage) = state.ui_state.storage {
+            self.render_storage_window(storage, state, hovered, &mut layout);
+        }
+
+        // Gravestone recovery window (when open)
+        if let Some(ref gravestone) = state.ui_state.gravestone {
+            self.render_gravestone_window(gravestone, state, hovered, &mut layout);
+        }
+
+        // Incoming trade request prompt
+        if let Some(ref prompt) = state.ui_state.trade_request {
+            self.render_trade_request_prompt(prompt, hovered, &mut layout);
+        }
+
+        // Party invite prompt
+        if let Some(ref invite) = state.ui_state.party_invite {
+            self.render_party_invite_prompt(invite, hovered, &mut layout);
+        }
+
         // Gold drop dialog (when active)
         if let Some(ref dialog) = state.ui_state.gold_drop_dialog {
             self.render_gold_drop_dialog(dialog, state.inventory.gold, hovered, &mut layout);
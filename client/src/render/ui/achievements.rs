@@ -0,0 +1,108 @@
+//! Achievements panel rendering (progress list and title selection)
+
+use macroquad::prelude::*;
+use crate::game::GameState;
+use crate::ui::{UiElementId, UiLayout};
+use crate::util::virtual_screen_size;
+use super::super::Renderer;
+use super::common::*;
+
+/// Number of achievement rows shown at once (the list scrolls with the mouse wheel)
+pub const ACHIEVEMENT_ROWS_VISIBLE: usize = 8;
+
+const ROW_HEIGHT: f32 = 44.0;
+const BAR_HEIGHT: f32 = 6.0;
+
+impl Renderer {
+    /// Render the achievements panel; unlocked achievements with a title get a button to display it
+    pub(crate) fn render_achievements_panel(&self, state: &GameState, hovered: &Option<UiElementId>, layout: &mut UiLayout) {
+        let (sw, sh) = virtual_screen_size();
+        let achievements = &state.ui_state.achievements;
+
+        let panel_width = 420.0;
+        let panel_height = FRAME_THICKNESS * 2.0 + HEADER_HEIGHT + FOOTER_HEIGHT + 16.0 + ACHIEVEMENT_ROWS_VISIBLE as f32 * ROW_HEIGHT;
+        let panel_x = (sw - panel_width) / 2.0;
+        let panel_y = (sh - panel_height) / 2.0;
+
+        self.draw_panel_frame(panel_x, panel_y, panel_width, panel_height);
+        self.draw_corner_accents(panel_x, panel_y, panel_width, panel_height);
+
+        // ===== HEADER SECTION =====
+        let header_x = panel_x + FRAME_THICKNESS;
+        let header_y = panel_y + FRAME_THICKNESS;
+        let header_w = panel_width - FRAME_THICKNESS * 2.0;
+
+        draw_rectangle(header_x, header_y, header_w, HEADER_HEIGHT, HEADER_BG);
+        draw_line(header_x + 10.0, header_y + HEADER_HEIGHT, header_x + header_w - 10.0, header_y + HEADER_HEIGHT, 2.0, HEADER_BORDER);
+        self.draw_text_sharp("ACHIEVEMENTS", header_x + 12.0, header_y + 26.0, 16.0, TEXT_TITLE);
+
+        // ===== CONTENT AREA =====
+        let content_x = panel_x + FRAME_THICKNESS + 8.0;
+        let content_w = panel_width - FRAME_THICKNESS * 2.0 - 16.0;
+        let mut y = panel_y + FRAME_THICKNESS + HEADER_HEIGHT + 8.0;
+
+        if achievements.is_empty() {
+            self.draw_text_sharp("No achievements available", content_x + 12.0, y + 24.0, 16.0, TEXT_DIM);
+        }
+
+        let first = state.ui_state.achievements_scroll.min(achievements.len());
+        for (idx, entry) in achievements.iter().enumerate().skip(first).take(ACHIEVEMENT_ROWS_VISIBLE) {
+            let row_bg = if entry.unlocked { SLOT_BG_FILLED } else { SLOT_BG_EMPTY };
+            draw_rectangle(content_x, y, content_w, ROW_HEIGHT - 4.0, SLOT_BORDER);
+            draw_rectangle(content_x + 1.0, y + 1.0, content_w - 2.0, ROW_HEIGHT - 6.0, row_bg);
+
+            let name_color = if entry.unlocked { TEXT_GOLD } else { TEXT_NORMAL };
+            self.draw_text_sharp(&entry.name, content_x + 8.0, y + 15.0, 16.0, name_color);
+            self.draw_text_sharp(&entry.description, content_x + 8.0, y + 31.0, 16.0, TEXT_DIM);
+
+            let right_x = content_x + content_w - 8.0;
+            if entry.unlocked {
+                if let Some(title) = &entry.title {
+                    let is_active = state.ui_state.active_title.as_ref() == Some(&entry.id);
+                    let label = if is_active { "Active".to_string() } else { format!("\"{}\"", title) };
+                    let button_w = self.measure_text_sharp(&label, 16.0).width + 16.0;
+                    self.draw_text_button(
+                        &label,
+                        Rect::new(right_x - button_w, y + 8.0, button_w, 22.0),
+                        UiElementId::AchievementTitleButton(idx),
+                        false,
+                        hovered,
+                        layout,
+                    );
+                }
+            } else {
+                // Progress text and bar
+                let progress_text = format!("{} / {}", entry.progress, entry.target);
+                let text_w = self.measure_text_sharp(&progress_text, 16.0).width;
+                self.draw_text_sharp(&progress_text, right_x - text_w, y + 15.0, 16.0, TEXT_DIM);
+
+                let bar_w = 90.0;
+                let bar_x = right_x - bar_w;
+                let bar_y = y + 24.0;
+                let fraction = if entry.target > 0 {
+                    (entry.progress as f32 / entry.target as f32).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                draw_rectangle(bar_x, bar_y, bar_w, BAR_HEIGHT, HEALTHBAR_BG_OUTER);
+                draw_rectangle(bar_x + 1.0, bar_y + 1.0, (bar_w - 2.0) * fraction, BAR_HEIGHT - 2.0, FRAME_ACCENT);
+            }
+
+            y += ROW_HEIGHT;
+        }
+
+        // ===== FOOTER SECTION =====
+        let footer_x = panel_x + FRAME_THICKNESS;
+        let footer_y = panel_y + panel_height - FRAME_THICKNESS - FOOTER_HEIGHT;
+        let footer_w = panel_width - FRAME_THICKNESS * 2.0;
+
+        draw_rectangle(footer_x, footer_y, footer_w, FOOTER_HEIGHT, FOOTER_BG);
+        draw_line(footer_x + 10.0, footer_y, footer_x + footer_w - 10.0, footer_y, 1.0, HEADER_BORDER);
+        self.draw_text_sharp("[Y] Close", footer_x + 10.0, footer_y + 20.0, 16.0, TEXT_DIM);
+
+        let unlocked = achievements.iter().filter(|a| a.unlocked).count();
+        let count_text = format!("{} / {} Unlocked", unlocked, achievements.len());
+        let count_width = self.measure_text_sharp(&count_text, 16.0).width;
+        self.draw_text_sharp(&count_text, footer_x + footer_w - count_width - 10.0, footer_y + 20.0, 16.0, FRAME_MID);
+    }
+}
@@ -0,0 +1,72 @@
+//! Boss health bar (top of screen while a boss encounter is nearby)
+
+use macroquad::prelude::*;
+use crate::game::GameState;
+use crate::util::virtual_screen_size;
+use super::super::Renderer;
+use super::common::*;
+
+const BAR_WIDTH: f32 = 420.0;
+const BAR_HEIGHT: f32 = 14.0;
+const LEADERBOARD_ROW_HEIGHT: f32 = 16.0;
+const ENRAGED_COLOR: Color = Color::new(1.0, 0.35, 0.2, 1.0);
+
+impl Renderer {
+    /// Render the boss health bar with phase, enrage state and damage leaderboard
+    pub(crate) fn render_boss_bar(&self, state: &GameState) {
+        let Some(bar) = &state.ui_state.boss_bar else {
+            return;
+        };
+        // Only while the boss is loaded nearby
+        if !state.npcs.contains_key(&bar.boss_id) {
+            return;
+        }
+
+        let (sw, _) = virtual_screen_size();
+        let panel_w = BAR_WIDTH + 24.0;
+        let panel_h = 58.0 + bar.leaderboard.len() as f32 * LEADERBOARD_ROW_HEIGHT;
+        let panel_x = ((sw - panel_w) / 2.0).floor();
+        let panel_y = 12.0;
+
+        draw_rectangle(panel_x, panel_y, panel_w, panel_h, PANEL_BG_DARK);
+        draw_rectangle_lines(panel_x, panel_y, panel_w, panel_h, 2.0, if bar.enraged { ENRAGED_COLOR } else { FRAME_MID });
+
+        // Name and phase
+        let content_x = panel_x + 12.0;
+        self.draw_text_sharp(&bar.name, content_x, panel_y + 20.0, 16.0, TEXT_TITLE);
+        let phase_text = match &bar.phase_name {
+            Some(name) => format!("Phase {}: {}", bar.phase, name),
+            None => format!("Phase {}", bar.phase),
+        };
+        let phase_text = if bar.enraged { format!("{} - ENRAGED", phase_text) } else { phase_text };
+        let phase_w = self.measure_text_sharp(&phase_text, 16.0).width;
+        let phase_color = if bar.enraged { ENRAGED_COLOR } else { TEXT_DIM };
+        self.draw_text_sharp(&phase_text, content_x + BAR_WIDTH - phase_w, panel_y + 20.0, 16.0, phase_color);
+
+        // Health bar
+        let bar_y = panel_y + 28.0;
+        let hp_ratio = (bar.hp as f32 / bar.max_hp.max(1) as f32).clamp(0.0, 1.0);
+        draw_rectangle(content_x - 1.0, bar_y - 1.0, BAR_WIDTH + 2.0, BAR_HEIGHT + 2.0, HEALTHBAR_FRAME_DARK);
+        draw_rectangle(content_x, bar_y, BAR_WIDTH, BAR_HEIGHT, HEALTHBAR_BG_OUTER);
+        if hp_ratio > 0.0 {
+            let fill_w = (BAR_WIDTH * hp_ratio).max(1.0).floor();
+            draw_rectangle(content_x, bar_y, fill_w, BAR_HEIGHT, HEALTH_RED_DARK);
+            draw_rectangle(content_x, bar_y + 1.0, fill_w, BAR_HEIGHT - 2.0, HEALTH_RED_MID);
+            draw_rectangle(content_x, bar_y + 1.0, fill_w, (BAR_HEIGHT * 0.35).floor(), HEALTH_RED_LIGHT);
+        }
+        let hp_text = format!("{} / {}", bar.hp.max(0), bar.max_hp);
+        let hp_w = self.measure_text_sharp(&hp_text, 16.0).width;
+        self.draw_text_sharp(&hp_text, content_x + (BAR_WIDTH - hp_w) / 2.0, bar_y + BAR_HEIGHT - 2.0, 16.0, WHITE);
+
+        // Damage leaderboard
+        let mut y = bar_y + BAR_HEIGHT + 16.0;
+        for (rank, (name, damage)) in bar.leaderboard.iter().enumerate() {
+            let color = if rank == 0 { TEXT_GOLD } else { TEXT_NORMAL };
+            self.draw_text_sharp(&format!("{}. {}", rank + 1, name), content_x, y, 16.0, color);
+            let damage_text = damage.to_string();
+            let damage_w = self.measure_text_sharp(&damage_text, 16.0).width;
+            self.draw_text_sharp(&damage_text, content_x + BAR_WIDTH - damage_w, y, 16.0, color);
+            y += LEADERBOARD_ROW_HEIGHT;
+        }
+    }
+}
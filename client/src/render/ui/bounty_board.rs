@@ -0,0 +1,132 @@
+//! Bounty board panel rendering (running world events, bounties and upcoming events)
+
+use macroquad::prelude::*;
+use crate::game::{GameState, WorldEventEntry};
+use crate::util::virtual_screen_size;
+use super::super::Renderer;
+use super::challenges::format_remaining;
+use super::common::*;
+
+const ROW_HEIGHT: f32 = 60.0;
+const UPCOMING_ROW_HEIGHT: f32 = 22.0;
+const SECTION_HEIGHT: f32 = 26.0;
+const BAR_HEIGHT: f32 = 6.0;
+
+impl Renderer {
+    /// Render the bounty board with bounties (elite events), other running events and upcoming ones
+    pub(crate) fn render_bounty_board(&self, state: &GameState) {
+        let (sw, sh) = virtual_screen_size();
+        let ui = &state.ui_state;
+        let now = get_time();
+
+        let bounties: Vec<&WorldEventEntry> = ui.bounty_events.iter().filter(|e| e.kind == "elite").collect();
+        let events: Vec<&WorldEventEntry> = ui.bounty_events.iter().filter(|e| e.kind != "elite").collect();
+
+        let rows = bounties.len().max(1) + events.len().max(1);
+        let panel_width = 440.0;
+        let panel_height = FRAME_THICKNESS * 2.0 + HEADER_HEIGHT + FOOTER_HEIGHT + 16.0
+            + SECTION_HEIGHT * 3.0 + rows as f32 * ROW_HEIGHT
+            + ui.upcoming_events.len().max(1) as f32 * UPCOMING_ROW_HEIGHT;
+        let panel_x = (sw - panel_width) / 2.0;
+        let panel_y = (sh - panel_height) / 2.0;
+
+        self.draw_panel_frame(panel_x, panel_y, panel_width, panel_height);
+        self.draw_corner_accents(panel_x, panel_y, panel_width, panel_height);
+
+        // ===== HEADER SECTION =====
+        let header_x = panel_x + FRAME_THICKNESS;
+        let header_y = panel_y + FRAME_THICKNESS;
+        let header_w = panel_width - FRAME_THICKNESS * 2.0;
+
+        draw_rectangle(header_x, header_y, header_w, HEADER_HEIGHT, HEADER_BG);
+        draw_line(header_x + 10.0, header_y + HEADER_HEIGHT, header_x + header_w - 10.0, header_y + HEADER_HEIGHT, 2.0, HEADER_BORDER);
+        self.draw_text_sharp("BOUNTY BOARD", header_x + 12.0, header_y + 26.0, 16.0, TEXT_TITLE);
+
+        // ===== CONTENT AREA =====
+        let content_x = panel_x + FRAME_THICKNESS + 8.0;
+        let content_w = panel_width - FRAME_THICKNESS * 2.0 - 16.0;
+        let mut y = panel_y + FRAME_THICKNESS + HEADER_HEIGHT + 8.0;
+
+        let sections = [
+            ("Bounties", &bounties, "No bounties posted"),
+            ("World Events", &events, "All is quiet"),
+        ];
+        for (label, entries, empty_text) in sections {
+            self.draw_text_sharp(label, content_x + 4.0, y + 17.0, 16.0, TEXT_TITLE);
+            y += SECTION_HEIGHT;
+
+            if entries.is_empty() {
+                self.draw_text_sharp(empty_text, content_x + 12.0, y + 24.0, 16.0, TEXT_DIM);
+                y += ROW_HEIGHT;
+            }
+            for entry in entries.iter() {
+                self.draw_world_event_row(entry, content_x, y, content_w, now);
+                y += ROW_HEIGHT;
+            }
+        }
+
+        self.draw_text_sharp("Upcoming", content_x + 4.0, y + 17.0, 16.0, TEXT_TITLE);
+        y += SECTION_HEIGHT;
+        if ui.upcoming_events.is_empty() {
+            self.draw_text_sharp("Nothing scheduled", content_x + 12.0, y + 16.0, 16.0, TEXT_DIM);
+        }
+        for upcoming in &ui.upcoming_events {
+            self.draw_text_sharp(&upcoming.name, content_x + 12.0, y + 16.0, 16.0, TEXT_NORMAL);
+            let starts_text = format!("in {}", format_remaining(upcoming.starts_at - now));
+            let starts_w = self.measure_text_sharp(&starts_text, 16.0).width;
+            self.draw_text_sharp(&starts_text, content_x + content_w - starts_w - 4.0, y + 16.0, 16.0, TEXT_DIM);
+            y += UPCOMING_ROW_HEIGHT;
+        }
+
+        // ===== FOOTER SECTION =====
+        let footer_x = panel_x + FRAME_THICKNESS;
+        let footer_y = panel_y + panel_height - FRAME_THICKNESS - FOOTER_HEIGHT;
+        let footer_w = panel_width - FRAME_THICKNESS * 2.0;
+
+        draw_rectangle(footer_x, footer_y, footer_w, FOOTER_HEIGHT, FOOTER_BG);
+        draw_line(footer_x + 10.0, footer_y, footer_x + footer_w - 10.0, footer_y, 1.0, HEADER_BORDER);
+        self.draw_text_sharp("[Esc] Close", footer_x + 10.0, footer_y + 20.0, 16.0, TEXT_DIM);
+    }
+
+    fn draw_world_event_row(&self, entry: &WorldEventEntry, x: f32, y: f32, w: f32, now: f64) {
+        let eligible = entry.contribution >= entry.min_contribution;
+        let row_bg = if eligible { SLOT_BG_FILLED } else { SLOT_BG_EMPTY };
+        draw_rectangle(x, y, w, ROW_HEIGHT - 4.0, SLOT_BORDER);
+        draw_rectangle(x + 1.0, y + 1.0, w - 2.0, ROW_HEIGHT - 6.0, row_bg);
+
+        let right_x = x + w - 8.0;
+
+        // Name and reward, time left on the right
+        let mut name = entry.name.clone();
+        if entry.reward_gold > 0 {
+            name.push_str(&format!("  ({}g)", entry.reward_gold));
+        }
+        self.draw_text_sharp(&name, x + 8.0, y + 15.0, 16.0, TEXT_NORMAL);
+        let time_text = format!("Ends in {}", format_remaining(entry.ends_at - now));
+        let time_w = self.measure_text_sharp(&time_text, 16.0).width;
+        self.draw_text_sharp(&time_text, right_x - time_w, y + 15.0, 16.0, TEXT_DIM);
+
+        self.draw_text_sharp(&entry.description, x + 8.0, y + 31.0, 16.0, TEXT_DIM);
+
+        // Location and kill progress bar
+        let location_text = format!("At ({}, {})", entry.x, entry.y);
+        self.draw_text_sharp(&location_text, x + 8.0, y + 47.0, 16.0, TEXT_DIM);
+
+        let bar_w = 90.0;
+        let bar_x = right_x - bar_w;
+        let bar_y = y + 40.0;
+        let fraction = if entry.total > 0 {
+            (entry.killed as f32 / entry.total as f32).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        draw_rectangle(bar_x, bar_y, bar_w, BAR_HEIGHT, HEALTHBAR_BG_OUTER);
+        draw_rectangle(bar_x + 1.0, bar_y + 1.0, (bar_w - 2.0) * fraction, BAR_HEIGHT - 2.0, FRAME_ACCENT);
+
+        // Contribution turns gold once it qualifies for the reward
+        let contribution_text = format!("Damage {}/{}", entry.contribution, entry.min_contribution);
+        let contribution_w = self.measure_text_sharp(&contribution_text, 16.0).width;
+        let contribution_color = if eligible { TEXT_GOLD } else { TEXT_DIM };
+        self.draw_text_sharp(&contribution_text, bar_x - contribution_w - 8.0, y + 47.0, 16.0, contribution_color);
+    }
+}
@@ -0,0 +1,129 @@
+//! Daily and weekly challenges panel rendering
+
+use macroquad::prelude::*;
+use crate::game::{ChallengeEntry, GameState};
+use crate::util::virtual_screen_size;
+use super::super::Renderer;
+use super::common::*;
+
+const ROW_HEIGHT: f32 = 44.0;
+const SECTION_HEIGHT: f32 = 26.0;
+const BAR_HEIGHT: f32 = 6.0;
+
+/// Format seconds until a reset as "2d 5h", "5h 12m" or "12m 30s"
+pub(super) fn format_remaining(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    let (days, hours, minutes, secs) = (total / 86400, total / 3600 % 24, total / 60 % 60, total % 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m {}s", minutes, secs)
+    }
+}
+
+impl Renderer {
+    /// Render the challenges panel with daily and weekly objectives and time until reset
+    pub(crate) fn render_challenges_panel(&self, state: &GameState) {
+        let (sw, sh) = virtual_screen_size();
+        let ui = &state.ui_state;
+        let now = get_time();
+
+        let rows = ui.daily_challenges.len().max(1) + ui.weekly_challenges.len().max(1);
+        let panel_width = 420.0;
+        let panel_height = FRAME_THICKNESS * 2.0 + HEADER_HEIGHT + FOOTER_HEIGHT + 16.0
+            + SECTION_HEIGHT * 2.0 + rows as f32 * ROW_HEIGHT;
+        let panel_x = (sw - panel_width) / 2.0;
+        let panel_y = (sh - panel_height) / 2.0;
+
+        self.draw_panel_frame(panel_x, panel_y, panel_width, panel_height);
+        self.draw_corner_accents(panel_x, panel_y, panel_width, panel_height);
+
+        // ===== HEADER SECTION =====
+        let header_x = panel_x + FRAME_THICKNESS;
+        let header_y = panel_y + FRAME_THICKNESS;
+        let header_w = panel_width - FRAME_THICKNESS * 2.0;
+
+        draw_rectangle(header_x, header_y, header_w, HEADER_HEIGHT, HEADER_BG);
+        draw_line(header_x + 10.0, header_y + HEADER_HEIGHT, header_x + header_w - 10.0, header_y + HEADER_HEIGHT, 2.0, HEADER_BORDER);
+        self.draw_text_sharp("CHALLENGES", header_x + 12.0, header_y + 26.0, 16.0, TEXT_TITLE);
+
+        // ===== CONTENT AREA =====
+        let content_x = panel_x + FRAME_THICKNESS + 8.0;
+        let content_w = panel_width - FRAME_THICKNESS * 2.0 - 16.0;
+        let mut y = panel_y + FRAME_THICKNESS + HEADER_HEIGHT + 8.0;
+
+        let sections = [
+            ("Daily", &ui.daily_challenges, ui.daily_reset_time),
+            ("Weekly", &ui.weekly_challenges, ui.weekly_reset_time),
+        ];
+        for (label, challenges, reset_time) in sections {
+            self.draw_text_sharp(label, content_x + 4.0, y + 17.0, 16.0, TEXT_TITLE);
+            let reset_text = format!("Resets in {}", format_remaining(reset_time - now));
+            let reset_w = self.measure_text_sharp(&reset_text, 16.0).width;
+            self.draw_text_sharp(&reset_text, content_x + content_w - reset_w - 4.0, y + 17.0, 16.0, TEXT_DIM);
+            y += SECTION_HEIGHT;
+
+            if challenges.is_empty() {
+                self.draw_text_sharp("No active challenges", content_x + 12.0, y + 24.0, 16.0, TEXT_DIM);
+                y += ROW_HEIGHT;
+            }
+            for challenge in challenges.iter() {
+                self.draw_challenge_row(challenge, content_x, y, content_w);
+                y += ROW_HEIGHT;
+            }
+        }
+
+        // ===== FOOTER SECTION =====
+        let footer_x = panel_x + FRAME_THICKNESS;
+        let footer_y = panel_y + panel_height - FRAME_THICKNESS - FOOTER_HEIGHT;
+        let footer_w = panel_width - FRAME_THICKNESS * 2.0;
+
+        draw_rectangle(footer_x, footer_y, footer_w, FOOTER_HEIGHT, FOOTER_BG);
+        draw_line(footer_x + 10.0, footer_y, footer_x + footer_w - 10.0, footer_y, 1.0, HEADER_BORDER);
+        self.draw_text_sharp("[J] Close", footer_x + 10.0, footer_y + 20.0, 16.0, TEXT_DIM);
+
+        let streak_text = format!("Streak: {} daily / {} weekly", ui.daily_streak, ui.weekly_streak);
+        let streak_width = self.measure_text_sharp(&streak_text, 16.0).width;
+        self.draw_text_sharp(&streak_text, footer_x + footer_w - streak_width - 10.0, footer_y + 20.0, 16.0, FRAME_MID);
+    }
+
+    fn draw_challenge_row(&self, challenge: &ChallengeEntry, x: f32, y: f32, w: f32) {
+        let row_bg = if challenge.completed { SLOT_BG_FILLED } else { SLOT_BG_EMPTY };
+        draw_rectangle(x, y, w, ROW_HEIGHT - 4.0, SLOT_BORDER);
+        draw_rectangle(x + 1.0, y + 1.0, w - 2.0, ROW_HEIGHT - 6.0, row_bg);
+
+        let name_color = if challenge.completed { TEXT_GOLD } else { TEXT_NORMAL };
+        self.draw_text_sharp(&challenge.name, x + 8.0, y + 15.0, 16.0, name_color);
+        self.draw_text_sharp(&challenge.description, x + 8.0, y + 31.0, 16.0, TEXT_DIM);
+
+        let right_x = x + w - 8.0;
+        if challenge.completed {
+            let done_w = self.measure_text_sharp("Complete", 16.0).width;
+            self.draw_text_sharp("Complete", right_x - done_w, y + 15.0, 16.0, TEXT_GOLD);
+        } else {
+            let progress_text = format!("{} / {}", challenge.progress, challenge.target);
+            let text_w = self.measure_text_sharp(&progress_text, 16.0).width;
+            self.draw_text_sharp(&progress_text, right_x - text_w, y + 15.0, 16.0, TEXT_DIM);
+        }
+
+        // Progress bar with the gold reward beside it
+        let bar_w = 90.0;
+        let bar_x = right_x - bar_w;
+        let bar_y = y + 24.0;
+        let fraction = if challenge.target > 0 {
+            (challenge.progress as f32 / challenge.target as f32).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        draw_rectangle(bar_x, bar_y, bar_w, BAR_HEIGHT, HEALTHBAR_BG_OUTER);
+        draw_rectangle(bar_x + 1.0, bar_y + 1.0, (bar_w - 2.0) * fraction, BAR_HEIGHT - 2.0, FRAME_ACCENT);
+
+        if challenge.reward_gold > 0 {
+            let reward_text = format!("{}g", challenge.reward_gold);
+            let reward_w = self.measure_text_sharp(&reward_text, 16.0).width;
+            self.draw_text_sharp(&reward_text, bar_x - reward_w - 8.0, y + 15.0, 16.0, TEXT_GOLD);
+        }
+    }
+}
@@ -8,7 +8,7 @@ use super::super::Renderer;
 use super::common::*;
 
 impl Renderer {
-    /// Render the gold drop amount dialog
+    /// Render the gold drop amount dialog (also used to set gold in a trade)
     pub(crate) fn render_gold_drop_dialog(&self, dialog: &GoldDropDialog, player_gold: i32, hovered: &Option<UiElementId>, layout: &mut UiLayout) {
         let (sw, sh) = virtual_screen_size();
 
@@ -25,7 +25,7 @@ impl Renderer {
         self.draw_corner_accents(box_x, box_y, box_width, box_height);
 
         // ===== TITLE TAB =====
-        let title_text = "DROP GOLD";
+        let title_text = if dialog.for_trade { "OFFER GOLD" } else { "DROP GOLD" };
         let title_width = self.measure_text_sharp(title_text, 16.0).width + 28.0;
         let title_x = box_x + (box_width - title_width) / 2.0;
         let title_y = box_y - 8.0;
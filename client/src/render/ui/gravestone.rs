@@ -0,0 +1,79 @@
+//! Gravestone recovery window rendering
+
+use macroquad::prelude::*;
+use crate::game::{GameState, GravestoneWindow};
+use crate::ui::{UiElementId, UiLayout};
+use crate::util::virtual_screen_size;
+use super::super::Renderer;
+use super::challenges::format_remaining;
+use super::common::*;
+
+const GRAVE_SLOT_SIZE: f32 = 40.0;
+const GRAVE_SLOT_SPACING: f32 = 4.0;
+const GRAVE_COLUMNS: usize = 5;
+
+impl Renderer {
+    /// Render the items left in the local player's gravestone with a recover button
+    pub(crate) fn render_gravestone_window(&self, grave: &GravestoneWindow, state: &GameState, hovered: &Option<UiElementId>, layout: &mut UiLayout) {
+        let (sw, sh) = virtual_screen_size();
+
+        let rows = grave.items.len().div_ceil(GRAVE_COLUMNS).max(1);
+        let grid_width = GRAVE_COLUMNS as f32 * (GRAVE_SLOT_SIZE + GRAVE_SLOT_SPACING) - GRAVE_SLOT_SPACING;
+        let grid_height = rows as f32 * (GRAVE_SLOT_SIZE + GRAVE_SLOT_SPACING) - GRAVE_SLOT_SPACING;
+        let padding = FRAME_THICKNESS + 12.0;
+
+        let box_width = grid_width + padding * 2.0;
+        let box_height = padding * 2.0 + 16.0 + grid_height + 32.0 + 28.0;
+        let box_x = (sw - box_width) / 2.0;
+        let box_y = (sh - box_height) / 2.0;
+
+        self.draw_panel_frame(box_x, box_y, box_width, box_height);
+        self.draw_corner_accents(box_x, box_y, box_width, box_height);
+
+        // ===== TITLE TAB =====
+        let title_text = "GRAVESTONE";
+        let title_width = self.measure_text_sharp(title_text, 16.0).width + 28.0;
+        let title_x = box_x + (box_width - title_width) / 2.0;
+        let title_y = box_y - 8.0;
+        let title_h = 26.0;
+
+        draw_rectangle(title_x - 1.0, title_y - 1.0, title_width + 2.0, title_h + 2.0, FRAME_OUTER);
+        draw_rectangle(title_x, title_y, title_width, title_h, HEADER_BG);
+        draw_line(title_x + 2.0, title_y + 2.0, title_x + title_width - 2.0, title_y + 2.0, 1.0, FRAME_INNER);
+        self.draw_text_sharp(title_text, title_x + 14.0, title_y + 18.0, 16.0, TEXT_TITLE);
+
+        // ===== ITEMS =====
+        let grid_x = box_x + padding;
+        let grid_y = box_y + padding + 16.0;
+
+        for (i, (item_id, quantity)) in grave.items.iter().enumerate() {
+            let col = i % GRAVE_COLUMNS;
+            let row = i / GRAVE_COLUMNS;
+            let slot_x = grid_x + col as f32 * (GRAVE_SLOT_SIZE + GRAVE_SLOT_SPACING);
+            let slot_y = grid_y + row as f32 * (GRAVE_SLOT_SIZE + GRAVE_SLOT_SPACING);
+
+            draw_rectangle(slot_x, slot_y, GRAVE_SLOT_SIZE, GRAVE_SLOT_SIZE, SLOT_BORDER);
+            draw_rectangle(slot_x + 1.0, slot_y + 1.0, GRAVE_SLOT_SIZE - 2.0, GRAVE_SLOT_SIZE - 2.0, SLOT_BG_FILLED);
+            self.draw_item_icon(item_id, slot_x, slot_y, GRAVE_SLOT_SIZE, GRAVE_SLOT_SIZE, state, false);
+            if *quantity > 1 {
+                let qty_text = quantity.to_string();
+                let qty_width = self.measure_text_sharp(&qty_text, 16.0).width;
+                let qty_x = slot_x + GRAVE_SLOT_SIZE - qty_width - 3.0;
+                let qty_y = slot_y + GRAVE_SLOT_SIZE - 4.0;
+                self.draw_text_sharp(&qty_text, qty_x + 1.0, qty_y + 1.0, 16.0, BLACK);
+                self.draw_text_sharp(&qty_text, qty_x, qty_y, 16.0, WHITE);
+            }
+        }
+
+        // ===== TIME LEFT =====
+        let time_text = format!("Crumbles in {}", format_remaining(grave.expires_at - get_time()));
+        let time_width = self.measure_text_sharp(&time_text, 16.0).width;
+        self.draw_text_sharp(&time_text, box_x + (box_width - time_width) / 2.0, grid_y + grid_height + 22.0, 16.0, TEXT_DIM);
+
+        // ===== BUTTONS =====
+        let button_y = grid_y + grid_height + 32.0;
+        let button_width = (grid_width - 12.0) / 2.0;
+        self.draw_text_button("Recover All", Rect::new(grid_x, button_y, button_width, 28.0), UiElementId::GravestoneRecoverButton, false, hovered, layout);
+        self.draw_text_button("Close", Rect::new(grid_x + button_width + 12.0, button_y, button_width, 28.0), UiElementId::GravestoneCloseButton, true, hovered, layout);
+    }
+}
@@ -1,5 +1,9 @@
 //! UI rendering components split from the main renderer
 
+pub mod achievements;
+pub mod boss_bar;
+pub mod bounty_board;
+pub mod challenges;
 pub mod common;
 pub mod inventory;
 pub mod character;
@@ -13,6 +17,12 @@ pub mod menu;
 pub mod shop;
 pub mod bottom_bar;
 pub mod skills;
+pub mod status_effects;
 pub mod gold_drop_dialog;
+pub mod trade;
+pub mod storage;
+pub mod gravestone;
+pub mod party;
+pub mod reputation;
 pub mod area_banner;
 pub mod xp_globes;
@@ -0,0 +1,128 @@
+//! Party frames and party invite prompt rendering
+
+use macroquad::prelude::*;
+use crate::game::{GameState, PartyInvitePrompt};
+use crate::ui::{UiElementId, UiLayout};
+use crate::util::virtual_screen_size;
+use super::super::Renderer;
+use super::common::*;
+
+const FRAME_WIDTH: f32 = 170.0;
+const FRAME_HEIGHT: f32 = 36.0;
+const FRAME_SPACING: f32 = 4.0;
+const SMALL_BUTTON_SIZE: f32 = 16.0;
+
+impl Renderer {
+    /// Render party member frames (name, level and HP) on the left side of the screen
+    pub(crate) fn render_party_frames(&self, state: &GameState, hovered: &Option<UiElementId>, layout: &mut UiLayout) {
+        let Some(party) = &state.ui_state.party else {
+            return;
+        };
+
+        let local_id = state.local_player_id.as_deref().unwrap_or("");
+        let is_leader = party.leader_id == local_id;
+
+        let x = 10.0;
+        let mut y = if state.debug_mode { 640.0 } else { 200.0 };
+
+        // Header with loot rule and leave button
+        self.draw_text_sharp("PARTY", x, y, 16.0, Color::from_rgba(255, 220, 100, 255));
+        let rule_text = format!("loot: {}", party.loot_rule);
+        self.draw_text_sharp(&rule_text, x + 56.0, y, 16.0, TEXT_DIM);
+        self.draw_text_button(
+            "Leave",
+            Rect::new(x + FRAME_WIDTH - 48.0, y - 14.0, 48.0, 20.0),
+            UiElementId::PartyLeaveButton,
+            true,
+            hovered,
+            layout,
+        );
+        y += 10.0;
+
+        for (idx, member) in party.members.iter().enumerate() {
+            if member.id == local_id {
+                continue;
+            }
+
+            draw_rectangle(x, y, FRAME_WIDTH, FRAME_HEIGHT, FRAME_OUTER);
+            draw_rectangle(x + 1.0, y + 1.0, FRAME_WIDTH - 2.0, FRAME_HEIGHT - 2.0, PANEL_BG_DARK);
+
+            // Name line (leader marked with a star)
+            let name_color = if member.online { TEXT_NORMAL } else { TEXT_DIM };
+            let name = if member.id == party.leader_id {
+                format!("* {}", member.name)
+            } else {
+                member.name.clone()
+            };
+            self.draw_text_sharp(&name, x + 6.0, y + 14.0, 16.0, name_color);
+
+            if member.online {
+                let level_text = format!("Lv{}", member.level);
+                let level_width = self.measure_text_sharp(&level_text, 16.0).width;
+                let level_right = if is_leader { x + FRAME_WIDTH - SMALL_BUTTON_SIZE * 2.0 - 12.0 } else { x + FRAME_WIDTH - 6.0 };
+                self.draw_text_sharp(&level_text, level_right - level_width, y + 14.0, 16.0, TEXT_DIM);
+
+                // HP bar
+                let bar_x = x + 6.0;
+                let bar_y = y + 20.0;
+                let bar_w = FRAME_WIDTH - 12.0;
+                let bar_h = 10.0;
+                let ratio = if member.max_hp > 0 {
+                    (member.hp as f32 / member.max_hp as f32).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                let fill = if ratio > 0.5 {
+                    HEALTH_GREEN_MID
+                } else if ratio > 0.25 {
+                    HEALTH_YELLOW_MID
+                } else {
+                    HEALTH_RED_MID
+                };
+                draw_rectangle(bar_x, bar_y, bar_w, bar_h, HEALTHBAR_BG_OUTER);
+                draw_rectangle(bar_x + 1.0, bar_y + 1.0, (bar_w - 2.0) * ratio, bar_h - 2.0, fill);
+            } else {
+                self.draw_text_sharp("Offline", x + 6.0, y + 30.0, 16.0, TEXT_DIM);
+            }
+
+            // Leader controls: promote and kick
+            if is_leader {
+                let button_y = y + 3.0;
+                let promote_x = x + FRAME_WIDTH - SMALL_BUTTON_SIZE * 2.0 - 6.0;
+                let kick_x = x + FRAME_WIDTH - SMALL_BUTTON_SIZE - 3.0;
+                self.draw_text_button("L", Rect::new(promote_x, button_y, SMALL_BUTTON_SIZE, SMALL_BUTTON_SIZE), UiElementId::PartyPromoteButton(idx), false, hovered, layout);
+                self.draw_text_button("X", Rect::new(kick_x, button_y, SMALL_BUTTON_SIZE, SMALL_BUTTON_SIZE), UiElementId::PartyKickButton(idx), true, hovered, layout);
+            }
+
+            y += FRAME_HEIGHT + FRAME_SPACING;
+        }
+    }
+
+    /// Render the incoming party invite prompt (top-center)
+    pub(crate) fn render_party_invite_prompt(&self, invite: &PartyInvitePrompt, hovered: &Option<UiElementId>, layout: &mut UiLayout) {
+        // Invites expire on the server after 60 seconds
+        let remaining = 60.0 - (get_time() - invite.time);
+        if remaining <= 0.0 {
+            return;
+        }
+
+        let (sw, _) = virtual_screen_size();
+        let box_width = 300.0;
+        let box_height = 92.0;
+        let box_x = (sw - box_width) / 2.0;
+        let box_y = 180.0;
+
+        self.draw_panel_frame(box_x, box_y, box_width, box_height);
+        self.draw_corner_accents(box_x, box_y, box_width, box_height);
+
+        let content_x = box_x + FRAME_THICKNESS + 12.0;
+        let content_width = box_width - FRAME_THICKNESS * 2.0 - 24.0;
+        let text = format!("{} invites you to a party ({}s)", invite.inviter_name, remaining.ceil() as i32);
+        self.draw_text_sharp(&text, content_x, box_y + FRAME_THICKNESS + 24.0, 16.0, TEXT_NORMAL);
+
+        let button_y = box_y + box_height - FRAME_THICKNESS - 12.0 - 28.0;
+        let button_width = (content_width - 12.0) / 2.0;
+        self.draw_text_button("Join", Rect::new(content_x, button_y, button_width, 28.0), UiElementId::PartyInviteAccept, false, hovered, layout);
+        self.draw_text_button("Decline", Rect::new(content_x + button_width + 12.0, button_y, button_width, 28.0), UiElementId::PartyInviteDecline, true, hovered, layout);
+    }
+}
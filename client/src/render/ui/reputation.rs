@@ -0,0 +1,94 @@
+//! Faction reputation panel rendering
+
+use macroquad::prelude::*;
+use crate::game::GameState;
+use crate::util::virtual_screen_size;
+use super::super::Renderer;
+use super::common::*;
+
+const ENTRY_HEIGHT: f32 = 58.0;
+const BAR_HEIGHT: f32 = 10.0;
+
+/// Bar color for a reputation level (relative to Neutral)
+fn level_color(level: i32) -> Color {
+    match level {
+        i32::MIN..=-2 => Color::from_rgba(200, 60, 50, 255),
+        -1 => Color::from_rgba(220, 130, 50, 255),
+        0 => Color::from_rgba(210, 190, 70, 255),
+        _ => Color::from_rgba(80, 190, 90, 255),
+    }
+}
+
+impl Renderer {
+    /// Render the reputation panel listing standing with every faction
+    pub(crate) fn render_reputation_panel(&self, state: &GameState) {
+        let (sw, sh) = virtual_screen_size();
+
+        let factions = &state.ui_state.reputation;
+        let panel_width = 360.0;
+        let content_rows = factions.len().max(1) as f32;
+        let panel_height = FRAME_THICKNESS * 2.0 + HEADER_HEIGHT + FOOTER_HEIGHT + 16.0 + content_rows * ENTRY_HEIGHT;
+        let panel_x = (sw - panel_width) / 2.0;
+        let panel_y = (sh - panel_height) / 2.0;
+
+        self.draw_panel_frame(panel_x, panel_y, panel_width, panel_height);
+        self.draw_corner_accents(panel_x, panel_y, panel_width, panel_height);
+
+        // ===== HEADER SECTION =====
+        let header_x = panel_x + FRAME_THICKNESS;
+        let header_y = panel_y + FRAME_THICKNESS;
+        let header_w = panel_width - FRAME_THICKNESS * 2.0;
+
+        draw_rectangle(header_x, header_y, header_w, HEADER_HEIGHT, HEADER_BG);
+        draw_line(header_x + 10.0, header_y + HEADER_HEIGHT, header_x + header_w - 10.0, header_y + HEADER_HEIGHT, 2.0, HEADER_BORDER);
+        self.draw_text_sharp("REPUTATION", header_x + 12.0, header_y + 26.0, 16.0, TEXT_TITLE);
+
+        // ===== CONTENT AREA =====
+        let content_x = panel_x + FRAME_THICKNESS + 8.0;
+        let content_w = panel_width - FRAME_THICKNESS * 2.0 - 16.0;
+        let mut y = panel_y + FRAME_THICKNESS + HEADER_HEIGHT + 8.0;
+
+        if factions.is_empty() {
+            self.draw_text_sharp("No known factions", content_x + 12.0, y + 24.0, 16.0, TEXT_DIM);
+        }
+
+        for faction in factions {
+            draw_rectangle(content_x, y, content_w, ENTRY_HEIGHT - 4.0, SLOT_BORDER);
+            draw_rectangle(content_x + 1.0, y + 1.0, content_w - 2.0, ENTRY_HEIGHT - 6.0, SLOT_BG_EMPTY);
+
+            // Name and level
+            self.draw_text_sharp(&faction.name, content_x + 8.0, y + 16.0, 16.0, TEXT_NORMAL);
+            let color = level_color(faction.level);
+            let level_width = self.measure_text_sharp(&faction.level_name, 16.0).width;
+            self.draw_text_sharp(&faction.level_name, content_x + content_w - level_width - 8.0, y + 16.0, 16.0, color);
+
+            // Progress through the current level
+            let bar_x = content_x + 8.0;
+            let bar_y = y + 24.0;
+            let bar_w = content_w - 16.0;
+            let (progress, progress_text) = match faction.level_max {
+                Some(max) if max > faction.level_min => {
+                    let span = (max - faction.level_min) as f32;
+                    let into = (faction.standing - faction.level_min) as f32;
+                    ((into / span).clamp(0.0, 1.0), format!("{} / {}", faction.standing - faction.level_min, max - faction.level_min))
+                }
+                _ => (1.0, faction.standing.to_string()),
+            };
+            draw_rectangle(bar_x, bar_y, bar_w, BAR_HEIGHT, HEALTHBAR_BG_OUTER);
+            draw_rectangle(bar_x + 1.0, bar_y + 1.0, (bar_w - 2.0) * progress, BAR_HEIGHT - 2.0, color);
+
+            self.draw_text_sharp(&progress_text, bar_x, bar_y + BAR_HEIGHT + 14.0, 16.0, TEXT_DIM);
+
+            y += ENTRY_HEIGHT;
+        }
+
+        // ===== FOOTER SECTION =====
+        let footer_x = panel_x + FRAME_THICKNESS;
+        let footer_y = panel_y + panel_height - FRAME_THICKNESS - FOOTER_HEIGHT;
+        let footer_w = panel_width - FRAME_THICKNESS * 2.0;
+
+        draw_rectangle(footer_x, footer_y, footer_w, FOOTER_HEIGHT, FOOTER_BG);
+        draw_line(footer_x + 10.0, footer_y, footer_x + footer_w - 10.0, footer_y, 1.0, HEADER_BORDER);
+        self.draw_text_sharp("[R] Close", footer_x + 10.0, footer_y + 20.0, 16.0, TEXT_DIM);
+    }
+}
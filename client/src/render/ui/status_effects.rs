@@ -0,0 +1,59 @@
+//! Status effect icons (poison, stun, slow, bleed) for the HUD and above players
+
+use macroquad::prelude::*;
+use crate::game::Player;
+use super::super::Renderer;
+use super::common::*;
+
+const ICON_SPACING: f32 = 3.0;
+
+/// Badge color and letter for a status effect kind
+fn effect_style(kind: &str) -> (Color, &'static str) {
+    match kind {
+        "poison" => (Color::from_rgba(70, 160, 60, 255), "P"),
+        "stun" => (Color::from_rgba(210, 180, 40, 255), "S"),
+        "slow" => (Color::from_rgba(70, 110, 200, 255), "W"),
+        "bleed" => (Color::from_rgba(180, 40, 40, 255), "B"),
+        _ => (Color::from_rgba(120, 120, 120, 255), "?"),
+    }
+}
+
+/// Total width of a row of status icons
+pub fn status_icons_width(count: usize, size: f32) -> f32 {
+    if count == 0 {
+        return 0.0;
+    }
+    count as f32 * (size + ICON_SPACING) - ICON_SPACING
+}
+
+impl Renderer {
+    /// Draw a row of status effect badges starting at (x, y).
+    /// With `show_time`, each badge shows the seconds left underneath.
+    pub(crate) fn render_status_icons(&self, player: &Player, x: f32, y: f32, size: f32, show_time: bool) {
+        let now = get_time();
+        for (i, effect) in player.active_status_effects().enumerate() {
+            let icon_x = (x + i as f32 * (size + ICON_SPACING)).floor();
+            let (color, letter) = effect_style(&effect.kind);
+
+            draw_rectangle(icon_x, y, size, size, SLOT_INNER_SHADOW);
+            draw_rectangle(icon_x + 1.0, y + 1.0, size - 2.0, size - 2.0, color);
+            draw_rectangle(icon_x + 1.0, y + 1.0, size - 2.0, (size - 2.0) / 2.0, Color::new(1.0, 1.0, 1.0, 0.2));
+
+            let letter_w = self.measure_text_sharp(letter, 16.0).width;
+            self.draw_text_sharp(letter, (icon_x + (size - letter_w) / 2.0).floor(), (y + size / 2.0 + 5.0).floor(), 16.0, WHITE);
+
+            if effect.stacks > 1 {
+                let stacks = effect.stacks.to_string();
+                let stacks_w = self.measure_text_sharp(&stacks, 16.0).width;
+                self.draw_text_sharp(&stacks, icon_x + size - stacks_w + 1.0, y + size + 1.0, 16.0, BLACK);
+                self.draw_text_sharp(&stacks, icon_x + size - stacks_w, y + size, 16.0, WHITE);
+            }
+
+            if show_time {
+                let secs = format!("{}s", (effect.expires_at - now).ceil().max(0.0) as i32);
+                let secs_w = self.measure_text_sharp(&secs, 16.0).width;
+                self.draw_text_sharp(&secs, (icon_x + (size - secs_w) / 2.0).floor(), y + size + 14.0, 16.0, TEXT_DIM);
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! House storage chest window rendering
+
+use macroquad::prelude::*;
+use crate::game::{GameState, StorageWindow};
+use crate::ui::{UiElementId, UiLayout};
+use crate::util::virtual_screen_size;
+use super::super::Renderer;
+use super::common::*;
+
+const STORAGE_SLOT_SIZE: f32 = 40.0;
+const STORAGE_SLOT_SPACING: f32 = 4.0;
+const STORAGE_COLUMNS: usize = 5;
+
+impl Renderer {
+    /// Render an open storage chest next to the inventory
+    pub(crate) fn render_storage_window(&self, storage: &StorageWindow, state: &GameState, hovered: &Option<UiElementId>, layout: &mut UiLayout) {
+        let (sw, sh) = virtual_screen_size();
+
+        let rows = storage.slots.len().div_ceil(STORAGE_COLUMNS).max(1);
+        let grid_width = STORAGE_COLUMNS as f32 * (STORAGE_SLOT_SIZE + STORAGE_SLOT_SPACING) - STORAGE_SLOT_SPACING;
+        let grid_height = rows as f32 * (STORAGE_SLOT_SIZE + STORAGE_SLOT_SPACING) - STORAGE_SLOT_SPACING;
+        let padding = FRAME_THICKNESS + 12.0;
+
+        let box_width = grid_width + padding * 2.0;
+        let box_height = padding * 2.0 + 16.0 + grid_height + 28.0;
+        let box_x = (sw - box_width) / 2.0 - INV_WIDTH / 2.0;
+        let box_y = (sh - box_height) / 2.0;
+
+        self.draw_panel_frame(box_x, box_y, box_width, box_height);
+        self.draw_corner_accents(box_x, box_y, box_width, box_height);
+
+        // ===== TITLE TAB =====
+        let title_text = storage.name.to_uppercase();
+        let title_width = self.measure_text_sharp(&title_text, 16.0).width + 28.0;
+        let title_x = box_x + (box_width - title_width) / 2.0;
+        let title_y = box_y - 8.0;
+        let title_h = 26.0;
+
+        draw_rectangle(title_x - 1.0, title_y - 1.0, title_width + 2.0, title_h + 2.0, FRAME_OUTER);
+        draw_rectangle(title_x, title_y, title_width, title_h, HEADER_BG);
+        draw_line(title_x + 2.0, title_y + 2.0, title_x + title_width - 2.0, title_y + 2.0, 1.0, FRAME_INNER);
+        self.draw_text_sharp(&title_text, title_x + 14.0, title_y + 18.0, 16.0, TEXT_TITLE);
+
+        // ===== SLOTS =====
+        let grid_x = box_x + padding;
+        let grid_y = box_y + padding + 16.0;
+
+        for (i, slot) in storage.slots.iter().enumerate() {
+            let col = i % STORAGE_COLUMNS;
+            let row = i / STORAGE_COLUMNS;
+            let slot_x = grid_x + col as f32 * (STORAGE_SLOT_SIZE + STORAGE_SLOT_SPACING);
+            let slot_y = grid_y + row as f32 * (STORAGE_SLOT_SIZE + STORAGE_SLOT_SPACING);
+
+            let is_hovered = slot.is_some() && matches!(hovered, Some(UiElementId::StorageSlot(idx)) if *idx == i);
+            let (bg, border) = match (slot.is_some(), is_hovered) {
+                (_, true) => (SLOT_HOVER_BG, SLOT_HOVER_BORDER),
+                (true, false) => (SLOT_BG_FILLED, SLOT_BORDER),
+                (false, false) => (SLOT_BG_EMPTY, SLOT_BORDER),
+            };
+            draw_rectangle(slot_x, slot_y, STORAGE_SLOT_SIZE, STORAGE_SLOT_SIZE, border);
+            draw_rectangle(slot_x + 1.0, slot_y + 1.0, STORAGE_SLOT_SIZE - 2.0, STORAGE_SLOT_SIZE - 2.0, bg);
+
+            if let Some((item_id, quantity)) = slot {
+                layout.add(UiElementId::StorageSlot(i), Rect::new(slot_x, slot_y, STORAGE_SLOT_SIZE, STORAGE_SLOT_SIZE));
+                self.draw_item_icon(item_id, slot_x, slot_y, STORAGE_SLOT_SIZE, STORAGE_SLOT_SIZE, state, false);
+                if *quantity > 1 {
+                    let qty_text = quantity.to_string();
+                    let qty_width = self.measure_text_sharp(&qty_text, 16.0).width;
+                    let qty_x = slot_x + STORAGE_SLOT_SIZE - qty_width - 3.0;
+                    let qty_y = slot_y + STORAGE_SLOT_SIZE - 4.0;
+                    self.draw_text_sharp(&qty_text, qty_x + 1.0, qty_y + 1.0, 16.0, BLACK);
+                    self.draw_text_sharp(&qty_text, qty_x, qty_y, 16.0, WHITE);
+                }
+            }
+        }
+
+        // ===== HINT =====
+        let hint = "Click items to store or take them.";
+        let hint_width = self.measure_text_sharp(hint, 16.0).width;
+        self.draw_text_sharp(hint, box_x + (box_width - hint_width) / 2.0, grid_y + grid_height + 22.0, 16.0, TEXT_DIM);
+    }
+}
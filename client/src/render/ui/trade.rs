@@ -0,0 +1,198 @@
+//! Player trade window and trade request prompt rendering
+
+use macroquad::prelude::*;
+use crate::game::{GameState, TradeItem, TradeRequestPrompt, TradeWindow};
+use crate::ui::{UiElementId, UiLayout};
+use crate::util::virtual_screen_size;
+use super::super::Renderer;
+use super::common::*;
+
+const TRADE_SLOT_SIZE: f32 = 40.0;
+const TRADE_SLOT_SPACING: f32 = 4.0;
+const TRADE_COLUMNS: usize = 4;
+const TRADE_ROWS: usize = 3;
+
+impl Renderer {
+    /// Render the two-sided trade window (own offer on the left, partner's on the right)
+    pub(crate) fn render_trade_window(&self, trade: &TradeWindow, state: &GameState, hovered: &Option<UiElementId>, layout: &mut UiLayout) {
+        let (sw, sh) = virtual_screen_size();
+
+        let grid_width = TRADE_COLUMNS as f32 * (TRADE_SLOT_SIZE + TRADE_SLOT_SPACING) - TRADE_SLOT_SPACING;
+        let grid_height = TRADE_ROWS as f32 * (TRADE_SLOT_SIZE + TRADE_SLOT_SPACING) - TRADE_SLOT_SPACING;
+        let column_gap = 24.0;
+        let padding = FRAME_THICKNESS + 12.0;
+
+        let box_width = grid_width * 2.0 + column_gap + padding * 2.0;
+        let box_height = padding * 2.0 + 24.0 + grid_height + 12.0 + 24.0 + 20.0 + 12.0 + 28.0;
+        let box_x = (sw - box_width) / 2.0 - INV_WIDTH / 2.0;
+        let box_y = (sh - box_height) / 2.0;
+
+        self.draw_panel_frame(box_x, box_y, box_width, box_height);
+        self.draw_corner_accents(box_x, box_y, box_width, box_height);
+
+        // ===== TITLE TAB =====
+        let title_text = format!("TRADE - {}", trade.partner_name);
+        let title_width = self.measure_text_sharp(&title_text, 16.0).width + 28.0;
+        let title_x = box_x + (box_width - title_width) / 2.0;
+        let title_y = box_y - 8.0;
+        let title_h = 26.0;
+
+        draw_rectangle(title_x - 1.0, title_y - 1.0, title_width + 2.0, title_h + 2.0, FRAME_OUTER);
+        draw_rectangle(title_x, title_y, title_width, title_h, HEADER_BG);
+        draw_line(title_x + 2.0, title_y + 2.0, title_x + title_width - 2.0, title_y + 2.0, 1.0, FRAME_INNER);
+        self.draw_text_sharp(&title_text, title_x + 14.0, title_y + 18.0, 16.0, TEXT_TITLE);
+
+        // ===== OFFER COLUMNS =====
+        let left_x = box_x + padding;
+        let right_x = left_x + grid_width + column_gap;
+        let header_y = box_y + padding + 16.0;
+
+        let my_status = Self::trade_status_text(trade.my_accepted, trade.my_confirmed);
+        let their_status = Self::trade_status_text(trade.their_accepted, trade.their_confirmed);
+        self.draw_text_sharp("Your offer", left_x, header_y, 16.0, TEXT_TITLE);
+        self.draw_text_sharp(my_status.0, left_x + grid_width - self.measure_text_sharp(my_status.0, 16.0).width, header_y, 16.0, my_status.1);
+        self.draw_text_sharp(&trade.partner_name, right_x, header_y, 16.0, TEXT_TITLE);
+        self.draw_text_sharp(their_status.0, right_x + grid_width - self.measure_text_sharp(their_status.0, 16.0).width, header_y, 16.0, their_status.1);
+
+        let grid_y = header_y + 8.0;
+        self.draw_trade_offer_grid(&trade.my_items, left_x, grid_y, true, state, hovered, layout);
+        self.draw_trade_offer_grid(&trade.their_items, right_x, grid_y, false, state, hovered, layout);
+
+        // Divider between the two offers
+        let divider_x = left_x + grid_width + column_gap / 2.0;
+        draw_line(divider_x, grid_y, divider_x, grid_y + grid_height, 1.0, FRAME_MID);
+
+        // ===== GOLD =====
+        let gold_y = grid_y + grid_height + 12.0;
+        let gold_bounds = Rect::new(left_x, gold_y, grid_width, 24.0);
+        layout.add(UiElementId::TradeGoldButton, gold_bounds);
+        let gold_hovered = matches!(hovered, Some(UiElementId::TradeGoldButton));
+        draw_rectangle(gold_bounds.x, gold_bounds.y, gold_bounds.w, gold_bounds.h, if gold_hovered { SLOT_HOVER_BORDER } else { SLOT_BORDER });
+        draw_rectangle(gold_bounds.x + 1.0, gold_bounds.y + 1.0, gold_bounds.w - 2.0, gold_bounds.h - 2.0, if gold_hovered { SLOT_HOVER_BG } else { SLOT_BG_EMPTY });
+        self.draw_text_sharp(&format!("{}g", trade.my_gold), left_x + 8.0, gold_y + 17.0, 16.0, TEXT_GOLD);
+
+        draw_rectangle(right_x, gold_y, grid_width, 24.0, SLOT_BORDER);
+        draw_rectangle(right_x + 1.0, gold_y + 1.0, grid_width - 2.0, 22.0, SLOT_BG_EMPTY);
+        self.draw_text_sharp(&format!("{}g", trade.their_gold), right_x + 8.0, gold_y + 17.0, 16.0, TEXT_GOLD);
+
+        // ===== HINT =====
+        let hint_y = gold_y + 24.0 + 20.0;
+        let hint = if trade.is_confirming() {
+            "Review both offers carefully, then confirm."
+        } else {
+            "Click inventory items to offer them."
+        };
+        let hint_width = self.measure_text_sharp(hint, 16.0).width;
+        self.draw_text_sharp(hint, box_x + (box_width - hint_width) / 2.0, hint_y, 16.0, TEXT_DIM);
+
+        // ===== BUTTONS =====
+        let button_y = hint_y + 12.0;
+        let button_height = 28.0;
+        let accept_label = match (trade.is_confirming(), trade.my_accepted, trade.my_confirmed) {
+            (true, _, false) => "Confirm",
+            (true, _, true) => "Waiting...",
+            (false, false, _) => "Accept",
+            (false, true, _) => "Waiting...",
+        };
+        self.draw_text_button(accept_label, Rect::new(left_x, button_y, grid_width, button_height), UiElementId::TradeAcceptButton, false, hovered, layout);
+        self.draw_text_button("Cancel", Rect::new(right_x, button_y, grid_width, button_height), UiElementId::TradeCancelButton, true, hovered, layout);
+    }
+
+    /// Status label and color for one side of the trade
+    fn trade_status_text(accepted: bool, confirmed: bool) -> (&'static str, Color) {
+        if confirmed {
+            ("Confirmed", HEALTH_GREEN_LIGHT)
+        } else if accepted {
+            ("Accepted", HEALTH_YELLOW_LIGHT)
+        } else {
+            ("", TEXT_DIM)
+        }
+    }
+
+    /// Draw a grid of offered stacks. Own slots are clickable to remove them.
+    #[allow(clippy::too_many_arguments)]
+    fn draw_trade_offer_grid(&self, items: &[TradeItem], x: f32, y: f32, own: bool, state: &GameState, hovered: &Option<UiElementId>, layout: &mut UiLayout) {
+        for i in 0..(TRADE_COLUMNS * TRADE_ROWS) {
+            let col = i % TRADE_COLUMNS;
+            let row = i / TRADE_COLUMNS;
+            let slot_x = x + col as f32 * (TRADE_SLOT_SIZE + TRADE_SLOT_SPACING);
+            let slot_y = y + row as f32 * (TRADE_SLOT_SIZE + TRADE_SLOT_SPACING);
+            let item = items.get(i);
+
+            let is_hovered = own && item.is_some() && matches!(hovered, Some(UiElementId::TradeOfferSlot(idx)) if *idx == i);
+            let (bg, border) = match (item.is_some(), is_hovered) {
+                (_, true) => (SLOT_HOVER_BG, SLOT_HOVER_BORDER),
+                (true, false) => (SLOT_BG_FILLED, SLOT_BORDER),
+                (false, false) => (SLOT_BG_EMPTY, SLOT_BORDER),
+            };
+            draw_rectangle(slot_x, slot_y, TRADE_SLOT_SIZE, TRADE_SLOT_SIZE, border);
+            draw_rectangle(slot_x + 1.0, slot_y + 1.0, TRADE_SLOT_SIZE - 2.0, TRADE_SLOT_SIZE - 2.0, bg);
+
+            if let Some(item) = item {
+                if own {
+                    layout.add(UiElementId::TradeOfferSlot(i), Rect::new(slot_x, slot_y, TRADE_SLOT_SIZE, TRADE_SLOT_SIZE));
+                }
+                self.draw_item_icon(&item.item_id, slot_x, slot_y, TRADE_SLOT_SIZE, TRADE_SLOT_SIZE, state, false);
+                if item.quantity > 1 {
+                    let qty_text = item.quantity.to_string();
+                    let qty_width = self.measure_text_sharp(&qty_text, 16.0).width;
+                    let qty_x = slot_x + TRADE_SLOT_SIZE - qty_width - 3.0;
+                    let qty_y = slot_y + TRADE_SLOT_SIZE - 4.0;
+                    self.draw_text_sharp(&qty_text, qty_x + 1.0, qty_y + 1.0, 16.0, BLACK);
+                    self.draw_text_sharp(&qty_text, qty_x, qty_y, 16.0, WHITE);
+                }
+            }
+        }
+    }
+
+    /// Draw a themed button and register it for hit detection
+    pub(crate) fn draw_text_button(&self, label: &str, bounds: Rect, id: UiElementId, danger: bool, hovered: &Option<UiElementId>, layout: &mut UiLayout) {
+        let is_hovered = hovered.as_ref() == Some(&id);
+        layout.add(id, bounds);
+
+        let (bg, border) = match (is_hovered, danger) {
+            (true, false) => (Color::new(0.235, 0.204, 0.141, 1.0), FRAME_ACCENT),
+            (true, true) => (Color::new(0.235, 0.141, 0.141, 1.0), Color::new(0.8, 0.4, 0.4, 1.0)),
+            (false, false) => (Color::new(0.157, 0.141, 0.110, 1.0), FRAME_MID),
+            (false, true) => (Color::new(0.157, 0.110, 0.110, 1.0), FRAME_MID),
+        };
+
+        draw_rectangle(bounds.x, bounds.y, bounds.w, bounds.h, border);
+        draw_rectangle(bounds.x + 1.0, bounds.y + 1.0, bounds.w - 2.0, bounds.h - 2.0, bg);
+        if is_hovered {
+            draw_line(bounds.x + 2.0, bounds.y + 2.0, bounds.x + bounds.w - 2.0, bounds.y + 2.0, 1.0, FRAME_INNER);
+        }
+
+        let text_color = if is_hovered { TEXT_TITLE } else { TEXT_NORMAL };
+        let text_width = self.measure_text_sharp(label, 16.0).width;
+        self.draw_text_sharp(label, bounds.x + (bounds.w - text_width) / 2.0, bounds.y + bounds.h / 2.0 + 5.0, 16.0, text_color);
+    }
+
+    /// Render the incoming trade request prompt (top-center)
+    pub(crate) fn render_trade_request_prompt(&self, prompt: &TradeRequestPrompt, hovered: &Option<UiElementId>, layout: &mut UiLayout) {
+        // Requests expire on the server after 30 seconds
+        let remaining = 30.0 - (get_time() - prompt.time);
+        if remaining <= 0.0 {
+            return;
+        }
+
+        let (sw, _) = virtual_screen_size();
+        let box_width = 300.0;
+        let box_height = 92.0;
+        let box_x = (sw - box_width) / 2.0;
+        let box_y = 80.0;
+
+        self.draw_panel_frame(box_x, box_y, box_width, box_height);
+        self.draw_corner_accents(box_x, box_y, box_width, box_height);
+
+        let content_x = box_x + FRAME_THICKNESS + 12.0;
+        let content_width = box_width - FRAME_THICKNESS * 2.0 - 24.0;
+        let text = format!("{} wants to trade ({}s)", prompt.requester_name, remaining.ceil() as i32);
+        self.draw_text_sharp(&text, content_x, box_y + FRAME_THICKNESS + 24.0, 16.0, TEXT_NORMAL);
+
+        let button_y = box_y + box_height - FRAME_THICKNESS - 12.0 - 28.0;
+        let button_width = (content_width - 12.0) / 2.0;
+        self.draw_text_button("Accept", Rect::new(content_x, button_y, button_width, 28.0), UiElementId::TradeRequestAccept, false, hovered, layout);
+        self.draw_text_button("Decline", Rect::new(content_x + button_width + 12.0, button_y, button_width, 28.0), UiElementId::TradeRequestDecline, true, hovered, layout);
+    }
+}
@@ -76,6 +76,35 @@ pub enum UiElementId {
     // Gold Drop Dialog
     GoldDropConfirm,
     GoldDropCancel,
+
+    // Trade Window
+    TradeOfferSlot(usize), // Index into own offer (click to remove)
+    TradeGoldButton,
+    TradeAcceptButton,
+    TradeCancelButton,
+
+    // Trade Request Prompt
+    TradeRequestAccept,
+    TradeRequestDecline,
+
+    // Party Frames
+    PartyLeaveButton,
+    PartyPromoteButton(usize), // Index into party members
+    PartyKickButton(usize),
+
+    // Party Invite Prompt
+    PartyInviteAccept,
+    PartyInviteDecline,
+
+    // Achievements Panel
+    AchievementTitleButton(usize), // Index into achievements
+
+    // House Storage
+    StorageSlot(usize), // Index into the chest (click to withdraw)
+
+    // Gravestone Recovery
+    GravestoneRecoverButton,
+    GravestoneCloseButton,
 }
 
 /// A single interactive UI element with its bounds
@@ -1037,7 +1037,7 @@ impl Screen for CharacterSelectScreen {
                 if point_in_rect(mx, my, list_x, y, list_w, item_height - 5.0) {
                     // Ensure click is within the visible list area
                     if my >= list_y && my <= list_y + list_visible_height {
-                        if self.selected_index == i {
+                        if self.selected_index == i && !self.characters[i].perished {
                             let character = &self.characters[self.selected_index];
                             return ScreenState::StartGame {
                                 session: self.session.clone(),
@@ -1057,7 +1057,7 @@ impl Screen for CharacterSelectScreen {
         if clicked {
             // Play button
             if point_in_rect(mx, my, list_x, inst_y - 10.0, 100.0, 30.0) {
-                if !self.characters.is_empty() {
+                if !self.characters.is_empty() && !self.characters[self.selected_index].perished {
                     let character = &self.characters[self.selected_index];
                     return ScreenState::StartGame {
                         session: self.session.clone(),
@@ -1122,7 +1122,7 @@ impl Screen for CharacterSelectScreen {
 
         // Keyboard: Select character and start game
         if is_key_pressed(KeyCode::Enter) {
-            if !self.characters.is_empty() {
+            if !self.characters.is_empty() && !self.characters[self.selected_index].perished {
                 let character = &self.characters[self.selected_index];
                 return ScreenState::StartGame {
                     session: self.session.clone(),
@@ -1239,7 +1239,12 @@ impl Screen for CharacterSelectScreen {
 
                 // Character info (shifted right to make room for preview)
                 let text_x = list_x + 50.0;
-                self.draw_text_sharp(&character.name, text_x, y + 26.0, 16.0, WHITE);
+                // Characters lost to a hardcore death are greyed out and can't be played
+                if character.perished {
+                    self.draw_text_sharp(&format!("{} (Perished)", character.name), text_x, y + 26.0, 16.0, GRAY);
+                } else {
+                    self.draw_text_sharp(&character.name, text_x, y + 26.0, 16.0, WHITE);
+                }
                 let class_info = format!("Level {} {} {}", character.level, character.gender, character.skin);
                 self.draw_text_sharp(&class_info, text_x, y + 48.0, 16.0, LIGHTGRAY);
 
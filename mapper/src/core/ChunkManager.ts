@@ -235,6 +235,7 @@ export class ChunkManager {
         targetMap: p.targetMap,
         targetSpawn: p.targetSpawn,
       })),
+      spawnRegions: data.spawnRegions || [],
       dirty: false,
     };
 
@@ -410,6 +411,7 @@ export class ChunkManager {
       mapObjects,
       walls,
       portals,
+      ...(chunk.spawnRegions?.length ? { spawnRegions: chunk.spawnRegions } : {}),
     };
   }
 
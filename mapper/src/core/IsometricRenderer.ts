@@ -110,6 +110,7 @@ export class IsometricRenderer {
 
     if (this.options.showEntities) {
       for (const chunk of sortedChunks) {
+        this.renderSpawnRegions(chunk, viewport);
         this.renderEntities(chunk, viewport);
       }
     }
@@ -733,6 +734,49 @@ export class IsometricRenderer {
     }
   }
 
+  private renderSpawnRegions(chunk: Chunk, viewport: Viewport): void {
+    if (!this.ctx || !chunk.spawnRegions) return;
+
+    for (const region of chunk.spawnRegions) {
+      // Outline through tile corners: the polygon, or the rectangle's four corners
+      const corners = region.polygon && region.polygon.length >= 3
+        ? region.polygon
+        : [
+            { x: region.x, y: region.y },
+            { x: region.x + region.width, y: region.y },
+            { x: region.x + region.width, y: region.y + region.height },
+            { x: region.x, y: region.y + region.height },
+          ];
+      const points = corners.map((c) =>
+        worldToScreen(chunkLocalToWorld(chunk.coord, { lx: c.x, ly: c.y }), viewport)
+      );
+
+      this.ctx.beginPath();
+      points.forEach((p, i) => (i === 0 ? this.ctx!.moveTo(p.sx, p.sy) : this.ctx!.lineTo(p.sx, p.sy)));
+      this.ctx.closePath();
+      this.ctx.fillStyle = 'rgba(255, 80, 80, 0.12)';
+      this.ctx.fill();
+      this.ctx.setLineDash([6, 4]);
+      this.ctx.strokeStyle = 'rgba(255, 80, 80, 0.8)';
+      this.ctx.lineWidth = 2;
+      this.ctx.stroke();
+      this.ctx.setLineDash([]);
+
+      // Label with the monsters and cap at the region's center
+      if (viewport.zoom >= 0.5) {
+        const cx = points.reduce((sum, p) => sum + p.sx, 0) / points.length;
+        const cy = points.reduce((sum, p) => sum + p.sy, 0) / points.length;
+        const names = region.entities.map((e) => e.entityId).join(', ');
+
+        this.ctx.fillStyle = '#ffffff';
+        this.ctx.font = `bold ${10 * Math.max(1, viewport.zoom)}px sans-serif`;
+        this.ctx.textAlign = 'center';
+        this.ctx.textBaseline = 'middle';
+        this.ctx.fillText(`${names} (max ${region.maxPopulation})`, cx, cy);
+      }
+    }
+  }
+
   private renderPortals(chunk: Chunk, viewport: Viewport): void {
     if (!this.ctx || !chunk.portals) return;
 
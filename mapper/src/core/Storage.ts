@@ -126,6 +126,7 @@ class StorageManager {
             mapObjects: stored.mapObjects || [],
             walls: stored.walls || [],
             portals: stored.portals || [],
+            spawnRegions: stored.spawnRegions || [],
             dirty: stored.dirty,
           };
           chunks.set(stored.key, chunk);
@@ -184,6 +185,7 @@ class StorageManager {
       mapObjects: (stored.mapObjects as Chunk['mapObjects']) || [],
       walls: (stored.walls as Chunk['walls']) || [],
       portals: (stored.portals as Chunk['portals']) || [],
+      spawnRegions: (stored.spawnRegions as Chunk['spawnRegions']) || [],
       dirty: false,
     };
   }
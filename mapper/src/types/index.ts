@@ -138,6 +138,7 @@ export interface Chunk {
   mapObjects: MapObject[]; // Trees, rocks, decorations
   walls: Wall[];
   portals: Portal[];
+  spawnRegions?: SpawnRegion[];
   dirty: boolean;
 }
 
@@ -242,6 +243,7 @@ export interface SimplifiedChunk {
   mapObjects: SimplifiedMapObject[];
   walls: SimplifiedWall[];
   portals: SimplifiedPortal[];
+  spawnRegions?: SpawnRegion[];
 }
 
 export interface SimplifiedEntitySpawn {
@@ -300,6 +302,21 @@ export interface SimplifiedPortal {
   targetSpawn: string;
 }
 
+// Monster spawn region (rectangle, or polygon of tile corners when given)
+export interface SpawnRegion {
+  id: string;
+  x: number;            // Local tile X within chunk
+  y: number;            // Local tile Y within chunk
+  width: number;
+  height: number;
+  polygon?: Array<{ x: number; y: number }>;
+  entities: Array<{ entityId: string; weight?: number }>;
+  maxPopulation: number;
+  respawnMs?: number;
+  minLevel?: number;
+  maxLevel?: number;
+}
+
 // Exit portal (for interior maps - links back to overworld)
 export interface ExitPortal {
   id: string;
@@ -0,0 +1,51 @@
+-- Reaper Lord
+-- Phase 1: melee and the occasional scythe sweep
+-- Phase 2 (75%): calls reapers to its side
+-- Phase 3 (50%): sweeps come faster and hit harder, and it turns on someone new
+-- Phase 4 (25%): enrages
+
+thresholds = {75, 50, 25}
+
+local sweep_damage = 12
+local sweep_radius = 1
+
+function on_engage(boss)
+    boss:say("Your soul will join my harvest.")
+end
+
+function on_hp_threshold(boss, percent)
+    if percent == 75 then
+        boss:set_phase(2, "The Gathering")
+        boss:say("Rise, my servants!")
+        boss:summon("reaper", 2, 5)
+    elseif percent == 50 then
+        boss:set_phase(3, "Shadow Sweep")
+        boss:say("The shadows grow long...")
+        sweep_damage = 18
+        sweep_radius = 2
+        -- Forget whoever is tanking so the next in line takes over
+        local top = boss.threat[1]
+        if top then
+            boss:set_threat(top.id, 0)
+        end
+    elseif percent == 25 then
+        boss:set_phase(4, "Final Harvest")
+        boss:say("ENOUGH!")
+        boss:summon("reaper", 1, 8)
+        boss:enrage(1.5)
+    end
+end
+
+function on_tick(boss)
+    local count = #boss.players
+    if count == 0 then
+        return
+    end
+    -- Sweep around a random player, giving them time to step out
+    local victim = boss.players[math.random(count)]
+    boss:telegraph_circle(victim.x, victim.y, sweep_radius, sweep_damage, 1500)
+end
+
+function on_death(boss)
+    boss:say("The harvest... is not... over...")
+end
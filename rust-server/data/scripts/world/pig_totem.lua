@@ -0,0 +1,31 @@
+-- Pig Totem
+-- Every 20 corrupted pigs slain, whoever lands the last blow finds a pig totem.
+-- Using the totem calls the Pig King to their side.
+
+local KILLS_PER_TOTEM = 20
+
+function on_npc_death(npc_id, prototype_id, killer_id)
+    if prototype_id ~= "corrupted_pig" then
+        return
+    end
+    local slain = (world.get_flag("corrupted_pigs_slain") or 0) + 1
+    if slain >= KILLS_PER_TOTEM then
+        slain = 0
+        world.give_item(killer_id, "pig_totem")
+        world.message(killer_id, "Something squeals in your pack...")
+    end
+    world.set_flag("corrupted_pigs_slain", slain)
+end
+
+function on_item_use(player_id, item_id)
+    if item_id ~= "pig_totem" then
+        return
+    end
+    local x, y = world.player_position(player_id)
+    if not x then
+        world.message(player_id, "The totem stays silent here.")
+        return
+    end
+    world.spawn_npc("pig_king", x + 2, y, 3)
+    world.announce("The Pig King answers the call of the totem!")
+end
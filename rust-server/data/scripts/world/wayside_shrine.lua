@@ -0,0 +1,30 @@
+-- Wayside Shrine
+-- Stepping onto the stones south of the bounty board offers a prayer.
+-- Each player is blessed once until the server restarts.
+
+world.define_region("wayside_shrine", 18, 18, 2, 2)
+
+function on_player_enter_region(player_id, region_id)
+    world.show_dialogue(player_id, {
+        speaker = "Wayside Shrine",
+        text = "Weathered stones ring a small offering bowl.",
+        choices = {
+            { id = "pray", text = "Kneel and pray" },
+            { id = "leave", text = "Walk on" },
+        },
+    })
+end
+
+function on_dialogue_choice(player_id, choice_id)
+    if choice_id ~= "pray" then
+        return
+    end
+    local key = "shrine_blessed:" .. player_id
+    if world.get_flag(key) then
+        world.message(player_id, "The shrine is silent.")
+        return
+    end
+    world.set_flag(key, true)
+    world.give_item(player_id, "health_potion", 2)
+    world.message(player_id, "A quiet warmth settles over you.")
+end
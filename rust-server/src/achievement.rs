@@ -0,0 +1,320 @@
+//! Achievements and earned titles
+//!
+//! Achievements are loaded from `data/achievements/*.toml` as tables keyed by
+//! achievement ID. Progress is tracked as named counters per character (e.g.
+//! `kill:corrupted_pig`, `gold_earned`), fed by events the game room already
+//! produces. An achievement unlocks once its counter reaches the target.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use tracing::{info, warn};
+
+// ============================================================================
+// Definitions
+// ============================================================================
+
+/// What a player has to do to unlock an achievement
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AchievementCriteria {
+    /// Kill `count` monsters of a prototype (any monster if `target` is omitted)
+    Kill {
+        #[serde(default)]
+        target: Option<String>,
+        count: i64,
+    },
+    /// Reach `level` in a skill
+    SkillLevel { skill: String, level: i64 },
+    /// Complete `count` quests
+    QuestsCompleted { count: i64 },
+    /// Earn `amount` gold from loot, quests and selling to merchants
+    GoldEarned { amount: i64 },
+    /// Craft `count` items (of one item ID if `item` is set)
+    ItemsCrafted {
+        #[serde(default)]
+        item: Option<String>,
+        count: i64,
+    },
+}
+
+impl AchievementCriteria {
+    /// Name of the progress counter this criteria checks
+    pub fn counter(&self) -> String {
+        match self {
+            AchievementCriteria::Kill { target, .. } => {
+                format!("kill:{}", target.as_deref().unwrap_or("*"))
+            }
+            AchievementCriteria::SkillLevel { skill, .. } => format!("skill:{}", skill),
+            AchievementCriteria::QuestsCompleted { .. } => "quests_completed".to_string(),
+            AchievementCriteria::GoldEarned { .. } => "gold_earned".to_string(),
+            AchievementCriteria::ItemsCrafted { item, .. } => {
+                format!("crafted:{}", item.as_deref().unwrap_or("*"))
+            }
+        }
+    }
+
+    /// Counter value needed to unlock
+    pub fn target(&self) -> i64 {
+        match self {
+            AchievementCriteria::Kill { count, .. }
+            | AchievementCriteria::QuestsCompleted { count }
+            | AchievementCriteria::ItemsCrafted { count, .. } => *count,
+            AchievementCriteria::SkillLevel { level, .. } => *level,
+            AchievementCriteria::GoldEarned { amount } => *amount,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RawAchievementDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Title the player may display before their name once unlocked
+    #[serde(default)]
+    pub title: Option<String>,
+    pub criteria: AchievementCriteria,
+}
+
+#[derive(Debug, Clone)]
+pub struct AchievementDefinition {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub title: Option<String>,
+    pub criteria: AchievementCriteria,
+}
+
+/// Registry of all achievement definitions
+#[derive(Debug, Default)]
+pub struct AchievementRegistry {
+    achievements: HashMap<String, AchievementDefinition>,
+}
+
+impl AchievementRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load all achievement definitions from `<data_dir>/achievements`
+    pub fn load_from_directory(&mut self, data_dir: &Path) -> Result<(), String> {
+        let achievements_dir = data_dir.join("achievements");
+
+        if !achievements_dir.exists() {
+            warn!("Achievements directory does not exist: {:?}", achievements_dir);
+            return Ok(());
+        }
+
+        let entries = std::fs::read_dir(&achievements_dir)
+            .map_err(|e| format!("Failed to read achievements directory: {}", e))?;
+
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
+            let path = entry.path();
+
+            if path.extension().is_some_and(|ext| ext == "toml") {
+                let content = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+                let table: HashMap<String, RawAchievementDefinition> = toml::from_str(&content)
+                    .map_err(|e| format!("Failed to parse {:?}: {}", path, e))?;
+
+                for (id, raw) in table {
+                    if self.achievements.contains_key(&id) {
+                        warn!("Duplicate achievement ID '{}' in {:?}, overwriting", id, path);
+                    }
+                    self.achievements.insert(id.clone(), AchievementDefinition {
+                        id,
+                        name: raw.name,
+                        description: raw.description,
+                        title: raw.title,
+                        criteria: raw.criteria,
+                    });
+                }
+            }
+        }
+
+        info!("Loaded {} achievement definitions", self.achievements.len());
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> Option<&AchievementDefinition> {
+        self.achievements.get(id)
+    }
+
+    /// All achievements, sorted by name
+    pub fn all(&self) -> Vec<&AchievementDefinition> {
+        let mut achievements: Vec<_> = self.achievements.values().collect();
+        achievements.sort_by(|a, b| a.name.cmp(&b.name));
+        achievements
+    }
+}
+
+// ============================================================================
+// Events
+// ============================================================================
+
+/// Something a player did that may count towards achievements
+#[derive(Debug, Clone, Copy)]
+pub enum AchievementEvent<'a> {
+    Kill { prototype_id: &'a str },
+    SkillLevel { skill: &'a str, level: i32 },
+    QuestCompleted,
+    GoldEarned { amount: i64 },
+    ItemCrafted { item_id: &'a str, count: i32 },
+}
+
+// ============================================================================
+// Player Progress
+// ============================================================================
+
+/// A character's achievement counters, unlocks and selected title
+#[derive(Debug, Clone, Default)]
+pub struct PlayerAchievements {
+    pub counters: HashMap<String, i64>,
+    /// Achievement ID -> unlock time (ms since epoch)
+    pub unlocked: HashMap<String, u64>,
+    /// ID of the achievement whose title is displayed
+    pub active_title: Option<String>,
+}
+
+impl PlayerAchievements {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn counter(&self, name: &str) -> i64 {
+        self.counters.get(name).copied().unwrap_or(0)
+    }
+
+    fn add(&mut self, name: String, amount: i64) {
+        *self.counters.entry(name).or_insert(0) += amount;
+    }
+
+    /// Update counters for an event
+    pub fn record(&mut self, event: AchievementEvent) {
+        match event {
+            AchievementEvent::Kill { prototype_id } => {
+                self.add(format!("kill:{}", prototype_id), 1);
+                self.add("kill:*".to_string(), 1);
+            }
+            AchievementEvent::SkillLevel { skill, level } => {
+                let counter = self.counters.entry(format!("skill:{}", skill)).or_insert(0);
+                *counter = (*counter).max(level as i64);
+            }
+            AchievementEvent::QuestCompleted => self.add("quests_completed".to_string(), 1),
+            AchievementEvent::GoldEarned { amount } => self.add("gold_earned".to_string(), amount),
+            AchievementEvent::ItemCrafted { item_id, count } => {
+                self.add(format!("crafted:{}", item_id), count as i64);
+                self.add("crafted:*".to_string(), count as i64);
+            }
+        }
+    }
+
+    /// Current progress towards an achievement, capped at its target
+    pub fn progress(&self, achievement: &AchievementDefinition) -> i64 {
+        self.counter(&achievement.criteria.counter()).min(achievement.criteria.target())
+    }
+
+    /// Mark every achievement whose target has been reached as unlocked.
+    /// Returns the IDs of the newly unlocked achievements.
+    pub fn unlock_completed(&mut self, registry: &AchievementRegistry, now: u64) -> Vec<String> {
+        let completed: Vec<String> = registry.achievements.values()
+            .filter(|a| !self.unlocked.contains_key(&a.id))
+            .filter(|a| self.counter(&a.criteria.counter()) >= a.criteria.target())
+            .map(|a| a.id.clone())
+            .collect();
+        for id in &completed {
+            self.unlocked.insert(id.clone(), now);
+        }
+        completed
+    }
+
+    /// Title text of the selected achievement, if it's still unlocked and has one
+    pub fn title<'a>(&self, registry: &'a AchievementRegistry) -> Option<&'a str> {
+        let id = self.active_title.as_ref()?;
+        if !self.unlocked.contains_key(id) {
+            return None;
+        }
+        registry.get(id)?.title.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> AchievementRegistry {
+        let toml = r#"
+            [pig_slayer]
+            name = "Pig Slayer"
+            title = "Swineherd"
+            criteria = { type = "kill", target = "corrupted_pig", count = 2 }
+
+            [veteran]
+            name = "Veteran"
+            criteria = { type = "skill_level", skill = "combat", level = 10 }
+
+            [merchant]
+            name = "Merchant"
+            criteria = { type = "gold_earned", amount = 100 }
+        "#;
+        let table: HashMap<String, RawAchievementDefinition> = toml::from_str(toml).unwrap();
+        let mut registry = AchievementRegistry::new();
+        for (id, raw) in table {
+            registry.achievements.insert(id.clone(), AchievementDefinition {
+                id,
+                name: raw.name,
+                description: raw.description,
+                title: raw.title,
+                criteria: raw.criteria,
+            });
+        }
+        registry
+    }
+
+    #[test]
+    fn test_kill_counter_unlocks_once() {
+        let registry = registry();
+        let mut progress = PlayerAchievements::new();
+
+        progress.record(AchievementEvent::Kill { prototype_id: "corrupted_pig" });
+        progress.record(AchievementEvent::Kill { prototype_id: "wolf" });
+        assert!(progress.unlock_completed(&registry, 1).is_empty());
+        assert_eq!(progress.counter("kill:*"), 2);
+
+        progress.record(AchievementEvent::Kill { prototype_id: "corrupted_pig" });
+        assert_eq!(progress.unlock_completed(&registry, 2), vec!["pig_slayer".to_string()]);
+        assert!(progress.unlock_completed(&registry, 3).is_empty());
+        assert_eq!(progress.unlocked.get("pig_slayer"), Some(&2));
+    }
+
+    #[test]
+    fn test_skill_level_and_gold_progress() {
+        let registry = registry();
+        let mut progress = PlayerAchievements::new();
+
+        progress.record(AchievementEvent::SkillLevel { skill: "combat", level: 12 });
+        progress.record(AchievementEvent::SkillLevel { skill: "combat", level: 5 });
+        assert_eq!(progress.counter("skill:combat"), 12);
+
+        progress.record(AchievementEvent::GoldEarned { amount: 60 });
+        let merchant = registry.get("merchant").unwrap();
+        assert_eq!(progress.progress(merchant), 60);
+
+        let mut unlocked = progress.unlock_completed(&registry, 1);
+        unlocked.sort();
+        assert_eq!(unlocked, vec!["veteran".to_string()]);
+    }
+
+    #[test]
+    fn test_title_requires_unlock() {
+        let registry = registry();
+        let mut progress = PlayerAchievements::new();
+        progress.active_title = Some("pig_slayer".to_string());
+        assert_eq!(progress.title(&registry), None);
+
+        progress.unlocked.insert("pig_slayer".to_string(), 1);
+        assert_eq!(progress.title(&registry), Some("Swineherd"));
+    }
+}
@@ -0,0 +1,239 @@
+//! Data-driven NPC behavior profiles
+//!
+//! Entity prototypes list composable `[[<id>.behavior_profiles]]` picked by
+//! `type`; `Npc::update` asks `BehaviorProfiles` how to fight and whom to aggro.
+
+use serde::Deserialize;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Length of a full day/night cycle
+pub const DAY_LENGTH_MS: u64 = 40 * 60 * 1000;
+/// Night is the last third of each day
+const NIGHT_START_MS: u64 = DAY_LENGTH_MS * 2 / 3;
+
+/// Whether it is night in the world at `current_time` (Unix ms)
+pub fn is_night(current_time: u64) -> bool {
+    current_time % DAY_LENGTH_MS >= NIGHT_START_MS
+}
+
+/// Hour of the in-game day (0-23) at `current_time` (Unix ms)
+pub fn hour_of_day(current_time: u64) -> u32 {
+    (current_time % DAY_LENGTH_MS * 24 / DAY_LENGTH_MS) as u32
+}
+
+// ============================================================================
+// Profiles
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BehaviorProfile {
+    /// Run from the target once HP drops below `below_hp_percent`
+    Flee { below_hp_percent: i32 },
+    /// Back off to keep `min_distance` tiles from the target
+    Kite { min_distance: i32 },
+    /// When attacked, allies of the same family within `radius` join the fight
+    CallForHelp { radius: i32 },
+    /// Only fight players within `radius` of the spawn point (or of `x`, `y`)
+    /// and never chase past it
+    Guard {
+        radius: i32,
+        #[serde(default)]
+        x: Option<i32>,
+        #[serde(default)]
+        y: Option<i32>,
+    },
+    /// Only aggro on sight at night
+    Nocturnal,
+    /// Don't aggro on sight at players more than `max_level_gap` levels above
+    /// the NPC; an NPC that is attacked always fights back
+    IgnoreOutleveled { max_level_gap: i32 },
+}
+
+/// How an NPC in a fight should move this tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombatMove {
+    /// Close in to attack range
+    Approach,
+    /// Back away from a target that got too close
+    Retreat,
+    /// Run for it
+    Flee,
+}
+
+/// Chebyshev distance, matching NPC ranges
+fn distance(a: (i32, i32), b: (i32, i32)) -> i32 {
+    (a.0 - b.0).abs().max((a.1 - b.1).abs())
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BehaviorProfiles {
+    profiles: Vec<BehaviorProfile>,
+}
+
+impl BehaviorProfiles {
+    pub fn new(profiles: Vec<BehaviorProfile>) -> Self {
+        Self { profiles }
+    }
+
+    /// Whether seeing a player is enough to start a fight
+    pub fn will_aggro(&self, npc_level: i32, home: (i32, i32), player: (i32, i32), player_level: i32, current_time: u64) -> bool {
+        self.profiles.iter().all(|profile| match profile {
+            BehaviorProfile::Nocturnal => is_night(current_time),
+            BehaviorProfile::IgnoreOutleveled { max_level_gap } => player_level - npc_level <= *max_level_gap,
+            BehaviorProfile::Guard { .. } => self.may_pursue(home, player),
+            _ => true,
+        })
+    }
+
+    /// Whether the NPC may fight a player standing at `target` (guards stay at their post)
+    pub fn may_pursue(&self, home: (i32, i32), target: (i32, i32)) -> bool {
+        self.profiles.iter().all(|profile| match profile {
+            BehaviorProfile::Guard { radius, x, y } => {
+                let post = (x.unwrap_or(home.0), y.unwrap_or(home.1));
+                distance(post, target) <= *radius
+            }
+            _ => true,
+        })
+    }
+
+    /// How far allies are called when this NPC is attacked
+    pub fn help_radius(&self) -> Option<i32> {
+        self.profiles.iter()
+            .filter_map(|profile| match profile {
+                BehaviorProfile::CallForHelp { radius } => Some(*radius),
+                _ => None,
+            })
+            .max()
+    }
+
+    /// How to move against a target at `target` while fighting
+    pub fn combat_move(&self, position: (i32, i32), target: (i32, i32), hp: i32, max_hp: i32) -> CombatMove {
+        let hp_percent = hp * 100 / max_hp.max(1);
+        let dist = distance(position, target);
+        for profile in &self.profiles {
+            match profile {
+                BehaviorProfile::Flee { below_hp_percent } if hp_percent < *below_hp_percent => return CombatMove::Flee,
+                BehaviorProfile::Kite { min_distance } if dist < *min_distance => return CombatMove::Retreat,
+                _ => {}
+            }
+        }
+        CombatMove::Approach
+    }
+}
+
+/// Best neighbouring tile for getting away from `threat`: the walkable one
+/// furthest from it that stays within `leash` of home. None if boxed in.
+pub fn pick_retreat_step(
+    position: (i32, i32),
+    threat: (i32, i32),
+    home: (i32, i32),
+    leash: i32,
+    can_step: impl Fn((i32, i32)) -> bool,
+) -> Option<(i32, i32)> {
+    let current = distance(position, threat);
+    [(0, -1), (0, 1), (-1, 0), (1, 0)].into_iter()
+        .map(|(dx, dy)| (position.0 + dx, position.1 + dy))
+        .filter(|tile| distance(*tile, home) <= leash && can_step(*tile))
+        .map(|tile| (tile, (tile.0 - threat.0).abs() + (tile.1 - threat.1).abs(), distance(tile, threat)))
+        .filter(|(_, _, chebyshev)| *chebyshev >= current)
+        .max_by_key(|(_, manhattan, chebyshev)| (*chebyshev, *manhattan))
+        .map(|(tile, _, _)| tile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profiles(toml_src: &str) -> BehaviorProfiles {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            behavior_profiles: Vec<BehaviorProfile>,
+        }
+        let wrapper: Wrapper = toml::from_str(toml_src).unwrap();
+        BehaviorProfiles::new(wrapper.behavior_profiles)
+    }
+
+    const DAY: u64 = 0;
+    const NIGHT: u64 = NIGHT_START_MS + 1;
+
+    #[test]
+    fn test_profiles_parse_from_toml() {
+        let parsed = profiles(r#"
+            [[behavior_profiles]]
+            type = "guard"
+            radius = 4
+
+            [[behavior_profiles]]
+            type = "nocturnal"
+        "#);
+        assert_eq!(parsed.profiles, vec![
+            BehaviorProfile::Guard { radius: 4, x: None, y: None },
+            BehaviorProfile::Nocturnal,
+        ]);
+        assert!(is_night(NIGHT) && !is_night(DAY) && !is_night(DAY_LENGTH_MS));
+    }
+
+    #[test]
+    fn test_aggro_filters() {
+        let none = BehaviorProfiles::default();
+        assert!(none.will_aggro(5, (0, 0), (30, 30), 99, DAY));
+
+        let picky = profiles(r#"
+            [[behavior_profiles]]
+            type = "nocturnal"
+
+            [[behavior_profiles]]
+            type = "ignore_outleveled"
+            max_level_gap = 10
+
+            [[behavior_profiles]]
+            type = "guard"
+            radius = 3
+            x = 10
+            y = 10
+        "#);
+        assert!(picky.will_aggro(5, (0, 0), (12, 11), 15, NIGHT));
+        assert!(!picky.will_aggro(5, (0, 0), (12, 11), 15, DAY));
+        assert!(!picky.will_aggro(5, (0, 0), (12, 11), 16, NIGHT));
+        // The guard post overrides the spawn point
+        assert!(!picky.will_aggro(5, (0, 0), (1, 1), 5, NIGHT));
+        assert!(!picky.may_pursue((0, 0), (14, 10)));
+    }
+
+    #[test]
+    fn test_combat_moves() {
+        let skittish = profiles(r#"
+            [[behavior_profiles]]
+            type = "flee"
+            below_hp_percent = 25
+
+            [[behavior_profiles]]
+            type = "kite"
+            min_distance = 3
+
+            [[behavior_profiles]]
+            type = "call_for_help"
+            radius = 6
+        "#);
+        assert_eq!(skittish.combat_move((0, 0), (5, 0), 100, 100), CombatMove::Approach);
+        assert_eq!(skittish.combat_move((0, 0), (2, 0), 100, 100), CombatMove::Retreat);
+        assert_eq!(skittish.combat_move((0, 0), (5, 0), 24, 100), CombatMove::Flee);
+        assert_eq!(skittish.help_radius(), Some(6));
+        assert_eq!(BehaviorProfiles::default().help_radius(), None);
+    }
+
+    #[test]
+    fn test_retreat_steps_away_within_the_leash() {
+        // Threat to the east: step west
+        assert_eq!(pick_retreat_step((5, 5), (7, 5), (5, 5), 5, |_| true), Some((4, 5)));
+        // West is blocked: sidestep keeps the distance rather than closing in
+        let step = pick_retreat_step((5, 5), (7, 5), (5, 5), 5, |tile| tile != (4, 5));
+        assert!(matches!(step, Some((5, 4)) | Some((5, 6))));
+        // Pinned against the leash with the sides blocked
+        assert_eq!(pick_retreat_step((0, 5), (1, 5), (5, 5), 5, |tile| tile.1 == 5), None);
+    }
+}
@@ -0,0 +1,618 @@
+//! Scripted boss encounters
+//!
+//! Each encounter runs the boss's Lua script from `data/scripts/bosses/` in its
+//! own sandbox and turns the hooks' calls into `BossAction`s for the game room
+//! (see `data/scripts/README.md` for the script API).
+
+use mlua::{Function, Lua, Result as LuaResult, Table};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{error, info, warn};
+
+use crate::lua_sandbox;
+
+/// Longest telegraph delay a script may request
+const MAX_TELEGRAPH_DELAY_MS: u64 = 10_000;
+
+/// `[<id>.boss]` section of an entity prototype
+#[derive(Debug, Clone, Deserialize)]
+pub struct BossConfig {
+    /// Script path relative to `data/scripts/bosses/`
+    pub script: String,
+    #[serde(default = "default_tick_interval")]
+    pub tick_interval_ms: u64,
+    /// Share of the total damage (percent) a player needs to be eligible for loot
+    #[serde(default = "default_loot_share")]
+    pub min_damage_percent: f32,
+}
+
+fn default_tick_interval() -> u64 {
+    1000
+}
+
+fn default_loot_share() -> f32 {
+    5.0
+}
+
+// ============================================================================
+// Scripts
+// ============================================================================
+
+/// Boss script sources, keyed by path relative to the bosses script directory
+#[derive(Debug, Default)]
+pub struct BossScripts {
+    scripts: HashMap<String, String>,
+}
+
+impl BossScripts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load every `.lua` file under `data_dir/scripts/bosses`
+    pub fn load_from_directory(&mut self, data_dir: &Path) -> Result<(), String> {
+        let scripts_dir = data_dir.join("scripts").join("bosses");
+        if !scripts_dir.exists() {
+            warn!("Boss script directory does not exist: {:?}", scripts_dir);
+            return Ok(());
+        }
+        self.load_dir(&scripts_dir, &scripts_dir)?;
+        info!("Loaded {} boss scripts", self.scripts.len());
+        Ok(())
+    }
+
+    fn load_dir(&mut self, root: &Path, dir: &Path) -> Result<(), String> {
+        for entry in fs::read_dir(dir).map_err(|e| e.to_string())? {
+            let path: PathBuf = entry.map_err(|e| e.to_string())?.path();
+            if path.is_dir() {
+                self.load_dir(root, &path)?;
+            } else if path.extension().and_then(|s| s.to_str()) == Some("lua") {
+                let source = fs::read_to_string(&path)
+                    .map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+                let key = path.strip_prefix(root)
+                    .map_err(|e| e.to_string())?
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                self.scripts.insert(key, source);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, script: &str) -> Option<&str> {
+        self.scripts.get(script).map(|s| s.as_str())
+    }
+}
+
+// ============================================================================
+// Actions
+// ============================================================================
+
+/// Something a boss script asked for
+#[derive(Debug, Clone, PartialEq)]
+pub enum BossAction {
+    /// Spawn adds around the boss
+    Summon { prototype: String, count: u32, level: i32 },
+    /// Warn players about tiles that will be hit after a delay
+    Telegraph { tiles: Vec<(i32, i32)>, damage: i32, delay_ms: u64 },
+    /// Multiply the boss's damage for the rest of the fight
+    Enrage { multiplier: f32 },
+    SetPhase { phase: i32, name: Option<String> },
+    /// Broadcast a line of boss dialogue
+    Say { text: String },
+    /// Overwrite a player's threat on the boss
+    SetThreat { player_id: String, amount: f32 },
+}
+
+/// Tiles within `radius` of a point
+pub fn circle_tiles(x: i32, y: i32, radius: i32) -> Vec<(i32, i32)> {
+    let radius = radius.clamp(0, 8);
+    let mut tiles = Vec::new();
+    for dy in -radius..=radius {
+        for dx in -radius..=radius {
+            if dx * dx + dy * dy <= radius * radius {
+                tiles.push((x + dx, y + dy));
+            }
+        }
+    }
+    tiles
+}
+
+/// What scripts can see about the boss and the players around it
+#[derive(Debug, Clone)]
+pub struct BossSnapshot {
+    pub name: String,
+    pub hp: i32,
+    pub max_hp: i32,
+    pub x: i32,
+    pub y: i32,
+    pub target: Option<String>,
+    /// (player ID, x, y) of living players near the boss
+    pub players: Vec<(String, i32, i32)>,
+    /// (player ID, threat) highest first
+    pub threat: Vec<(String, f32)>,
+    pub now: u64,
+}
+
+impl BossSnapshot {
+    pub fn hp_percent(&self) -> i32 {
+        if self.max_hp <= 0 {
+            return 0;
+        }
+        (self.hp as i64 * 100 / self.max_hp as i64) as i32
+    }
+}
+
+/// Area attack waiting to land
+#[derive(Debug, Clone)]
+pub struct PendingTelegraph {
+    pub tiles: Vec<(i32, i32)>,
+    pub damage: i32,
+    pub hits_at: u64,
+}
+
+// ============================================================================
+// Encounter
+// ============================================================================
+
+/// A boss fight in progress
+pub struct BossEncounter {
+    pub npc_id: String,
+    pub prototype_id: String,
+    lua: Lua,
+    pub phase: i32,
+    pub phase_name: Option<String>,
+    pub enraged: bool,
+    /// Boss damage before any enrage, restored when the fight ends
+    pub base_damage: i32,
+    pub started_at: u64,
+    last_tick: u64,
+    /// HP percentages from the script, highest first
+    thresholds: Vec<i32>,
+    fired_thresholds: HashSet<i32>,
+    /// Player ID -> damage dealt to the boss
+    pub damage: HashMap<String, i32>,
+    pub telegraphs: Vec<PendingTelegraph>,
+    /// Whether clients need a fresh boss update
+    pub dirty: bool,
+}
+
+impl BossEncounter {
+    pub fn new(
+        npc_id: &str,
+        prototype_id: &str,
+        script_name: &str,
+        source: &str,
+        base_damage: i32,
+        now: u64,
+    ) -> Result<Self, String> {
+        let lua = lua_sandbox::new_sandbox().map_err(|e| e.to_string())?;
+        let globals = lua.globals();
+        lua_sandbox::reset_budget(&lua);
+        lua.load(source)
+            .set_name(script_name)
+            .exec()
+            .map_err(|e| format!("Failed to load boss script {}: {}", script_name, e))?;
+
+        let mut thresholds: Vec<i32> = globals.get::<Option<Table>>("thresholds")
+            .ok()
+            .flatten()
+            .map(|t| t.sequence_values::<i32>().filter_map(|v| v.ok()).collect())
+            .unwrap_or_default();
+        thresholds.sort_by(|a, b| b.cmp(a));
+        thresholds.dedup();
+
+        Ok(Self {
+            npc_id: npc_id.to_string(),
+            prototype_id: prototype_id.to_string(),
+            lua,
+            phase: 1,
+            phase_name: None,
+            enraged: false,
+            base_damage,
+            started_at: now,
+            last_tick: now,
+            thresholds,
+            fired_thresholds: HashSet::new(),
+            damage: HashMap::new(),
+            telegraphs: Vec::new(),
+            dirty: true,
+        })
+    }
+
+    pub fn record_damage(&mut self, player_id: &str, amount: i32) {
+        *self.damage.entry(player_id.to_string()).or_insert(0) += amount;
+        self.dirty = true;
+    }
+
+    /// Thresholds the boss's HP has reached that haven't fired yet, highest first
+    pub fn take_due_thresholds(&mut self, hp_percent: i32) -> Vec<i32> {
+        let due: Vec<i32> = self.thresholds.iter()
+            .copied()
+            .filter(|t| hp_percent <= *t && !self.fired_thresholds.contains(t))
+            .collect();
+        self.fired_thresholds.extend(due.iter().copied());
+        due
+    }
+
+    /// Whether `on_tick` should run, advancing the tick timer if so
+    pub fn tick_due(&mut self, now: u64, interval_ms: u64) -> bool {
+        if now.saturating_sub(self.last_tick) < interval_ms.max(100) {
+            return false;
+        }
+        self.last_tick = now;
+        true
+    }
+
+    /// Run a hook if the script defines it, returning the actions it queued
+    pub fn run_hook(&self, hook: &str, snapshot: &BossSnapshot, arg: Option<i32>) -> Vec<BossAction> {
+        let Ok(function) = self.lua.globals().get::<Function>(hook) else {
+            return Vec::new();
+        };
+        match self.call_hook(function, snapshot, arg) {
+            Ok(actions) => actions,
+            Err(e) => {
+                error!("Boss {} script error in {}: {}", self.prototype_id, hook, e);
+                Vec::new()
+            }
+        }
+    }
+
+    fn call_hook(&self, function: Function, snapshot: &BossSnapshot, arg: Option<i32>) -> LuaResult<Vec<BossAction>> {
+        let boss = self.build_context(snapshot)?;
+        lua_sandbox::reset_budget(&self.lua);
+        match arg {
+            Some(arg) => function.call::<()>((boss.clone(), arg))?,
+            None => function.call::<()>(boss.clone())?,
+        }
+        let actions: Table = boss.get("_actions")?;
+        Ok(actions.sequence_values::<Table>()
+            .filter_map(|action| action.ok())
+            .filter_map(|action| parse_action(&action))
+            .collect())
+    }
+
+    fn build_context(&self, snapshot: &BossSnapshot) -> LuaResult<Table> {
+        let lua = &self.lua;
+        let boss = lua.create_table()?;
+        boss.set("_actions", lua.create_table()?)?;
+        boss.set("id", self.npc_id.clone())?;
+        boss.set("name", snapshot.name.clone())?;
+        boss.set("hp", snapshot.hp)?;
+        boss.set("max_hp", snapshot.max_hp)?;
+        boss.set("hp_percent", snapshot.hp_percent())?;
+        boss.set("phase", self.phase)?;
+        boss.set("enraged", self.enraged)?;
+        boss.set("x", snapshot.x)?;
+        boss.set("y", snapshot.y)?;
+        boss.set("target", snapshot.target.clone())?;
+        boss.set("elapsed", snapshot.now.saturating_sub(self.started_at) as f64 / 1000.0)?;
+
+        let players = lua.create_table()?;
+        for (i, (id, x, y)) in snapshot.players.iter().enumerate() {
+            let player = lua.create_table()?;
+            player.set("id", id.clone())?;
+            player.set("x", *x)?;
+            player.set("y", *y)?;
+            players.set(i + 1, player)?;
+        }
+        boss.set("players", players)?;
+
+        let threat = lua.create_table()?;
+        for (i, (id, amount)) in snapshot.threat.iter().enumerate() {
+            let entry = lua.create_table()?;
+            entry.set("id", id.clone())?;
+            entry.set("threat", *amount)?;
+            threat.set(i + 1, entry)?;
+        }
+        boss.set("threat", threat)?;
+
+        // boss:summon(prototype, [count], [level])
+        let summon = lua.create_function(|lua, (this, prototype, count, level): (Table, String, Option<u32>, Option<i32>)| {
+            let action = lua.create_table()?;
+            action.set("kind", "summon")?;
+            action.set("prototype", prototype)?;
+            action.set("count", count.unwrap_or(1))?;
+            action.set("level", level.unwrap_or(1))?;
+            push_action(&this, action)
+        })?;
+        boss.set("summon", summon)?;
+
+        // boss:telegraph({{x = .., y = ..}, ...}, damage, [delay_ms])
+        let telegraph = lua.create_function(|lua, (this, tiles, damage, delay_ms): (Table, Table, i32, Option<u64>)| {
+            let action = lua.create_table()?;
+            action.set("kind", "telegraph")?;
+            action.set("tiles", tiles)?;
+            action.set("damage", damage)?;
+            action.set("delay_ms", delay_ms.unwrap_or(1500))?;
+            push_action(&this, action)
+        })?;
+        boss.set("telegraph", telegraph)?;
+
+        // boss:telegraph_circle(x, y, radius, damage, [delay_ms])
+        let telegraph_circle = lua.create_function(|lua, (this, x, y, radius, damage, delay_ms): (Table, i32, i32, i32, i32, Option<u64>)| {
+            let tiles = lua.create_table()?;
+            for (i, (tx, ty)) in circle_tiles(x, y, radius).into_iter().enumerate() {
+                let tile = lua.create_table()?;
+                tile.set("x", tx)?;
+                tile.set("y", ty)?;
+                tiles.set(i + 1, tile)?;
+            }
+            let action = lua.create_table()?;
+            action.set("kind", "telegraph")?;
+            action.set("tiles", tiles)?;
+            action.set("damage", damage)?;
+            action.set("delay_ms", delay_ms.unwrap_or(1500))?;
+            push_action(&this, action)
+        })?;
+        boss.set("telegraph_circle", telegraph_circle)?;
+
+        // boss:enrage([multiplier])
+        let enrage = lua.create_function(|lua, (this, multiplier): (Table, Option<f32>)| {
+            let action = lua.create_table()?;
+            action.set("kind", "enrage")?;
+            action.set("multiplier", multiplier.unwrap_or(1.5))?;
+            push_action(&this, action)
+        })?;
+        boss.set("enrage", enrage)?;
+
+        // boss:set_phase(phase, [name])
+        let set_phase = lua.create_function(|lua, (this, phase, name): (Table, i32, Option<String>)| {
+            let action = lua.create_table()?;
+            action.set("kind", "set_phase")?;
+            action.set("phase", phase)?;
+            action.set("name", name)?;
+            push_action(&this, action)
+        })?;
+        boss.set("set_phase", set_phase)?;
+
+        // boss:say(text)
+        let say = lua.create_function(|lua, (this, text): (Table, String)| {
+            let action = lua.create_table()?;
+            action.set("kind", "say")?;
+            action.set("text", text)?;
+            push_action(&this, action)
+        })?;
+        boss.set("say", say)?;
+
+        // boss:set_threat(player_id, amount)
+        let set_threat = lua.create_function(|lua, (this, player_id, amount): (Table, String, f32)| {
+            let action = lua.create_table()?;
+            action.set("kind", "set_threat")?;
+            action.set("player_id", player_id)?;
+            action.set("amount", amount)?;
+            push_action(&this, action)
+        })?;
+        boss.set("set_threat", set_threat)?;
+
+        Ok(boss)
+    }
+
+    /// Update phase and enrage state from actions, returning true if anything changed
+    pub fn apply_state(&mut self, actions: &[BossAction]) -> bool {
+        let mut changed = false;
+        for action in actions {
+            match action {
+                BossAction::SetPhase { phase, name } => {
+                    self.phase = *phase;
+                    self.phase_name = name.clone();
+                    changed = true;
+                }
+                BossAction::Enrage { .. } => {
+                    self.enraged = true;
+                    changed = true;
+                }
+                _ => {}
+            }
+        }
+        self.dirty |= changed;
+        changed
+    }
+
+    /// Queue a telegraphed area attack, returning the delay actually used
+    pub fn add_telegraph(&mut self, tiles: Vec<(i32, i32)>, damage: i32, delay_ms: u64, now: u64) -> u64 {
+        let delay_ms = delay_ms.min(MAX_TELEGRAPH_DELAY_MS);
+        self.telegraphs.push(PendingTelegraph {
+            tiles,
+            damage,
+            hits_at: now + delay_ms,
+        });
+        delay_ms
+    }
+
+    /// Remove and return telegraphs whose delay has passed
+    pub fn take_due_telegraphs(&mut self, now: u64) -> Vec<PendingTelegraph> {
+        let (due, pending) = std::mem::take(&mut self.telegraphs)
+            .into_iter()
+            .partition(|t| t.hits_at <= now);
+        self.telegraphs = pending;
+        due
+    }
+
+    /// Players by damage dealt, highest first
+    pub fn leaderboard(&self) -> Vec<(String, i32)> {
+        let mut board: Vec<(String, i32)> = self.damage.iter()
+            .map(|(id, damage)| (id.clone(), *damage))
+            .collect();
+        board.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        board
+    }
+
+    /// Players who dealt at least `min_percent` of the total damage
+    pub fn loot_eligible(&self, min_percent: f32) -> Vec<String> {
+        let total: i64 = self.damage.values().map(|d| *d as i64).sum();
+        if total <= 0 {
+            return Vec::new();
+        }
+        self.leaderboard()
+            .into_iter()
+            .filter(|(_, damage)| *damage as f32 * 100.0 / total as f32 >= min_percent)
+            .map(|(id, _)| id)
+            .collect()
+    }
+}
+
+fn push_action(this: &Table, action: Table) -> LuaResult<()> {
+    let actions: Table = this.get("_actions")?;
+    let len = actions.len()? + 1;
+    actions.set(len, action)
+}
+
+fn parse_action(action: &Table) -> Option<BossAction> {
+    let kind: String = action.get("kind").ok()?;
+    match kind.as_str() {
+        "summon" => Some(BossAction::Summon {
+            prototype: action.get("prototype").ok()?,
+            count: action.get::<u32>("count").ok()?.min(20),
+            level: action.get("level").ok()?,
+        }),
+        "telegraph" => {
+            let tiles: Table = action.get("tiles").ok()?;
+            let tiles = tiles.sequence_values::<Table>()
+                .filter_map(|tile| tile.ok())
+                .filter_map(|tile| {
+                    // Accept both {x = 1, y = 2} and {1, 2}
+                    let x = tile.get::<i32>("x").or_else(|_| tile.get::<i32>(1)).ok()?;
+                    let y = tile.get::<i32>("y").or_else(|_| tile.get::<i32>(2)).ok()?;
+                    Some((x, y))
+                })
+                .collect();
+            Some(BossAction::Telegraph {
+                tiles,
+                damage: action.get("damage").ok()?,
+                delay_ms: action.get("delay_ms").ok()?,
+            })
+        }
+        "enrage" => Some(BossAction::Enrage {
+            multiplier: action.get("multiplier").ok()?,
+        }),
+        "set_phase" => Some(BossAction::SetPhase {
+            phase: action.get("phase").ok()?,
+            name: action.get("name").ok()?,
+        }),
+        "say" => Some(BossAction::Say {
+            text: action.get("text").ok()?,
+        }),
+        "set_threat" => Some(BossAction::SetThreat {
+            player_id: action.get("player_id").ok()?,
+            amount: action.get("amount").ok()?,
+        }),
+        _ => {
+            warn!("Unknown boss action '{}'", kind);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCRIPT: &str = r#"
+        thresholds = { 50, 75 }
+
+        function on_hp_threshold(boss, percent)
+            if percent == 50 then
+                boss:set_phase(2, "Harvest")
+                boss:enrage(2.0)
+            end
+            boss:summon("slime", 2, 3)
+        end
+
+        function on_tick(boss)
+            local p = boss.players[1]
+            if p then
+                boss:telegraph({ { x = p.x, y = p.y }, { p.x + 1, p.y } }, 20, 1000)
+            end
+            boss:say("Hp " .. boss.hp_percent)
+        end
+    "#;
+
+    fn snapshot(hp: i32) -> BossSnapshot {
+        BossSnapshot {
+            name: "Reaper Lord".to_string(),
+            hp,
+            max_hp: 200,
+            x: 5,
+            y: 5,
+            target: None,
+            players: vec![("p1".to_string(), 7, 8)],
+            threat: vec![("p1".to_string(), 30.0), ("p2".to_string(), 12.0)],
+            now: 1000,
+        }
+    }
+
+    #[test]
+    fn test_thresholds_fire_once_in_order() {
+        let mut encounter = BossEncounter::new("boss", "reaper_lord", "test.lua", SCRIPT, 20, 0).unwrap();
+        assert!(encounter.take_due_thresholds(90).is_empty());
+        assert_eq!(encounter.take_due_thresholds(40), vec![75, 50]);
+        assert!(encounter.take_due_thresholds(10).is_empty());
+
+        let actions = encounter.run_hook("on_hp_threshold", &snapshot(100), Some(50));
+        assert_eq!(actions, vec![
+            BossAction::SetPhase { phase: 2, name: Some("Harvest".to_string()) },
+            BossAction::Enrage { multiplier: 2.0 },
+            BossAction::Summon { prototype: "slime".to_string(), count: 2, level: 3 },
+        ]);
+        assert!(encounter.apply_state(&actions));
+        assert_eq!(encounter.phase, 2);
+        assert!(encounter.enraged);
+    }
+
+    #[test]
+    fn test_tick_actions_and_missing_hooks() {
+        let encounter = BossEncounter::new("boss", "reaper_lord", "test.lua", SCRIPT, 20, 0).unwrap();
+        let actions = encounter.run_hook("on_tick", &snapshot(150), None);
+        assert_eq!(actions, vec![
+            BossAction::Telegraph { tiles: vec![(7, 8), (8, 8)], damage: 20, delay_ms: 1000 },
+            BossAction::Say { text: "Hp 75".to_string() },
+        ]);
+        assert!(encounter.run_hook("on_death", &snapshot(0), None).is_empty());
+    }
+
+    #[test]
+    fn test_runaway_hooks_are_stopped() {
+        let script = r#"
+            function on_tick(boss) while true do end end
+            function on_death(boss) boss:say("Farewell") end
+        "#;
+        let encounter = BossEncounter::new("boss", "reaper_lord", "test.lua", script, 20, 0).unwrap();
+        assert!(encounter.run_hook("on_tick", &snapshot(150), None).is_empty());
+        assert_eq!(encounter.run_hook("on_death", &snapshot(0), None), vec![
+            BossAction::Say { text: "Farewell".to_string() },
+        ]);
+        assert!(BossEncounter::new("boss", "reaper_lord", "loop.lua", "while true do end", 20, 0).is_err());
+    }
+
+    #[test]
+    fn test_scripts_read_and_set_threat() {
+        let script = r#"
+            function on_tick(boss)
+                local top = boss.threat[1]
+                boss:set_threat(top.id, 0)
+                boss:set_threat(boss.threat[2].id, top.threat)
+            end
+        "#;
+        let encounter = BossEncounter::new("boss", "reaper_lord", "test.lua", script, 20, 0).unwrap();
+        assert_eq!(encounter.run_hook("on_tick", &snapshot(150), None), vec![
+            BossAction::SetThreat { player_id: "p1".to_string(), amount: 0.0 },
+            BossAction::SetThreat { player_id: "p2".to_string(), amount: 30.0 },
+        ]);
+    }
+
+    #[test]
+    fn test_loot_eligibility_by_damage_share() {
+        let mut encounter = BossEncounter::new("boss", "reaper_lord", "test.lua", SCRIPT, 20, 0).unwrap();
+        encounter.record_damage("alice", 150);
+        encounter.record_damage("bob", 45);
+        encounter.record_damage("carol", 5);
+        encounter.record_damage("alice", 0);
+
+        assert_eq!(encounter.leaderboard()[0], ("alice".to_string(), 150));
+        assert_eq!(encounter.loot_eligible(5.0), vec!["alice".to_string(), "bob".to_string()]);
+        assert_eq!(encounter.loot_eligible(2.5).len(), 3);
+    }
+}
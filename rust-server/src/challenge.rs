@@ -0,0 +1,501 @@
+//! Daily and weekly challenges
+//!
+//! Each period picks the same challenges from `data/challenges/*.toml` for all
+//! players, deterministically from the period number so rotation survives
+//! restarts. Objectives are fed the same `QuestEvent`s as quest objectives.
+
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use tracing::{info, warn};
+
+use crate::quest::definition::{RawReward, Reward};
+use crate::quest::{ObjectiveType, QuestEvent};
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+const HOUR_MS: i64 = 60 * 60 * 1000;
+/// 1970-01-01 was a Thursday; day 4 since the epoch is the first Monday
+const FIRST_MONDAY: i64 = 4;
+
+// ============================================================================
+// Settings
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChallengePeriod {
+    Daily,
+    Weekly,
+}
+
+impl ChallengePeriod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChallengePeriod::Daily => "daily",
+            ChallengePeriod::Weekly => "weekly",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "daily" => Some(ChallengePeriod::Daily),
+            "weekly" => Some(ChallengePeriod::Weekly),
+            _ => None,
+        }
+    }
+}
+
+/// Rotation and streak configuration
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ChallengeSettings {
+    /// Hour of the day (UTC) at which challenges reset
+    pub reset_hour: u32,
+    /// Day of the week weekly challenges reset (0 = Monday .. 6 = Sunday)
+    pub weekly_reset_day: u32,
+    /// Number of daily challenges active at once
+    pub daily_count: usize,
+    /// Number of weekly challenges active at once
+    pub weekly_count: usize,
+    /// Bonus gold per streak period for completing all daily challenges
+    pub daily_streak_bonus: i32,
+    /// Bonus gold per streak period for completing all weekly challenges
+    pub weekly_streak_bonus: i32,
+    /// Streak length after which the bonus stops growing
+    pub max_streak_bonus: i32,
+}
+
+impl Default for ChallengeSettings {
+    fn default() -> Self {
+        Self {
+            reset_hour: 0,
+            weekly_reset_day: 0,
+            daily_count: 3,
+            weekly_count: 2,
+            daily_streak_bonus: 0,
+            weekly_streak_bonus: 0,
+            max_streak_bonus: 7,
+        }
+    }
+}
+
+impl ChallengeSettings {
+    /// Index of the period containing `now` (ms since epoch). Increments at every reset.
+    pub fn period_index(&self, period: ChallengePeriod, now: u64) -> i64 {
+        let day = (now as i64 - self.reset_hour as i64 * HOUR_MS).div_euclid(DAY_MS);
+        match period {
+            ChallengePeriod::Daily => day,
+            ChallengePeriod::Weekly => (day - FIRST_MONDAY - self.weekly_reset_day as i64).div_euclid(7),
+        }
+    }
+
+    /// Time of the next reset after `now` (ms since epoch)
+    pub fn next_reset(&self, period: ChallengePeriod, now: u64) -> u64 {
+        let index = self.period_index(period, now);
+        let next_day = match period {
+            ChallengePeriod::Daily => index + 1,
+            ChallengePeriod::Weekly => (index + 1) * 7 + FIRST_MONDAY + self.weekly_reset_day as i64,
+        };
+        (next_day * DAY_MS + self.reset_hour as i64 * HOUR_MS) as u64
+    }
+
+    pub fn count(&self, period: ChallengePeriod) -> usize {
+        match period {
+            ChallengePeriod::Daily => self.daily_count,
+            ChallengePeriod::Weekly => self.weekly_count,
+        }
+    }
+
+    /// Bonus gold for completing every challenge of a period with the given streak
+    pub fn streak_bonus(&self, period: ChallengePeriod, streak: i32) -> i32 {
+        let per_period = match period {
+            ChallengePeriod::Daily => self.daily_streak_bonus,
+            ChallengePeriod::Weekly => self.weekly_streak_bonus,
+        };
+        per_period * streak.min(self.max_streak_bonus)
+    }
+}
+
+// ============================================================================
+// Definitions
+// ============================================================================
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RawChallengeObjective {
+    /// Same types as quest objectives ("kill_monster", "craft_item", "earn_gold", ...)
+    #[serde(rename = "type")]
+    pub objective_type: String,
+    /// Target ID, or "*" for any
+    #[serde(default = "any_target")]
+    pub target: String,
+    pub count: i32,
+}
+
+fn any_target() -> String {
+    "*".to_string()
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RawChallenge {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub period: ChallengePeriod,
+    pub objective: RawChallengeObjective,
+    #[serde(default)]
+    pub rewards: RawReward,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RawChallengeFile {
+    pub settings: Option<ChallengeSettings>,
+    #[serde(default)]
+    pub challenges: HashMap<String, RawChallenge>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChallengeDefinition {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub period: ChallengePeriod,
+    pub objective_type: ObjectiveType,
+    pub target: String,
+    pub count: i32,
+    pub rewards: Reward,
+}
+
+impl ChallengeDefinition {
+    fn from_raw(id: String, raw: RawChallenge) -> Option<Self> {
+        let objective_type = ObjectiveType::from_str(&raw.objective.objective_type)?;
+        Some(Self {
+            id,
+            name: raw.name,
+            description: raw.description,
+            period: raw.period,
+            objective_type,
+            target: raw.objective.target,
+            count: raw.objective.count.max(1),
+            rewards: Reward::from_raw(&raw.rewards),
+        })
+    }
+}
+
+/// Registry of the challenge pool and rotation settings
+#[derive(Debug, Default)]
+pub struct ChallengeRegistry {
+    settings: ChallengeSettings,
+    challenges: HashMap<String, ChallengeDefinition>,
+}
+
+impl ChallengeRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load settings and challenges from every TOML file in a directory
+    pub fn load_from_directory(&mut self, path: &Path) -> Result<(), String> {
+        if !path.exists() {
+            warn!("Challenge directory does not exist: {:?}", path);
+            return Ok(());
+        }
+
+        for entry in fs::read_dir(path).map_err(|e| e.to_string())? {
+            let entry = entry.map_err(|e| e.to_string())?;
+            let file_path = entry.path();
+
+            if file_path.extension().and_then(|s| s.to_str()) == Some("toml") {
+                let contents = fs::read_to_string(&file_path)
+                    .map_err(|e| format!("Failed to read {:?}: {}", file_path, e))?;
+                let file: RawChallengeFile = toml::from_str(&contents)
+                    .map_err(|e| format!("Failed to parse {:?}: {}", file_path, e))?;
+                self.add_file(file, &file_path);
+            }
+        }
+
+        info!("Loaded {} challenge definitions", self.challenges.len());
+        Ok(())
+    }
+
+    fn add_file(&mut self, file: RawChallengeFile, path: &Path) {
+        if let Some(settings) = file.settings {
+            self.settings = settings;
+        }
+        for (id, raw) in file.challenges {
+            let objective_type = raw.objective.objective_type.clone();
+            let Some(challenge) = ChallengeDefinition::from_raw(id.clone(), raw) else {
+                warn!("Challenge '{}' in {:?} has unknown objective type '{}', skipping", id, path, objective_type);
+                continue;
+            };
+            if self.challenges.contains_key(&id) {
+                warn!("Duplicate challenge ID '{}' in {:?}, overwriting", id, path);
+            }
+            self.challenges.insert(id, challenge);
+        }
+    }
+
+    pub fn settings(&self) -> &ChallengeSettings {
+        &self.settings
+    }
+
+    pub fn get(&self, id: &str) -> Option<&ChallengeDefinition> {
+        self.challenges.get(id)
+    }
+
+    /// The challenges active during a period, in display order
+    pub fn active(&self, period: ChallengePeriod, index: i64) -> Vec<&ChallengeDefinition> {
+        let seed = mix(index as u64 ^ ((period as u64) << 56));
+        let mut pool: Vec<_> = self.challenges.values().filter(|c| c.period == period).collect();
+        pool.sort_by_key(|c| (mix(seed ^ hash_id(&c.id)), c.id.clone()));
+        pool.truncate(self.settings.count(period));
+        pool
+    }
+}
+
+/// splitmix64 finalizer; stable across builds unlike `DefaultHasher`
+fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+/// FNV-1a hash of a challenge ID
+fn hash_id(id: &str) -> u64 {
+    id.bytes().fold(0xCBF2_9CE4_8422_2325, |hash, b| (hash ^ b as u64).wrapping_mul(0x0100_0000_01B3))
+}
+
+// ============================================================================
+// Player Progress
+// ============================================================================
+
+/// A character's progress through one period's challenges
+#[derive(Debug, Clone, Default)]
+pub struct ChallengeTrack {
+    /// Period index the progress belongs to
+    pub period: i64,
+    pub progress: HashMap<String, i32>,
+    pub completed: HashSet<String>,
+    /// Consecutive periods in which every challenge was completed
+    pub streak: i32,
+    pub last_completed_period: Option<i64>,
+}
+
+impl ChallengeTrack {
+    /// Discard progress from an earlier period. Returns true if anything was reset.
+    fn roll_over(&mut self, period: i64) -> bool {
+        if self.period == period {
+            return false;
+        }
+        self.period = period;
+        self.progress.clear();
+        self.completed.clear();
+        true
+    }
+
+    /// Streak as of the current period (broken if the previous period was missed)
+    pub fn current_streak(&self) -> i32 {
+        match self.last_completed_period {
+            Some(last) if last >= self.period - 1 => self.streak,
+            _ => 0,
+        }
+    }
+}
+
+/// A challenge that a recorded event made progress on
+#[derive(Debug, Clone)]
+pub struct ChallengeUpdate {
+    pub challenge_id: String,
+    pub period: ChallengePeriod,
+    /// Whether this event completed the challenge
+    pub completed: bool,
+    /// New streak length if this finished every challenge of the period
+    pub streak: Option<i32>,
+}
+
+/// A character's daily and weekly challenge progress
+#[derive(Debug, Clone, Default)]
+pub struct PlayerChallenges {
+    pub daily: ChallengeTrack,
+    pub weekly: ChallengeTrack,
+}
+
+impl PlayerChallenges {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn track(&self, period: ChallengePeriod) -> &ChallengeTrack {
+        match period {
+            ChallengePeriod::Daily => &self.daily,
+            ChallengePeriod::Weekly => &self.weekly,
+        }
+    }
+
+    pub fn track_mut(&mut self, period: ChallengePeriod) -> &mut ChallengeTrack {
+        match period {
+            ChallengePeriod::Daily => &mut self.daily,
+            ChallengePeriod::Weekly => &mut self.weekly,
+        }
+    }
+
+    /// Move both tracks to the current periods. Returns true if either was reset.
+    pub fn roll_over(&mut self, settings: &ChallengeSettings, now: u64) -> bool {
+        let daily = self.daily.roll_over(settings.period_index(ChallengePeriod::Daily, now));
+        let weekly = self.weekly.roll_over(settings.period_index(ChallengePeriod::Weekly, now));
+        daily || weekly
+    }
+
+    /// Count an event towards the active challenges and return those it progressed
+    pub fn record(&mut self, registry: &ChallengeRegistry, event: &QuestEvent, now: u64) -> Vec<ChallengeUpdate> {
+        self.roll_over(registry.settings(), now);
+
+        let mut updates = Vec::new();
+        for period in [ChallengePeriod::Daily, ChallengePeriod::Weekly] {
+            let track = self.track_mut(period);
+            let active = registry.active(period, track.period);
+
+            for challenge in &active {
+                if track.completed.contains(&challenge.id) {
+                    continue;
+                }
+                let Some(amount) = challenge.objective_type.progress_for(&challenge.target, event) else {
+                    continue;
+                };
+                let progress = track.progress.entry(challenge.id.clone()).or_insert(0);
+                *progress = (*progress + amount).min(challenge.count);
+                let completed = *progress >= challenge.count;
+                if completed {
+                    track.completed.insert(challenge.id.clone());
+                }
+                updates.push(ChallengeUpdate {
+                    challenge_id: challenge.id.clone(),
+                    period,
+                    completed,
+                    streak: None,
+                });
+            }
+
+            let finished_set = !active.is_empty()
+                && active.iter().all(|c| track.completed.contains(&c.id))
+                && track.last_completed_period != Some(track.period);
+            if finished_set {
+                track.streak = track.current_streak() + 1;
+                track.last_completed_period = Some(track.period);
+                if let Some(last) = updates.iter_mut().rev().find(|u| u.period == period && u.completed) {
+                    last.streak = Some(track.streak);
+                }
+            }
+        }
+        updates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> ChallengeRegistry {
+        let toml = r#"
+            [settings]
+            reset_hour = 4
+            daily_count = 2
+            weekly_count = 1
+            daily_streak_bonus = 10
+
+            [challenges.slime_cull]
+            name = "Slime Cull"
+            period = "daily"
+            objective = { type = "kill_monster", target = "slime", count = 3 }
+            rewards = { gold = 50 }
+
+            [challenges.potion_brewer]
+            name = "Potion Brewer"
+            period = "daily"
+            objective = { type = "craft_item", target = "health_potion", count = 2 }
+
+            [challenges.market_day]
+            name = "Market Day"
+            period = "weekly"
+            objective = { type = "earn_gold", target = "sales", count = 500 }
+        "#;
+        let file: RawChallengeFile = toml::from_str(toml).unwrap();
+        let mut registry = ChallengeRegistry::new();
+        registry.add_file(file, Path::new("test.toml"));
+        registry
+    }
+
+    fn kill(entity_type: &str) -> QuestEvent {
+        QuestEvent::MonsterKilled {
+            player_id: "p".to_string(),
+            entity_type: entity_type.to_string(),
+            level: 1,
+        }
+    }
+
+    #[test]
+    fn test_period_boundaries() {
+        let settings = registry().settings().clone();
+        // 2024-01-01 was a Monday
+        let monday = 19723 * DAY_MS as u64;
+
+        // Before 04:00 still belongs to the previous day
+        let early = monday + 3 * HOUR_MS as u64;
+        let late = monday + 5 * HOUR_MS as u64;
+        assert_eq!(settings.period_index(ChallengePeriod::Daily, early) + 1, settings.period_index(ChallengePeriod::Daily, late));
+        assert_eq!(settings.next_reset(ChallengePeriod::Daily, early), monday + 4 * HOUR_MS as u64);
+        assert_eq!(settings.next_reset(ChallengePeriod::Daily, late), monday + (DAY_MS + 4 * HOUR_MS) as u64);
+
+        // Weekly resets Monday 04:00
+        assert_eq!(settings.next_reset(ChallengePeriod::Weekly, early), monday + 4 * HOUR_MS as u64);
+        assert_eq!(settings.next_reset(ChallengePeriod::Weekly, late), monday + (7 * DAY_MS + 4 * HOUR_MS) as u64);
+    }
+
+    #[test]
+    fn test_rotation_is_deterministic() {
+        let registry = registry();
+        let daily: Vec<_> = registry.active(ChallengePeriod::Daily, 100).iter().map(|c| c.id.clone()).collect();
+        let again: Vec<_> = registry.active(ChallengePeriod::Daily, 100).iter().map(|c| c.id.clone()).collect();
+        assert_eq!(daily, again);
+        assert_eq!(daily.len(), 2);
+        assert_eq!(registry.active(ChallengePeriod::Weekly, 5).len(), 1);
+    }
+
+    #[test]
+    fn test_progress_completion_and_streak() {
+        let registry = registry();
+        let settings = registry.settings().clone();
+        let day = 19723 * DAY_MS as u64 + 12 * HOUR_MS as u64;
+        let mut progress = PlayerChallenges::new();
+
+        assert!(progress.record(&registry, &kill("pig"), day).is_empty());
+        progress.record(&registry, &kill("slime"), day);
+        let updates = progress.record(&registry, &kill("slime"), day);
+        assert_eq!(updates.len(), 1);
+        assert!(!updates[0].completed);
+        let updates = progress.record(&registry, &kill("slime"), day);
+        assert_eq!(updates[0].challenge_id, "slime_cull");
+        assert!(updates[0].completed);
+        assert_eq!(updates[0].streak, None);
+        assert!(progress.record(&registry, &kill("slime"), day).is_empty());
+
+        let craft = QuestEvent::ItemCrafted {
+            player_id: "p".to_string(),
+            item_id: "health_potion".to_string(),
+            count: 2,
+        };
+        let updates = progress.record(&registry, &craft, day);
+        assert_eq!(updates[0].streak, Some(1));
+        assert_eq!(settings.streak_bonus(ChallengePeriod::Daily, 1), 10);
+
+        // Next day: progress resets, streak carries over until a day is missed
+        let next_day = day + DAY_MS as u64;
+        assert!(progress.roll_over(&settings, next_day));
+        assert!(progress.daily.progress.is_empty());
+        assert_eq!(progress.daily.current_streak(), 1);
+
+        progress.roll_over(&settings, next_day + 2 * DAY_MS as u64);
+        assert_eq!(progress.daily.current_streak(), 0);
+    }
+}
@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use crate::routine::{ScheduleEntry, Waypoint};
+use crate::spawn_region::SpawnRegion;
 
 pub const CHUNK_SIZE: u32 = 32;
 
@@ -21,6 +23,10 @@ pub struct EntitySpawn {
     pub facing: Option<String>,
     /// Optional unique instance ID (for quest targets)
     pub unique_id: Option<String>,
+    /// Waypoints walked in a loop (world coordinates)
+    pub patrol: Vec<Waypoint>,
+    /// Time-of-day routes, overriding the patrol (world coordinates)
+    pub schedule: Vec<ScheduleEntry>,
 }
 
 /// Map object placed from Tiled's object layer (trees, rocks, decorations)
@@ -39,7 +45,7 @@ pub struct MapObject {
 }
 
 /// Wall edge direction
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum WallEdge {
     Down,
     Right,
@@ -72,6 +78,24 @@ pub struct Portal {
     pub target_spawn: String,
 }
 
+/// A rectangle of tiles where players may attack each other freely
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PvpZone {
+    #[serde(default)]
+    pub id: String,
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl PvpZone {
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+}
+
 /// Chunk coordinates in the world grid
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ChunkCoord {
@@ -192,6 +216,10 @@ pub struct Chunk {
     pub walls: Vec<Wall>,
     /// Portals that teleport players to other maps
     pub portals: Vec<Portal>,
+    /// PvP zones (world coordinates)
+    pub pvp_zones: Vec<PvpZone>,
+    /// Areas that keep themselves stocked with monsters (world coordinates)
+    pub spawn_regions: Vec<SpawnRegion>,
 }
 
 impl Chunk {
@@ -209,6 +237,8 @@ impl Chunk {
             objects: Vec::new(),
             walls: Vec::new(),
             portals: Vec::new(),
+            pvp_zones: Vec::new(),
+            spawn_regions: Vec::new(),
         }
     }
 
@@ -265,6 +295,8 @@ impl Chunk {
                 respawn_time_override: None,
                 facing: None,
                 unique_id: None,
+                patrol: Vec::new(),
+                schedule: Vec::new(),
             });
             chunk.entity_spawns.push(EntitySpawn {
                 entity_id: "pig".to_string(),
@@ -275,6 +307,8 @@ impl Chunk {
                 respawn_time_override: None,
                 facing: None,
                 unique_id: None,
+                patrol: Vec::new(),
+                schedule: Vec::new(),
             });
             chunk.entity_spawns.push(EntitySpawn {
                 entity_id: "pig".to_string(),
@@ -285,6 +319,8 @@ impl Chunk {
                 respawn_time_override: None,
                 facing: None,
                 unique_id: None,
+                patrol: Vec::new(),
+                schedule: Vec::new(),
             });
             chunk.entity_spawns.push(EntitySpawn {
                 entity_id: "pig".to_string(),
@@ -295,6 +331,8 @@ impl Chunk {
                 respawn_time_override: None,
                 facing: None,
                 unique_id: None,
+                patrol: Vec::new(),
+                schedule: Vec::new(),
             });
             chunk.entity_spawns.push(EntitySpawn {
                 entity_id: "pig".to_string(),
@@ -305,6 +343,8 @@ impl Chunk {
                 respawn_time_override: Some(300000), // 5 minutes
                 facing: None,
                 unique_id: Some("piggy_boss".to_string()),
+                patrol: Vec::new(),
+                schedule: Vec::new(),
             });
         }
 
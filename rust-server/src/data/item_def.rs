@@ -1,4 +1,7 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::status_effect::StatusKind;
 
 // ============================================================================
 // Item Categories
@@ -104,6 +107,28 @@ pub struct EquipmentStats {
     /// Attack range in tiles (1 = melee adjacent, higher = ranged)
     #[serde(default = "default_range")]
     pub range: i32,
+
+    /// Percent resistance to status effects, e.g. `resistances = { poison = 25 }`
+    #[serde(default)]
+    pub resistances: HashMap<StatusKind, i32>,
+}
+
+// ============================================================================
+// Furniture
+// ============================================================================
+
+/// Furniture placed in a player's house from the inventory
+#[derive(Debug, Clone, Deserialize)]
+pub struct FurnitureStats {
+    /// Object GID from objects.tsx drawn for the placed furniture
+    pub gid: u32,
+    /// Sprite width in pixels
+    pub width: u32,
+    /// Sprite height in pixels
+    pub height: u32,
+    /// Item slots when the furniture is a storage chest (0 = not storage)
+    #[serde(default)]
+    pub storage_slots: usize,
 }
 
 // ============================================================================
@@ -121,6 +146,18 @@ pub enum UseEffect {
         duration_ms: u64,
     },
     Teleport { destination: String },
+    /// Summon (or dismiss) a pet; the item is kept
+    SummonPet { pet: String },
+    /// Remove status effects (all of them when `effects` is empty)
+    Cure {
+        #[serde(default)]
+        effects: Vec<StatusKind>,
+    },
+    /// Only runs the `on_item_use` hook of world scripts
+    Script {
+        #[serde(default = "default_true")]
+        consume: bool,
+    },
 }
 
 // ============================================================================
@@ -141,6 +178,8 @@ pub struct RawItemDefinition {
     pub use_effect: Option<UseEffect>,
     /// Equipment-specific stats (only for equipment items)
     pub equipment: Option<EquipmentStats>,
+    /// Furniture-specific stats (only for furniture items)
+    pub furniture: Option<FurnitureStats>,
 }
 
 fn default_true() -> bool { true }
@@ -163,6 +202,8 @@ pub struct ItemDefinition {
     pub use_effect: Option<UseEffect>,
     /// Equipment-specific stats (only for equipment items)
     pub equipment: Option<EquipmentStats>,
+    /// Furniture-specific stats (only for furniture items)
+    pub furniture: Option<FurnitureStats>,
 }
 
 impl ItemDefinition {
@@ -181,12 +222,21 @@ impl ItemDefinition {
             sellable: raw.sellable,
             use_effect: raw.use_effect.clone(),
             equipment: raw.equipment.clone(),
+            furniture: raw.furniture.clone(),
         }
     }
 
-    /// Check if this item can be used (has a use effect)
+    /// Check if this item can be used (has a use effect or is placed as furniture)
     pub fn is_usable(&self) -> bool {
-        self.use_effect.is_some()
+        self.use_effect.is_some() || self.furniture.is_some()
+    }
+
+    /// Check if using this item uses it up (pet items can be used again,
+    /// furniture is only taken once it has been placed, and script items
+    /// can opt out)
+    pub fn consumed_on_use(&self) -> bool {
+        self.furniture.is_none()
+            && !matches!(self.use_effect, Some(UseEffect::SummonPet { .. }) | Some(UseEffect::Script { consume: false }))
     }
 
     /// Check if this is a consumable
@@ -49,6 +49,7 @@ pub struct CharacterData {
     pub played_time: i64,       // Seconds played
     pub created_at: Option<String>,
     pub is_admin: bool,         // Game Master privileges
+    pub perished: bool,         // Died under a hardcore death rule; can no longer play
 }
 
 
@@ -175,6 +176,36 @@ impl Database {
                 .ok();
         }
 
+        // Migration: Add title column (selected achievement title) if it doesn't exist
+        let title_exists: bool = sqlx::query_scalar(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('characters') WHERE name = 'title'"
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap_or(false);
+
+        if !title_exists {
+            sqlx::query("ALTER TABLE characters ADD COLUMN title TEXT DEFAULT NULL")
+                .execute(pool)
+                .await
+                .ok();
+        }
+
+        // Migration: Add perished column (hardcore death) if it doesn't exist
+        let perished_exists: bool = sqlx::query_scalar(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('characters') WHERE name = 'perished'"
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap_or(false);
+
+        if !perished_exists {
+            sqlx::query("ALTER TABLE characters ADD COLUMN perished BOOLEAN DEFAULT FALSE")
+                .execute(pool)
+                .await
+                .ok();
+        }
+
         // Character quest tables (renamed from player_*)
         sqlx::query(
             r#"
@@ -223,6 +254,229 @@ impl Database {
         .execute(pool)
         .await?;
 
+        // Log of completed player-to-player trades
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS trades (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                trade_id TEXT NOT NULL,
+                character_a_id INTEGER NOT NULL,
+                character_b_id INTEGER NOT NULL,
+                character_a_name TEXT NOT NULL,
+                character_b_name TEXT NOT NULL,
+                items_a_json TEXT NOT NULL DEFAULT '[]',
+                items_b_json TEXT NOT NULL DEFAULT '[]',
+                gold_a INTEGER NOT NULL DEFAULT 0,
+                gold_b INTEGER NOT NULL DEFAULT 0,
+                completed_at TEXT DEFAULT CURRENT_TIMESTAMP
+            )
+            "#,
+        )
+        .execute(pool)
+        .await?;
+
+        // Log of player kills for the PvP leaderboard
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS pvp_kills (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                killer_character_id INTEGER NOT NULL,
+                victim_character_id INTEGER NOT NULL,
+                killer_name TEXT NOT NULL,
+                victim_name TEXT NOT NULL,
+                zone TEXT NOT NULL,
+                in_pvp_zone BOOLEAN NOT NULL DEFAULT FALSE,
+                killed_at TEXT DEFAULT CURRENT_TIMESTAMP
+            )
+            "#,
+        )
+        .execute(pool)
+        .await?;
+
+        // Achievement progress counters and unlocks
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS character_achievement_counters (
+                character_id INTEGER NOT NULL,
+                counter TEXT NOT NULL,
+                value INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY(character_id, counter),
+                FOREIGN KEY(character_id) REFERENCES characters(id)
+            )
+            "#,
+        )
+        .execute(pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS character_achievements (
+                character_id INTEGER NOT NULL,
+                achievement_id TEXT NOT NULL,
+                unlocked_at INTEGER NOT NULL,
+                PRIMARY KEY(character_id, achievement_id),
+                FOREIGN KEY(character_id) REFERENCES characters(id)
+            )
+            "#,
+        )
+        .execute(pool)
+        .await?;
+
+        // Daily/weekly challenge progress and streaks
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS character_challenges (
+                character_id INTEGER NOT NULL,
+                period TEXT NOT NULL,
+                challenge_id TEXT NOT NULL,
+                progress INTEGER NOT NULL DEFAULT 0,
+                completed INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY(character_id, period, challenge_id),
+                FOREIGN KEY(character_id) REFERENCES characters(id)
+            )
+            "#,
+        )
+        .execute(pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS character_challenge_streaks (
+                character_id INTEGER NOT NULL,
+                period TEXT NOT NULL,
+                period_index INTEGER NOT NULL,
+                streak INTEGER NOT NULL DEFAULT 0,
+                last_completed_period INTEGER,
+                PRIMARY KEY(character_id, period),
+                FOREIGN KEY(character_id) REFERENCES characters(id)
+            )
+            "#,
+        )
+        .execute(pool)
+        .await?;
+
+        // Per-character faction standing
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS character_reputation (
+                character_id INTEGER NOT NULL,
+                faction_id TEXT NOT NULL,
+                standing INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY(character_id, faction_id),
+                FOREIGN KEY(character_id) REFERENCES characters(id)
+            )
+            "#,
+        )
+        .execute(pool)
+        .await?;
+
+        // Pet levels (active = the pet currently summoned)
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS character_pets (
+                character_id INTEGER NOT NULL,
+                pet_id TEXT NOT NULL,
+                level INTEGER NOT NULL DEFAULT 1,
+                exp INTEGER NOT NULL DEFAULT 0,
+                active INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY(character_id, pet_id),
+                FOREIGN KEY(character_id) REFERENCES characters(id)
+            )
+            "#,
+        )
+        .execute(pool)
+        .await?;
+
+        // Player houses: the house a character owns, its furniture (chest
+        // contents use the inventory JSON format) and the guest list
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS character_houses (
+                character_id INTEGER PRIMARY KEY,
+                house_id TEXT NOT NULL,
+                FOREIGN KEY(character_id) REFERENCES characters(id)
+            )
+            "#,
+        )
+        .execute(pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS house_furniture (
+                character_id INTEGER NOT NULL,
+                furniture_id INTEGER NOT NULL,
+                item_id TEXT NOT NULL,
+                x INTEGER NOT NULL,
+                y INTEGER NOT NULL,
+                storage_slots INTEGER NOT NULL DEFAULT 0,
+                storage_json TEXT NOT NULL DEFAULT '[]',
+                PRIMARY KEY(character_id, furniture_id),
+                FOREIGN KEY(character_id) REFERENCES characters(id)
+            )
+            "#,
+        )
+        .execute(pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS house_guests (
+                character_id INTEGER NOT NULL,
+                guest_name TEXT NOT NULL,
+                PRIMARY KEY(character_id, guest_name),
+                FOREIGN KEY(character_id) REFERENCES characters(id)
+            )
+            "#,
+        )
+        .execute(pool)
+        .await?;
+
+        // Guilds, their ranks (index 0 = leader) and members
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS guilds (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT UNIQUE NOT NULL COLLATE NOCASE,
+                tag TEXT UNIQUE NOT NULL COLLATE NOCASE,
+                motd TEXT NOT NULL DEFAULT '',
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP
+            )
+            "#,
+        )
+        .execute(pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS guild_ranks (
+                guild_id INTEGER NOT NULL,
+                rank_index INTEGER NOT NULL,
+                name TEXT NOT NULL,
+                permissions INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY(guild_id, rank_index),
+                FOREIGN KEY(guild_id) REFERENCES guilds(id)
+            )
+            "#,
+        )
+        .execute(pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS guild_members (
+                character_id INTEGER PRIMARY KEY,
+                guild_id INTEGER NOT NULL,
+                rank_index INTEGER NOT NULL,
+                joined_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY(character_id) REFERENCES characters(id),
+                FOREIGN KEY(guild_id) REFERENCES guilds(id)
+            )
+            "#,
+        )
+        .execute(pool)
+        .await?;
+
         tracing::info!("Database migrations complete");
         Ok(())
     }
@@ -310,7 +564,7 @@ impl Database {
             r#"SELECT id, account_id, name, gender, skin, hair_style, hair_color, x, y, hp, gold,
                 equipped_head, equipped_body, equipped_weapon, equipped_back, equipped_feet,
                 equipped_ring, equipped_gloves, equipped_necklace, equipped_belt,
-                inventory_json, skills_json, played_time, is_admin, created_at
+                inventory_json, skills_json, played_time, is_admin, perished, created_at
             FROM characters WHERE account_id = ? ORDER BY created_at DESC"#,
         )
         .bind(account_id)
@@ -361,6 +615,7 @@ impl Database {
                 played_time: r.get("played_time"),
                 created_at: r.get("created_at"),
                 is_admin: r.try_get::<bool, _>("is_admin").unwrap_or(false),
+                perished: r.try_get::<bool, _>("perished").unwrap_or(false),
             }
         }).collect())
     }
@@ -462,7 +717,7 @@ impl Database {
             r#"SELECT id, account_id, name, gender, skin, hair_style, hair_color, x, y, hp, gold,
                 equipped_head, equipped_body, equipped_weapon, equipped_back, equipped_feet,
                 equipped_ring, equipped_gloves, equipped_necklace, equipped_belt,
-                inventory_json, skills_json, played_time, is_admin, created_at
+                inventory_json, skills_json, played_time, is_admin, perished, created_at
             FROM characters WHERE id = ?"#,
         )
         .bind(character_id)
@@ -513,6 +768,7 @@ impl Database {
                 played_time: r.get("played_time"),
                 created_at: r.get("created_at"),
                 is_admin: r.try_get::<bool, _>("is_admin").unwrap_or(false),
+                perished: r.try_get::<bool, _>("perished").unwrap_or(false),
             }
         }))
     }
@@ -532,6 +788,42 @@ impl Database {
             .bind(character_id)
             .execute(&self.pool)
             .await?;
+        sqlx::query("DELETE FROM character_reputation WHERE character_id = ?")
+            .bind(character_id)
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("DELETE FROM character_achievement_counters WHERE character_id = ?")
+            .bind(character_id)
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("DELETE FROM character_achievements WHERE character_id = ?")
+            .bind(character_id)
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("DELETE FROM character_challenges WHERE character_id = ?")
+            .bind(character_id)
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("DELETE FROM character_challenge_streaks WHERE character_id = ?")
+            .bind(character_id)
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("DELETE FROM character_pets WHERE character_id = ?")
+            .bind(character_id)
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("DELETE FROM character_houses WHERE character_id = ?")
+            .bind(character_id)
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("DELETE FROM house_furniture WHERE character_id = ?")
+            .bind(character_id)
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("DELETE FROM house_guests WHERE character_id = ?")
+            .bind(character_id)
+            .execute(&self.pool)
+            .await?;
 
         // Delete the character (only if owned by this account)
         let result = sqlx::query("DELETE FROM characters WHERE id = ? AND account_id = ?")
@@ -542,6 +834,7 @@ impl Database {
 
         let deleted = result.rows_affected() > 0;
         if deleted {
+            self.remove_guild_member(character_id).await?;
             tracing::info!("Deleted character {} for account {}", character_id, account_id);
         }
         Ok(deleted)
@@ -610,6 +903,15 @@ impl Database {
         Ok(())
     }
 
+    /// Permanently retire a character that died under a hardcore death rule
+    pub async fn mark_character_perished(&self, character_id: i64) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE characters SET perished = TRUE WHERE id = ?")
+            .bind(character_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
     // =========================================================================
     // Character Quest State Functions (new - uses character_id)
     // =========================================================================
@@ -782,4 +1084,625 @@ impl Database {
 
         Ok(())
     }
+
+    // =========================================================================
+    // Character Achievements
+    // =========================================================================
+
+    /// Load a character's achievement counters, unlocks and selected title
+    pub async fn load_character_achievements(&self, character_id: i64) -> Result<crate::achievement::PlayerAchievements, sqlx::Error> {
+        let mut achievements = crate::achievement::PlayerAchievements::new();
+
+        let counter_rows = sqlx::query("SELECT counter, value FROM character_achievement_counters WHERE character_id = ?")
+            .bind(character_id)
+            .fetch_all(&self.pool)
+            .await?;
+        for row in counter_rows {
+            achievements.counters.insert(row.get("counter"), row.get("value"));
+        }
+
+        let unlock_rows = sqlx::query("SELECT achievement_id, unlocked_at FROM character_achievements WHERE character_id = ?")
+            .bind(character_id)
+            .fetch_all(&self.pool)
+            .await?;
+        for row in unlock_rows {
+            let unlocked_at: i64 = row.get("unlocked_at");
+            achievements.unlocked.insert(row.get("achievement_id"), unlocked_at as u64);
+        }
+
+        achievements.active_title = sqlx::query_scalar("SELECT title FROM characters WHERE id = ?")
+            .bind(character_id)
+            .fetch_optional(&self.pool)
+            .await?
+            .flatten();
+
+        Ok(achievements)
+    }
+
+    /// Save a character's achievement counters and selected title
+    /// (unlocks are written immediately by `record_achievement_unlock`)
+    pub async fn save_character_achievements(&self, character_id: i64, achievements: &crate::achievement::PlayerAchievements) -> Result<(), sqlx::Error> {
+        for (counter, value) in &achievements.counters {
+            sqlx::query(
+                r#"INSERT INTO character_achievement_counters (character_id, counter, value)
+                   VALUES (?, ?, ?)
+                   ON CONFLICT(character_id, counter) DO UPDATE SET value = excluded.value"#
+            )
+            .bind(character_id)
+            .bind(counter)
+            .bind(value)
+            .execute(&self.pool)
+            .await?;
+        }
+
+        sqlx::query("UPDATE characters SET title = ? WHERE id = ?")
+            .bind(&achievements.active_title)
+            .bind(character_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Record that a character unlocked an achievement
+    pub async fn record_achievement_unlock(&self, character_id: i64, achievement_id: &str, unlocked_at: u64) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT OR IGNORE INTO character_achievements (character_id, achievement_id, unlocked_at) VALUES (?, ?, ?)"
+        )
+        .bind(character_id)
+        .bind(achievement_id)
+        .bind(unlocked_at as i64)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    // =========================================================================
+    // Character Challenges
+    // =========================================================================
+
+    /// Load a character's daily/weekly challenge progress and streaks
+    pub async fn load_character_challenges(&self, character_id: i64) -> Result<crate::challenge::PlayerChallenges, sqlx::Error> {
+        use crate::challenge::ChallengePeriod;
+
+        let mut challenges = crate::challenge::PlayerChallenges::new();
+
+        let streak_rows = sqlx::query(
+            "SELECT period, period_index, streak, last_completed_period FROM character_challenge_streaks WHERE character_id = ?"
+        )
+            .bind(character_id)
+            .fetch_all(&self.pool)
+            .await?;
+        for row in streak_rows {
+            let period: String = row.get("period");
+            let Some(period) = ChallengePeriod::from_str(&period) else {
+                continue;
+            };
+            let track = challenges.track_mut(period);
+            track.period = row.get("period_index");
+            track.streak = row.get::<i64, _>("streak") as i32;
+            track.last_completed_period = row.get("last_completed_period");
+        }
+
+        let progress_rows = sqlx::query(
+            "SELECT period, challenge_id, progress, completed FROM character_challenges WHERE character_id = ?"
+        )
+            .bind(character_id)
+            .fetch_all(&self.pool)
+            .await?;
+        for row in progress_rows {
+            let period: String = row.get("period");
+            let Some(period) = ChallengePeriod::from_str(&period) else {
+                continue;
+            };
+            let track = challenges.track_mut(period);
+            let challenge_id: String = row.get("challenge_id");
+            if row.get::<i64, _>("completed") != 0 {
+                track.completed.insert(challenge_id.clone());
+            }
+            track.progress.insert(challenge_id, row.get::<i64, _>("progress") as i32);
+        }
+
+        Ok(challenges)
+    }
+
+    /// Save a character's challenge progress, replacing the previous period's rows
+    pub async fn save_character_challenges(&self, character_id: i64, challenges: &crate::challenge::PlayerChallenges) -> Result<(), sqlx::Error> {
+        use crate::challenge::ChallengePeriod;
+
+        sqlx::query("DELETE FROM character_challenges WHERE character_id = ?")
+            .bind(character_id)
+            .execute(&self.pool)
+            .await?;
+
+        for period in [ChallengePeriod::Daily, ChallengePeriod::Weekly] {
+            let track = challenges.track(period);
+            for (challenge_id, progress) in &track.progress {
+                sqlx::query(
+                    "INSERT INTO character_challenges (character_id, period, challenge_id, progress, completed) VALUES (?, ?, ?, ?, ?)"
+                )
+                .bind(character_id)
+                .bind(period.as_str())
+                .bind(challenge_id)
+                .bind(*progress as i64)
+                .bind(track.completed.contains(challenge_id) as i64)
+                .execute(&self.pool)
+                .await?;
+            }
+
+            sqlx::query(
+                r#"INSERT INTO character_challenge_streaks (character_id, period, period_index, streak, last_completed_period)
+                   VALUES (?, ?, ?, ?, ?)
+                   ON CONFLICT(character_id, period) DO UPDATE SET
+                       period_index = excluded.period_index,
+                       streak = excluded.streak,
+                       last_completed_period = excluded.last_completed_period"#
+            )
+            .bind(character_id)
+            .bind(period.as_str())
+            .bind(track.period)
+            .bind(track.streak as i64)
+            .bind(track.last_completed_period)
+            .execute(&self.pool)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    // =========================================================================
+    // Character Reputation
+    // =========================================================================
+
+    /// Load a character's standing with each faction
+    pub async fn load_character_reputation(&self, character_id: i64) -> Result<crate::reputation::PlayerReputation, sqlx::Error> {
+        let rows = sqlx::query("SELECT faction_id, standing FROM character_reputation WHERE character_id = ?")
+            .bind(character_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut reputation = crate::reputation::PlayerReputation::new();
+        for row in rows {
+            reputation.standings.insert(row.get("faction_id"), row.get("standing"));
+        }
+        Ok(reputation)
+    }
+
+    /// Save a character's faction standings
+    pub async fn save_character_reputation(&self, character_id: i64, reputation: &crate::reputation::PlayerReputation) -> Result<(), sqlx::Error> {
+        for (faction_id, standing) in &reputation.standings {
+            sqlx::query(
+                r#"INSERT INTO character_reputation (character_id, faction_id, standing)
+                   VALUES (?, ?, ?)
+                   ON CONFLICT(character_id, faction_id) DO UPDATE SET standing = excluded.standing"#
+            )
+            .bind(character_id)
+            .bind(faction_id)
+            .bind(standing)
+            .execute(&self.pool)
+            .await?;
+        }
+        Ok(())
+    }
+
+    // =========================================================================
+    // Character Pets
+    // =========================================================================
+
+    /// Load a character's pet levels and active pet
+    pub async fn load_character_pets(&self, character_id: i64) -> Result<crate::pet::PlayerPets, sqlx::Error> {
+        let rows = sqlx::query("SELECT pet_id, level, exp, active FROM character_pets WHERE character_id = ?")
+            .bind(character_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut pets = crate::pet::PlayerPets::new();
+        for row in rows {
+            let pet_id: String = row.get("pet_id");
+            if row.get::<i64, _>("active") != 0 {
+                pets.active = Some(pet_id.clone());
+            }
+            pets.owned.insert(pet_id, crate::pet::PetProgress {
+                level: row.get::<i64, _>("level") as i32,
+                exp: row.get("exp"),
+            });
+        }
+        Ok(pets)
+    }
+
+    /// Save a character's pet levels and which pet is out
+    pub async fn save_character_pets(&self, character_id: i64, pets: &crate::pet::PlayerPets) -> Result<(), sqlx::Error> {
+        for (pet_id, progress) in &pets.owned {
+            sqlx::query(
+                r#"INSERT INTO character_pets (character_id, pet_id, level, exp, active)
+                   VALUES (?, ?, ?, ?, ?)
+                   ON CONFLICT(character_id, pet_id) DO UPDATE SET
+                       level = excluded.level,
+                       exp = excluded.exp,
+                       active = excluded.active"#
+            )
+            .bind(character_id)
+            .bind(pet_id)
+            .bind(progress.level as i64)
+            .bind(progress.exp)
+            .bind((pets.active.as_deref() == Some(pet_id.as_str())) as i64)
+            .execute(&self.pool)
+            .await?;
+        }
+        Ok(())
+    }
+
+    // =========================================================================
+    // Player Houses
+    // =========================================================================
+
+    /// Load a character's house, furniture and guest list
+    pub async fn load_character_house(&self, character_id: i64) -> Result<crate::house::PlayerHouse, sqlx::Error> {
+        let mut house = crate::house::PlayerHouse::new();
+        house.house_id = sqlx::query_scalar("SELECT house_id FROM character_houses WHERE character_id = ?")
+            .bind(character_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        let rows = sqlx::query(
+            "SELECT furniture_id, item_id, x, y, storage_slots, storage_json FROM house_furniture WHERE character_id = ? ORDER BY furniture_id"
+        )
+        .bind(character_id)
+        .fetch_all(&self.pool)
+        .await?;
+        for row in rows {
+            let mut furniture = crate::house::PlacedFurniture {
+                id: row.get::<i64, _>("furniture_id") as i32,
+                item_id: row.get("item_id"),
+                x: row.get::<i64, _>("x") as i32,
+                y: row.get::<i64, _>("y") as i32,
+                storage: vec![None; row.get::<i64, _>("storage_slots").max(0) as usize],
+            };
+            furniture.load_storage(&row.get::<String, _>("storage_json"));
+            house.furniture.push(furniture);
+        }
+
+        house.guests = sqlx::query_scalar("SELECT guest_name FROM house_guests WHERE character_id = ? ORDER BY guest_name")
+            .bind(character_id)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(house)
+    }
+
+    /// Save a character's house, replacing the previous furniture and guest rows
+    pub async fn save_character_house(&self, character_id: i64, house: &crate::house::PlayerHouse) -> Result<(), sqlx::Error> {
+        let Some(house_id) = &house.house_id else {
+            return Ok(());
+        };
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            r#"INSERT INTO character_houses (character_id, house_id) VALUES (?, ?)
+               ON CONFLICT(character_id) DO UPDATE SET house_id = excluded.house_id"#
+        )
+        .bind(character_id)
+        .bind(house_id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM house_furniture WHERE character_id = ?")
+            .bind(character_id)
+            .execute(&mut *tx)
+            .await?;
+        for furniture in &house.furniture {
+            sqlx::query(
+                "INSERT INTO house_furniture (character_id, furniture_id, item_id, x, y, storage_slots, storage_json) VALUES (?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(character_id)
+            .bind(furniture.id as i64)
+            .bind(&furniture.item_id)
+            .bind(furniture.x as i64)
+            .bind(furniture.y as i64)
+            .bind(furniture.storage.len() as i64)
+            .bind(furniture.storage_json())
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        sqlx::query("DELETE FROM house_guests WHERE character_id = ?")
+            .bind(character_id)
+            .execute(&mut *tx)
+            .await?;
+        for guest in &house.guests {
+            sqlx::query("INSERT INTO house_guests (character_id, guest_name) VALUES (?, ?)")
+                .bind(character_id)
+                .bind(guest)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await
+    }
+
+    // =========================================================================
+    // Trade Log
+    // =========================================================================
+
+    /// Record a completed trade between two characters
+    pub async fn record_trade(
+        &self,
+        character_a_id: i64,
+        character_b_id: i64,
+        trade: &crate::trade::CompletedTrade,
+    ) -> Result<(), sqlx::Error> {
+        let items_a_json = serde_json::to_string(&trade.items_a).unwrap_or_else(|_| "[]".to_string());
+        let items_b_json = serde_json::to_string(&trade.items_b).unwrap_or_else(|_| "[]".to_string());
+
+        sqlx::query(
+            r#"INSERT INTO trades (trade_id, character_a_id, character_b_id, character_a_name, character_b_name,
+                                   items_a_json, items_b_json, gold_a, gold_b)
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"#
+        )
+        .bind(&trade.trade_id)
+        .bind(character_a_id)
+        .bind(character_b_id)
+        .bind(&trade.name_a)
+        .bind(&trade.name_b)
+        .bind(&items_a_json)
+        .bind(&items_b_json)
+        .bind(trade.gold_a)
+        .bind(trade.gold_b)
+        .execute(&self.pool)
+        .await?;
+
+        tracing::debug!("Recorded trade {} ({} <-> {})", trade.trade_id, trade.name_a, trade.name_b);
+        Ok(())
+    }
+
+    // =========================================================================
+    // PvP Kill Log
+    // =========================================================================
+
+    /// Record one player killing another
+    pub async fn record_pvp_kill(
+        &self,
+        killer_character_id: i64,
+        victim_character_id: i64,
+        killer_name: &str,
+        victim_name: &str,
+        zone: &str,
+        in_pvp_zone: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"INSERT INTO pvp_kills (killer_character_id, victim_character_id, killer_name, victim_name, zone, in_pvp_zone)
+               VALUES (?, ?, ?, ?, ?, ?)"#
+        )
+        .bind(killer_character_id)
+        .bind(victim_character_id)
+        .bind(killer_name)
+        .bind(victim_name)
+        .bind(zone)
+        .bind(in_pvp_zone)
+        .execute(&self.pool)
+        .await?;
+
+        tracing::debug!("Recorded PvP kill: {} killed {} in {}", killer_name, victim_name, zone);
+        Ok(())
+    }
+
+    // =========================================================================
+    // Guilds
+    // =========================================================================
+
+    /// Create a guild with the default ranks and make the character its leader
+    pub async fn create_guild(&self, name: &str, tag: &str, leader_character_id: i64) -> Result<i64, String> {
+        let mut tx = self.pool.begin().await.map_err(|e| format!("Database error: {}", e))?;
+
+        let result = sqlx::query("INSERT INTO guilds (name, tag) VALUES (?, ?)")
+            .bind(name)
+            .bind(tag)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                if e.to_string().contains("UNIQUE constraint failed") {
+                    "A guild with that name or tag already exists.".to_string()
+                } else {
+                    format!("Database error: {}", e)
+                }
+            })?;
+        let guild_id = result.last_insert_rowid();
+
+        for rank in crate::guild::default_ranks() {
+            sqlx::query("INSERT INTO guild_ranks (guild_id, rank_index, name, permissions) VALUES (?, ?, ?, ?)")
+                .bind(guild_id)
+                .bind(rank.index)
+                .bind(&rank.name)
+                .bind(rank.permissions as i64)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Database error: {}", e))?;
+        }
+
+        sqlx::query("INSERT INTO guild_members (character_id, guild_id, rank_index) VALUES (?, ?, ?)")
+            .bind(leader_character_id)
+            .bind(guild_id)
+            .bind(crate::guild::GUILD_LEADER_RANK)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                if e.to_string().contains("UNIQUE constraint failed") {
+                    "You are already in a guild.".to_string()
+                } else {
+                    format!("Database error: {}", e)
+                }
+            })?;
+
+        tx.commit().await.map_err(|e| format!("Database error: {}", e))?;
+        tracing::info!("Created guild [{}] {} (id: {}) led by character {}", tag, name, guild_id, leader_character_id);
+        Ok(guild_id)
+    }
+
+    /// Get a character's guild membership (with their rank), if any
+    pub async fn get_guild_membership(&self, character_id: i64) -> Result<Option<crate::guild::GuildMembership>, sqlx::Error> {
+        let row = sqlx::query(
+            r#"SELECT g.id, g.name, g.tag, m.rank_index, r.name AS rank_name, r.permissions
+               FROM guild_members m
+               JOIN guilds g ON g.id = m.guild_id
+               JOIN guild_ranks r ON r.guild_id = m.guild_id AND r.rank_index = m.rank_index
+               WHERE m.character_id = ?"#
+        )
+        .bind(character_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| crate::guild::GuildMembership {
+            guild_id: row.get("id"),
+            guild_name: row.get("name"),
+            tag: row.get("tag"),
+            rank: crate::guild::GuildRank {
+                index: row.get("rank_index"),
+                name: row.get("rank_name"),
+                permissions: row.get::<i64, _>("permissions") as u32,
+            },
+        }))
+    }
+
+    /// Get a guild's ranks ordered from leader down
+    pub async fn get_guild_ranks(&self, guild_id: i64) -> Result<Vec<crate::guild::GuildRank>, sqlx::Error> {
+        let rows = sqlx::query("SELECT rank_index, name, permissions FROM guild_ranks WHERE guild_id = ? ORDER BY rank_index")
+            .bind(guild_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.iter().map(|row| crate::guild::GuildRank {
+            index: row.get("rank_index"),
+            name: row.get("name"),
+            permissions: row.get::<i64, _>("permissions") as u32,
+        }).collect())
+    }
+
+    /// Get a guild's message of the day
+    pub async fn get_guild_motd(&self, guild_id: i64) -> Result<String, sqlx::Error> {
+        let motd: Option<String> = sqlx::query_scalar("SELECT motd FROM guilds WHERE id = ?")
+            .bind(guild_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(motd.unwrap_or_default())
+    }
+
+    pub async fn set_guild_motd(&self, guild_id: i64, motd: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE guilds SET motd = ? WHERE id = ?")
+            .bind(motd)
+            .bind(guild_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Get a guild's members ordered by rank, then join date
+    pub async fn get_guild_roster(&self, guild_id: i64) -> Result<Vec<crate::guild::GuildRosterEntry>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"SELECT m.character_id, c.name, m.rank_index, r.name AS rank_name
+               FROM guild_members m
+               JOIN characters c ON c.id = m.character_id
+               JOIN guild_ranks r ON r.guild_id = m.guild_id AND r.rank_index = m.rank_index
+               WHERE m.guild_id = ?
+               ORDER BY m.rank_index, m.joined_at"#
+        )
+        .bind(guild_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(|row| crate::guild::GuildRosterEntry {
+            character_id: row.get("character_id"),
+            name: row.get("name"),
+            rank_index: row.get("rank_index"),
+            rank_name: row.get("rank_name"),
+        }).collect())
+    }
+
+    pub async fn count_guild_members(&self, guild_id: i64) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM guild_members WHERE guild_id = ?")
+            .bind(guild_id)
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    pub async fn add_guild_member(&self, guild_id: i64, character_id: i64, rank_index: i32) -> Result<(), sqlx::Error> {
+        sqlx::query("INSERT INTO guild_members (character_id, guild_id, rank_index) VALUES (?, ?, ?)")
+            .bind(character_id)
+            .bind(guild_id)
+            .bind(rank_index)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn set_guild_member_rank(&self, character_id: i64, rank_index: i32) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE guild_members SET rank_index = ? WHERE character_id = ?")
+            .bind(rank_index)
+            .bind(character_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Hand leadership to another member; the old leader becomes the next rank down
+    pub async fn transfer_guild_leadership(&self, from_character_id: i64, to_character_id: i64) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("UPDATE guild_members SET rank_index = ? WHERE character_id = ?")
+            .bind(crate::guild::GUILD_LEADER_RANK + 1)
+            .bind(from_character_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("UPDATE guild_members SET rank_index = ? WHERE character_id = ?")
+            .bind(crate::guild::GUILD_LEADER_RANK)
+            .bind(to_character_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await
+    }
+
+    /// Remove a character from their guild. If they led it, the highest-ranked,
+    /// longest-standing member takes over; an empty guild is deleted.
+    pub async fn remove_guild_member(&self, character_id: i64) -> Result<(), sqlx::Error> {
+        let Some(membership) = self.get_guild_membership(character_id).await? else {
+            return Ok(());
+        };
+
+        sqlx::query("DELETE FROM guild_members WHERE character_id = ?")
+            .bind(character_id)
+            .execute(&self.pool)
+            .await?;
+
+        if membership.rank.index == crate::guild::GUILD_LEADER_RANK {
+            let successor: Option<i64> = sqlx::query_scalar(
+                "SELECT character_id FROM guild_members WHERE guild_id = ? ORDER BY rank_index, joined_at LIMIT 1"
+            )
+            .bind(membership.guild_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+            match successor {
+                Some(successor) => self.set_guild_member_rank(successor, crate::guild::GUILD_LEADER_RANK).await?,
+                None => self.disband_guild(membership.guild_id).await?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Delete a guild, its ranks and all memberships
+    pub async fn disband_guild(&self, guild_id: i64) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM guild_members WHERE guild_id = ?")
+            .bind(guild_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM guild_ranks WHERE guild_id = ?")
+            .bind(guild_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM guilds WHERE id = ?")
+            .bind(guild_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        tracing::info!("Disbanded guild {}", guild_id);
+        Ok(())
+    }
 }
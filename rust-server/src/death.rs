@@ -0,0 +1,275 @@
+//! Death penalties and gravestones
+//!
+//! Rules from `data/death/*.toml` pick a `DeathPenalty` per zone, falling back
+//! to `[default]`; the sample data there documents each penalty.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use tracing::{info, warn};
+
+use crate::data::ItemRegistry;
+use crate::item::{Inventory, InventorySlot, InventorySlotUpdate};
+
+/// Zone key of the open world
+pub const OVERWORLD_ZONE: &str = "overworld";
+
+/// Owners must stand this close to a gravestone (Chebyshev tiles) to loot it
+pub const GRAVESTONE_REACH: i32 = 1;
+
+// ============================================================================
+// Rules
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "penalty", rename_all = "snake_case")]
+pub enum DeathPenalty {
+    /// Nothing is lost
+    KeepAll,
+    /// Unequipped items drop into a gravestone only the owner can loot, which
+    /// crumbles after `duration_secs`
+    Gravestone {
+        #[serde(default = "default_gravestone_secs")]
+        duration_secs: u64,
+    },
+    /// `percent` of carried gold is lost
+    GoldLoss {
+        percent: u8,
+    },
+    /// The character is permanently dead
+    Hardcore,
+}
+
+fn default_gravestone_secs() -> u64 {
+    600
+}
+
+impl DeathPenalty {
+    /// Identifier sent to clients
+    pub fn name(&self) -> &'static str {
+        match self {
+            DeathPenalty::KeepAll => "keep_all",
+            DeathPenalty::Gravestone { .. } => "gravestone",
+            DeathPenalty::GoldLoss { .. } => "gold_loss",
+            DeathPenalty::Hardcore => "hardcore",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RawDeathFile {
+    #[serde(default)]
+    pub default: Option<DeathPenalty>,
+    #[serde(default)]
+    pub zones: HashMap<String, DeathPenalty>,
+}
+
+#[derive(Debug)]
+pub struct DeathRules {
+    default: DeathPenalty,
+    zones: HashMap<String, DeathPenalty>,
+}
+
+impl Default for DeathRules {
+    fn default() -> Self {
+        Self {
+            default: DeathPenalty::KeepAll,
+            zones: HashMap::new(),
+        }
+    }
+}
+
+impl DeathRules {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load every death rule file from `<data_dir>/death`
+    pub fn load_from_directory(&mut self, data_dir: &Path) -> Result<(), String> {
+        let path = data_dir.join("death");
+        if !path.exists() {
+            warn!("Death rule directory does not exist: {:?}", path);
+            return Ok(());
+        }
+
+        for entry in fs::read_dir(&path).map_err(|e| e.to_string())? {
+            let file_path = entry.map_err(|e| e.to_string())?.path();
+            if file_path.extension().is_some_and(|ext| ext == "toml") {
+                let content = fs::read_to_string(&file_path)
+                    .map_err(|e| format!("Failed to read {:?}: {}", file_path, e))?;
+                let raw: RawDeathFile = toml::from_str(&content)
+                    .map_err(|e| format!("Failed to parse {:?}: {}", file_path, e))?;
+                if let Some(default) = raw.default {
+                    self.default = default;
+                }
+                self.zones.extend(raw.zones);
+            }
+        }
+
+        info!("Loaded death rules for {} zones (default: {})", self.zones.len(), self.default.name());
+        Ok(())
+    }
+
+    /// Penalty for dying on a map: exact zone, then the longest matching `prefix*`, then the default
+    pub fn rule_for(&self, map_id: &str) -> &DeathPenalty {
+        if let Some(rule) = self.zones.get(map_id) {
+            return rule;
+        }
+        self.zones.iter()
+            .filter_map(|(key, rule)| key.strip_suffix('*').map(|prefix| (prefix, rule)))
+            .filter(|(prefix, _)| map_id.starts_with(prefix))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, rule)| rule)
+            .unwrap_or(&self.default)
+    }
+}
+
+/// Gold lost to a `gold_loss` penalty (rounded down)
+pub fn gold_lost(gold: i32, percent: u8) -> i32 {
+    (gold.max(0) as i64 * percent.min(100) as i64 / 100) as i32
+}
+
+// ============================================================================
+// Gravestones
+// ============================================================================
+
+#[derive(Debug, Clone)]
+pub struct Gravestone {
+    pub id: String,
+    pub owner_id: String,
+    pub owner_name: String,
+    pub x: i32,
+    pub y: i32,
+    /// Instance the owner died in (None = overworld)
+    pub instance_id: Option<String>,
+    pub items: Vec<InventorySlot>,
+    pub expires_at: u64,
+}
+
+impl Gravestone {
+    pub fn is_expired(&self, current_time: u64) -> bool {
+        current_time >= self.expires_at
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Seconds left before the gravestone crumbles
+    pub fn remaining_secs(&self, current_time: u64) -> u64 {
+        self.expires_at.saturating_sub(current_time) / 1000
+    }
+
+    pub fn in_reach(&self, x: i32, y: i32) -> bool {
+        (self.x - x).abs() <= GRAVESTONE_REACH && (self.y - y).abs() <= GRAVESTONE_REACH
+    }
+
+    pub fn to_update(&self) -> Vec<InventorySlotUpdate> {
+        self.items.iter()
+            .enumerate()
+            .map(|(i, item)| InventorySlotUpdate {
+                slot: i as u8,
+                item_id: item.item_id.clone(),
+                quantity: item.quantity,
+            })
+            .collect()
+    }
+
+    /// Move as much as fits into an inventory. Returns whether anything moved.
+    pub fn recover_into(&mut self, inventory: &mut Inventory, registry: &ItemRegistry) -> bool {
+        let mut moved = false;
+        self.items.retain_mut(|item| {
+            let leftover = inventory.add_item(&item.item_id, item.quantity, registry);
+            moved |= leftover < item.quantity;
+            item.quantity = leftover;
+            leftover > 0
+        });
+        moved
+    }
+}
+
+/// Empty an inventory's slots into a list of stacks for a gravestone
+pub fn take_unequipped_items(inventory: &mut Inventory) -> Vec<InventorySlot> {
+    inventory.slots.iter_mut()
+        .filter_map(|slot| slot.take())
+        .filter(|slot| slot.quantity > 0)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(toml: &str) -> DeathRules {
+        let raw: RawDeathFile = toml::from_str(toml).unwrap();
+        let mut rules = DeathRules::new();
+        if let Some(default) = raw.default {
+            rules.default = default;
+        }
+        rules.zones = raw.zones;
+        rules
+    }
+
+    #[test]
+    fn zone_lookup_prefers_exact_then_longest_prefix() {
+        let rules = rules(r#"
+            [default]
+            penalty = "gold_loss"
+            percent = 10
+
+            [zones.overworld]
+            penalty = "gravestone"
+
+            [zones."dungeon_*"]
+            penalty = "keep_all"
+
+            [zones."dungeon_abyss*"]
+            penalty = "hardcore"
+        "#);
+
+        assert_eq!(rules.rule_for("overworld"), &DeathPenalty::Gravestone { duration_secs: 600 });
+        assert_eq!(rules.rule_for("dungeon_crypt_42"), &DeathPenalty::KeepAll);
+        assert_eq!(rules.rule_for("dungeon_abyss_7"), &DeathPenalty::Hardcore);
+        assert_eq!(rules.rule_for("old_house"), &DeathPenalty::GoldLoss { percent: 10 });
+    }
+
+    #[test]
+    fn gold_loss_rounds_down_and_clamps() {
+        assert_eq!(gold_lost(99, 10), 9);
+        assert_eq!(gold_lost(50, 150), 50);
+        assert_eq!(gold_lost(-5, 10), 0);
+    }
+
+    #[test]
+    fn recovery_keeps_what_does_not_fit() {
+        let registry = ItemRegistry::new();
+        let mut inventory = Inventory::new();
+        inventory.slots[0] = Some(InventorySlot::new("bones".to_string(), 5));
+        inventory.slots[3] = Some(InventorySlot::new("herb".to_string(), 2));
+
+        let items = take_unequipped_items(&mut inventory);
+        assert_eq!(items.len(), 2);
+        assert!(inventory.slots.iter().all(|s| s.is_none()));
+
+        let mut grave = Gravestone {
+            id: "g".to_string(),
+            owner_id: "p".to_string(),
+            owner_name: "P".to_string(),
+            x: 0,
+            y: 0,
+            instance_id: None,
+            items,
+            expires_at: 1000,
+        };
+
+        // Fill all but one slot so only the first stack fits
+        for slot in inventory.slots.iter_mut().skip(1) {
+            *slot = Some(InventorySlot::new("rock".to_string(), 99));
+        }
+        assert!(grave.recover_into(&mut inventory, &registry));
+        assert_eq!(grave.items.len(), 1);
+        assert_eq!(grave.items[0].item_id, "herb");
+        assert!(!grave.recover_into(&mut inventory, &registry));
+    }
+}
@@ -0,0 +1,594 @@
+//! Procedurally generated dungeons
+//!
+//! Templates from `data/dungeons/*.toml` plus a seed and a level generate an
+//! ordinary `InteriorMapDef` of rooms and corridors, deterministically.
+//! Overworld portals open a dungeon by targeting `dungeon:<id>`.
+
+use base64::Engine;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use tracing::{info, warn};
+
+use crate::loot_table::LootTableRegistry;
+use crate::interior::{
+    InstanceType, InteriorEntitySpawn, InteriorLayers, InteriorMapDef, InteriorPortal, InteriorSize,
+    InteriorWall, SpawnPoint,
+};
+
+/// Prefix of portal targets that open a generated dungeon
+pub const DUNGEON_PORTAL_PREFIX: &str = "dungeon:";
+
+/// Give up placing rooms after this many failed attempts
+const ROOM_PLACEMENT_ATTEMPTS: u32 = 300;
+
+// ============================================================================
+// Templates
+// ============================================================================
+
+/// A monster that can be placed in a dungeon
+#[derive(Debug, Clone, Deserialize)]
+pub struct DungeonMonster {
+    pub prototype: String,
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+    /// Lowest dungeon level this monster appears at
+    #[serde(default = "default_min_level")]
+    pub min_level: i32,
+}
+
+/// An item that can be placed as treasure
+#[derive(Debug, Clone, Deserialize)]
+pub struct DungeonTreasure {
+    pub item_id: String,
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+    #[serde(default = "default_quantity")]
+    pub quantity_min: i32,
+    #[serde(default = "default_quantity")]
+    pub quantity_max: i32,
+    /// Extra quantity per dungeon level
+    #[serde(default)]
+    pub quantity_per_level: f32,
+    #[serde(default = "default_min_level")]
+    pub min_level: i32,
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+fn default_min_level() -> i32 {
+    1
+}
+
+fn default_quantity() -> i32 {
+    1
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RawDungeonTemplate {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    /// Room count at the lowest level, growing towards `rooms_max` with level
+    pub rooms_min: u32,
+    pub rooms_max: u32,
+    pub room_size_min: u32,
+    pub room_size_max: u32,
+    #[serde(default = "default_min_level")]
+    pub min_level: i32,
+    #[serde(default = "default_max_level")]
+    pub max_level: i32,
+    pub floor_tile: u32,
+    pub wall_right_gid: u32,
+    pub wall_down_gid: u32,
+    /// Monsters per room before level scaling
+    pub monsters_min: u32,
+    pub monsters_max: u32,
+    /// One more monster per room every this many levels above `min_level`
+    #[serde(default = "default_levels_per_extra_monster")]
+    pub levels_per_extra_monster: i32,
+    pub monsters: Vec<DungeonMonster>,
+    /// Placed in the last room, a few levels above the dungeon
+    #[serde(default)]
+    pub boss: Option<String>,
+    /// Chance for each room besides the entrance to hold treasure (the last room always does)
+    #[serde(default)]
+    pub treasure_chance: f64,
+    #[serde(default)]
+    pub treasure: Vec<DungeonTreasure>,
+    /// Loot table each treasure spot rolls instead of picking from `treasure`
+    #[serde(default)]
+    pub treasure_table: Option<String>,
+}
+
+fn default_max_level() -> i32 {
+    99
+}
+
+fn default_levels_per_extra_monster() -> i32 {
+    5
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RawDungeonFile {
+    #[serde(default)]
+    pub dungeons: HashMap<String, RawDungeonTemplate>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DungeonTemplate {
+    pub id: String,
+    pub raw: RawDungeonTemplate,
+}
+
+impl DungeonTemplate {
+    /// Clamp a requested level into the template's level range
+    pub fn clamp_level(&self, level: i32) -> i32 {
+        level.clamp(self.raw.min_level, self.raw.max_level.max(self.raw.min_level))
+    }
+}
+
+/// Registry of dungeon templates
+#[derive(Debug, Default)]
+pub struct DungeonRegistry {
+    templates: HashMap<String, DungeonTemplate>,
+}
+
+impl DungeonRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load templates from every TOML file in `data_dir/dungeons`
+    pub fn load_from_directory(&mut self, data_dir: &Path) -> Result<(), String> {
+        let path = data_dir.join("dungeons");
+        if !path.exists() {
+            warn!("Dungeon directory does not exist: {:?}", path);
+            return Ok(());
+        }
+
+        for entry in fs::read_dir(&path).map_err(|e| e.to_string())? {
+            let file_path = entry.map_err(|e| e.to_string())?.path();
+            if file_path.extension().and_then(|s| s.to_str()) == Some("toml") {
+                let contents = fs::read_to_string(&file_path)
+                    .map_err(|e| format!("Failed to read {:?}: {}", file_path, e))?;
+                let file: RawDungeonFile = toml::from_str(&contents)
+                    .map_err(|e| format!("Failed to parse {:?}: {}", file_path, e))?;
+                self.add_file(file, &file_path);
+            }
+        }
+
+        info!("Loaded {} dungeon templates", self.templates.len());
+        Ok(())
+    }
+
+    fn add_file(&mut self, file: RawDungeonFile, path: &Path) {
+        for (id, raw) in file.dungeons {
+            if raw.rooms_min == 0 || raw.room_size_min < 3 || raw.room_size_max < raw.room_size_min {
+                warn!("Skipping dungeon {} in {:?}: needs at least one room of size 3 or more", id, path);
+                continue;
+            }
+            if raw.room_size_max + 2 > raw.width.min(raw.height) {
+                warn!("Skipping dungeon {} in {:?}: rooms don't fit in the map", id, path);
+                continue;
+            }
+            self.templates.insert(id.clone(), DungeonTemplate { id, raw });
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<&DungeonTemplate> {
+        self.templates.get(id)
+    }
+
+    /// (dungeon ID, loot table ID) for every template with a treasure table
+    pub fn treasure_tables(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.templates.values()
+            .filter_map(|template| template.raw.treasure_table.as_deref().map(|table| (template.id.as_str(), table)))
+    }
+}
+
+// ============================================================================
+// Generation
+// ============================================================================
+
+/// Treasure to place as a ground item when the dungeon instance is created
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreasurePlacement {
+    pub item_id: String,
+    pub quantity: i32,
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct GeneratedDungeon {
+    pub map: InteriorMapDef,
+    pub treasure: Vec<TreasurePlacement>,
+    pub seed: u64,
+    pub level: i32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Room {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
+impl Room {
+    fn center(&self) -> (i32, i32) {
+        (self.x + self.width / 2, self.y + self.height / 2)
+    }
+
+    /// Whether this room overlaps another, keeping `gap` tiles between them
+    fn overlaps(&self, other: &Room, gap: i32) -> bool {
+        self.x - gap < other.x + other.width
+            && other.x - gap < self.x + self.width
+            && self.y - gap < other.y + other.height
+            && other.y - gap < self.y + self.height
+    }
+}
+
+struct Grid {
+    width: i32,
+    height: i32,
+    floor: Vec<bool>,
+}
+
+impl Grid {
+    fn is_floor(&self, x: i32, y: i32) -> bool {
+        x >= 0 && y >= 0 && x < self.width && y < self.height && self.floor[(y * self.width + x) as usize]
+    }
+
+    fn set_floor(&mut self, x: i32, y: i32) {
+        if x >= 0 && y >= 0 && x < self.width && y < self.height {
+            self.floor[(y * self.width + x) as usize] = true;
+        }
+    }
+
+    /// Bit-packed collision (1 = blocked), the same format as chunk collision
+    fn pack_collision(&self) -> Vec<u8> {
+        let mut packed = vec![0u8; self.floor.len().div_ceil(8)];
+        for (i, &floor) in self.floor.iter().enumerate() {
+            if !floor {
+                packed[i / 8] |= 1 << (i % 8);
+            }
+        }
+        packed
+    }
+}
+
+/// Generate a dungeon. `level` is clamped into the template's level range.
+pub fn generate(template: &DungeonTemplate, seed: u64, level: i32, loot_tables: &LootTableRegistry) -> GeneratedDungeon {
+    let raw = &template.raw;
+    let level = template.clamp_level(level);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    // More rooms at higher levels
+    let level_span = (raw.max_level - raw.min_level).max(1) as f64;
+    let level_progress = (level - raw.min_level) as f64 / level_span;
+    let rooms_max = raw.rooms_max.max(raw.rooms_min);
+    let room_target = raw.rooms_min + ((rooms_max - raw.rooms_min) as f64 * level_progress).round() as u32;
+
+    let (width, height) = (raw.width as i32, raw.height as i32);
+    let mut rooms: Vec<Room> = Vec::new();
+    for _ in 0..ROOM_PLACEMENT_ATTEMPTS {
+        if rooms.len() as u32 >= room_target {
+            break;
+        }
+        let room_width = rng.gen_range(raw.room_size_min..=raw.room_size_max) as i32;
+        let room_height = rng.gen_range(raw.room_size_min..=raw.room_size_max) as i32;
+        // Keep a one-tile border so walls and corridors stay on the map
+        let room = Room {
+            x: rng.gen_range(1..=width - room_width - 1),
+            y: rng.gen_range(1..=height - room_height - 1),
+            width: room_width,
+            height: room_height,
+        };
+        if rooms.iter().all(|other| !room.overlaps(other, 2)) {
+            rooms.push(room);
+        }
+    }
+
+    let mut grid = Grid {
+        width,
+        height,
+        floor: vec![false; (width * height) as usize],
+    };
+    for room in &rooms {
+        for y in room.y..room.y + room.height {
+            for x in room.x..room.x + room.width {
+                grid.set_floor(x, y);
+            }
+        }
+    }
+    // Chain each room to the previous one with an L-shaped corridor
+    for pair in rooms.windows(2) {
+        let (from_x, from_y) = pair[0].center();
+        let (to_x, to_y) = pair[1].center();
+        let horizontal_first = rng.gen_bool(0.5);
+        let corner = if horizontal_first { (to_x, from_y) } else { (from_x, to_y) };
+        carve_line(&mut grid, (from_x, from_y), corner);
+        carve_line(&mut grid, corner, (to_x, to_y));
+    }
+
+    let mut walls = Vec::new();
+    for y in 0..height {
+        for x in 0..width {
+            if !grid.is_floor(x, y) {
+                continue;
+            }
+            if !grid.is_floor(x - 1, y) {
+                walls.push(InteriorWall { gid: raw.wall_right_gid, x, y, edge: "right".to_string() });
+            }
+            if !grid.is_floor(x, y - 1) {
+                walls.push(InteriorWall { gid: raw.wall_down_gid, x, y, edge: "down".to_string() });
+            }
+        }
+    }
+
+    // Entrance in the first room, exit portal in its corner
+    let entrance_room = rooms[0];
+    let (spawn_x, spawn_y) = entrance_room.center();
+    let mut spawn_points = HashMap::new();
+    spawn_points.insert("entrance".to_string(), SpawnPoint { x: spawn_x as f32, y: spawn_y as f32 });
+    let portals = vec![InteriorPortal {
+        id: "dungeon_exit".to_string(),
+        x: entrance_room.x,
+        y: entrance_room.y,
+        width: 1,
+        height: 1,
+        target_map: "overworld".to_string(),
+        target_x: 0.0,
+        target_y: 0.0,
+        target_spawn: None,
+    }];
+
+    let (entities, treasure) = place_contents(template, &rooms, level, loot_tables, &mut rng);
+
+    let tile_count = (width * height) as usize;
+    let ground = grid.floor.iter().map(|&floor| if floor { raw.floor_tile } else { 0 }).collect();
+    let map = InteriorMapDef {
+        id: format!("dungeon_{}", template.id),
+        name: format!("{} (Level {})", raw.name, level),
+        instance_type: InstanceType::Private,
+        size: InteriorSize { width: raw.width, height: raw.height },
+        spawn_points,
+        portals,
+        layers: InteriorLayers {
+            ground,
+            objects: vec![0; tile_count],
+            overhead: vec![0; tile_count],
+        },
+        collision: base64::engine::general_purpose::STANDARD.encode(grid.pack_collision()),
+        entities,
+        map_objects: Vec::new(),
+        walls,
+        pvp_zones: Vec::new(),
+    };
+
+    GeneratedDungeon { map, treasure, seed, level }
+}
+
+fn carve_line(grid: &mut Grid, from: (i32, i32), to: (i32, i32)) {
+    let (mut x, mut y) = from;
+    grid.set_floor(x, y);
+    while (x, y) != to {
+        x += (to.0 - x).signum();
+        y += (to.1 - y).signum();
+        grid.set_floor(x, y);
+    }
+}
+
+/// Monsters for every room but the entrance, and treasure for some of them
+fn place_contents(
+    template: &DungeonTemplate,
+    rooms: &[Room],
+    level: i32,
+    loot_tables: &LootTableRegistry,
+    rng: &mut StdRng,
+) -> (Vec<InteriorEntitySpawn>, Vec<TreasurePlacement>) {
+    let raw = &template.raw;
+    let monsters: Vec<&DungeonMonster> = raw.monsters.iter().filter(|m| m.min_level <= level).collect();
+    let treasure_pool: Vec<&DungeonTreasure> = raw.treasure.iter().filter(|t| t.min_level <= level).collect();
+    let extra_monsters = ((level - raw.min_level) / raw.levels_per_extra_monster.max(1)) as u32;
+
+    let mut entities = Vec::new();
+    let mut treasure = Vec::new();
+    let last_room = rooms.len() - 1;
+    for (index, room) in rooms.iter().enumerate().skip(1) {
+        let mut free: Vec<(i32, i32)> = (room.y..room.y + room.height)
+            .flat_map(|y| (room.x..room.x + room.width).map(move |x| (x, y)))
+            .collect();
+
+        let boss = if index == last_room { raw.boss.as_ref() } else { None };
+        if let Some(boss) = boss {
+            let center = room.center();
+            free.retain(|tile| *tile != center);
+            entities.push(monster_spawn(boss, center, level + 2));
+        }
+
+        if !monsters.is_empty() {
+            let count = rng.gen_range(raw.monsters_min..=raw.monsters_max.max(raw.monsters_min)) + extra_monsters;
+            for _ in 0..count {
+                let Some(tile) = take_random_tile(&mut free, rng) else {
+                    break;
+                };
+                let monster = pick_weighted(&monsters, |m| m.weight, rng);
+                let monster_level = (level + rng.gen_range(-1..=1)).max(1);
+                entities.push(monster_spawn(&monster.prototype, tile, monster_level));
+            }
+        }
+
+        let has_treasure = (raw.treasure_table.is_some() || !treasure_pool.is_empty())
+            && (index == last_room || rng.gen_bool(raw.treasure_chance.clamp(0.0, 1.0)));
+        let treasure_tile = if has_treasure { take_random_tile(&mut free, rng) } else { None };
+        if let Some((x, y)) = treasure_tile {
+            if let Some(table) = &raw.treasure_table {
+                for loot in loot_tables.roll(table, level, rng) {
+                    treasure.push(TreasurePlacement { item_id: loot.item_id, quantity: loot.quantity, x, y });
+                }
+            } else {
+                let item = pick_weighted(&treasure_pool, |t| t.weight, rng);
+                let base = rng.gen_range(item.quantity_min..=item.quantity_max.max(item.quantity_min));
+                let quantity = base + (item.quantity_per_level * level as f32) as i32;
+                treasure.push(TreasurePlacement { item_id: item.item_id.clone(), quantity: quantity.max(1), x, y });
+            }
+        }
+    }
+    (entities, treasure)
+}
+
+fn monster_spawn(prototype: &str, (x, y): (i32, i32), level: i32) -> InteriorEntitySpawn {
+    InteriorEntitySpawn {
+        entity_id: prototype.to_string(),
+        x,
+        y,
+        level,
+        unique_id: None,
+        facing: None,
+        respawn: false,
+        patrol: Vec::new(),
+        schedule: Vec::new(),
+    }
+}
+
+fn take_random_tile(free: &mut Vec<(i32, i32)>, rng: &mut StdRng) -> Option<(i32, i32)> {
+    if free.is_empty() {
+        return None;
+    }
+    let index = rng.gen_range(0..free.len());
+    Some(free.swap_remove(index))
+}
+
+fn pick_weighted<'a, T>(entries: &[&'a T], weight: impl Fn(&T) -> u32, rng: &mut StdRng) -> &'a T {
+    let total: u32 = entries.iter().map(|e| weight(e)).sum();
+    if total == 0 {
+        return entries[0];
+    }
+    let mut roll = rng.gen_range(0..total);
+    for entry in entries {
+        let w = weight(entry);
+        if roll < w {
+            return entry;
+        }
+        roll -= w;
+    }
+    entries[entries.len() - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template() -> DungeonTemplate {
+        let toml = r#"
+            [dungeons.crypt]
+            name = "Crypt"
+            width = 40
+            height = 40
+            rooms_min = 4
+            rooms_max = 8
+            room_size_min = 4
+            room_size_max = 7
+            min_level = 1
+            max_level = 20
+            floor_tile = 311
+            wall_right_gid = 485
+            wall_down_gid = 480
+            monsters_min = 1
+            monsters_max = 2
+            boss = "reaper"
+            treasure_chance = 0.5
+            monsters = [{ prototype = "slime" }, { prototype = "reaper", min_level = 10 }]
+            treasure = [{ item_id = "gold", quantity_min = 10, quantity_max = 20, quantity_per_level = 2.0 }]
+        "#;
+        let file: RawDungeonFile = toml::from_str(toml).unwrap();
+        let mut registry = DungeonRegistry::new();
+        registry.add_file(file, Path::new("test.toml"));
+        registry.get("crypt").unwrap().clone()
+    }
+
+    fn layout(dungeon: &GeneratedDungeon) -> String {
+        serde_json::to_string(&dungeon.map).unwrap()
+    }
+
+    #[test]
+    fn test_same_seed_same_dungeon() {
+        let template = template();
+        let a = generate(&template, 42, 5, &LootTableRegistry::new());
+        let b = generate(&template, 42, 5, &LootTableRegistry::new());
+        assert_eq!(layout(&a), layout(&b));
+        assert_eq!(a.treasure, b.treasure);
+
+        let c = generate(&template, 43, 5, &LootTableRegistry::new());
+        assert_ne!(layout(&a), layout(&c));
+    }
+
+    #[test]
+    fn test_dungeon_is_connected_and_contents_on_floor() {
+        let template = template();
+        for seed in 0..20 {
+            let dungeon = generate(&template, seed, 10, &LootTableRegistry::new());
+            let map = &dungeon.map;
+            let width = map.size.width as i32;
+            let floor = |x: i32, y: i32| {
+                x >= 0 && y >= 0 && x < width && y < map.size.height as i32
+                    && map.layers.ground[(y * width + x) as usize] != 0
+            };
+
+            // Flood fill from the entrance reaches every floor tile
+            let spawn = map.get_spawn_point("entrance").unwrap();
+            let mut seen = std::collections::HashSet::new();
+            let mut stack = vec![(spawn.x as i32, spawn.y as i32)];
+            while let Some((x, y)) = stack.pop() {
+                if !floor(x, y) || !seen.insert((x, y)) {
+                    continue;
+                }
+                stack.extend([(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]);
+            }
+            let floor_count = map.layers.ground.iter().filter(|&&t| t != 0).count();
+            assert_eq!(seen.len(), floor_count, "seed {} has unreachable floor", seed);
+
+            assert!(map.entities.iter().all(|e| floor(e.x, e.y)));
+            assert!(dungeon.treasure.iter().all(|t| floor(t.x, t.y)));
+            assert!(map.portals.iter().all(|p| floor(p.x, p.y)));
+            assert!(map.get_portal_at(spawn.x as i32, spawn.y as i32).is_none());
+            assert!(!dungeon.treasure.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_level_scaling() {
+        let template = template();
+        let low = generate(&template, 7, 1, &LootTableRegistry::new());
+        let high = generate(&template, 7, 50, &LootTableRegistry::new());
+        assert_eq!(high.level, 20);
+
+        assert!(low.map.entities.iter().all(|e| e.entity_id == "slime" || e.level == 3));
+        assert!(high.map.entities.len() > low.map.entities.len());
+        assert!(high.map.entities.iter().all(|e| e.level >= 19));
+        assert!(high.treasure.iter().all(|t| t.quantity >= 50));
+    }
+
+    #[test]
+    fn test_treasure_table_rolls_at_dungeon_level() {
+        let mut template = template();
+        template.raw.treasure_table = Some("crypt_chest".to_string());
+        let mut tables = LootTableRegistry::new();
+        tables.load_from_str(r#"
+            [tables.crypt_chest]
+            guaranteed = [{ item = "dark_essence", quantity_per_level = 0.5 }]
+        "#).unwrap();
+
+        let dungeon = generate(&template, 3, 10, &tables);
+        assert!(!dungeon.treasure.is_empty());
+        assert!(dungeon.treasure.iter().all(|t| t.item_id == "dark_essence" && t.quantity == 6));
+    }
+}
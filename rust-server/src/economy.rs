@@ -0,0 +1,516 @@
+//! Economy balancing report
+//!
+//! `isometric-server economy` checks shops, recipes and drops together for
+//! unknown items and gold exploits, exiting with status 1 on errors.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::crafting::{CraftingRegistry, RecipeDefinition};
+use crate::data::ItemRegistry;
+use crate::entity::prototype::MerchantConfig;
+use crate::entity::{calculate_exp_reward, EntityPrototype, EntityRegistry};
+use crate::item::GOLD_ITEM_ID;
+use crate::loot_table::LootTableRegistry;
+use crate::reputation;
+use crate::shop::ShopRegistry;
+use crate::skills::COMBAT_XP_PER_DAMAGE;
+
+pub const USAGE: &str = "\
+Usage: isometric-server economy [options]
+
+Options:
+  --level N   monster level used for XP and drop values (default 1)
+  --data DIR  data directory (default \"data\")";
+
+/// Multipliers used for shops without a merchant
+const DEFAULT_BUY_MULTIPLIER: f32 = 0.5;
+const DEFAULT_SELL_MULTIPLIER: f32 = 1.0;
+
+// ============================================================================
+// Findings
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub severity: Severity,
+    /// Which check raised it, e.g. "recipe"
+    pub check: &'static str,
+    pub message: String,
+}
+
+#[derive(Debug, Default)]
+pub struct EconomyReport {
+    pub level: i32,
+    /// Median gold a kill drops per point of XP it awards
+    pub gold_per_xp: f64,
+    pub findings: Vec<Finding>,
+}
+
+impl EconomyReport {
+    fn error(&mut self, check: &'static str, message: String) {
+        self.findings.push(Finding { severity: Severity::Error, check, message });
+    }
+
+    fn warning(&mut self, check: &'static str, message: String) {
+        self.findings.push(Finding { severity: Severity::Warning, check, message });
+    }
+
+    pub fn count(&self, severity: Severity) -> usize {
+        self.findings.iter().filter(|finding| finding.severity == severity).count()
+    }
+
+    pub fn format(&self) -> String {
+        let mut out = format!(
+            "Economy report (monster level {}, {:.3} gold per XP)\n",
+            self.level, self.gold_per_xp
+        );
+        if self.findings.is_empty() {
+            out.push_str("\nNo problems found\n");
+            return out;
+        }
+        for (severity, title) in [(Severity::Error, "Errors"), (Severity::Warning, "Warnings")] {
+            let count = self.count(severity);
+            if count == 0 {
+                continue;
+            }
+            out.push_str(&format!("\n{} ({})\n", title, count));
+            for finding in self.findings.iter().filter(|finding| finding.severity == severity) {
+                out.push_str(&format!("  [{}] {}\n", finding.check, finding.message));
+            }
+        }
+        out
+    }
+}
+
+// ============================================================================
+// Prices
+// ============================================================================
+
+/// Buy and sell prices across every shop and merchant. Prices follow the shop
+/// handlers at the best reputation discount; shops no merchant runs use the
+/// default multipliers.
+struct Market<'a> {
+    items: &'a ItemRegistry,
+    shops: &'a ShopRegistry,
+    merchants: Vec<(&'a str, &'a MerchantConfig)>,
+}
+
+impl<'a> Market<'a> {
+    fn new(items: &'a ItemRegistry, shops: &'a ShopRegistry, entities: &'a EntityRegistry) -> Self {
+        let mut merchants: Vec<(&str, &MerchantConfig)> = entities
+            .all()
+            .filter_map(|prototype| prototype.merchant.as_ref().map(|merchant| (prototype.id.as_str(), merchant)))
+            .collect();
+        merchants.sort_by(|a, b| a.0.cmp(b.0));
+        Self { items, shops, merchants }
+    }
+
+    /// Best (purchase, sale) reputation modifiers a merchant offers
+    fn best_modifiers(merchant: &MerchantConfig) -> (f32, f32) {
+        match merchant.faction {
+            Some(_) => (reputation::purchase_price_modifier(i32::MAX), reputation::sale_price_modifier(i32::MAX)),
+            None => (1.0, 1.0),
+        }
+    }
+
+    fn price(base_price: i32, multiplier: f32) -> i32 {
+        (base_price as f32 * multiplier).max(1.0) as i32
+    }
+
+    /// Cheapest price and shop an item can be bought from
+    fn buy_price(&self, item_id: &str) -> Option<(i32, String)> {
+        let base_price = self.items.get(item_id)?.base_price;
+        let mut best: Option<(i32, String)> = None;
+        for shop in self.shops.all().filter(|shop| shop.get_stock(item_id).is_some()) {
+            let multipliers: Vec<f32> = self.merchants.iter()
+                .filter(|(_, merchant)| merchant.shop_id == shop.id)
+                .map(|(_, merchant)| merchant.sell_multiplier * Self::best_modifiers(merchant).0)
+                .collect();
+            let multiplier = multipliers.into_iter().reduce(f32::min).unwrap_or(DEFAULT_SELL_MULTIPLIER);
+            let price = Self::price(base_price, multiplier);
+            if best.as_ref().is_none_or(|(best_price, _)| price < *best_price) {
+                best = Some((price, shop.id.clone()));
+            }
+        }
+        best
+    }
+
+    /// Most any merchant pays for an item, if it can be sold at all
+    fn sale_price(&self, item_id: &str) -> Option<i32> {
+        let item = self.items.get(item_id).filter(|item| item.sellable)?;
+        let multiplier = self.merchants.iter()
+            .map(|(_, merchant)| merchant.buy_multiplier * Self::best_modifiers(merchant).1)
+            .reduce(f32::max)
+            .unwrap_or(DEFAULT_BUY_MULTIPLIER);
+        Some(Self::price(item.base_price, multiplier))
+    }
+}
+
+// ============================================================================
+// Checks
+// ============================================================================
+
+fn check_shops(report: &mut EconomyReport, market: &Market) {
+    let mut shops: Vec<_> = market.shops.all().collect();
+    shops.sort_by(|a, b| a.id.cmp(&b.id));
+    for shop in shops {
+        for stock in &shop.stock {
+            if !market.items.contains(&stock.item_id) {
+                report.error("shop", format!("{} stocks unknown item '{}'", shop.id, stock.item_id));
+            }
+        }
+        if !market.merchants.iter().any(|(_, merchant)| merchant.shop_id == shop.id) {
+            report.warning("shop", format!("{} is not run by any merchant", shop.id));
+        }
+    }
+    for (prototype_id, merchant) in &market.merchants {
+        if !market.shops.contains(&merchant.shop_id) {
+            report.error("shop", format!("merchant {} runs unknown shop '{}'", prototype_id, merchant.shop_id));
+        }
+    }
+}
+
+fn check_arbitrage(report: &mut EconomyReport, market: &Market) {
+    let mut item_ids: Vec<&String> = market.shops.all()
+        .flat_map(|shop| shop.stock.iter().map(|stock| &stock.item_id))
+        .collect();
+    item_ids.sort();
+    item_ids.dedup();
+    for item_id in item_ids {
+        let (Some((buy, shop_id)), Some(sale)) = (market.buy_price(item_id), market.sale_price(item_id)) else {
+            continue;
+        };
+        if sale > buy {
+            report.error("arbitrage", format!(
+                "{} costs {} at {} but sells for {}", item_id, buy, shop_id, sale
+            ));
+        }
+    }
+}
+
+fn check_recipe(report: &mut EconomyReport, market: &Market, recipe: &RecipeDefinition) {
+    let mut unknown: Vec<&str> = recipe.ingredients.iter().map(|i| i.item_id.as_str())
+        .chain(recipe.results.iter().map(|r| r.item_id.as_str()))
+        .filter(|item_id| !market.items.contains(item_id))
+        .collect();
+    unknown.dedup();
+    if !unknown.is_empty() {
+        report.error("recipe", format!("{} uses unknown items: {}", recipe.id, unknown.join(", ")));
+        return;
+    }
+
+    // Only recipes whose every input can be bought are exploitable without gathering
+    let mut cost = 0;
+    let mut shops = Vec::new();
+    for ingredient in &recipe.ingredients {
+        let Some((price, shop_id)) = market.buy_price(&ingredient.item_id) else {
+            return;
+        };
+        cost += price * ingredient.count;
+        if !shops.contains(&shop_id) {
+            shops.push(shop_id);
+        }
+    }
+    let value: i32 = recipe.results.iter()
+        .filter_map(|result| market.sale_price(&result.item_id).map(|price| price * result.count))
+        .sum();
+    if value > cost {
+        report.error("recipe", format!(
+            "{} inputs cost {} at {} but the output sells for {}", recipe.id, cost, shops.join(", "), value
+        ));
+    }
+}
+
+/// Item ID -> average quantity one kill drops, gold included
+fn expected_drops(prototype: &EntityPrototype, loot_tables: &LootTableRegistry, level: i32) -> HashMap<String, f64> {
+    let mut drops = match &prototype.loot_table {
+        Some(table) => loot_tables.expected(table, level),
+        None => HashMap::new(),
+    };
+    for entry in &prototype.loot {
+        let quantity = (entry.quantity_min + entry.quantity_max.max(entry.quantity_min)) as f64 / 2.0;
+        *drops.entry(entry.item_id.clone()).or_insert(0.0) += entry.drop_chance.clamp(0.0, 1.0) as f64 * quantity;
+    }
+    let gold = (prototype.rewards.gold_min + prototype.rewards.gold_max) as f64 / 2.0 * level as f64;
+    if gold > 0.0 {
+        *drops.entry(GOLD_ITEM_ID.to_string()).or_insert(0.0) += gold;
+    }
+    drops
+}
+
+/// Combat XP the killer gets, as awarded by the kill handler
+fn kill_xp(prototype: &EntityPrototype, level: i32) -> f64 {
+    calculate_exp_reward(prototype, level) as f64 * COMBAT_XP_PER_DAMAGE
+}
+
+/// Median gold dropped per XP across monsters that award both
+fn median_gold_per_xp(monsters: &[&EntityPrototype], loot_tables: &LootTableRegistry, level: i32) -> f64 {
+    let mut ratios: Vec<f64> = monsters.iter()
+        .filter_map(|prototype| {
+            let xp = kill_xp(prototype, level);
+            let gold = expected_drops(prototype, loot_tables, level).get(GOLD_ITEM_ID).copied().unwrap_or(0.0);
+            (xp > 0.0 && gold > 0.0).then(|| gold / xp)
+        })
+        .collect();
+    if ratios.is_empty() {
+        return 0.0;
+    }
+    ratios.sort_by(f64::total_cmp);
+    ratios[ratios.len() / 2]
+}
+
+/// Flag unknown drops, and drops selling for more than the kill's XP is worth
+/// at the median gold-per-XP rate
+fn check_drops(report: &mut EconomyReport, market: &Market, loot_tables: &LootTableRegistry, monsters: &[&EntityPrototype]) {
+    for prototype in monsters {
+        let xp_value = kill_xp(prototype, report.level) * report.gold_per_xp;
+        let mut drops: Vec<(String, f64)> = expected_drops(prototype, loot_tables, report.level).into_iter().collect();
+        drops.sort_by(|a, b| a.0.cmp(&b.0));
+        for (item_id, quantity) in drops {
+            if item_id == GOLD_ITEM_ID {
+                continue;
+            }
+            if !market.items.contains(&item_id) {
+                report.error("drops", format!("{} drops unknown item '{}'", prototype.id, item_id));
+                continue;
+            }
+            let Some(price) = market.sale_price(&item_id) else {
+                continue;
+            };
+            let value = quantity * price as f64;
+            if report.gold_per_xp > 0.0 && value > xp_value {
+                report.warning("drops", format!(
+                    "{} from {} sells for {:.1} gold per kill, more than the kill's XP is worth ({:.1} gold)",
+                    item_id, prototype.id, value, xp_value
+                ));
+            }
+        }
+    }
+}
+
+/// Run every check against loaded registries
+pub fn build_report(
+    items: &ItemRegistry,
+    recipes: &CraftingRegistry,
+    shops: &ShopRegistry,
+    entities: &EntityRegistry,
+    loot_tables: &LootTableRegistry,
+    level: i32,
+) -> EconomyReport {
+    let market = Market::new(items, shops, entities);
+    let mut monsters: Vec<&EntityPrototype> = entities.all()
+        .filter(|prototype| prototype.behaviors.hostile && !prototype.is_pet() && !prototype.is_npc())
+        .collect();
+    monsters.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let mut report = EconomyReport {
+        level,
+        gold_per_xp: median_gold_per_xp(&monsters, loot_tables, level),
+        findings: Vec::new(),
+    };
+
+    check_shops(&mut report, &market);
+    check_arbitrage(&mut report, &market);
+    let mut all_recipes: Vec<&RecipeDefinition> = recipes.all().collect();
+    all_recipes.sort_by(|a, b| a.id.cmp(&b.id));
+    for recipe in all_recipes {
+        check_recipe(&mut report, &market, recipe);
+    }
+    check_drops(&mut report, &market, loot_tables, &monsters);
+    for problem in loot_tables.validate() {
+        report.error("drops", format!("loot {}", problem));
+    }
+    report
+}
+
+/// Entry point for `isometric-server economy`. Returns the number of errors.
+pub fn run(args: &[String]) -> Result<usize, String> {
+    let mut level = 1;
+    let mut data_dir = PathBuf::from("data");
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        if flag == "--help" || flag == "-h" {
+            println!("{}", USAGE);
+            return Ok(0);
+        }
+        let value = args.next().ok_or_else(|| format!("Missing value for {}", flag))?;
+        match flag.as_str() {
+            "--level" => {
+                level = value.parse().map_err(|_| format!("Invalid value for {}: {}", flag, value))?;
+            }
+            "--data" => data_dir = PathBuf::from(value),
+            _ => return Err(format!("Unknown option: {}", flag)),
+        }
+    }
+
+    let mut items = ItemRegistry::new();
+    items.load_from_directory(&data_dir)?;
+    let mut recipes = CraftingRegistry::new();
+    recipes.load_from_directory(&data_dir)?;
+    let mut shops = ShopRegistry::new();
+    shops.load_from_directory(&data_dir.join("shops"))?;
+    let mut entities = EntityRegistry::new();
+    entities.load_from_directory(&data_dir)?;
+    let mut loot_tables = LootTableRegistry::new();
+    loot_tables.load_from_directory(&data_dir)?;
+
+    let report = build_report(&items, &recipes, &shops, &entities, &loot_tables, level.max(1));
+    print!("{}", report.format());
+    Ok(report.count(Severity::Error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write(dir: &TempDir, path: &str, content: &str) {
+        let path = dir.path().join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn report_for(dir: &TempDir) -> EconomyReport {
+        let mut items = ItemRegistry::new();
+        items.load_from_directory(dir.path()).unwrap();
+        let mut recipes = CraftingRegistry::new();
+        recipes.load_from_directory(dir.path()).unwrap();
+        let mut shops = ShopRegistry::new();
+        shops.load_from_directory(&dir.path().join("shops")).unwrap();
+        let mut entities = EntityRegistry::new();
+        entities.load_from_directory(dir.path()).unwrap();
+        build_report(&items, &recipes, &shops, &entities, &LootTableRegistry::new(), 1)
+    }
+
+    fn setup_data() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        write(&dir, "items/items.toml", r#"
+            [herb]
+            display_name = "Herb"
+            base_price = 4
+
+            [potion]
+            display_name = "Potion"
+            base_price = 20
+
+            [bone]
+            display_name = "Bone"
+            base_price = 100
+        "#);
+        write(&dir, "recipes/recipes.toml", r#"
+            [brew]
+            [[brew.ingredients]]
+            item_id = "herb"
+            count = 2
+            [[brew.results]]
+            item_id = "potion"
+
+            [broken]
+            [[broken.ingredients]]
+            item_id = "unobtainium"
+            [[broken.results]]
+            item_id = "potion"
+        "#);
+        write(&dir, "shops/general_store.toml", r#"
+            id = "general_store"
+            display_name = "General Goods"
+
+            [[stock]]
+            item_id = "herb"
+            max_quantity = 10
+            restock_rate = 1
+
+            [[stock]]
+            item_id = "ghost_item"
+            max_quantity = 1
+            restock_rate = 1
+        "#);
+        write(&dir, "entities/npcs/merchants.toml", r#"
+            [grocer]
+            display_name = "Grocer"
+            [grocer.behaviors]
+            merchant = true
+            [grocer.merchant]
+            shop_id = "general_store"
+            buy_multiplier = 0.5
+            sell_multiplier = 1.0
+        "#);
+        write(&dir, "entities/monsters/monsters.toml", r#"
+            [rat]
+            display_name = "Rat"
+            [rat.rewards]
+            exp_base = 5
+            gold_min = 2
+            gold_max = 2
+            [rat.behaviors]
+            hostile = true
+            [[rat.loot]]
+            item_id = "bone"
+            drop_chance = 0.5
+            quantity_min = 1
+            quantity_max = 1
+
+            [slug]
+            display_name = "Slug"
+            [slug.rewards]
+            exp_base = 5
+            gold_min = 2
+            gold_max = 2
+            [slug.behaviors]
+            hostile = true
+            [[slug.loot]]
+            item_id = "herb"
+            drop_chance = 0.1
+            quantity_min = 1
+            quantity_max = 1
+        "#);
+        dir
+    }
+
+    fn messages(report: &EconomyReport, check: &str) -> Vec<String> {
+        report.findings.iter().filter(|f| f.check == check).map(|f| f.message.clone()).collect()
+    }
+
+    #[test]
+    fn test_flags_shop_stock_and_recipe_exploits() {
+        let dir = setup_data();
+        let report = report_for(&dir);
+        assert_eq!(messages(&report, "shop"), vec!["general_store stocks unknown item 'ghost_item'"]);
+        assert_eq!(messages(&report, "recipe"), vec![
+            "brew inputs cost 8 at general_store but the output sells for 10",
+            "broken uses unknown items: unobtainium",
+        ]);
+        assert!(messages(&report, "arbitrage").is_empty());
+    }
+
+    #[test]
+    fn test_flags_drops_worth_more_than_their_xp() {
+        let dir = setup_data();
+        let report = report_for(&dir);
+        // 2 gold per 20 XP
+        assert_eq!(report.gold_per_xp, 0.1);
+        // Half a bone per kill sells for 25 gold against 2 gold of XP; the herb is worth 0.2
+        assert_eq!(messages(&report, "drops"), vec![
+            "bone from rat sells for 25.0 gold per kill, more than the kill's XP is worth (2.0 gold)",
+        ]);
+    }
+
+    #[test]
+    fn test_report_lists_errors_before_warnings() {
+        let mut report = EconomyReport { level: 1, ..Default::default() };
+        assert!(report.format().ends_with("No problems found\n"));
+        report.warning("drops", "late".to_string());
+        report.error("shop", "early".to_string());
+        assert_eq!(report.count(Severity::Error), 1);
+        let text = report.format();
+        assert!(text.find("[shop] early").unwrap() < text.find("[drops] late").unwrap());
+    }
+}
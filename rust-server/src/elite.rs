@@ -0,0 +1,296 @@
+//! Elite and champion monster variants
+//!
+//! Hostile spawns may roll a rank from `data/elites/*.toml` (rarest first) and
+//! get that many random affixes, whose effects stack; the data file documents
+//! each field.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use tracing::{info, warn};
+
+use crate::entity::EntityPrototype;
+
+fn default_one() -> f32 { 1.0 }
+fn default_affix_count() -> usize { 1 }
+fn default_loot_rolls() -> u32 { 1 }
+
+// ============================================================================
+// Definitions
+// ============================================================================
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EliteRank {
+    /// Chance per spawn (0.0 - 1.0)
+    pub chance: f32,
+    #[serde(default = "default_affix_count", rename = "affixes")]
+    pub affix_count: usize,
+    #[serde(default = "default_one")]
+    pub exp_multiplier: f32,
+    #[serde(default = "default_loot_rolls")]
+    pub loot_rolls: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Affix {
+    /// Shown before the monster's name
+    pub prefix: String,
+    #[serde(default = "default_one")]
+    pub hp_multiplier: f32,
+    #[serde(default = "default_one")]
+    pub damage_multiplier: f32,
+    #[serde(default = "default_one")]
+    pub speed_multiplier: f32,
+    #[serde(default)]
+    pub lifesteal_percent: i32,
+    #[serde(default)]
+    pub reflect_percent: i32,
+    #[serde(default)]
+    pub split_count: u32,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RawEliteFile {
+    #[serde(default)]
+    ranks: HashMap<String, EliteRank>,
+    #[serde(default)]
+    affixes: HashMap<String, Affix>,
+}
+
+// ============================================================================
+// Rolled Variant
+// ============================================================================
+
+/// Stats an elite replaced, restored when a respawn rolls it as a normal monster
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BaseStats {
+    pub max_hp: i32,
+    pub damage: i32,
+    pub move_cooldown_ms: u64,
+    pub attack_cooldown_ms: u64,
+}
+
+/// Rank and combined affixes of one elite monster
+#[derive(Debug, Clone, PartialEq)]
+pub struct Elite {
+    pub rank: String,
+    /// Affix prefixes in roll order, e.g. ["Vampiric", "Swift"]
+    pub prefixes: Vec<String>,
+    pub exp_multiplier: f32,
+    pub loot_rolls: u32,
+    pub hp_multiplier: f32,
+    pub damage_multiplier: f32,
+    pub speed_multiplier: f32,
+    pub lifesteal_percent: i32,
+    pub reflect_percent: i32,
+    pub split_count: u32,
+    /// Set when the variant is applied to an NPC
+    pub base: BaseStats,
+}
+
+impl Elite {
+    fn new(rank: &str, definition: &EliteRank, affixes: &[&Affix]) -> Self {
+        let mut elite = Self {
+            rank: rank.to_string(),
+            prefixes: Vec::new(),
+            exp_multiplier: definition.exp_multiplier,
+            loot_rolls: definition.loot_rolls.max(1),
+            hp_multiplier: 1.0,
+            damage_multiplier: 1.0,
+            speed_multiplier: 1.0,
+            lifesteal_percent: 0,
+            reflect_percent: 0,
+            split_count: 0,
+            base: BaseStats::default(),
+        };
+        for affix in affixes {
+            elite.prefixes.push(affix.prefix.clone());
+            elite.hp_multiplier *= affix.hp_multiplier;
+            elite.damage_multiplier *= affix.damage_multiplier;
+            elite.speed_multiplier *= affix.speed_multiplier;
+            elite.lifesteal_percent += affix.lifesteal_percent;
+            elite.reflect_percent += affix.reflect_percent;
+            elite.split_count += affix.split_count;
+        }
+        elite
+    }
+
+    /// Stats after the affixes are applied to `base`
+    pub fn apply(&self, base: BaseStats) -> BaseStats {
+        let speed = self.speed_multiplier.max(0.1);
+        BaseStats {
+            max_hp: (base.max_hp as f32 * self.hp_multiplier).round().max(1.0) as i32,
+            damage: (base.damage as f32 * self.damage_multiplier).round().max(0.0) as i32,
+            move_cooldown_ms: (base.move_cooldown_ms as f32 / speed).round() as u64,
+            attack_cooldown_ms: (base.attack_cooldown_ms as f32 / speed).round() as u64,
+        }
+    }
+
+    /// HP healed for dealing `damage`
+    pub fn lifesteal(&self, damage: i32) -> i32 {
+        damage * self.lifesteal_percent.clamp(0, 100) / 100
+    }
+
+    /// Damage sent back for taking `damage`
+    pub fn reflected(&self, damage: i32) -> i32 {
+        damage * self.reflect_percent.clamp(0, 100) / 100
+    }
+}
+
+// ============================================================================
+// Table
+// ============================================================================
+
+#[derive(Debug, Default)]
+pub struct EliteTable {
+    ranks: HashMap<String, EliteRank>,
+    affixes: HashMap<String, Affix>,
+}
+
+impl EliteTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load every rank and affix file from `<data_dir>/elites`
+    pub fn load_from_directory(&mut self, data_dir: &Path) -> Result<(), String> {
+        let path = data_dir.join("elites");
+        if !path.exists() {
+            warn!("Elite directory does not exist: {:?}", path);
+            return Ok(());
+        }
+
+        for entry in fs::read_dir(&path).map_err(|e| e.to_string())? {
+            let file_path = entry.map_err(|e| e.to_string())?.path();
+            if file_path.extension().is_some_and(|ext| ext == "toml") {
+                let content = fs::read_to_string(&file_path)
+                    .map_err(|e| format!("Failed to read {:?}: {}", file_path, e))?;
+                self.load_from_str(&content)
+                    .map_err(|e| format!("Failed to parse {:?}: {}", file_path, e))?;
+            }
+        }
+
+        info!("Loaded {} elite ranks and {} affixes", self.ranks.len(), self.affixes.len());
+        Ok(())
+    }
+
+    fn load_from_str(&mut self, content: &str) -> Result<(), String> {
+        let raw: RawEliteFile = toml::from_str(content).map_err(|e| e.to_string())?;
+        self.ranks.extend(raw.ranks);
+        self.affixes.extend(raw.affixes);
+        Ok(())
+    }
+
+    /// Roll a variant for a freshly spawned monster. Bosses, pets and
+    /// friendly NPCs always spawn as themselves.
+    pub fn roll_for(&self, prototype: &EntityPrototype, rng: &mut impl Rng) -> Option<Elite> {
+        if !prototype.behaviors.hostile || prototype.boss.is_some() || prototype.is_pet() || prototype.is_npc() {
+            return None;
+        }
+        self.roll(rng)
+    }
+
+    /// Roll whether a spawn is an elite, and with which affixes
+    pub fn roll(&self, rng: &mut impl Rng) -> Option<Elite> {
+        if self.affixes.is_empty() {
+            return None;
+        }
+        let mut ranks: Vec<(&String, &EliteRank)> = self.ranks.iter().collect();
+        ranks.sort_by(|a, b| a.1.chance.total_cmp(&b.1.chance).then_with(|| a.0.cmp(b.0)));
+
+        let (rank_id, rank) = ranks.into_iter().find(|(_, rank)| rng.r#gen::<f32>() < rank.chance)?;
+
+        let mut affix_ids: Vec<&String> = self.affixes.keys().collect();
+        affix_ids.sort();
+        let affixes: Vec<&Affix> = affix_ids
+            .choose_multiple(rng, rank.affix_count.max(1))
+            .map(|id| &self.affixes[*id])
+            .collect();
+        Some(Elite::new(rank_id, rank, &affixes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const TABLE: &str = r#"
+        [ranks.elite]
+        chance = 0.5
+        affixes = 1
+        exp_multiplier = 2.0
+        loot_rolls = 2
+
+        [ranks.champion]
+        chance = 0.1
+        affixes = 2
+        exp_multiplier = 4.0
+        loot_rolls = 3
+
+        [affixes.hulking]
+        prefix = "Hulking"
+        hp_multiplier = 2.0
+
+        [affixes.swift]
+        prefix = "Swift"
+        speed_multiplier = 2.0
+
+        [affixes.vampiric]
+        prefix = "Vampiric"
+        lifesteal_percent = 50
+    "#;
+
+    fn table() -> EliteTable {
+        let mut table = EliteTable::new();
+        table.load_from_str(TABLE).unwrap();
+        table
+    }
+
+    #[test]
+    fn test_rolls_pick_distinct_affixes_per_rank() {
+        let table = table();
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for _ in 0..2000 {
+            let Some(elite) = table.roll(&mut rng) else {
+                *counts.entry("normal".to_string()).or_insert(0) += 1;
+                continue;
+            };
+            let expected = if elite.rank == "champion" { 2 } else { 1 };
+            assert_eq!(elite.prefixes.len(), expected);
+            assert!(elite.prefixes.len() < 2 || elite.prefixes[0] != elite.prefixes[1]);
+            *counts.entry(elite.rank).or_insert(0) += 1;
+        }
+        // Champions 10%, elites 50% of the remaining 90%
+        assert!((120..280).contains(&counts["champion"]), "{:?}", counts);
+        assert!((800..1000).contains(&counts["elite"]), "{:?}", counts);
+        assert!((800..1000).contains(&counts["normal"]), "{:?}", counts);
+    }
+
+    #[test]
+    fn test_affixes_stack_and_scale_stats() {
+        let table = table();
+        let affixes = [&table.affixes["hulking"], &table.affixes["swift"], &table.affixes["vampiric"]];
+        let elite = Elite::new("champion", &table.ranks["champion"], &affixes);
+        assert_eq!(elite.prefixes, vec!["Hulking", "Swift", "Vampiric"]);
+        assert_eq!(elite.loot_rolls, 3);
+
+        let base = BaseStats { max_hp: 30, damage: 5, move_cooldown_ms: 600, attack_cooldown_ms: 1000 };
+        let scaled = elite.apply(base);
+        assert_eq!(scaled, BaseStats { max_hp: 60, damage: 5, move_cooldown_ms: 300, attack_cooldown_ms: 500 });
+        assert_eq!(elite.lifesteal(9), 4);
+        assert_eq!(elite.reflected(9), 0);
+    }
+
+    #[test]
+    fn test_empty_table_never_rolls() {
+        let mut rng = StdRng::seed_from_u64(1);
+        assert!(EliteTable::new().roll(&mut rng).is_none());
+        assert!(table().load_from_str("[ranks.elite]\naffixes = 1").is_err());
+    }
+}
@@ -1,5 +1,10 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use crate::behavior::BehaviorProfile;
+use crate::boss::BossConfig;
+use crate::pet::PetConfig;
+use crate::reputation::ReputationReward;
+use crate::status_effect::OnHitEffect;
 
 // ============================================================================
 // Animation Types
@@ -54,6 +59,8 @@ pub struct RawEntityRewards {
     pub exp_base: Option<i32>,
     pub gold_min: Option<i32>,
     pub gold_max: Option<i32>,
+    /// Reputation changes for the killer
+    pub reputation: Option<Vec<ReputationReward>>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -83,6 +90,11 @@ pub struct RawEntityBehaviors {
     #[serde(default)]
     pub teleporter: bool,
     #[serde(default)]
+    pub bounty_board: bool,
+    /// Companion summoned by a pet item (see `crate::pet`)
+    #[serde(default)]
+    pub pet: bool,
+    #[serde(default)]
     pub wander_enabled: bool,
     pub wander_radius: Option<i32>,
     pub wander_pause_min_ms: Option<u64>,
@@ -97,11 +109,28 @@ pub struct MerchantConfig {
     #[serde(default = "default_sell_mult")]
     pub sell_multiplier: f32,
     pub restock_interval_minutes: Option<u32>,
+    /// Faction whose standing adjusts this merchant's prices
+    pub faction: Option<String>,
+    /// Minimum standing required to trade
+    pub requires_reputation: Option<ReputationRequirement>,
 }
 
 fn default_buy_mult() -> f32 { 0.5 }
 fn default_sell_mult() -> f32 { 1.0 }
 
+/// A ranged or magic attack fired as a projectile, alongside the melee attack
+#[derive(Debug, Clone, Deserialize)]
+pub struct RangedAttackConfig {
+    /// Projectile sprite shown by the client (e.g. "arrow", "shadow_bolt")
+    pub projectile: String,
+    /// Closest the monster likes its target; it backs off when nearer
+    #[serde(default = "default_min_range")]
+    pub min_range: i32,
+    pub max_range: i32,
+}
+
+fn default_min_range() -> i32 { 2 }
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct QuestGiverConfig {
     #[serde(default)]
@@ -109,6 +138,7 @@ pub struct QuestGiverConfig {
     pub requires_reputation: Option<ReputationRequirement>,
 }
 
+/// Minimum reputation level with a faction (see `crate::reputation` for levels)
 #[derive(Debug, Clone, Deserialize)]
 pub struct ReputationRequirement {
     pub faction: String,
@@ -141,6 +171,8 @@ pub struct RawEntityPrototype {
 
     #[serde(default)]
     pub loot: Vec<LootEntry>,
+    /// Shared table from `data/loot_tables`, rolled alongside `loot`
+    pub loot_table: Option<String>,
 
     #[serde(default)]
     pub behaviors: RawEntityBehaviors,
@@ -148,6 +180,15 @@ pub struct RawEntityPrototype {
     pub merchant: Option<MerchantConfig>,
     pub quest_giver: Option<QuestGiverConfig>,
     pub dialogue: Option<DialogueConfig>,
+    pub boss: Option<BossConfig>,
+    pub pet: Option<PetConfig>,
+    pub ranged_attack: Option<RangedAttackConfig>,
+    /// Status effects rolled on every successful hit
+    pub on_hit_effects: Option<Vec<OnHitEffect>>,
+    /// Group of related monsters that answer each other's calls for help
+    pub family: Option<String>,
+    /// Composable AI behaviors (see `crate::behavior`)
+    pub behavior_profiles: Option<Vec<BehaviorProfile>>,
 }
 
 // ============================================================================
@@ -188,6 +229,7 @@ pub struct ResolvedRewards {
     pub exp_base: i32,
     pub gold_min: i32,
     pub gold_max: i32,
+    pub reputation: Vec<ReputationReward>,
 }
 
 impl Default for ResolvedRewards {
@@ -196,6 +238,7 @@ impl Default for ResolvedRewards {
             exp_base: 10,
             gold_min: 1,
             gold_max: 5,
+            reputation: Vec::new(),
         }
     }
 }
@@ -208,6 +251,8 @@ pub struct EntityBehaviors {
     pub banker: bool,
     pub craftsman: bool,
     pub teleporter: bool,
+    pub bounty_board: bool,
+    pub pet: bool,
     pub wander_enabled: bool,
     pub wander_radius: i32,
     pub wander_pause_min_ms: u64,
@@ -223,6 +268,8 @@ impl Default for EntityBehaviors {
             banker: false,
             craftsman: false,
             teleporter: false,
+            bounty_board: false,
+            pet: false,
             wander_enabled: false,
             wander_radius: 3,
             wander_pause_min_ms: 2000,
@@ -240,6 +287,8 @@ impl From<&RawEntityBehaviors> for EntityBehaviors {
             banker: raw.banker,
             craftsman: raw.craftsman,
             teleporter: raw.teleporter,
+            bounty_board: raw.bounty_board,
+            pet: raw.pet,
             wander_enabled: raw.wander_enabled,
             wander_radius: raw.wander_radius.unwrap_or(3),
             wander_pause_min_ms: raw.wander_pause_min_ms.unwrap_or(2000),
@@ -260,11 +309,21 @@ pub struct EntityPrototype {
     pub stats: ResolvedStats,
     pub rewards: ResolvedRewards,
     pub loot: Vec<LootEntry>,
+    pub loot_table: Option<String>,
 
     pub behaviors: EntityBehaviors,
     pub merchant: Option<MerchantConfig>,
     pub quest_giver: Option<QuestGiverConfig>,
     pub dialogue: DialogueConfig,
+    pub boss: Option<BossConfig>,
+    /// Pet growth settings (defaults apply when only `behaviors.pet` is set)
+    pub pet: Option<PetConfig>,
+    pub ranged_attack: Option<RangedAttackConfig>,
+    /// Status effects rolled on every successful hit against a player
+    pub on_hit_effects: Vec<OnHitEffect>,
+    /// Monster family, defaulting to the root of the `extends` chain
+    pub family: String,
+    pub behavior_profiles: Vec<BehaviorProfile>,
 }
 
 impl EntityPrototype {
@@ -280,5 +339,11 @@ impl EntityPrototype {
             || self.behaviors.banker
             || self.behaviors.craftsman
             || self.behaviors.teleporter
+            || self.behaviors.bounty_board
+    }
+
+    /// Check if this entity can be summoned as a pet
+    pub fn is_pet(&self) -> bool {
+        self.behaviors.pet
     }
 }
@@ -38,6 +38,12 @@ impl EntityRegistry {
             self.load_toml_files(&npcs_dir, &mut raw_prototypes)?;
         }
 
+        // Load pets
+        let pets_dir = entities_dir.join("pets");
+        if pets_dir.exists() {
+            self.load_toml_files(&pets_dir, &mut raw_prototypes)?;
+        }
+
         info!("Loaded {} raw entity prototypes", raw_prototypes.len());
 
         // Second pass: resolve inheritance
@@ -201,6 +207,9 @@ impl EntityRegistry {
             gold_max: raw.rewards.gold_max
                 .or_else(|| parent.map(|p| p.rewards.gold_max))
                 .unwrap_or(5),
+            reputation: raw.rewards.reputation.clone()
+                .or_else(|| parent.map(|p| p.rewards.reputation.clone()))
+                .unwrap_or_default(),
         };
 
         // Merge loot tables (child appends to parent)
@@ -233,12 +242,29 @@ impl EntityRegistry {
             stats,
             rewards,
             loot,
+            loot_table: raw.loot_table.clone()
+                .or_else(|| parent.and_then(|p| p.loot_table.clone())),
             behaviors,
             merchant: raw.merchant.clone()
                 .or_else(|| parent.and_then(|p| p.merchant.clone())),
             quest_giver: raw.quest_giver.clone()
                 .or_else(|| parent.and_then(|p| p.quest_giver.clone())),
             dialogue: raw.dialogue.clone().unwrap_or_default(),
+            boss: raw.boss.clone()
+                .or_else(|| parent.and_then(|p| p.boss.clone())),
+            pet: raw.pet.clone()
+                .or_else(|| parent.and_then(|p| p.pet.clone())),
+            ranged_attack: raw.ranged_attack.clone()
+                .or_else(|| parent.and_then(|p| p.ranged_attack.clone())),
+            on_hit_effects: raw.on_hit_effects.clone()
+                .or_else(|| parent.map(|p| p.on_hit_effects.clone()))
+                .unwrap_or_default(),
+            family: raw.family.clone()
+                .or_else(|| parent.map(|p| p.family.clone()))
+                .unwrap_or_else(|| id.to_string()),
+            behavior_profiles: raw.behavior_profiles.clone()
+                .or_else(|| parent.map(|p| p.behavior_profiles.clone()))
+                .unwrap_or_default(),
         })
     }
 
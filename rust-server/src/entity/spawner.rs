@@ -2,12 +2,13 @@ use rand::Rng;
 
 use super::prototype::EntityPrototype;
 use crate::item::{GroundItem, GOLD_ITEM_ID};
+use crate::loot_table::LootTableRegistry;
 
-/// Generate loot drops from a prototype's loot table
+/// Generate loot drops from a prototype's inline loot and shared loot table
 pub fn generate_loot_from_prototype(
     prototype: &EntityPrototype,
-    x: f32,
-    y: f32,
+    loot_tables: &LootTableRegistry,
+    (x, y): (f32, f32),
     killer_id: &str,
     current_time: u64,
     level: i32,
@@ -62,6 +63,24 @@ pub fn generate_loot_from_prototype(
         }
     }
 
+    // Shared loot table, with quantities scaled by level
+    if let Some(table) = &prototype.loot_table {
+        for loot in loot_tables.roll(table, level, &mut rng) {
+            let id = format!("item_{}_{}", current_time, item_counter);
+            item_counter += 1;
+            drops.push(GroundItem::new_in_instance(
+                &id,
+                &loot.item_id,
+                x,
+                y,
+                loot.quantity,
+                Some(killer_id.to_string()),
+                current_time,
+                instance_id.clone(),
+            ));
+        }
+    }
+
     drops
 }
 
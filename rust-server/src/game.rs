@@ -4,17 +4,46 @@ use std::sync::Arc;
 use tokio::sync::{broadcast, mpsc, RwLock};
 use uuid::Uuid;
 
+use crate::achievement::{AchievementEvent, AchievementRegistry, PlayerAchievements};
+use crate::boss::{BossAction, BossEncounter, BossScripts, BossSnapshot, PendingTelegraph};
 use crate::chunk::ChunkCoord;
 use crate::entity::{EntityPrototype, EntityRegistry};
+use crate::entity::prototype::{MerchantConfig, ReputationRequirement};
+use crate::house::{HouseDefinition, HouseRegistry, PlayerHouse, HOUSE_MAP_PREFIX, MAX_GUESTS};
+use crate::guild::{
+    self, GuildInvite, GuildMembership, GuildRosterEntry, PlayerGuild, GUILD_INVITE_TIMEOUT_MS, GUILD_LEADER_RANK, GUILD_MAX_MEMBERS, GUILD_MOTD_MAX_LEN,
+    GUILD_PERM_DISBAND, GUILD_PERM_INVITE, GUILD_PERM_KICK, GUILD_PERM_PROMOTE, GUILD_PERM_SET_MOTD,
+};
 use crate::data::ItemRegistry;
+use crate::db::Database;
+use crate::death::{self, DeathPenalty, DeathRules, Gravestone, OVERWORLD_ZONE};
+use crate::dungeon::TreasurePlacement;
+use crate::elite::EliteTable;
+use crate::loot_table::LootTableRegistry;
 use crate::data::item_def::WeaponType;
 use crate::skills::{Skills, SkillType, calculate_hit, calculate_max_hit, roll_damage};
+use crate::spawn_region::{SpawnRegion, SpawnRegionManager};
+use crate::status_effect::{self, StatusEffects, StatusKind};
 use crate::item::{self, GroundItem, Inventory, GOLD_ITEM_ID};
-use crate::npc::{Npc, NpcUpdate};
-use crate::protocol::{ServerMessage, QuestObjectiveData};
-use crate::quest::{QuestRegistry, QuestRunner, PlayerQuestState, QuestEvent};
+use crate::npc::{Npc, NpcState, NpcUpdate};
+use crate::challenge::{ChallengePeriod, ChallengeRegistry, PlayerChallenges};
+use crate::instance::Instance;
+use crate::interior::InteriorMapDef;
+use crate::interior_registry::InteriorRegistry;
+use crate::protocol::{AchievementData, BossDamageData, ChallengeData, FurnitureData, ServerMessage, PartyMemberData, QuestObjectiveData, ReputationData, StatusEffectData, UpcomingEventData, WorldEventData};
+use crate::pvp::{self, PvpStanding, SKULL_DURATION_MS};
+use crate::threat::{DAMAGE_THREAT, HEAL_THREAT};
+use crate::pathfinding::{NavGrid, PathContext, MAX_NODES_PER_TICK};
+use crate::quest::{QuestRegistry, QuestRunner, PlayerQuestState, QuestEvent, Reward};
+use crate::reputation::{self, FactionRegistry, PlayerReputation, ReputationReward};
+use crate::routine::Routine;
 use crate::shop::{ShopRegistry, ShopDefinition, ShopStockItem};
+use crate::party::{LootRule, PartyManager, split_xp, PARTY_SHARE_DISTANCE};
+use crate::pet::{self, PlayerPets, SummonedPet, PET_ASSIST_RANGE, PET_FOLLOW_DISTANCE, PET_TELEPORT_DISTANCE};
+use crate::trade::{TradeManager, TradeSession, TRADE_MAX_DISTANCE, TRADE_MAX_OFFER_ITEMS};
 use crate::world::World;
+use crate::world_event::{EventWave, WorldEventDefinition, WorldEventManager, WorldEventRegistry};
+use crate::world_script::{ScriptAction, ScriptHook, ScriptOutput, WorldScriptHost, WorldScripts, SCRIPT_DIALOGUE_PREFIX};
 
 // ============================================================================
 // Constants
@@ -31,10 +60,16 @@ const STARTING_HP: i32 = 100;
 
 // Combat constants
 const ATTACK_RANGE: i32 = 1; // Maximum distance to attack (in tiles)
-const ATTACK_COOLDOWN_MS: u64 = 700; // Slightly shorter than client (800ms) to account for network latency
+pub const ATTACK_COOLDOWN_MS: u64 = 700; // Slightly shorter than client (800ms) to account for network latency
 const PLAYER_HP_REGEN_PERCENT: f32 = 2.0;
 const REGEN_INTERVAL_MS: u64 = 30000;
 
+// How long a killed world event NPC or boss add stays before it is despawned
+const TEMPORARY_NPC_LINGER_MS: u64 = 3000;
+
+// Players within this many tiles of a boss are visible to its script
+const BOSS_AREA_RADIUS: i32 = 15;
+
 // ============================================================================
 // Player Save Data (for database persistence)
 // ============================================================================
@@ -99,6 +134,20 @@ impl Direction {
         }
     }
 
+    /// Tile offset one step in this direction
+    pub fn offset(&self) -> (i32, i32) {
+        match self {
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Up => (0, -1),
+            Direction::Right => (1, 0),
+            Direction::DownLeft => (-1, 1),
+            Direction::DownRight => (1, 1),
+            Direction::UpLeft => (-1, -1),
+            Direction::UpRight => (1, -1),
+        }
+    }
+
     pub fn from_u8(value: u8) -> Self {
         match value {
             0 => Direction::Down,
@@ -122,6 +171,7 @@ impl Direction {
 pub struct Player {
     pub id: String,
     pub name: String,
+    pub character_id: i64, // Database character ID (0 if not loaded from a character)
     // Grid position (integer tile coordinates)
     pub x: i32,
     pub y: i32,
@@ -158,18 +208,34 @@ pub struct Player {
     // Admin privileges
     pub is_admin: bool,
     pub is_god_mode: bool, // Invincibility for admins
+    // Guild tag shown on the nameplate
+    pub guild_tag: Option<String>,
+    // Achievement title shown before the name
+    pub title: Option<String>,
     // HP regeneration tracking
     pub last_regen_time: u64,
+    // Died under a hardcore death rule: stays dead for good
+    pub perished: bool,
+    // Opted in to PvP outside PvP zones
+    pub pvp_enabled: bool,
+    // When the skull from attacking outside a PvP zone wears off (ms)
+    pub skull_expires_at: u64,
+    // Poison, stun, slow and bleed from monster hits
+    pub status_effects: StatusEffects,
 }
 
 const PLAYER_RESPAWN_TIME_MS: u64 = 5000; // 5 seconds to respawn
 
+/// (SkillType, xp_gained, total_xp, level, leveled_up) for a skill that gained XP
+pub type SkillXpGain = (SkillType, i64, i64, i32, bool);
+
 impl Player {
     pub fn new(id: &str, name: &str, spawn_x: i32, spawn_y: i32, gender: &str, skin: &str, hair_style: Option<i32>, hair_color: Option<i32>) -> Self {
         let skills = Skills::new(); // HP 10, Attack/Strength/Defence 1
         Self {
             id: id.to_string(),
             name: name.to_string(),
+            character_id: 0,
             x: spawn_x,
             y: spawn_y,
             spawn_x,
@@ -201,7 +267,13 @@ impl Player {
             equipped_belt: None,
             is_admin: false,
             is_god_mode: false,
+            guild_tag: None,
+            title: None,
             last_regen_time: 0,
+            perished: false,
+            pvp_enabled: false,
+            skull_expires_at: 0,
+            status_effects: StatusEffects::new(),
         }
     }
 
@@ -275,11 +347,22 @@ impl Player {
         bonus
     }
 
+    /// Total equipment resistance (percent) to a status effect
+    pub fn resistance(&self, kind: StatusKind, item_registry: &ItemRegistry) -> i32 {
+        self.all_equipped()
+            .into_iter()
+            .flatten()
+            .filter_map(|item_id| item_registry.get(item_id))
+            .filter_map(|def| def.equipment.as_ref())
+            .filter_map(|equip| equip.resistances.get(&kind))
+            .sum()
+    }
+
     /// Award combat XP based on damage dealt.
     /// Combat skill gets 4 XP per damage.
     /// Hitpoints gets 1.33 XP per damage (1/3 of combat rate).
-    /// Returns a vector of (SkillType, xp_gained, total_xp, level, leveled_up) for skills that gained XP.
-    pub fn award_combat_xp(&mut self, damage: i32) -> Vec<(SkillType, i64, i64, i32, bool)> {
+    /// Returns a SkillXpGain for each skill that gained XP.
+    pub fn award_combat_xp(&mut self, damage: i32) -> Vec<SkillXpGain> {
         use crate::skills::{COMBAT_XP_PER_DAMAGE, HITPOINTS_XP_PER_DAMAGE};
 
         let mut results = Vec::new();
@@ -322,10 +405,15 @@ impl Player {
         self.move_dx = 0;
         self.move_dy = 0;
         self.target_id = None;
+        self.status_effects.clear();
+    }
+
+    pub fn is_skulled(&self, current_time: u64) -> bool {
+        current_time < self.skull_expires_at
     }
 
     pub fn ready_to_respawn(&self, current_time: u64) -> bool {
-        self.is_dead && (current_time - self.death_time >= PLAYER_RESPAWN_TIME_MS)
+        self.is_dead && !self.perished && (current_time - self.death_time >= PLAYER_RESPAWN_TIME_MS)
     }
 
     pub fn respawn(&mut self) {
@@ -393,6 +481,36 @@ pub struct PlayerUpdate {
     pub equipped_belt: Option<String>,
     // Admin status
     pub is_admin: bool,
+    pub guild_tag: Option<String>,
+    pub title: Option<String>,
+    // PvP status shown on the nameplate
+    pub pvp_enabled: bool,
+    pub skulled: bool,
+}
+
+/// An attack an NPC made this tick, resolved against its target afterwards
+struct NpcAttack {
+    npc_id: String,
+    prototype_id: String,
+    target_id: String,
+    /// Attack level for the hit roll
+    level: i32,
+    max_hit: i32,
+    projectile: Option<String>,
+}
+
+/// Which NPCs a player can reach, from `GameRoom::npcs_near`
+enum NpcMap {
+    Overworld,
+    Instance(Arc<Instance>),
+}
+
+/// What one map's NPCs did during a tick
+#[derive(Default)]
+struct NpcTickResult {
+    updates: Vec<NpcUpdate>,
+    respawned: Vec<(String, i32, i32)>,
+    attacks: Vec<NpcAttack>,
 }
 
 // ============================================================================
@@ -408,6 +526,8 @@ pub struct GameRoom {
     world: Arc<World>,
     /// Entity prototype registry for spawning and loot
     entity_registry: Arc<EntityRegistry>,
+    /// Shared loot tables for monster drops and quest rewards
+    loot_tables: Arc<LootTableRegistry>,
     /// Quest registry for quest definitions
     quest_registry: Arc<QuestRegistry>,
     /// Quest script runner for Lua execution
@@ -432,27 +552,106 @@ pub struct GameRoom {
     player_instances: Arc<RwLock<HashMap<String, String>>>,
     /// Instance manager for looking up instance NPCs
     instance_manager: Arc<crate::instance::InstanceManager>,
+    /// Pending trade requests and open trade sessions
+    trades: RwLock<TradeManager>,
+    /// Parties and pending party invites
+    parties: RwLock<PartyManager>,
+    /// Database for guild persistence
+    db: Arc<Database>,
+    /// Character ID and guild membership of connected players
+    player_guilds: RwLock<HashMap<String, PlayerGuild>>,
+    /// Invitee ID -> pending guild invite
+    guild_invites: RwLock<HashMap<String, GuildInvite>>,
+    /// Achievement definitions
+    achievement_registry: Arc<AchievementRegistry>,
+    /// Character ID and achievement progress of connected players
+    player_achievements: RwLock<HashMap<String, PlayerAchievements>>,
+    /// Faction definitions
+    faction_registry: FactionRegistry,
+    /// Per-player faction standings
+    player_reputations: RwLock<HashMap<String, PlayerReputation>>,
+    /// Daily/weekly challenge pool and rotation settings
+    challenge_registry: ChallengeRegistry,
+    /// Per-player challenge progress
+    player_challenges: RwLock<HashMap<String, PlayerChallenges>>,
+    /// World event definitions
+    world_event_registry: WorldEventRegistry,
+    /// Running world events, cooldowns and schedule state
+    world_events: RwLock<WorldEventManager>,
+    /// Monster spawn regions from the map and their respawn timers
+    spawn_regions: RwLock<SpawnRegionManager>,
+    /// Elite ranks and affixes hostile spawns can roll
+    elite_table: EliteTable,
+    /// Lua sources for boss encounters
+    boss_scripts: BossScripts,
+    /// World script sources, reloaded when they change on disk
+    world_scripts: Arc<WorldScripts>,
+    /// Lua state running the world scripts
+    script_host: RwLock<WorldScriptHost>,
+    /// Boss NPC ID -> encounter in progress
+    boss_encounters: RwLock<HashMap<String, BossEncounter>>,
+    /// Per-player pet levels and active pet
+    player_pets: RwLock<HashMap<String, PlayerPets>>,
+    /// Owner player ID -> pet currently out
+    pets: RwLock<HashMap<String, SummonedPet>>,
+    /// House definitions
+    house_registry: HouseRegistry,
+    /// Per-player house, furniture and guest list
+    player_houses: RwLock<HashMap<String, PlayerHouse>>,
+    /// Visitor player ID -> owner whose house the next house door leads to
+    house_visits: RwLock<HashMap<String, String>>,
+    /// Death penalty per zone
+    death_rules: DeathRules,
+    /// Gravestone ID -> items a dead player left behind
+    gravestones: RwLock<HashMap<String, Gravestone>>,
+    /// Interior map definitions, for PvP zones inside instances
+    interior_registry: Arc<InteriorRegistry>,
+}
+
+/// Registries and shared state a game room is built with
+pub struct RoomServices {
+    pub entity_registry: Arc<EntityRegistry>,
+    pub loot_tables: Arc<LootTableRegistry>,
+    pub quest_registry: Arc<QuestRegistry>,
+    pub crafting_registry: Arc<crate::crafting::CraftingRegistry>,
+    pub item_registry: Arc<ItemRegistry>,
+    pub player_instances: Arc<RwLock<HashMap<String, String>>>,
+    pub instance_manager: Arc<crate::instance::InstanceManager>,
+    pub db: Arc<Database>,
+    pub achievement_registry: Arc<AchievementRegistry>,
+    pub interior_registry: Arc<InteriorRegistry>,
 }
 
 impl GameRoom {
-    pub async fn new(
-        name: &str,
-        entity_registry: Arc<EntityRegistry>,
-        quest_registry: Arc<QuestRegistry>,
-        crafting_registry: Arc<crate::crafting::CraftingRegistry>,
-        item_registry: Arc<ItemRegistry>,
-        player_instances: Arc<RwLock<HashMap<String, String>>>,
-        instance_manager: Arc<crate::instance::InstanceManager>,
-    ) -> Self {
+    pub async fn new(name: &str, services: RoomServices) -> Self {
+        let RoomServices {
+            entity_registry,
+            loot_tables,
+            quest_registry,
+            crafting_registry,
+            item_registry,
+            player_instances,
+            instance_manager,
+            db,
+            achievement_registry,
+            interior_registry,
+        } = services;
         let (tx, _) = broadcast::channel(256);
         let world = Arc::new(World::new("maps/world_0"));
 
         // Create quest runner with the registry
         let quest_runner = Arc::new(QuestRunner::new(quest_registry.clone()));
 
+        // Load elite ranks and affixes before the first monsters spawn
+        let mut elite_table = EliteTable::new();
+        if let Err(e) = elite_table.load_from_directory(std::path::Path::new("data")) {
+            tracing::error!("Failed to load elite table: {}", e);
+        }
+
         // Load all chunks and spawn NPCs from entity_spawns
         let mut npcs = HashMap::new();
         let mut npc_counter = 0u32;
+        let mut spawn_regions = Vec::new();
 
         // Discover all chunk files and load entities from each
         let chunk_coords = world.discover_chunk_coords();
@@ -460,6 +659,7 @@ impl GameRoom {
 
         for coord in chunk_coords {
             if let Some(chunk) = world.get_or_load_chunk(coord).await {
+                spawn_regions.extend(chunk.spawn_regions.iter().cloned());
                 for spawn in &chunk.entity_spawns {
                     let npc_id = spawn.unique_id.clone()
                         .unwrap_or_else(|| format!("npc_{}", npc_counter));
@@ -471,7 +671,7 @@ impl GameRoom {
                             "Spawning {} at ({}, {}) level {}",
                             spawn.entity_id, spawn.world_x, spawn.world_y, spawn.level
                         );
-                        let npc = Npc::from_prototype(
+                        let mut npc = Npc::from_prototype(
                             &npc_id,
                             &spawn.entity_id,
                             prototype,
@@ -479,6 +679,8 @@ impl GameRoom {
                             spawn.world_y,
                             spawn.level,
                         );
+                        npc.routine = Routine::new(&spawn.patrol, &spawn.schedule);
+                        npc.set_elite(elite_table.roll_for(prototype, &mut rand::thread_rng()));
                         npcs.insert(npc_id, npc);
                     } else {
                         tracing::warn!("Prototype '{}' not found, skipping spawn", spawn.entity_id);
@@ -488,6 +690,7 @@ impl GameRoom {
         }
 
         tracing::info!("Spawned {} NPCs from chunk entity_spawns", npcs.len());
+        tracing::info!("Loaded {} spawn regions", spawn_regions.len());
 
         // Load shop registry
         let mut shop_registry = ShopRegistry::new();
@@ -496,6 +699,65 @@ impl GameRoom {
         }
         tracing::info!("Loaded {} shop definitions", shop_registry.len());
 
+        // Load faction registry
+        let mut faction_registry = FactionRegistry::new();
+        if let Err(e) = faction_registry.load_from_directory(std::path::Path::new("data/factions")) {
+            tracing::error!("Failed to load faction registry: {}", e);
+        }
+
+        // Load challenge pool
+        let mut challenge_registry = ChallengeRegistry::new();
+        if let Err(e) = challenge_registry.load_from_directory(std::path::Path::new("data/challenges")) {
+            tracing::error!("Failed to load challenge registry: {}", e);
+        }
+
+        // Load world events
+        let mut world_event_registry = WorldEventRegistry::new();
+        if let Err(e) = world_event_registry.load_from_directory(std::path::Path::new("data/events")) {
+            tracing::error!("Failed to load world event registry: {}", e);
+        }
+
+        // Load boss scripts and check every boss prototype has one
+        let mut boss_scripts = BossScripts::new();
+        if let Err(e) = boss_scripts.load_from_directory(std::path::Path::new("data")) {
+            tracing::error!("Failed to load boss scripts: {}", e);
+        }
+        for prototype in entity_registry.all() {
+            if let Some(boss) = &prototype.boss
+                && boss_scripts.get(&boss.script).is_none()
+            {
+                tracing::warn!("Boss {} references missing script '{}'", prototype.id, boss.script);
+            }
+        }
+
+        // Load world scripts and watch them for changes (dev mode)
+        let world_scripts = Arc::new(WorldScripts::new(std::path::Path::new("data")));
+        if let Err(e) = world_scripts.reload() {
+            tracing::error!("Failed to load world scripts: {}", e);
+        }
+        #[cfg(debug_assertions)]
+        if let Err(e) = world_scripts.start_file_watcher() {
+            tracing::warn!("Failed to start world script hot-reload: {}", e);
+        }
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        let script_host = WorldScriptHost::new(&world_scripts.snapshot(), world_scripts.version(), now)
+            .expect("Failed to create world script state");
+
+        // Load house definitions
+        let mut house_registry = HouseRegistry::new();
+        if let Err(e) = house_registry.load_from_directory(std::path::Path::new("data")) {
+            tracing::error!("Failed to load house registry: {}", e);
+        }
+
+        // Load death penalty rules
+        let mut death_rules = DeathRules::new();
+        if let Err(e) = death_rules.load_from_directory(std::path::Path::new("data")) {
+            tracing::error!("Failed to load death rules: {}", e);
+        }
+
         Self {
             id: Uuid::new_v4().to_string(),
             name: name.to_string(),
@@ -504,6 +766,7 @@ impl GameRoom {
             ground_items: RwLock::new(HashMap::new()),
             world,
             entity_registry,
+            loot_tables,
             quest_registry,
             quest_runner,
             player_quest_states: RwLock::new(HashMap::new()),
@@ -517,6 +780,33 @@ impl GameRoom {
             player_senders: RwLock::new(HashMap::new()),
             player_instances,
             instance_manager,
+            trades: RwLock::new(TradeManager::new()),
+            parties: RwLock::new(PartyManager::new()),
+            db,
+            player_guilds: RwLock::new(HashMap::new()),
+            guild_invites: RwLock::new(HashMap::new()),
+            achievement_registry,
+            player_achievements: RwLock::new(HashMap::new()),
+            faction_registry,
+            player_reputations: RwLock::new(HashMap::new()),
+            challenge_registry,
+            player_challenges: RwLock::new(HashMap::new()),
+            world_event_registry,
+            world_events: RwLock::new(WorldEventManager::new()),
+            spawn_regions: RwLock::new(SpawnRegionManager::new(spawn_regions)),
+            elite_table,
+            boss_scripts,
+            world_scripts,
+            script_host: RwLock::new(script_host),
+            boss_encounters: RwLock::new(HashMap::new()),
+            player_pets: RwLock::new(HashMap::new()),
+            pets: RwLock::new(HashMap::new()),
+            house_registry,
+            player_houses: RwLock::new(HashMap::new()),
+            house_visits: RwLock::new(HashMap::new()),
+            death_rules,
+            gravestones: RwLock::new(HashMap::new()),
+            interior_registry,
         }
     }
 
@@ -524,6 +814,37 @@ impl GameRoom {
         self.broadcast_tx.subscribe()
     }
 
+    /// Map an instance runs: its generated map, or the hand-authored interior
+    fn instance_map<'a>(&'a self, instance: &'a Instance) -> Option<&'a InteriorMapDef> {
+        instance.generated_map.as_deref()
+            .or_else(|| self.interior_registry.get(&instance.map_id))
+    }
+
+    /// NPCs on the map a player is on: their instance's, or the overworld's
+    async fn npcs_near(&self, player_id: &str) -> NpcMap {
+        match self.instance_manager.find_player_instance(player_id).await {
+            Some(instance) => NpcMap::Instance(instance),
+            None => NpcMap::Overworld,
+        }
+    }
+
+    /// Line of sight over the collision of a map from `npcs_near`
+    async fn has_line_of_sight_on(&self, map: &NpcMap, from: (i32, i32), to: (i32, i32)) -> bool {
+        match map {
+            NpcMap::Instance(instance) => self.instance_map(instance)
+                .is_some_and(|interior| instance.nav_grid(interior).has_line_of_sight(from.0, from.1, to.0, to.1)),
+            NpcMap::Overworld => self.world.has_line_of_sight(from.0, from.1, to.0, to.1).await,
+        }
+    }
+
+    /// Lock for the NPCs of a map from `npcs_near`
+    fn npc_lock<'a>(&'a self, map: &'a NpcMap) -> &'a RwLock<HashMap<String, Npc>> {
+        match map {
+            NpcMap::Instance(instance) => &instance.npcs,
+            NpcMap::Overworld => &self.npcs,
+        }
+    }
+
     pub async fn broadcast(&self, msg: ServerMessage) {
         // Ignore send errors (no receivers)
         let _ = self.broadcast_tx.send(msg);
@@ -638,6 +959,7 @@ impl GameRoom {
     pub async fn reserve_player_with_data(
         &self,
         player_id: &str,
+        character_id: i64,
         name: &str,
         x: i32,
         y: i32,
@@ -664,6 +986,7 @@ impl GameRoom {
         let mut player = Player::new(player_id, name, x, y, gender, skin, hair_style, hair_color);
 
         // Restore saved stats
+        player.character_id = character_id;
         player.hp = hp.min(skills.hitpoints.level); // Cap HP at max (hitpoints level)
         player.skills = skills;
         player.inventory.gold = gold;
@@ -722,6 +1045,20 @@ impl GameRoom {
     }
 
     pub async fn remove_player(&self, player_id: &str) {
+        self.cancel_trade(player_id, "Your trade partner disconnected").await;
+        self.trades.write().await.clear_requests_for(player_id);
+        self.handle_party_disconnect(player_id).await;
+        self.guild_invites.write().await.remove(player_id);
+        self.player_guilds.write().await.remove(player_id);
+        self.player_reputations.write().await.remove(player_id);
+        self.player_achievements.write().await.remove(player_id);
+        self.player_challenges.write().await.remove(player_id);
+        // The active pet stays saved and is summoned again on the next login
+        self.despawn_pet(player_id).await;
+        self.player_pets.write().await.remove(player_id);
+        self.player_houses.write().await.remove(player_id);
+        self.house_visits.write().await.remove(player_id);
+
         let mut players = self.players.write().await;
         players.remove(player_id);
     }
@@ -848,11 +1185,56 @@ impl GameRoom {
         players.get(player_id).map(|p| (p.hair_style, p.hair_color))
     }
 
+    pub async fn get_player_guild_tag(&self, player_id: &str) -> Option<String> {
+        let players = self.players.read().await;
+        players.get(player_id).and_then(|p| p.guild_tag.clone())
+    }
+
     pub async fn get_player_name(&self, player_id: &str) -> Option<String> {
         let players = self.players.read().await;
         players.get(player_id).map(|p| p.name.clone())
     }
 
+    pub async fn get_player_character_id(&self, player_id: &str) -> Option<i64> {
+        let players = self.players.read().await;
+        players.get(player_id).map(|p| p.character_id)
+    }
+
+    pub async fn get_player_combat_level(&self, player_id: &str) -> Option<i32> {
+        let players = self.players.read().await;
+        players.get(player_id).map(|p| p.combat_level())
+    }
+
+    /// Place a generated dungeon's treasure in its instance (unowned, never expires)
+    pub async fn place_dungeon_treasure(&self, instance_id: &str, treasure: &[TreasurePlacement]) {
+        let current_time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        let mut items = self.ground_items.write().await;
+        for placement in treasure {
+            let mut item = GroundItem::new_in_instance(
+                &uuid::Uuid::new_v4().to_string(),
+                &placement.item_id,
+                placement.x as f32,
+                placement.y as f32,
+                placement.quantity,
+                None,
+                current_time,
+                Some(instance_id.to_string()),
+            );
+            item.persistent = true;
+            items.insert(item.id.clone(), item);
+        }
+    }
+
+    /// Remove every ground item left in an instance (called when the instance is removed)
+    pub async fn remove_instance_ground_items(&self, instance_id: &str) {
+        let mut items = self.ground_items.write().await;
+        items.retain(|_, item| item.instance_id.as_deref() != Some(instance_id));
+    }
+
     /// Get all ground items in a specific instance (or overworld if None)
     pub async fn get_ground_items_in_instance(&self, instance_id: Option<&str>) -> Vec<ServerMessage> {
         let items = self.ground_items.read().await;
@@ -908,7 +1290,7 @@ impl GameRoom {
 
         let mut npcs = self.npcs.write().await;
         npcs.insert(npc_id.clone(), npc);
-        tracing::info!("Admin spawned NPC {} at ({}, {})", prototype_id, x, y);
+        tracing::info!("Spawned NPC {} at ({}, {})", prototype_id, x, y);
         Some(npc_id)
     }
 
@@ -933,6 +1315,17 @@ impl GameRoom {
                 move_dy = 0;
             }
 
+            // Stunned players stay put
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as u64;
+            if player.status_effects.is_stunned(now) {
+                player.move_dx = 0;
+                player.move_dy = 0;
+                return;
+            }
+
             player.move_dx = move_dx;
             player.move_dy = move_dy;
 
@@ -981,6 +1374,7 @@ impl GameRoom {
                     .duration_since(std::time::UNIX_EPOCH)
                     .unwrap()
                     .as_millis() as u64,
+                channel: None,
             };
             drop(players); // Release lock before broadcast
             self.broadcast(msg).await;
@@ -999,7 +1393,7 @@ impl GameRoom {
         };
 
         // Admin-only commands check
-        let admin_commands = ["/give", "/setlevel", "/teleport", "/spawn", "/heal", "/kill", "/god", "/announce"];
+        let admin_commands = ["/give", "/setlevel", "/teleport", "/spawn", "/heal", "/kill", "/god", "/announce", "/event"];
         if admin_commands.contains(&command.as_str()) && !is_admin {
             self.send_system_message(player_id, "This command requires admin privileges.").await;
             return;
@@ -1085,11 +1479,93 @@ impl GameRoom {
             }
             "/help" => {
                 if is_admin {
-                    self.send_system_message(player_id, "Commands: /give <item> [qty], /setlevel <lvl>, /teleport <x> <y>, /spawn <npc> [x] [y], /heal [player], /kill <player>, /god, /announce <msg>, /items, /help").await;
+                    self.send_system_message(player_id, "Commands: /give <item> [qty], /setlevel <lvl>, /teleport <x> <y>, /spawn <npc> [x] [y], /heal [player], /kill <player>, /god, /announce <msg>, /event [start|stop <id>], /trade <player>, /invite <player>, /party, /p <msg>, /guild, /g <msg>, /house, /pvp [on|off], /items, /help").await;
                 } else {
-                    self.send_system_message(player_id, "Commands: /items, /help").await;
+                    self.send_system_message(player_id, "Commands: /trade <player>, /invite <player>, /party, /p <msg>, /guild, /g <msg>, /house, /pvp [on|off], /items, /help").await;
+                }
+            }
+            "/trade" => {
+                // /trade <player_name>
+                if parts.len() < 2 {
+                    self.send_system_message(player_id, "Usage: /trade <player_name>").await;
+                    return;
+                }
+                match self.find_active_player_by_name(parts[1]).await {
+                    Some(target_id) => self.handle_trade_request(player_id, &target_id).await,
+                    None => self.send_system_message(player_id, "Player not found").await,
+                }
+            }
+            "/p" => {
+                // /p <message> - party chat
+                let message = text.split_once(char::is_whitespace).map(|(_, m)| m.trim()).unwrap_or("");
+                if message.is_empty() {
+                    self.send_system_message(player_id, "Usage: /p <message>").await;
+                    return;
+                }
+                self.handle_party_chat(player_id, message).await;
+            }
+            "/invite" => {
+                // /invite <player_name>
+                if parts.len() < 2 {
+                    self.send_system_message(player_id, "Usage: /invite <player_name>").await;
+                    return;
+                }
+                match self.find_active_player_by_name(parts[1]).await {
+                    Some(target_id) => self.handle_party_invite(player_id, &target_id).await,
+                    None => self.send_system_message(player_id, "Player not found").await,
+                }
+            }
+            "/party" => {
+                // /party leave | kick <name> | leader <name> | loot <shared|roundrobin>
+                let sub = parts.get(1).map(|s| s.to_lowercase()).unwrap_or_default();
+                let member_id = match parts.get(2) {
+                    Some(name) => {
+                        let parties = self.parties.read().await;
+                        parties.party_of(player_id).and_then(|party| {
+                            party.members.iter()
+                                .find(|m| m.name.eq_ignore_ascii_case(name))
+                                .map(|m| m.player_id.clone())
+                        })
+                    }
+                    None => None,
+                };
+                match (sub.as_str(), member_id) {
+                    ("leave", _) => self.handle_party_leave(player_id).await,
+                    ("kick", Some(member_id)) => self.handle_party_kick(player_id, &member_id).await,
+                    ("leader", Some(member_id)) => self.handle_party_promote(player_id, &member_id).await,
+                    ("kick", None) | ("leader", None) => {
+                        self.send_system_message(player_id, "No party member with that name.").await;
+                    }
+                    ("loot", _) => match parts.get(2).and_then(|r| LootRule::parse(r)) {
+                        Some(rule) => self.handle_party_loot_rule(player_id, rule).await,
+                        None => self.send_system_message(player_id, "Usage: /party loot <shared|roundrobin>").await,
+                    },
+                    _ => {
+                        self.send_system_message(player_id, "Usage: /party leave | kick <name> | leader <name> | loot <shared|roundrobin>").await;
+                    }
                 }
             }
+            "/g" => {
+                // /g <message> - guild chat
+                let message = text.split_once(char::is_whitespace).map(|(_, m)| m.trim()).unwrap_or("");
+                if message.is_empty() {
+                    self.send_system_message(player_id, "Usage: /g <message>").await;
+                    return;
+                }
+                self.handle_guild_chat(player_id, message).await;
+            }
+            "/guild" => {
+                // /guild [create | invite | accept | decline | leave | kick | promote | demote | leader | motd | disband | roster]
+                self.handle_guild_command(player_id, &parts, text).await;
+            }
+            "/house" => {
+                // /house list | buy <id> | invite <name> | uninvite <name> | guests | visit <name> | pickup
+                self.handle_house_command(player_id, &parts).await;
+            }
+            "/pvp" => {
+                // /pvp [on|off]
+                self.handle_pvp_command(player_id, &parts).await;
+            }
             "/items" => {
                 // List available items
                 let items: Vec<&String> = self.item_registry.ids().collect();
@@ -1252,6 +1728,10 @@ impl GameRoom {
                 }).await;
                 tracing::info!("Admin {} announced: {}", player_id, message);
             }
+            "/event" => {
+                // /event [start <id> | stop <id>]
+                self.handle_event_command(player_id, &parts).await;
+            }
             _ => {
                 self.send_system_message(player_id, &format!("Unknown command: {}. Try /help", command)).await;
             }
@@ -1268,6 +1748,7 @@ impl GameRoom {
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_millis() as u64,
+            channel: None,
         };
         self.send_to_player(player_id, msg).await;
     }
@@ -1290,8 +1771,8 @@ impl GameRoom {
                 }
             };
 
-            // Dead players can't attack
-            if player.is_dead {
+            // Dead or stunned players can't attack
+            if player.is_dead || player.status_effects.is_stunned(current_time) {
                 return;
             }
 
@@ -1363,20 +1844,24 @@ impl GameRoom {
             Direction::DownRight => (1, 1),
         };
 
+        // Players can only hit players and NPCs on the same map
+        let attacker_instance = self.player_instances.read().await.get(player_id).cloned();
+        let npc_map = self.npcs_near(player_id).await;
+
         // Scan tiles in facing direction up to weapon range
         for dist in 1..=weapon_range {
             let check_x = attacker_x + dir_dx * dist;
             let check_y = attacker_y + dir_dy * dist;
 
             // For ranged weapons, check line of sight
-            if weapon_range > 1 && !self.world.has_line_of_sight(attacker_x, attacker_y, check_x, check_y).await {
+            if weapon_range > 1 && !self.has_line_of_sight_on(&npc_map, (attacker_x, attacker_y), (check_x, check_y)).await {
                 tracing::debug!("{} ranged attack blocked by wall at ({}, {})", attacker_name, check_x, check_y);
                 break;
             }
 
             // Check NPCs at this tile
             {
-                let npcs = self.npcs.read().await;
+                let npcs = self.npc_lock(&npc_map).read().await;
                 for (npc_id, npc) in npcs.iter() {
                     if npc.is_alive() && npc.is_attackable() && npc.x == check_x && npc.y == check_y {
                         target_id = Some(npc_id.clone());
@@ -1393,8 +1878,11 @@ impl GameRoom {
             // Check players at this tile
             {
                 let players = self.players.read().await;
+                let instances = self.player_instances.read().await;
                 for (pid, player) in players.iter() {
-                    if pid != player_id && player.active && player.hp > 0 && player.x == check_x && player.y == check_y {
+                    if pid != player_id && player.active && player.hp > 0 && player.x == check_x && player.y == check_y
+                        && instances.get(pid) == attacker_instance.as_ref()
+                    {
                         target_id = Some(pid.clone());
                         is_npc = false;
                         target_tile_x = check_x;
@@ -1416,15 +1904,44 @@ impl GameRoom {
             }
         };
 
+        // Attacks on players must pass the PvP rules (zone or opt-in)
+        let (pvp_skull, pvp_in_zone) = if is_npc {
+            (false, false)
+        } else {
+            let (Some(attacker), Some(target)) = (
+                self.pvp_standing(player_id, current_time).await,
+                self.pvp_standing(&target_id, current_time).await,
+            ) else {
+                return;
+            };
+            match pvp::check_attack(attacker, target) {
+                Ok(skull) => (skull, attacker.in_zone && target.in_zone),
+                Err(reason) => {
+                    self.send_system_message(player_id, reason).await;
+                    return;
+                }
+            }
+        };
+
         // Update attacker's last attack time and stop movement
-        {
+        let newly_skulled = {
             let mut players = self.players.write().await;
             if let Some(player) = players.get_mut(player_id) {
                 player.last_attack_time = current_time;
                 // Stop movement when attacking (player must stand still to attack)
                 player.move_dx = 0;
                 player.move_dy = 0;
+                let was_skulled = player.is_skulled(current_time);
+                if pvp_skull {
+                    player.skull_expires_at = current_time + SKULL_DURATION_MS;
+                }
+                pvp_skull && !was_skulled
+            } else {
+                false
             }
+        };
+        if newly_skulled {
+            self.send_system_message(player_id, "You have been skulled for attacking a player outside a PvP zone.").await;
         }
 
         // Apply damage to target using hit/miss mechanics
@@ -1432,7 +1949,7 @@ impl GameRoom {
         // 2. If hit, calculate max hit from strength and roll damage
         let (target_hp, target_name, target_died, actual_damage) = if is_npc {
             // NPCs use their level as both attack and defence level (no equipment bonuses)
-            let mut npcs = self.npcs.write().await;
+            let mut npcs = self.npc_lock(&npc_map).write().await;
             if let Some(npc) = npcs.get_mut(&target_id) {
                 // NPC's defence = level, no equipment bonus
                 let npc_defence_level = npc.level;
@@ -1531,6 +2048,19 @@ impl GameRoom {
         };
         self.broadcast(damage_msg).await;
 
+        if is_npc {
+            self.add_damage_threat(&target_id, player_id, actual_damage).await;
+            if actual_damage > 0 {
+                self.reflect_elite_damage(player_id, &target_id, actual_damage, current_time).await;
+            }
+        }
+
+        // Damage against world event NPCs counts as contribution, and against bosses for the leaderboard
+        if is_npc && actual_damage > 0 {
+            self.record_event_contribution(player_id, &target_id, actual_damage).await;
+            self.record_boss_damage(player_id, &target_id, actual_damage).await;
+        }
+
         // Send success result to attacker
         let result_msg = ServerMessage::AttackResult {
             success: true,
@@ -1542,133 +2072,7 @@ impl GameRoom {
         if target_died {
             tracing::info!("{} killed {}", attacker_name, target_name);
             if is_npc {
-                // Get NPC info for exp and loot
-                let (prototype_id, npc_level) = {
-                    let npcs = self.npcs.read().await;
-                    npcs.get(&target_id)
-                        .map(|n| (n.prototype_id.clone(), n.level))
-                        .unwrap_or(("unknown".to_string(), 1))
-                };
-
-                // Calculate EXP reward from prototype
-                let exp_reward = if let Some(prototype) = self.entity_registry.get(&prototype_id) {
-                    crate::entity::calculate_exp_reward(prototype, npc_level)
-                } else {
-                    0 // No prototype found, no exp
-                };
-
-                // Award combat XP to killer based on damage dealt
-                // Use exp_reward as a proxy for "damage" in XP calculation
-                let xp_results: Option<Vec<(SkillType, i64, i64, i32, bool)>> = if exp_reward > 0 {
-                    let mut players = self.players.write().await;
-                    if let Some(killer) = players.get_mut(player_id) {
-                        Some(killer.award_combat_xp(exp_reward))
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                };
-
-                // Send skill XP and level-up messages (after releasing write lock)
-                if let Some(results) = xp_results {
-                    for (skill_type, xp_gained, total_xp, level, leveled_up) in results {
-                        // Send XP gain message
-                        self.send_to_player(player_id, ServerMessage::SkillXp {
-                            player_id: player_id.to_string(),
-                            skill: skill_type.as_str().to_string(),
-                            xp_gained,
-                            total_xp,
-                            level,
-                        }).await;
-
-                        // Send level-up message if applicable
-                        if leveled_up {
-                            tracing::info!("Player {} leveled up {} to {}", player_id, skill_type.as_str(), level);
-                            self.broadcast(ServerMessage::SkillLevelUp {
-                                player_id: player_id.to_string(),
-                                skill: skill_type.as_str().to_string(),
-                                new_level: level,
-                            }).await;
-                        }
-                    }
-                }
-
-                // Broadcast NPC death
-                let death_msg = ServerMessage::NpcDied {
-                    id: target_id.clone(),
-                    killer_id: player_id.to_string(),
-                };
-                self.broadcast(death_msg).await;
-
-                // Process quest kill event
-                self.process_quest_kill(player_id, &prototype_id).await;
-
-                // Spawn item drops from prototype loot table
-                let current_time = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
-                    .as_millis() as u64;
-
-                // Get killer's current instance for loot zone tracking
-                let killer_instance = {
-                    let instances = self.player_instances.read().await;
-                    instances.get(player_id).cloned()
-                };
-
-                let drops = if let Some(prototype) = self.entity_registry.get(&prototype_id) {
-                    crate::entity::generate_loot_from_prototype(
-                        prototype, target_x, target_y, player_id, current_time, npc_level, killer_instance
-                    )
-                } else {
-                    vec![] // No prototype found, no drops
-                };
-
-                for item in drops {
-                    let mut items = self.ground_items.write().await;
-
-                    // For gold, try to combine with existing pile at same tile
-                    if item.item_id == "gold" {
-                        let tile_x = item.x.floor() as i32;
-                        let tile_y = item.y.floor() as i32;
-
-                        // Find existing gold at same tile with same owner
-                        let existing_gold_id = items.iter()
-                            .find(|(_, existing)| {
-                                existing.item_id == "gold"
-                                    && existing.x.floor() as i32 == tile_x
-                                    && existing.y.floor() as i32 == tile_y
-                                    && existing.owner_id == item.owner_id
-                            })
-                            .map(|(id, _)| id.clone());
-
-                        if let Some(existing_id) = existing_gold_id {
-                            // Combine with existing pile
-                            if let Some(existing) = items.get_mut(&existing_id) {
-                                existing.quantity += item.quantity;
-                                let update_msg = ServerMessage::ItemQuantityUpdated {
-                                    id: existing_id.clone(),
-                                    quantity: existing.quantity,
-                                };
-                                drop(items); // Release lock before broadcast
-                                self.broadcast_to_zone(player_id, update_msg).await;
-                            }
-                            continue;
-                        }
-                    }
-
-                    // No existing pile to combine with - create new item
-                    let drop_msg = ServerMessage::ItemDropped {
-                        id: item.id.clone(),
-                        item_id: item.item_id.clone(),
-                        x: item.x,
-                        y: item.y,
-                        quantity: item.quantity,
-                    };
-                    items.insert(item.id.clone(), item);
-                    drop(items); // Release lock before broadcast
-                    self.broadcast_to_zone(player_id, drop_msg).await;
-                }
+                self.handle_npc_kill(player_id, &target_id, target_x, target_y).await;
             } else {
                 // Broadcast player death
                 let death_msg = ServerMessage::PlayerDied {
@@ -1676,103 +2080,348 @@ impl GameRoom {
                     killer_id: player_id.to_string(),
                 };
                 self.broadcast(death_msg).await;
+                self.record_pvp_kill(player_id, &target_id, pvp_in_zone).await;
+                self.apply_death_penalty(&target_id, current_time).await;
             }
         }
     }
 
-    /// Process quest kill event
-    async fn process_quest_kill(&self, player_id: &str, entity_type: &str) {
-        // Get player's quest state
-        let mut quest_states = self.player_quest_states.write().await;
-        let quest_state = quest_states.entry(player_id.to_string())
-            .or_insert_with(PlayerQuestState::new);
+    /// Award XP, quest/achievement progress, reputation and loot for killing an NPC
+    async fn handle_npc_kill(&self, player_id: &str, target_id: &str, target_x: f32, target_y: f32) {
+        // Get NPC info for exp and loot
+        let (prototype_id, npc_level, elite) = {
+            let npc_map = self.npcs_near(player_id).await;
+            let npcs = self.npc_lock(&npc_map).read().await;
+            npcs.get(target_id)
+                .map(|n| (n.prototype_id.clone(), n.level, n.elite.clone()))
+                .unwrap_or(("unknown".to_string(), 1, None))
+        };
 
-        // Create kill event
-        let event = QuestEvent::MonsterKilled {
-            player_id: player_id.to_string(),
-            entity_type: entity_type.to_string(),
-            level: 1, // TODO: Get actual monster level from context
+        // Calculate EXP reward from prototype, boosted for elites
+        let exp_reward = if let Some(prototype) = self.entity_registry.get(&prototype_id) {
+            let base = crate::entity::calculate_exp_reward(prototype, npc_level);
+            match &elite {
+                Some(elite) => (base as f32 * elite.exp_multiplier).round() as i32,
+                None => base,
+            }
+        } else {
+            0 // No prototype found, no exp
         };
 
-        // Process the event through the registry
-        let results = self.quest_registry.process_event(&event, quest_state).await;
+        // The killer's pet learns from every kill made while it is out
+        if exp_reward > 0 {
+            self.award_pet_exp(player_id, exp_reward as i64).await;
+        }
 
-        // Handle results - send notifications to player
-        for result in results {
-            if let (Some(objective_id), Some(current), Some(target)) =
-                (&result.objective_id, result.new_progress, result.target)
-            {
-                tracing::info!(
-                    "Player {} progress on objective {} for quest {}: {}/{}",
-                    player_id, objective_id, result.quest_id, current, target
-                );
+        // Party members near the killer share XP and loot
+        let sharing_members = self.nearby_party_members(player_id).await;
 
-                // Send objective progress update to player
-                if let Some(sender) = self.player_senders.read().await.get(player_id) {
-                    let msg = ServerMessage::QuestObjectiveProgress {
-                        quest_id: result.quest_id.clone(),
-                        objective_id: objective_id.clone(),
-                        current,
-                        target,
-                    };
-                    if let Ok(data) = crate::protocol::encode_server_message(&msg) {
-                        let _ = sender.send(data).await;
-                    }
-                }
+        // Award combat XP based on damage dealt, split between nearby party members
+        // Use exp_reward as a proxy for "damage" in XP calculation
+        let xp_results: Vec<(String, Vec<SkillXpGain>)> = if exp_reward > 0 {
+            let share = split_xp(exp_reward, sharing_members.len());
+            let mut players = self.players.write().await;
+            sharing_members.iter()
+                .filter_map(|member_id| {
+                    players.get_mut(member_id)
+                        .map(|member| (member_id.clone(), member.award_combat_xp(share)))
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
 
-                if result.objective_completed {
-                    tracing::info!(
-                        "Player {} completed objective {} for quest {}",
-                        player_id, objective_id, result.quest_id
-                    );
-                }
-            }
+        // Send skill XP and level-up messages (after releasing write lock)
+        for (member_id, results) in xp_results {
+            for (skill_type, xp_gained, total_xp, level, leveled_up) in results {
+                // Send XP gain message
+                self.send_to_player(&member_id, ServerMessage::SkillXp {
+                    player_id: member_id.clone(),
+                    skill: skill_type.as_str().to_string(),
+                    xp_gained,
+                    total_xp,
+                    level,
+                }).await;
 
-            if result.quest_ready {
-                tracing::info!(
-                    "Player {} quest {} is ready to complete!",
-                    player_id, result.quest_id
-                );
+                // Send level-up message if applicable
+                if leveled_up {
+                    tracing::info!("Player {} leveled up {} to {}", member_id, skill_type.as_str(), level);
+                    self.record_achievement_event(&member_id, AchievementEvent::SkillLevel {
+                        skill: skill_type.as_str(),
+                        level,
+                    }).await;
+                    self.broadcast(ServerMessage::SkillLevelUp {
+                        player_id: member_id.clone(),
+                        skill: skill_type.as_str().to_string(),
+                        new_level: level,
+                    }).await;
+                }
             }
         }
-    }
-
-    /// Process quest item collection event
-    async fn process_quest_item_collect(&self, player_id: &str, item_id: &str, count: i32) {
-        let mut quest_states = self.player_quest_states.write().await;
-        let quest_state = quest_states.entry(player_id.to_string())
-            .or_insert_with(PlayerQuestState::new);
 
-        let event = QuestEvent::ItemCollected {
-            player_id: player_id.to_string(),
-            item_id: item_id.to_string(),
-            count,
+        // Broadcast NPC death
+        let death_msg = ServerMessage::NpcDied {
+            id: target_id.to_string(),
+            killer_id: player_id.to_string(),
         };
+        self.broadcast(death_msg).await;
+
+        // Process quest kill event
+        self.process_quest_kill(player_id, &prototype_id).await;
+        self.record_achievement_event(player_id, AchievementEvent::Kill { prototype_id: &prototype_id }).await;
+        self.run_script_hook(ScriptHook::NpcDeath {
+            npc_id: target_id,
+            prototype_id: &prototype_id,
+            killer_id: player_id,
+        }).await;
 
-        let results = self.quest_registry.process_event(&event, quest_state).await;
+        // Faction reputation for the kill goes to the killer
+        if let Some(prototype) = self.entity_registry.get(&prototype_id) {
+            self.grant_reputation_rewards(player_id, &prototype.rewards.reputation).await;
+        }
 
-        for result in results {
-            if let (Some(objective_id), Some(current), Some(target)) =
-                (&result.objective_id, result.new_progress, result.target)
-            {
-                tracing::info!(
-                    "Player {} collected quest item objective {} for quest {}: {}/{}",
+        // Spawn item drops from prototype loot table
+        let current_time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        // Get killer's current instance for loot zone tracking
+        let killer_instance = {
+            let instances = self.player_instances.read().await;
+            instances.get(player_id).cloned()
+        };
+
+        // Bosses roll loot separately for everyone high enough on the damage leaderboard
+        let boss_looters = self.finish_boss_kill(target_id, current_time).await;
+
+        let drops = match (self.entity_registry.get(&prototype_id), boss_looters) {
+            (Some(prototype), Some(looters)) => {
+                let mut drops = Vec::new();
+                for (index, looter) in looters.iter().enumerate() {
+                    for mut item in crate::entity::generate_loot_from_prototype(
+                        prototype, &self.loot_tables, (target_x, target_y), looter, current_time, npc_level, killer_instance.clone()
+                    ) {
+                        item.id = format!("{}_{}", item.id, index);
+                        drops.push(item);
+                    }
+                }
+                drops
+            }
+            (Some(prototype), None) => {
+                // Elites roll their loot table more than once
+                let loot_rolls = elite.as_ref().map_or(1, |elite| elite.loot_rolls);
+                let mut drops = Vec::new();
+                for roll in 0..loot_rolls {
+                    for mut item in crate::entity::generate_loot_from_prototype(
+                        prototype, &self.loot_tables, (target_x, target_y), player_id, current_time, npc_level, killer_instance.clone()
+                    ) {
+                        if roll > 0 {
+                            item.id = format!("{}_{}", item.id, roll);
+                        }
+                        drops.push(item);
+                    }
+                }
+                // Apply the party loot rule to the owner-only pickup window
+                for item in drops.iter_mut() {
+                    let (owner_id, party_id) = self.party_loot_owner(player_id, &sharing_members).await;
+                    item.owner_id = owner_id;
+                    item.party_id = party_id;
+                }
+                drops
+            }
+            (None, _) => vec![], // No prototype found, no drops
+        };
+
+        for item in drops {
+            let mut items = self.ground_items.write().await;
+
+            // For gold, try to combine with existing pile at same tile
+            if item.item_id == "gold" {
+                let tile_x = item.x.floor() as i32;
+                let tile_y = item.y.floor() as i32;
+
+                // Find existing gold at same tile with same owner
+                let existing_gold_id = items.iter()
+                    .find(|(_, existing)| {
+                        existing.item_id == "gold"
+                            && existing.x.floor() as i32 == tile_x
+                            && existing.y.floor() as i32 == tile_y
+                            && existing.owner_id == item.owner_id
+                            && existing.party_id == item.party_id
+                    })
+                    .map(|(id, _)| id.clone());
+
+                if let Some(existing_id) = existing_gold_id {
+                    // Combine with existing pile
+                    if let Some(existing) = items.get_mut(&existing_id) {
+                        existing.quantity += item.quantity;
+                        let update_msg = ServerMessage::ItemQuantityUpdated {
+                            id: existing_id.clone(),
+                            quantity: existing.quantity,
+                        };
+                        drop(items); // Release lock before broadcast
+                        self.broadcast_to_zone(player_id, update_msg).await;
+                    }
+                    continue;
+                }
+            }
+
+            // No existing pile to combine with - create new item
+            let drop_msg = ServerMessage::ItemDropped {
+                id: item.id.clone(),
+                item_id: item.item_id.clone(),
+                x: item.x,
+                y: item.y,
+                quantity: item.quantity,
+            };
+            items.insert(item.id.clone(), item);
+            drop(items); // Release lock before broadcast
+            self.broadcast_to_zone(player_id, drop_msg).await;
+        }
+
+        // Splitting elites break into normal copies of themselves
+        if let Some(count) = elite.map(|elite| elite.split_count).filter(|count| *count > 0) {
+            self.split_elite(player_id, target_id, &prototype_id, npc_level, (target_x as i32, target_y as i32), count).await;
+        }
+    }
+
+    /// Give a player a quest's item rewards: the fixed items plus a roll of its
+    /// loot table at the player's combat level
+    async fn grant_quest_items(&self, player_id: &str, reward: &Reward) {
+        let inventory = {
+            let mut players = self.players.write().await;
+            let Some(player) = players.get_mut(player_id) else {
+                return;
+            };
+            let mut items: Vec<(String, i32)> = reward.items.iter()
+                .map(|item| (item.item_id.clone(), item.count))
+                .collect();
+            if let Some(table) = &reward.loot_table {
+                let level = player.combat_level();
+                items.extend(self.loot_tables.roll(table, level, &mut rand::thread_rng())
+                    .into_iter()
+                    .map(|loot| (loot.item_id, loot.quantity)));
+            }
+            if items.is_empty() {
+                return;
+            }
+            let mut overflow = Vec::new();
+            for (item_id, count) in items {
+                let leftover = player.inventory.add_item(&item_id, count, &self.item_registry);
+                if leftover > 0 {
+                    overflow.push((item_id, leftover));
+                }
+            }
+            (player.inventory.to_update(), player.inventory.gold, (player.x, player.y), overflow)
+        };
+        let (slots, gold, (x, y), overflow) = inventory;
+        self.send_to_player(player_id, ServerMessage::InventoryUpdate {
+            player_id: player_id.to_string(),
+            slots,
+            gold,
+        }).await;
+        if overflow.is_empty() {
+            return;
+        }
+
+        // Rewards that don't fit go on the ground at the player's feet, protected for them
+        let current_time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        let instance_id = {
+            let instances = self.player_instances.read().await;
+            instances.get(player_id).cloned()
+        };
+        for (item_id, quantity) in overflow {
+            let ground_item = GroundItem::new_in_instance(
+                &uuid::Uuid::new_v4().to_string(),
+                &item_id,
+                x as f32,
+                y as f32,
+                quantity,
+                Some(player_id.to_string()),
+                current_time,
+                instance_id.clone(),
+            );
+            tracing::info!("Player {} inventory full, dropped {}x {} quest reward", player_id, quantity, item_id);
+            self.broadcast_to_zone(player_id, ServerMessage::ItemDropped {
+                id: ground_item.id.clone(),
+                item_id,
+                x: ground_item.x,
+                y: ground_item.y,
+                quantity,
+            }).await;
+            self.ground_items.write().await.insert(ground_item.id.clone(), ground_item);
+        }
+        self.send_system_message(player_id, "Inventory full - some rewards were dropped at your feet").await;
+    }
+
+    /// Process quest kill event
+    async fn process_quest_kill(&self, player_id: &str, entity_type: &str) {
+        let event = QuestEvent::MonsterKilled {
+            player_id: player_id.to_string(),
+            entity_type: entity_type.to_string(),
+            level: 1, // TODO: Get actual monster level from context
+        };
+        self.process_quest_event(player_id, event).await;
+    }
+
+    /// Process quest item collection event
+    async fn process_quest_item_collect(&self, player_id: &str, item_id: &str, count: i32) {
+        let event = QuestEvent::ItemCollected {
+            player_id: player_id.to_string(),
+            item_id: item_id.to_string(),
+            count,
+        };
+        self.process_quest_event(player_id, event).await;
+    }
+
+    /// Feed a count-based event (kill, collect, craft, gold) to the player's
+    /// quest objectives and challenges
+    async fn process_quest_event(&self, player_id: &str, event: QuestEvent) {
+        let results = {
+            let mut quest_states = self.player_quest_states.write().await;
+            let quest_state = quest_states.entry(player_id.to_string())
+                .or_insert_with(PlayerQuestState::new);
+            self.quest_registry.process_event(&event, quest_state).await
+        };
+
+        // Handle results - send notifications to player
+        for result in results {
+            if let (Some(objective_id), Some(current), Some(target)) =
+                (&result.objective_id, result.new_progress, result.target)
+            {
+                tracing::info!(
+                    "Player {} progress on objective {} for quest {}: {}/{}",
                     player_id, objective_id, result.quest_id, current, target
                 );
 
-                if let Some(sender) = self.player_senders.read().await.get(player_id) {
-                    let msg = ServerMessage::QuestObjectiveProgress {
-                        quest_id: result.quest_id.clone(),
-                        objective_id: objective_id.clone(),
-                        current,
-                        target,
-                    };
-                    if let Ok(data) = crate::protocol::encode_server_message(&msg) {
-                        let _ = sender.send(data).await;
-                    }
+                self.send_to_player(player_id, ServerMessage::QuestObjectiveProgress {
+                    quest_id: result.quest_id.clone(),
+                    objective_id: objective_id.clone(),
+                    current,
+                    target,
+                }).await;
+
+                if result.objective_completed {
+                    tracing::info!(
+                        "Player {} completed objective {} for quest {}",
+                        player_id, objective_id, result.quest_id
+                    );
                 }
             }
+
+            if result.quest_ready {
+                tracing::info!(
+                    "Player {} quest {} is ready to complete!",
+                    player_id, result.quest_id
+                );
+            }
         }
+
+        self.record_challenge_event(player_id, &event).await;
     }
 
     /// Check if two directions are close enough (within 45 degrees)
@@ -1847,6 +2496,11 @@ impl GameRoom {
             }
         };
 
+        let party_id = {
+            let parties = self.parties.read().await;
+            parties.party_of(player_id).map(|p| p.id.clone())
+        };
+
         // Check if item exists and can be picked up
         let item_info = {
             let items = self.ground_items.read().await;
@@ -1860,15 +2514,15 @@ impl GameRoom {
                     return None;
                 }
 
-                if !item.can_pickup(player_id, current_time) {
+                if !item.can_pickup(player_id, party_id.as_deref(), current_time) {
                     return None;
                 }
 
-                Some((item.item_id.clone(), item.quantity))
+                Some((item.item_id.clone(), item.quantity, item.dropped_by_player))
             }).flatten()
         };
 
-        if let Some((picked_item_id, quantity)) = item_info {
+        if let Some((picked_item_id, quantity, dropped_by_player)) = item_info {
             // Remove item from ground
             let removed = {
                 let mut items = self.ground_items.write().await;
@@ -1900,6 +2554,16 @@ impl GameRoom {
                     self.process_quest_item_collect(player_id, &picked_item_id, picked_up_count).await;
                 }
 
+                // Looted gold counts as earned; gold other players dropped doesn't
+                if picked_item_id == GOLD_ITEM_ID && !dropped_by_player && picked_up_count > 0 {
+                    self.record_achievement_event(player_id, AchievementEvent::GoldEarned { amount: picked_up_count as i64 }).await;
+                    self.process_quest_event(player_id, QuestEvent::GoldEarned {
+                        player_id: player_id.to_string(),
+                        source: "loot".to_string(),
+                        amount: picked_up_count,
+                    }).await;
+                }
+
                 // Broadcast pickup to players in same zone
                 let pickup_msg = ServerMessage::ItemPickedUp {
                     item_id: item_id.to_string(),
@@ -1986,9 +2650,36 @@ impl GameRoom {
             return;
         }
 
+        // Overworld NPCs stop whatever route they're on and turn to the player
+        if instance_id.is_none() {
+            let current_time = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as u64;
+            if let Some(npc) = self.npcs.write().await.get_mut(npc_id) {
+                npc.hold_for_conversation(player_x, player_y, current_time);
+            }
+        }
+
+        // World scripts can take over the interaction with any entity
+        let output = self.run_script_hook(ScriptHook::InteractObject {
+            player_id,
+            npc_id,
+            prototype_id: &entity_type,
+        }).await;
+        if output.handled {
+            return;
+        }
+
         // Check entity prototype for behaviors
         let prototype = self.entity_registry.get(&entity_type);
 
+        // Bounty boards list world events instead of talking
+        if prototype.as_ref().map(|p| p.behaviors.bounty_board).unwrap_or(false) {
+            self.send_bounty_board(player_id, npc_id).await;
+            return;
+        }
+
         // Check if this NPC is a merchant/craftsman
         let is_merchant = prototype.as_ref()
             .map(|p| p.behaviors.merchant || p.behaviors.craftsman)
@@ -2008,6 +2699,14 @@ impl GameRoom {
             // Get merchant config to load shop data
             if let Some(proto) = prototype {
                 if let Some(merchant_config) = &proto.merchant {
+                    if let Err(reason) = self.check_merchant_reputation(player_id, merchant_config).await {
+                        self.send_system_message(player_id, &reason).await;
+                        return;
+                    }
+                    let (purchase_mod, sale_mod) = self.merchant_price_modifiers(player_id, merchant_config).await;
+                    let buy_multiplier = merchant_config.buy_multiplier * sale_mod;
+                    let sell_multiplier = merchant_config.sell_multiplier * purchase_mod;
+
                     // Get shop definition from registry
                     let shop_registry = self.shop_registry.read().await;
                     if let Some(shop_def) = shop_registry.get(&merchant_config.shop_id) {
@@ -2017,7 +2716,7 @@ impl GameRoom {
                                 .get(&item.item_id)
                                 .map(|def| def.base_price)
                                 .unwrap_or(10);
-                            let price = (base_price as f32 * merchant_config.sell_multiplier).max(1.0) as i32;
+                            let price = (base_price as f32 * sell_multiplier).max(1.0) as i32;
 
                             crate::protocol::ShopStockItemData {
                                 item_id: item.item_id.clone(),
@@ -2029,8 +2728,8 @@ impl GameRoom {
                         let shop_data = crate::protocol::ShopData {
                             shop_id: shop_def.id.clone(),
                             display_name: shop_def.display_name.clone(),
-                            buy_multiplier: merchant_config.buy_multiplier,
-                            sell_multiplier: merchant_config.sell_multiplier,
+                            buy_multiplier,
+                            sell_multiplier,
                             stock,
                         };
 
@@ -2062,6 +2761,12 @@ impl GameRoom {
             return;
         }
 
+        // Quest givers may only offer new quests to players in good standing
+        let quest_gate = match prototype.as_ref().and_then(|p| p.quest_giver.as_ref()).and_then(|q| q.requires_reputation.as_ref()) {
+            Some(requirement) => self.check_reputation(player_id, requirement).await,
+            None => Ok(()),
+        };
+
         // Get or create player quest state
         let mut quest_states = self.player_quest_states.write().await;
         let quest_state = quest_states.entry(player_id.to_string())
@@ -2114,6 +2819,13 @@ impl GameRoom {
         }
 
         if let Some((quest_id, state)) = target_quest {
+            if state == "not_started"
+                && let Err(reason) = &quest_gate
+            {
+                self.send_system_message(player_id, reason).await;
+                return;
+            }
+
             tracing::info!(
                 "Player {} interacting with quest {} (state: {})",
                 player_id, quest_id, state
@@ -2214,11 +2926,23 @@ impl GameRoom {
                             self.send_to_player(player_id, msg).await;
 
                             // Grant rewards
-                            let mut players = self.players.write().await;
-                            if let Some(player) = players.get_mut(player_id) {
-                                player.inventory.gold += quest.rewards.gold;
-                                // TODO: Grant EXP and items
+                            {
+                                let mut players = self.players.write().await;
+                                if let Some(player) = players.get_mut(player_id) {
+                                    player.inventory.gold += quest.rewards.gold;
+                                    // TODO: Grant EXP
+                                }
                             }
+                            self.grant_quest_items(player_id, &quest.rewards).await;
+                            self.grant_reputation_rewards(player_id, &quest.rewards.reputation).await;
+                            self.record_achievement_event(player_id, AchievementEvent::QuestCompleted).await;
+                            self.record_achievement_event(player_id, AchievementEvent::GoldEarned { amount: quest.rewards.gold as i64 }).await;
+                            // Quest state is locked here, so quest gold only counts towards challenges
+                            self.record_challenge_event(player_id, &QuestEvent::GoldEarned {
+                                player_id: player_id.to_string(),
+                                source: "quests".to_string(),
+                                amount: quest.rewards.gold,
+                            }).await;
                         }
                         tracing::info!("Player {} completed quest {}", player_id, quest_id);
                     }
@@ -2238,6 +2962,18 @@ impl GameRoom {
 
     /// Handle dialogue choice from player
     pub async fn handle_dialogue_choice(&self, player_id: &str, quest_id: &str, choice_id: &str) {
+        // Dialogues opened by world scripts go back to the script
+        if let Some(script) = quest_id.strip_prefix(SCRIPT_DIALOGUE_PREFIX) {
+            let output = self.run_script_hook(ScriptHook::DialogueChoice { script, player_id, choice_id }).await;
+            let continues = output.actions.iter().any(|action| {
+                matches!(action, ScriptAction::ShowDialogue { player_id: target, .. } if target == player_id)
+            });
+            if !continues {
+                self.send_to_player(player_id, ServerMessage::DialogueClosed).await;
+            }
+            return;
+        }
+
         let mut quest_states = self.player_quest_states.write().await;
         let quest_state = quest_states.entry(player_id.to_string())
             .or_insert_with(PlayerQuestState::new);
@@ -2334,10 +3070,22 @@ impl GameRoom {
                         };
                         self.send_to_player(player_id, msg).await;
 
-                        let mut players = self.players.write().await;
-                        if let Some(player) = players.get_mut(player_id) {
-                            player.inventory.gold += quest.rewards.gold;
+                        {
+                            let mut players = self.players.write().await;
+                            if let Some(player) = players.get_mut(player_id) {
+                                player.inventory.gold += quest.rewards.gold;
+                            }
                         }
+                        self.grant_quest_items(player_id, &quest.rewards).await;
+                        self.grant_reputation_rewards(player_id, &quest.rewards.reputation).await;
+                        self.record_achievement_event(player_id, AchievementEvent::QuestCompleted).await;
+                        self.record_achievement_event(player_id, AchievementEvent::GoldEarned { amount: quest.rewards.gold as i64 }).await;
+                        // Quest state is locked here, so quest gold only counts towards challenges
+                        self.record_challenge_event(player_id, &QuestEvent::GoldEarned {
+                            player_id: player_id.to_string(),
+                            source: "quests".to_string(),
+                            amount: quest.rewards.gold,
+                        }).await;
                     }
                     tracing::info!("Player {} completed quest {}", player_id, quest_id);
                 }
@@ -2349,6 +3097,11 @@ impl GameRoom {
     }
 
     pub async fn handle_use_item(&self, player_id: &str, slot_index: u8) {
+        let mut pet_to_toggle = None;
+        let mut furniture_to_place = None;
+        let mut cured = false;
+        let mut healed = 0;
+
         // Get player and try to use item
         let (used_item_id, effect, inventory_update, gold) = {
             let mut players = self.players.write().await;
@@ -2364,7 +3117,9 @@ impl GameRoom {
                         use crate::data::UseEffect;
                         match &def.use_effect {
                             Some(UseEffect::Heal { amount }) => {
+                                let before = player.hp;
                                 player.hp = (player.hp + amount).min(player.max_hp());
+                                healed = player.hp - before;
                                 format!("heal:{}", amount)
                             }
                             Some(UseEffect::RestoreMana { amount }) => {
@@ -2379,6 +3134,23 @@ impl GameRoom {
                                 // Teleport not implemented yet
                                 format!("teleport:{}", destination)
                             }
+                            Some(UseEffect::SummonPet { pet }) => {
+                                // Summoned after the player lock is released
+                                pet_to_toggle = Some(pet.clone());
+                                format!("pet:{}", pet)
+                            }
+                            Some(UseEffect::Cure { effects }) => {
+                                cured = player.status_effects.cure(effects);
+                                let names: Vec<&str> = effects.iter().map(|e| e.as_str()).collect();
+                                format!("cure:{}", if names.is_empty() { "all".to_string() } else { names.join(",") })
+                            }
+                            // Handled by the world scripts' on_item_use below
+                            Some(UseEffect::Script { .. }) => "script".to_string(),
+                            None if def.furniture.is_some() => {
+                                // Placed after the player lock is released
+                                furniture_to_place = Some(item_id.clone());
+                                "furniture".to_string()
+                            }
                             None => "none".to_string(),
                         }
                     } else {
@@ -2417,6 +3189,23 @@ impl GameRoom {
                 gold,
             };
             self.send_to_player(player_id, inv_msg).await;
+
+            self.run_script_hook(ScriptHook::ItemUse { player_id, item_id: &item_id }).await;
+        }
+
+        self.add_heal_threat(player_id, healed).await;
+        if cured {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as u64;
+            self.broadcast_status_effects(player_id, now).await;
+        }
+        if let Some(pet_id) = pet_to_toggle {
+            self.toggle_pet(player_id, &pet_id).await;
+        }
+        if let Some(item_id) = furniture_to_place {
+            self.place_furniture(player_id, slot_index, &item_id).await;
         }
     }
 
@@ -2533,6 +3322,18 @@ impl GameRoom {
             items_gained
         );
 
+        for result in &items_gained {
+            self.record_achievement_event(player_id, AchievementEvent::ItemCrafted {
+                item_id: &result.item_id,
+                count: result.count,
+            }).await;
+            self.process_quest_event(player_id, QuestEvent::ItemCrafted {
+                player_id: player_id.to_string(),
+                item_id: result.item_id.clone(),
+                count: result.count,
+            }).await;
+        }
+
         // Send success result
         self.send_to_player(
             player_id,
@@ -2614,6 +3415,13 @@ impl GameRoom {
             }
         };
 
+        // Faction standing gates trading and adjusts prices
+        if let Err(reason) = self.check_merchant_reputation(player_id, &merchant_config).await {
+            self.send_shop_result(player_id, false, "buy", item_id, 0, 0, Some(&reason)).await;
+            return;
+        }
+        let (purchase_mod, _) = self.merchant_price_modifiers(player_id, &merchant_config).await;
+
         // Get shop definition and check stock
         let mut shop_registry = self.shop_registry.write().await;
         let shop = match shop_registry.get_mut(&merchant_config.shop_id) {
@@ -2654,7 +3462,7 @@ impl GameRoom {
 
         // Calculate total cost
         let base_price = item_def.base_price;
-        let unit_price = (base_price as f32 * merchant_config.sell_multiplier).max(1.0) as i32;
+        let unit_price = (base_price as f32 * merchant_config.sell_multiplier * purchase_mod).max(1.0) as i32;
         let total_cost = unit_price * quantity;
 
         // Check if player has enough gold
@@ -2815,6 +3623,13 @@ impl GameRoom {
             }
         };
 
+        // Faction standing gates trading and adjusts prices
+        if let Err(reason) = self.check_merchant_reputation(player_id, &merchant_config).await {
+            self.send_shop_result(player_id, false, "sell", item_id, 0, 0, Some(&reason)).await;
+            return;
+        }
+        let (_, sale_mod) = self.merchant_price_modifiers(player_id, &merchant_config).await;
+
         // Get item definition
         let item_def = match self.item_registry.get(item_id) {
             Some(def) => def.clone(),
@@ -2846,7 +3661,7 @@ impl GameRoom {
 
         // Calculate sell price
         let base_price = item_def.base_price;
-        let unit_price = (base_price as f32 * merchant_config.buy_multiplier).max(1.0) as i32;
+        let unit_price = (base_price as f32 * merchant_config.buy_multiplier * sale_mod).max(1.0) as i32;
         let total_value = unit_price * quantity;
 
         // Process transaction
@@ -2880,6 +3695,12 @@ impl GameRoom {
                     "Player {} sold {}x{} to {} for {} gold",
                     player_id, quantity, item_id, npc_id, total_value
                 );
+                self.record_achievement_event(player_id, AchievementEvent::GoldEarned { amount: total_value as i64 }).await;
+                self.process_quest_event(player_id, QuestEvent::GoldEarned {
+                    player_id: player_id.to_string(),
+                    source: "sales".to_string(),
+                    amount: total_value,
+                }).await;
             }
         }
     }
@@ -3281,7 +4102,7 @@ impl GameRoom {
             instances.get(player_id).cloned()
         };
 
-        let ground_item = GroundItem::new_in_instance(
+        let mut ground_item = GroundItem::new_in_instance(
             &uuid::Uuid::new_v4().to_string(),
             &item_id,
             drop_x_f,
@@ -3291,6 +4112,7 @@ impl GameRoom {
             current_time,
             instance_id,
         );
+        ground_item.dropped_by_player = true;
 
         tracing::info!("Player {} dropped {}x {} (protected for 10s)", player_id, qty_to_drop, item_id);
 
@@ -3370,7 +4192,7 @@ impl GameRoom {
             instances.get(player_id).cloned()
         };
 
-        let ground_item = GroundItem::new_in_instance(
+        let mut ground_item = GroundItem::new_in_instance(
             &uuid::Uuid::new_v4().to_string(),
             GOLD_ITEM_ID,
             drop_x,
@@ -3380,6 +4202,7 @@ impl GameRoom {
             current_time,
             instance_id,
         );
+        ground_item.dropped_by_player = true;
 
         tracing::info!("Player {} dropped {}g (protected for 10s)", player_id, amount);
 
@@ -3440,94 +4263,4164 @@ impl GameRoom {
         }).await;
     }
 
-    pub async fn tick(&self) {
-        let delta_time = 1.0 / TICK_RATE;
-        let current_time = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_millis() as u64;
-
-        // Update tick counter and get current tick for movement timing
-        let current_tick = {
-            let mut tick = self.tick.write().await;
-            *tick += 1;
-            *tick
-        };
+    // ========================================================================
+    // Trading
+    // ========================================================================
 
-        // Handle player respawns
-        let mut respawned_players = Vec::new();
+    /// Check that two players are able to trade with each other right now
+    async fn validate_trade_pair(&self, player_a: &str, player_b: &str) -> Result<(), String> {
         {
-            let mut players = self.players.write().await;
-            for player in players.values_mut() {
-                if !player.active {
-                    continue;
-                }
-
-                if player.ready_to_respawn(current_time) {
-                    player.respawn();
-                    respawned_players.push((player.id.clone(), player.x, player.y, player.hp));
-                }
+            let instances = self.player_instances.read().await;
+            if instances.get(player_a) != instances.get(player_b) {
+                return Err("That player is too far away to trade.".to_string());
             }
         }
 
-        // Broadcast respawns
-        for (id, x, y, hp) in respawned_players {
-            tracing::info!("Player {} respawned at ({}, {})", id, x, y);
-            self.broadcast(ServerMessage::PlayerRespawned { id, x, y, hp }).await;
+        let players = self.players.read().await;
+        let a = players.get(player_a).filter(|p| p.active).ok_or("Player not found.")?;
+        let b = players.get(player_b).filter(|p| p.active).ok_or("That player is no longer online.")?;
+
+        if a.is_dead || b.is_dead {
+            return Err("You can't trade while dead.".to_string());
         }
 
-        // Collect pending moves (id, target_x, target_y)
-        // Use tick-based cooldown for deterministic timing (5 ticks = 250ms)
-        let pending_moves: Vec<(String, i32, i32)> = {
-            let players = self.players.read().await;
-            players.values()
-                .filter(|p| p.active && !p.is_dead)
-                .filter(|p| p.move_dx != 0 || p.move_dy != 0)
-                .filter(|p| current_tick - p.last_move_tick >= MOVE_COOLDOWN_TICKS)
-                .map(|p| (p.id.clone(), p.x + p.move_dx, p.y + p.move_dy))
-                .collect()
-        };
+        let distance = (a.x - b.x).abs().max((a.y - b.y).abs());
+        if distance > TRADE_MAX_DISTANCE {
+            return Err("That player is too far away to trade.".to_string());
+        }
 
-        // Collect current entity positions for collision checking
-        let player_positions: std::collections::HashSet<(i32, i32)> = {
-            let players = self.players.read().await;
-            players.values()
-                .filter(|p| p.active && !p.is_dead)
-                .map(|p| (p.x, p.y))
-                .collect()
-        };
-        let npc_positions: std::collections::HashSet<(i32, i32)> = {
-            let npcs = self.npcs.read().await;
-            npcs.values()
-                .filter(|n| n.is_alive())
-                .map(|n| (n.x, n.y))
+        Ok(())
+    }
+
+    /// Send the current trade window state to both participants
+    async fn send_trade_update(&self, session: &TradeSession) {
+        use crate::protocol::TradeItemData;
+
+        let to_items = |items: &Vec<(String, i32)>| -> Vec<TradeItemData> {
+            items.iter()
+                .map(|(item_id, quantity)| TradeItemData { item_id: item_id.clone(), quantity: *quantity })
                 .collect()
         };
 
-        // Get players in instances to skip world collision for them
-        let players_in_instances: std::collections::HashSet<String> = {
-            let instances = self.player_instances.read().await;
-            instances.keys().cloned().collect()
-        };
+        for player_id in [&session.player_a, &session.player_b] {
+            if let Some((mine, theirs)) = session.offers_for(player_id) {
+                self.send_to_player(player_id, ServerMessage::TradeUpdate {
+                    trade_id: session.id.clone(),
+                    stage: session.stage.as_str().to_string(),
+                    my_items: to_items(&mine.items),
+                    my_gold: mine.gold,
+                    my_accepted: mine.accepted,
+                    my_confirmed: mine.confirmed,
+                    their_items: to_items(&theirs.items),
+                    their_gold: theirs.gold,
+                    their_accepted: theirs.accepted,
+                    their_confirmed: theirs.confirmed,
+                }).await;
+            }
+        }
+    }
 
-        // Check walkability and entity collision for each pending move
-        let mut valid_moves: Vec<(String, i32, i32)> = Vec::new();
-        for (id, target_x, target_y) in pending_moves {
-            // Skip world collision check for players in interiors
-            // Interior collision is handled client-side for now
+    /// Notify both sides that a trade window has closed
+    async fn send_trade_closed(&self, session: &TradeSession, completed: bool, reason: Option<&str>) {
+        for player_id in [&session.player_a, &session.player_b] {
+            self.send_to_player(player_id, ServerMessage::TradeClosed {
+                trade_id: session.id.clone(),
+                completed,
+                reason: reason.map(|r| r.to_string()),
+            }).await;
+        }
+    }
+
+    /// Cancel any trade the player is part of
+    pub async fn cancel_trade(&self, player_id: &str, reason: &str) {
+        let session = self.trades.write().await.close_for(player_id);
+        if let Some(session) = session {
+            tracing::info!("Trade {} cancelled: {}", session.id, reason);
+            self.send_trade_closed(&session, false, Some(reason)).await;
+        }
+    }
+
+    /// Open a trade window between two players and notify both
+    async fn open_trade(&self, player_a: &str, player_b: &str) {
+        let session = {
+            let mut trades = self.trades.write().await;
+            match trades.open(player_a, player_b) {
+                Some(session) => session.clone(),
+                None => {
+                    drop(trades);
+                    self.send_system_message(player_a, "That player is busy.").await;
+                    return;
+                }
+            }
+        };
+
+        let (name_a, name_b) = {
+            let players = self.players.read().await;
+            (
+                players.get(player_a).map(|p| p.name.clone()).unwrap_or_default(),
+                players.get(player_b).map(|p| p.name.clone()).unwrap_or_default(),
+            )
+        };
+
+        tracing::info!("Trade {} opened between {} and {}", session.id, name_a, name_b);
+
+        self.send_to_player(player_a, ServerMessage::TradeOpened {
+            trade_id: session.id.clone(),
+            partner_id: player_b.to_string(),
+            partner_name: name_b,
+        }).await;
+        self.send_to_player(player_b, ServerMessage::TradeOpened {
+            trade_id: session.id.clone(),
+            partner_id: player_a.to_string(),
+            partner_name: name_a,
+        }).await;
+        self.send_trade_update(&session).await;
+    }
+
+    pub async fn handle_trade_request(&self, player_id: &str, target_id: &str) {
+        if player_id == target_id {
+            return;
+        }
+
+        if let Err(e) = self.validate_trade_pair(player_id, target_id).await {
+            self.send_system_message(player_id, &e).await;
+            return;
+        }
+
+        let current_time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        // If the target already asked us, treat this as accepting their request
+        let mutual = {
+            let mut trades = self.trades.write().await;
+            if trades.is_trading(player_id) || trades.is_trading(target_id) {
+                drop(trades);
+                self.send_system_message(player_id, "That player is busy.").await;
+                return;
+            }
+            if trades.take_request(target_id, player_id, current_time).is_some() {
+                true
+            } else {
+                trades.add_request(player_id, target_id, current_time);
+                false
+            }
+        };
+
+        if mutual {
+            self.open_trade(target_id, player_id).await;
+            return;
+        }
+
+        let (requester_name, target_name) = {
+            let players = self.players.read().await;
+            (
+                players.get(player_id).map(|p| p.name.clone()).unwrap_or_default(),
+                players.get(target_id).map(|p| p.name.clone()).unwrap_or_default(),
+            )
+        };
+
+        self.send_to_player(target_id, ServerMessage::TradeRequested {
+            requester_id: player_id.to_string(),
+            requester_name,
+        }).await;
+        self.send_system_message(player_id, &format!("Sent a trade request to {}.", target_name)).await;
+    }
+
+    pub async fn handle_trade_respond(&self, player_id: &str, requester_id: &str, accept: bool) {
+        let current_time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        let request = self.trades.write().await.take_request(requester_id, player_id, current_time);
+        if request.is_none() {
+            self.send_system_message(player_id, "That trade request has expired.").await;
+            return;
+        }
+
+        if !accept {
+            let name = self.get_player_name(player_id).await.unwrap_or_default();
+            self.send_system_message(requester_id, &format!("{} declined your trade request.", name)).await;
+            return;
+        }
+
+        if let Err(e) = self.validate_trade_pair(requester_id, player_id).await {
+            self.send_system_message(player_id, &e).await;
+            return;
+        }
+
+        self.open_trade(requester_id, player_id).await;
+    }
+
+    pub async fn handle_trade_offer_item(&self, player_id: &str, slot_index: u8, quantity: i32) {
+        if quantity <= 0 {
+            return;
+        }
+
+        // Look up what's in the slot and how many the player owns in total
+        let (item_id, owned) = {
+            let players = self.players.read().await;
+            let Some(player) = players.get(player_id) else { return };
+            let Some(Some(slot)) = player.inventory.slots.get(slot_index as usize) else { return };
+            (slot.item_id.clone(), player.inventory.count_item(&slot.item_id))
+        };
+
+        let is_quest_item = self.item_registry.get(&item_id)
+            .map(|def| def.category == crate::data::item_def::ItemCategory::Quest)
+            .unwrap_or(false);
+        if is_quest_item {
+            self.send_system_message(player_id, "Quest items can't be traded.").await;
+            return;
+        }
+
+        let result = {
+            let mut trades = self.trades.write().await;
+            let Some(session) = trades.session_for_mut(player_id) else { return };
+            let Some(offer) = session.offer_of(player_id) else { return };
+
+            let already_offered = offer.offered_quantity(&item_id);
+            let is_new_stack = already_offered == 0;
+            if already_offered + quantity > owned {
+                Err("You don't have that many.")
+            } else if is_new_stack && offer.items.len() >= TRADE_MAX_OFFER_ITEMS {
+                Err("You can't offer any more items.")
+            } else {
+                session.modify_offer(player_id, |offer| {
+                    if let Some(entry) = offer.items.iter_mut().find(|(id, _)| *id == item_id) {
+                        entry.1 += quantity;
+                    } else {
+                        offer.items.push((item_id.clone(), quantity));
+                    }
+                });
+                Ok(session.clone())
+            }
+        };
+
+        match result {
+            Ok(session) => self.send_trade_update(&session).await,
+            Err(e) => self.send_system_message(player_id, e).await,
+        }
+    }
+
+    pub async fn handle_trade_remove_item(&self, player_id: &str, offer_index: u8) {
+        let session = {
+            let mut trades = self.trades.write().await;
+            let Some(session) = trades.session_for_mut(player_id) else { return };
+            let in_range = session.offer_of(player_id)
+                .is_some_and(|offer| (offer_index as usize) < offer.items.len());
+            if !in_range {
+                return;
+            }
+            session.modify_offer(player_id, |offer| {
+                offer.items.remove(offer_index as usize);
+            });
+            session.clone()
+        };
+        self.send_trade_update(&session).await;
+    }
+
+    pub async fn handle_trade_set_gold(&self, player_id: &str, amount: i32) {
+        let gold = {
+            let players = self.players.read().await;
+            let Some(player) = players.get(player_id) else { return };
+            player.inventory.gold
+        };
+
+        if amount < 0 || amount > gold {
+            self.send_system_message(player_id, "You don't have that much gold.").await;
+            return;
+        }
+
+        let session = {
+            let mut trades = self.trades.write().await;
+            let Some(session) = trades.session_for_mut(player_id) else { return };
+            session.modify_offer(player_id, |offer| offer.gold = amount);
+            session.clone()
+        };
+        self.send_trade_update(&session).await;
+    }
+
+    pub async fn handle_trade_accept(&self, player_id: &str) {
+        let session = {
+            let mut trades = self.trades.write().await;
+            let Some(session) = trades.session_for_mut(player_id) else { return };
+            if !session.accept(player_id) {
+                return;
+            }
+            session.clone()
+        };
+        self.send_trade_update(&session).await;
+    }
+
+    /// Confirm the final trade offer. When both players have confirmed, the
+    /// swap is executed and the completed trade is returned for logging.
+    pub async fn handle_trade_confirm(&self, player_id: &str) -> Option<crate::trade::CompletedTrade> {
+        let (session, ready) = {
+            let mut trades = self.trades.write().await;
+            let session = trades.session_for_mut(player_id)?;
+            let ready = session.confirm(player_id);
+            if ready {
+                (trades.close_for(player_id)?, true)
+            } else {
+                (session.clone(), false)
+            }
+        };
+
+        if !ready {
+            self.send_trade_update(&session).await;
+            return None;
+        }
+
+        // Re-check range and state right before swapping
+        if let Err(e) = self.validate_trade_pair(&session.player_a, &session.player_b).await {
+            self.send_trade_closed(&session, false, Some(&e)).await;
+            return None;
+        }
+
+        match self.execute_trade(&session).await {
+            Ok(completed) => {
+                tracing::info!(
+                    "Trade {} completed: {} gave {:?} + {}g, {} gave {:?} + {}g",
+                    completed.trade_id,
+                    completed.name_a, completed.items_a, completed.gold_a,
+                    completed.name_b, completed.items_b, completed.gold_b
+                );
+                self.send_trade_closed(&session, true, None).await;
+                for pid in [&session.player_a, &session.player_b] {
+                    if let Some(update) = self.get_player_inventory_update(pid).await {
+                        self.send_to_player(pid, update).await;
+                    }
+                }
+                Some(completed)
+            }
+            Err(e) => {
+                tracing::warn!("Trade {} failed: {}", session.id, e);
+                self.send_trade_closed(&session, false, Some(&e)).await;
+                None
+            }
+        }
+    }
+
+    /// Atomically swap both offers. All checks and the swap happen under a
+    /// single players write lock, so neither inventory can change in between.
+    async fn execute_trade(&self, session: &TradeSession) -> Result<crate::trade::CompletedTrade, String> {
+        let mut players = self.players.write().await;
+
+        let player_a = players.get(&session.player_a).ok_or("Trade partner not found.")?;
+        let player_b = players.get(&session.player_b).ok_or("Trade partner not found.")?;
+
+        // Build both resulting inventories on copies first
+        let build = |giver: &Player, given: &crate::trade::TradeOffer, received: &crate::trade::TradeOffer| -> Result<Inventory, String> {
+            if giver.inventory.gold < given.gold {
+                return Err(format!("{} no longer has the offered gold.", giver.name));
+            }
+            for (item_id, quantity) in &given.items {
+                if !giver.inventory.has_item(item_id, *quantity) {
+                    return Err(format!("{} no longer has the offered items.", giver.name));
+                }
+            }
+
+            let mut inventory = giver.inventory.clone();
+            inventory.gold -= given.gold;
+            for (item_id, quantity) in &given.items {
+                inventory.remove_item(item_id, *quantity);
+            }
+
+            inventory.gold += received.gold;
+            for (item_id, quantity) in &received.items {
+                if !inventory.has_space_for(item_id, *quantity, &self.item_registry)
+                    || inventory.add_item(item_id, *quantity, &self.item_registry) > 0
+                {
+                    return Err(format!("{} doesn't have enough inventory space.", giver.name));
+                }
+            }
+            Ok(inventory)
+        };
+
+        let new_inventory_a = build(player_a, &session.offer_a, &session.offer_b)?;
+        let new_inventory_b = build(player_b, &session.offer_b, &session.offer_a)?;
+
+        let completed = crate::trade::CompletedTrade {
+            trade_id: session.id.clone(),
+            player_a: session.player_a.clone(),
+            player_b: session.player_b.clone(),
+            name_a: player_a.name.clone(),
+            name_b: player_b.name.clone(),
+            items_a: session.offer_a.items.clone(),
+            items_b: session.offer_b.items.clone(),
+            gold_a: session.offer_a.gold,
+            gold_b: session.offer_b.gold,
+        };
+
+        if let Some(player) = players.get_mut(&session.player_a) {
+            player.inventory = new_inventory_a;
+        }
+        if let Some(player) = players.get_mut(&session.player_b) {
+            player.inventory = new_inventory_b;
+        }
+
+        Ok(completed)
+    }
+
+    pub async fn handle_trade_cancel(&self, player_id: &str) {
+        self.cancel_trade(player_id, "Trade cancelled").await;
+    }
+
+    /// Cancel trades whose players moved out of range, died or changed maps,
+    /// and drop expired trade requests
+    async fn validate_active_trades(&self, current_time: u64) {
+        let pairs: Vec<(String, String)> = {
+            let mut trades = self.trades.write().await;
+            trades.expire_requests(current_time);
+            trades.sessions()
+                .map(|s| (s.player_a.clone(), s.player_b.clone()))
+                .collect()
+        };
+
+        for (player_a, player_b) in pairs {
+            if let Err(reason) = self.validate_trade_pair(&player_a, &player_b).await {
+                self.cancel_trade(&player_a, &reason).await;
+            }
+        }
+    }
+
+    // ========================================================================
+    // Party
+    // ========================================================================
+
+    /// Find an active player by name (case-insensitive)
+    async fn find_active_player_by_name(&self, name: &str) -> Option<String> {
+        let players = self.players.read().await;
+        players.values()
+            .find(|p| p.active && p.name.eq_ignore_ascii_case(name))
+            .map(|p| p.id.clone())
+    }
+
+    /// IDs of the other party members within sharing range of a player
+    /// (same instance, alive, online). Always includes the player.
+    async fn nearby_party_members(&self, player_id: &str) -> Vec<String> {
+        let member_ids = {
+            let parties = self.parties.read().await;
+            match parties.party_of(player_id) {
+                Some(party) => party.member_ids(),
+                None => return vec![player_id.to_string()],
+            }
+        };
+
+        let instances = self.player_instances.read().await;
+        let players = self.players.read().await;
+        let Some(source) = players.get(player_id) else {
+            return vec![player_id.to_string()];
+        };
+        let source_instance = instances.get(player_id);
+
+        member_ids.into_iter()
+            .filter(|id| {
+                if id == player_id {
+                    return true;
+                }
+                players.get(id).is_some_and(|p| {
+                    p.active
+                        && !p.is_dead
+                        && instances.get(id) == source_instance
+                        && (p.x - source.x).abs().max((p.y - source.y).abs()) <= PARTY_SHARE_DISTANCE
+                })
+            })
+            .collect()
+    }
+
+    /// Send a party's roster (with current HP) to all of its online members
+    async fn send_party_update(&self, party_id: &str) {
+        let (update, member_ids) = {
+            let parties = self.parties.read().await;
+            let Some(party) = parties.get(party_id) else {
+                return;
+            };
+            let players = self.players.read().await;
+            let members = party.members.iter().map(|m| {
+                match players.get(&m.player_id).filter(|p| p.active) {
+                    Some(p) => PartyMemberData {
+                        id: m.player_id.clone(),
+                        name: m.name.clone(),
+                        hp: p.hp,
+                        max_hp: p.max_hp(),
+                        level: p.combat_level(),
+                        online: true,
+                    },
+                    None => PartyMemberData {
+                        id: m.player_id.clone(),
+                        name: m.name.clone(),
+                        hp: 0,
+                        max_hp: 0,
+                        level: 0,
+                        online: false,
+                    },
+                }
+            }).collect();
+
+            let update = ServerMessage::PartyUpdate {
+                party_id: party.id.clone(),
+                leader_id: party.leader_id.clone(),
+                loot_rule: party.loot_rule.as_str().to_string(),
+                members,
+            };
+            (update, party.member_ids())
+        };
+
+        for member_id in member_ids {
+            self.send_to_player(&member_id, update.clone()).await;
+        }
+    }
+
+    /// Send a system message to every member of a party
+    async fn send_party_system_message(&self, member_ids: &[String], text: &str) {
+        for member_id in member_ids {
+            self.send_system_message(member_id, text).await;
+        }
+    }
+
+    /// Re-send the party roster after a (re)connect and let the party know
+    pub async fn refresh_party_for(&self, player_id: &str) {
+        let party_id = {
+            let parties = self.parties.read().await;
+            parties.party_of(player_id).map(|p| p.id.clone())
+        };
+        if let Some(party_id) = party_id {
+            self.send_party_update(&party_id).await;
+        }
+    }
+
+    /// Route a party chat message to all online members
+    async fn handle_party_chat(&self, player_id: &str, text: &str) {
+        let member_ids = {
+            let parties = self.parties.read().await;
+            parties.party_of(player_id).map(|p| p.member_ids())
+        };
+        let Some(member_ids) = member_ids else {
+            self.send_system_message(player_id, "You are not in a party.").await;
+            return;
+        };
+
+        let sender_name = {
+            let players = self.players.read().await;
+            players.get(player_id).map(|p| p.name.clone()).unwrap_or_default()
+        };
+        let msg = ServerMessage::ChatMessage {
+            sender_id: player_id.to_string(),
+            sender_name,
+            text: text.to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as u64,
+            channel: Some("party".to_string()),
+        };
+        for member_id in member_ids {
+            self.send_to_player(&member_id, msg.clone()).await;
+        }
+    }
+
+    pub async fn handle_party_invite(&self, player_id: &str, target_id: &str) {
+        if player_id == target_id {
+            return;
+        }
+
+        let (inviter_name, target_name) = {
+            let players = self.players.read().await;
+            match (players.get(player_id), players.get(target_id).filter(|p| p.active)) {
+                (Some(a), Some(b)) => (a.name.clone(), b.name.clone()),
+                _ => {
+                    drop(players);
+                    self.send_system_message(player_id, "Player not found").await;
+                    return;
+                }
+            }
+        };
+
+        let current_time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        let result = {
+            let mut parties = self.parties.write().await;
+            if parties.party_of(target_id).is_some() {
+                Err(format!("{} is already in a party.", target_name))
+            } else {
+                match parties.party_of(player_id) {
+                    Some(party) if !party.is_leader(player_id) => Err("Only the party leader can invite players.".to_string()),
+                    Some(party) if party.is_full() => Err("The party is full.".to_string()),
+                    _ => {
+                        parties.add_invite(player_id, target_id, current_time);
+                        Ok(())
+                    }
+                }
+            }
+        };
+
+        match result {
+            Ok(()) => {
+                self.send_to_player(target_id, ServerMessage::PartyInvite {
+                    inviter_id: player_id.to_string(),
+                    inviter_name,
+                }).await;
+                self.send_system_message(player_id, &format!("Invited {} to your party.", target_name)).await;
+            }
+            Err(e) => self.send_system_message(player_id, &e).await,
+        }
+    }
+
+    pub async fn handle_party_respond(&self, player_id: &str, inviter_id: &str, accept: bool) {
+        let current_time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        let invite = self.parties.write().await.take_invite(player_id, inviter_id, current_time);
+        if invite.is_none() {
+            self.send_system_message(player_id, "That party invite has expired.").await;
+            return;
+        }
+
+        let (inviter_name, player_name) = {
+            let players = self.players.read().await;
+            (
+                players.get(inviter_id).filter(|p| p.active).map(|p| p.name.clone()),
+                players.get(player_id).map(|p| p.name.clone()).unwrap_or_default(),
+            )
+        };
+
+        if !accept {
+            self.send_system_message(inviter_id, &format!("{} declined your party invite.", player_name)).await;
+            return;
+        }
+
+        let Some(inviter_name) = inviter_name else {
+            self.send_system_message(player_id, "That player is no longer online.").await;
+            return;
+        };
+
+        let joined = {
+            let mut parties = self.parties.write().await;
+            parties.join(inviter_id, &inviter_name, player_id, &player_name)
+                .map(|party| (party.id.clone(), party.member_ids()))
+        };
+
+        match joined {
+            Ok((party_id, member_ids)) => {
+                tracing::info!("{} joined {}'s party", player_name, inviter_name);
+                self.send_party_system_message(&member_ids, &format!("{} joined the party.", player_name)).await;
+                self.send_party_update(&party_id).await;
+            }
+            Err(e) => self.send_system_message(player_id, &e).await,
+        }
+    }
+
+    /// Remove a member from their party and notify everyone involved.
+    /// Returns false if the player wasn't in a party.
+    async fn remove_party_member(&self, player_id: &str, message: &str) -> bool {
+        let departure = self.parties.write().await.leave(player_id);
+        let Some(departure) = departure else {
+            return false;
+        };
+
+        self.send_to_player(player_id, ServerMessage::PartyLeft {
+            party_id: departure.party.id.clone(),
+        }).await;
+
+        let remaining = departure.party.member_ids();
+        self.send_party_system_message(&remaining, message).await;
+
+        if departure.disbanded {
+            for member_id in &remaining {
+                self.send_to_player(member_id, ServerMessage::PartyLeft {
+                    party_id: departure.party.id.clone(),
+                }).await;
+            }
+            self.send_party_system_message(&remaining, "The party has been disbanded.").await;
+        } else {
+            self.send_party_update(&departure.party.id).await;
+        }
+        true
+    }
+
+    pub async fn handle_party_leave(&self, player_id: &str) {
+        let name = {
+            let players = self.players.read().await;
+            players.get(player_id).map(|p| p.name.clone()).unwrap_or_default()
+        };
+        if self.remove_party_member(player_id, &format!("{} left the party.", name)).await {
+            self.send_system_message(player_id, "You left the party.").await;
+        } else {
+            self.send_system_message(player_id, "You are not in a party.").await;
+        }
+    }
+
+    pub async fn handle_party_kick(&self, player_id: &str, member_id: &str) {
+        let member_name = {
+            let parties = self.parties.read().await;
+            match parties.party_of(player_id) {
+                Some(party) if party.is_leader(player_id) && member_id != player_id => {
+                    party.member_name(member_id).map(|n| n.to_string())
+                }
+                _ => None,
+            }
+        };
+        let Some(member_name) = member_name else {
+            self.send_system_message(player_id, "Only the party leader can remove members.").await;
+            return;
+        };
+
+        if self.remove_party_member(member_id, &format!("{} was removed from the party.", member_name)).await {
+            self.send_system_message(member_id, "You were removed from the party.").await;
+        }
+    }
+
+    pub async fn handle_party_promote(&self, player_id: &str, member_id: &str) {
+        let promoted = {
+            let mut parties = self.parties.write().await;
+            match parties.party_of(player_id) {
+                Some(party) if party.is_leader(player_id) => {
+                    let party_id = party.id.clone();
+                    let name = party.member_name(member_id).map(|n| n.to_string());
+                    let members = party.member_ids();
+                    if parties.set_leader(&party_id, member_id) {
+                        name.map(|n| (party_id, n, members))
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            }
+        };
+
+        match promoted {
+            Some((party_id, name, members)) => {
+                self.send_party_system_message(&members, &format!("{} is now the party leader.", name)).await;
+                self.send_party_update(&party_id).await;
+            }
+            None => self.send_system_message(player_id, "Only the party leader can transfer leadership.").await,
+        }
+    }
+
+    async fn handle_party_loot_rule(&self, player_id: &str, rule: LootRule) {
+        let updated = {
+            let mut parties = self.parties.write().await;
+            match parties.party_of_mut(player_id) {
+                Some(party) if party.is_leader(player_id) => {
+                    party.loot_rule = rule;
+                    Some((party.id.clone(), party.member_ids()))
+                }
+                _ => None,
+            }
+        };
+
+        match updated {
+            Some((party_id, members)) => {
+                self.send_party_system_message(&members, &format!("Loot rule set to {}.", rule.as_str())).await;
+                self.send_party_update(&party_id).await;
+            }
+            None => self.send_system_message(player_id, "Only the party leader can change the loot rule.").await,
+        }
+    }
+
+    /// Party bookkeeping when a player disconnects. Members keep their place;
+    /// the party is only disbanded once nobody in it is online.
+    async fn handle_party_disconnect(&self, player_id: &str) {
+        let party = {
+            let mut parties = self.parties.write().await;
+            parties.clear_invites_for(player_id);
+            parties.party_of(player_id).cloned()
+        };
+        let Some(party) = party else {
+            return;
+        };
+
+        let online_member = {
+            let players = self.players.read().await;
+            party.members.iter()
+                .find(|m| m.player_id != player_id && players.get(&m.player_id).is_some_and(|p| p.active))
+                .map(|m| m.player_id.clone())
+        };
+
+        match online_member {
+            Some(new_leader) => {
+                // An offline leader can't invite, so hand leadership to someone online.
+                // The roster is re-sent from tick, which shows the member as offline.
+                if party.is_leader(player_id) {
+                    self.parties.write().await.set_leader(&party.id, &new_leader);
+                }
+            }
+            None => {
+                self.parties.write().await.disband(&party.id);
+                tracing::info!("Party {} disbanded (all members offline)", party.id);
+            }
+        }
+    }
+
+    /// Periodic party upkeep: expire invites and refresh member HP
+    async fn update_parties(&self, current_time: u64) {
+        let party_ids: Vec<String> = {
+            let mut parties = self.parties.write().await;
+            parties.expire_invites(current_time);
+            parties.parties().map(|p| p.id.clone()).collect()
+        };
+        for party_id in party_ids {
+            self.send_party_update(&party_id).await;
+        }
+    }
+
+    /// Owner assignment for a kill drop according to the killer's party loot rule.
+    /// Returns (owner_id, party_id) for the ground item.
+    async fn party_loot_owner(&self, killer_id: &str, eligible: &[String]) -> (Option<String>, Option<String>) {
+        let mut parties = self.parties.write().await;
+        match parties.party_of_mut(killer_id) {
+            Some(party) => match party.loot_rule {
+                LootRule::Shared => (Some(killer_id.to_string()), Some(party.id.clone())),
+                LootRule::RoundRobin => (
+                    Some(party.next_looter(eligible).unwrap_or_else(|| killer_id.to_string())),
+                    None,
+                ),
+            },
+            None => (Some(killer_id.to_string()), None),
+        }
+    }
+
+    // ========================================================================
+    // Guilds
+    // ========================================================================
+
+    /// Load a player's guild membership from the database and set their nameplate tag
+    pub async fn load_player_guild(&self, player_id: &str, character_id: i64) {
+        let membership = match self.db.get_guild_membership(character_id).await {
+            Ok(membership) => membership,
+            Err(e) => {
+                tracing::error!("Failed to load guild for character {}: {}", character_id, e);
+                None
+            }
+        };
+
+        if let Some(player) = self.players.write().await.get_mut(player_id) {
+            player.guild_tag = membership.as_ref().map(|m| m.tag.clone());
+        }
+        self.player_guilds.write().await.insert(player_id.to_string(), PlayerGuild { character_id, membership });
+    }
+
+    /// Re-read a connected player's membership after it changed
+    async fn reload_player_guild(&self, player_id: &str) {
+        let character_id = {
+            let player_guilds = self.player_guilds.read().await;
+            match player_guilds.get(player_id) {
+                Some(pg) => pg.character_id,
+                None => return,
+            }
+        };
+        self.load_player_guild(player_id, character_id).await;
+    }
+
+    /// Show the guild's message of the day (on connect)
+    pub async fn send_guild_motd(&self, player_id: &str) {
+        let guild_id = {
+            let player_guilds = self.player_guilds.read().await;
+            player_guilds.get(player_id).and_then(|pg| pg.membership.as_ref().map(|m| m.guild_id))
+        };
+        let Some(guild_id) = guild_id else {
+            return;
+        };
+        match self.db.get_guild_motd(guild_id).await {
+            Ok(motd) if !motd.is_empty() => {
+                self.send_system_message(player_id, &format!("Guild message of the day: {}", motd)).await;
+            }
+            Ok(_) => {}
+            Err(e) => tracing::error!("Failed to load MOTD for guild {}: {}", guild_id, e),
+        }
+    }
+
+    /// The player's character ID and guild membership, or tell them they aren't in a guild
+    async fn require_guild(&self, player_id: &str) -> Option<(i64, GuildMembership)> {
+        let pg = self.player_guilds.read().await.get(player_id).cloned()?;
+        match pg.membership {
+            Some(membership) => Some((pg.character_id, membership)),
+            None => {
+                self.send_system_message(player_id, "You are not in a guild.").await;
+                None
+            }
+        }
+    }
+
+    /// IDs of the connected members of a guild
+    async fn online_guild_members(&self, guild_id: i64) -> Vec<String> {
+        let player_guilds = self.player_guilds.read().await;
+        player_guilds.iter()
+            .filter(|(_, pg)| pg.membership.as_ref().is_some_and(|m| m.guild_id == guild_id))
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// Player ID of a connected character, if any
+    async fn online_player_for_character(&self, character_id: i64) -> Option<String> {
+        let player_guilds = self.player_guilds.read().await;
+        player_guilds.iter()
+            .find(|(_, pg)| pg.character_id == character_id)
+            .map(|(id, _)| id.clone())
+    }
+
+    async fn send_guild_system_message(&self, guild_id: i64, text: &str) {
+        for member_id in self.online_guild_members(guild_id).await {
+            self.send_system_message(&member_id, text).await;
+        }
+    }
+
+    /// Find a guild member by name (case-insensitive), telling the player if there is none
+    async fn find_guild_member(&self, player_id: &str, guild_id: i64, name: &str) -> Option<GuildRosterEntry> {
+        let roster = match self.db.get_guild_roster(guild_id).await {
+            Ok(roster) => roster,
+            Err(e) => {
+                tracing::error!("Failed to load roster for guild {}: {}", guild_id, e);
+                return None;
+            }
+        };
+        let entry = roster.into_iter().find(|e| e.name.eq_ignore_ascii_case(name));
+        if entry.is_none() {
+            self.send_system_message(player_id, "No guild member with that name.").await;
+        }
+        entry
+    }
+
+    /// Route a guild chat message to all online members
+    async fn handle_guild_chat(&self, player_id: &str, text: &str) {
+        let Some((_, membership)) = self.require_guild(player_id).await else {
+            return;
+        };
+
+        let sender_name = self.get_player_name(player_id).await.unwrap_or_default();
+        let msg = ServerMessage::ChatMessage {
+            sender_id: player_id.to_string(),
+            sender_name,
+            text: text.to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as u64,
+            channel: Some("guild".to_string()),
+        };
+        for member_id in self.online_guild_members(membership.guild_id).await {
+            self.send_to_player(&member_id, msg.clone()).await;
+        }
+    }
+
+    /// Show the player's guild, rank and message of the day
+    async fn handle_guild_info(&self, player_id: &str) {
+        let pg = self.player_guilds.read().await.get(player_id).cloned();
+        let Some(membership) = pg.and_then(|pg| pg.membership) else {
+            self.send_system_message(player_id, "You are not in a guild. Create one with /guild create <tag> <name>.").await;
+            return;
+        };
+        let motd = self.db.get_guild_motd(membership.guild_id).await.unwrap_or_default();
+        self.send_system_message(player_id, &format!("<{}> {} - rank: {}", membership.tag, membership.guild_name, membership.rank.name)).await;
+        if !motd.is_empty() {
+            self.send_system_message(player_id, &format!("Message of the day: {}", motd)).await;
+        }
+    }
+
+    async fn handle_guild_create(&self, player_id: &str, tag: &str, name: &str) {
+        let Some(pg) = self.player_guilds.read().await.get(player_id).cloned() else {
+            return;
+        };
+        if pg.membership.is_some() {
+            self.send_system_message(player_id, "You are already in a guild.").await;
+            return;
+        }
+        if let Err(e) = guild::validate_guild_tag(tag).and_then(|_| guild::validate_guild_name(name)) {
+            self.send_system_message(player_id, &e).await;
+            return;
+        }
+
+        let tag = tag.to_uppercase();
+        match self.db.create_guild(name, &tag, pg.character_id).await {
+            Ok(_) => {
+                self.reload_player_guild(player_id).await;
+                self.send_system_message(player_id, &format!("Guild <{}> {} created. Invite players with /guild invite <name>.", tag, name)).await;
+            }
+            Err(e) => self.send_system_message(player_id, &e).await,
+        }
+    }
+
+    async fn handle_guild_invite(&self, player_id: &str, target_name: &str) {
+        let Some((_, membership)) = self.require_guild(player_id).await else {
+            return;
+        };
+        if !membership.rank.has(GUILD_PERM_INVITE) {
+            self.send_system_message(player_id, "Your guild rank can't invite players.").await;
+            return;
+        }
+        let Some(target_id) = self.find_active_player_by_name(target_name).await else {
+            self.send_system_message(player_id, "Player not found").await;
+            return;
+        };
+        if target_id == player_id {
+            return;
+        }
+
+        let target_name = self.get_player_name(&target_id).await.unwrap_or_default();
+        let target_in_guild = {
+            let player_guilds = self.player_guilds.read().await;
+            player_guilds.get(&target_id).is_some_and(|pg| pg.membership.is_some())
+        };
+        if target_in_guild {
+            self.send_system_message(player_id, &format!("{} is already in a guild.", target_name)).await;
+            return;
+        }
+        match self.db.count_guild_members(membership.guild_id).await {
+            Ok(count) if count >= GUILD_MAX_MEMBERS => {
+                self.send_system_message(player_id, "Your guild is full.").await;
+                return;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::error!("Failed to count members of guild {}: {}", membership.guild_id, e);
+                return;
+            }
+        }
+
+        let inviter_name = self.get_player_name(player_id).await.unwrap_or_default();
+        let current_time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        self.guild_invites.write().await.insert(target_id.clone(), GuildInvite {
+            guild_id: membership.guild_id,
+            guild_name: membership.guild_name.clone(),
+            inviter_name: inviter_name.clone(),
+            created_at: current_time,
+        });
+
+        self.send_system_message(&target_id, &format!(
+            "{} invites you to join <{}> {}. Type /guild accept or /guild decline.",
+            inviter_name, membership.tag, membership.guild_name
+        )).await;
+        self.send_system_message(player_id, &format!("Guild invite sent to {}.", target_name)).await;
+    }
+
+    async fn handle_guild_accept(&self, player_id: &str) {
+        let current_time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        let invite = self.guild_invites.write().await.remove(player_id)
+            .filter(|invite| current_time.saturating_sub(invite.created_at) <= GUILD_INVITE_TIMEOUT_MS);
+        let Some(invite) = invite else {
+            self.send_system_message(player_id, "You have no pending guild invite.").await;
+            return;
+        };
+        let Some(pg) = self.player_guilds.read().await.get(player_id).cloned() else {
+            return;
+        };
+        if pg.membership.is_some() {
+            self.send_system_message(player_id, "You are already in a guild.").await;
+            return;
+        }
+
+        // The guild may have been disbanded or filled up since the invite
+        match self.db.count_guild_members(invite.guild_id).await {
+            Ok(0) => {
+                self.send_system_message(player_id, "That guild no longer exists.").await;
+                return;
+            }
+            Ok(count) if count >= GUILD_MAX_MEMBERS => {
+                self.send_system_message(player_id, "That guild is full.").await;
+                return;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::error!("Failed to count members of guild {}: {}", invite.guild_id, e);
+                return;
+            }
+        }
+
+        // New members start at the lowest rank
+        let lowest_rank = match self.db.get_guild_ranks(invite.guild_id).await {
+            Ok(ranks) => ranks.last().map(|r| r.index).unwrap_or(GUILD_LEADER_RANK + 1),
+            Err(e) => {
+                tracing::error!("Failed to load ranks of guild {}: {}", invite.guild_id, e);
+                return;
+            }
+        };
+        if let Err(e) = self.db.add_guild_member(invite.guild_id, pg.character_id, lowest_rank).await {
+            tracing::error!("Failed to add character {} to guild {}: {}", pg.character_id, invite.guild_id, e);
+            return;
+        }
+        self.reload_player_guild(player_id).await;
+
+        let name = self.get_player_name(player_id).await.unwrap_or_default();
+        tracing::info!("{} joined guild {} (invited by {})", name, invite.guild_name, invite.inviter_name);
+        self.send_guild_system_message(invite.guild_id, &format!("{} has joined the guild.", name)).await;
+        self.send_guild_motd(player_id).await;
+    }
+
+    async fn handle_guild_decline(&self, player_id: &str) {
+        match self.guild_invites.write().await.remove(player_id) {
+            Some(invite) => {
+                self.send_system_message(player_id, &format!("Declined the invite to {}.", invite.guild_name)).await;
+            }
+            None => self.send_system_message(player_id, "You have no pending guild invite.").await,
+        }
+    }
+
+    async fn handle_guild_leave(&self, player_id: &str) {
+        let Some((character_id, membership)) = self.require_guild(player_id).await else {
+            return;
+        };
+        if membership.rank.index == GUILD_LEADER_RANK {
+            self.send_system_message(player_id, "The guild leader can't leave. Hand over leadership with /guild leader <name> or use /guild disband.").await;
+            return;
+        }
+        if let Err(e) = self.db.remove_guild_member(character_id).await {
+            tracing::error!("Failed to remove character {} from guild: {}", character_id, e);
+            return;
+        }
+        self.reload_player_guild(player_id).await;
+
+        let name = self.get_player_name(player_id).await.unwrap_or_default();
+        self.send_system_message(player_id, &format!("You left {}.", membership.guild_name)).await;
+        self.send_guild_system_message(membership.guild_id, &format!("{} has left the guild.", name)).await;
+    }
+
+    async fn handle_guild_kick(&self, player_id: &str, target_name: &str) {
+        let Some((_, membership)) = self.require_guild(player_id).await else {
+            return;
+        };
+        if !membership.rank.has(GUILD_PERM_KICK) {
+            self.send_system_message(player_id, "Your guild rank can't remove members.").await;
+            return;
+        }
+        let Some(target) = self.find_guild_member(player_id, membership.guild_id, target_name).await else {
+            return;
+        };
+        if !guild::outranks(membership.rank.index, target.rank_index) {
+            self.send_system_message(player_id, &format!("You don't outrank {}.", target.name)).await;
+            return;
+        }
+        if let Err(e) = self.db.remove_guild_member(target.character_id).await {
+            tracing::error!("Failed to remove character {} from guild: {}", target.character_id, e);
+            return;
+        }
+
+        if let Some(target_id) = self.online_player_for_character(target.character_id).await {
+            self.reload_player_guild(&target_id).await;
+            self.send_system_message(&target_id, &format!("You were removed from {}.", membership.guild_name)).await;
+        }
+        let actor_name = self.get_player_name(player_id).await.unwrap_or_default();
+        self.send_guild_system_message(membership.guild_id, &format!("{} was removed from the guild by {}.", target.name, actor_name)).await;
+    }
+
+    /// Move a member one rank up (`promote`) or down
+    async fn handle_guild_set_rank(&self, player_id: &str, target_name: &str, promote: bool) {
+        let Some((_, membership)) = self.require_guild(player_id).await else {
+            return;
+        };
+        if !membership.rank.has(GUILD_PERM_PROMOTE) {
+            self.send_system_message(player_id, "Your guild rank can't change member ranks.").await;
+            return;
+        }
+        let Some(target) = self.find_guild_member(player_id, membership.guild_id, target_name).await else {
+            return;
+        };
+        if !guild::outranks(membership.rank.index, target.rank_index) {
+            self.send_system_message(player_id, &format!("You don't outrank {}.", target.name)).await;
+            return;
+        }
+
+        let ranks = match self.db.get_guild_ranks(membership.guild_id).await {
+            Ok(ranks) => ranks,
+            Err(e) => {
+                tracing::error!("Failed to load ranks of guild {}: {}", membership.guild_id, e);
+                return;
+            }
+        };
+        let new_index = if promote { target.rank_index - 1 } else { target.rank_index + 1 };
+        // Nobody can promote to their own rank; leadership is handed over with /guild leader
+        if promote && !guild::outranks(membership.rank.index, new_index) {
+            self.send_system_message(player_id, &format!("You can't promote {} any further.", target.name)).await;
+            return;
+        }
+        let Some(new_rank) = ranks.iter().find(|r| r.index == new_index) else {
+            self.send_system_message(player_id, &format!("{} already has the lowest rank.", target.name)).await;
+            return;
+        };
+
+        if let Err(e) = self.db.set_guild_member_rank(target.character_id, new_rank.index).await {
+            tracing::error!("Failed to set guild rank of character {}: {}", target.character_id, e);
+            return;
+        }
+        if let Some(target_id) = self.online_player_for_character(target.character_id).await {
+            self.reload_player_guild(&target_id).await;
+        }
+        let verb = if promote { "promoted" } else { "demoted" };
+        self.send_guild_system_message(membership.guild_id, &format!("{} was {} to {}.", target.name, verb, new_rank.name)).await;
+    }
+
+    async fn handle_guild_leader(&self, player_id: &str, target_name: &str) {
+        let Some((character_id, membership)) = self.require_guild(player_id).await else {
+            return;
+        };
+        if membership.rank.index != GUILD_LEADER_RANK {
+            self.send_system_message(player_id, "Only the guild leader can hand over leadership.").await;
+            return;
+        }
+        let Some(target) = self.find_guild_member(player_id, membership.guild_id, target_name).await else {
+            return;
+        };
+        if target.character_id == character_id {
+            return;
+        }
+        if let Err(e) = self.db.transfer_guild_leadership(character_id, target.character_id).await {
+            tracing::error!("Failed to transfer leadership of guild {}: {}", membership.guild_id, e);
+            return;
+        }
+
+        self.reload_player_guild(player_id).await;
+        if let Some(target_id) = self.online_player_for_character(target.character_id).await {
+            self.reload_player_guild(&target_id).await;
+        }
+        self.send_guild_system_message(membership.guild_id, &format!("{} is now the guild leader.", target.name)).await;
+    }
+
+    async fn handle_guild_motd(&self, player_id: &str, motd: &str) {
+        let Some((_, membership)) = self.require_guild(player_id).await else {
+            return;
+        };
+        if !membership.rank.has(GUILD_PERM_SET_MOTD) {
+            self.send_system_message(player_id, "Your guild rank can't change the message of the day.").await;
+            return;
+        }
+        let motd: String = motd.chars().take(GUILD_MOTD_MAX_LEN).collect();
+        if let Err(e) = self.db.set_guild_motd(membership.guild_id, &motd).await {
+            tracing::error!("Failed to set MOTD for guild {}: {}", membership.guild_id, e);
+            return;
+        }
+        self.send_guild_system_message(membership.guild_id, &format!("Guild message of the day: {}", motd)).await;
+    }
+
+    async fn handle_guild_disband(&self, player_id: &str) {
+        let Some((_, membership)) = self.require_guild(player_id).await else {
+            return;
+        };
+        if !membership.rank.has(GUILD_PERM_DISBAND) {
+            self.send_system_message(player_id, "Only the guild leader can disband the guild.").await;
+            return;
+        }
+        let member_ids = self.online_guild_members(membership.guild_id).await;
+        if let Err(e) = self.db.disband_guild(membership.guild_id).await {
+            tracing::error!("Failed to disband guild {}: {}", membership.guild_id, e);
+            return;
+        }
+
+        self.guild_invites.write().await.retain(|_, invite| invite.guild_id != membership.guild_id);
+        for member_id in member_ids {
+            self.reload_player_guild(&member_id).await;
+            self.send_system_message(&member_id, &format!("{} has been disbanded.", membership.guild_name)).await;
+        }
+    }
+
+    async fn handle_guild_roster(&self, player_id: &str) {
+        let Some((_, membership)) = self.require_guild(player_id).await else {
+            return;
+        };
+        let roster = match self.db.get_guild_roster(membership.guild_id).await {
+            Ok(roster) => roster,
+            Err(e) => {
+                tracing::error!("Failed to load roster for guild {}: {}", membership.guild_id, e);
+                return;
+            }
+        };
+        let online_characters: Vec<i64> = {
+            let player_guilds = self.player_guilds.read().await;
+            player_guilds.values().map(|pg| pg.character_id).collect()
+        };
+        let entries: Vec<String> = roster.iter().map(|entry| {
+            let online = online_characters.contains(&entry.character_id);
+            format!("{} ({}){}", entry.name, entry.rank_name, if online { " *" } else { "" })
+        }).collect();
+
+        self.send_system_message(player_id, &format!("<{}> {} - {} members (* online):", membership.tag, membership.guild_name, roster.len())).await;
+        self.send_system_message(player_id, &entries.join(", ")).await;
+    }
+
+    /// Dispatch `/guild <subcommand> ...`
+    async fn handle_guild_command(&self, player_id: &str, parts: &[&str], text: &str) {
+        let sub = parts.get(1).map(|s| s.to_lowercase()).unwrap_or_default();
+        let target = parts.get(2).copied();
+        match (sub.as_str(), target) {
+            ("", _) => self.handle_guild_info(player_id).await,
+            ("create", Some(tag)) if parts.len() > 3 => {
+                let name = parts[3..].join(" ");
+                self.handle_guild_create(player_id, tag, &name).await;
+            }
+            ("invite", Some(name)) => self.handle_guild_invite(player_id, name).await,
+            ("accept", _) => self.handle_guild_accept(player_id).await,
+            ("decline", _) => self.handle_guild_decline(player_id).await,
+            ("leave", _) => self.handle_guild_leave(player_id).await,
+            ("kick", Some(name)) => self.handle_guild_kick(player_id, name).await,
+            ("promote", Some(name)) => self.handle_guild_set_rank(player_id, name, true).await,
+            ("demote", Some(name)) => self.handle_guild_set_rank(player_id, name, false).await,
+            ("leader", Some(name)) => self.handle_guild_leader(player_id, name).await,
+            ("motd", _) => {
+                // Everything after "/guild motd"
+                let motd = text.splitn(3, char::is_whitespace).nth(2).map(str::trim).unwrap_or("");
+                self.handle_guild_motd(player_id, motd).await;
+            }
+            ("disband", _) => self.handle_guild_disband(player_id).await,
+            ("roster", _) => self.handle_guild_roster(player_id).await,
+            _ => {
+                self.send_system_message(player_id, "Usage: /guild [create <tag> <name> | invite <name> | accept | decline | leave | kick <name> | promote <name> | demote <name> | leader <name> | motd <text> | disband | roster]").await;
+            }
+        }
+    }
+
+    // ========================================================================
+    // Achievements
+    // ========================================================================
+
+    /// Load a player's achievement progress from the database and set their title
+    pub async fn load_player_achievements(&self, player_id: &str) {
+        let Some((character_id, skills)) = self.players.read().await.get(player_id)
+            .map(|p| (p.character_id, p.skills.clone()))
+        else {
+            return;
+        };
+        let mut progress = match self.db.load_character_achievements(character_id).await {
+            Ok(progress) => progress,
+            Err(e) => {
+                tracing::error!("Failed to load achievements for character {}: {}", character_id, e);
+                PlayerAchievements::new()
+            }
+        };
+
+        // Skill levels reached before achievements existed count too
+        for skill_type in [SkillType::Hitpoints, SkillType::Combat] {
+            progress.record(AchievementEvent::SkillLevel {
+                skill: skill_type.as_str(),
+                level: skills.get(skill_type).level,
+            });
+        }
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        for achievement_id in progress.unlock_completed(&self.achievement_registry, now) {
+            if let Err(e) = self.db.record_achievement_unlock(character_id, &achievement_id, now).await {
+                tracing::error!("Failed to record achievement {} for character {}: {}", achievement_id, character_id, e);
+            }
+        }
+
+        let title = progress.title(&self.achievement_registry).map(|t| t.to_string());
+        if let Some(player) = self.players.write().await.get_mut(player_id) {
+            player.title = title;
+        }
+        self.player_achievements.write().await.insert(player_id.to_string(), progress);
+    }
+
+    /// Get achievement progress for saving (called on disconnect/auto-save)
+    pub async fn get_player_achievements(&self, player_id: &str) -> Option<PlayerAchievements> {
+        self.player_achievements.read().await.get(player_id).cloned()
+    }
+
+    pub async fn get_player_title(&self, player_id: &str) -> Option<String> {
+        let players = self.players.read().await;
+        players.get(player_id).and_then(|p| p.title.clone())
+    }
+
+    /// Send the player every achievement with their progress
+    pub async fn send_achievements_update(&self, player_id: &str) {
+        let msg = {
+            let player_achievements = self.player_achievements.read().await;
+            let Some(progress) = player_achievements.get(player_id) else {
+                return;
+            };
+            let achievements = self.achievement_registry.all().into_iter().map(|a| AchievementData {
+                id: a.id.clone(),
+                name: a.name.clone(),
+                description: a.description.clone(),
+                title: a.title.clone(),
+                progress: progress.progress(a),
+                target: a.criteria.target(),
+                unlocked_at: progress.unlocked.get(&a.id).copied(),
+            }).collect();
+            ServerMessage::AchievementsUpdate {
+                achievements,
+                active_title: progress.active_title.clone(),
+            }
+        };
+        self.send_to_player(player_id, msg).await;
+    }
+
+    /// Count an event towards the player's achievements and announce any unlocks
+    async fn record_achievement_event(&self, player_id: &str, event: AchievementEvent<'_>) {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        let unlocked = {
+            let mut player_achievements = self.player_achievements.write().await;
+            let Some(progress) = player_achievements.get_mut(player_id) else {
+                return;
+            };
+            progress.record(event);
+            progress.unlock_completed(&self.achievement_registry, now)
+        };
+        if unlocked.is_empty() {
+            return;
+        }
+        let character_id = self.get_player_character_id(player_id).await.unwrap_or(0);
+
+        let player_name = self.get_player_name(player_id).await.unwrap_or_default();
+        for achievement_id in &unlocked {
+            if let Err(e) = self.db.record_achievement_unlock(character_id, achievement_id, now).await {
+                tracing::error!("Failed to record achievement {} for character {}: {}", achievement_id, character_id, e);
+            }
+            let Some(achievement) = self.achievement_registry.get(achievement_id) else {
+                continue;
+            };
+            tracing::info!("Player {} unlocked achievement {}", player_id, achievement_id);
+            self.broadcast(ServerMessage::Announcement {
+                text: format!("{} has earned the achievement [{}]!", player_name, achievement.name),
+            }).await;
+            if let Some(title) = &achievement.title {
+                self.send_system_message(
+                    player_id,
+                    &format!("You can now use the title \"{}\". Select it in the achievements panel.", title),
+                ).await;
+            }
+        }
+
+        self.send_achievements_update(player_id).await;
+    }
+
+    /// Select the title shown before the player's name (None clears it)
+    pub async fn handle_set_title(&self, player_id: &str, achievement_id: Option<String>) {
+        let title = {
+            let mut player_achievements = self.player_achievements.write().await;
+            let Some(progress) = player_achievements.get_mut(player_id) else {
+                return;
+            };
+            if let Some(id) = &achievement_id {
+                let has_title = self.achievement_registry.get(id).is_some_and(|a| a.title.is_some());
+                if !has_title || !progress.unlocked.contains_key(id) {
+                    drop(player_achievements);
+                    self.send_system_message(player_id, "You haven't earned that title.").await;
+                    return;
+                }
+            }
+            progress.active_title = achievement_id;
+            progress.title(&self.achievement_registry).map(|t| t.to_string())
+        };
+
+        if let Some(player) = self.players.write().await.get_mut(player_id) {
+            player.title = title;
+        }
+        self.send_achievements_update(player_id).await;
+    }
+
+    // ========================================================================
+    // Reputation
+    // ========================================================================
+
+    /// Initialize faction standings for a player (called on join)
+    pub async fn set_player_reputation(&self, player_id: &str, reputation: PlayerReputation) {
+        self.player_reputations.write().await.insert(player_id.to_string(), reputation);
+    }
+
+    /// Get faction standings for saving (called on disconnect/auto-save)
+    pub async fn get_player_reputation(&self, player_id: &str) -> Option<PlayerReputation> {
+        self.player_reputations.read().await.get(player_id).cloned()
+    }
+
+    /// Send the player their standing with every faction
+    pub async fn send_reputation_update(&self, player_id: &str) {
+        let factions = {
+            let reputations = self.player_reputations.read().await;
+            let empty = PlayerReputation::new();
+            let reputation = reputations.get(player_id).unwrap_or(&empty);
+            self.faction_registry.all().into_iter().map(|faction| {
+                let standing = reputation.standing(faction);
+                let level = reputation::reputation_level(standing);
+                let (level_min, level_max) = reputation::level_bounds(level);
+                ReputationData {
+                    faction_id: faction.id.clone(),
+                    name: faction.display_name.clone(),
+                    description: faction.description.clone(),
+                    standing,
+                    level,
+                    level_name: reputation::level_name(level).to_string(),
+                    level_min,
+                    level_max,
+                }
+            }).collect()
+        };
+        self.send_to_player(player_id, ServerMessage::ReputationUpdate { factions }).await;
+    }
+
+    /// Player's reputation level with a faction (Neutral for unknown factions)
+    async fn reputation_level(&self, player_id: &str, faction_id: &str) -> i32 {
+        let Some(faction) = self.faction_registry.get(faction_id) else {
+            return 0;
+        };
+        let reputations = self.player_reputations.read().await;
+        match reputations.get(player_id) {
+            Some(reputation) => reputation.level(faction),
+            None => reputation::reputation_level(faction.starting_standing),
+        }
+    }
+
+    /// Check a reputation requirement, returning a message for the player if it isn't met
+    async fn check_reputation(&self, player_id: &str, requirement: &ReputationRequirement) -> Result<(), String> {
+        if self.reputation_level(player_id, &requirement.faction).await >= requirement.level {
+            return Ok(());
+        }
+        let faction_name = self.faction_registry.get(&requirement.faction)
+            .map(|f| f.display_name.as_str())
+            .unwrap_or(&requirement.faction);
+        Err(format!(
+            "You must be {} with {} to do that.",
+            reputation::level_name(requirement.level), faction_name
+        ))
+    }
+
+    /// Check whether a merchant will trade with the player at all
+    async fn check_merchant_reputation(&self, player_id: &str, merchant: &MerchantConfig) -> Result<(), String> {
+        if let Some(requirement) = &merchant.requires_reputation {
+            self.check_reputation(player_id, requirement).await?;
+        }
+        if let Some(faction_id) = &merchant.faction
+            && self.reputation_level(player_id, faction_id).await < reputation::MIN_TRADE_LEVEL
+        {
+            return Err("The merchant refuses to trade with you.".to_string());
+        }
+        Ok(())
+    }
+
+    /// (purchase, sale) price modifiers from the player's standing with the merchant's faction
+    async fn merchant_price_modifiers(&self, player_id: &str, merchant: &MerchantConfig) -> (f32, f32) {
+        match &merchant.faction {
+            Some(faction_id) => {
+                let level = self.reputation_level(player_id, faction_id).await;
+                (reputation::purchase_price_modifier(level), reputation::sale_price_modifier(level))
+            }
+            None => (1.0, 1.0),
+        }
+    }
+
+    /// Change a player's standing with a faction and notify them
+    pub async fn adjust_reputation(&self, player_id: &str, faction_id: &str, amount: i32) {
+        let Some(faction) = self.faction_registry.get(faction_id) else {
+            tracing::warn!("Reputation reward for unknown faction '{}'", faction_id);
+            return;
+        };
+        if amount == 0 {
+            return;
+        }
+
+        let (old, new) = {
+            let mut reputations = self.player_reputations.write().await;
+            reputations.entry(player_id.to_string())
+                .or_insert_with(PlayerReputation::new)
+                .adjust(faction, amount)
+        };
+        if old == new {
+            return;
+        }
+
+        let verb = if new > old { "increased" } else { "decreased" };
+        self.send_system_message(
+            player_id,
+            &format!("Reputation with {} {} by {}.", faction.display_name, verb, (new - old).abs()),
+        ).await;
+
+        let (old_level, new_level) = (reputation::reputation_level(old), reputation::reputation_level(new));
+        if old_level != new_level {
+            self.send_system_message(
+                player_id,
+                &format!("You are now {} with {}.", reputation::level_name(new_level), faction.display_name),
+            ).await;
+        }
+
+        self.send_reputation_update(player_id).await;
+    }
+
+    /// Apply the reputation part of a quest or kill reward
+    async fn grant_reputation_rewards(&self, player_id: &str, rewards: &[ReputationReward]) {
+        for reward in rewards {
+            self.adjust_reputation(player_id, &reward.faction, reward.amount).await;
+        }
+    }
+
+    // ========================================================================
+    // Challenges
+    // ========================================================================
+
+    /// Initialize challenge progress for a player (called on join)
+    pub async fn set_player_challenges(&self, player_id: &str, mut challenges: PlayerChallenges) {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        challenges.roll_over(self.challenge_registry.settings(), now);
+        self.player_challenges.write().await.insert(player_id.to_string(), challenges);
+    }
+
+    /// Get challenge progress for saving (called on disconnect/auto-save)
+    pub async fn get_player_challenges(&self, player_id: &str) -> Option<PlayerChallenges> {
+        self.player_challenges.read().await.get(player_id).cloned()
+    }
+
+    /// Send the player the active challenges, their progress and time until reset
+    pub async fn send_challenges_update(&self, player_id: &str) {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        let settings = self.challenge_registry.settings();
+
+        let msg = {
+            let player_challenges = self.player_challenges.read().await;
+            let Some(challenges) = player_challenges.get(player_id) else {
+                return;
+            };
+            let challenge_data = |period: ChallengePeriod| -> Vec<ChallengeData> {
+                let track = challenges.track(period);
+                self.challenge_registry.active(period, track.period).into_iter().map(|c| ChallengeData {
+                    id: c.id.clone(),
+                    name: c.name.clone(),
+                    description: c.description.clone(),
+                    progress: track.progress.get(&c.id).copied().unwrap_or(0),
+                    target: c.count,
+                    completed: track.completed.contains(&c.id),
+                    reward_gold: c.rewards.gold,
+                }).collect()
+            };
+            ServerMessage::ChallengesUpdate {
+                daily: challenge_data(ChallengePeriod::Daily),
+                weekly: challenge_data(ChallengePeriod::Weekly),
+                daily_resets_in: settings.next_reset(ChallengePeriod::Daily, now) - now,
+                weekly_resets_in: settings.next_reset(ChallengePeriod::Weekly, now) - now,
+                daily_streak: challenges.daily.current_streak(),
+                weekly_streak: challenges.weekly.current_streak(),
+            }
+        };
+        self.send_to_player(player_id, msg).await;
+    }
+
+    /// Count an event towards the player's challenges and grant rewards for any completed
+    async fn record_challenge_event(&self, player_id: &str, event: &QuestEvent) {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        let updates = {
+            let mut player_challenges = self.player_challenges.write().await;
+            let Some(challenges) = player_challenges.get_mut(player_id) else {
+                return;
+            };
+            challenges.record(&self.challenge_registry, event, now)
+        };
+        if updates.is_empty() {
+            return;
+        }
+
+        let mut rewarded = false;
+        for update in updates.iter().filter(|u| u.completed) {
+            let Some(challenge) = self.challenge_registry.get(&update.challenge_id) else {
+                continue;
+            };
+            let bonus = update.streak
+                .map(|streak| self.challenge_registry.settings().streak_bonus(update.period, streak))
+                .unwrap_or(0);
+            tracing::info!(
+                "Player {} completed {} challenge {} (streak bonus {})",
+                player_id, update.period.as_str(), challenge.id, bonus
+            );
+
+            {
+                let mut players = self.players.write().await;
+                if let Some(player) = players.get_mut(player_id) {
+                    player.inventory.gold += challenge.rewards.gold + bonus;
+                    for item in &challenge.rewards.items {
+                        let leftover = player.inventory.add_item(&item.item_id, item.count, &self.item_registry);
+                        if leftover > 0 {
+                            tracing::warn!("Player {} inventory full, lost {}x{} challenge reward", player_id, leftover, item.item_id);
+                        }
+                    }
+                }
+            }
+            self.grant_reputation_rewards(player_id, &challenge.rewards.reputation).await;
+            rewarded = true;
+
+            let mut text = format!("Challenge complete: {}!", challenge.name);
+            if challenge.rewards.gold > 0 {
+                text.push_str(&format!(" (+{} gold)", challenge.rewards.gold));
+            }
+            self.send_system_message(player_id, &text).await;
+            if let Some(streak) = update.streak {
+                let mut text = format!("All {} challenges complete! Streak: {}", update.period.as_str(), streak);
+                if bonus > 0 {
+                    text.push_str(&format!(" (+{} bonus gold)", bonus));
+                }
+                self.send_system_message(player_id, &text).await;
+            }
+        }
+
+        if rewarded {
+            let inventory = {
+                let players = self.players.read().await;
+                players.get(player_id).map(|p| (p.inventory.to_update(), p.inventory.gold))
+            };
+            if let Some((slots, gold)) = inventory {
+                self.send_to_player(player_id, ServerMessage::InventoryUpdate {
+                    player_id: player_id.to_string(),
+                    slots,
+                    gold,
+                }).await;
+            }
+        }
+        self.send_challenges_update(player_id).await;
+    }
+
+    /// Reset the progress of players whose daily or weekly challenges have rotated
+    async fn rotate_challenges(&self, now: u64) {
+        let rotated: Vec<String> = {
+            let mut player_challenges = self.player_challenges.write().await;
+            player_challenges.iter_mut()
+                .filter_map(|(player_id, challenges)| {
+                    challenges.roll_over(self.challenge_registry.settings(), now).then(|| player_id.clone())
+                })
+                .collect()
+        };
+
+        for player_id in rotated {
+            self.send_system_message(&player_id, "New challenges are available!").await;
+            self.send_challenges_update(&player_id).await;
+        }
+    }
+
+    // ========================================================================
+    // World Events
+    // ========================================================================
+
+    /// Start triggered events, spawn due waves, clean up killed event NPCs and
+    /// resolve events that were cleared or ran out of time
+    async fn update_world_events(&self, now: u64) {
+        let started: Vec<String> = {
+            let mut manager = self.world_events.write().await;
+            let due = manager.due_events(&self.world_event_registry, now, rand::random::<f64>);
+            for event_id in &due {
+                if let Some(definition) = self.world_event_registry.get(event_id) {
+                    manager.start(definition, now);
+                }
+            }
+            due
+        };
+        for event_id in &started {
+            if let Some(definition) = self.world_event_registry.get(event_id) {
+                self.announce_world_event(definition).await;
+            }
+        }
+
+        // Spawn waves whose delay has passed
+        let waves: Vec<(String, Vec<EventWave>)> = {
+            let mut manager = self.world_events.write().await;
+            manager.active_mut()
+                .filter_map(|event| {
+                    let definition = self.world_event_registry.get(&event.event_id)?;
+                    let waves = event.take_due_waves(definition, now);
+                    (!waves.is_empty()).then(|| (event.event_id.clone(), waves))
+                })
+                .collect()
+        };
+        for (event_id, waves) in waves {
+            let Some(definition) = self.world_event_registry.get(&event_id) else {
+                continue;
+            };
+            let mut spawned = Vec::new();
+            for wave in &waves {
+                spawned.extend(self.spawn_event_wave(definition, wave).await);
+            }
+            let mut manager = self.world_events.write().await;
+            if let Some(event) = manager.get_mut(&event_id) {
+                event.npc_ids.extend(spawned);
+            }
+        }
+
+        // Remove killed event NPCs once their death animation has played
+        let removed: Vec<(String, String)> = {
+            let mut npcs = self.npcs.write().await;
+            let dead: Vec<(String, String)> = npcs.values()
+                .filter(|n| !n.is_alive() && now.saturating_sub(n.death_time) >= TEMPORARY_NPC_LINGER_MS)
+                .filter_map(|n| n.event_id.clone().map(|event_id| (n.id.clone(), event_id)))
+                .collect();
+            for (npc_id, _) in &dead {
+                npcs.remove(npc_id);
+            }
+            dead
+        };
+        if !removed.is_empty() {
+            {
+                let mut manager = self.world_events.write().await;
+                for (npc_id, event_id) in &removed {
+                    if let Some(event) = manager.get_mut(event_id) {
+                        event.npc_killed(npc_id);
+                    }
+                }
+            }
+            for (npc_id, _) in removed {
+                self.broadcast(ServerMessage::NpcDespawned { id: npc_id }).await;
+            }
+        }
+
+        let finished: Vec<(String, bool)> = {
+            let manager = self.world_events.read().await;
+            manager.active()
+                .filter_map(|event| {
+                    let definition = self.world_event_registry.get(&event.event_id)?;
+                    if event.is_cleared(definition) {
+                        Some((event.event_id.clone(), true))
+                    } else if event.is_expired(now) {
+                        Some((event.event_id.clone(), false))
+                    } else {
+                        None
+                    }
+                })
+                .collect()
+        };
+        for (event_id, cleared) in finished {
+            self.finish_world_event(&event_id, cleared, now).await;
+        }
+    }
+
+    async fn announce_world_event(&self, definition: &WorldEventDefinition) {
+        tracing::info!("World event {} started at ({}, {})", definition.id, definition.location.x, definition.location.y);
+        self.broadcast(ServerMessage::Announcement {
+            text: definition.announcement.clone(),
+        }).await;
+    }
+
+    /// Spawn one wave of an event around its location, returning the new NPC IDs
+    async fn spawn_event_wave(&self, definition: &WorldEventDefinition, wave: &EventWave) -> Vec<String> {
+        let mut spawned = Vec::new();
+        for _ in 0..wave.count {
+            let location = definition.location;
+            let (x, y) = self.find_walkable_tile_near(location.x, location.y, location.radius).await;
+            let Some(npc_id) = self.spawn_npc_at(&wave.prototype, x as f32, y as f32).await else {
+                break;
+            };
+            let mut npcs = self.npcs.write().await;
+            if let Some(npc) = npcs.get_mut(&npc_id) {
+                npc.event_id = Some(definition.id.clone());
+                npc.level = wave.level;
+                npc.max_hp = (npc.max_hp as f32 * wave.hp_multiplier).round().max(1.0) as i32;
+                npc.hp = npc.max_hp;
+                if let Some(name) = &wave.name {
+                    npc.stats.display_name = name.clone();
+                }
+            }
+            spawned.push(npc_id);
+        }
+        spawned
+    }
+
+    /// A random walkable tile within `radius` of a point, or the point itself if none is found
+    async fn find_walkable_tile_near(&self, center_x: i32, center_y: i32, radius: i32) -> (i32, i32) {
+        use rand::Rng;
+
+        let radius = radius.max(0);
+        for _ in 0..10 {
+            let (dx, dy) = {
+                let mut rng = rand::thread_rng();
+                (rng.gen_range(-radius..=radius), rng.gen_range(-radius..=radius))
+            };
+            let (x, y) = (center_x + dx, center_y + dy);
+            if self.world.is_tile_walkable(x, y).await {
+                return (x, y);
+            }
+        }
+        (center_x, center_y)
+    }
+
+    /// Credit damage dealt to a world event NPC to the attacker
+    async fn record_event_contribution(&self, player_id: &str, npc_id: &str, damage: i32) {
+        let mut manager = self.world_events.write().await;
+        if let Some(event) = manager.event_for_npc(npc_id) {
+            event.record_contribution(player_id, damage);
+        }
+    }
+
+    /// End an event, despawning what is left of it and rewarding contributors if it was cleared
+    async fn finish_world_event(&self, event_id: &str, cleared: bool, now: u64) {
+        let Some(definition) = self.world_event_registry.get(event_id) else {
+            return;
+        };
+        let Some(event) = self.world_events.write().await.finish(definition, now) else {
+            return;
+        };
+
+        if !event.npc_ids.is_empty() {
+            {
+                let mut npcs = self.npcs.write().await;
+                for npc_id in &event.npc_ids {
+                    npcs.remove(npc_id);
+                }
+            }
+            for npc_id in event.npc_ids.iter().cloned() {
+                self.broadcast(ServerMessage::NpcDespawned { id: npc_id }).await;
+            }
+        }
+
+        if !cleared {
+            tracing::info!("World event {} ended unresolved ({} killed)", event_id, event.killed);
+            self.broadcast(ServerMessage::Announcement {
+                text: format!("{} has ended. The threat was not repelled in time.", definition.name),
+            }).await;
+            return;
+        }
+
+        let contributors = event.eligible_contributors(definition.min_contribution);
+        tracing::info!("World event {} completed with {} rewarded contributors", event_id, contributors.len());
+        for (index, (player_id, _)) in contributors.iter().enumerate() {
+            let bonus = if index == 0 { definition.top_contributor_gold } else { 0 };
+            self.grant_world_event_rewards(player_id, definition, bonus).await;
+        }
+
+        let top = match contributors.first() {
+            Some((player_id, damage)) => self.get_player_name(player_id).await.map(|name| (name, *damage)),
+            None => None,
+        };
+        let text = match top {
+            Some((name, damage)) => format!("{} is complete! Top contributor: {} ({} damage)", definition.name, name, damage),
+            None => format!("{} is complete!", definition.name),
+        };
+        self.broadcast(ServerMessage::Announcement { text }).await;
+    }
+
+    async fn grant_world_event_rewards(&self, player_id: &str, definition: &WorldEventDefinition, bonus_gold: i32) {
+        let inventory = {
+            let mut players = self.players.write().await;
+            let Some(player) = players.get_mut(player_id) else {
+                return;
+            };
+            player.inventory.gold += definition.rewards.gold + bonus_gold;
+            for item in &definition.rewards.items {
+                let leftover = player.inventory.add_item(&item.item_id, item.count, &self.item_registry);
+                if leftover > 0 {
+                    tracing::warn!("Player {} inventory full, lost {}x{} event reward", player_id, leftover, item.item_id);
+                }
+            }
+            (player.inventory.to_update(), player.inventory.gold)
+        };
+        self.grant_reputation_rewards(player_id, &definition.rewards.reputation).await;
+
+        let mut text = format!("Event reward for {}:", definition.name);
+        if definition.rewards.gold > 0 {
+            text.push_str(&format!(" +{} gold", definition.rewards.gold));
+        }
+        if bonus_gold > 0 {
+            text.push_str(&format!(" (+{} top contributor bonus)", bonus_gold));
+        }
+        self.send_system_message(player_id, &text).await;
+
+        let (slots, gold) = inventory;
+        self.send_to_player(player_id, ServerMessage::InventoryUpdate {
+            player_id: player_id.to_string(),
+            slots,
+            gold,
+        }).await;
+    }
+
+    /// Send the running and upcoming world events to a player at a bounty board
+    async fn send_bounty_board(&self, player_id: &str, npc_id: &str) {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        let mut events: Vec<WorldEventData> = {
+            let manager = self.world_events.read().await;
+            manager.active()
+                .filter_map(|event| {
+                    let definition = self.world_event_registry.get(&event.event_id)?;
+                    Some(WorldEventData {
+                        id: definition.id.clone(),
+                        name: definition.name.clone(),
+                        description: definition.description.clone(),
+                        kind: definition.kind.as_str().to_string(),
+                        x: definition.location.x,
+                        y: definition.location.y,
+                        killed: event.killed,
+                        total: definition.total_npcs(),
+                        ends_in: event.ends_at.saturating_sub(now),
+                        contribution: event.contributions.get(player_id).copied().unwrap_or(0),
+                        min_contribution: definition.min_contribution,
+                        reward_gold: definition.rewards.gold,
+                    })
+                })
+                .collect()
+        };
+        events.sort_by_key(|e| e.ends_in);
+
+        let mut upcoming: Vec<UpcomingEventData> = self.world_event_registry.all()
+            .filter_map(|definition| {
+                let starts_at = definition.next_scheduled(now)?;
+                Some(UpcomingEventData {
+                    name: definition.name.clone(),
+                    kind: definition.kind.as_str().to_string(),
+                    starts_in: starts_at - now,
+                })
+            })
+            .collect();
+        upcoming.sort_by_key(|u| u.starts_in);
+
+        self.send_to_player(player_id, ServerMessage::BountyBoard {
+            npc_id: npc_id.to_string(),
+            events,
+            upcoming,
+        }).await;
+    }
+
+    /// Admin control over world events: /event [start <id> | stop <id>]
+    async fn handle_event_command(&self, player_id: &str, parts: &[&str]) {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        match (parts.get(1).copied(), parts.get(2).copied()) {
+            (None, _) => {
+                let running: Vec<String> = {
+                    let manager = self.world_events.read().await;
+                    manager.active().map(|e| e.event_id.clone()).collect()
+                };
+                let mut ids: Vec<&str> = self.world_event_registry.all().map(|d| d.id.as_str()).collect();
+                ids.sort();
+                let list = ids.iter()
+                    .map(|id| if running.iter().any(|r| r == id) { format!("{} (running)", id) } else { id.to_string() })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                self.send_system_message(player_id, &format!("World events: {}", list)).await;
+            }
+            (Some("start"), Some(event_id)) => {
+                let Some(definition) = self.world_event_registry.get(event_id) else {
+                    self.send_system_message(player_id, &format!("Unknown world event: {}", event_id)).await;
+                    return;
+                };
+                {
+                    let mut manager = self.world_events.write().await;
+                    if manager.active().any(|e| e.event_id == event_id) {
+                        drop(manager);
+                        self.send_system_message(player_id, &format!("{} is already running", event_id)).await;
+                        return;
+                    }
+                    manager.start(definition, now);
+                }
+                tracing::info!("Admin {} started world event {}", player_id, event_id);
+                self.announce_world_event(definition).await;
+            }
+            (Some("stop"), Some(event_id)) => {
+                tracing::info!("Admin {} stopped world event {}", player_id, event_id);
+                self.finish_world_event(event_id, false, now).await;
+            }
+            _ => {
+                self.send_system_message(player_id, "Usage: /event [start <id> | stop <id>]").await;
+            }
+        }
+    }
+
+    // ========================================================================
+    // Spawn Regions
+    // ========================================================================
+
+    /// Remove dead region monsters and spawn replacements for regions below their cap
+    async fn update_spawn_regions(&self, now: u64, nav_grid: &NavGrid) {
+        let (removed, populations) = {
+            let mut npcs = self.npcs.write().await;
+            let dead: Vec<String> = npcs.values()
+                .filter(|n| n.spawn_region.is_some() && !n.is_alive())
+                .filter(|n| now.saturating_sub(n.death_time) >= TEMPORARY_NPC_LINGER_MS)
+                .map(|n| n.id.clone())
+                .collect();
+            for npc_id in &dead {
+                npcs.remove(npc_id);
+            }
+            // Monsters still in their death animation count until they are removed
+            let mut populations: HashMap<String, usize> = HashMap::new();
+            for region_id in npcs.values().filter_map(|n| n.spawn_region.as_ref()) {
+                *populations.entry(region_id.clone()).or_insert(0) += 1;
+            }
+            (dead, populations)
+        };
+        for npc_id in removed {
+            self.broadcast(ServerMessage::NpcDespawned { id: npc_id }).await;
+        }
+
+        let due = self.spawn_regions.write().await.due_spawns(&populations, now);
+        if due.is_empty() {
+            return;
+        }
+
+        let mut occupied: std::collections::HashSet<(i32, i32)> = {
+            let players = self.players.read().await;
+            players.values().map(|p| (p.x, p.y)).collect()
+        };
+        let mut npcs = self.npcs.write().await;
+        occupied.extend(npcs.values().filter(|n| n.is_alive()).map(|n| (n.x, n.y)));
+
+        let mut rng = rand::thread_rng();
+        for (region, count) in due {
+            for _ in 0..count {
+                let Some(entity_id) = region.pick_entity(&mut rng) else {
+                    break;
+                };
+                let Some(prototype) = self.entity_registry.get(entity_id) else {
+                    tracing::warn!("Spawn region {}: prototype '{}' not found", region.id, entity_id);
+                    continue;
+                };
+                let Some((x, y)) = region.random_tile(&mut rng, |x, y| {
+                    nav_grid.is_walkable(x, y) && !occupied.contains(&(x, y))
+                }) else {
+                    tracing::debug!("Spawn region {} has no free tile", region.id);
+                    break;
+                };
+                let level = region.roll_level(&mut rng);
+                let npc_id = format!("{}_{}", region.id, Uuid::new_v4());
+                let mut npc = Npc::from_prototype(&npc_id, entity_id, prototype, x, y, level);
+                npc.spawn_region = Some(region.id.clone());
+                npc.set_elite(self.elite_table.roll_for(prototype, &mut rng));
+                occupied.insert((x, y));
+                npcs.insert(npc_id, npc);
+            }
+        }
+    }
+
+    /// Every spawn region with its current living population, for the stats API
+    pub async fn spawn_region_populations(&self) -> Vec<(SpawnRegion, usize)> {
+        let mut populations: HashMap<String, usize> = HashMap::new();
+        {
+            let npcs = self.npcs.read().await;
+            for npc in npcs.values().filter(|n| n.is_alive()) {
+                if let Some(region_id) = &npc.spawn_region {
+                    *populations.entry(region_id.clone()).or_insert(0) += 1;
+                }
+            }
+        }
+        let manager = self.spawn_regions.read().await;
+        manager.regions().iter()
+            .map(|region| (region.clone(), populations.get(&region.id).copied().unwrap_or(0)))
+            .collect()
+    }
+
+    // ========================================================================
+    // Elites
+    // ========================================================================
+
+    /// Spawn `count` normal copies of a dead elite around its corpse, already fighting the killer
+    async fn split_elite(&self, killer_id: &str, npc_id: &str, prototype_id: &str, level: i32, at: (i32, i32), count: u32) {
+        let Some(prototype) = self.entity_registry.get(prototype_id) else {
+            return;
+        };
+        let npc_map = self.npcs_near(killer_id).await;
+        let world_grid;
+        let nav_grid = match &npc_map {
+            NpcMap::Instance(instance) => match self.instance_map(instance) {
+                Some(map) => instance.nav_grid(map),
+                None => return,
+            },
+            NpcMap::Overworld => {
+                world_grid = self.world.nav_grid().await;
+                &world_grid
+            }
+        };
+        let mut occupied: std::collections::HashSet<(i32, i32)> = {
+            let players = self.players.read().await;
+            players.values().map(|p| (p.x, p.y)).collect()
+        };
+        let mut npcs = self.npc_lock(&npc_map).write().await;
+        occupied.extend(npcs.values().filter(|n| n.is_alive()).map(|n| (n.x, n.y)));
+
+        let mut tiles = (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| (at.0 + dx, at.1 + dy)))
+            .filter(|tile| nav_grid.is_walkable(tile.0, tile.1) && !occupied.contains(tile));
+        for _ in 0..count {
+            let Some((x, y)) = tiles.next() else {
+                break;
+            };
+            let split_id = format!("{}_split_{}", npc_id, Uuid::new_v4());
+            let mut split = Npc::from_prototype(&split_id, prototype_id, prototype, x, y, level);
+            split.split_from = Some(npc_id.to_string());
+            split.add_threat(killer_id, 1.0);
+            npcs.insert(split_id, split);
+        }
+    }
+
+    /// Remove dead split copies once their death animation has played
+    async fn remove_dead_splits(&self, now: u64) {
+        let removed: Vec<String> = {
+            let mut npcs = self.npcs.write().await;
+            let dead: Vec<String> = npcs.values()
+                .filter(|n| n.split_from.is_some() && !n.is_alive())
+                .filter(|n| now.saturating_sub(n.death_time) >= TEMPORARY_NPC_LINGER_MS)
+                .map(|n| n.id.clone())
+                .collect();
+            for npc_id in &dead {
+                npcs.remove(npc_id);
+            }
+            dead
+        };
+        for npc_id in removed {
+            self.broadcast(ServerMessage::NpcDespawned { id: npc_id }).await;
+        }
+    }
+
+    /// Heal a vampiric elite for part of the damage it just dealt to a player
+    async fn apply_elite_lifesteal(&self, npc_id: &str, target_id: &str, damage: i32) {
+        let npc_map = self.npcs_near(target_id).await;
+        let mut npcs = self.npc_lock(&npc_map).write().await;
+        let Some(npc) = npcs.get_mut(npc_id).filter(|n| n.is_alive()) else {
+            return;
+        };
+        let healed = npc.elite.as_ref().map_or(0, |elite| elite.lifesteal(damage));
+        npc.hp = (npc.hp + healed).min(npc.max_hp);
+    }
+
+    /// Deal part of a player's hit on an elite with reflect back to the player
+    async fn reflect_elite_damage(&self, player_id: &str, npc_id: &str, damage: i32, current_time: u64) {
+        let reflected = {
+            let npc_map = self.npcs_near(player_id).await;
+            let npcs = self.npc_lock(&npc_map).read().await;
+            npcs.get(npc_id)
+                .and_then(|n| n.elite.as_ref())
+                .map_or(0, |elite| elite.reflected(damage))
+        };
+        if reflected <= 0 {
+            return;
+        }
+
+        let (target_hp, target_x, target_y, died) = {
+            let mut players = self.players.write().await;
+            let Some(player) = players.get_mut(player_id) else {
+                return;
+            };
+            if player.is_dead || player.is_god_mode {
+                return;
+            }
+            player.hp = (player.hp - reflected).max(0);
+            let died = player.hp <= 0;
+            if died {
+                player.die(current_time);
+            }
+            (player.hp, player.x as f32, player.y as f32, died)
+        };
+
+        self.broadcast(ServerMessage::DamageEvent {
+            source_id: npc_id.to_string(),
+            target_id: player_id.to_string(),
+            damage: reflected,
+            target_hp,
+            target_x,
+            target_y,
+            projectile: None,
+        }).await;
+
+        if died {
+            tracing::info!("Player {} died to damage reflected by {}", player_id, npc_id);
+            self.broadcast(ServerMessage::PlayerDied {
+                id: player_id.to_string(),
+                killer_id: npc_id.to_string(),
+            }).await;
+            self.apply_death_penalty(player_id, current_time).await;
+        }
+    }
+
+    // ========================================================================
+    // World Scripts
+    // ========================================================================
+
+    /// Hot-reload changed scripts, clean up dead script NPCs, and run due
+    /// timers and region checks for overworld players
+    async fn update_world_scripts(&self, now: u64) {
+        let removed: Vec<String> = {
+            let mut npcs = self.npcs.write().await;
+            let dead: Vec<String> = npcs.values()
+                .filter(|n| n.spawned_by_script.is_some() && !n.is_alive())
+                .filter(|n| now.saturating_sub(n.death_time) >= TEMPORARY_NPC_LINGER_MS)
+                .map(|n| n.id.clone())
+                .collect();
+            for npc_id in &dead {
+                npcs.remove(npc_id);
+            }
+            dead
+        };
+        for npc_id in removed {
+            self.broadcast(ServerMessage::NpcDespawned { id: npc_id }).await;
+        }
+
+        let in_instances: std::collections::HashSet<String> = self.player_instances.read().await.keys().cloned().collect();
+        let positions: Vec<(String, i32, i32)> = {
+            let players = self.players.read().await;
+            players.values()
+                .filter(|p| p.active && !p.is_dead && !in_instances.contains(&p.id))
+                .map(|p| (p.id.clone(), p.x, p.y))
+                .collect()
+        };
+
+        let output = {
+            let mut host = self.script_host.write().await;
+            let version = self.world_scripts.version();
+            if host.version() != version {
+                host.reload(&self.world_scripts.snapshot(), version, now);
+            }
+            host.update(now, &positions)
+        };
+        self.apply_script_actions(&output.actions).await;
+    }
+
+    /// Run a world script hook and apply whatever the scripts asked for
+    async fn run_script_hook(&self, hook: ScriptHook<'_>) -> ScriptOutput {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        let output = self.script_host.write().await.run_hook(hook, now);
+        self.apply_script_actions(&output.actions).await;
+        output
+    }
+
+    async fn apply_script_actions(&self, actions: &[ScriptAction]) {
+        for action in actions {
+            match action {
+                ScriptAction::SpawnNpc { npc_id, prototype, x, y, level, script } => {
+                    let Some(definition) = self.entity_registry.get(prototype) else {
+                        tracing::warn!("World script {} tried to spawn unknown prototype '{}'", script, prototype);
+                        continue;
+                    };
+                    let mut npc = Npc::from_prototype(npc_id, prototype, definition, *x, *y, *level);
+                    npc.spawned_by_script = Some(script.clone());
+                    self.npcs.write().await.insert(npc_id.clone(), npc);
+                }
+                ScriptAction::GiveItem { player_id, item_id, quantity } => {
+                    if self.item_registry.get(item_id).is_none() {
+                        tracing::warn!("World script tried to give unknown item '{}'", item_id);
+                        continue;
+                    }
+                    let inventory = {
+                        let mut players = self.players.write().await;
+                        let Some(player) = players.get_mut(player_id) else {
+                            continue;
+                        };
+                        let leftover = player.inventory.add_item(item_id, *quantity, &self.item_registry);
+                        if leftover > 0 {
+                            tracing::warn!("Player {} inventory full, lost {}x{} from a world script", player_id, leftover, item_id);
+                        }
+                        (player.inventory.to_update(), player.inventory.gold)
+                    };
+                    let (slots, gold) = inventory;
+                    self.send_to_player(player_id, ServerMessage::InventoryUpdate {
+                        player_id: player_id.clone(),
+                        slots,
+                        gold,
+                    }).await;
+                }
+                ScriptAction::Announce { text } => {
+                    self.broadcast(ServerMessage::Announcement { text: text.clone() }).await;
+                }
+                ScriptAction::Message { player_id, text } => {
+                    self.send_system_message(player_id, text).await;
+                }
+                ScriptAction::ShowDialogue { script, player_id, speaker, text, choices } => {
+                    self.send_to_player(player_id, ServerMessage::ShowDialogue {
+                        quest_id: format!("{}{}", SCRIPT_DIALOGUE_PREFIX, script),
+                        npc_id: String::new(),
+                        speaker: speaker.clone(),
+                        text: text.clone(),
+                        choices: choices.iter()
+                            .map(|(id, text)| crate::protocol::DialogueChoice { id: id.clone(), text: text.clone() })
+                            .collect(),
+                    }).await;
+                }
+            }
+        }
+    }
+
+    // ========================================================================
+    // Bosses
+    // ========================================================================
+
+    /// Add damage to a boss's leaderboard, starting the encounter on the first hit
+    async fn record_boss_damage(&self, player_id: &str, npc_id: &str, damage: i32) {
+        let boss = {
+            let npcs = self.npcs.read().await;
+            npcs.get(npc_id).map(|n| (n.prototype_id.clone(), n.stats.damage))
+        };
+        let Some((prototype_id, base_damage)) = boss else {
+            return;
+        };
+        let Some(config) = self.entity_registry.get(&prototype_id).and_then(|p| p.boss.as_ref()) else {
+            return;
+        };
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        let engaged = {
+            let mut encounters = self.boss_encounters.write().await;
+            let engaged = !encounters.contains_key(npc_id);
+            if engaged {
+                let Some(source) = self.boss_scripts.get(&config.script) else {
+                    return; // Warned about at load time
+                };
+                match BossEncounter::new(npc_id, &prototype_id, &config.script, source, base_damage, now) {
+                    Ok(encounter) => {
+                        encounters.insert(npc_id.to_string(), encounter);
+                    }
+                    Err(e) => {
+                        tracing::error!("{}", e);
+                        return;
+                    }
+                }
+            }
+            if let Some(encounter) = encounters.get_mut(npc_id) {
+                encounter.record_damage(player_id, damage);
+            }
+            engaged
+        };
+
+        if engaged {
+            tracing::info!("Boss encounter with {} ({}) started by {}", prototype_id, npc_id, player_id);
+            self.run_boss_hook(npc_id, "on_engage", None, now).await;
+        }
+    }
+
+    /// What a boss script can see: the boss and living overworld players around it
+    async fn boss_snapshot(&self, npc_id: &str, now: u64) -> Option<BossSnapshot> {
+        let (name, hp, max_hp, x, y, target, threat) = {
+            let npcs = self.npcs.read().await;
+            let npc = npcs.get(npc_id)?;
+            (npc.stats.display_name.clone(), npc.hp, npc.max_hp, npc.x, npc.y, npc.target_id.clone(), npc.threat.sorted())
+        };
+        let in_instances: std::collections::HashSet<String> = self.player_instances.read().await.keys().cloned().collect();
+        let players = {
+            let players = self.players.read().await;
+            players.values()
+                .filter(|p| p.active && !p.is_dead && !in_instances.contains(&p.id))
+                .filter(|p| (p.x - x).abs() <= BOSS_AREA_RADIUS && (p.y - y).abs() <= BOSS_AREA_RADIUS)
+                .map(|p| (p.id.clone(), p.x, p.y))
+                .collect()
+        };
+        Some(BossSnapshot { name, hp, max_hp, x, y, target, players, threat, now })
+    }
+
+    /// Run a script hook for a boss and apply the actions it queued
+    async fn run_boss_hook(&self, npc_id: &str, hook: &str, arg: Option<i32>, now: u64) {
+        let Some(snapshot) = self.boss_snapshot(npc_id, now).await else {
+            return;
+        };
+        let actions = {
+            let mut encounters = self.boss_encounters.write().await;
+            let Some(encounter) = encounters.get_mut(npc_id) else {
+                return;
+            };
+            let actions = encounter.run_hook(hook, &snapshot, arg);
+            encounter.apply_state(&actions);
+            actions
+        };
+        self.apply_boss_actions(npc_id, &snapshot, actions, now).await;
+    }
+
+    async fn apply_boss_actions(&self, npc_id: &str, snapshot: &BossSnapshot, actions: Vec<BossAction>, now: u64) {
+        for action in actions {
+            match action {
+                BossAction::Summon { prototype, count, level } => {
+                    for _ in 0..count {
+                        let (x, y) = self.find_walkable_tile_near(snapshot.x, snapshot.y, 2).await;
+                        let Some(add_id) = self.spawn_npc_at(&prototype, x as f32, y as f32).await else {
+                            break;
+                        };
+                        let mut npcs = self.npcs.write().await;
+                        if let Some(add) = npcs.get_mut(&add_id) {
+                            add.level = level;
+                            add.summoned_by = Some(npc_id.to_string());
+                        }
+                    }
+                }
+                BossAction::Telegraph { tiles, damage, delay_ms } => {
+                    if tiles.is_empty() {
+                        continue;
+                    }
+                    let delay_ms = {
+                        let mut encounters = self.boss_encounters.write().await;
+                        match encounters.get_mut(npc_id) {
+                            Some(encounter) => encounter.add_telegraph(tiles.clone(), damage, delay_ms, now),
+                            None => continue,
+                        }
+                    };
+                    self.broadcast(ServerMessage::BossTelegraph {
+                        boss_id: npc_id.to_string(),
+                        tiles,
+                        delay_ms,
+                    }).await;
+                }
+                BossAction::Enrage { multiplier } => {
+                    let base_damage = self.boss_encounters.read().await.get(npc_id).map(|e| e.base_damage);
+                    if let Some(base_damage) = base_damage {
+                        let mut npcs = self.npcs.write().await;
+                        if let Some(npc) = npcs.get_mut(npc_id) {
+                            npc.stats.damage = (base_damage as f32 * multiplier.max(1.0)).round() as i32;
+                        }
+                    }
+                    tracing::info!("Boss {} enraged (x{})", npc_id, multiplier);
+                    self.send_boss_yell(npc_id, &snapshot.name, &format!("{} becomes enraged!", snapshot.name), now).await;
+                }
+                BossAction::SetPhase { phase, name } => {
+                    tracing::info!("Boss {} entered phase {} ({:?})", npc_id, phase, name);
+                }
+                BossAction::Say { text } => {
+                    self.send_boss_yell(npc_id, &snapshot.name, &text, now).await;
+                }
+                BossAction::SetThreat { player_id, amount } => {
+                    let mut npcs = self.npcs.write().await;
+                    if let Some(npc) = npcs.get_mut(npc_id) {
+                        npc.threat.set(&player_id, amount);
+                    }
+                }
+            }
+        }
+    }
+
+    async fn send_boss_yell(&self, npc_id: &str, name: &str, text: &str, now: u64) {
+        self.broadcast(ServerMessage::ChatMessage {
+            sender_id: npc_id.to_string(),
+            sender_name: name.to_string(),
+            text: text.to_string(),
+            timestamp: now,
+            channel: None,
+        }).await;
+    }
+
+    /// Advance boss encounters: resets, HP thresholds, ticks, telegraphs and health bar updates
+    async fn update_bosses(&self, now: u64) {
+        let boss_ids: Vec<(String, String)> = self.boss_encounters.read().await
+            .values()
+            .map(|e| (e.npc_id.clone(), e.prototype_id.clone()))
+            .collect();
+
+        for (npc_id, prototype_id) in boss_ids {
+            let status = {
+                let npcs = self.npcs.read().await;
+                npcs.get(&npc_id).map(|n| (n.is_alive(), n.state, n.hp, n.max_hp))
+            };
+            // A boss that is gone, or back at full health out of combat, resets the fight
+            let hp_percent = match status {
+                Some((true, state, hp, max_hp)) => {
+                    if matches!(state, NpcState::Idle | NpcState::Wandering) && hp >= max_hp {
+                        self.end_boss_encounter(&npc_id, false).await;
+                        continue;
+                    }
+                    (hp as i64 * 100 / max_hp.max(1) as i64) as i32
+                }
+                _ => {
+                    self.end_boss_encounter(&npc_id, false).await;
+                    continue;
+                }
+            };
+            let tick_interval_ms = self.entity_registry.get(&prototype_id)
+                .and_then(|p| p.boss.as_ref())
+                .map(|b| b.tick_interval_ms)
+                .unwrap_or(1000);
+
+            let (thresholds, tick_due, telegraphs) = {
+                let mut encounters = self.boss_encounters.write().await;
+                let Some(encounter) = encounters.get_mut(&npc_id) else {
+                    continue;
+                };
+                (
+                    encounter.take_due_thresholds(hp_percent),
+                    encounter.tick_due(now, tick_interval_ms),
+                    encounter.take_due_telegraphs(now),
+                )
+            };
+            for threshold in thresholds {
+                self.run_boss_hook(&npc_id, "on_hp_threshold", Some(threshold), now).await;
+            }
+            if tick_due {
+                self.run_boss_hook(&npc_id, "on_tick", None, now).await;
+            }
+            for telegraph in telegraphs {
+                self.resolve_boss_telegraph(&npc_id, telegraph, now).await;
+            }
+            self.send_boss_update(&npc_id, false).await;
+        }
+
+        // Remove killed adds once their death animation has played
+        let removed: Vec<String> = {
+            let mut npcs = self.npcs.write().await;
+            let dead: Vec<String> = npcs.values()
+                .filter(|n| n.summoned_by.is_some() && !n.is_alive())
+                .filter(|n| now.saturating_sub(n.death_time) >= TEMPORARY_NPC_LINGER_MS)
+                .map(|n| n.id.clone())
+                .collect();
+            for npc_id in &dead {
+                npcs.remove(npc_id);
+            }
+            dead
+        };
+        for npc_id in removed {
+            self.broadcast(ServerMessage::NpcDespawned { id: npc_id }).await;
+        }
+    }
+
+    /// Damage every overworld player standing on a telegraphed tile
+    async fn resolve_boss_telegraph(&self, npc_id: &str, telegraph: PendingTelegraph, now: u64) {
+        let in_instances: std::collections::HashSet<String> = self.player_instances.read().await.keys().cloned().collect();
+        let hits: Vec<(String, i32, f32, f32, bool)> = {
+            let mut players = self.players.write().await;
+            players.values_mut()
+                .filter(|p| p.active && !p.is_dead && !p.is_god_mode && !in_instances.contains(&p.id))
+                .filter(|p| telegraph.tiles.contains(&(p.x, p.y)))
+                .map(|p| {
+                    p.hp = (p.hp - telegraph.damage).max(0);
+                    let died = p.hp <= 0;
+                    if died {
+                        p.die(now);
+                    }
+                    (p.id.clone(), p.hp, p.x as f32, p.y as f32, died)
+                })
+                .collect()
+        };
+
+        for (target_id, target_hp, target_x, target_y, died) in hits {
+            self.broadcast(ServerMessage::DamageEvent {
+                source_id: npc_id.to_string(),
+                target_id: target_id.clone(),
+                damage: telegraph.damage,
+                target_hp,
+                target_x,
+                target_y,
+                projectile: None,
+            }).await;
+            if died {
+                tracing::info!("Boss {} killed player {} with an area attack", npc_id, target_id);
+                self.broadcast(ServerMessage::PlayerDied {
+                    id: target_id.clone(),
+                    killer_id: npc_id.to_string(),
+                }).await;
+                self.apply_death_penalty(&target_id, now).await;
+            }
+        }
+    }
+
+    /// Broadcast the boss health bar, only if something changed unless `force` is set
+    async fn send_boss_update(&self, npc_id: &str, force: bool) {
+        let (phase, phase_name, enraged, leaderboard) = {
+            let mut encounters = self.boss_encounters.write().await;
+            let Some(encounter) = encounters.get_mut(npc_id) else {
+                return;
+            };
+            if !encounter.dirty && !force {
+                return;
+            }
+            encounter.dirty = false;
+            let mut leaderboard = encounter.leaderboard();
+            leaderboard.truncate(5);
+            (encounter.phase, encounter.phase_name.clone(), encounter.enraged, leaderboard)
+        };
+        let Some((name, hp, max_hp)) = ({
+            let npcs = self.npcs.read().await;
+            npcs.get(npc_id).map(|n| (n.stats.display_name.clone(), n.hp, n.max_hp))
+        }) else {
+            return;
+        };
+
+        let mut entries = Vec::new();
+        for (player_id, damage) in leaderboard {
+            let name = self.get_player_name(&player_id).await.unwrap_or_else(|| "Unknown".to_string());
+            entries.push(BossDamageData { name, damage });
+        }
+        self.broadcast(ServerMessage::BossUpdate {
+            boss_id: npc_id.to_string(),
+            name,
+            hp,
+            max_hp,
+            phase,
+            phase_name,
+            enraged,
+            leaderboard: entries,
+        }).await;
+    }
+
+    /// Run the death hook and end the encounter, returning the players eligible for loot
+    /// (None if the NPC was not a boss in an encounter)
+    async fn finish_boss_kill(&self, npc_id: &str, now: u64) -> Option<Vec<String>> {
+        if !self.boss_encounters.read().await.contains_key(npc_id) {
+            return None;
+        }
+        self.run_boss_hook(npc_id, "on_death", None, now).await;
+        self.send_boss_update(npc_id, true).await;
+        self.end_boss_encounter(npc_id, true).await
+    }
+
+    /// End an encounter, restoring the boss's damage. A reset also despawns its adds.
+    async fn end_boss_encounter(&self, npc_id: &str, defeated: bool) -> Option<Vec<String>> {
+        let encounter = self.boss_encounters.write().await.remove(npc_id)?;
+        {
+            let mut npcs = self.npcs.write().await;
+            if let Some(npc) = npcs.get_mut(npc_id) {
+                npc.stats.damage = encounter.base_damage;
+            }
+        }
+        self.broadcast(ServerMessage::BossEnded {
+            boss_id: npc_id.to_string(),
+            defeated,
+        }).await;
+
+        if !defeated {
+            let adds: Vec<String> = {
+                let mut npcs = self.npcs.write().await;
+                let adds: Vec<String> = npcs.values()
+                    .filter(|n| n.summoned_by.as_deref() == Some(npc_id))
+                    .map(|n| n.id.clone())
+                    .collect();
+                for add_id in &adds {
+                    npcs.remove(add_id);
+                }
+                adds
+            };
+            for add_id in adds {
+                self.broadcast(ServerMessage::NpcDespawned { id: add_id }).await;
+            }
+            tracing::info!("Boss encounter with {} reset", npc_id);
+            return None;
+        }
+
+        let min_damage_percent = self.entity_registry.get(&encounter.prototype_id)
+            .and_then(|p| p.boss.as_ref())
+            .map(|b| b.min_damage_percent)
+            .unwrap_or(0.0);
+        let eligible = encounter.loot_eligible(min_damage_percent);
+        let total: i32 = encounter.damage.values().sum();
+        for (rank, (player_id, damage)) in encounter.leaderboard().iter().enumerate() {
+            let text = if eligible.contains(player_id) {
+                format!("Boss defeated! You ranked #{} with {} damage and earned a share of the loot.", rank + 1, damage)
+            } else {
+                format!(
+                    "Boss defeated! You ranked #{} with {} damage - not enough for loot (need {}% of {}).",
+                    rank + 1, damage, min_damage_percent, total
+                )
+            };
+            self.send_system_message(player_id, &text).await;
+        }
+        tracing::info!("Boss {} defeated, {} players eligible for loot", npc_id, eligible.len());
+        Some(eligible)
+    }
+
+    // ========================================================================
+    // Pets
+    // ========================================================================
+
+    /// Initialize pet levels for a player (called on join) and bring back the active pet
+    pub async fn set_player_pets(&self, player_id: &str, pets: PlayerPets) {
+        let active = pets.active.clone();
+        self.player_pets.write().await.insert(player_id.to_string(), pets);
+        if let Some(pet_id) = active {
+            self.summon_pet(player_id, &pet_id).await;
+        }
+    }
+
+    /// Get a player's pets (for saving)
+    pub async fn get_player_pets(&self, player_id: &str) -> Option<PlayerPets> {
+        self.player_pets.read().await.get(player_id).cloned()
+    }
+
+    /// Summon a pet, or dismiss it if it is the one already out
+    async fn toggle_pet(&self, player_id: &str, pet_id: &str) {
+        let current = self.pets.read().await.get(player_id).map(|p| p.pet_id.clone());
+        self.despawn_pet(player_id).await;
+
+        if current.as_deref() == Some(pet_id) {
+            if let Some(pets) = self.player_pets.write().await.get_mut(player_id) {
+                pets.active = None;
+            }
+            let name = self.entity_registry.get(pet_id).map(|p| p.display_name.clone()).unwrap_or_default();
+            self.send_system_message(player_id, &format!("You dismiss your {}.", name)).await;
+            return;
+        }
+
+        if let Some(level) = self.summon_pet(player_id, pet_id).await {
+            let name = self.entity_registry.get(pet_id).map(|p| p.display_name.clone()).unwrap_or_default();
+            self.send_system_message(player_id, &format!("Your {} (Lv.{}) is now following you.", name, level)).await;
+        }
+    }
+
+    /// Spawn a player's pet next to them, returning its level
+    async fn summon_pet(&self, player_id: &str, pet_id: &str) -> Option<i32> {
+        let Some(prototype) = self.entity_registry.get(pet_id).filter(|p| p.is_pet()) else {
+            tracing::warn!("Player {} tried to summon unknown pet {}", player_id, pet_id);
+            return None;
+        };
+        let (owner_name, x, y) = {
+            let players = self.players.read().await;
+            let player = players.get(player_id)?;
+            (player.name.clone(), player.x, player.y)
+        };
+        let instance_id = self.player_instances.read().await.get(player_id).cloned();
+
+        let level = {
+            let mut all_pets = self.player_pets.write().await;
+            let pets = all_pets.entry(player_id.to_string()).or_default();
+            pets.active = Some(pet_id.to_string());
+            pets.owned.entry(pet_id.to_string()).or_default().level
+        };
+
+        let npc = pet::create_pet_npc(player_id, &owner_name, prototype, level, x, y);
+        let assist = prototype.pet.as_ref().map(|c| c.assist).unwrap_or(true);
+        self.pets.write().await.insert(player_id.to_string(), SummonedPet {
+            pet_id: pet_id.to_string(),
+            npc,
+            instance_id,
+            assist,
+        });
+        tracing::info!("Player {} summoned pet {} (level {})", player_id, pet_id, level);
+        Some(level)
+    }
+
+    /// Remove a player's pet from the world (its saved state is untouched)
+    async fn despawn_pet(&self, player_id: &str) {
+        let removed = self.pets.write().await.remove(player_id);
+        if let Some(pet) = removed {
+            self.broadcast(ServerMessage::NpcDespawned { id: pet.npc.id }).await;
+        }
+    }
+
+    /// Give experience to a player's active pet, scaling it up on level-up
+    async fn award_pet_exp(&self, player_id: &str, amount: i64) {
+        let Some(pet_id) = self.pets.read().await.get(player_id).map(|p| p.pet_id.clone()) else {
+            return;
+        };
+        let Some(prototype) = self.entity_registry.get(&pet_id) else {
+            return;
+        };
+        let config = prototype.pet.clone().unwrap_or_default();
+
+        let new_level = {
+            let mut all_pets = self.player_pets.write().await;
+            all_pets.entry(player_id.to_string()).or_default().add_exp(&pet_id, amount, &config)
+        };
+        let Some(level) = new_level else {
+            return;
+        };
+
+        if let Some(summoned) = self.pets.write().await.get_mut(player_id) {
+            pet::apply_pet_level(&mut summoned.npc, prototype, level);
+        }
+        self.send_system_message(player_id, &format!("Your {} reached level {}!", prototype.display_name, level)).await;
+    }
+
+    /// Move pets after their owners and let them attack their owners' targets
+    async fn update_pets(&self, now: u64, nav_grid: &NavGrid) {
+        if self.pets.read().await.is_empty() {
+            return;
+        }
+
+        // Owner position, death state and current target
+        let owners: HashMap<String, (i32, i32, bool, Option<String>)> = {
+            let players = self.players.read().await;
+            let pets = self.pets.read().await;
+            pets.keys()
+                .filter_map(|owner_id| {
+                    players.get(owner_id)
+                        .map(|p| (owner_id.clone(), (p.x, p.y, p.is_dead, p.target_id.clone())))
+                })
+                .collect()
+        };
+        let owner_instances: HashMap<String, String> = {
+            let instances = self.player_instances.read().await;
+            owners.keys()
+                .filter_map(|id| instances.get(id).map(|inst| (id.clone(), inst.clone())))
+                .collect()
+        };
+        // Overworld NPCs a pet could attack, and tiles they block
+        let (targets, npc_tiles) = {
+            let npcs = self.npcs.read().await;
+            let targets: HashMap<String, (i32, i32, i32)> = npcs.values()
+                .filter(|n| n.is_alive() && n.is_attackable())
+                .map(|n| (n.id.clone(), (n.x, n.y, n.hp)))
+                .collect();
+            let tiles: std::collections::HashSet<(i32, i32)> = npcs.values().filter(|n| n.is_alive()).map(|n| (n.x, n.y)).collect();
+            (targets, tiles)
+        };
+        let npc_tile_list: Vec<(i32, i32)> = npc_tiles.iter().copied().collect();
+        // Instances pet owners are in, with the tiles their NPCs block
+        let mut owner_maps = HashMap::new();
+        if !owner_instances.is_empty() {
+            for instance in self.instance_manager.occupied_instances().await {
+                let tiles: Vec<(i32, i32)> = instance.npcs.read().await.values()
+                    .filter(|n| n.is_alive())
+                    .map(|n| (n.x, n.y))
+                    .collect();
+                owner_maps.insert(instance.id.clone(), (instance, tiles));
+            }
+        }
+
+        // (owner_id, pet entity ID, target NPC ID, pet level, max hit)
+        let mut pet_attacks: Vec<(String, String, String, i32, i32)> = Vec::new();
+        // Pets that moved to another map, to be removed from the old map's clients
+        let mut changed_map: Vec<String> = Vec::new();
+        {
+            let mut pets = self.pets.write().await;
+            let mut paths = PathContext::new(nav_grid, MAX_NODES_PER_TICK);
+            for (owner_id, summoned) in pets.iter_mut() {
+                let Some((ox, oy, owner_dead, owner_target)) = owners.get(owner_id) else {
+                    continue;
+                };
+                let owner_instance = owner_instances.get(owner_id).cloned();
+                let in_overworld = owner_instance.is_none();
+                let npc = &mut summoned.npc;
+                let owner_dist = (npc.x - ox).abs().max((npc.y - oy).abs());
+
+                // Catch up with owners who changed map or got too far ahead
+                if summoned.instance_id != owner_instance || owner_dist > PET_TELEPORT_DISTANCE {
+                    if summoned.instance_id != owner_instance {
+                        changed_map.push(npc.id.clone());
+                    }
+                    summoned.instance_id = owner_instance;
+                    npc.x = *ox;
+                    npc.y = *oy;
+                    npc.state = NpcState::Idle;
+                    npc.target_id = None;
+                    continue;
+                }
+
+                let target = owner_target.as_ref()
+                    .filter(|_| summoned.assist && in_overworld && !owner_dead)
+                    .and_then(|id| targets.get(id).map(|t| (id.clone(), *t)))
+                    .filter(|(_, (tx, ty, _))| (tx - ox).abs().max((ty - oy).abs()) <= PET_ASSIST_RANGE);
+
+                match target {
+                    Some((target_id, (tx, ty, hp))) => {
+                        if npc.target_id.as_deref() != Some(target_id.as_str()) {
+                            npc.target_id = Some(target_id.clone());
+                            npc.state = NpcState::Chasing;
+                        }
+                        // Chase range is measured from the owner rather than a spawn point
+                        npc.spawn_x = *ox;
+                        npc.spawn_y = *oy;
+                        let occupied: Vec<(i32, i32)> = npc_tiles.iter()
+                            .filter(|tile| **tile != (tx, ty))
+                            .copied()
+                            .collect();
+                        let target_list = [(target_id, tx, ty, hp, npc.level)];
+                        if let Some((hit_id, max_hit)) = npc.update(1.0 / TICK_RATE, &target_list, &occupied, now, &mut paths) {
+                            pet_attacks.push((owner_id.clone(), npc.id.clone(), hit_id, npc.level, max_hit));
+                        }
+                        // Following takes over from the NPC return-to-spawn logic
+                        if npc.state == NpcState::Returning {
+                            npc.state = NpcState::Idle;
+                            npc.target_id = None;
+                        }
+                    }
+                    None => {
+                        npc.state = NpcState::Idle;
+                        npc.target_id = None;
+                        npc.just_attacked = false;
+                        if owner_dist <= PET_FOLLOW_DISTANCE {
+                            continue;
+                        }
+                        // Path around walls and monsters; a boxed-in pet hops over to its owner
+                        let followed = match summoned.instance_id.as_ref() {
+                            None => npc.follow((*ox, *oy), PET_FOLLOW_DISTANCE, now, &npc_tile_list, &mut paths),
+                            Some(instance_id) => match owner_maps.get(instance_id)
+                                .and_then(|(instance, tiles)| self.instance_map(instance).map(|map| (instance.nav_grid(map), tiles)))
+                            {
+                                Some((grid, tiles)) => {
+                                    let mut instance_paths = PathContext::new(grid, MAX_NODES_PER_TICK);
+                                    npc.follow((*ox, *oy), PET_FOLLOW_DISTANCE, now, tiles, &mut instance_paths)
+                                }
+                                None => false,
+                            },
+                        };
+                        if !followed {
+                            npc.x = *ox;
+                            npc.y = *oy;
+                            npc.path = None;
+                            npc.last_move_time = now;
+                        }
+                    }
+                }
+            }
+        }
+
+        for id in changed_map {
+            self.broadcast(ServerMessage::NpcDespawned { id }).await;
+        }
+
+        // Pet attacks use NPC hit/miss rolls against the target's level
+        for (owner_id, pet_npc_id, target_id, pet_level, max_hit) in pet_attacks {
+            let result = {
+                let mut npcs = self.npcs.write().await;
+                let Some(target) = npcs.get_mut(&target_id).filter(|n| n.is_alive()) else {
+                    continue;
+                };
+                let damage = if calculate_hit(pet_level, 0, target.level, 0) {
+                    roll_damage(max_hit)
+                } else {
+                    0
+                };
+                let died = damage > 0 && target.take_damage(damage, now);
+                (damage, target.hp, target.x, target.y, died)
+            };
+            let (damage, target_hp, target_x, target_y, died) = result;
+
+            self.broadcast(ServerMessage::DamageEvent {
+                source_id: pet_npc_id,
+                target_id: target_id.clone(),
+                damage,
+                target_hp,
+                target_x: target_x as f32,
+                target_y: target_y as f32,
+                projectile: None,
+            }).await;
+
+            // Pet damage counts as the owner's
+            self.add_damage_threat(&target_id, &owner_id, damage).await;
+            if damage > 0 {
+                self.record_event_contribution(&owner_id, &target_id, damage).await;
+                self.record_boss_damage(&owner_id, &target_id, damage).await;
+            }
+            if died {
+                tracing::info!("Pet of {} killed {}", owner_id, target_id);
+                self.handle_npc_kill(&owner_id, &target_id, target_x as f32, target_y as f32).await;
+            }
+        }
+    }
+
+    // ========================================================================
+    // Housing
+    // ========================================================================
+
+    /// Initialize a player's house (called on join)
+    pub async fn set_player_house(&self, player_id: &str, house: PlayerHouse) {
+        self.player_houses.write().await.insert(player_id.to_string(), house);
+    }
+
+    /// Get a player's house (for saving)
+    pub async fn get_player_house(&self, player_id: &str) -> Option<PlayerHouse> {
+        self.player_houses.read().await.get(player_id).cloned()
+    }
+
+    pub fn get_house_definition(&self, house_id: &str) -> Option<HouseDefinition> {
+        self.house_registry.get(house_id).cloned()
+    }
+
+    /// Work out whose house a player goes into through a house door: the house
+    /// they asked to visit, otherwise their own. Returns (owner ID, house ID),
+    /// or tells the player why they can't go in.
+    pub async fn resolve_house_entry(&self, player_id: &str) -> Option<(String, String)> {
+        let owner_id = self.house_visits.write().await.remove(player_id)
+            .unwrap_or_else(|| player_id.to_string());
+        let visitor_name = self.get_player_name(player_id).await.unwrap_or_default();
+        let owner_online = self.players.read().await.get(&owner_id).map(|p| p.active).unwrap_or(false);
+
+        let (house_id, invited) = {
+            let houses = self.player_houses.read().await;
+            match houses.get(&owner_id) {
+                Some(house) => (house.house_id.clone(), house.is_guest(&visitor_name)),
+                None => (None, false),
+            }
+        };
+
+        if owner_id == player_id {
+            if house_id.is_none() {
+                self.send_system_message(player_id, "You don't own a house. See /house list to buy one.").await;
+            }
+            return house_id.map(|id| (owner_id, id));
+        }
+
+        match house_id {
+            Some(id) if owner_online && invited => Some((owner_id, id)),
+            Some(_) if owner_online => {
+                self.send_system_message(player_id, "You are no longer invited to that house.").await;
+                None
+            }
+            _ => {
+                self.send_system_message(player_id, "That house is locked.").await;
+                None
+            }
+        }
+    }
+
+    /// Owner ID and instance of the house a player is in, if they are in one
+    async fn current_house(&self, player_id: &str) -> Option<(String, Arc<crate::instance::Instance>)> {
+        let instance = self.instance_manager.find_player_instance(player_id).await?;
+        if !instance.map_id.starts_with(HOUSE_MAP_PREFIX) {
+            return None;
+        }
+        let owner_id = instance.owner_id.clone()?;
+        Some((owner_id, instance))
+    }
+
+    /// Tile in front of a player
+    async fn get_facing_tile(&self, player_id: &str) -> Option<(i32, i32)> {
+        let players = self.players.read().await;
+        players.get(player_id).map(|p| {
+            let (dx, dy) = p.direction.offset();
+            (p.x + dx, p.y + dy)
+        })
+    }
+
+    async fn house_furniture_message(&self, owner_id: &str) -> ServerMessage {
+        let owner_name = self.get_player_name(owner_id).await.unwrap_or_default();
+        let houses = self.player_houses.read().await;
+        let furniture = houses.get(owner_id)
+            .map(|house| {
+                house.furniture.iter().filter_map(|f| {
+                    let stats = self.item_registry.get(&f.item_id)?.furniture.as_ref()?;
+                    Some(FurnitureData {
+                        id: f.id,
+                        item_id: f.item_id.clone(),
+                        gid: stats.gid,
+                        x: f.x,
+                        y: f.y,
+                        width: stats.width,
+                        height: stats.height,
+                        storage: f.is_storage(),
+                    })
+                }).collect()
+            })
+            .unwrap_or_default();
+        ServerMessage::HouseFurniture { owner_name, furniture }
+    }
+
+    /// Send the furniture of a house to a player who just walked in
+    pub async fn send_house_furniture(&self, player_id: &str, owner_id: &str) {
+        let msg = self.house_furniture_message(owner_id).await;
+        self.send_to_player(player_id, msg).await;
+    }
+
+    /// Send the furniture of a house to everyone inside after it changed
+    async fn broadcast_house_furniture(&self, owner_id: &str, instance: &crate::instance::Instance) {
+        let msg = self.house_furniture_message(owner_id).await;
+        for id in instance.get_player_ids().await {
+            self.send_to_player(&id, msg.clone()).await;
+        }
+    }
+
+    /// /house list | buy <id> | invite <name> | uninvite <name> | guests | visit <name> | pickup
+    async fn handle_house_command(&self, player_id: &str, parts: &[&str]) {
+        let sub = parts.get(1).map(|s| s.to_lowercase()).unwrap_or_default();
+        let arg = parts.get(2).copied();
+
+        match (sub.as_str(), arg) {
+            ("list", _) => {
+                let owned = self.player_houses.read().await.get(player_id).and_then(|h| h.house_id.clone());
+                for house in self.house_registry.all() {
+                    let marker = if owned.as_deref() == Some(house.id.as_str()) { " (yours)" } else { "" };
+                    self.send_system_message(player_id, &format!(
+                        "{} [{}] - {} gold, up to {} furniture{}",
+                        house.display_name, house.id, house.price, house.max_furniture, marker,
+                    )).await;
+                }
+            }
+            ("buy", Some(house_id)) => self.buy_house(player_id, house_id).await,
+            ("invite", Some(name)) => {
+                let own_name = self.get_player_name(player_id).await.unwrap_or_default();
+                if name.eq_ignore_ascii_case(&own_name) {
+                    self.send_system_message(player_id, "You can always enter your own house.").await;
+                    return;
+                }
+                let result = {
+                    let mut houses = self.player_houses.write().await;
+                    match houses.get_mut(player_id) {
+                        Some(house) if house.house_id.is_none() => Err("You don't own a house."),
+                        Some(house) if house.is_guest(name) => Err("They are already invited."),
+                        Some(house) if house.guests.len() >= MAX_GUESTS => Err("Your guest list is full."),
+                        Some(house) => {
+                            house.guests.push(name.to_string());
+                            Ok(())
+                        }
+                        None => return,
+                    }
+                };
+                match result {
+                    Ok(()) => {
+                        self.send_system_message(player_id, &format!("{} can now visit your house.", name)).await;
+                        if let Some(guest_id) = self.find_active_player_by_name(name).await {
+                            self.send_system_message(&guest_id, &format!(
+                                "{} invited you to their house. Use /house visit {} and a house door to go in.",
+                                own_name, own_name,
+                            )).await;
+                        }
+                    }
+                    Err(message) => self.send_system_message(player_id, message).await,
+                }
+            }
+            ("uninvite", Some(name)) => {
+                let removed = {
+                    let mut houses = self.player_houses.write().await;
+                    match houses.get_mut(player_id) {
+                        Some(house) => {
+                            let before = house.guests.len();
+                            house.guests.retain(|g| !g.eq_ignore_ascii_case(name));
+                            house.guests.len() < before
+                        }
+                        None => false,
+                    }
+                };
+                let message = if removed {
+                    format!("{} can no longer visit your house.", name)
+                } else {
+                    format!("{} isn't on your guest list.", name)
+                };
+                self.send_system_message(player_id, &message).await;
+            }
+            ("guests", _) => {
+                let guests = self.player_houses.read().await.get(player_id)
+                    .map(|h| h.guests.join(", "))
+                    .unwrap_or_default();
+                if guests.is_empty() {
+                    self.send_system_message(player_id, "Nobody is on your guest list.").await;
+                } else {
+                    self.send_system_message(player_id, &format!("Guests: {}", guests)).await;
+                }
+            }
+            ("visit", Some(name)) => {
+                let Some(owner_id) = self.find_active_player_by_name(name).await else {
+                    self.send_system_message(player_id, "Player not found").await;
+                    return;
+                };
+                let visitor_name = self.get_player_name(player_id).await.unwrap_or_default();
+                let allowed = {
+                    let houses = self.player_houses.read().await;
+                    houses.get(&owner_id).map(|h| h.house_id.is_some() && h.is_guest(&visitor_name))
+                };
+                match allowed {
+                    Some(true) => {
+                        self.house_visits.write().await.insert(player_id.to_string(), owner_id);
+                        self.send_system_message(player_id, &format!("The next house door you use takes you to {}'s house.", name)).await;
+                    }
+                    _ => self.send_system_message(player_id, &format!("{} hasn't invited you to a house.", name)).await,
+                }
+            }
+            ("pickup", _) => self.pickup_furniture(player_id).await,
+            _ => {
+                self.send_system_message(player_id, "Usage: /house list | buy <id> | invite <name> | uninvite <name> | guests | visit <name> | pickup").await;
+            }
+        }
+    }
+
+    async fn buy_house(&self, player_id: &str, house_id: &str) {
+        let Some(definition) = self.house_registry.get(house_id) else {
+            self.send_system_message(player_id, &format!("Unknown house: {}. See /house list", house_id)).await;
+            return;
+        };
+
+        let result = {
+            let mut players = self.players.write().await;
+            let Some(player) = players.get_mut(player_id) else {
+                return;
+            };
+            let mut houses = self.player_houses.write().await;
+            let house = houses.entry(player_id.to_string()).or_default();
+            if house.house_id.is_some() {
+                Err("You already own a house.".to_string())
+            } else if player.inventory.gold < definition.price {
+                Err(format!("You need {} gold to buy the {}.", definition.price, definition.display_name))
+            } else {
+                player.inventory.gold -= definition.price;
+                house.house_id = Some(definition.id.clone());
+                Ok((player.inventory.to_update(), player.inventory.gold))
+            }
+        };
+
+        match result {
+            Ok((slots, gold)) => {
+                tracing::info!("Player {} bought house {}", player_id, definition.id);
+                self.send_to_player(player_id, ServerMessage::InventoryUpdate {
+                    player_id: player_id.to_string(),
+                    slots,
+                    gold,
+                }).await;
+                self.send_system_message(player_id, &format!(
+                    "You bought the {}! Use any house door to go inside.", definition.display_name,
+                )).await;
+            }
+            Err(message) => self.send_system_message(player_id, &message).await,
+        }
+    }
+
+    /// Place a furniture item from an inventory slot on the tile the player faces
+    async fn place_furniture(&self, player_id: &str, slot_index: u8, item_id: &str) {
+        let Some(stats) = self.item_registry.get(item_id).and_then(|def| def.furniture.clone()) else {
+            return;
+        };
+        let instance = match self.current_house(player_id).await {
+            Some((owner_id, instance)) if owner_id == player_id => instance,
+            _ => {
+                self.send_system_message(player_id, "Furniture can only be placed inside your own house.").await;
+                return;
+            }
+        };
+        let Some(map) = instance.generated_map.clone() else {
+            return;
+        };
+        let Some((x, y)) = self.get_facing_tile(player_id).await else {
+            return;
+        };
+
+        let result = {
+            let mut players = self.players.write().await;
+            let Some(player) = players.get_mut(player_id) else {
+                return;
+            };
+            let mut houses = self.player_houses.write().await;
+            let Some(house) = houses.get_mut(player_id) else {
+                return;
+            };
+            let max_furniture = house.house_id.as_deref()
+                .and_then(|id| self.house_registry.get(id))
+                .map(|h| h.max_furniture)
+                .unwrap_or(0);
+
+            match house.can_place(&map, x, y, max_furniture) {
+                Ok(()) => {
+                    // Take the item from the slot it was used from
+                    let Some(slot) = player.inventory.slots.get_mut(slot_index as usize) else {
+                        return;
+                    };
+                    match slot {
+                        Some(stack) if stack.item_id == item_id => {
+                            stack.quantity -= 1;
+                            if stack.quantity <= 0 {
+                                *slot = None;
+                            }
+                        }
+                        _ => return,
+                    }
+                    house.place(item_id, x, y, stats.storage_slots);
+                    Ok((player.inventory.to_update(), player.inventory.gold))
+                }
+                Err(message) => Err(message),
+            }
+        };
+
+        match result {
+            Ok((slots, gold)) => {
+                tracing::debug!("Player {} placed {} at ({}, {})", player_id, item_id, x, y);
+                self.send_to_player(player_id, ServerMessage::InventoryUpdate {
+                    player_id: player_id.to_string(),
+                    slots,
+                    gold,
+                }).await;
+                self.broadcast_house_furniture(player_id, &instance).await;
+            }
+            Err(message) => self.send_system_message(player_id, &message).await,
+        }
+    }
+
+    /// Return the furniture the player faces to their inventory
+    async fn pickup_furniture(&self, player_id: &str) {
+        let instance = match self.current_house(player_id).await {
+            Some((owner_id, instance)) if owner_id == player_id => instance,
+            _ => {
+                self.send_system_message(player_id, "You can only pick up furniture in your own house.").await;
+                return;
+            }
+        };
+        let Some((x, y)) = self.get_facing_tile(player_id).await else {
+            return;
+        };
+
+        let result = {
+            let mut players = self.players.write().await;
+            let Some(player) = players.get_mut(player_id) else {
+                return;
+            };
+            let mut houses = self.player_houses.write().await;
+            let Some(house) = houses.get_mut(player_id) else {
+                return;
+            };
+
+            match house.furniture_at(x, y).map(|f| (f.id, f.item_id.clone(), f.is_empty())) {
+                None => Err("There is no furniture in front of you."),
+                Some((_, _, false)) => Err("Empty it before picking it up."),
+                Some((_, item_id, _)) if !player.inventory.has_space_for(&item_id, 1, &self.item_registry) => {
+                    Err("Your inventory is full.")
+                }
+                Some((id, item_id, _)) => {
+                    house.remove(id);
+                    player.inventory.add_item(&item_id, 1, &self.item_registry);
+                    Ok((player.inventory.to_update(), player.inventory.gold))
+                }
+            }
+        };
+
+        match result {
+            Ok((slots, gold)) => {
+                self.send_to_player(player_id, ServerMessage::InventoryUpdate {
+                    player_id: player_id.to_string(),
+                    slots,
+                    gold,
+                }).await;
+                self.broadcast_house_furniture(player_id, &instance).await;
+            }
+            Err(message) => self.send_system_message(player_id, message).await,
+        }
+    }
+
+    /// Whether a player can use a chest: it must be in their own house and next to them
+    async fn storage_in_reach(&self, player_id: &str, furniture_id: i32) -> bool {
+        match self.current_house(player_id).await {
+            Some((owner_id, _)) if owner_id == player_id => {}
+            _ => return false,
+        }
+        let Some((px, py)) = self.get_player_position(player_id).await else {
+            return false;
+        };
+        let houses = self.player_houses.read().await;
+        houses.get(player_id)
+            .and_then(|house| house.furniture.iter().find(|f| f.id == furniture_id))
+            .map(|f| f.is_storage() && (f.x - px).abs() <= 1 && (f.y - py).abs() <= 1)
+            .unwrap_or(false)
+    }
+
+    async fn send_storage_contents(&self, player_id: &str, furniture_id: i32) {
+        let msg = {
+            let houses = self.player_houses.read().await;
+            let Some(chest) = houses.get(player_id)
+                .and_then(|house| house.furniture.iter().find(|f| f.id == furniture_id)) else {
+                return;
+            };
+            let name = self.item_registry.get(&chest.item_id)
+                .map(|def| def.display_name.clone())
+                .unwrap_or_else(|| chest.item_id.clone());
+            let slots = chest.storage.iter()
+                .enumerate()
+                .filter_map(|(i, slot)| slot.as_ref().map(|s| item::InventorySlotUpdate {
+                    slot: i as u8,
+                    item_id: s.item_id.clone(),
+                    quantity: s.quantity,
+                }))
+                .collect();
+            ServerMessage::StorageContents {
+                furniture_id,
+                name,
+                capacity: chest.storage.len() as i32,
+                slots,
+            }
+        };
+        self.send_to_player(player_id, msg).await;
+    }
+
+    pub async fn handle_storage_open(&self, player_id: &str, furniture_id: i32) {
+        if !self.storage_in_reach(player_id, furniture_id).await {
+            return;
+        }
+        self.send_storage_contents(player_id, furniture_id).await;
+    }
+
+    pub async fn handle_storage_deposit(&self, player_id: &str, furniture_id: i32, slot_index: u8, quantity: i32) {
+        if !self.storage_in_reach(player_id, furniture_id).await {
+            return;
+        }
+
+        let result = {
+            let mut players = self.players.write().await;
+            let player = match players.get_mut(player_id) {
+                Some(p) if p.active && !p.is_dead => p,
+                _ => return,
+            };
+            let mut houses = self.player_houses.write().await;
+            let Some(chest) = houses.get_mut(player_id).and_then(|house| house.furniture_mut(furniture_id)) else {
+                return;
+            };
+            let Some(slot) = player.inventory.slots.get_mut(slot_index as usize) else {
+                return;
+            };
+            let Some(stack) = slot.as_mut() else {
+                return;
+            };
+
+            let quantity = quantity.clamp(1, stack.quantity.max(1));
+            let max_stack = self.item_registry.get(&stack.item_id)
+                .map(|def| def.max_stack)
+                .unwrap_or(item::DEFAULT_MAX_STACK);
+            let moved = quantity - chest.deposit(&stack.item_id, quantity, max_stack);
+            if moved > 0 {
+                stack.quantity -= moved;
+                if stack.quantity <= 0 {
+                    *slot = None;
+                }
+                Some((player.inventory.to_update(), player.inventory.gold))
+            } else {
+                None
+            }
+        };
+
+        match result {
+            Some((slots, gold)) => {
+                self.send_to_player(player_id, ServerMessage::InventoryUpdate {
+                    player_id: player_id.to_string(),
+                    slots,
+                    gold,
+                }).await;
+                self.send_storage_contents(player_id, furniture_id).await;
+            }
+            None => self.send_system_message(player_id, "It's full.").await,
+        }
+    }
+
+    pub async fn handle_storage_withdraw(&self, player_id: &str, furniture_id: i32, storage_slot: u8) {
+        if !self.storage_in_reach(player_id, furniture_id).await {
+            return;
+        }
+
+        let result = {
+            let mut players = self.players.write().await;
+            let player = match players.get_mut(player_id) {
+                Some(p) if p.active && !p.is_dead => p,
+                _ => return,
+            };
+            let mut houses = self.player_houses.write().await;
+            let Some(chest) = houses.get_mut(player_id).and_then(|house| house.furniture_mut(furniture_id)) else {
+                return;
+            };
+            let Some(slot) = chest.storage.get_mut(storage_slot as usize) else {
+                return;
+            };
+            let Some(stack) = slot.as_mut() else {
+                return;
+            };
+
+            let leftover = player.inventory.add_item(&stack.item_id, stack.quantity, &self.item_registry);
+            if leftover == stack.quantity {
+                None
+            } else {
+                stack.quantity = leftover;
+                if stack.quantity <= 0 {
+                    *slot = None;
+                }
+                Some((player.inventory.to_update(), player.inventory.gold))
+            }
+        };
+
+        match result {
+            Some((slots, gold)) => {
+                self.send_to_player(player_id, ServerMessage::InventoryUpdate {
+                    player_id: player_id.to_string(),
+                    slots,
+                    gold,
+                }).await;
+                self.send_storage_contents(player_id, furniture_id).await;
+            }
+            None => self.send_system_message(player_id, "Inventory full").await,
+        }
+    }
+
+    // ========================================================================
+    // Threat
+    // ========================================================================
+
+    /// Build threat on an NPC for damage a player dealt (a miss still pulls it)
+    async fn add_damage_threat(&self, npc_id: &str, player_id: &str, damage: i32) {
+        let Some((x, y)) = self.players.read().await.get(player_id).map(|p| (p.x, p.y)) else {
+            return;
+        };
+        let threat = damage.max(1) as f32 * DAMAGE_THREAT;
+        let npc_map = self.npcs_near(player_id).await;
+        let mut npcs = self.npc_lock(&npc_map).write().await;
+        let Some(npc) = npcs.get_mut(npc_id) else {
+            return;
+        };
+        npc.add_damage_threat(player_id, threat, x, y);
+
+        // Monsters that call for help pull in nearby members of their family
+        let Some(radius) = npc.stats.profiles.help_radius() else {
+            return;
+        };
+        let (family, npc_x, npc_y) = (npc.stats.family.clone(), npc.x, npc.y);
+        for ally in npcs.values_mut() {
+            if ally.id != npc_id && ally.stats.family == family
+                && (ally.x - npc_x).abs().max((ally.y - npc_y).abs()) <= radius
+            {
+                ally.add_damage_threat(player_id, threat, x, y);
+            }
+        }
+    }
+
+    /// Healing draws threat from every NPC already fighting the healer
+    async fn add_heal_threat(&self, player_id: &str, healed: i32) {
+        if healed <= 0 {
+            return;
+        }
+        let npc_map = self.npcs_near(player_id).await;
+        let mut npcs = self.npc_lock(&npc_map).write().await;
+        for npc in npcs.values_mut() {
+            if npc.threat.contains(player_id) {
+                npc.add_threat(player_id, healed as f32 * HEAL_THREAT);
+            }
+        }
+    }
+
+    // ========================================================================
+    // Status Effects
+    // ========================================================================
+
+    /// Send everyone a player's current status effects
+    async fn broadcast_status_effects(&self, player_id: &str, current_time: u64) {
+        let effects: Vec<StatusEffectData> = {
+            let players = self.players.read().await;
+            let Some(player) = players.get(player_id) else {
+                return;
+            };
+            player.status_effects.iter()
+                .map(|effect| StatusEffectData {
+                    kind: effect.kind.as_str().to_string(),
+                    remaining_ms: effect.expires_at.saturating_sub(current_time),
+                    stacks: effect.stacks,
+                })
+                .collect()
+        };
+        self.broadcast(ServerMessage::StatusEffects {
+            player_id: player_id.to_string(),
+            effects,
+        }).await;
+    }
+
+    /// Apply poison and bleed damage and drop effects that wore off
+    async fn update_status_effects(&self, current_time: u64) {
+        let mut damage_events = Vec::new();
+        let mut deaths = Vec::new();
+        let mut expired = Vec::new();
+        {
+            let mut players = self.players.write().await;
+            for player in players.values_mut() {
+                if player.is_dead || player.status_effects.is_empty() {
+                    continue;
+                }
+                let (ticks, any_expired) = player.status_effects.tick(current_time);
+                for tick in ticks {
+                    // God mode prevents all damage
+                    if player.is_dead || player.is_god_mode {
+                        break;
+                    }
+                    player.hp = (player.hp - tick.damage).max(0);
+                    damage_events.push(ServerMessage::DamageEvent {
+                        source_id: tick.source_id.clone(),
+                        target_id: player.id.clone(),
+                        damage: tick.damage,
+                        target_hp: player.hp,
+                        target_x: player.x as f32,
+                        target_y: player.y as f32,
+                        projectile: None,
+                    });
+                    if player.hp <= 0 {
+                        player.die(current_time);
+                        deaths.push((player.id.clone(), tick.source_id));
+                    }
+                }
+                if any_expired {
+                    expired.push(player.id.clone());
+                }
+            }
+        }
+
+        for msg in damage_events {
+            self.broadcast(msg).await;
+        }
+        for player_id in expired {
+            self.broadcast_status_effects(&player_id, current_time).await;
+        }
+        for (player_id, killer_id) in deaths {
+            tracing::info!("Player {} succumbed to a status effect from {}", player_id, killer_id);
+            self.broadcast(ServerMessage::PlayerDied {
+                id: player_id.clone(),
+                killer_id,
+            }).await;
+            self.apply_death_penalty(&player_id, current_time).await;
+        }
+    }
+
+    // ========================================================================
+    // PvP
+    // ========================================================================
+
+    /// Whether a tile on the player's current map lies inside a PvP zone
+    async fn is_pvp_tile(&self, player_id: &str, x: i32, y: i32) -> bool {
+        match self.instance_manager.find_player_instance(player_id).await {
+            Some(instance) => self.instance_map(&instance)
+                .map(|map| map.is_pvp_zone(x, y))
+                .unwrap_or(false),
+            None => self.world.is_pvp_zone(x, y).await,
+        }
+    }
+
+    async fn pvp_standing(&self, player_id: &str, current_time: u64) -> Option<PvpStanding> {
+        let (x, y, opted_in, skulled) = {
+            let players = self.players.read().await;
+            let player = players.get(player_id)?;
+            (player.x, player.y, player.pvp_enabled, player.is_skulled(current_time))
+        };
+        Some(PvpStanding {
+            in_zone: self.is_pvp_tile(player_id, x, y).await,
+            opted_in,
+            skulled,
+        })
+    }
+
+    /// Log a player kill for the PvP leaderboard
+    async fn record_pvp_kill(&self, killer_id: &str, victim_id: &str, in_zone: bool) {
+        let (killer_name, killer_character, victim_name, victim_character) = {
+            let players = self.players.read().await;
+            match (players.get(killer_id), players.get(victim_id)) {
+                (Some(killer), Some(victim)) => (killer.name.clone(), killer.character_id, victim.name.clone(), victim.character_id),
+                _ => return,
+            }
+        };
+
+        let zone = self.instance_manager.find_player_instance(victim_id).await
+            .map(|instance| instance.map_id.clone())
+            .unwrap_or_else(|| OVERWORLD_ZONE.to_string());
+        if let Err(e) = self.db.record_pvp_kill(killer_character, victim_character, &killer_name, &victim_name, &zone, in_zone).await {
+            tracing::error!("Failed to record PvP kill of {} by {}: {}", victim_name, killer_name, e);
+        }
+    }
+
+    /// /pvp [on|off] - show or change the PvP opt-in
+    async fn handle_pvp_command(&self, player_id: &str, parts: &[&str]) {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        let sub = parts.get(1).map(|s| s.to_lowercase()).unwrap_or_default();
+
+        let message = {
+            let mut players = self.players.write().await;
+            let Some(player) = players.get_mut(player_id) else {
+                return;
+            };
+            let skull_secs = player.skull_expires_at.saturating_sub(now).div_ceil(1000);
+            match sub.as_str() {
+                "on" => {
+                    player.pvp_enabled = true;
+                    "PvP enabled. Attacking players outside PvP zones will skull you.".to_string()
+                }
+                "off" if player.is_skulled(now) => {
+                    format!("You can't disable PvP while skulled ({}s left).", skull_secs)
+                }
+                "off" => {
+                    player.pvp_enabled = false;
+                    "PvP disabled.".to_string()
+                }
+                "" if player.is_skulled(now) => {
+                    format!("PvP is {}. Skulled for {}s.", if player.pvp_enabled { "on" } else { "off" }, skull_secs)
+                }
+                "" => format!("PvP is {}.", if player.pvp_enabled { "on" } else { "off" }),
+                _ => "Usage: /pvp [on|off]".to_string(),
+            }
+        };
+        self.send_system_message(player_id, &message).await;
+    }
+
+    // ========================================================================
+    // Death Penalties
+    // ========================================================================
+
+    /// Apply the death rule of the zone a player just died in
+    async fn apply_death_penalty(&self, player_id: &str, current_time: u64) {
+        let instance = self.instance_manager.find_player_instance(player_id).await;
+        let zone = instance.as_ref().map(|i| i.map_id.as_str()).unwrap_or(OVERWORLD_ZONE);
+        let penalty = self.death_rules.rule_for(zone).clone();
+        let instance_id = instance.as_ref().map(|i| i.id.clone());
+
+        let mut gold_lost = 0;
+        let mut gravestone = None;
+        let inventory_update = {
+            let mut players = self.players.write().await;
+            let Some(player) = players.get_mut(player_id) else {
+                return;
+            };
+            match &penalty {
+                DeathPenalty::KeepAll => None,
+                DeathPenalty::GoldLoss { percent } => {
+                    gold_lost = death::gold_lost(player.inventory.gold, *percent);
+                    player.inventory.gold -= gold_lost;
+                    Some((player.inventory.to_update(), player.inventory.gold))
+                }
+                DeathPenalty::Gravestone { duration_secs } => {
+                    let items = death::take_unequipped_items(&mut player.inventory);
+                    if !items.is_empty() {
+                        gravestone = Some(Gravestone {
+                            id: Uuid::new_v4().to_string(),
+                            owner_id: player_id.to_string(),
+                            owner_name: player.name.clone(),
+                            x: player.x,
+                            y: player.y,
+                            instance_id: instance_id.clone(),
+                            items,
+                            expires_at: current_time + duration_secs * 1000,
+                        });
+                    }
+                    Some((player.inventory.to_update(), player.inventory.gold))
+                }
+                DeathPenalty::Hardcore => {
+                    player.perished = true;
+                    None
+                }
+            }
+        };
+
+        if let Some((slots, gold)) = inventory_update {
+            self.send_to_player(player_id, ServerMessage::InventoryUpdate {
+                player_id: player_id.to_string(),
+                slots,
+                gold,
+            }).await;
+        }
+
+        let (gravestone_id, expires_in) = match &gravestone {
+            Some(grave) => (Some(grave.id.clone()), grave.remaining_secs(current_time)),
+            None => (None, 0),
+        };
+        if let Some(grave) = gravestone {
+            tracing::info!("Player {} left gravestone {} at ({}, {}) in {}", player_id, grave.id, grave.x, grave.y, zone);
+            let msg = Self::gravestone_spawned_message(&grave, current_time);
+            self.gravestones.write().await.insert(grave.id.clone(), grave);
+            self.broadcast_to_zone(player_id, msg).await;
+        }
+
+        if penalty == DeathPenalty::Hardcore {
+            match self.get_player_character_id(player_id).await {
+                Some(character_id) => {
+                    if let Err(e) = self.db.mark_character_perished(character_id).await {
+                        tracing::error!("Failed to mark character {} as perished: {}", character_id, e);
+                    }
+                }
+                None => tracing::warn!("No character ID for perished player {}", player_id),
+            }
+            tracing::info!("Player {} perished permanently in {}", player_id, zone);
+        }
+
+        self.send_to_player(player_id, ServerMessage::DeathPenalty {
+            penalty: penalty.name().to_string(),
+            gold_lost,
+            gravestone_id,
+            expires_in,
+        }).await;
+    }
+
+    fn gravestone_spawned_message(grave: &Gravestone, current_time: u64) -> ServerMessage {
+        ServerMessage::GravestoneSpawned {
+            id: grave.id.clone(),
+            owner_id: grave.owner_id.clone(),
+            owner_name: grave.owner_name.clone(),
+            x: grave.x,
+            y: grave.y,
+            expires_in: grave.remaining_secs(current_time),
+        }
+    }
+
+    /// Send the gravestones in a player's current zone (after joining or changing maps)
+    pub async fn send_gravestones(&self, player_id: &str) {
+        let current_time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        let instance_id = self.player_instances.read().await.get(player_id).cloned();
+        let messages: Vec<ServerMessage> = {
+            let gravestones = self.gravestones.read().await;
+            gravestones.values()
+                .filter(|grave| grave.instance_id == instance_id)
+                .map(|grave| Self::gravestone_spawned_message(grave, current_time))
+                .collect()
+        };
+        for msg in messages {
+            self.send_to_player(player_id, msg).await;
+        }
+    }
+
+    /// Check that a living player stands next to their own gravestone
+    async fn check_gravestone_access(&self, player_id: &str, gravestone_id: &str) -> Result<(), &'static str> {
+        let instance_id = self.player_instances.read().await.get(player_id).cloned();
+        let (x, y) = {
+            let players = self.players.read().await;
+            match players.get(player_id) {
+                Some(p) if p.active && !p.is_dead => (p.x, p.y),
+                _ => return Err("You can't do that right now."),
+            }
+        };
+        let gravestones = self.gravestones.read().await;
+        match gravestones.get(gravestone_id) {
+            None => Err("That gravestone has crumbled."),
+            Some(grave) if grave.owner_id != player_id => Err("That gravestone is not yours."),
+            Some(grave) if grave.instance_id != instance_id || !grave.in_reach(x, y) => {
+                Err("You are too far away from your gravestone.")
+            }
+            Some(_) => Ok(()),
+        }
+    }
+
+    async fn send_gravestone_contents(&self, player_id: &str, gravestone_id: &str, current_time: u64) {
+        let msg = {
+            let gravestones = self.gravestones.read().await;
+            gravestones.get(gravestone_id).map(|grave| ServerMessage::GravestoneContents {
+                id: grave.id.clone(),
+                items: grave.to_update(),
+                expires_in: grave.remaining_secs(current_time),
+            })
+        };
+        if let Some(msg) = msg {
+            self.send_to_player(player_id, msg).await;
+        }
+    }
+
+    pub async fn handle_gravestone_open(&self, player_id: &str, gravestone_id: &str) {
+        if let Err(message) = self.check_gravestone_access(player_id, gravestone_id).await {
+            self.send_system_message(player_id, message).await;
+            return;
+        }
+        let current_time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        self.send_gravestone_contents(player_id, gravestone_id, current_time).await;
+    }
+
+    pub async fn handle_gravestone_recover(&self, player_id: &str, gravestone_id: &str) {
+        if let Err(message) = self.check_gravestone_access(player_id, gravestone_id).await {
+            self.send_system_message(player_id, message).await;
+            return;
+        }
+        let current_time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        let (moved, emptied, slots, gold) = {
+            let mut players = self.players.write().await;
+            let Some(player) = players.get_mut(player_id) else {
+                return;
+            };
+            let mut gravestones = self.gravestones.write().await;
+            let Some(grave) = gravestones.get_mut(gravestone_id) else {
+                return;
+            };
+            let moved = grave.recover_into(&mut player.inventory, &self.item_registry);
+            let emptied = grave.is_empty();
+            if emptied {
+                gravestones.remove(gravestone_id);
+            }
+            (moved, emptied, player.inventory.to_update(), player.inventory.gold)
+        };
+
+        if moved {
+            self.send_to_player(player_id, ServerMessage::InventoryUpdate {
+                player_id: player_id.to_string(),
+                slots,
+                gold,
+            }).await;
+        }
+        if emptied {
+            tracing::debug!("Player {} emptied gravestone {}", player_id, gravestone_id);
+            self.broadcast(ServerMessage::GravestoneRemoved { id: gravestone_id.to_string() }).await;
+        } else {
+            self.send_system_message(player_id, "Your inventory is too full to take everything.").await;
+            self.send_gravestone_contents(player_id, gravestone_id, current_time).await;
+        }
+    }
+
+    /// Crumble gravestones whose time ran out, along with the items in them
+    async fn update_gravestones(&self, current_time: u64) {
+        let expired: Vec<Gravestone> = {
+            let mut gravestones = self.gravestones.write().await;
+            let ids: Vec<String> = gravestones.values()
+                .filter(|grave| grave.is_expired(current_time))
+                .map(|grave| grave.id.clone())
+                .collect();
+            ids.iter().filter_map(|id| gravestones.remove(id)).collect()
+        };
+        for grave in expired {
+            tracing::info!("Gravestone {} of {} crumbled with {} stacks", grave.id, grave.owner_name, grave.items.len());
+            self.broadcast(ServerMessage::GravestoneRemoved { id: grave.id.clone() }).await;
+            self.send_system_message(&grave.owner_id, "Your gravestone has crumbled to dust.").await;
+        }
+    }
+
+    /// Run one tick of AI, respawns and regen for the NPCs on one map
+    fn update_npc_map(
+        &self,
+        npcs: &mut HashMap<String, Npc>,
+        player_positions: &[(String, i32, i32, i32, i32)],
+        paths: &mut PathContext,
+        current_time: u64,
+        delta_time: f32,
+    ) -> NpcTickResult {
+        let mut result = NpcTickResult::default();
+
+        // Collect NPC positions for collision detection (only alive NPCs)
+        let mut npc_positions: HashMap<String, (i32, i32)> = npcs
+            .values()
+            .filter(|n| n.is_alive())
+            .map(|n| (n.id.clone(), (n.x, n.y)))
+            .collect();
+
+        for npc in npcs.values_mut() {
+            // Check for respawn (world event, boss add, region, script and split NPCs are cleaned up instead)
+            if npc.respawns && npc.event_id.is_none() && npc.summoned_by.is_none() && npc.spawn_region.is_none()
+                && npc.spawned_by_script.is_none() && npc.split_from.is_none() && npc.ready_to_respawn(current_time)
+            {
+                npc.respawn();
+                // Every respawn rolls its elite variant afresh
+                if let Some(prototype) = self.entity_registry.get(&npc.prototype_id) {
+                    npc.set_elite(self.elite_table.roll_for(prototype, &mut rand::thread_rng()));
+                }
+                result.respawned.push((npc.id.clone(), npc.x, npc.y));
+                // Update position in collision map
+                npc_positions.insert(npc.id.clone(), (npc.x, npc.y));
+            }
+
+            // Get positions of other NPCs (excluding self) for collision detection
+            let mut occupied_tiles: Vec<(i32, i32)> = npc_positions
+                .iter()
+                .filter(|(id, _)| *id != &npc.id)
+                .map(|(_, pos)| *pos)
+                .collect();
+
+            // Add player positions to occupied tiles so NPCs avoid walking into players
+            for (_, px, py, _, _) in player_positions {
+                occupied_tiles.push((*px, *py));
+            }
+
+            // Run NPC AI update
+            if let Some((target_id, max_hit)) = npc.update(delta_time, player_positions, &occupied_tiles, current_time, paths) {
+                // Store NPC level and max hit for hit/miss calculation during attack processing
+                result.attacks.push(NpcAttack {
+                    npc_id: npc.id.clone(),
+                    prototype_id: npc.prototype_id.clone(),
+                    target_id,
+                    level: npc.level,
+                    max_hit,
+                    projectile: npc.attack_projectile.clone(),
+                });
+            }
+
+            // Update position in collision map after movement
+            if npc.is_alive() {
+                npc_positions.insert(npc.id.clone(), (npc.x, npc.y));
+            }
+
+            // Apply HP regen
+            npc.apply_regen(current_time);
+
+            // Add to updates (all NPCs including dead ones for client awareness)
+            result.updates.push(NpcUpdate::from(&*npc));
+        }
+        result
+    }
+
+    pub async fn tick(&self) {
+        let delta_time = 1.0 / TICK_RATE;
+        let current_time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        // Update tick counter and get current tick for movement timing
+        let current_tick = {
+            let mut tick = self.tick.write().await;
+            *tick += 1;
+            *tick
+        };
+
+        // Handle player respawns
+        let mut respawned_players = Vec::new();
+        {
+            let mut players = self.players.write().await;
+            for player in players.values_mut() {
+                if !player.active {
+                    continue;
+                }
+
+                if player.ready_to_respawn(current_time) {
+                    player.respawn();
+                    respawned_players.push((player.id.clone(), player.x, player.y, player.hp));
+                }
+            }
+        }
+
+        // Broadcast respawns
+        for (id, x, y, hp) in respawned_players {
+            tracing::info!("Player {} respawned at ({}, {})", id, x, y);
+            self.broadcast(ServerMessage::PlayerRespawned { id, x, y, hp }).await;
+        }
+
+        // Collect pending moves (id, target_x, target_y)
+        // Use tick-based cooldown for deterministic timing (5 ticks = 250ms)
+        let pending_moves: Vec<(String, i32, i32)> = {
+            let players = self.players.read().await;
+            players.values()
+                .filter(|p| p.active && !p.is_dead)
+                .filter(|p| p.move_dx != 0 || p.move_dy != 0)
+                .filter(|p| !p.status_effects.is_stunned(current_time))
+                .filter(|p| current_tick - p.last_move_tick >= p.status_effects.move_cooldown_ticks(MOVE_COOLDOWN_TICKS, current_time))
+                .map(|p| (p.id.clone(), p.x + p.move_dx, p.y + p.move_dy))
+                .collect()
+        };
+
+        // Get players in instances to skip world collision for them
+        let players_in_instances: HashMap<String, String> = self.player_instances.read().await.clone();
+
+        // Collect current entity positions for collision checking, keyed by map (None = overworld)
+        let player_positions: std::collections::HashSet<(Option<&String>, i32, i32)> = {
+            let players = self.players.read().await;
+            players.values()
+                .filter(|p| p.active && !p.is_dead)
+                .map(|p| (players_in_instances.get(&p.id), p.x, p.y))
+                .collect()
+        };
+        let mut npc_positions: std::collections::HashSet<(Option<&String>, i32, i32)> = {
+            let npcs = self.npcs.read().await;
+            npcs.values()
+                .filter(|n| n.is_alive())
+                .map(|n| (None, n.x, n.y))
+                .collect()
+        };
+        let occupied_instances = self.instance_manager.occupied_instances().await;
+        for instance in &occupied_instances {
+            let npcs = instance.npcs.read().await;
+            npc_positions.extend(npcs.values()
+                .filter(|n| n.is_alive())
+                .map(|n| (Some(&instance.id), n.x, n.y)));
+        }
+
+        // Check walkability and entity collision for each pending move
+        let mut valid_moves: Vec<(String, i32, i32)> = Vec::new();
+        for (id, target_x, target_y) in pending_moves {
+            let map = players_in_instances.get(&id);
+            // Skip world collision check for players in interiors
+            // Interior collision is handled client-side for now
             // TODO: Add server-side interior collision checking
-            if !players_in_instances.contains(&id) {
+            if map.is_none() {
                 // Check static tile collision (only for overworld players)
                 if !self.world.is_tile_walkable(target_x, target_y).await {
                     continue;
                 }
             }
             // Check if another player is on the target tile
-            if player_positions.contains(&(target_x, target_y)) {
+            if player_positions.contains(&(map, target_x, target_y)) {
                 continue;
             }
             // Check if an NPC is on the target tile
-            if npc_positions.contains(&(target_x, target_y)) {
+            if npc_positions.contains(&(map, target_x, target_y)) {
                 continue;
             }
             valid_moves.push((id, target_x, target_y));
@@ -3589,74 +8482,66 @@ impl GameRoom {
                     equipped_necklace: player.equipped_necklace.clone(),
                     equipped_belt: player.equipped_belt.clone(),
                     is_admin: player.is_admin,
+                    guild_tag: player.guild_tag.clone(),
+                    title: player.title.clone(),
+                    pvp_enabled: player.pvp_enabled,
+                    skulled: player.is_skulled(current_time),
                 });
             }
         }
 
         // Get player positions for NPC AI (only alive players, grid positions)
-        let player_positions: Vec<(String, i32, i32, i32)> = {
+        let player_positions: Vec<(String, i32, i32, i32, i32)> = {
             let players = self.players.read().await;
             players.values()
                 .filter(|p| p.active && p.is_alive())
-                .map(|p| (p.id.clone(), p.x, p.y, p.hp))
+                .map(|p| (p.id.clone(), p.x, p.y, p.hp, p.combat_level()))
                 .collect()
         };
 
-        let mut npc_updates = Vec::new();
-        let mut respawned_npcs = Vec::new();
-        let mut npc_attacks: Vec<(String, String, i32, i32)> = Vec::new(); // (npc_id, target_id, npc_level, max_hit)
-        {
-            let mut npcs = self.npcs.write().await;
-
-            // Collect NPC positions for collision detection (only alive NPCs)
-            let mut npc_positions: std::collections::HashMap<String, (i32, i32)> = npcs
-                .values()
-                .filter(|n| n.is_alive())
-                .map(|n| (n.id.clone(), (n.x, n.y)))
-                .collect();
-
-            for npc in npcs.values_mut() {
-                // Check for respawn
-                if npc.ready_to_respawn(current_time) {
-                    npc.respawn();
-                    respawned_npcs.push((npc.id.clone(), npc.x, npc.y));
-                    // Update position in collision map
-                    npc_positions.insert(npc.id.clone(), (npc.x, npc.y));
-                }
-
-                // Get positions of other NPCs (excluding self) for collision detection
-                let mut occupied_tiles: Vec<(i32, i32)> = npc_positions
-                    .iter()
-                    .filter(|(id, _)| *id != &npc.id)
-                    .map(|(_, pos)| *pos)
-                    .collect();
-
-                // Add player positions to occupied tiles so NPCs avoid walking into players
-                for (_, px, py, _) in &player_positions {
-                    occupied_tiles.push((*px, *py));
-                }
+        // NPCs only see players on their own map
+        let players_on_map = |instance_id: Option<&String>| -> Vec<(String, i32, i32, i32, i32)> {
+            player_positions.iter()
+                .filter(|(id, ..)| players_in_instances.get(id) == instance_id)
+                .cloned()
+                .collect()
+        };
 
-                // Run NPC AI update
-                if let Some((target_id, max_hit)) = npc.update(delta_time, &player_positions, &occupied_tiles, current_time) {
-                    // Store NPC level and max hit for hit/miss calculation during attack processing
-                    npc_attacks.push((npc.id.clone(), target_id, npc.level, max_hit));
-                }
+        // Collision snapshot for NPC pathfinding, shared with pets below
+        let nav_grid = self.world.nav_grid().await;
 
-                // Update position in collision map after movement
-                if npc.is_alive() {
-                    npc_positions.insert(npc.id.clone(), (npc.x, npc.y));
+        let overworld = {
+            let mut npcs = self.npcs.write().await;
+            let mut paths = PathContext::new(&nav_grid, MAX_NODES_PER_TICK);
+            self.update_npc_map(&mut npcs, &players_on_map(None), &mut paths, current_time, delta_time)
+        };
+        let npc_updates = overworld.updates;
+        let respawned_npcs = overworld.respawned;
+        let mut npc_attacks = overworld.attacks;
+
+        // Instance NPCs move on their own map's collision, and only while someone is there
+        let mut instance_npc_updates: HashMap<String, Vec<NpcUpdate>> = HashMap::new();
+        for instance in occupied_instances {
+            let Some(map) = self.instance_map(&instance) else {
+                continue;
+            };
+            let result = {
+                let mut npcs = instance.npcs.write().await;
+                let mut paths = PathContext::new(instance.nav_grid(map), MAX_NODES_PER_TICK);
+                self.update_npc_map(&mut npcs, &players_on_map(Some(&instance.id)), &mut paths, current_time, delta_time)
+            };
+            npc_attacks.extend(result.attacks);
+            for (id, x, y) in result.respawned {
+                for player_id in instance.get_player_ids().await {
+                    self.send_to_player(&player_id, ServerMessage::NpcRespawned { id: id.clone(), x, y }).await;
                 }
-
-                // Apply HP regen
-                npc.apply_regen(current_time);
-
-                // Add to updates (all NPCs including dead ones for client awareness)
-                npc_updates.push(NpcUpdate::from(&*npc));
             }
+            instance_npc_updates.insert(instance.id.clone(), result.updates);
         }
 
         // Process NPC attacks on players using hit/miss mechanics
-        for (npc_id, target_id, npc_level, max_hit) in npc_attacks {
+        for NpcAttack { npc_id, prototype_id, target_id, level: npc_level, max_hit, projectile } in npc_attacks {
+            let mut effects_changed = false;
             let (target_hp, target_x, target_y, died, damage): (i32, f32, f32, bool, i32) = {
                 let mut players = self.players.write().await;
                 if let Some(target) = players.get_mut(&target_id) {
@@ -3691,6 +8576,15 @@ impl GameRoom {
                         let died = target.hp <= 0;
                         if died {
                             target.die(current_time);
+                        } else if let Some(prototype) = self.entity_registry.get(&prototype_id) {
+                            // Roll the monster's on-hit status effects against the target's resistances
+                            for effect in &prototype.on_hit_effects {
+                                let resistance = target.resistance(effect.effect, &self.item_registry);
+                                if status_effect::roll_effect(effect.chance, resistance) {
+                                    target.status_effects.apply(effect, &npc_id, current_time);
+                                    effects_changed = true;
+                                }
+                            }
                         }
                         tracing::debug!(
                             "NPC {} hits {} for {} damage (max: {}, HP: {})",
@@ -3711,9 +8605,17 @@ impl GameRoom {
                 target_hp,
                 target_x,
                 target_y,
-                projectile: None,
+                projectile,
             }).await;
 
+            if effects_changed {
+                self.broadcast_status_effects(&target_id, current_time).await;
+            }
+
+            if damage > 0 {
+                self.apply_elite_lifesteal(&npc_id, &target_id, damage).await;
+            }
+
             // Handle player death
             if died {
                 tracing::info!("NPC {} killed player {}", npc_id, target_id);
@@ -3721,6 +8623,7 @@ impl GameRoom {
                     id: target_id.clone(),
                     killer_id: npc_id.clone(),
                 }).await;
+                self.apply_death_penalty(&target_id, current_time).await;
             }
         }
 
@@ -3747,6 +8650,63 @@ impl GameRoom {
             }
         }
 
+        // Cancel trades that are no longer valid (range, death, map change)
+        self.validate_active_trades(current_time).await;
+
+        // Refresh party rosters/HP twice per second
+        if current_tick % 10 == 0 {
+            self.update_parties(current_time).await;
+        }
+
+        // Check for challenge rotation once per second
+        if current_tick % 20 == 0 {
+            self.rotate_challenges(current_time).await;
+        }
+
+        // Start, spawn and resolve world events once per second
+        if current_tick % 20 == 0 {
+            self.update_world_events(current_time).await;
+        }
+
+        // Clear out and restock spawn regions once per second
+        if current_tick % 20 == 0 {
+            self.update_spawn_regions(current_time, &nav_grid).await;
+        }
+
+        // Clear out dead elite split copies once per second
+        if current_tick % 20 == 0 {
+            self.remove_dead_splits(current_time).await;
+        }
+
+        // Run world script timers and region checks four times per second
+        if current_tick % 5 == 0 {
+            self.update_world_scripts(current_time).await;
+        }
+
+        // Run boss scripts and land telegraphed attacks four times per second
+        if current_tick % 5 == 0 {
+            self.update_bosses(current_time).await;
+        }
+
+        // Crumble expired gravestones once per second
+        if current_tick % 20 == 0 {
+            self.update_gravestones(current_time).await;
+        }
+
+        // Deal damage over time and expire status effects four times per second
+        if current_tick % 5 == 0 {
+            self.update_status_effects(current_time).await;
+        }
+
+        // Pets follow and assist their owners every tick (movement cooldowns apply)
+        self.update_pets(current_time, &nav_grid).await;
+        let pet_updates: Vec<(Option<String>, NpcUpdate)> = {
+            let pets = self.pets.read().await;
+            pets.values()
+                .map(|pet| (pet.instance_id.clone(), NpcUpdate::from(&pet.npc)))
+                .collect()
+        };
+
         // Check for shop restocks (every 60 seconds)
         {
             let last_restock = *self.last_shop_restock.read().await;
@@ -3777,15 +8737,19 @@ impl GameRoom {
                 .cloned()
                 .collect();
 
-            // Filter NPCs: players in instances should not receive world NPCs
-            let npcs_for_player = if player_instances.contains_key(player_id) {
-                // Player is in an instance - send empty NPC list (instance NPCs are sent separately)
-                Vec::new()
-            } else {
-                // Player is in overworld - send all world NPCs
-                npc_updates.clone()
+            // Filter NPCs: players in instances only receive their instance's NPCs
+            let mut npcs_for_player = match my_instance {
+                Some(instance_id) => instance_npc_updates.get(instance_id).cloned().unwrap_or_default(),
+                None => npc_updates.clone(),
             };
 
+            // Pets are sent to everyone in the same instance/overworld as the pet
+            npcs_for_player.extend(
+                pet_updates.iter()
+                    .filter(|(pet_instance, _)| pet_instance.as_ref() == my_instance)
+                    .map(|(_, update)| update.clone())
+            );
+
             let msg = ServerMessage::StateSync {
                 tick,
                 players: players_for_player,
@@ -3943,3 +8907,79 @@ impl GameRoom {
         ServerMessage::EntityDefinitions { entities }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dungeon::{self, DungeonRegistry};
+    use crate::instance::InstanceManager;
+    use std::path::Path;
+
+    async fn test_room(entity_registry: Arc<EntityRegistry>, loot_tables: Arc<LootTableRegistry>) -> GameRoom {
+        let db_path = std::env::temp_dir().join(format!("game_room_test_{}.db", Uuid::new_v4()));
+        let db = Database::new(&format!("sqlite:{}?mode=rwc", db_path.display())).await.unwrap();
+        GameRoom::new("test", RoomServices {
+            entity_registry,
+            loot_tables,
+            quest_registry: Arc::new(QuestRegistry::new(Path::new("data"))),
+            crafting_registry: Arc::new(crate::crafting::CraftingRegistry::new()),
+            item_registry: Arc::new(ItemRegistry::new()),
+            player_instances: Arc::new(RwLock::new(HashMap::new())),
+            instance_manager: Arc::new(InstanceManager::new()),
+            db: Arc::new(db),
+            achievement_registry: Arc::new(AchievementRegistry::new()),
+            interior_registry: Arc::new(InteriorRegistry::load_from_directory("maps/interiors").unwrap()),
+        }).await
+    }
+
+    #[tokio::test]
+    async fn test_player_kills_monster_in_generated_dungeon() {
+        let data_dir = Path::new("data");
+        let mut entity_registry = EntityRegistry::new();
+        entity_registry.load_from_directory(data_dir).unwrap();
+        let mut loot_tables = LootTableRegistry::new();
+        loot_tables.load_from_directory(data_dir).unwrap();
+        let mut dungeon_registry = DungeonRegistry::new();
+        dungeon_registry.load_from_directory(data_dir).unwrap();
+        let entity_registry = Arc::new(entity_registry);
+        let room = test_room(entity_registry.clone(), Arc::new(loot_tables)).await;
+
+        // Generate a dungeon and put the player at its entrance, facing right
+        let template = dungeon_registry.get("crypt").unwrap();
+        let generated = dungeon::generate(template, 7, 1, &room.loot_tables);
+        let (instance, _) = room.instance_manager.get_or_create_generated(generated.map, "p1");
+        let map = instance.generated_map.clone().unwrap();
+        let entrance = map.get_spawn_point("entrance").unwrap();
+        let (x, y) = (entrance.x as i32, entrance.y as i32);
+        instance.spawn_npcs(&map.entities, &entity_registry).await;
+        instance.add_player("p1").await;
+        room.player_instances.write().await.insert("p1".to_string(), instance.id.clone());
+        room.reserve_player("p1", "Hero", "male", "tan", None, None).await;
+        room.set_player_position("p1", x, y).await;
+        room.players.write().await.get_mut("p1").unwrap().direction = Direction::Right;
+
+        // Move one dungeon monster in front of the player, with an overworld NPC on the same tile
+        let monster_id = {
+            let mut npcs = instance.npcs.write().await;
+            let (id, monster) = npcs.iter_mut().find(|(_, npc)| npc.is_attackable()).unwrap();
+            monster.x = x + 1;
+            monster.y = y;
+            monster.hp = 1;
+            let mut decoy = monster.clone();
+            decoy.hp = 50;
+            room.npcs.write().await.insert("overworld_decoy".to_string(), decoy);
+            id.clone()
+        };
+
+        for _ in 0..200 {
+            if !instance.npcs.read().await[&monster_id].is_alive() {
+                break;
+            }
+            room.players.write().await.get_mut("p1").unwrap().last_attack_time = 0;
+            room.handle_attack("p1").await;
+        }
+
+        assert!(!instance.npcs.read().await[&monster_id].is_alive());
+        assert_eq!(room.npcs.read().await["overworld_decoy"].hp, 50);
+    }
+}
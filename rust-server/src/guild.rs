@@ -0,0 +1,170 @@
+//! Player guilds
+//!
+//! Guilds, their ranks and their rosters are persisted in the database (see
+//! `Database` guild functions). This module holds the rank/permission model
+//! and name validation shared by the database layer and the game room.
+//! Rank 0 is the leader; a higher index means a lower rank.
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Permission to invite new members
+pub const GUILD_PERM_INVITE: u32 = 1 << 0;
+/// Permission to remove lower-ranked members
+pub const GUILD_PERM_KICK: u32 = 1 << 1;
+/// Permission to promote/demote lower-ranked members
+pub const GUILD_PERM_PROMOTE: u32 = 1 << 2;
+/// Permission to change the message of the day
+pub const GUILD_PERM_SET_MOTD: u32 = 1 << 3;
+/// Permission to disband the guild
+pub const GUILD_PERM_DISBAND: u32 = 1 << 4;
+
+pub const GUILD_PERM_ALL: u32 =
+    GUILD_PERM_INVITE | GUILD_PERM_KICK | GUILD_PERM_PROMOTE | GUILD_PERM_SET_MOTD | GUILD_PERM_DISBAND;
+
+/// Rank index of the guild leader
+pub const GUILD_LEADER_RANK: i32 = 0;
+
+/// Maximum number of members in a guild
+pub const GUILD_MAX_MEMBERS: i64 = 50;
+
+/// How long a guild invite stays valid before it expires
+pub const GUILD_INVITE_TIMEOUT_MS: u64 = 60_000;
+
+/// Maximum length of the message of the day
+pub const GUILD_MOTD_MAX_LEN: usize = 120;
+
+// ============================================================================
+// Ranks
+// ============================================================================
+
+#[derive(Debug, Clone)]
+pub struct GuildRank {
+    pub index: i32,
+    pub name: String,
+    pub permissions: u32,
+}
+
+impl GuildRank {
+    pub fn has(&self, permission: u32) -> bool {
+        self.permissions & permission == permission
+    }
+}
+
+/// Ranks every new guild starts with
+pub fn default_ranks() -> Vec<GuildRank> {
+    vec![
+        GuildRank { index: 0, name: "Leader".to_string(), permissions: GUILD_PERM_ALL },
+        GuildRank {
+            index: 1,
+            name: "Officer".to_string(),
+            permissions: GUILD_PERM_INVITE | GUILD_PERM_KICK | GUILD_PERM_PROMOTE | GUILD_PERM_SET_MOTD,
+        },
+        GuildRank { index: 2, name: "Member".to_string(), permissions: 0 },
+        GuildRank { index: 3, name: "Recruit".to_string(), permissions: 0 },
+    ]
+}
+
+/// A rank can only manage (kick, promote, demote) members strictly below it
+pub fn outranks(actor_rank: i32, target_rank: i32) -> bool {
+    actor_rank < target_rank
+}
+
+// ============================================================================
+// Membership
+// ============================================================================
+
+/// A character's guild membership, including their rank's permissions
+#[derive(Debug, Clone)]
+pub struct GuildMembership {
+    pub guild_id: i64,
+    pub guild_name: String,
+    pub tag: String,
+    pub rank: GuildRank,
+}
+
+/// Guild state of a connected player
+#[derive(Debug, Clone)]
+pub struct PlayerGuild {
+    pub character_id: i64,
+    pub membership: Option<GuildMembership>,
+}
+
+/// Pending invite to join a guild
+#[derive(Debug, Clone)]
+pub struct GuildInvite {
+    pub guild_id: i64,
+    pub guild_name: String,
+    pub inviter_name: String,
+    pub created_at: u64,
+}
+
+/// One row of a guild roster
+#[derive(Debug, Clone)]
+pub struct GuildRosterEntry {
+    pub character_id: i64,
+    pub name: String,
+    pub rank_index: i32,
+    pub rank_name: String,
+}
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+/// Guild names are 3-24 characters of letters, digits and single spaces
+pub fn validate_guild_name(name: &str) -> Result<(), String> {
+    let len = name.chars().count();
+    if !(3..=24).contains(&len) {
+        return Err("Guild name must be 3-24 characters.".to_string());
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == ' ') || name.contains("  ") {
+        return Err("Guild name may only contain letters, numbers and single spaces.".to_string());
+    }
+    if name.starts_with(' ') || name.ends_with(' ') {
+        return Err("Guild name can't start or end with a space.".to_string());
+    }
+    Ok(())
+}
+
+/// Guild tags are 2-4 letters or digits
+pub fn validate_guild_tag(tag: &str) -> Result<(), String> {
+    if !(2..=4).contains(&tag.len()) || !tag.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err("Guild tag must be 2-4 letters or numbers.".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_rank_permissions() {
+        let ranks = default_ranks();
+        assert!(ranks[0].has(GUILD_PERM_DISBAND));
+        assert!(ranks[1].has(GUILD_PERM_KICK | GUILD_PERM_PROMOTE));
+        assert!(!ranks[1].has(GUILD_PERM_DISBAND));
+        assert!(!ranks[2].has(GUILD_PERM_INVITE));
+
+        assert!(outranks(GUILD_LEADER_RANK, 1));
+        assert!(!outranks(1, 1));
+        assert!(!outranks(2, 1));
+    }
+
+    #[test]
+    fn test_name_and_tag_validation() {
+        assert!(validate_guild_name("Iron Wolves").is_ok());
+        assert!(validate_guild_name("ab").is_err());
+        assert!(validate_guild_name("Iron  Wolves").is_err());
+        assert!(validate_guild_name(" Wolves").is_err());
+        assert!(validate_guild_name("W0lves!").is_err());
+
+        assert!(validate_guild_tag("IW").is_ok());
+        assert!(validate_guild_tag("WOLF").is_ok());
+        assert!(validate_guild_tag("W").is_err());
+        assert!(validate_guild_tag("WOLVES").is_err());
+        assert!(validate_guild_tag("W-F").is_err());
+    }
+}
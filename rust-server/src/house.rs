@@ -0,0 +1,364 @@
+//! Player housing
+//!
+//! Houses from `data/houses/*.toml` are private interior instances keyed by
+//! their owner, entered through overworld portals targeting `house`. Ownership,
+//! furniture, chest contents and the guest list are persisted per character.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use tracing::{info, warn};
+
+use crate::interior::{InstanceType, InteriorMapDef};
+use crate::item::InventorySlot;
+
+/// Target map of overworld portals that lead to a house
+pub const HOUSE_PORTAL_TARGET: &str = "house";
+
+/// Prefix of house map IDs (`house_<house id>`)
+pub const HOUSE_MAP_PREFIX: &str = "house_";
+
+/// Spawn point players arrive at inside a house
+pub const HOUSE_SPAWN_POINT: &str = "entrance";
+
+/// Most guests an owner can invite
+pub const MAX_GUESTS: usize = 20;
+
+// ============================================================================
+// Definitions
+// ============================================================================
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HouseDefinition {
+    #[serde(skip)]
+    pub id: String,
+    pub display_name: String,
+    /// Interior map used as the house layout
+    pub interior: String,
+    pub price: i32,
+    #[serde(default = "default_max_furniture")]
+    pub max_furniture: usize,
+}
+
+fn default_max_furniture() -> usize {
+    20
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RawHouseFile {
+    #[serde(default)]
+    pub houses: HashMap<String, HouseDefinition>,
+}
+
+#[derive(Debug, Default)]
+pub struct HouseRegistry {
+    houses: HashMap<String, HouseDefinition>,
+}
+
+impl HouseRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load every house file from `<data_dir>/houses`
+    pub fn load_from_directory(&mut self, data_dir: &Path) -> Result<(), String> {
+        let path = data_dir.join("houses");
+        if !path.exists() {
+            warn!("House directory does not exist: {:?}", path);
+            return Ok(());
+        }
+
+        for entry in fs::read_dir(&path).map_err(|e| e.to_string())? {
+            let file_path = entry.map_err(|e| e.to_string())?.path();
+            if file_path.extension().and_then(|s| s.to_str()) == Some("toml") {
+                let contents = fs::read_to_string(&file_path)
+                    .map_err(|e| format!("Failed to read {:?}: {}", file_path, e))?;
+                let file: RawHouseFile = toml::from_str(&contents)
+                    .map_err(|e| format!("Failed to parse {:?}: {}", file_path, e))?;
+                for (id, mut house) in file.houses {
+                    house.id = id.clone();
+                    self.houses.insert(id, house);
+                }
+            }
+        }
+
+        info!("Loaded {} house definitions", self.houses.len());
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> Option<&HouseDefinition> {
+        self.houses.get(id)
+    }
+
+    /// All houses, cheapest first
+    pub fn all(&self) -> Vec<&HouseDefinition> {
+        let mut houses: Vec<&HouseDefinition> = self.houses.values().collect();
+        houses.sort_by(|a, b| a.price.cmp(&b.price).then_with(|| a.id.cmp(&b.id)));
+        houses
+    }
+}
+
+/// Build the map of a house from its interior template. Template NPCs and PvP
+/// zones are left out, and exits return players to the door they came in through.
+pub fn build_house_map(house: &HouseDefinition, template: &InteriorMapDef) -> InteriorMapDef {
+    let mut map = template.clone();
+    map.id = format!("{}{}", HOUSE_MAP_PREFIX, house.id);
+    map.name = house.display_name.clone();
+    map.instance_type = InstanceType::Private;
+    map.entities.clear();
+    map.pvp_zones.clear();
+    for portal in &mut map.portals {
+        portal.target_x = 0.0;
+        portal.target_y = 0.0;
+    }
+    map
+}
+
+// ============================================================================
+// Player Houses
+// ============================================================================
+
+/// A piece of furniture placed in a house
+#[derive(Debug, Clone)]
+pub struct PlacedFurniture {
+    /// Unique within the house
+    pub id: i32,
+    pub item_id: String,
+    pub x: i32,
+    pub y: i32,
+    /// Chest contents; empty for furniture without storage
+    pub storage: Vec<Option<InventorySlot>>,
+}
+
+impl PlacedFurniture {
+    pub fn is_storage(&self) -> bool {
+        !self.storage.is_empty()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.storage.iter().all(|slot| slot.is_none())
+    }
+
+    /// Add items to the chest, filling existing stacks first. Returns the
+    /// quantity that didn't fit.
+    pub fn deposit(&mut self, item_id: &str, mut quantity: i32, max_stack: i32) -> i32 {
+        for slot in self.storage.iter_mut().flatten() {
+            if quantity <= 0 {
+                break;
+            }
+            if slot.item_id == item_id && slot.quantity < max_stack {
+                let add = quantity.min(max_stack - slot.quantity);
+                slot.quantity += add;
+                quantity -= add;
+            }
+        }
+        for slot in self.storage.iter_mut() {
+            if quantity <= 0 {
+                break;
+            }
+            if slot.is_none() {
+                let add = quantity.min(max_stack);
+                *slot = Some(InventorySlot::new(item_id.to_string(), add));
+                quantity -= add;
+            }
+        }
+        quantity
+    }
+
+    /// Chest contents in the same JSON format as the inventory
+    pub fn storage_json(&self) -> String {
+        let slots: Vec<(usize, String, i32)> = self.storage.iter()
+            .enumerate()
+            .filter_map(|(i, slot)| slot.as_ref().map(|s| (i, s.item_id.clone(), s.quantity)))
+            .collect();
+        serde_json::to_string(&slots).unwrap_or_else(|_| "[]".to_string())
+    }
+
+    /// Restore chest contents saved with `storage_json`
+    pub fn load_storage(&mut self, json: &str) {
+        if let Ok(slots) = serde_json::from_str::<Vec<(usize, String, i32)>>(json) {
+            for (index, item_id, quantity) in slots {
+                if let Some(slot) = self.storage.get_mut(index) {
+                    *slot = Some(InventorySlot::new(item_id, quantity));
+                }
+            }
+        }
+    }
+}
+
+/// A character's house, its furniture and who may visit
+#[derive(Debug, Clone, Default)]
+pub struct PlayerHouse {
+    /// House definition ID (None until a house is bought)
+    pub house_id: Option<String>,
+    pub furniture: Vec<PlacedFurniture>,
+    /// Names of characters allowed to visit
+    pub guests: Vec<String>,
+}
+
+impl PlayerHouse {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_guest(&self, name: &str) -> bool {
+        self.guests.iter().any(|g| g.eq_ignore_ascii_case(name))
+    }
+
+    pub fn furniture_at(&self, x: i32, y: i32) -> Option<&PlacedFurniture> {
+        self.furniture.iter().find(|f| f.x == x && f.y == y)
+    }
+
+    pub fn furniture_mut(&mut self, id: i32) -> Option<&mut PlacedFurniture> {
+        self.furniture.iter_mut().find(|f| f.id == id)
+    }
+
+    /// Check that a piece of furniture can go on a tile of the house map
+    pub fn can_place(&self, map: &InteriorMapDef, x: i32, y: i32, max_furniture: usize) -> Result<(), String> {
+        if self.furniture.len() >= max_furniture {
+            return Err(format!("Your house can't hold more than {} pieces of furniture.", max_furniture));
+        }
+        if map.is_blocked(x, y) {
+            return Err("You can't place furniture there.".to_string());
+        }
+        if map.get_portal_at(x, y).is_some() {
+            return Err("Furniture can't block the door.".to_string());
+        }
+        let on_spawn = map.spawn_points.values()
+            .any(|spawn| spawn.x as i32 == x && spawn.y as i32 == y);
+        if on_spawn {
+            return Err("Furniture can't block the entrance.".to_string());
+        }
+        if self.furniture_at(x, y).is_some() {
+            return Err("There is already furniture there.".to_string());
+        }
+        Ok(())
+    }
+
+    /// Place furniture, returning its ID
+    pub fn place(&mut self, item_id: &str, x: i32, y: i32, storage_slots: usize) -> i32 {
+        let id = self.furniture.iter().map(|f| f.id).max().unwrap_or(0) + 1;
+        self.furniture.push(PlacedFurniture {
+            id,
+            item_id: item_id.to_string(),
+            x,
+            y,
+            storage: vec![None; storage_slots],
+        });
+        id
+    }
+
+    pub fn remove(&mut self, id: i32) -> Option<PlacedFurniture> {
+        let index = self.furniture.iter().position(|f| f.id == id)?;
+        Some(self.furniture.remove(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interior::{InteriorPortal, InteriorSize, SpawnPoint};
+    use base64::Engine;
+
+    fn test_map() -> InteriorMapDef {
+        // 4x4 map with (3, 0) blocked
+        let mut collision = vec![0u8; 2];
+        collision[0] |= 1 << 3;
+        InteriorMapDef {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            instance_type: InstanceType::Public,
+            size: InteriorSize { width: 4, height: 4 },
+            spawn_points: HashMap::from([("entrance".to_string(), SpawnPoint { x: 1.0, y: 3.0 })]),
+            portals: vec![InteriorPortal {
+                id: "exit".to_string(),
+                x: 0,
+                y: 3,
+                width: 1,
+                height: 1,
+                target_map: "overworld".to_string(),
+                target_x: 9.0,
+                target_y: 4.0,
+                target_spawn: None,
+            }],
+            layers: Default::default(),
+            collision: base64::engine::general_purpose::STANDARD.encode(collision),
+            entities: vec![],
+            map_objects: vec![],
+            walls: vec![],
+            pvp_zones: vec![],
+        }
+    }
+
+    #[test]
+    fn placement_rules() {
+        let map = test_map();
+        let mut house = PlayerHouse::new();
+
+        assert!(house.can_place(&map, 1, 1, 2).is_ok());
+        assert!(house.can_place(&map, 3, 0, 2).is_err(), "blocked tile");
+        assert!(house.can_place(&map, 4, 0, 2).is_err(), "outside the map");
+        assert!(house.can_place(&map, 0, 3, 2).is_err(), "portal");
+        assert!(house.can_place(&map, 1, 3, 2).is_err(), "spawn point");
+
+        let id = house.place("wooden_table", 1, 1, 0);
+        assert!(house.can_place(&map, 1, 1, 2).is_err(), "occupied");
+        house.place("wooden_bench", 2, 1, 0);
+        assert!(house.can_place(&map, 2, 2, 2).is_err(), "furniture limit");
+
+        assert_eq!(house.remove(id).map(|f| f.item_id), Some("wooden_table".to_string()));
+        assert!(house.can_place(&map, 1, 1, 2).is_ok());
+    }
+
+    #[test]
+    fn chest_stacks_and_round_trips() {
+        let mut house = PlayerHouse::new();
+        let id = house.place("wooden_chest", 1, 1, 2);
+        let chest = house.furniture_mut(id).unwrap();
+
+        assert_eq!(chest.deposit("bones", 60, 50), 0);
+        assert_eq!(chest.deposit("bones", 50, 50), 10);
+        assert!(!chest.is_empty());
+
+        let mut restored = PlacedFurniture {
+            id,
+            item_id: "wooden_chest".to_string(),
+            x: 1,
+            y: 1,
+            storage: vec![None; 2],
+        };
+        restored.load_storage(&chest.storage_json());
+        let quantities: Vec<i32> = restored.storage.iter().flatten().map(|s| s.quantity).collect();
+        assert_eq!(quantities, vec![50, 50]);
+    }
+
+    #[test]
+    fn house_map_drops_npcs_and_exit_targets() {
+        let mut template = test_map();
+        template.entities.push(crate::interior::InteriorEntitySpawn {
+            entity_id: "elder_villager".to_string(),
+            x: 1,
+            y: 1,
+            level: 1,
+            unique_id: None,
+            facing: None,
+            respawn: true,
+            patrol: Vec::new(),
+            schedule: Vec::new(),
+        });
+        let house = HouseDefinition {
+            id: "small_cottage".to_string(),
+            display_name: "Small Cottage".to_string(),
+            interior: "test".to_string(),
+            price: 100,
+            max_furniture: 10,
+        };
+
+        let map = build_house_map(&house, &template);
+        assert_eq!(map.id, "house_small_cottage");
+        assert_eq!(map.instance_type, InstanceType::Private);
+        assert!(map.entities.is_empty());
+        assert_eq!((map.portals[0].target_x, map.portals[0].target_y), (0.0, 0.0));
+    }
+}
@@ -1,12 +1,13 @@
 use dashmap::DashMap;
 use std::collections::{HashMap, HashSet};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use tokio::sync::RwLock;
 use tracing::info;
 use uuid::Uuid;
 
-use crate::interior::InstanceType;
+use crate::interior::{InstanceType, InteriorMapDef};
 use crate::npc::Npc;
+use crate::pathfinding::NavGrid;
 
 /// Tracks an active instance
 pub struct Instance {
@@ -19,6 +20,10 @@ pub struct Instance {
     pub npcs: RwLock<HashMap<String, Npc>>,
     /// Whether NPCs have been spawned for this instance
     pub npcs_spawned: RwLock<bool>,
+    /// Map generated for this instance (dungeons); hand-authored maps come from the InteriorRegistry
+    pub generated_map: Option<Arc<InteriorMapDef>>,
+    /// Collision grid for NPC movement, built on first use
+    nav_grid: OnceLock<NavGrid>,
 }
 
 impl Instance {
@@ -40,6 +45,11 @@ impl Instance {
         self.players.read().await.contains(player_id)
     }
 
+    /// Collision grid of the instance's map for NPC pathfinding
+    pub fn nav_grid(&self, map: &InteriorMapDef) -> &NavGrid {
+        self.nav_grid.get_or_init(|| NavGrid::from_interior(map))
+    }
+
     /// Get all player IDs in this instance
     pub async fn get_player_ids(&self) -> Vec<String> {
         self.players.read().await.iter().cloned().collect()
@@ -64,7 +74,7 @@ impl Instance {
             if let Some(prototype) = entity_registry.get(&spawn.entity_id) {
                 info!("Spawning {} at ({}, {}) in instance {}",
                     spawn.entity_id, spawn.x, spawn.y, self.id);
-                let npc = Npc::from_prototype(
+                let mut npc = Npc::from_prototype(
                     &npc_id,
                     &spawn.entity_id,
                     prototype,
@@ -72,6 +82,8 @@ impl Instance {
                     spawn.y,
                     spawn.level,
                 );
+                npc.routine = crate::routine::Routine::new(&spawn.patrol, &spawn.schedule);
+                npc.respawns = spawn.respawn;
                 npcs.insert(npc_id, npc);
             } else {
                 tracing::warn!("Prototype '{}' not found for instance {}", spawn.entity_id, self.id);
@@ -125,6 +137,8 @@ impl InstanceManager {
             players: RwLock::new(HashSet::new()),
             npcs: RwLock::new(HashMap::new()),
             npcs_spawned: RwLock::new(false),
+            generated_map: None,
+            nav_grid: OnceLock::new(),
         });
 
         self.public_instances.insert(map_id.to_string(), instance.clone());
@@ -149,6 +163,8 @@ impl InstanceManager {
             players: RwLock::new(HashSet::new()),
             npcs: RwLock::new(HashMap::new()),
             npcs_spawned: RwLock::new(false),
+            generated_map: None,
+            nav_grid: OnceLock::new(),
         });
 
         self.private_instances.insert(key, instance.clone());
@@ -156,6 +172,33 @@ impl InstanceManager {
         (instance, true)
     }
 
+    /// Get or create a private instance for a generated map. The map is only used
+    /// if the owner doesn't already have an instance with the same map ID.
+    pub fn get_or_create_generated(&self, map: InteriorMapDef, owner_id: &str) -> (Arc<Instance>, bool) {
+        let key = (owner_id.to_string(), map.id.clone());
+
+        if let Some(instance) = self.private_instances.get(&key) {
+            return (instance.clone(), false);
+        }
+
+        let instance_id = format!("priv_{}_{}", map.id, Uuid::new_v4());
+        let instance = Arc::new(Instance {
+            id: instance_id.clone(),
+            map_id: map.id.clone(),
+            instance_type: InstanceType::Private,
+            owner_id: Some(owner_id.to_string()),
+            players: RwLock::new(HashSet::new()),
+            npcs: RwLock::new(HashMap::new()),
+            npcs_spawned: RwLock::new(false),
+            generated_map: Some(Arc::new(map)),
+            nav_grid: OnceLock::new(),
+        });
+
+        self.private_instances.insert(key, instance.clone());
+        info!("Created generated instance: {} for owner {}", instance_id, owner_id);
+        (instance, true)
+    }
+
     /// Remove a private instance (called when empty)
     pub fn remove_private(&self, owner_id: &str, map_id: &str) {
         let key = (owner_id.to_string(), map_id.to_string());
@@ -164,6 +207,20 @@ impl InstanceManager {
         }
     }
 
+    /// Instances with at least one player in them
+    pub async fn occupied_instances(&self) -> Vec<Arc<Instance>> {
+        let all: Vec<Arc<Instance>> = self.public_instances.iter().map(|entry| entry.value().clone())
+            .chain(self.private_instances.iter().map(|entry| entry.value().clone()))
+            .collect();
+        let mut occupied = Vec::new();
+        for instance in all {
+            if instance.player_count().await > 0 {
+                occupied.push(instance);
+            }
+        }
+        occupied
+    }
+
     /// Find which instance a player is in by checking all instances
     pub async fn find_player_instance(&self, player_id: &str) -> Option<Arc<Instance>> {
         for entry in self.public_instances.iter() {
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::chunk::PvpZone;
+use crate::routine::{ScheduleEntry, Waypoint};
+
 /// Type of instance this interior creates
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -65,6 +68,12 @@ pub struct InteriorEntitySpawn {
     pub facing: Option<String>,
     #[serde(default = "default_true")]
     pub respawn: bool,
+    /// Waypoints walked in a loop
+    #[serde(default)]
+    pub patrol: Vec<Waypoint>,
+    /// Time-of-day routes, overriding the patrol
+    #[serde(default)]
+    pub schedule: Vec<ScheduleEntry>,
 }
 
 fn default_level() -> i32 { 1 }
@@ -90,6 +99,8 @@ pub struct InteriorMapDef {
     pub map_objects: Vec<InteriorMapObject>,
     #[serde(default)]
     pub walls: Vec<InteriorWall>,
+    #[serde(default, rename = "pvpZones")]
+    pub pvp_zones: Vec<PvpZone>,
 }
 
 /// Layer data for interior maps
@@ -121,6 +132,30 @@ impl InteriorMapDef {
         self.spawn_points.get(name)
     }
 
+    /// Whether a tile is outside the map or blocked by collision
+    pub fn is_blocked(&self, x: i32, y: i32) -> bool {
+        self.is_blocked_in(&self.packed_collision(), x, y)
+    }
+
+    /// Collision bits decoded from the base64 layer, for repeated `is_blocked_in` checks
+    pub fn packed_collision(&self) -> Vec<u8> {
+        use base64::Engine;
+        base64::engine::general_purpose::STANDARD.decode(&self.collision).unwrap_or_default()
+    }
+
+    /// `is_blocked` against collision already decoded by `packed_collision`
+    pub fn is_blocked_in(&self, packed: &[u8], x: i32, y: i32) -> bool {
+        if x < 0 || y < 0 || x >= self.size.width as i32 || y >= self.size.height as i32 {
+            return true;
+        }
+        let index = (y as u32 * self.size.width + x as u32) as usize;
+        packed.get(index / 8).map(|byte| byte & (1 << (index % 8)) != 0).unwrap_or(false)
+    }
+
+    pub fn is_pvp_zone(&self, x: i32, y: i32) -> bool {
+        self.pvp_zones.iter().any(|zone| zone.contains(x, y))
+    }
+
     pub fn get_portal_at(&self, x: i32, y: i32) -> Option<&InteriorPortal> {
         self.portals.iter().find(|p| {
             x >= p.x && x < p.x + p.width && y >= p.y && y < p.y + p.height
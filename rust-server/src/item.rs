@@ -97,16 +97,18 @@ impl Inventory {
 
         if let Some(ref mut slot) = self.slots[slot_index] {
             // Check if item is usable via registry
-            let is_usable = registry
+            let (is_usable, consumed) = registry
                 .get(&slot.item_id)
-                .map(|def| def.is_usable())
-                .unwrap_or(false);
+                .map(|def| (def.is_usable(), def.consumed_on_use()))
+                .unwrap_or((false, false));
 
             if is_usable {
                 let item_id = slot.item_id.clone();
-                slot.quantity -= 1;
-                if slot.quantity <= 0 {
-                    self.slots[slot_index] = None;
+                if consumed {
+                    slot.quantity -= 1;
+                    if slot.quantity <= 0 {
+                        self.slots[slot_index] = None;
+                    }
                 }
                 return Some(item_id);
             }
@@ -233,8 +235,11 @@ pub struct GroundItem {
     pub y: f32,
     pub quantity: i32,
     pub owner_id: Option<String>,    // Player who can pick up (None = anyone)
+    pub party_id: Option<String>,    // Party whose members can also pick up during the owner period
     pub drop_time: u64,              // When the item was dropped
     pub instance_id: Option<String>, // Which instance this item is in (None = overworld)
+    pub dropped_by_player: bool,     // Dropped from a player's inventory rather than loot
+    pub persistent: bool,            // Placed with the map (dungeon treasure); never expires
 }
 
 impl GroundItem {
@@ -254,8 +259,11 @@ impl GroundItem {
             y,
             quantity,
             owner_id,
+            party_id: None,
             drop_time: current_time,
             instance_id: None,
+            dropped_by_player: false,
+            persistent: false,
         }
     }
 
@@ -276,23 +284,30 @@ impl GroundItem {
             y,
             quantity,
             owner_id,
+            party_id: None,
             drop_time: current_time,
             instance_id,
+            dropped_by_player: false,
+            persistent: false,
         }
     }
 
     /// Check if the item has expired (60 second lifetime)
     pub fn is_expired(&self, current_time: u64) -> bool {
         const ITEM_LIFETIME_MS: u64 = 60000; // 60 seconds
-        current_time - self.drop_time > ITEM_LIFETIME_MS
+        !self.persistent && current_time - self.drop_time > ITEM_LIFETIME_MS
     }
 
-    /// Check if a player can pick up this item
-    pub fn can_pickup(&self, player_id: &str, current_time: u64) -> bool {
+    /// Check if a player (in `player_party`, if any) can pick up this item
+    pub fn can_pickup(&self, player_id: &str, player_party: Option<&str>, current_time: u64) -> bool {
         // Owner-only period: first 10 seconds
         const OWNER_PERIOD_MS: u64 = 10000;
 
         if current_time - self.drop_time < OWNER_PERIOD_MS {
+            // Party loot can be picked up by any member of the owning party
+            if self.party_id.is_some() && self.party_id.as_deref() == player_party {
+                return true;
+            }
             // During owner period, only owner can pick up
             match &self.owner_id {
                 Some(owner) => owner == player_id,
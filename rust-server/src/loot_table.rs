@@ -0,0 +1,405 @@
+//! Shared loot tables
+//!
+//! Named tables from `data/loot_tables/*.toml` (see `shared.toml` for the format)
+//! are referenced by ID from entity prototypes, dungeons and quest rewards.
+
+use rand::Rng;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use tracing::{info, warn};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Tables nested deeper than this are skipped, which also stops reference cycles
+const MAX_TABLE_DEPTH: usize = 8;
+
+fn default_one() -> i32 { 1 }
+fn default_chance() -> f32 { 1.0 }
+fn default_rolls() -> u32 { 1 }
+
+// ============================================================================
+// Definitions
+// ============================================================================
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Rarity {
+    pub weight: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LootTableEntry {
+    #[serde(default)]
+    pub item: Option<String>,
+    /// Another table to roll instead of an item
+    #[serde(default)]
+    pub table: Option<String>,
+    /// Weight within a `pick` group
+    #[serde(default)]
+    pub weight: Option<u32>,
+    #[serde(default)]
+    pub rarity: Option<String>,
+    /// Chance for `drops` entries
+    #[serde(default = "default_chance")]
+    pub chance: f32,
+    #[serde(default = "default_one")]
+    pub quantity_min: i32,
+    #[serde(default = "default_one")]
+    pub quantity_max: i32,
+    #[serde(default)]
+    pub quantity_per_level: f32,
+    #[serde(default)]
+    pub min_level: i32,
+}
+
+impl LootTableEntry {
+    /// Quantity at a level, before any chance rolls
+    pub fn roll_quantity(&self, level: i32, rng: &mut impl Rng) -> i32 {
+        let base = rng.gen_range(self.quantity_min..=self.quantity_max.max(self.quantity_min));
+        base + (self.quantity_per_level * level as f32).floor() as i32
+    }
+}
+
+/// Pick one entry by weight
+#[derive(Debug, Clone, Deserialize)]
+pub struct LootPick {
+    #[serde(default = "default_rolls")]
+    pub rolls: u32,
+    #[serde(default = "default_chance")]
+    pub chance: f32,
+    pub entries: Vec<LootTableEntry>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LootTable {
+    #[serde(default)]
+    pub guaranteed: Vec<LootTableEntry>,
+    #[serde(default)]
+    pub drops: Vec<LootTableEntry>,
+    #[serde(default)]
+    pub pick: Vec<LootPick>,
+}
+
+impl LootTable {
+    fn entries(&self) -> impl Iterator<Item = &LootTableEntry> {
+        self.guaranteed.iter()
+            .chain(self.drops.iter())
+            .chain(self.pick.iter().flat_map(|pick| pick.entries.iter()))
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RawLootTableFile {
+    #[serde(default)]
+    rarities: HashMap<String, Rarity>,
+    #[serde(default)]
+    tables: HashMap<String, LootTable>,
+}
+
+/// One stack produced by rolling a table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RolledLoot {
+    pub item_id: String,
+    pub quantity: i32,
+    /// Rarity tier of the entry that dropped it
+    pub rarity: Option<String>,
+}
+
+// ============================================================================
+// Registry
+// ============================================================================
+
+#[derive(Debug, Default)]
+pub struct LootTableRegistry {
+    rarities: HashMap<String, Rarity>,
+    tables: HashMap<String, LootTable>,
+}
+
+impl LootTableRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load every table file from `<data_dir>/loot_tables`
+    pub fn load_from_directory(&mut self, data_dir: &Path) -> Result<(), String> {
+        let path = data_dir.join("loot_tables");
+        if !path.exists() {
+            warn!("Loot table directory does not exist: {:?}", path);
+            return Ok(());
+        }
+
+        for entry in fs::read_dir(&path).map_err(|e| e.to_string())? {
+            let file_path = entry.map_err(|e| e.to_string())?.path();
+            if file_path.extension().is_some_and(|ext| ext == "toml") {
+                let content = fs::read_to_string(&file_path)
+                    .map_err(|e| format!("Failed to read {:?}: {}", file_path, e))?;
+                self.load_from_str(&content)
+                    .map_err(|e| format!("Failed to parse {:?}: {}", file_path, e))?;
+            }
+        }
+
+        for problem in self.validate() {
+            warn!("Loot tables: {}", problem);
+        }
+        info!("Loaded {} loot tables", self.tables.len());
+        Ok(())
+    }
+
+    pub fn load_from_str(&mut self, content: &str) -> Result<(), String> {
+        let raw: RawLootTableFile = toml::from_str(content).map_err(|e| e.to_string())?;
+        self.rarities.extend(raw.rarities);
+        self.tables.extend(raw.tables);
+        Ok(())
+    }
+
+    /// Unknown table and rarity references, one message each
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        let mut ids: Vec<&String> = self.tables.keys().collect();
+        ids.sort();
+        for id in ids {
+            for entry in self.tables[id].entries() {
+                if let Some(table) = entry.table.as_ref().filter(|table| !self.tables.contains_key(*table)) {
+                    problems.push(format!("table '{}' references unknown table '{}'", id, table));
+                }
+                if let Some(rarity) = entry.rarity.as_ref().filter(|rarity| !self.rarities.contains_key(*rarity)) {
+                    problems.push(format!("table '{}' uses unknown rarity '{}'", id, rarity));
+                }
+            }
+        }
+        problems
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.tables.contains_key(id)
+    }
+
+    /// Weight of an entry within a `pick` group
+    fn weight(&self, entry: &LootTableEntry) -> u32 {
+        entry.weight
+            .or_else(|| entry.rarity.as_ref().and_then(|rarity| self.rarities.get(rarity)).map(|rarity| rarity.weight))
+            .unwrap_or(1)
+    }
+
+    /// Roll a table for a monster, chest or reward of the given level
+    pub fn roll(&self, id: &str, level: i32, rng: &mut impl Rng) -> Vec<RolledLoot> {
+        let mut loot = Vec::new();
+        self.roll_into(id, level, rng, 0, &mut loot);
+        loot
+    }
+
+    fn roll_into(&self, id: &str, level: i32, rng: &mut impl Rng, depth: usize, loot: &mut Vec<RolledLoot>) {
+        if depth >= MAX_TABLE_DEPTH {
+            warn!("Loot table '{}' is nested too deeply, skipping", id);
+            return;
+        }
+        let Some(table) = self.tables.get(id) else {
+            warn!("Unknown loot table '{}'", id);
+            return;
+        };
+
+        for entry in table.guaranteed.iter().filter(|entry| entry.min_level <= level) {
+            self.drop_entry(entry, level, rng, depth, loot);
+        }
+        for entry in table.drops.iter().filter(|entry| entry.min_level <= level) {
+            if rng.r#gen::<f32>() < entry.chance {
+                self.drop_entry(entry, level, rng, depth, loot);
+            }
+        }
+        for pick in &table.pick {
+            let entries: Vec<&LootTableEntry> = pick.entries.iter().filter(|entry| entry.min_level <= level).collect();
+            let total: u32 = entries.iter().map(|entry| self.weight(entry)).sum();
+            if total == 0 {
+                continue;
+            }
+            for _ in 0..pick.rolls {
+                if rng.r#gen::<f32>() >= pick.chance {
+                    continue;
+                }
+                let mut roll = rng.gen_range(0..total);
+                for entry in &entries {
+                    let weight = self.weight(entry);
+                    if roll < weight {
+                        self.drop_entry(entry, level, rng, depth, loot);
+                        break;
+                    }
+                    roll -= weight;
+                }
+            }
+        }
+    }
+
+    fn drop_entry(&self, entry: &LootTableEntry, level: i32, rng: &mut impl Rng, depth: usize, loot: &mut Vec<RolledLoot>) {
+        let quantity = entry.roll_quantity(level, rng);
+        if quantity <= 0 {
+            return;
+        }
+        if let Some(table) = &entry.table {
+            for _ in 0..quantity {
+                self.roll_into(table, level, rng, depth + 1, loot);
+            }
+        } else if let Some(item_id) = &entry.item {
+            loot.push(RolledLoot { item_id: item_id.clone(), quantity, rarity: entry.rarity.clone() });
+        }
+    }
+
+    /// Average quantity of each item one roll of a table drops at a level
+    pub fn expected(&self, id: &str, level: i32) -> HashMap<String, f64> {
+        let mut expected = HashMap::new();
+        self.expected_into(id, level, 1.0, 0, &mut expected);
+        expected
+    }
+
+    fn expected_into(&self, id: &str, level: i32, scale: f64, depth: usize, expected: &mut HashMap<String, f64>) {
+        if depth >= MAX_TABLE_DEPTH {
+            return;
+        }
+        let Some(table) = self.tables.get(id) else {
+            return;
+        };
+
+        for entry in table.guaranteed.iter().filter(|entry| entry.min_level <= level) {
+            self.expected_entry(entry, level, scale, depth, expected);
+        }
+        for entry in table.drops.iter().filter(|entry| entry.min_level <= level) {
+            let chance = entry.chance.clamp(0.0, 1.0) as f64;
+            self.expected_entry(entry, level, scale * chance, depth, expected);
+        }
+        for pick in &table.pick {
+            let entries: Vec<&LootTableEntry> = pick.entries.iter().filter(|entry| entry.min_level <= level).collect();
+            let total: u32 = entries.iter().map(|entry| self.weight(entry)).sum();
+            if total == 0 {
+                continue;
+            }
+            let picks = pick.rolls as f64 * pick.chance.clamp(0.0, 1.0) as f64;
+            for entry in entries {
+                let share = self.weight(entry) as f64 / total as f64;
+                self.expected_entry(entry, level, scale * picks * share, depth, expected);
+            }
+        }
+    }
+
+    fn expected_entry(&self, entry: &LootTableEntry, level: i32, scale: f64, depth: usize, expected: &mut HashMap<String, f64>) {
+        let bonus = (entry.quantity_per_level * level as f32).floor() as i32;
+        let max = entry.quantity_max.max(entry.quantity_min);
+        let total: i64 = (entry.quantity_min..=max).map(|quantity| (quantity + bonus).max(0) as i64).sum();
+        let quantity = total as f64 / (max - entry.quantity_min + 1) as f64;
+
+        if let Some(table) = &entry.table {
+            self.expected_into(table, level, scale * quantity, depth + 1, expected);
+        } else if let Some(item_id) = &entry.item {
+            *expected.entry(item_id.clone()).or_insert(0.0) += scale * quantity;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn registry(toml_src: &str) -> LootTableRegistry {
+        let mut registry = LootTableRegistry::new();
+        registry.load_from_str(toml_src).unwrap();
+        registry
+    }
+
+    fn total(loot: &[RolledLoot], item_id: &str) -> i32 {
+        loot.iter().filter(|l| l.item_id == item_id).map(|l| l.quantity).sum()
+    }
+
+    #[test]
+    fn test_guaranteed_drops_scale_with_level() {
+        let tables = registry(r#"
+            [tables.chest]
+            guaranteed = [
+                { item = "gold", quantity_min = 10, quantity_max = 10, quantity_per_level = 2.5 },
+                { item = "dark_essence", min_level = 10 },
+            ]
+        "#);
+        let mut rng = StdRng::seed_from_u64(1);
+        assert_eq!(tables.roll("chest", 4, &mut rng), vec![
+            RolledLoot { item_id: "gold".to_string(), quantity: 20, rarity: None },
+        ]);
+        let high = tables.roll("chest", 10, &mut rng);
+        assert_eq!(total(&high, "gold"), 35);
+        assert_eq!(total(&high, "dark_essence"), 1);
+        assert!(tables.roll("missing", 1, &mut rng).is_empty());
+    }
+
+    #[test]
+    fn test_pick_groups_use_weights_and_rarities() {
+        let tables = registry(r#"
+            [rarities.rare]
+            weight = 1
+
+            [[tables.mob.pick]]
+            rolls = 2
+            entries = [
+                { weight = 3 },
+                { item = "bones", weight = 6 },
+                { item = "whistle", rarity = "rare" },
+            ]
+        "#);
+        let mut rng = StdRng::seed_from_u64(9);
+        let (mut bones, mut whistles) = (0, 0);
+        for _ in 0..1000 {
+            let loot = tables.roll("mob", 1, &mut rng);
+            assert!(loot.len() <= 2);
+            bones += total(&loot, "bones");
+            whistles += total(&loot, "whistle");
+            assert!(loot.iter().filter(|l| l.item_id == "whistle").all(|l| l.rarity.as_deref() == Some("rare")));
+        }
+        // 2000 picks: 60% bones, 10% whistles
+        assert!((1100..1300).contains(&bones), "{}", bones);
+        assert!((140..260).contains(&whistles), "{}", whistles);
+    }
+
+    #[test]
+    fn test_nested_tables_and_bad_references() {
+        let tables = registry(r#"
+            [tables.pig]
+            drops = [{ table = "rare_drop_table", quantity_min = 2, quantity_max = 2 }]
+
+            [tables.rare_drop_table]
+            guaranteed = [{ item = "ruby" }]
+
+            [tables.loop]
+            guaranteed = [{ item = "pebble" }, { table = "loop" }]
+
+            [tables.broken]
+            guaranteed = [{ table = "nowhere" }, { item = "x", rarity = "mythic" }]
+        "#);
+        let mut rng = StdRng::seed_from_u64(5);
+        assert_eq!(total(&tables.roll("pig", 1, &mut rng), "ruby"), 2);
+        // A cycle stops at the depth limit
+        assert_eq!(total(&tables.roll("loop", 1, &mut rng), "pebble"), MAX_TABLE_DEPTH as i32);
+        assert_eq!(tables.validate(), vec![
+            "table 'broken' references unknown table 'nowhere'".to_string(),
+            "table 'broken' uses unknown rarity 'mythic'".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_expected_quantities_follow_chances_and_weights() {
+        let tables = registry(r#"
+            [tables.pig]
+            guaranteed = [{ item = "gold", quantity_min = 1, quantity_max = 3, quantity_per_level = 1.0 }]
+            drops = [{ table = "gems", chance = 0.5, quantity_min = 2, quantity_max = 2 }]
+
+            [[tables.gems.pick]]
+            rolls = 2
+            entries = [{ weight = 3 }, { item = "ruby", weight = 1 }, { item = "opal", min_level = 5 }]
+        "#);
+        let expected = tables.expected("pig", 2);
+        assert_eq!(expected["gold"], 4.0);
+        // Half the kills roll gems twice, each picking twice with a 1 in 4 chance
+        assert_eq!(expected["ruby"], 0.5);
+        assert!(!expected.contains_key("opal"));
+        assert!(tables.expected("missing", 1).is_empty());
+    }
+}
@@ -0,0 +1,66 @@
+//! Sandboxed Lua states for boss and world scripts
+//!
+//! Scripts get the table, string, math and utf8 libraries only, a memory cap,
+//! and an instruction budget that `reset_budget` refills before each call.
+
+use mlua::{HookTriggers, Lua, LuaOptions, Result as LuaResult, StdLib, Value, VmState};
+
+const MEMORY_LIMIT_BYTES: usize = 32 * 1024 * 1024;
+/// Instructions a single load or hook call may run
+const MAX_INSTRUCTIONS_PER_CALL: u32 = 1_000_000;
+/// How often the instruction budget is checked
+const INSTRUCTION_CHECK_INTERVAL: u32 = 1000;
+
+/// Instruction checks left for the running call
+struct InstructionBudget(u32);
+
+/// Create a Lua state without os, io, package or debug access
+pub fn new_sandbox() -> LuaResult<Lua> {
+    let lua = Lua::new_with(StdLib::TABLE | StdLib::STRING | StdLib::MATH | StdLib::UTF8, LuaOptions::default())?;
+    let globals = lua.globals();
+    for name in ["loadfile", "dofile", "load", "collectgarbage"] {
+        globals.set(name, Value::Nil)?;
+    }
+    lua.set_memory_limit(MEMORY_LIMIT_BYTES)?;
+    lua.set_app_data(InstructionBudget(0));
+    lua.set_hook(HookTriggers::new().every_nth_instruction(INSTRUCTION_CHECK_INTERVAL), |lua, _| {
+        let Some(mut budget) = lua.app_data_mut::<InstructionBudget>() else {
+            return Ok(VmState::Continue);
+        };
+        if budget.0 == 0 {
+            return Err(mlua::Error::RuntimeError("script ran too long".to_string()));
+        }
+        budget.0 -= 1;
+        Ok(VmState::Continue)
+    });
+    Ok(lua)
+}
+
+/// Give the next call a full instruction budget
+pub fn reset_budget(lua: &Lua) {
+    if let Some(mut budget) = lua.app_data_mut::<InstructionBudget>() {
+        budget.0 = MAX_INSTRUCTIONS_PER_CALL / INSTRUCTION_CHECK_INTERVAL;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sandbox_limits() {
+        let lua = new_sandbox().unwrap();
+        reset_budget(&lua);
+        let sandboxed: bool = lua.load("return os == nil and io == nil and load == nil and require == nil").eval().unwrap();
+        assert!(sandboxed);
+
+        reset_budget(&lua);
+        assert!(lua.load("while true do end").exec().is_err());
+        reset_budget(&lua);
+        assert!(lua.load("local t = {} for i = 1, 1e7 do t[i] = string.rep('x', 64) .. i end").exec().is_err());
+
+        // A fresh budget runs normally after a failure
+        reset_budget(&lua);
+        assert_eq!(lua.load("return 1 + 1").eval::<i32>().unwrap(), 2);
+    }
+}
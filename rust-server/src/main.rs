@@ -27,32 +27,61 @@ use crate::skills::Skills;
 use crate::data::item_def::EquipmentSlot;
 use sqlx::Row;
 
+mod achievement;
+mod behavior;
+mod boss;
+mod challenge;
 mod chunk;
 mod crafting;
 mod data;
 mod db;
+mod death;
+mod dungeon;
+mod economy;
+mod elite;
 mod entity;
 mod game;
+mod guild;
+mod house;
 mod instance;
 mod interior;
 mod interior_registry;
 mod item;
+mod loot_table;
+mod lua_sandbox;
 mod npc;
+mod party;
+mod pathfinding;
+mod pet;
 mod protocol;
+mod pvp;
 mod quest;
+mod reputation;
+mod routine;
 mod shop;
+mod simulate;
 mod skills;
+mod spawn_region;
+mod status_effect;
+mod threat;
 mod tilemap;
+mod trade;
 mod world;
+mod world_event;
+mod world_script;
 
+use achievement::AchievementRegistry;
 use crafting::CraftingRegistry;
 use data::ItemRegistry;
 use db::Database;
+use dungeon::{DungeonRegistry, DUNGEON_PORTAL_PREFIX};
+use house::{HOUSE_PORTAL_TARGET, HOUSE_SPAWN_POINT};
 use entity::EntityRegistry;
 use instance::InstanceManager;
 use interior_registry::InteriorRegistry;
+use loot_table::LootTableRegistry;
 use quest::QuestRegistry;
-use game::{GameRoom, Player, PlayerUpdate};
+use game::{GameRoom, Player, PlayerUpdate, RoomServices};
 use protocol::{ClientMessage, ServerMessage};
 
 // ============================================================================
@@ -87,7 +116,10 @@ struct AppState {
     item_registry: Arc<ItemRegistry>,
     quest_registry: Arc<QuestRegistry>,
     crafting_registry: Arc<CraftingRegistry>,
+    achievement_registry: Arc<AchievementRegistry>,
     interior_registry: Arc<InteriorRegistry>,
+    dungeon_registry: Arc<DungeonRegistry>,
+    loot_tables: Arc<LootTableRegistry>,
     instance_manager: Arc<InstanceManager>,
     /// Tracks which instance each player is currently in (None = overworld)
     player_instances: Arc<RwLock<HashMap<String, String>>>,
@@ -129,12 +161,40 @@ impl AppState {
             error!("Failed to load crafting registry: {}", e);
         }
 
+        // Load achievement registry from TOML files
+        let mut achievement_registry = AchievementRegistry::new();
+        if let Err(e) = achievement_registry.load_from_directory(data_dir) {
+            error!("Failed to load achievement registry: {}", e);
+        }
+
         // Load interior registry from JSON files
         let interior_registry = Arc::new(
             InteriorRegistry::load_from_directory("maps/interiors")
                 .expect("Failed to load interior registry")
         );
 
+        // Load dungeon templates from TOML files
+        let mut dungeon_registry = DungeonRegistry::new();
+        if let Err(e) = dungeon_registry.load_from_directory(data_dir) {
+            error!("Failed to load dungeon registry: {}", e);
+        }
+
+        // Load shared loot tables and check everything that references them
+        let mut loot_tables = LootTableRegistry::new();
+        if let Err(e) = loot_tables.load_from_directory(data_dir) {
+            error!("Failed to load loot tables: {}", e);
+        }
+        for prototype in entity_registry.all() {
+            if let Some(table) = prototype.loot_table.as_ref().filter(|table| !loot_tables.contains(table)) {
+                warn!("Prototype '{}' references unknown loot table '{}'", prototype.id, table);
+            }
+        }
+        for (id, table) in dungeon_registry.treasure_tables() {
+            if !loot_tables.contains(table) {
+                warn!("Dungeon '{}' references unknown loot table '{}'", id, table);
+            }
+        }
+
         // Initialize instance manager
         let instance_manager = Arc::new(InstanceManager::new());
 
@@ -179,7 +239,10 @@ impl AppState {
             item_registry: Arc::new(item_registry),
             quest_registry,
             crafting_registry: Arc::new(crafting_registry),
+            achievement_registry: Arc::new(achievement_registry),
             interior_registry,
+            dungeon_registry: Arc::new(dungeon_registry),
+            loot_tables: Arc::new(loot_tables),
             instance_manager,
             player_instances: Arc::new(RwLock::new(HashMap::new())),
             player_entrance_positions: Arc::new(RwLock::new(HashMap::new())),
@@ -196,15 +259,18 @@ impl AppState {
         }
 
         // Create new room and store by its UUID
-        let room = Arc::new(GameRoom::new(
-            room_name,
-            self.entity_registry.clone(),
-            self.quest_registry.clone(),
-            self.crafting_registry.clone(),
-            self.item_registry.clone(),
-            self.player_instances.clone(),
-            self.instance_manager.clone(),
-        ).await);
+        let room = Arc::new(GameRoom::new(room_name, RoomServices {
+            entity_registry: self.entity_registry.clone(),
+            loot_tables: self.loot_tables.clone(),
+            quest_registry: self.quest_registry.clone(),
+            crafting_registry: self.crafting_registry.clone(),
+            item_registry: self.item_registry.clone(),
+            player_instances: self.player_instances.clone(),
+            instance_manager: self.instance_manager.clone(),
+            db: self.db.clone(),
+            achievement_registry: self.achievement_registry.clone(),
+            interior_registry: self.interior_registry.clone(),
+        }).await);
         self.rooms.insert(room.id.clone(), room.clone());
         room
     }
@@ -533,6 +599,8 @@ struct CharacterInfo {
     hair_color: Option<i32>,
     #[serde(rename = "playedTime")]
     played_time: i64,
+    /// Died under a hardcore death rule and can no longer be played
+    perished: bool,
 }
 
 #[derive(Deserialize)]
@@ -597,6 +665,7 @@ async fn list_characters(
                 hair_style: c.hair_style,
                 hair_color: c.hair_color,
                 played_time: c.played_time,
+                perished: c.perished,
             }).collect();
 
             (
@@ -708,6 +777,7 @@ async fn create_character(
                         hair_style: char_data.hair_style,
                         hair_color: char_data.hair_color,
                         played_time: char_data.played_time,
+                        perished: char_data.perished,
                     }),
                     error: None,
                 }),
@@ -884,6 +954,13 @@ async fn matchmake_join_or_create(
                     Json(serde_json::json!({ "error": "Character does not belong to this account" }))
                 ).into_response();
             }
+            if char.perished {
+                warn!("Matchmaking rejected: Character {} has perished", character_id);
+                return (
+                    StatusCode::FORBIDDEN,
+                    Json(serde_json::json!({ "error": "This character has perished and can no longer be played" }))
+                ).into_response();
+            }
             char
         }
         Ok(None) => {
@@ -932,6 +1009,7 @@ async fn matchmake_join_or_create(
 
     room.reserve_player_with_data(
         &player_id,
+        character_id,
         &character_data.name,
         character_data.x as i32,
         character_data.y as i32,
@@ -972,6 +1050,30 @@ async fn matchmake_join_or_create(
         }
     }
 
+    // Load faction reputation from database
+    match state.db.load_character_reputation(character_id).await {
+        Ok(reputation) => room.set_player_reputation(&player_id, reputation).await,
+        Err(e) => tracing::warn!("Failed to load reputation for character {}: {}", character_id, e),
+    }
+
+    // Load daily/weekly challenge progress from database
+    match state.db.load_character_challenges(character_id).await {
+        Ok(challenges) => room.set_player_challenges(&player_id, challenges).await,
+        Err(e) => tracing::warn!("Failed to load challenges for character {}: {}", character_id, e),
+    }
+
+    // Load pets from database (re-summons the pet that was out on logout)
+    match state.db.load_character_pets(character_id).await {
+        Ok(pets) => room.set_player_pets(&player_id, pets).await,
+        Err(e) => tracing::warn!("Failed to load pets for character {}: {}", character_id, e),
+    }
+
+    // Load house, furniture and guest list from database
+    match state.db.load_character_house(character_id).await {
+        Ok(house) => room.set_player_house(&player_id, house).await,
+        Err(e) => tracing::warn!("Failed to load house for character {}: {}", character_id, e),
+    }
+
     let client_count = room.player_count().await;
 
     // Generate signed session token for WebSocket upgrade
@@ -1114,6 +1216,221 @@ async fn stats_leaderboard(
     Json(entries)
 }
 
+#[derive(Deserialize)]
+struct GuildLeaderboardQuery {
+    #[serde(default = "default_guild_leaderboard_sort")]
+    sort: String,
+    #[serde(default = "default_leaderboard_limit")]
+    limit: usize,
+}
+
+fn default_guild_leaderboard_sort() -> String { "total_level".to_string() }
+
+#[derive(Serialize)]
+struct GuildLeaderboardEntry {
+    name: String,
+    tag: String,
+    leader: String,
+    member_count: i32,
+    total_level: i32,
+    average_combat_level: f32,
+    created_at: Option<String>,
+}
+
+async fn stats_guilds(
+    State(state): State<AppState>,
+    Query(query): Query<GuildLeaderboardQuery>,
+) -> impl IntoResponse {
+    let order = match query.sort.as_str() {
+        "member_count" => "member_count DESC",
+        "average_combat_level" => "average_combat_level DESC",
+        _ => "total_level DESC",
+    };
+    // Member levels are read out of each character's skills JSON
+    let sql = format!(
+        r#"SELECT g.name, g.tag, g.created_at,
+                  MAX(CASE WHEN m.rank_index = 0 THEN c.name END) AS leader,
+                  COUNT(*) AS member_count,
+                  SUM(json_extract(c.skills_json, '$.hitpoints.level') + json_extract(c.skills_json, '$.combat.level')) AS total_level,
+                  AVG((json_extract(c.skills_json, '$.hitpoints.level') + json_extract(c.skills_json, '$.combat.level')) / 2) AS average_combat_level
+           FROM guilds g
+           JOIN guild_members m ON m.guild_id = g.id
+           JOIN characters c ON c.id = m.character_id
+           GROUP BY g.id
+           ORDER BY {}
+           LIMIT ?"#,
+        order
+    );
+    let pool = state.db.pool();
+    let rows = sqlx::query(&sql)
+        .bind(query.limit.min(100) as i64)
+        .fetch_all(pool)
+        .await
+        .unwrap_or_default();
+
+    let entries: Vec<GuildLeaderboardEntry> = rows
+        .iter()
+        .map(|row| GuildLeaderboardEntry {
+            name: row.try_get("name").unwrap_or_default(),
+            tag: row.try_get("tag").unwrap_or_default(),
+            leader: row.try_get::<Option<String>, _>("leader").ok().flatten().unwrap_or_default(),
+            member_count: row.try_get("member_count").unwrap_or(0),
+            total_level: row.try_get("total_level").unwrap_or(0),
+            average_combat_level: row.try_get::<f64, _>("average_combat_level").unwrap_or(0.0) as f32,
+            created_at: row.try_get("created_at").ok(),
+        })
+        .collect();
+
+    Json(entries)
+}
+
+#[derive(Deserialize)]
+struct PvpLeaderboardQuery {
+    #[serde(default = "default_pvp_leaderboard_sort")]
+    sort: String,
+    #[serde(default = "default_leaderboard_limit")]
+    limit: usize,
+}
+
+fn default_pvp_leaderboard_sort() -> String { "kills".to_string() }
+
+#[derive(Serialize)]
+struct PvpLeaderboardEntry {
+    name: String,
+    kills: i64,
+    deaths: i64,
+    zone_kills: i64,
+}
+
+async fn stats_pvp(
+    State(state): State<AppState>,
+    Query(query): Query<PvpLeaderboardQuery>,
+) -> impl IntoResponse {
+    let order = match query.sort.as_str() {
+        "deaths" => "deaths DESC",
+        "zone_kills" => "zone_kills DESC",
+        _ => "kills DESC, deaths ASC",
+    };
+    // Each kill counts once for the killer and once for the victim. With
+    // MAX(id), SQLite takes the name from each character's latest kill.
+    let sql = format!(
+        r#"SELECT name, MAX(id) AS last_kill_id,
+                  SUM(is_kill) AS kills, SUM(is_death) AS deaths, SUM(is_zone_kill) AS zone_kills
+           FROM (
+               SELECT id, killer_character_id AS character_id, killer_name AS name,
+                      1 AS is_kill, 0 AS is_death, in_pvp_zone AS is_zone_kill
+               FROM pvp_kills
+               UNION ALL
+               SELECT id, victim_character_id, victim_name, 0, 1, 0
+               FROM pvp_kills
+           )
+           GROUP BY character_id
+           ORDER BY {}
+           LIMIT ?"#,
+        order
+    );
+    let pool = state.db.pool();
+    let rows = sqlx::query(&sql)
+        .bind(query.limit.min(100) as i64)
+        .fetch_all(pool)
+        .await
+        .unwrap_or_default();
+
+    let entries: Vec<PvpLeaderboardEntry> = rows
+        .iter()
+        .map(|row| PvpLeaderboardEntry {
+            name: row.try_get("name").unwrap_or_default(),
+            kills: row.try_get("kills").unwrap_or(0),
+            deaths: row.try_get("deaths").unwrap_or(0),
+            zone_kills: row.try_get("zone_kills").unwrap_or(0),
+        })
+        .collect();
+
+    Json(entries)
+}
+
+#[derive(Deserialize)]
+struct RecentAchievementsQuery {
+    #[serde(default = "default_leaderboard_limit")]
+    limit: usize,
+}
+
+#[derive(Serialize)]
+struct RecentAchievementEntry {
+    character_name: String,
+    achievement_id: String,
+    achievement_name: String,
+    description: String,
+    title: Option<String>,
+    unlocked_at: i64,
+}
+
+async fn stats_achievements(
+    State(state): State<AppState>,
+    Query(query): Query<RecentAchievementsQuery>,
+) -> impl IntoResponse {
+    let pool = state.db.pool();
+    let rows = sqlx::query(
+        r#"SELECT c.name, a.achievement_id, a.unlocked_at
+           FROM character_achievements a
+           JOIN characters c ON c.id = a.character_id
+           ORDER BY a.unlocked_at DESC
+           LIMIT ?"#
+    )
+    .bind(query.limit.min(100) as i64)
+    .fetch_all(pool)
+    .await
+    .unwrap_or_default();
+
+    let entries: Vec<RecentAchievementEntry> = rows
+        .iter()
+        .filter_map(|row| {
+            let achievement_id: String = row.try_get("achievement_id").ok()?;
+            let achievement = state.achievement_registry.get(&achievement_id)?;
+            Some(RecentAchievementEntry {
+                character_name: row.try_get("name").unwrap_or_default(),
+                achievement_name: achievement.name.clone(),
+                description: achievement.description.clone(),
+                title: achievement.title.clone(),
+                unlocked_at: row.try_get("unlocked_at").unwrap_or(0),
+                achievement_id,
+            })
+        })
+        .collect();
+
+    Json(entries)
+}
+
+#[derive(Serialize)]
+struct SpawnRegionEntry {
+    id: String,
+    entities: Vec<String>,
+    population: usize,
+    max_population: usize,
+    min_level: i32,
+    max_level: i32,
+    respawn_ms: u64,
+}
+
+async fn stats_spawn_regions(State(state): State<AppState>) -> impl IntoResponse {
+    let mut entries = Vec::new();
+    for entry in state.rooms.iter() {
+        for (region, population) in entry.value().spawn_region_populations().await {
+            entries.push(SpawnRegionEntry {
+                id: region.id,
+                entities: region.entities.into_iter().map(|e| e.entity_id).collect(),
+                population,
+                max_population: region.max_population,
+                min_level: region.min_level,
+                max_level: region.max_level,
+                respawn_ms: region.respawn_ms,
+            });
+        }
+    }
+    entries.sort_by(|a, b| a.id.cmp(&b.id));
+    Json(entries)
+}
+
 #[derive(Serialize)]
 struct StatsEquipment {
     slot_type: String,
@@ -1242,7 +1559,7 @@ async fn handle_socket(
     player_id: String,
     session_id: String,
     character_name: String,
-    _character_id: i64,  // Used for future persistence binding
+    character_id: i64,
 ) {
     let (mut sender, mut receiver) = socket.split();
 
@@ -1257,6 +1574,8 @@ async fn handle_socket(
 
     // Activate the player
     let player_name = room.activate_player(&player_id).await;
+    room.load_player_guild(&player_id, character_id).await;
+    room.load_player_achievements(&player_id).await;
     info!("Player {} ({}) connected to room {}", player_name, player_id, room_id);
 
     // Subscribe to room broadcasts
@@ -1317,6 +1636,8 @@ async fn handle_socket(
                 skin: existing_player.skin.clone(),
                 hair_style: existing_player.hair_style,
                 hair_color: existing_player.hair_color,
+                guild_tag: existing_player.guild_tag.clone(),
+                title: existing_player.title.clone(),
             };
             if let Ok(bytes) = protocol::encode_server_message(&msg) {
                 let _ = sender.send(Message::Binary(bytes)).await;
@@ -1342,6 +1663,8 @@ async fn handle_socket(
     let (x, y) = room.get_player_position(&player_id).await.unwrap_or((0, 0));
     let (gender, skin) = room.get_player_appearance(&player_id).await.unwrap_or_else(|| ("male".to_string(), "tan".to_string()));
     let (hair_style, hair_color) = room.get_player_hair(&player_id).await.unwrap_or((None, None));
+    let guild_tag = room.get_player_guild_tag(&player_id).await;
+    let title = room.get_player_title(&player_id).await;
     room.broadcast(ServerMessage::PlayerJoined {
         id: player_id.clone(),
         name: player_name.clone(),
@@ -1351,6 +1674,8 @@ async fn handle_socket(
         skin,
         hair_style,
         hair_color,
+        guild_tag,
+        title,
     })
     .await;
 
@@ -1360,6 +1685,14 @@ async fn handle_socket(
     // SECURITY: Register this player's sender for unicast messages
     room.register_player_sender(&player_id, tx).await;
 
+    // Restore party roster (parties survive a reconnect) and tell the party we're back
+    room.refresh_party_for(&player_id).await;
+    room.send_guild_motd(&player_id).await;
+    room.send_reputation_update(&player_id).await;
+    room.send_achievements_update(&player_id).await;
+    room.send_challenges_update(&player_id).await;
+    room.send_gravestones(&player_id).await;
+
     // Spawn task to forward messages to WebSocket
     let mut send_task = tokio::spawn(async move {
         loop {
@@ -1463,6 +1796,31 @@ async fn handle_socket(
                         character_name, quest_state.active_quests.len(), quest_state.completed_quests.len());
                 }
             }
+            if let Some(reputation) = room.get_player_reputation(&player_id).await
+                && let Err(e) = state.db.save_character_reputation(character_id, &reputation).await
+            {
+                error!("Failed to save reputation for {} on disconnect: {}", character_name, e);
+            }
+            if let Some(achievements) = room.get_player_achievements(&player_id).await
+                && let Err(e) = state.db.save_character_achievements(character_id, &achievements).await
+            {
+                error!("Failed to save achievements for {} on disconnect: {}", character_name, e);
+            }
+            if let Some(challenges) = room.get_player_challenges(&player_id).await
+                && let Err(e) = state.db.save_character_challenges(character_id, &challenges).await
+            {
+                error!("Failed to save challenges for {} on disconnect: {}", character_name, e);
+            }
+            if let Some(pets) = room.get_player_pets(&player_id).await
+                && let Err(e) = state.db.save_character_pets(character_id, &pets).await
+            {
+                error!("Failed to save pets for {} on disconnect: {}", character_name, e);
+            }
+            if let Some(house) = room.get_player_house(&player_id).await
+                && let Err(e) = state.db.save_character_house(character_id, &house).await
+            {
+                error!("Failed to save house for {} on disconnect: {}", character_name, e);
+            }
         }
     } else {
         warn!("Skipping save for {} on disconnect: invalid auth", character_name);
@@ -1478,6 +1836,7 @@ async fn handle_socket(
                 if remaining == 0 && instance.instance_type == InstanceType::Private {
                     if let Some(owner_id) = &instance.owner_id {
                         state.instance_manager.remove_private(owner_id, &instance.map_id);
+                        room.remove_instance_ground_items(&instance.id).await;
                     }
                 }
             }
@@ -1504,8 +1863,6 @@ async fn handle_enter_portal(
     portal_id: &str,
 ) {
     use crate::interior::InstanceType;
-    use crate::protocol::{ChunkLayerData, ChunkPortalData};
-    use base64::Engine;
 
     info!("Player {} attempting to enter portal '{}'", player_id, portal_id);
 
@@ -1519,12 +1876,14 @@ async fn handle_enter_portal(
     if let Some(instance_id) = current_instance_id {
         info!("Player {} is in instance '{}', checking for interior exit portal", player_id, instance_id);
 
-        // Find the interior this instance belongs to
+        // Find the interior this instance belongs to (generated dungeons carry their own map)
+        let generated_map = state.instance_manager.find_player_instance(player_id).await
+            .and_then(|instance| instance.generated_map.clone());
         let interior_id = instance_id.strip_prefix("pub_")
             .or_else(|| instance_id.split('_').nth(1))
             .unwrap_or(&instance_id);
 
-        let interior = match state.interior_registry.get(interior_id) {
+        let interior = match generated_map.as_deref().or_else(|| state.interior_registry.get(interior_id)) {
             Some(i) => i,
             None => {
                 error!("Could not find interior definition for instance '{}'", instance_id);
@@ -1565,6 +1924,7 @@ async fn handle_enter_portal(
                     if remaining == 0 && instance.instance_type == InstanceType::Private {
                         if let Some(owner_id) = &instance.owner_id {
                             state.instance_manager.remove_private(owner_id, &instance.map_id);
+                            room.remove_instance_ground_items(&instance.id).await;
                         }
                     }
 
@@ -1634,6 +1994,7 @@ async fn handle_enter_portal(
                             instance_id: String::new(),
                         },
                     ).await;
+                    room.send_gravestones(player_id).await;
 
                     return;
                 } else {
@@ -1670,6 +2031,67 @@ async fn handle_enter_portal(
         }
     };
 
+    // Dungeon portals generate a new map for the player
+    if let Some(dungeon_id) = portal.target_map.strip_prefix(DUNGEON_PORTAL_PREFIX) {
+        let template = match state.dungeon_registry.get(dungeon_id) {
+            Some(t) => t,
+            None => {
+                error!("Portal '{}' references unknown dungeon '{}'", portal_id, dungeon_id);
+                return;
+            }
+        };
+
+        let level = room.get_player_combat_level(player_id).await.unwrap_or(1);
+        let dungeon = dungeon::generate(template, rand::random(), level, &state.loot_tables);
+        let (instance, is_new) = state.instance_manager.get_or_create_generated(dungeon.map, player_id);
+        if is_new {
+            info!("Generated dungeon '{}' for player {} (seed {}, level {})",
+                dungeon_id, player_id, dungeon.seed, dungeon.level);
+            room.place_dungeon_treasure(&instance.id, &dungeon.treasure).await;
+        }
+
+        let Some(map) = instance.generated_map.clone() else {
+            error!("Dungeon instance {} has no generated map", instance.id);
+            return;
+        };
+        let Some(spawn) = map.get_spawn_point("entrance").cloned() else {
+            error!("Generated dungeon '{}' has no entrance", dungeon_id);
+            return;
+        };
+        enter_interior_instance(state, room, player_id, &map, instance, is_new, &spawn).await;
+        return;
+    }
+
+    // House doors lead to the player's own house, or the house they asked to visit
+    if portal.target_map == HOUSE_PORTAL_TARGET {
+        // Tells the player why when they can't go in
+        let Some((owner_id, house_id)) = room.resolve_house_entry(player_id).await else {
+            return;
+        };
+        let Some(house) = room.get_house_definition(&house_id) else {
+            error!("Player {} owns unknown house '{}'", owner_id, house_id);
+            return;
+        };
+        let Some(template) = state.interior_registry.get(&house.interior) else {
+            error!("House '{}' references unknown interior '{}'", house_id, house.interior);
+            return;
+        };
+
+        let (instance, is_new) = state.instance_manager
+            .get_or_create_generated(house::build_house_map(&house, template), &owner_id);
+        let Some(map) = instance.generated_map.clone() else {
+            error!("House instance {} has no generated map", instance.id);
+            return;
+        };
+        let Some(spawn) = map.get_spawn_point(HOUSE_SPAWN_POINT).cloned() else {
+            error!("House interior '{}' has no '{}' spawn point", house.interior, HOUSE_SPAWN_POINT);
+            return;
+        };
+        enter_interior_instance(state, room, player_id, &map, instance, is_new, &spawn).await;
+        room.send_house_furniture(player_id, &owner_id).await;
+        return;
+    }
+
     // Get interior definition
     info!("Looking up interior map '{}'", portal.target_map);
     let interior = match state.interior_registry.get(&portal.target_map) {
@@ -1708,6 +2130,22 @@ async fn handle_enter_portal(
         }
     };
 
+    enter_interior_instance(state, room, player_id, interior, instance, is_new, spawn).await;
+}
+
+/// Move a player into an interior instance and send them its map, NPCs and items
+async fn enter_interior_instance(
+    state: &AppState,
+    room: &GameRoom,
+    player_id: &str,
+    interior: &crate::interior::InteriorMapDef,
+    instance: Arc<crate::instance::Instance>,
+    is_new: bool,
+    spawn: &crate::interior::SpawnPoint,
+) {
+    use crate::protocol::{ChunkLayerData, ChunkPortalData};
+    use base64::Engine;
+
     // Spawn NPCs if this is a new instance
     if is_new || !*instance.npcs_spawned.read().await {
         instance.spawn_npcs(&interior.entities, &state.entity_registry).await;
@@ -1740,6 +2178,8 @@ async fn handle_enter_portal(
         let player_name = room.get_player_name(player_id).await.unwrap_or_default();
         let (gender, skin) = room.get_player_appearance(player_id).await.unwrap_or_else(|| ("male".to_string(), "tan".to_string()));
         let (hair_style, hair_color) = room.get_player_hair(player_id).await.unwrap_or((None, None));
+        let guild_tag = room.get_player_guild_tag(player_id).await;
+        let title = room.get_player_title(player_id).await;
 
         for other_id in &other_players_in_instance {
             room.send_to_player(
@@ -1753,6 +2193,8 @@ async fn handle_enter_portal(
                     skin: skin.clone(),
                     hair_style,
                     hair_color,
+                    guild_tag: guild_tag.clone(),
+                    title: title.clone(),
                 },
             ).await;
         }
@@ -1763,6 +2205,8 @@ async fn handle_enter_portal(
                 let (other_x, other_y) = room.get_player_position(other_id).await.unwrap_or((spawn.x as i32, spawn.y as i32));
                 let (other_gender, other_skin) = room.get_player_appearance(other_id).await.unwrap_or_else(|| ("male".to_string(), "tan".to_string()));
                 let (other_hair_style, other_hair_color) = room.get_player_hair(other_id).await.unwrap_or((None, None));
+                let other_guild_tag = room.get_player_guild_tag(other_id).await;
+                let other_title = room.get_player_title(other_id).await;
 
                 room.send_to_player(
                     player_id,
@@ -1775,6 +2219,8 @@ async fn handle_enter_portal(
                         skin: other_skin,
                         hair_style: other_hair_style,
                         hair_color: other_hair_color,
+                        guild_tag: other_guild_tag,
+                        title: other_title,
                     },
                 ).await;
             }
@@ -1873,6 +2319,7 @@ async fn handle_enter_portal(
     for item_msg in ground_items {
         room.send_to_player(player_id, item_msg).await;
     }
+    room.send_gravestones(player_id).await;
 }
 
 async fn handle_client_message(
@@ -1952,6 +2399,69 @@ async fn handle_client_message(
         ClientMessage::EnterPortal { portal_id } => {
             handle_enter_portal(state, room, player_id, &portal_id).await;
         }
+        ClientMessage::TradeRequest { target_id } => {
+            room.handle_trade_request(player_id, &target_id).await;
+        }
+        ClientMessage::TradeRespond { requester_id, accept } => {
+            room.handle_trade_respond(player_id, &requester_id, accept).await;
+        }
+        ClientMessage::TradeOfferItem { slot_index, quantity } => {
+            room.handle_trade_offer_item(player_id, slot_index, quantity).await;
+        }
+        ClientMessage::TradeRemoveItem { offer_index } => {
+            room.handle_trade_remove_item(player_id, offer_index).await;
+        }
+        ClientMessage::TradeSetGold { amount } => {
+            room.handle_trade_set_gold(player_id, amount).await;
+        }
+        ClientMessage::TradeAccept => {
+            room.handle_trade_accept(player_id).await;
+        }
+        ClientMessage::TradeConfirm => {
+            if let Some(completed) = room.handle_trade_confirm(player_id).await {
+                let character_a = room.get_player_character_id(&completed.player_a).await.unwrap_or(0);
+                let character_b = room.get_player_character_id(&completed.player_b).await.unwrap_or(0);
+                if let Err(e) = state.db.record_trade(character_a, character_b, &completed).await {
+                    error!("Failed to record trade {}: {}", completed.trade_id, e);
+                }
+            }
+        }
+        ClientMessage::TradeCancel => {
+            room.handle_trade_cancel(player_id).await;
+        }
+        ClientMessage::PartyInvite { target_id } => {
+            room.handle_party_invite(player_id, &target_id).await;
+        }
+        ClientMessage::PartyRespond { inviter_id, accept } => {
+            room.handle_party_respond(player_id, &inviter_id, accept).await;
+        }
+        ClientMessage::PartyLeave => {
+            room.handle_party_leave(player_id).await;
+        }
+        ClientMessage::PartyKick { member_id } => {
+            room.handle_party_kick(player_id, &member_id).await;
+        }
+        ClientMessage::PartyPromote { member_id } => {
+            room.handle_party_promote(player_id, &member_id).await;
+        }
+        ClientMessage::SetTitle { achievement_id } => {
+            room.handle_set_title(player_id, achievement_id).await;
+        }
+        ClientMessage::StorageOpen { furniture_id } => {
+            room.handle_storage_open(player_id, furniture_id).await;
+        }
+        ClientMessage::StorageDeposit { furniture_id, slot_index, quantity } => {
+            room.handle_storage_deposit(player_id, furniture_id, slot_index, quantity).await;
+        }
+        ClientMessage::StorageWithdraw { furniture_id, storage_slot } => {
+            room.handle_storage_withdraw(player_id, furniture_id, storage_slot).await;
+        }
+        ClientMessage::GravestoneOpen { gravestone_id } => {
+            room.handle_gravestone_open(player_id, &gravestone_id).await;
+        }
+        ClientMessage::GravestoneRecover { gravestone_id } => {
+            room.handle_gravestone_recover(player_id, &gravestone_id).await;
+        }
         // Auth and Register are handled via HTTP endpoints, not WebSocket
         ClientMessage::Auth { .. } | ClientMessage::Register { .. } => {}
     }
@@ -1965,6 +2475,28 @@ async fn handle_client_message(
 
 #[tokio::main]
 async fn main() {
+    // Offline balancing simulation: `isometric-server simulate [options]`
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("simulate") {
+        if let Err(e) = simulate::run(&args[1..]) {
+            eprintln!("{}\n\n{}", e, simulate::USAGE);
+            std::process::exit(1);
+        }
+        return;
+    }
+    // Economy balancing report: `isometric-server economy [options]`
+    if args.first().map(String::as_str) == Some("economy") {
+        match economy::run(&args[1..]) {
+            Ok(0) => {}
+            Ok(_) => std::process::exit(1),
+            Err(e) => {
+                eprintln!("{}\n\n{}", e, economy::USAGE);
+                std::process::exit(2);
+            }
+        }
+        return;
+    }
+
     // Initialize logging
     tracing_subscriber::fmt()
         .with_env_filter(
@@ -2049,6 +2581,21 @@ async fn main() {
                     if let Some(quest_state) = room.get_player_quest_state(player_id).await {
                         let _ = save_state.db.save_character_quest_state(character_id, &quest_state).await;
                     }
+                    if let Some(reputation) = room.get_player_reputation(player_id).await {
+                        let _ = save_state.db.save_character_reputation(character_id, &reputation).await;
+                    }
+                    if let Some(achievements) = room.get_player_achievements(player_id).await {
+                        let _ = save_state.db.save_character_achievements(character_id, &achievements).await;
+                    }
+                    if let Some(challenges) = room.get_player_challenges(player_id).await {
+                        let _ = save_state.db.save_character_challenges(character_id, &challenges).await;
+                    }
+                    if let Some(pets) = room.get_player_pets(player_id).await {
+                        let _ = save_state.db.save_character_pets(character_id, &pets).await;
+                    }
+                    if let Some(house) = room.get_player_house(player_id).await {
+                        let _ = save_state.db.save_character_house(character_id, &house).await;
+                    }
                 }
             }
 
@@ -2078,6 +2625,10 @@ async fn main() {
         .route("/api/stats/online", get(stats_online))
         .route("/api/stats/leaderboard", get(stats_leaderboard))
         .route("/api/stats/items", get(stats_items))
+        .route("/api/stats/guilds", get(stats_guilds))
+        .route("/api/stats/achievements", get(stats_achievements))
+        .route("/api/stats/pvp", get(stats_pvp))
+        .route("/api/stats/spawn-regions", get(stats_spawn_regions))
         // In development, you may want CorsLayer::permissive()
         // For production, specify allowed origins explicitly
         .layer(
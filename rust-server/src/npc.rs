@@ -1,6 +1,13 @@
 use serde::Serialize;
 use rand::Rng;
+use std::collections::HashSet;
+use crate::behavior::{pick_retreat_step, BehaviorProfiles, CombatMove};
+use crate::elite::{BaseStats, Elite};
+use crate::entity::prototype::RangedAttackConfig;
 use crate::game::Direction;
+use crate::pathfinding::{in_cardinal_range, CachedPath, NavGrid, PathContext, PathResult};
+use crate::routine::{Routine, MIN_WAYPOINT_PAUSE_MS, TALK_PAUSE_MS};
+use crate::threat::{ThreatTable, PROXIMITY_THREAT_PER_SEC};
 
 // ============================================================================
 // NPC State
@@ -14,6 +21,17 @@ pub enum NpcState {
     Returning,  // 3
     Dead,       // 4
     Wandering,  // 5 - added at end to preserve existing state values
+    Fleeing,    // 6
+}
+
+/// What came of trying to step toward a goal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MoveOutcome {
+    Moved,
+    /// Cooldown, crowding or path budget - try again later
+    Waiting,
+    /// No path exists
+    Unreachable,
 }
 
 // ============================================================================
@@ -41,6 +59,10 @@ pub struct PrototypeStats {
     pub wander_pause_min_ms: u64,
     pub wander_pause_max_ms: u64,
     pub hp_regen_percent_per_sec: f32,
+    pub ranged_attack: Option<RangedAttackConfig>,
+    /// Monster family answering calls for help
+    pub family: String,
+    pub profiles: BehaviorProfiles,
 }
 
 #[derive(Debug, Clone)]
@@ -61,17 +83,45 @@ pub struct Npc {
     pub level: i32,
     pub state: NpcState,
     pub target_id: Option<String>, // Player it's aggro'd on
+    /// Threat per player, deciding who a hostile NPC chases
+    pub threat: ThreatTable,
+    /// How far from spawn a ranged attacker pulled this NPC from (stretches the leash)
+    pub pulled_range: i32,
+    /// Path being followed, reused until the goal moves or the way is blocked
+    pub path: Option<CachedPath>,
     pub last_attack_time: u64,
     pub last_move_time: u64, // For grid movement cooldown
     pub death_time: u64, // When the NPC died (for respawn)
     /// Set to true on the tick when this NPC attacks, for client animation sync
     pub just_attacked: bool,
+    /// Projectile of this tick's attack, if it was a ranged shot
+    pub attack_projectile: Option<String>,
     /// Target position for wandering
     pub wander_target: Option<(i32, i32)>,
     /// Timestamp until which the NPC should remain idle before wandering
     pub idle_until: u64,
+    /// Patrol route or daily schedule from the spawn point, replacing random wandering
+    pub routine: Option<Routine>,
+    /// Timestamp until which the NPC stays put, facing a player who talked to it
+    pub talking_until: u64,
     /// Last time HP regen was applied
     pub last_regen_time: u64,
+    /// World event this NPC was spawned by; event NPCs are removed instead of respawning
+    pub event_id: Option<String>,
+    /// Boss that summoned this NPC; summoned adds are removed instead of respawning
+    pub summoned_by: Option<String>,
+    /// Spawn region this NPC belongs to; the region replaces it after it dies
+    pub spawn_region: Option<String>,
+    /// World script that spawned this NPC; script NPCs are removed instead of respawning
+    pub spawned_by_script: Option<String>,
+    /// Elite this NPC split off from; split copies are removed instead of respawning
+    pub split_from: Option<String>,
+    /// Elite or champion variant rolled at spawn
+    pub elite: Option<Elite>,
+    /// Player this NPC belongs to, if it is a summoned pet
+    pub owner_id: Option<String>,
+    /// Whether the NPC comes back after dying (interior spawns can turn this off)
+    pub respawns: bool,
 }
 
 impl Npc {
@@ -103,6 +153,9 @@ impl Npc {
             wander_pause_min_ms: prototype.behaviors.wander_pause_min_ms,
             wander_pause_max_ms: prototype.behaviors.wander_pause_max_ms,
             hp_regen_percent_per_sec: prototype.stats.hp_regen_percent_per_sec,
+            ranged_attack: prototype.ranged_attack.clone(),
+            family: prototype.family.clone(),
+            profiles: BehaviorProfiles::new(prototype.behavior_profiles.clone()),
         };
 
         Self {
@@ -118,13 +171,27 @@ impl Npc {
             level,
             state: NpcState::Idle,
             target_id: None,
+            threat: ThreatTable::new(),
+            pulled_range: 0,
+            path: None,
             last_attack_time: 0,
             last_move_time: 0,
             death_time: 0,
             just_attacked: false,
+            attack_projectile: None,
             wander_target: None,
             idle_until: 0,
+            routine: None,
+            talking_until: 0,
             last_regen_time: 0,
+            event_id: None,
+            summoned_by: None,
+            spawn_region: None,
+            spawned_by_script: None,
+            split_from: None,
+            elite: None,
+            owner_id: None,
+            respawns: true,
             stats,
         }
     }
@@ -143,7 +210,7 @@ impl Npc {
     }
 
     fn get_chase_range(&self) -> i32 {
-        self.stats.chase_range
+        self.stats.chase_range.max(self.pulled_range)
     }
 
     fn get_move_cooldown_ms(&self) -> u64 {
@@ -177,7 +244,33 @@ impl Npc {
     }
 
     pub fn name(&self) -> String {
-        format!("{} Lv.{}", self.stats.display_name, self.level)
+        match &self.elite {
+            Some(elite) => format!("{} {} Lv.{}", elite.prefixes.join(" "), self.stats.display_name, self.level),
+            None => format!("{} Lv.{}", self.stats.display_name, self.level),
+        }
+    }
+
+    /// Turn this NPC into an elite variant, or back into a normal monster, at full HP
+    pub fn set_elite(&mut self, elite: Option<Elite>) {
+        let base = match self.elite.take() {
+            Some(previous) => previous.base,
+            None => BaseStats {
+                max_hp: self.max_hp,
+                damage: self.stats.damage,
+                move_cooldown_ms: self.stats.move_cooldown_ms,
+                attack_cooldown_ms: self.stats.attack_cooldown_ms,
+            },
+        };
+        let stats = match &elite {
+            Some(elite) => elite.apply(base),
+            None => base,
+        };
+        self.max_hp = stats.max_hp;
+        self.hp = stats.max_hp;
+        self.stats.damage = stats.damage;
+        self.stats.move_cooldown_ms = stats.move_cooldown_ms;
+        self.stats.attack_cooldown_ms = stats.attack_cooldown_ms;
+        self.elite = elite.map(|elite| Elite { base, ..elite });
     }
 
     pub fn exp_reward(&self) -> i32 {
@@ -196,12 +289,72 @@ impl Npc {
             self.state = NpcState::Dead;
             self.death_time = current_time;
             self.target_id = None;
+            self.threat.clear();
+            self.pulled_range = 0;
             true
         } else {
             false
         }
     }
 
+    /// Whether this NPC picks targets from its threat table (pets are steered by their owner)
+    pub fn uses_threat(&self) -> bool {
+        self.is_hostile() && self.owner_id.is_none()
+    }
+
+    /// Add threat from a player. Ignored while leashing home, so a returning NPC can't be re-pulled.
+    pub fn add_threat(&mut self, player_id: &str, amount: f32) {
+        if !self.uses_threat() || !self.is_alive() || self.state == NpcState::Returning {
+            return;
+        }
+        self.threat.add(player_id, amount);
+    }
+
+    /// Add threat for damage from a player at (x, y). Being hit from further
+    /// away than the chase range stretches the leash, so ranged pulls reach the puller.
+    pub fn add_damage_threat(&mut self, player_id: &str, amount: f32, from_x: i32, from_y: i32) {
+        if !self.uses_threat() || !self.is_alive() || self.state == NpcState::Returning {
+            return;
+        }
+        self.threat.add(player_id, amount);
+        let dist = Self::grid_distance(self.spawn_x, self.spawn_y, from_x, from_y);
+        self.pulled_range = self.pulled_range.max(dist);
+    }
+
+    /// Give up the fight and walk back to spawn, forgetting all threat
+    fn leash(&mut self) {
+        self.state = NpcState::Returning;
+        self.target_id = None;
+        self.threat.clear();
+        self.pulled_range = 0;
+    }
+
+    /// Forget the current target, moving on to the next player on the threat table or leashing
+    fn drop_target(&mut self) {
+        if let Some(target_id) = self.target_id.take() {
+            self.threat.remove(&target_id);
+        }
+        if !self.update_threat_target() {
+            self.leash();
+        }
+    }
+
+    /// Switch to the top of the threat table if someone has pulled aggro.
+    /// Returns false if there's nobody left to fight.
+    fn update_threat_target(&mut self) -> bool {
+        if self.threat.is_empty() {
+            return self.target_id.is_some();
+        }
+        let next = self.threat.pick_target(self.target_id.as_deref());
+        if next != self.target_id {
+            self.target_id = next;
+            if self.state == NpcState::Attacking {
+                self.state = NpcState::Chasing;
+            }
+        }
+        self.target_id.is_some()
+    }
+
     /// Check if ready to respawn
     pub fn ready_to_respawn(&self, current_time: u64) -> bool {
         if self.state != NpcState::Dead {
@@ -217,10 +370,14 @@ impl Npc {
         self.hp = self.max_hp;
         self.state = NpcState::Idle;
         self.target_id = None;
+        self.threat.clear();
+        self.pulled_range = 0;
+        self.path = None;
         self.last_attack_time = 0;
         self.last_move_time = 0;
         self.wander_target = None;
         self.idle_until = 0;
+        self.talking_until = 0;
         self.last_regen_time = 0;
     }
 
@@ -253,6 +410,34 @@ impl Npc {
         (self.spawn_x + dx, self.spawn_y + dy)
     }
 
+    /// Head for the routine's current waypoint, or wait there once arrived
+    fn follow_routine(&mut self, current_time: u64) {
+        let Some(routine) = self.routine.as_mut() else {
+            return;
+        };
+        let waypoint = routine.current_waypoint(current_time);
+        if (waypoint.x, waypoint.y) == (self.x, self.y) {
+            routine.advance();
+            self.idle_until = current_time + waypoint.wait_ms.max(MIN_WAYPOINT_PAUSE_MS);
+        } else {
+            self.wander_target = Some((waypoint.x, waypoint.y));
+            self.state = NpcState::Wandering;
+        }
+    }
+
+    /// Stop and face a player who interacted with this NPC, if it isn't busy fighting
+    pub fn hold_for_conversation(&mut self, player_x: i32, player_y: i32, current_time: u64) {
+        if !matches!(self.state, NpcState::Idle | NpcState::Wandering) {
+            return;
+        }
+        self.talking_until = current_time + TALK_PAUSE_MS;
+        let dx = player_x - self.x;
+        let dy = player_y - self.y;
+        if dx != 0 || dy != 0 {
+            self.direction = crate::game::Direction::from_velocity(dx as f32, dy as f32);
+        }
+    }
+
     /// Set a random idle pause duration
     fn set_random_idle_pause(&mut self, current_time: u64) {
         let mut rng = rand::thread_rng();
@@ -267,105 +452,143 @@ impl Npc {
 
     /// Check if target is within attack range AND in a cardinal direction (not diagonal)
     fn is_in_attack_range(x1: i32, y1: i32, x2: i32, y2: i32, range: i32) -> bool {
-        let dx = (x1 - x2).abs();
-        let dy = (y1 - y2).abs();
-        // Must be cardinal (one axis is 0) and within range
-        (dx == 0 || dy == 0) && (dx + dy) <= range
+        in_cardinal_range((x1, y1), (x2, y2), range)
+    }
+
+    /// How to move against a target at (x, y). Ranged attackers back off from
+    /// anyone inside their minimum range, on top of their behavior profiles.
+    fn combat_move(&self, target_x: i32, target_y: i32) -> CombatMove {
+        let combat_move = self.stats.profiles.combat_move((self.x, self.y), (target_x, target_y), self.hp, self.max_hp);
+        match &self.stats.ranged_attack {
+            Some(ranged) if combat_move == CombatMove::Approach
+                && Self::grid_distance(self.x, self.y, target_x, target_y) < ranged.min_range => CombatMove::Retreat,
+            _ => combat_move,
+        }
+    }
+
+    /// Check if a target is within the ranged attack's reach with a clear line of sight
+    fn can_shoot(&self, target_x: i32, target_y: i32, grid: &NavGrid) -> bool {
+        self.stats.ranged_attack.as_ref().is_some_and(|ranged| {
+            Self::grid_distance(self.x, self.y, target_x, target_y) <= ranged.max_range
+                && grid.has_line_of_sight(self.x, self.y, target_x, target_y)
+        })
     }
 
-    /// Try to move one tile toward target position (grid-based)
-    /// Returns true if moved
+    /// Step toward a leader along an A* path, as pets follow their owner.
+    /// Returns false when no path exists.
+    pub fn follow(
+        &mut self,
+        (x, y): (i32, i32),
+        range: i32,
+        current_time: u64,
+        occupied_tiles: &[(i32, i32)],
+        paths: &mut PathContext,
+    ) -> bool {
+        !matches!(self.try_move_toward(x, y, range, current_time, occupied_tiles, paths), MoveOutcome::Unreachable)
+    }
+
+    /// Try to take one step along an A* path to within cardinal `range` of a goal
     fn try_move_toward(
         &mut self,
         target_x: i32,
         target_y: i32,
+        range: i32,
         current_time: u64,
         occupied_tiles: &[(i32, i32)],
-    ) -> bool {
+        paths: &mut PathContext,
+    ) -> MoveOutcome {
         // Check movement cooldown
         if current_time - self.last_move_time < self.get_move_cooldown_ms() {
-            return false;
+            return MoveOutcome::Waiting;
         }
-
-        let dx = target_x - self.x;
-        let dy = target_y - self.y;
-
-        // Already at target
-        if dx == 0 && dy == 0 {
-            return false;
+        let start = (self.x, self.y);
+        let goal = (target_x, target_y);
+        if in_cardinal_range(start, goal, range) {
+            return MoveOutcome::Waiting;
         }
 
-        // Build list of candidate moves in priority order
-        let mut candidates: Vec<(i32, i32)> = Vec::with_capacity(4);
-
-        // Primary direction: move along axis with greater distance
-        if dx.abs() > dy.abs() {
-            candidates.push((dx.signum(), 0)); // Primary: horizontal
-            if dy != 0 {
-                candidates.push((0, dy.signum())); // Secondary: vertical toward target
-            }
-        } else if dy.abs() > dx.abs() {
-            candidates.push((0, dy.signum())); // Primary: vertical
-            if dx != 0 {
-                candidates.push((dx.signum(), 0)); // Secondary: horizontal toward target
-            }
-        } else {
-            // Equal distance on both axes - try both
-            if dx != 0 {
-                candidates.push((dx.signum(), 0));
-            }
-            if dy != 0 {
-                candidates.push((0, dy.signum()));
+        // Keep following the cached path while it leads to the same goal and isn't blocked
+        let cached_step = self.path.as_ref()
+            .filter(|path| path.leads_to(goal, range))
+            .and_then(|path| path.next_step())
+            .filter(|step| paths.grid.can_step(start, *step) && !occupied_tiles.contains(step));
+
+        let step = match cached_step {
+            Some(step) => step,
+            None => {
+                let occupied: HashSet<(i32, i32)> = occupied_tiles.iter().copied().collect();
+                match paths.find_path(start, goal, range, &occupied) {
+                    PathResult::Found(steps) => {
+                        self.path = Some(CachedPath::new(goal, range, steps));
+                        match self.path.as_ref().and_then(|path| path.next_step()) {
+                            Some(step) => step,
+                            None => return MoveOutcome::Waiting,
+                        }
+                    }
+                    PathResult::OutOfBudget => return MoveOutcome::Waiting,
+                    PathResult::NoPath => {
+                        self.path = None;
+                        // Only crowded out by other entities - wait for them to move
+                        return match paths.find_path(start, goal, range, &HashSet::new()) {
+                            PathResult::NoPath => MoveOutcome::Unreachable,
+                            _ => MoveOutcome::Waiting,
+                        };
+                    }
+                }
             }
-        }
+        };
 
-        // Add perpendicular moves as last resort (to go around obstacles)
-        // These don't move us closer but allow pathfinding around blockers
-        if dy == 0 && dx != 0 {
-            // Moving horizontally, try vertical sidesteps
-            candidates.push((0, 1));
-            candidates.push((0, -1));
-        } else if dx == 0 && dy != 0 {
-            // Moving vertically, try horizontal sidesteps
-            candidates.push((1, 0));
-            candidates.push((-1, 0));
+        if let Some(path) = self.path.as_mut() {
+            path.advance();
         }
+        self.direction = crate::game::Direction::from_velocity((step.0 - self.x) as f32, (step.1 - self.y) as f32);
+        self.x = step.0;
+        self.y = step.1;
+        self.last_move_time = current_time;
+        MoveOutcome::Moved
+    }
 
-        // Try each candidate move
-        for (move_x, move_y) in candidates {
-            let new_x = self.x + move_x;
-            let new_y = self.y + move_y;
-
-            // Check if tile is occupied by another NPC
-            if occupied_tiles.iter().any(|(ox, oy)| *ox == new_x && *oy == new_y) {
-                continue; // Try next candidate
-            }
-
-            // Found a valid move
-            self.x = new_x;
-            self.y = new_y;
-            self.last_move_time = current_time;
-
-            // Update facing direction
-            self.direction = crate::game::Direction::from_velocity(move_x as f32, move_y as f32);
-
-            return true;
+    /// Try to take one step away from (x, y) without leaving the chase range.
+    /// Returns false if on cooldown or boxed in.
+    fn try_step_away(
+        &mut self,
+        from_x: i32,
+        from_y: i32,
+        current_time: u64,
+        occupied_tiles: &[(i32, i32)],
+        paths: &PathContext,
+    ) -> bool {
+        if current_time - self.last_move_time < self.get_move_cooldown_ms() {
+            return false;
         }
-
-        false // All moves blocked
+        let position = (self.x, self.y);
+        let step = pick_retreat_step(position, (from_x, from_y), (self.spawn_x, self.spawn_y), self.get_chase_range(), |tile| {
+            paths.grid.can_step(position, tile) && !occupied_tiles.contains(&tile)
+        });
+        let Some((x, y)) = step else {
+            return false;
+        };
+        self.path = None;
+        self.direction = crate::game::Direction::from_velocity((x - self.x) as f32, (y - self.y) as f32);
+        self.x = x;
+        self.y = y;
+        self.last_move_time = current_time;
+        true
     }
 
     /// Update NPC AI state and movement
     /// Returns Some((target_id, damage)) if the NPC attacks a player
     pub fn update(
         &mut self,
-        _delta: f32, // Not used for grid movement
-        players: &[(String, i32, i32, i32)], // (id, x, y, hp) - grid positions
+        delta: f32, // Seconds since last update (proximity threat)
+        players: &[(String, i32, i32, i32, i32)], // (id, x, y, hp, level) - grid positions
         other_npc_positions: &[(i32, i32)],  // positions of other NPCs (excluding self)
         current_time: u64,
+        paths: &mut PathContext,
     ) -> Option<(String, i32)> {
         // Reset attack flag each tick - will be set to true if we attack this tick
         self.just_attacked = false;
+        self.attack_projectile = None;
 
         if self.state == NpcState::Dead {
             return None;
@@ -373,35 +596,45 @@ impl Npc {
 
         let mut attack_result = None;
 
+        if self.uses_threat() {
+            // Dead and disconnected players drop off the threat table
+            self.threat.retain(|id| players.iter().any(|(pid, _, _, hp, _)| pid == id && *hp > 0));
+
+            // Players in aggro range build threat, faster the closer they stand
+            let aggro_range = self.get_aggro_range();
+            if aggro_range > 0 {
+                let home = (self.spawn_x, self.spawn_y);
+                for (player_id, px, py, hp, player_level) in players {
+                    if *hp <= 0 {
+                        continue; // Skip dead players
+                    }
+                    let dist = Self::grid_distance(self.x, self.y, *px, *py);
+                    if dist <= aggro_range && self.stats.profiles.will_aggro(self.level, home, (*px, *py), *player_level, current_time) {
+                        let closeness = (aggro_range + 1 - dist) as f32;
+                        self.add_threat(player_id, PROXIMITY_THREAT_PER_SEC * delta * closeness);
+                    }
+                }
+            }
+        }
+
         match self.state {
             NpcState::Idle => {
-                // Hostile NPCs look for players in aggro range
-                if self.is_hostile() {
-                    let aggro_range = self.get_aggro_range();
-                    if aggro_range > 0 {
-                        let mut nearest: Option<(String, i32)> = None;
-                        for (player_id, px, py, hp) in players {
-                            if *hp <= 0 {
-                                continue; // Skip dead players
-                            }
-                            let dist = Self::grid_distance(self.x, self.y, *px, *py);
-                            if dist <= aggro_range {
-                                if nearest.is_none() || dist < nearest.as_ref().unwrap().1 {
-                                    nearest = Some((player_id.clone(), dist));
-                                }
-                            }
-                        }
-
-                        if let Some((target_id, _)) = nearest {
-                            self.target_id = Some(target_id);
-                            self.state = NpcState::Chasing;
-                            return None; // State changed, skip wandering check
-                        }
-                    }
+                // Hostile NPCs go after whoever has built up the most threat
+                if let Some(target_id) = self.threat.pick_target(None) {
+                    self.target_id = Some(target_id);
+                    self.state = NpcState::Chasing;
+                    return None; // State changed, skip wandering check
+                }
+                if current_time < self.talking_until {
+                    return None;
                 }
 
-                // Check if wandering is enabled and idle pause has elapsed
-                if self.stats.wander_enabled && current_time >= self.idle_until {
+                if self.routine.is_some() {
+                    if current_time >= self.idle_until {
+                        self.follow_routine(current_time);
+                    }
+                } else if self.stats.wander_enabled && current_time >= self.idle_until {
+                    // Wandering is enabled and idle pause has elapsed
                     let target = self.pick_wander_target();
                     // Only wander if target is different from current position
                     if target.0 != self.x || target.1 != self.y {
@@ -415,34 +648,34 @@ impl Npc {
             }
 
             NpcState::Wandering => {
-                // Hostile NPCs interrupt wandering immediately if player in aggro range
-                if self.is_hostile() {
-                    let aggro_range = self.get_aggro_range();
-                    if aggro_range > 0 {
-                        for (player_id, px, py, hp) in players {
-                            if *hp <= 0 {
-                                continue;
-                            }
-                            let dist = Self::grid_distance(self.x, self.y, *px, *py);
-                            if dist <= aggro_range {
-                                self.target_id = Some(player_id.clone());
-                                self.state = NpcState::Chasing;
-                                self.wander_target = None;
-                                return None;
-                            }
-                        }
-                    }
+                // Hostile NPCs interrupt wandering as soon as anyone has threat on them
+                if let Some(target_id) = self.threat.pick_target(None) {
+                    self.target_id = Some(target_id);
+                    self.state = NpcState::Chasing;
+                    self.wander_target = None;
+                    return None;
+                }
+                if current_time < self.talking_until {
+                    return None;
                 }
 
                 // Move toward wander target
                 if let Some((tx, ty)) = self.wander_target {
                     if self.x == tx && self.y == ty {
-                        // Reached target, go idle with random pause
+                        // Reached target, go idle with random pause (routines pause at the waypoint instead)
+                        self.state = NpcState::Idle;
+                        self.wander_target = None;
+                        if self.routine.is_none() {
+                            self.set_random_idle_pause(current_time);
+                        }
+                    } else if self.try_move_toward(tx, ty, 0, current_time, other_npc_positions, paths) == MoveOutcome::Unreachable {
+                        // Can't get there, pick somewhere else after a pause
                         self.state = NpcState::Idle;
                         self.wander_target = None;
+                        if let Some(routine) = self.routine.as_mut() {
+                            routine.advance();
+                        }
                         self.set_random_idle_pause(current_time);
-                    } else {
-                        self.try_move_toward(tx, ty, current_time, other_npc_positions);
                     }
                 } else {
                     // No target, go idle
@@ -452,48 +685,77 @@ impl Npc {
             }
 
             NpcState::Chasing => {
+                self.update_threat_target();
+
                 // Check if target is still valid
                 let target_pos = self.target_id.as_ref().and_then(|tid| {
                     players.iter()
-                        .find(|(id, _, _, hp)| id == tid && *hp > 0)
-                        .map(|(_, x, y, _)| (*x, *y))
+                        .find(|(id, _, _, hp, _)| id == tid && *hp > 0)
+                        .map(|(_, x, y, _, _)| (*x, *y))
                 });
 
                 if let Some((tx, ty)) = target_pos {
                     let spawn_dist = Self::grid_distance(self.x, self.y, self.spawn_x, self.spawn_y);
                     let target_dist = Self::grid_distance(self.x, self.y, tx, ty);
                     let movement_done = current_time - self.last_move_time >= self.get_move_cooldown_ms();
-
-                    if spawn_dist > self.get_chase_range() || target_dist > self.get_chase_range() {
-                        // Too far from spawn OR target got too far away, return home
-                        self.state = NpcState::Returning;
-                        self.target_id = None;
-                    } else if Self::is_in_attack_range(self.x, self.y, tx, ty, self.get_attack_range()) && movement_done {
-                        // In attack range (cardinal direction only) and movement completed
+                    let combat_move = self.combat_move(tx, ty);
+                    let in_range = Self::is_in_attack_range(self.x, self.y, tx, ty, self.get_attack_range())
+                        || self.can_shoot(tx, ty, paths.grid);
+
+                    if spawn_dist > self.get_chase_range() {
+                        // Too far from spawn, leash back home
+                        self.leash();
+                    } else if target_dist > self.get_chase_range()
+                        || !self.stats.profiles.may_pursue((self.spawn_x, self.spawn_y), (tx, ty))
+                    {
+                        // Target got too far away, fall back to the next player on the table
+                        self.drop_target();
+                    } else if combat_move == CombatMove::Flee {
+                        self.state = NpcState::Fleeing;
+                    } else if combat_move == CombatMove::Retreat
+                        && self.try_step_away(tx, ty, current_time, other_npc_positions, paths)
+                    {
+                        // Kiting: backed off to keep some distance
+                    } else if in_range && movement_done {
+                        // In melee range (cardinal direction only) or with a clear shot, and movement completed
                         self.state = NpcState::Attacking;
-                    } else if !Self::is_in_attack_range(self.x, self.y, tx, ty, self.get_attack_range()) {
-                        // Not in range, move toward target (one tile at a time)
-                        self.try_move_toward(tx, ty, current_time, other_npc_positions);
+                    } else if !in_range {
+                        // Not in range, path toward target (one tile at a time)
+                        let range = self.get_attack_range();
+                        if self.try_move_toward(tx, ty, range, current_time, other_npc_positions, paths) == MoveOutcome::Unreachable {
+                            // Target is out of reach (e.g. behind walls), give up on them
+                            self.drop_target();
+                        }
                     }
                     // If in range but movement not done, stay in Chasing and wait
                 } else {
                     // Target lost (died or disconnected)
-                    self.state = NpcState::Returning;
-                    self.target_id = None;
+                    self.leash();
                 }
             }
 
             NpcState::Attacking => {
+                self.update_threat_target();
+                if self.state == NpcState::Chasing {
+                    return None; // Aggro switched, chase the new target
+                }
+
                 // Check if target is still in range
                 let target_info = self.target_id.as_ref().and_then(|tid| {
                     players.iter()
-                        .find(|(id, _, _, hp)| id == tid && *hp > 0)
-                        .map(|(id, x, y, _)| (id.clone(), *x, *y))
+                        .find(|(id, _, _, hp, _)| id == tid && *hp > 0)
+                        .map(|(id, x, y, _, _)| (id.clone(), *x, *y))
                 });
 
                 if let Some((target_id, tx, ty)) = target_info {
-                    if !Self::is_in_attack_range(self.x, self.y, tx, ty, self.get_attack_range()) {
-                        // Target moved out of range or not in cardinal direction, chase again
+                    let combat_move = self.combat_move(tx, ty);
+                    let in_melee_range = Self::is_in_attack_range(self.x, self.y, tx, ty, self.get_attack_range());
+                    if combat_move == CombatMove::Flee {
+                        self.state = NpcState::Fleeing;
+                    } else if !(in_melee_range || self.can_shoot(tx, ty, paths.grid))
+                        || (combat_move == CombatMove::Retreat && self.try_step_away(tx, ty, current_time, other_npc_positions, paths))
+                    {
+                        // Target moved out of range or not in cardinal direction (or we backed off), chase again
                         self.state = NpcState::Chasing;
                     } else {
                         // Face target
@@ -511,13 +773,35 @@ impl Npc {
                         if movement_done && attack_ready {
                             self.last_attack_time = current_time;
                             self.just_attacked = true; // Signal client to play animation
+                            if !in_melee_range {
+                                self.attack_projectile = self.stats.ranged_attack.as_ref()
+                                    .map(|ranged| ranged.projectile.clone());
+                            }
                             attack_result = Some((target_id, self.get_damage()));
                         }
                     }
                 } else {
                     // Target lost
-                    self.state = NpcState::Returning;
-                    self.target_id = None;
+                    self.leash();
+                }
+            }
+
+            NpcState::Fleeing => {
+                // Run from the target until out of its sight, then head home to recover
+                let target_pos = self.target_id.as_ref().and_then(|tid| {
+                    players.iter()
+                        .find(|(id, _, _, hp, _)| id == tid && *hp > 0)
+                        .map(|(_, x, y, _, _)| (*x, *y))
+                });
+                match target_pos {
+                    Some((tx, ty)) if Self::grid_distance(self.x, self.y, tx, ty) <= self.get_aggro_range().max(1) => {
+                        let movement_done = current_time - self.last_move_time >= self.get_move_cooldown_ms();
+                        if movement_done && !self.try_step_away(tx, ty, current_time, other_npc_positions, paths) {
+                            // Cornered
+                            self.leash();
+                        }
+                    }
+                    _ => self.leash(),
                 }
             }
 
@@ -533,8 +817,14 @@ impl Npc {
                         self.set_random_idle_pause(current_time);
                     }
                 } else {
-                    // Move toward spawn (one tile at a time)
-                    self.try_move_toward(self.spawn_x, self.spawn_y, current_time, other_npc_positions);
+                    // Path toward spawn (one tile at a time), resetting there if the way is gone
+                    let (spawn_x, spawn_y) = (self.spawn_x, self.spawn_y);
+                    if self.try_move_toward(spawn_x, spawn_y, 0, current_time, other_npc_positions, paths) == MoveOutcome::Unreachable {
+                        self.x = spawn_x;
+                        self.y = spawn_y;
+                        self.path = None;
+                        self.last_move_time = current_time;
+                    }
                 }
             }
 
@@ -573,6 +863,12 @@ pub struct NpcUpdate {
     pub move_speed: f32,
     /// True only on the tick when this NPC attacks (for animation sync)
     pub just_attacked: bool,
+    /// Owning player if this NPC is a pet
+    pub owner_id: Option<String>,
+    /// Elite rank ("elite", "champion") for the name color and aura
+    pub elite_rank: Option<String>,
+    /// Affix prefixes shown before the name
+    pub elite_prefixes: Vec<String>,
 }
 
 impl From<&Npc> for NpcUpdate {
@@ -601,6 +897,9 @@ impl From<&Npc> for NpcUpdate {
             is_merchant: npc.is_merchant(),
             move_speed,
             just_attacked: npc.just_attacked,
+            owner_id: npc.owner_id.clone(),
+            elite_rank: npc.elite.as_ref().map(|elite| elite.rank.clone()),
+            elite_prefixes: npc.elite.as_ref().map(|elite| elite.prefixes.clone()).unwrap_or_default(),
         }
     }
 }
@@ -0,0 +1,371 @@
+//! Player parties
+//!
+//! Parties are kept in memory per room and keyed by player ID. Player IDs are
+//! derived from the character ID, so a member who disconnects keeps their
+//! place in the party and gets the roster back on reconnect. A party is only
+//! disbanded once it drops below two members or every member is offline.
+
+use std::collections::HashMap;
+use uuid::Uuid;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Maximum number of members in a party
+pub const PARTY_MAX_SIZE: usize = 5;
+
+/// How long a party invite stays valid before it expires
+pub const PARTY_INVITE_TIMEOUT_MS: u64 = 60_000;
+
+/// Maximum tile distance (Chebyshev) for sharing XP and loot with the killer
+pub const PARTY_SHARE_DISTANCE: i32 = 15;
+
+// ============================================================================
+// Party
+// ============================================================================
+
+/// How kill loot is assigned within a party during the owner-only pickup window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LootRule {
+    /// Any nearby party member can pick up party loot
+    Shared,
+    /// Each drop is owned by the next nearby member in turn
+    RoundRobin,
+}
+
+impl LootRule {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LootRule::Shared => "shared",
+            LootRule::RoundRobin => "roundrobin",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "shared" | "ffa" => Some(LootRule::Shared),
+            "roundrobin" | "rr" => Some(LootRule::RoundRobin),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PartyMember {
+    pub player_id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Party {
+    pub id: String,
+    pub leader_id: String,
+    /// Members in join order (the leader is included)
+    pub members: Vec<PartyMember>,
+    pub loot_rule: LootRule,
+    /// Round-robin cursor into `members`
+    next_loot_index: usize,
+}
+
+impl Party {
+    fn new(leader_id: &str, leader_name: &str) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            leader_id: leader_id.to_string(),
+            members: vec![PartyMember {
+                player_id: leader_id.to_string(),
+                name: leader_name.to_string(),
+            }],
+            loot_rule: LootRule::Shared,
+            next_loot_index: 0,
+        }
+    }
+
+    pub fn is_member(&self, player_id: &str) -> bool {
+        self.members.iter().any(|m| m.player_id == player_id)
+    }
+
+    pub fn is_leader(&self, player_id: &str) -> bool {
+        self.leader_id == player_id
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.members.len() >= PARTY_MAX_SIZE
+    }
+
+    pub fn member_ids(&self) -> Vec<String> {
+        self.members.iter().map(|m| m.player_id.clone()).collect()
+    }
+
+    pub fn member_name(&self, player_id: &str) -> Option<&str> {
+        self.members
+            .iter()
+            .find(|m| m.player_id == player_id)
+            .map(|m| m.name.as_str())
+    }
+
+    /// Remove a member. Leadership passes to the longest-standing member
+    /// if the leader leaves.
+    fn remove_member(&mut self, player_id: &str) -> bool {
+        let before = self.members.len();
+        self.members.retain(|m| m.player_id != player_id);
+        if self.members.len() == before {
+            return false;
+        }
+        if self.leader_id == player_id {
+            self.leader_id = self.members.first().map(|m| m.player_id.clone()).unwrap_or_default();
+        }
+        true
+    }
+
+    /// Pick the owner of the next drop among the eligible (nearby) members
+    pub fn next_looter(&mut self, eligible: &[String]) -> Option<String> {
+        for _ in 0..self.members.len() {
+            let idx = self.next_loot_index % self.members.len();
+            self.next_loot_index = (idx + 1) % self.members.len();
+            let candidate = &self.members[idx].player_id;
+            if eligible.contains(candidate) {
+                return Some(candidate.clone());
+            }
+        }
+        None
+    }
+}
+
+/// XP each of `recipients` gets from a kill worth `total` XP.
+/// Rounds up so a small kill never gives anyone zero.
+pub fn split_xp(total: i32, recipients: usize) -> i32 {
+    if recipients <= 1 {
+        return total;
+    }
+    let n = recipients as i32;
+    (total + n - 1) / n
+}
+
+// ============================================================================
+// Party Manager
+// ============================================================================
+
+/// Pending invite from a party leader (or a player starting a party)
+#[derive(Debug, Clone)]
+pub struct PartyInvite {
+    pub inviter_id: String,
+    pub created_at: u64,
+}
+
+/// Result of a member leaving or being removed
+#[derive(Debug, Clone)]
+pub struct PartyDeparture {
+    /// The party after removal (still holds the remaining members)
+    pub party: Party,
+    /// True if the party fell below two members and was disbanded
+    pub disbanded: bool,
+}
+
+/// Tracks parties and pending invites for a room
+#[derive(Debug, Default)]
+pub struct PartyManager {
+    /// Party ID -> party
+    parties: HashMap<String, Party>,
+    /// Player ID -> party ID
+    player_parties: HashMap<String, String>,
+    /// Invitee ID -> pending invite
+    invites: HashMap<String, PartyInvite>,
+}
+
+impl PartyManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn party_of(&self, player_id: &str) -> Option<&Party> {
+        let party_id = self.player_parties.get(player_id)?;
+        self.parties.get(party_id)
+    }
+
+    pub fn party_of_mut(&mut self, player_id: &str) -> Option<&mut Party> {
+        let party_id = self.player_parties.get(player_id)?;
+        self.parties.get_mut(party_id)
+    }
+
+    pub fn get(&self, party_id: &str) -> Option<&Party> {
+        self.parties.get(party_id)
+    }
+
+    /// Record an invite, replacing any earlier invite to the same player
+    pub fn add_invite(&mut self, inviter_id: &str, invitee_id: &str, current_time: u64) {
+        self.invites.insert(invitee_id.to_string(), PartyInvite {
+            inviter_id: inviter_id.to_string(),
+            created_at: current_time,
+        });
+    }
+
+    /// Take a pending invite from `inviter_id` to `invitee_id` if it is still valid
+    pub fn take_invite(&mut self, invitee_id: &str, inviter_id: &str, current_time: u64) -> Option<PartyInvite> {
+        let valid = self.invites.get(invitee_id).is_some_and(|invite| {
+            invite.inviter_id == inviter_id
+                && current_time.saturating_sub(invite.created_at) <= PARTY_INVITE_TIMEOUT_MS
+        });
+        if valid {
+            self.invites.remove(invitee_id)
+        } else {
+            None
+        }
+    }
+
+    /// Add `invitee` to the inviter's party, creating the party if needed
+    pub fn join(&mut self, inviter_id: &str, inviter_name: &str, invitee_id: &str, invitee_name: &str) -> Result<&Party, String> {
+        if self.player_parties.contains_key(invitee_id) {
+            return Err("You are already in a party.".to_string());
+        }
+
+        let party_id = match self.player_parties.get(inviter_id) {
+            Some(id) => id.clone(),
+            None => {
+                let party = Party::new(inviter_id, inviter_name);
+                let id = party.id.clone();
+                self.player_parties.insert(inviter_id.to_string(), id.clone());
+                self.parties.insert(id.clone(), party);
+                id
+            }
+        };
+
+        let party = self.parties.get_mut(&party_id).ok_or("Party not found.")?;
+        if !party.is_leader(inviter_id) {
+            return Err("Only the party leader can invite players.".to_string());
+        }
+        if party.is_full() {
+            return Err("The party is full.".to_string());
+        }
+
+        party.members.push(PartyMember {
+            player_id: invitee_id.to_string(),
+            name: invitee_name.to_string(),
+        });
+        self.player_parties.insert(invitee_id.to_string(), party_id.clone());
+        self.invites.remove(invitee_id);
+        Ok(&self.parties[&party_id])
+    }
+
+    /// Remove a player from their party, disbanding it if fewer than two remain
+    pub fn leave(&mut self, player_id: &str) -> Option<PartyDeparture> {
+        let party_id = self.player_parties.remove(player_id)?;
+        let party = self.parties.get_mut(&party_id)?;
+        party.remove_member(player_id);
+
+        if party.members.len() < 2 {
+            let party = self.disband(&party_id)?;
+            return Some(PartyDeparture { party, disbanded: true });
+        }
+        Some(PartyDeparture { party: party.clone(), disbanded: false })
+    }
+
+    /// Make another member the leader
+    pub fn set_leader(&mut self, party_id: &str, new_leader_id: &str) -> bool {
+        match self.parties.get_mut(party_id) {
+            Some(party) if party.is_member(new_leader_id) => {
+                party.leader_id = new_leader_id.to_string();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Remove a party and all its memberships
+    pub fn disband(&mut self, party_id: &str) -> Option<Party> {
+        let party = self.parties.remove(party_id)?;
+        for member in &party.members {
+            self.player_parties.remove(&member.player_id);
+        }
+        Some(party)
+    }
+
+    /// Drop any invites involving a player (used on disconnect)
+    pub fn clear_invites_for(&mut self, player_id: &str) {
+        self.invites.remove(player_id);
+        self.invites.retain(|_, invite| invite.inviter_id != player_id);
+    }
+
+    /// Remove invites older than the timeout
+    pub fn expire_invites(&mut self, current_time: u64) {
+        self.invites
+            .retain(|_, invite| current_time.saturating_sub(invite.created_at) <= PARTY_INVITE_TIMEOUT_MS);
+    }
+
+    /// All parties (for periodic roster/HP updates)
+    pub fn parties(&self) -> impl Iterator<Item = &Party> {
+        self.parties.values()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn party_of_three(manager: &mut PartyManager) -> String {
+        manager.join("a", "Alice", "b", "Bob").unwrap();
+        manager.join("a", "Alice", "c", "Cara").unwrap().id.clone()
+    }
+
+    #[test]
+    fn test_join_creates_party_with_leader() {
+        let mut manager = PartyManager::new();
+        let party_id = party_of_three(&mut manager);
+
+        let party = manager.get(&party_id).unwrap();
+        assert!(party.is_leader("a"));
+        assert_eq!(party.member_ids(), vec!["a", "b", "c"]);
+
+        // Non-leaders can't invite, members can't join twice
+        assert!(manager.join("b", "Bob", "d", "Dan").is_err());
+        assert!(manager.join("a", "Alice", "b", "Bob").is_err());
+    }
+
+    #[test]
+    fn test_leader_leaving_transfers_leadership() {
+        let mut manager = PartyManager::new();
+        party_of_three(&mut manager);
+
+        let departure = manager.leave("a").unwrap();
+        assert!(!departure.disbanded);
+        assert!(departure.party.is_leader("b"));
+        assert!(manager.party_of("a").is_none());
+
+        // Dropping below two members disbands the party
+        let departure = manager.leave("c").unwrap();
+        assert!(departure.disbanded);
+        assert!(manager.party_of("b").is_none());
+    }
+
+    #[test]
+    fn test_round_robin_skips_ineligible_members() {
+        let mut manager = PartyManager::new();
+        party_of_three(&mut manager);
+        let party = manager.party_of_mut("a").unwrap();
+
+        let eligible = vec!["a".to_string(), "c".to_string()];
+        assert_eq!(party.next_looter(&eligible).as_deref(), Some("a"));
+        assert_eq!(party.next_looter(&eligible).as_deref(), Some("c"));
+        assert_eq!(party.next_looter(&eligible).as_deref(), Some("a"));
+        assert_eq!(party.next_looter(&[]), None);
+    }
+
+    #[test]
+    fn test_split_xp_rounds_up() {
+        assert_eq!(split_xp(100, 1), 100);
+        assert_eq!(split_xp(100, 3), 34);
+        assert_eq!(split_xp(1, 4), 1);
+    }
+
+    #[test]
+    fn test_invites_expire() {
+        let mut manager = PartyManager::new();
+        manager.add_invite("a", "b", 1000);
+        assert!(manager.take_invite("b", "c", 1000).is_none());
+        assert!(manager.take_invite("b", "a", 1000 + PARTY_INVITE_TIMEOUT_MS + 1).is_none());
+
+        manager.add_invite("a", "b", 5000);
+        assert!(manager.take_invite("b", "a", 6000).is_some());
+    }
+}
@@ -0,0 +1,384 @@
+//! Server-side A* pathfinding for NPC movement
+//!
+//! Searches run over a per-tick `NavGrid` snapshot of chunk collision and share
+//! a node budget so crowds can't stall the tick; found paths are cached on the
+//! NPC as a `CachedPath`.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+use crate::chunk::{world_to_local, Chunk, ChunkCoord, WallEdge, CHUNK_SIZE};
+use crate::interior::InteriorMapDef;
+use crate::tilemap::trace_line;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Nodes all NPC searches may expand in one tick
+pub const MAX_NODES_PER_TICK: usize = 4000;
+/// Nodes a single search may expand before the goal counts as unreachable
+pub const MAX_NODES_PER_SEARCH: usize = 600;
+
+const NEIGHBORS: [(i32, i32); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+
+// ============================================================================
+// Navigation Grid
+// ============================================================================
+
+/// Collision and walls of the loaded chunks
+pub struct NavGrid {
+    chunks: HashMap<ChunkCoord, Arc<Chunk>>,
+    /// Wall edges as (tile x, tile y, edge)
+    walls: HashSet<(i32, i32, WallEdge)>,
+}
+
+impl NavGrid {
+    pub fn new(chunks: impl IntoIterator<Item = Arc<Chunk>>) -> Self {
+        let chunks: HashMap<ChunkCoord, Arc<Chunk>> = chunks.into_iter()
+            .map(|chunk| (chunk.coord, chunk))
+            .collect();
+        let walls = chunks.values()
+            .flat_map(|chunk| chunk.walls.iter())
+            .map(|wall| (wall.tile_x, wall.tile_y, wall.edge))
+            .collect();
+        Self { chunks, walls }
+    }
+
+    /// Grid over an interior map, with tiles outside the map blocked
+    pub fn from_interior(map: &InteriorMapDef) -> Self {
+        let (width, height) = (map.size.width as i32, map.size.height as i32);
+        let chunk_span = |tiles: i32| (tiles + CHUNK_SIZE as i32 - 1) / CHUNK_SIZE as i32;
+        let packed = map.packed_collision();
+        let mut chunks = HashMap::new();
+        for chunk_y in 0..chunk_span(height) {
+            for chunk_x in 0..chunk_span(width) {
+                let coord = ChunkCoord::new(chunk_x, chunk_y);
+                let mut chunk = Chunk::new(coord);
+                for local_y in 0..CHUNK_SIZE {
+                    for local_x in 0..CHUNK_SIZE {
+                        let x = chunk_x * CHUNK_SIZE as i32 + local_x as i32;
+                        let y = chunk_y * CHUNK_SIZE as i32 + local_y as i32;
+                        chunk.set_collision(local_x, local_y, map.is_blocked_in(&packed, x, y));
+                    }
+                }
+                chunks.insert(coord, Arc::new(chunk));
+            }
+        }
+        let walls = map.walls.iter()
+            .filter_map(|wall| match wall.edge.as_str() {
+                "down" => Some((wall.x, wall.y, WallEdge::Down)),
+                "right" => Some((wall.x, wall.y, WallEdge::Right)),
+                _ => None,
+            })
+            .collect();
+        Self { chunks, walls }
+    }
+
+    /// Whether a tile can be stood on (tiles in unloaded chunks can't)
+    pub fn is_walkable(&self, x: i32, y: i32) -> bool {
+        match self.chunks.get(&ChunkCoord::from_world(x, y)) {
+            Some(chunk) => {
+                let (local_x, local_y) = world_to_local(x, y);
+                chunk.is_walkable_local(local_x, local_y)
+            }
+            None => false,
+        }
+    }
+
+    /// Whether a wall sits on the edge between two neighbouring tiles.
+    /// A `Down` wall separates (x, y) from (x, y + 1), a `Right` wall (x, y) from (x + 1, y).
+    fn wall_between(&self, from: (i32, i32), to: (i32, i32)) -> bool {
+        match (to.0 - from.0, to.1 - from.1) {
+            (1, 0) => self.walls.contains(&(from.0, from.1, WallEdge::Right)),
+            (-1, 0) => self.walls.contains(&(to.0, to.1, WallEdge::Right)),
+            (0, 1) => self.walls.contains(&(from.0, from.1, WallEdge::Down)),
+            (0, -1) => self.walls.contains(&(to.0, to.1, WallEdge::Down)),
+            _ => false,
+        }
+    }
+
+    /// Whether a single step between neighbouring tiles is allowed
+    pub fn can_step(&self, from: (i32, i32), to: (i32, i32)) -> bool {
+        self.is_walkable(to.0, to.1) && !self.wall_between(from, to)
+    }
+
+    /// Whether a line between two points is clear of collision and wall edges.
+    /// A blocked tile under the target itself doesn't hide it.
+    pub fn has_line_of_sight(&self, x0: i32, y0: i32, x1: i32, y1: i32) -> bool {
+        trace_line((x0, y0), (x1, y1), |from, to| {
+            (to == (x1, y1) || self.is_walkable(to.0, to.1)) && !self.wall_blocks_sight(from, to)
+        })
+    }
+
+    /// Whether walls block sight between neighbouring tiles. A diagonal step
+    /// squeezes past a corner unless walls close both ways around it.
+    fn wall_blocks_sight(&self, from: (i32, i32), to: (i32, i32)) -> bool {
+        if from.0 == to.0 || from.1 == to.1 {
+            return self.wall_between(from, to);
+        }
+        let via_x = (to.0, from.1);
+        let via_y = (from.0, to.1);
+        (self.wall_between(from, via_x) || self.wall_between(via_x, to))
+            && (self.wall_between(from, via_y) || self.wall_between(via_y, to))
+    }
+}
+
+// ============================================================================
+// Search
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathResult {
+    /// Steps to take, not including the start tile
+    Found(Vec<(i32, i32)>),
+    /// Nothing reachable within the search limit
+    NoPath,
+    /// The tick's node budget ran out, try again next tick
+    OutOfBudget,
+}
+
+/// Node for A* priority queue
+#[derive(Clone, Eq, PartialEq)]
+struct Node {
+    pos: (i32, i32),
+    g_cost: i32,
+    f_cost: i32,
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Min-heap: lower f_cost = higher priority
+        other.f_cost.cmp(&self.f_cost)
+            .then_with(|| other.g_cost.cmp(&self.g_cost))
+    }
+}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Whether `tile` is within `range` of `goal` in a straight cardinal line (range 0 = on the goal)
+pub fn in_cardinal_range(tile: (i32, i32), goal: (i32, i32), range: i32) -> bool {
+    let dx = (tile.0 - goal.0).abs();
+    let dy = (tile.1 - goal.1).abs();
+    (dx == 0 || dy == 0) && dx + dy <= range
+}
+
+/// Pathfinding state shared by every NPC updated in one tick
+pub struct PathContext<'a> {
+    pub grid: &'a NavGrid,
+    nodes_left: usize,
+}
+
+impl<'a> PathContext<'a> {
+    pub fn new(grid: &'a NavGrid, node_budget: usize) -> Self {
+        Self { grid, nodes_left: node_budget }
+    }
+
+    /// A* from `start` to any tile within cardinal `range` of `goal`,
+    /// avoiding collision, walls and `occupied` tiles
+    pub fn find_path(&mut self, start: (i32, i32), goal: (i32, i32), range: i32, occupied: &HashSet<(i32, i32)>) -> PathResult {
+        if in_cardinal_range(start, goal, range) {
+            return PathResult::Found(Vec::new());
+        }
+        if self.nodes_left == 0 {
+            return PathResult::OutOfBudget;
+        }
+
+        let heuristic = |(x, y): (i32, i32)| ((x - goal.0).abs() + (y - goal.1).abs() - range).max(0);
+        let mut open_set = BinaryHeap::new();
+        let mut came_from: HashMap<(i32, i32), (i32, i32)> = HashMap::new();
+        let mut g_score: HashMap<(i32, i32), i32> = HashMap::new();
+        g_score.insert(start, 0);
+        open_set.push(Node { pos: start, g_cost: 0, f_cost: heuristic(start) });
+
+        let mut expanded = 0;
+        let result = loop {
+            let Some(current) = open_set.pop() else {
+                break PathResult::NoPath;
+            };
+            // Skip if we've found a better path to this node
+            if current.g_cost > *g_score.get(&current.pos).unwrap_or(&i32::MAX) {
+                continue;
+            }
+
+            if in_cardinal_range(current.pos, goal, range) {
+                let mut path = vec![current.pos];
+                let mut pos = current.pos;
+                while let Some(&prev) = came_from.get(&pos) {
+                    if prev == start {
+                        break;
+                    }
+                    path.push(prev);
+                    pos = prev;
+                }
+                path.reverse();
+                break PathResult::Found(path);
+            }
+
+            expanded += 1;
+            if expanded >= self.nodes_left {
+                break PathResult::OutOfBudget;
+            }
+            if expanded >= MAX_NODES_PER_SEARCH {
+                break PathResult::NoPath;
+            }
+
+            for (dx, dy) in NEIGHBORS {
+                let neighbor = (current.pos.0 + dx, current.pos.1 + dy);
+                if !self.grid.can_step(current.pos, neighbor) || occupied.contains(&neighbor) {
+                    continue;
+                }
+                let tentative_g = current.g_cost + 1;
+                if tentative_g < *g_score.get(&neighbor).unwrap_or(&i32::MAX) {
+                    came_from.insert(neighbor, current.pos);
+                    g_score.insert(neighbor, tentative_g);
+                    open_set.push(Node {
+                        pos: neighbor,
+                        g_cost: tentative_g,
+                        f_cost: tentative_g + heuristic(neighbor),
+                    });
+                }
+            }
+        };
+
+        self.nodes_left = self.nodes_left.saturating_sub(expanded);
+        result
+    }
+}
+
+// ============================================================================
+// Cached Paths
+// ============================================================================
+
+/// A path an NPC is following, valid while its goal stays put
+#[derive(Debug, Clone)]
+pub struct CachedPath {
+    pub goal: (i32, i32),
+    pub range: i32,
+    steps: VecDeque<(i32, i32)>,
+}
+
+impl CachedPath {
+    pub fn new(goal: (i32, i32), range: i32, steps: Vec<(i32, i32)>) -> Self {
+        Self { goal, range, steps: steps.into() }
+    }
+
+    pub fn leads_to(&self, goal: (i32, i32), range: i32) -> bool {
+        self.goal == goal && self.range == range
+    }
+
+    pub fn next_step(&self) -> Option<(i32, i32)> {
+        self.steps.front().copied()
+    }
+
+    pub fn advance(&mut self) {
+        self.steps.pop_front();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk::Wall;
+
+    fn grid(setup: impl FnOnce(&mut Chunk)) -> NavGrid {
+        let mut chunk = Chunk::new(ChunkCoord::new(0, 0));
+        setup(&mut chunk);
+        NavGrid::new([Arc::new(chunk)])
+    }
+
+    #[test]
+    fn test_paths_go_around_collision() {
+        // Rock column at x = 3 with a gap at y = 6
+        let grid = grid(|chunk| {
+            for y in 0..6 {
+                chunk.set_collision(3, y, true);
+            }
+        });
+        let mut paths = PathContext::new(&grid, MAX_NODES_PER_TICK);
+
+        let PathResult::Found(path) = paths.find_path((1, 1), (5, 1), 0, &HashSet::new()) else {
+            panic!("expected a path");
+        };
+        assert_eq!(path.last(), Some(&(5, 1)));
+        assert!(path.contains(&(3, 6)));
+        assert!(path.iter().all(|(x, y)| grid.is_walkable(*x, *y)));
+
+        // Stopping in attack range of a goal on the far side
+        let PathResult::Found(path) = paths.find_path((1, 7), (5, 7), 2, &HashSet::new()) else {
+            panic!("expected a path");
+        };
+        assert_eq!(path, vec![(2, 7), (3, 7)]);
+    }
+
+    #[test]
+    fn test_walls_block_their_edge() {
+        let grid = grid(|chunk| {
+            chunk.walls.push(Wall { gid: 1, tile_x: 2, tile_y: 2, edge: WallEdge::Right });
+            chunk.walls.push(Wall { gid: 1, tile_x: 2, tile_y: 2, edge: WallEdge::Down });
+        });
+        assert!(!grid.can_step((2, 2), (3, 2)));
+        assert!(!grid.can_step((3, 2), (2, 2)));
+        assert!(!grid.can_step((2, 3), (2, 2)));
+        assert!(grid.can_step((2, 2), (1, 2)));
+        assert!(!grid.is_walkable(-1, 0)); // Unloaded chunk
+
+        let mut paths = PathContext::new(&grid, MAX_NODES_PER_TICK);
+        let PathResult::Found(path) = paths.find_path((2, 2), (3, 2), 0, &HashSet::new()) else {
+            panic!("expected a path");
+        };
+        assert_eq!(path.len(), 3);
+    }
+
+    #[test]
+    fn test_line_of_sight_stops_at_collision() {
+        let grid = grid(|chunk| chunk.set_collision(5, 5, true));
+        assert!(!grid.has_line_of_sight(3, 5, 8, 5));
+        assert!(!grid.has_line_of_sight(3, 3, 7, 7));
+        assert!(grid.has_line_of_sight(3, 4, 8, 4));
+        // A blocked tile under the target itself doesn't hide it
+        assert!(grid.has_line_of_sight(2, 5, 5, 5));
+    }
+
+    #[test]
+    fn test_line_of_sight_stops_at_edge_walls() {
+        let grid = grid(|chunk| {
+            chunk.walls.push(Wall { gid: 1, tile_x: 4, tile_y: 5, edge: WallEdge::Right });
+            chunk.walls.push(Wall { gid: 1, tile_x: 4, tile_y: 8, edge: WallEdge::Right });
+            chunk.walls.push(Wall { gid: 1, tile_x: 4, tile_y: 8, edge: WallEdge::Down });
+        });
+        assert!(!grid.has_line_of_sight(2, 5, 7, 5));
+        assert!(!grid.has_line_of_sight(7, 5, 2, 5));
+        assert!(grid.has_line_of_sight(2, 6, 7, 6));
+        // Both ways around the corner are walled off
+        assert!(!grid.has_line_of_sight(4, 8, 5, 9));
+        assert!(grid.has_line_of_sight(4, 7, 5, 8));
+    }
+
+    #[test]
+    fn test_budget_and_unreachable_goals() {
+        // Goal boxed in by rocks
+        let grid = grid(|chunk| {
+            for (x, y) in [(9, 10), (11, 10), (10, 9), (10, 11)] {
+                chunk.set_collision(x, y, true);
+            }
+        });
+        let mut paths = PathContext::new(&grid, MAX_NODES_PER_TICK);
+        assert_eq!(paths.find_path((2, 2), (10, 10), 0, &HashSet::new()), PathResult::NoPath);
+
+        let mut starved = PathContext::new(&grid, 5);
+        assert_eq!(starved.find_path((2, 2), (20, 20), 0, &HashSet::new()), PathResult::OutOfBudget);
+        assert_eq!(starved.find_path((2, 2), (2, 2), 0, &HashSet::new()), PathResult::Found(Vec::new()));
+
+        let occupied: HashSet<(i32, i32)> = [(3, 2)].into();
+        let PathResult::Found(path) = paths.find_path((2, 2), (4, 2), 0, &occupied) else {
+            panic!("expected a path");
+        };
+        assert!(!path.contains(&(3, 2)));
+    }
+}
@@ -0,0 +1,197 @@
+//! Pets and companions
+//!
+//! Items with a `summon_pet` use effect summon an `Npc` bound to its owner that
+//! follows them, attacks their target and levels from kills; pet levels are
+//! persisted in `character_pets`.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use crate::entity::EntityPrototype;
+use crate::npc::Npc;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Pets stop following once this close to their owner (Chebyshev distance)
+pub const PET_FOLLOW_DISTANCE: i32 = 1;
+
+/// Pets further than this from their owner are teleported back
+pub const PET_TELEPORT_DISTANCE: i32 = 12;
+
+/// Pets only assist against targets this close to their owner
+pub const PET_ASSIST_RANGE: i32 = 8;
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// `[<id>.pet]` section of an entity prototype
+#[derive(Debug, Clone, Deserialize)]
+pub struct PetConfig {
+    /// Whether the pet attacks its owner's target
+    #[serde(default = "default_assist")]
+    pub assist: bool,
+    #[serde(default = "default_max_level")]
+    pub max_level: i32,
+    /// Experience needed for the next level is `exp_per_level * level`
+    #[serde(default = "default_exp_per_level")]
+    pub exp_per_level: i64,
+    #[serde(default = "default_hp_per_level")]
+    pub hp_per_level: i32,
+    #[serde(default = "default_damage_per_level")]
+    pub damage_per_level: f32,
+}
+
+fn default_assist() -> bool {
+    true
+}
+
+fn default_max_level() -> i32 {
+    20
+}
+
+fn default_exp_per_level() -> i64 {
+    100
+}
+
+fn default_hp_per_level() -> i32 {
+    5
+}
+
+fn default_damage_per_level() -> f32 {
+    0.5
+}
+
+impl Default for PetConfig {
+    fn default() -> Self {
+        Self {
+            assist: default_assist(),
+            max_level: default_max_level(),
+            exp_per_level: default_exp_per_level(),
+            hp_per_level: default_hp_per_level(),
+            damage_per_level: default_damage_per_level(),
+        }
+    }
+}
+
+impl PetConfig {
+    /// Experience needed to go from `level` to the next level
+    pub fn exp_to_next_level(&self, level: i32) -> i64 {
+        self.exp_per_level * level as i64
+    }
+}
+
+// ============================================================================
+// Player Pets
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PetProgress {
+    pub level: i32,
+    /// Experience towards the next level
+    pub exp: i64,
+}
+
+impl Default for PetProgress {
+    fn default() -> Self {
+        Self { level: 1, exp: 0 }
+    }
+}
+
+/// Pets a character has summoned at least once, and the one currently out
+#[derive(Debug, Clone, Default)]
+pub struct PlayerPets {
+    pub owned: HashMap<String, PetProgress>,
+    pub active: Option<String>,
+}
+
+impl PlayerPets {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add experience to a pet, returning the new level if it leveled up
+    pub fn add_exp(&mut self, pet_id: &str, amount: i64, config: &PetConfig) -> Option<i32> {
+        let progress = self.owned.entry(pet_id.to_string()).or_default();
+        if progress.level >= config.max_level || amount <= 0 {
+            return None;
+        }
+
+        let old_level = progress.level;
+        progress.exp += amount;
+        while progress.level < config.max_level && progress.exp >= config.exp_to_next_level(progress.level) {
+            progress.exp -= config.exp_to_next_level(progress.level);
+            progress.level += 1;
+        }
+        if progress.level >= config.max_level {
+            progress.exp = 0;
+        }
+
+        (progress.level > old_level).then_some(progress.level)
+    }
+}
+
+// ============================================================================
+// Summoned Pets
+// ============================================================================
+
+/// A pet out in the world, keyed by its owner's player ID
+#[derive(Debug, Clone)]
+pub struct SummonedPet {
+    pub pet_id: String,
+    pub npc: Npc,
+    /// Instance the pet is in (None = overworld), kept in step with the owner
+    pub instance_id: Option<String>,
+    pub assist: bool,
+}
+
+/// Entity ID of a player's pet
+pub fn pet_entity_id(owner_id: &str) -> String {
+    format!("pet_{}", owner_id)
+}
+
+/// Build the pet entity for a prototype at the given level
+pub fn create_pet_npc(
+    owner_id: &str,
+    owner_name: &str,
+    prototype: &EntityPrototype,
+    level: i32,
+    x: i32,
+    y: i32,
+) -> Npc {
+    let mut npc = Npc::from_prototype(&pet_entity_id(owner_id), &prototype.id, prototype, x, y, level);
+    npc.owner_id = Some(owner_id.to_string());
+    npc.stats.display_name = format!("{}'s {}", owner_name, prototype.display_name);
+    apply_pet_level(&mut npc, prototype, level);
+    npc
+}
+
+/// Scale a pet's health and damage to its level
+pub fn apply_pet_level(npc: &mut Npc, prototype: &EntityPrototype, level: i32) {
+    let config = prototype.pet.clone().unwrap_or_default();
+    let levels_gained = (level - 1).max(0);
+    npc.level = level;
+    npc.max_hp = prototype.stats.max_hp + config.hp_per_level * levels_gained;
+    npc.hp = npc.max_hp;
+    npc.stats.damage = prototype.stats.damage + (config.damage_per_level * levels_gained as f32).floor() as i32;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pets_level_up_and_cap() {
+        let config = PetConfig { max_level: 3, exp_per_level: 100, ..PetConfig::default() };
+        let mut pets = PlayerPets::new();
+
+        assert_eq!(pets.add_exp("piglet", 50, &config), None);
+        assert_eq!(pets.owned["piglet"], PetProgress { level: 1, exp: 50 });
+
+        // 50 + 300 = level 2 (100) then level 3 (200), capped with no leftover
+        assert_eq!(pets.add_exp("piglet", 300, &config), Some(3));
+        assert_eq!(pets.owned["piglet"], PetProgress { level: 3, exp: 0 });
+        assert_eq!(pets.add_exp("piglet", 1000, &config), None);
+    }
+}
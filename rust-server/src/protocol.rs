@@ -91,6 +91,82 @@ pub enum ClientMessage {
     /// Enter a portal to transition to another map
     #[serde(rename = "enterPortal")]
     EnterPortal { portal_id: String },
+
+    /// Ask another player to trade
+    #[serde(rename = "tradeRequest")]
+    TradeRequest { target_id: String },
+
+    /// Accept or decline a trade request
+    #[serde(rename = "tradeRespond")]
+    TradeRespond { requester_id: String, accept: bool },
+
+    /// Add an item from an inventory slot to the trade offer
+    #[serde(rename = "tradeOfferItem")]
+    TradeOfferItem { slot_index: u8, quantity: i32 },
+
+    /// Remove a stack from the trade offer
+    #[serde(rename = "tradeRemoveItem")]
+    TradeRemoveItem { offer_index: u8 },
+
+    /// Set the amount of gold in the trade offer
+    #[serde(rename = "tradeSetGold")]
+    TradeSetGold { amount: i32 },
+
+    /// Accept the current offers (first stage)
+    #[serde(rename = "tradeAccept")]
+    TradeAccept,
+
+    /// Confirm the final offers (second stage)
+    #[serde(rename = "tradeConfirm")]
+    TradeConfirm,
+
+    /// Cancel the current trade
+    #[serde(rename = "tradeCancel")]
+    TradeCancel,
+
+    /// Invite another player to your party
+    #[serde(rename = "partyInvite")]
+    PartyInvite { target_id: String },
+
+    /// Accept or decline a party invite
+    #[serde(rename = "partyRespond")]
+    PartyRespond { inviter_id: String, accept: bool },
+
+    /// Leave the current party
+    #[serde(rename = "partyLeave")]
+    PartyLeave,
+
+    /// Remove a member from the party (leader only)
+    #[serde(rename = "partyKick")]
+    PartyKick { member_id: String },
+
+    /// Hand party leadership to another member (leader only)
+    #[serde(rename = "partyPromote")]
+    PartyPromote { member_id: String },
+
+    /// Select the achievement title shown before the player's name (None clears it)
+    #[serde(rename = "setTitle")]
+    SetTitle { achievement_id: Option<String> },
+
+    /// Open a storage chest in your house
+    #[serde(rename = "storageOpen")]
+    StorageOpen { furniture_id: i32 },
+
+    /// Move items from an inventory slot into a storage chest
+    #[serde(rename = "storageDeposit")]
+    StorageDeposit { furniture_id: i32, slot_index: u8, quantity: i32 },
+
+    /// Move a stack from a storage chest back to the inventory
+    #[serde(rename = "storageWithdraw")]
+    StorageWithdraw { furniture_id: i32, storage_slot: u8 },
+
+    /// Look inside your own gravestone
+    #[serde(rename = "gravestoneOpen")]
+    GravestoneOpen { gravestone_id: String },
+
+    /// Take back everything from your gravestone that fits in the inventory
+    #[serde(rename = "gravestoneRecover")]
+    GravestoneRecover { gravestone_id: String },
 }
 
 // ============================================================================
@@ -112,6 +188,8 @@ pub enum ServerMessage {
         skin: String,
         hair_style: Option<i32>,
         hair_color: Option<i32>,
+        guild_tag: Option<String>,
+        title: Option<String>,
     },
     PlayerLeft {
         id: String,
@@ -128,6 +206,8 @@ pub enum ServerMessage {
         sender_name: String,
         text: String,
         timestamp: u64,
+        /// Chat channel ("party", "guild"); None for local chat and system messages
+        channel: Option<String>,
     },
     TargetChanged {
         player_id: String,
@@ -344,6 +424,148 @@ pub enum ServerMessage {
         objects: Vec<ChunkObjectData>,
         walls: Vec<ChunkWallData>,
     },
+    /// Another player wants to trade
+    TradeRequested {
+        requester_id: String,
+        requester_name: String,
+    },
+    /// A trade window was opened with a partner
+    TradeOpened {
+        trade_id: String,
+        partner_id: String,
+        partner_name: String,
+    },
+    /// Current state of both offers, from the receiving player's perspective
+    TradeUpdate {
+        trade_id: String,
+        stage: String, // "offering" or "confirming"
+        my_items: Vec<TradeItemData>,
+        my_gold: i32,
+        my_accepted: bool,
+        my_confirmed: bool,
+        their_items: Vec<TradeItemData>,
+        their_gold: i32,
+        their_accepted: bool,
+        their_confirmed: bool,
+    },
+    /// Trade window closed (completed or cancelled)
+    TradeClosed {
+        trade_id: String,
+        completed: bool,
+        reason: Option<String>,
+    },
+    /// Invitation to join another player's party
+    PartyInvite {
+        inviter_id: String,
+        inviter_name: String,
+    },
+    /// Full party roster with member HP, sent on changes and periodically
+    PartyUpdate {
+        party_id: String,
+        leader_id: String,
+        loot_rule: String,
+        members: Vec<PartyMemberData>,
+    },
+    /// The receiving player is no longer in a party
+    PartyLeft {
+        party_id: String,
+    },
+    /// The player's standing with every faction
+    ReputationUpdate {
+        factions: Vec<ReputationData>,
+    },
+    /// Every achievement with the player's progress, and their selected title
+    AchievementsUpdate {
+        achievements: Vec<AchievementData>,
+        active_title: Option<String>,
+    },
+    /// The active daily and weekly challenges with the player's progress
+    ChallengesUpdate {
+        daily: Vec<ChallengeData>,
+        weekly: Vec<ChallengeData>,
+        /// Time until the next reset (ms)
+        daily_resets_in: u64,
+        weekly_resets_in: u64,
+        daily_streak: i32,
+        weekly_streak: i32,
+    },
+    /// An NPC was removed from the world for good (e.g. a world event NPC)
+    NpcDespawned {
+        id: String,
+    },
+    /// Running world events and upcoming scheduled ones, shown on a bounty board
+    BountyBoard {
+        npc_id: String,
+        events: Vec<WorldEventData>,
+        upcoming: Vec<UpcomingEventData>,
+    },
+    /// Boss health, phase and damage leaderboard during an encounter
+    BossUpdate {
+        boss_id: String,
+        name: String,
+        hp: i32,
+        max_hp: i32,
+        phase: i32,
+        phase_name: Option<String>,
+        enraged: bool,
+        leaderboard: Vec<BossDamageData>,
+    },
+    /// Tiles a boss area attack will hit after `delay_ms`
+    BossTelegraph {
+        boss_id: String,
+        tiles: Vec<(i32, i32)>,
+        delay_ms: u64,
+    },
+    /// A boss encounter ended, either with the boss dead or reset
+    BossEnded {
+        boss_id: String,
+        defeated: bool,
+    },
+    /// Furniture placed in the house the player is in, drawn as map objects
+    HouseFurniture {
+        owner_name: String,
+        furniture: Vec<FurnitureData>,
+    },
+    /// Contents of an open storage chest
+    StorageContents {
+        furniture_id: i32,
+        name: String,
+        capacity: i32,
+        slots: Vec<crate::item::InventorySlotUpdate>,
+    },
+    /// What dying cost the player, sent to them right after PlayerDied
+    DeathPenalty {
+        penalty: String,
+        gold_lost: i32,
+        gravestone_id: Option<String>,
+        expires_in: u64,
+    },
+    /// A gravestone appeared in the player's zone
+    GravestoneSpawned {
+        id: String,
+        owner_id: String,
+        owner_name: String,
+        x: i32,
+        y: i32,
+        expires_in: u64,
+    },
+    /// A gravestone was emptied or crumbled
+    GravestoneRemoved {
+        id: String,
+    },
+    /// Items left in the player's own gravestone
+    GravestoneContents {
+        id: String,
+        items: Vec<crate::item::InventorySlotUpdate>,
+        expires_in: u64,
+    },
+
+    // Status effects
+    /// A player's full list of active status effects (empty when all wore off)
+    StatusEffects {
+        player_id: String,
+        effects: Vec<StatusEffectData>,
+    },
 }
 
 /// Layer data for chunk transmission
@@ -461,6 +683,120 @@ pub struct ClientRecipeDef {
     pub results: Vec<RecipeResult>,
 }
 
+/// An offered item stack in a trade window
+#[derive(Debug, Clone, Serialize)]
+pub struct TradeItemData {
+    pub item_id: String,
+    pub quantity: i32,
+}
+
+/// A party member as shown in the client's party frames
+#[derive(Debug, Clone, Serialize)]
+pub struct PartyMemberData {
+    pub id: String,
+    pub name: String,
+    pub hp: i32,
+    pub max_hp: i32,
+    pub level: i32,
+    pub online: bool,
+}
+
+/// One achievement and the player's progress towards it
+#[derive(Debug, Clone, Serialize)]
+pub struct AchievementData {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub title: Option<String>,
+    pub progress: i64,
+    pub target: i64,
+    /// Unlock time (ms since epoch), None while locked
+    pub unlocked_at: Option<u64>,
+}
+
+/// A status effect on a player as shown in the client's buff bar
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusEffectData {
+    pub kind: String,
+    pub remaining_ms: u64,
+    pub stacks: u32,
+}
+
+/// One active challenge and the player's progress towards it
+#[derive(Debug, Clone, Serialize)]
+pub struct ChallengeData {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub progress: i32,
+    pub target: i32,
+    pub completed: bool,
+    pub reward_gold: i32,
+}
+
+/// A running world event and the player's contribution to it
+#[derive(Debug, Clone, Serialize)]
+pub struct WorldEventData {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub kind: String,
+    pub x: i32,
+    pub y: i32,
+    pub killed: u32,
+    pub total: u32,
+    /// Time until the event fails (ms)
+    pub ends_in: u64,
+    pub contribution: i32,
+    pub min_contribution: i32,
+    pub reward_gold: i32,
+}
+
+/// One row of a boss damage leaderboard
+#[derive(Debug, Clone, Serialize)]
+pub struct BossDamageData {
+    pub name: String,
+    pub damage: i32,
+}
+
+/// A scheduled world event that has not started yet
+#[derive(Debug, Clone, Serialize)]
+pub struct UpcomingEventData {
+    pub name: String,
+    pub kind: String,
+    /// Time until the event starts (ms)
+    pub starts_in: u64,
+}
+
+/// A player's standing with one faction, for the client's reputation panel
+#[derive(Debug, Clone, Serialize)]
+pub struct ReputationData {
+    pub faction_id: String,
+    pub name: String,
+    pub description: String,
+    pub standing: i32,
+    pub level: i32,
+    pub level_name: String,
+    /// Standing range of the current level; `level_max` is None at the top level
+    pub level_min: i32,
+    pub level_max: Option<i32>,
+}
+
+/// A piece of furniture placed in a house
+#[derive(Debug, Clone, Serialize)]
+pub struct FurnitureData {
+    pub id: i32,
+    pub item_id: String,
+    pub gid: u32,
+    pub x: i32,
+    pub y: i32,
+    /// Sprite size in pixels
+    pub width: u32,
+    pub height: u32,
+    /// Whether the furniture is a storage chest
+    pub storage: bool,
+}
+
 /// Shop data for client synchronization
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShopData {
@@ -524,6 +860,28 @@ impl ServerMessage {
             ServerMessage::Announcement { .. } => "announcement",
             ServerMessage::MapTransition { .. } => "mapTransition",
             ServerMessage::InteriorData { .. } => "interiorData",
+            ServerMessage::TradeRequested { .. } => "tradeRequested",
+            ServerMessage::TradeOpened { .. } => "tradeOpened",
+            ServerMessage::TradeUpdate { .. } => "tradeUpdate",
+            ServerMessage::TradeClosed { .. } => "tradeClosed",
+            ServerMessage::PartyInvite { .. } => "partyInvite",
+            ServerMessage::PartyUpdate { .. } => "partyUpdate",
+            ServerMessage::PartyLeft { .. } => "partyLeft",
+            ServerMessage::ReputationUpdate { .. } => "reputationUpdate",
+            ServerMessage::AchievementsUpdate { .. } => "achievementsUpdate",
+            ServerMessage::ChallengesUpdate { .. } => "challengesUpdate",
+            ServerMessage::NpcDespawned { .. } => "npcDespawned",
+            ServerMessage::BountyBoard { .. } => "bountyBoard",
+            ServerMessage::BossUpdate { .. } => "bossUpdate",
+            ServerMessage::BossTelegraph { .. } => "bossTelegraph",
+            ServerMessage::BossEnded { .. } => "bossEnded",
+            ServerMessage::HouseFurniture { .. } => "houseFurniture",
+            ServerMessage::StorageContents { .. } => "storageContents",
+            ServerMessage::DeathPenalty { .. } => "deathPenalty",
+            ServerMessage::GravestoneSpawned { .. } => "gravestoneSpawned",
+            ServerMessage::GravestoneRemoved { .. } => "gravestoneRemoved",
+            ServerMessage::GravestoneContents { .. } => "gravestoneContents",
+            ServerMessage::StatusEffects { .. } => "statusEffects",
         }
     }
 }
@@ -549,7 +907,7 @@ pub fn encode_server_message(msg: &ServerMessage) -> Result<Vec<u8>, String> {
             ));
             Value::Map(map)
         }
-        ServerMessage::PlayerJoined { id, name, x, y, gender, skin, hair_style, hair_color } => {
+        ServerMessage::PlayerJoined { id, name, x, y, gender, skin, hair_style, hair_color, guild_tag, title } => {
             let mut map = Vec::new();
             map.push((Value::String("id".into()), Value::String(id.clone().into())));
             map.push((
@@ -574,6 +932,20 @@ pub fn encode_server_message(msg: &ServerMessage) -> Result<Vec<u8>, String> {
                     None => Value::Nil,
                 },
             ));
+            map.push((
+                Value::String("guild_tag".into()),
+                match guild_tag {
+                    Some(tag) => Value::String(tag.clone().into()),
+                    None => Value::Nil,
+                },
+            ));
+            map.push((
+                Value::String("title".into()),
+                match title {
+                    Some(title) => Value::String(title.clone().into()),
+                    None => Value::Nil,
+                },
+            ));
             Value::Map(map)
         }
         ServerMessage::PlayerLeft { id } => {
@@ -696,6 +1068,28 @@ pub fn encode_server_message(msg: &ServerMessage) -> Result<Vec<u8>, String> {
                         Value::String("is_admin".into()),
                         Value::Boolean(p.is_admin),
                     ));
+                    pmap.push((
+                        Value::String("guild_tag".into()),
+                        match &p.guild_tag {
+                            Some(tag) => Value::String(tag.clone().into()),
+                            None => Value::Nil,
+                        },
+                    ));
+                    pmap.push((
+                        Value::String("title".into()),
+                        match &p.title {
+                            Some(title) => Value::String(title.clone().into()),
+                            None => Value::Nil,
+                        },
+                    ));
+                    pmap.push((
+                        Value::String("pvp_enabled".into()),
+                        Value::Boolean(p.pvp_enabled),
+                    ));
+                    pmap.push((
+                        Value::String("skulled".into()),
+                        Value::Boolean(p.skulled),
+                    ));
                     Value::Map(pmap)
                 })
                 .collect();
@@ -723,6 +1117,16 @@ pub fn encode_server_message(msg: &ServerMessage) -> Result<Vec<u8>, String> {
                     nmap.push((Value::String("is_merchant".into()), Value::Boolean(n.is_merchant)));
                     nmap.push((Value::String("move_speed".into()), Value::F32(n.move_speed)));
                     nmap.push((Value::String("just_attacked".into()), Value::Boolean(n.just_attacked)));
+                    if let Some(owner_id) = &n.owner_id {
+                        nmap.push((Value::String("owner_id".into()), Value::String(owner_id.clone().into())));
+                    }
+                    if let Some(rank) = &n.elite_rank {
+                        nmap.push((Value::String("elite_rank".into()), Value::String(rank.clone().into())));
+                        nmap.push((
+                            Value::String("elite_prefixes".into()),
+                            Value::Array(n.elite_prefixes.iter().map(|p| Value::String(p.clone().into())).collect()),
+                        ));
+                    }
                     Value::Map(nmap)
                 })
                 .collect();
@@ -735,6 +1139,7 @@ pub fn encode_server_message(msg: &ServerMessage) -> Result<Vec<u8>, String> {
             sender_name,
             text,
             timestamp,
+            channel,
         } => {
             let mut map = Vec::new();
             map.push((
@@ -753,6 +1158,12 @@ pub fn encode_server_message(msg: &ServerMessage) -> Result<Vec<u8>, String> {
                 Value::String("timestamp".into()),
                 Value::Integer((*timestamp).into()),
             ));
+            if let Some(channel) = channel {
+                map.push((
+                    Value::String("channel".into()),
+                    Value::String(channel.clone().into()),
+                ));
+            }
             Value::Map(map)
         }
         ServerMessage::TargetChanged {
@@ -1615,6 +2026,339 @@ pub fn encode_server_message(msg: &ServerMessage) -> Result<Vec<u8>, String> {
 
             Value::Map(map)
         }
+        ServerMessage::TradeRequested { requester_id, requester_name } => {
+            let mut map = Vec::new();
+            map.push((Value::String("requesterId".into()), Value::String(requester_id.clone().into())));
+            map.push((Value::String("requesterName".into()), Value::String(requester_name.clone().into())));
+            Value::Map(map)
+        }
+        ServerMessage::TradeOpened { trade_id, partner_id, partner_name } => {
+            let mut map = Vec::new();
+            map.push((Value::String("tradeId".into()), Value::String(trade_id.clone().into())));
+            map.push((Value::String("partnerId".into()), Value::String(partner_id.clone().into())));
+            map.push((Value::String("partnerName".into()), Value::String(partner_name.clone().into())));
+            Value::Map(map)
+        }
+        ServerMessage::TradeUpdate {
+            trade_id,
+            stage,
+            my_items,
+            my_gold,
+            my_accepted,
+            my_confirmed,
+            their_items,
+            their_gold,
+            their_accepted,
+            their_confirmed,
+        } => {
+            let encode_items = |items: &Vec<TradeItemData>| -> Value {
+                Value::Array(items.iter().map(|i| {
+                    let mut imap = Vec::new();
+                    imap.push((Value::String("itemId".into()), Value::String(i.item_id.clone().into())));
+                    imap.push((Value::String("quantity".into()), Value::Integer((i.quantity as i64).into())));
+                    Value::Map(imap)
+                }).collect())
+            };
+
+            let mut map = Vec::new();
+            map.push((Value::String("tradeId".into()), Value::String(trade_id.clone().into())));
+            map.push((Value::String("stage".into()), Value::String(stage.clone().into())));
+            map.push((Value::String("myItems".into()), encode_items(my_items)));
+            map.push((Value::String("myGold".into()), Value::Integer((*my_gold as i64).into())));
+            map.push((Value::String("myAccepted".into()), Value::Boolean(*my_accepted)));
+            map.push((Value::String("myConfirmed".into()), Value::Boolean(*my_confirmed)));
+            map.push((Value::String("theirItems".into()), encode_items(their_items)));
+            map.push((Value::String("theirGold".into()), Value::Integer((*their_gold as i64).into())));
+            map.push((Value::String("theirAccepted".into()), Value::Boolean(*their_accepted)));
+            map.push((Value::String("theirConfirmed".into()), Value::Boolean(*their_confirmed)));
+            Value::Map(map)
+        }
+        ServerMessage::TradeClosed { trade_id, completed, reason } => {
+            let mut map = Vec::new();
+            map.push((Value::String("tradeId".into()), Value::String(trade_id.clone().into())));
+            map.push((Value::String("completed".into()), Value::Boolean(*completed)));
+            map.push((
+                Value::String("reason".into()),
+                match reason {
+                    Some(r) => Value::String(r.clone().into()),
+                    None => Value::Nil,
+                },
+            ));
+            Value::Map(map)
+        }
+        ServerMessage::PartyInvite { inviter_id, inviter_name } => {
+            let mut map = Vec::new();
+            map.push((Value::String("inviterId".into()), Value::String(inviter_id.clone().into())));
+            map.push((Value::String("inviterName".into()), Value::String(inviter_name.clone().into())));
+            Value::Map(map)
+        }
+        ServerMessage::PartyUpdate { party_id, leader_id, loot_rule, members } => {
+            let member_values: Vec<Value> = members.iter().map(|m| {
+                let mut mmap = Vec::new();
+                mmap.push((Value::String("id".into()), Value::String(m.id.clone().into())));
+                mmap.push((Value::String("name".into()), Value::String(m.name.clone().into())));
+                mmap.push((Value::String("hp".into()), Value::Integer((m.hp as i64).into())));
+                mmap.push((Value::String("maxHp".into()), Value::Integer((m.max_hp as i64).into())));
+                mmap.push((Value::String("level".into()), Value::Integer((m.level as i64).into())));
+                mmap.push((Value::String("online".into()), Value::Boolean(m.online)));
+                Value::Map(mmap)
+            }).collect();
+
+            let mut map = Vec::new();
+            map.push((Value::String("partyId".into()), Value::String(party_id.clone().into())));
+            map.push((Value::String("leaderId".into()), Value::String(leader_id.clone().into())));
+            map.push((Value::String("lootRule".into()), Value::String(loot_rule.clone().into())));
+            map.push((Value::String("members".into()), Value::Array(member_values)));
+            Value::Map(map)
+        }
+        ServerMessage::PartyLeft { party_id } => {
+            let mut map = Vec::new();
+            map.push((Value::String("partyId".into()), Value::String(party_id.clone().into())));
+            Value::Map(map)
+        }
+        ServerMessage::ReputationUpdate { factions } => {
+            let faction_values: Vec<Value> = factions.iter().map(|f| {
+                let mut fmap = Vec::new();
+                fmap.push((Value::String("factionId".into()), Value::String(f.faction_id.clone().into())));
+                fmap.push((Value::String("name".into()), Value::String(f.name.clone().into())));
+                fmap.push((Value::String("description".into()), Value::String(f.description.clone().into())));
+                fmap.push((Value::String("standing".into()), Value::Integer((f.standing as i64).into())));
+                fmap.push((Value::String("level".into()), Value::Integer((f.level as i64).into())));
+                fmap.push((Value::String("levelName".into()), Value::String(f.level_name.clone().into())));
+                fmap.push((Value::String("levelMin".into()), Value::Integer((f.level_min as i64).into())));
+                fmap.push((Value::String("levelMax".into()), match f.level_max {
+                    Some(max) => Value::Integer((max as i64).into()),
+                    None => Value::Nil,
+                }));
+                Value::Map(fmap)
+            }).collect();
+
+            let mut map = Vec::new();
+            map.push((Value::String("factions".into()), Value::Array(faction_values)));
+            Value::Map(map)
+        }
+        ServerMessage::AchievementsUpdate { achievements, active_title } => {
+            let achievement_values: Vec<Value> = achievements.iter().map(|a| {
+                let mut amap = Vec::new();
+                amap.push((Value::String("id".into()), Value::String(a.id.clone().into())));
+                amap.push((Value::String("name".into()), Value::String(a.name.clone().into())));
+                amap.push((Value::String("description".into()), Value::String(a.description.clone().into())));
+                amap.push((Value::String("title".into()), match &a.title {
+                    Some(title) => Value::String(title.clone().into()),
+                    None => Value::Nil,
+                }));
+                amap.push((Value::String("progress".into()), Value::Integer(a.progress.into())));
+                amap.push((Value::String("target".into()), Value::Integer(a.target.into())));
+                amap.push((Value::String("unlockedAt".into()), match a.unlocked_at {
+                    Some(at) => Value::Integer(at.into()),
+                    None => Value::Nil,
+                }));
+                Value::Map(amap)
+            }).collect();
+
+            let mut map = Vec::new();
+            map.push((Value::String("achievements".into()), Value::Array(achievement_values)));
+            map.push((Value::String("activeTitle".into()), match active_title {
+                Some(id) => Value::String(id.clone().into()),
+                None => Value::Nil,
+            }));
+            Value::Map(map)
+        }
+        ServerMessage::ChallengesUpdate { daily, weekly, daily_resets_in, weekly_resets_in, daily_streak, weekly_streak } => {
+            let encode_challenges = |challenges: &[ChallengeData]| -> Value {
+                Value::Array(challenges.iter().map(|c| {
+                    let mut cmap = Vec::new();
+                    cmap.push((Value::String("id".into()), Value::String(c.id.clone().into())));
+                    cmap.push((Value::String("name".into()), Value::String(c.name.clone().into())));
+                    cmap.push((Value::String("description".into()), Value::String(c.description.clone().into())));
+                    cmap.push((Value::String("progress".into()), Value::Integer((c.progress as i64).into())));
+                    cmap.push((Value::String("target".into()), Value::Integer((c.target as i64).into())));
+                    cmap.push((Value::String("completed".into()), Value::Boolean(c.completed)));
+                    cmap.push((Value::String("rewardGold".into()), Value::Integer((c.reward_gold as i64).into())));
+                    Value::Map(cmap)
+                }).collect())
+            };
+
+            let mut map = Vec::new();
+            map.push((Value::String("daily".into()), encode_challenges(daily)));
+            map.push((Value::String("weekly".into()), encode_challenges(weekly)));
+            map.push((Value::String("dailyResetsIn".into()), Value::Integer((*daily_resets_in).into())));
+            map.push((Value::String("weeklyResetsIn".into()), Value::Integer((*weekly_resets_in).into())));
+            map.push((Value::String("dailyStreak".into()), Value::Integer((*daily_streak as i64).into())));
+            map.push((Value::String("weeklyStreak".into()), Value::Integer((*weekly_streak as i64).into())));
+            Value::Map(map)
+        }
+        ServerMessage::NpcDespawned { id } => {
+            let mut map = Vec::new();
+            map.push((Value::String("id".into()), Value::String(id.clone().into())));
+            Value::Map(map)
+        }
+        ServerMessage::BountyBoard { npc_id, events, upcoming } => {
+            let event_values: Vec<Value> = events.iter().map(|e| {
+                let mut emap = Vec::new();
+                emap.push((Value::String("id".into()), Value::String(e.id.clone().into())));
+                emap.push((Value::String("name".into()), Value::String(e.name.clone().into())));
+                emap.push((Value::String("description".into()), Value::String(e.description.clone().into())));
+                emap.push((Value::String("kind".into()), Value::String(e.kind.clone().into())));
+                emap.push((Value::String("x".into()), Value::Integer((e.x as i64).into())));
+                emap.push((Value::String("y".into()), Value::Integer((e.y as i64).into())));
+                emap.push((Value::String("killed".into()), Value::Integer((e.killed as i64).into())));
+                emap.push((Value::String("total".into()), Value::Integer((e.total as i64).into())));
+                emap.push((Value::String("endsIn".into()), Value::Integer(e.ends_in.into())));
+                emap.push((Value::String("contribution".into()), Value::Integer((e.contribution as i64).into())));
+                emap.push((Value::String("minContribution".into()), Value::Integer((e.min_contribution as i64).into())));
+                emap.push((Value::String("rewardGold".into()), Value::Integer((e.reward_gold as i64).into())));
+                Value::Map(emap)
+            }).collect();
+            let upcoming_values: Vec<Value> = upcoming.iter().map(|u| {
+                let mut umap = Vec::new();
+                umap.push((Value::String("name".into()), Value::String(u.name.clone().into())));
+                umap.push((Value::String("kind".into()), Value::String(u.kind.clone().into())));
+                umap.push((Value::String("startsIn".into()), Value::Integer(u.starts_in.into())));
+                Value::Map(umap)
+            }).collect();
+
+            let mut map = Vec::new();
+            map.push((Value::String("npcId".into()), Value::String(npc_id.clone().into())));
+            map.push((Value::String("events".into()), Value::Array(event_values)));
+            map.push((Value::String("upcoming".into()), Value::Array(upcoming_values)));
+            Value::Map(map)
+        }
+        ServerMessage::BossUpdate { boss_id, name, hp, max_hp, phase, phase_name, enraged, leaderboard } => {
+            let leaderboard_values: Vec<Value> = leaderboard.iter().map(|entry| {
+                let mut lmap = Vec::new();
+                lmap.push((Value::String("name".into()), Value::String(entry.name.clone().into())));
+                lmap.push((Value::String("damage".into()), Value::Integer((entry.damage as i64).into())));
+                Value::Map(lmap)
+            }).collect();
+
+            let mut map = Vec::new();
+            map.push((Value::String("bossId".into()), Value::String(boss_id.clone().into())));
+            map.push((Value::String("name".into()), Value::String(name.clone().into())));
+            map.push((Value::String("hp".into()), Value::Integer((*hp as i64).into())));
+            map.push((Value::String("maxHp".into()), Value::Integer((*max_hp as i64).into())));
+            map.push((Value::String("phase".into()), Value::Integer((*phase as i64).into())));
+            map.push((Value::String("phaseName".into()), match phase_name {
+                Some(name) => Value::String(name.clone().into()),
+                None => Value::Nil,
+            }));
+            map.push((Value::String("enraged".into()), Value::Boolean(*enraged)));
+            map.push((Value::String("leaderboard".into()), Value::Array(leaderboard_values)));
+            Value::Map(map)
+        }
+        ServerMessage::BossTelegraph { boss_id, tiles, delay_ms } => {
+            let tile_values: Vec<Value> = tiles.iter().map(|(x, y)| {
+                Value::Array(vec![
+                    Value::Integer((*x as i64).into()),
+                    Value::Integer((*y as i64).into()),
+                ])
+            }).collect();
+
+            let mut map = Vec::new();
+            map.push((Value::String("bossId".into()), Value::String(boss_id.clone().into())));
+            map.push((Value::String("tiles".into()), Value::Array(tile_values)));
+            map.push((Value::String("delayMs".into()), Value::Integer((*delay_ms).into())));
+            Value::Map(map)
+        }
+        ServerMessage::BossEnded { boss_id, defeated } => {
+            let mut map = Vec::new();
+            map.push((Value::String("bossId".into()), Value::String(boss_id.clone().into())));
+            map.push((Value::String("defeated".into()), Value::Boolean(*defeated)));
+            Value::Map(map)
+        }
+        ServerMessage::HouseFurniture { owner_name, furniture } => {
+            let furniture_values: Vec<Value> = furniture.iter().map(|f| {
+                let mut fmap = Vec::new();
+                fmap.push((Value::String("id".into()), Value::Integer((f.id as i64).into())));
+                fmap.push((Value::String("itemId".into()), Value::String(f.item_id.clone().into())));
+                fmap.push((Value::String("gid".into()), Value::Integer((f.gid as i64).into())));
+                fmap.push((Value::String("x".into()), Value::Integer((f.x as i64).into())));
+                fmap.push((Value::String("y".into()), Value::Integer((f.y as i64).into())));
+                fmap.push((Value::String("width".into()), Value::Integer((f.width as i64).into())));
+                fmap.push((Value::String("height".into()), Value::Integer((f.height as i64).into())));
+                fmap.push((Value::String("storage".into()), Value::Boolean(f.storage)));
+                Value::Map(fmap)
+            }).collect();
+
+            let mut map = Vec::new();
+            map.push((Value::String("ownerName".into()), Value::String(owner_name.clone().into())));
+            map.push((Value::String("furniture".into()), Value::Array(furniture_values)));
+            Value::Map(map)
+        }
+        ServerMessage::StorageContents { furniture_id, name, capacity, slots } => {
+            let slot_values: Vec<Value> = slots.iter().map(|slot| {
+                let mut smap = Vec::new();
+                smap.push((Value::String("slot".into()), Value::Integer((slot.slot as i64).into())));
+                smap.push((Value::String("itemId".into()), Value::String(slot.item_id.clone().into())));
+                smap.push((Value::String("quantity".into()), Value::Integer((slot.quantity as i64).into())));
+                Value::Map(smap)
+            }).collect();
+
+            let mut map = Vec::new();
+            map.push((Value::String("furnitureId".into()), Value::Integer((*furniture_id as i64).into())));
+            map.push((Value::String("name".into()), Value::String(name.clone().into())));
+            map.push((Value::String("capacity".into()), Value::Integer((*capacity as i64).into())));
+            map.push((Value::String("slots".into()), Value::Array(slot_values)));
+            Value::Map(map)
+        }
+        ServerMessage::DeathPenalty { penalty, gold_lost, gravestone_id, expires_in } => {
+            let mut map = Vec::new();
+            map.push((Value::String("penalty".into()), Value::String(penalty.clone().into())));
+            map.push((Value::String("goldLost".into()), Value::Integer((*gold_lost as i64).into())));
+            map.push((
+                Value::String("gravestoneId".into()),
+                match gravestone_id {
+                    Some(id) => Value::String(id.clone().into()),
+                    None => Value::Nil,
+                },
+            ));
+            map.push((Value::String("expiresIn".into()), Value::Integer((*expires_in).into())));
+            Value::Map(map)
+        }
+        ServerMessage::GravestoneSpawned { id, owner_id, owner_name, x, y, expires_in } => {
+            let mut map = Vec::new();
+            map.push((Value::String("id".into()), Value::String(id.clone().into())));
+            map.push((Value::String("ownerId".into()), Value::String(owner_id.clone().into())));
+            map.push((Value::String("ownerName".into()), Value::String(owner_name.clone().into())));
+            map.push((Value::String("x".into()), Value::Integer((*x as i64).into())));
+            map.push((Value::String("y".into()), Value::Integer((*y as i64).into())));
+            map.push((Value::String("expiresIn".into()), Value::Integer((*expires_in).into())));
+            Value::Map(map)
+        }
+        ServerMessage::GravestoneRemoved { id } => {
+            let mut map = Vec::new();
+            map.push((Value::String("id".into()), Value::String(id.clone().into())));
+            Value::Map(map)
+        }
+        ServerMessage::GravestoneContents { id, items, expires_in } => {
+            let item_values: Vec<Value> = items.iter().map(|item| {
+                let mut imap = Vec::new();
+                imap.push((Value::String("slot".into()), Value::Integer((item.slot as i64).into())));
+                imap.push((Value::String("itemId".into()), Value::String(item.item_id.clone().into())));
+                imap.push((Value::String("quantity".into()), Value::Integer((item.quantity as i64).into())));
+                Value::Map(imap)
+            }).collect();
+
+            let mut map = Vec::new();
+            map.push((Value::String("id".into()), Value::String(id.clone().into())));
+            map.push((Value::String("items".into()), Value::Array(item_values)));
+            map.push((Value::String("expiresIn".into()), Value::Integer((*expires_in).into())));
+            Value::Map(map)
+        }
+        ServerMessage::StatusEffects { player_id, effects } => {
+            let effect_values: Vec<Value> = effects.iter().map(|effect| {
+                let mut emap = Vec::new();
+                emap.push((Value::String("kind".into()), Value::String(effect.kind.clone().into())));
+                emap.push((Value::String("remainingMs".into()), Value::Integer(effect.remaining_ms.into())));
+                emap.push((Value::String("stacks".into()), Value::Integer((effect.stacks as i64).into())));
+                Value::Map(emap)
+            }).collect();
+
+            let mut map = Vec::new();
+            map.push((Value::String("playerId".into()), Value::String(player_id.clone().into())));
+            map.push((Value::String("effects".into()), Value::Array(effect_values)));
+            Value::Map(map)
+        }
     };
 
     // Encode as [13, "msg_type", data] - matching Colyseus ROOM_DATA format
@@ -1795,6 +2539,76 @@ pub fn decode_client_message(data: &[u8]) -> Result<ClientMessage, String> {
             let portal_id = extract_string(msg_data, "portalId").unwrap_or_default();
             Ok(ClientMessage::EnterPortal { portal_id })
         }
+        "tradeRequest" => {
+            let target_id = extract_string(msg_data, "targetId").unwrap_or_default();
+            Ok(ClientMessage::TradeRequest { target_id })
+        }
+        "tradeRespond" => {
+            let requester_id = extract_string(msg_data, "requesterId").unwrap_or_default();
+            let accept = extract_bool(msg_data, "accept").unwrap_or(false);
+            Ok(ClientMessage::TradeRespond { requester_id, accept })
+        }
+        "tradeOfferItem" => {
+            let slot_index = extract_i32(msg_data, "slotIndex").unwrap_or(0) as u8;
+            let quantity = extract_i32(msg_data, "quantity").unwrap_or(1);
+            Ok(ClientMessage::TradeOfferItem { slot_index, quantity })
+        }
+        "tradeRemoveItem" => {
+            let offer_index = extract_i32(msg_data, "offerIndex").unwrap_or(0) as u8;
+            Ok(ClientMessage::TradeRemoveItem { offer_index })
+        }
+        "tradeSetGold" => {
+            let amount = extract_i32(msg_data, "amount").unwrap_or(0);
+            Ok(ClientMessage::TradeSetGold { amount })
+        }
+        "tradeAccept" => Ok(ClientMessage::TradeAccept),
+        "tradeConfirm" => Ok(ClientMessage::TradeConfirm),
+        "tradeCancel" => Ok(ClientMessage::TradeCancel),
+        "partyInvite" => {
+            let target_id = extract_string(msg_data, "targetId").unwrap_or_default();
+            Ok(ClientMessage::PartyInvite { target_id })
+        }
+        "partyRespond" => {
+            let inviter_id = extract_string(msg_data, "inviterId").unwrap_or_default();
+            let accept = extract_bool(msg_data, "accept").unwrap_or(false);
+            Ok(ClientMessage::PartyRespond { inviter_id, accept })
+        }
+        "partyLeave" => Ok(ClientMessage::PartyLeave),
+        "partyKick" => {
+            let member_id = extract_string(msg_data, "memberId").unwrap_or_default();
+            Ok(ClientMessage::PartyKick { member_id })
+        }
+        "partyPromote" => {
+            let member_id = extract_string(msg_data, "memberId").unwrap_or_default();
+            Ok(ClientMessage::PartyPromote { member_id })
+        }
+        "setTitle" => {
+            let achievement_id = extract_string(msg_data, "achievementId");
+            Ok(ClientMessage::SetTitle { achievement_id })
+        }
+        "storageOpen" => {
+            let furniture_id = extract_i32(msg_data, "furnitureId").unwrap_or(0);
+            Ok(ClientMessage::StorageOpen { furniture_id })
+        }
+        "storageDeposit" => {
+            let furniture_id = extract_i32(msg_data, "furnitureId").unwrap_or(0);
+            let slot_index = extract_i32(msg_data, "slotIndex").unwrap_or(0) as u8;
+            let quantity = extract_i32(msg_data, "quantity").unwrap_or(1);
+            Ok(ClientMessage::StorageDeposit { furniture_id, slot_index, quantity })
+        }
+        "storageWithdraw" => {
+            let furniture_id = extract_i32(msg_data, "furnitureId").unwrap_or(0);
+            let storage_slot = extract_i32(msg_data, "storageSlot").unwrap_or(0) as u8;
+            Ok(ClientMessage::StorageWithdraw { furniture_id, storage_slot })
+        }
+        "gravestoneOpen" => {
+            let gravestone_id = extract_string(msg_data, "gravestoneId").unwrap_or_default();
+            Ok(ClientMessage::GravestoneOpen { gravestone_id })
+        }
+        "gravestoneRecover" => {
+            let gravestone_id = extract_string(msg_data, "gravestoneId").unwrap_or_default();
+            Ok(ClientMessage::GravestoneRecover { gravestone_id })
+        }
         _ => Err(format!("Unknown message type: {}", msg_type)),
     }
 }
@@ -1835,3 +2649,11 @@ fn extract_i32(value: &rmpv::Value, key: &str) -> Option<i32> {
             })
     })
 }
+
+fn extract_bool(value: &rmpv::Value, key: &str) -> Option<bool> {
+    value.as_map().and_then(|map| {
+        map.iter()
+            .find(|(k, _)| k.as_str() == Some(key))
+            .and_then(|(_, v)| v.as_bool())
+    })
+}
@@ -0,0 +1,74 @@
+//! Player-versus-player combat rules
+//!
+//! Players inside a PvP zone (rectangles in chunk and interior map data) can
+//! attack each other freely. Elsewhere both sides must have opted in with
+//! `/pvp on`, and the attacker is marked with a skull for a while. Skulled
+//! players can be attacked by anyone who has opted in, and can't opt out
+//! until the skull wears off.
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// How long a skull lasts after the latest attack outside a PvP zone
+pub const SKULL_DURATION_MS: u64 = 5 * 60 * 1000;
+
+// ============================================================================
+// Attack Rules
+// ============================================================================
+
+/// Where a player stands with regard to PvP at the moment of an attack
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PvpStanding {
+    /// Standing inside a PvP zone
+    pub in_zone: bool,
+    /// Opted in with `/pvp on`
+    pub opted_in: bool,
+    /// Currently carrying a skull
+    pub skulled: bool,
+}
+
+/// Whether an attack on another player is allowed.
+/// Returns whether the attacker gets skulled for it, or the reason it was refused.
+pub fn check_attack(attacker: PvpStanding, target: PvpStanding) -> Result<bool, &'static str> {
+    if attacker.in_zone && target.in_zone {
+        return Ok(false);
+    }
+    if !attacker.opted_in {
+        return Err("You can only attack players in a PvP zone. Use /pvp on to fight elsewhere.");
+    }
+    if !target.opted_in && !target.skulled {
+        return Err("That player has not enabled PvP.");
+    }
+    // Hunting down a skulled player doesn't earn a skull of your own
+    Ok(!target.skulled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn standing(in_zone: bool, opted_in: bool, skulled: bool) -> PvpStanding {
+        PvpStanding { in_zone, opted_in, skulled }
+    }
+
+    #[test]
+    fn test_zone_fights_need_no_opt_in() {
+        assert_eq!(check_attack(standing(true, false, false), standing(true, false, false)), Ok(false));
+        // Only one side in the zone falls back to the opt-in rules
+        assert!(check_attack(standing(true, false, false), standing(false, false, false)).is_err());
+    }
+
+    #[test]
+    fn test_opt_in_fights_skull_the_attacker() {
+        assert_eq!(check_attack(standing(false, true, false), standing(false, true, false)), Ok(true));
+        assert!(check_attack(standing(false, true, false), standing(false, false, false)).is_err());
+        assert!(check_attack(standing(false, false, false), standing(false, true, false)).is_err());
+    }
+
+    #[test]
+    fn test_skulled_players_are_fair_game() {
+        assert_eq!(check_attack(standing(false, true, false), standing(false, false, true)), Ok(false));
+        assert!(check_attack(standing(false, false, false), standing(false, false, true)).is_err());
+    }
+}
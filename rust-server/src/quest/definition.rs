@@ -4,6 +4,10 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::reputation::ReputationReward;
+
+use super::events::QuestEvent;
+
 /// A quest definition loaded from TOML
 #[derive(Debug, Clone, Deserialize)]
 pub struct RawQuestFile {
@@ -77,6 +81,11 @@ pub struct RawReward {
     pub gold: i32,
     #[serde(default)]
     pub items: Vec<RawItemReward>,
+    #[serde(default)]
+    pub reputation: Vec<ReputationReward>,
+    /// Shared loot table rolled at the player's combat level
+    #[serde(default)]
+    pub loot_table: Option<String>,
 }
 
 /// Item reward entry
@@ -111,6 +120,10 @@ pub enum ObjectiveType {
     TalkTo,
     /// Reach a specific location
     ReachLocation,
+    /// Craft X items of type Y
+    CraftItem,
+    /// Earn X gold from source Y ("sales", "loot", "quests")
+    EarnGold,
 }
 
 impl ObjectiveType {
@@ -120,9 +133,26 @@ impl ObjectiveType {
             "collect_item" | "collect" => Some(ObjectiveType::CollectItem),
             "talk_to" | "talk" => Some(ObjectiveType::TalkTo),
             "reach_location" | "reach" | "location" => Some(ObjectiveType::ReachLocation),
+            "craft_item" | "craft" => Some(ObjectiveType::CraftItem),
+            "earn_gold" | "gold" => Some(ObjectiveType::EarnGold),
             _ => None,
         }
     }
+
+    /// Progress an event makes towards an objective of this type on `target`,
+    /// or None if the event doesn't count. A target of "*" matches anything.
+    pub fn progress_for(&self, target: &str, event: &QuestEvent) -> Option<i32> {
+        let (event_target, amount) = match (self, event) {
+            (ObjectiveType::KillMonster, QuestEvent::MonsterKilled { entity_type, .. }) => (entity_type, 1),
+            (ObjectiveType::CollectItem, QuestEvent::ItemCollected { item_id, count, .. }) => (item_id, *count),
+            (ObjectiveType::TalkTo, QuestEvent::NpcInteraction { npc_id, .. }) => (npc_id, 1),
+            (ObjectiveType::ReachLocation, QuestEvent::LocationReached { location_id, .. }) => (location_id, 1),
+            (ObjectiveType::CraftItem, QuestEvent::ItemCrafted { item_id, count, .. }) => (item_id, *count),
+            (ObjectiveType::EarnGold, QuestEvent::GoldEarned { source, amount, .. }) => (source, *amount),
+            _ => return None,
+        };
+        (target == "*" || target == event_target).then_some(amount)
+    }
 }
 
 /// A resolved quest objective
@@ -167,6 +197,8 @@ pub struct Reward {
     pub exp: i32,
     pub gold: i32,
     pub items: Vec<ItemReward>,
+    pub reputation: Vec<ReputationReward>,
+    pub loot_table: Option<String>,
 }
 
 impl Reward {
@@ -178,6 +210,8 @@ impl Reward {
                 item_id: i.id.clone(),
                 count: i.count,
             }).collect(),
+            reputation: raw.reputation.clone(),
+            loot_table: raw.loot_table.clone(),
         }
     }
 }
@@ -314,6 +348,29 @@ mod tests {
         assert_eq!(ObjectiveType::from_str("collect_item"), Some(ObjectiveType::CollectItem));
         assert_eq!(ObjectiveType::from_str("talk_to"), Some(ObjectiveType::TalkTo));
         assert_eq!(ObjectiveType::from_str("reach_location"), Some(ObjectiveType::ReachLocation));
+        assert_eq!(ObjectiveType::from_str("craft_item"), Some(ObjectiveType::CraftItem));
+        assert_eq!(ObjectiveType::from_str("earn_gold"), Some(ObjectiveType::EarnGold));
         assert_eq!(ObjectiveType::from_str("invalid"), None);
     }
+
+    #[test]
+    fn test_objective_progress_for_event() {
+        let kill = QuestEvent::MonsterKilled {
+            player_id: "p".to_string(),
+            entity_type: "slime".to_string(),
+            level: 1,
+        };
+        assert_eq!(ObjectiveType::KillMonster.progress_for("slime", &kill), Some(1));
+        assert_eq!(ObjectiveType::KillMonster.progress_for("*", &kill), Some(1));
+        assert_eq!(ObjectiveType::KillMonster.progress_for("pig", &kill), None);
+        assert_eq!(ObjectiveType::CollectItem.progress_for("slime", &kill), None);
+
+        let sale = QuestEvent::GoldEarned {
+            player_id: "p".to_string(),
+            source: "sales".to_string(),
+            amount: 75,
+        };
+        assert_eq!(ObjectiveType::EarnGold.progress_for("sales", &sale), Some(75));
+        assert_eq!(ObjectiveType::EarnGold.progress_for("loot", &sale), None);
+    }
 }
@@ -42,6 +42,23 @@ pub enum QuestEvent {
         y: i32,
     },
 
+    /// Player crafted an item
+    ItemCrafted {
+        player_id: String,
+        /// Item ID of the crafted result
+        item_id: String,
+        /// Quantity crafted
+        count: i32,
+    },
+
+    /// Player earned gold
+    GoldEarned {
+        player_id: String,
+        /// Where the gold came from ("sales", "loot", "quests")
+        source: String,
+        amount: i32,
+    },
+
     /// Player accepted a quest (from dialogue)
     QuestAccepted {
         player_id: String,
@@ -70,6 +87,8 @@ impl QuestEvent {
             QuestEvent::ItemCollected { player_id, .. } => player_id,
             QuestEvent::NpcInteraction { player_id, .. } => player_id,
             QuestEvent::LocationReached { player_id, .. } => player_id,
+            QuestEvent::ItemCrafted { player_id, .. } => player_id,
+            QuestEvent::GoldEarned { player_id, .. } => player_id,
             QuestEvent::QuestAccepted { player_id, .. } => player_id,
             QuestEvent::QuestAbandoned { player_id, .. } => player_id,
             QuestEvent::DialogueChoice { player_id, .. } => player_id,
@@ -83,6 +102,8 @@ impl QuestEvent {
             QuestEvent::ItemCollected { .. } => "item_collected",
             QuestEvent::NpcInteraction { .. } => "npc_interaction",
             QuestEvent::LocationReached { .. } => "location_reached",
+            QuestEvent::ItemCrafted { .. } => "item_crafted",
+            QuestEvent::GoldEarned { .. } => "gold_earned",
             QuestEvent::QuestAccepted { .. } => "quest_accepted",
             QuestEvent::QuestAbandoned { .. } => "quest_abandoned",
             QuestEvent::DialogueChoice { .. } => "dialogue_choice",
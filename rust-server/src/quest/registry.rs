@@ -255,14 +255,12 @@ impl QuestRegistry {
         let mut results = Vec::new();
 
         match event {
-            QuestEvent::MonsterKilled { entity_type, .. } => {
+            QuestEvent::MonsterKilled { .. }
+            | QuestEvent::ItemCollected { .. }
+            | QuestEvent::ItemCrafted { .. }
+            | QuestEvent::GoldEarned { .. } => {
                 results.extend(
-                    self.update_kill_objectives(player_state, entity_type).await
-                );
-            }
-            QuestEvent::ItemCollected { item_id, count, .. } => {
-                results.extend(
-                    self.update_collect_objectives(player_state, item_id, *count).await
+                    self.update_counted_objectives(player_state, event).await
                 );
             }
             QuestEvent::NpcInteraction { npc_id, .. } => {
@@ -281,29 +279,27 @@ impl QuestRegistry {
         results
     }
 
-    /// Update kill objectives for active quests
-    async fn update_kill_objectives(
+    /// Update count-based objectives (kill, collect, craft, earn gold) for active quests
+    async fn update_counted_objectives(
         &self,
         player_state: &mut PlayerQuestState,
-        entity_type: &str,
+        event: &QuestEvent,
     ) -> Vec<QuestEventResult> {
         let mut results = Vec::new();
         let quests = self.quests.read().await;
 
-        // Find all active quests with kill objectives matching this entity
+        // Find all active quests with objectives this event counts towards
         let quest_ids: Vec<String> = player_state.active_quests.keys().cloned().collect();
 
         for quest_id in quest_ids {
             if let Some(quest) = quests.get(&quest_id) {
                 for objective in &quest.objectives {
-                    if objective.objective_type == ObjectiveType::KillMonster
-                        && objective.target == entity_type
+                    if let Some(amount) = objective.objective_type.progress_for(&objective.target, event)
+                        && let Some(result) = self.update_single_objective(
+                            player_state, &quest_id, &objective.id, amount
+                        )
                     {
-                        if let Some(result) = self.update_single_objective(
-                            player_state, &quest_id, &objective.id, 1
-                        ) {
-                            results.push(result);
-                        }
+                        results.push(result);
                     }
                 }
             }
@@ -349,37 +345,6 @@ impl QuestRegistry {
         ))
     }
 
-    /// Update collect objectives for active quests
-    async fn update_collect_objectives(
-        &self,
-        player_state: &mut PlayerQuestState,
-        item_id: &str,
-        count: i32,
-    ) -> Vec<QuestEventResult> {
-        let mut results = Vec::new();
-        let quests = self.quests.read().await;
-
-        let quest_ids: Vec<String> = player_state.active_quests.keys().cloned().collect();
-
-        for quest_id in quest_ids {
-            if let Some(quest) = quests.get(&quest_id) {
-                for objective in &quest.objectives {
-                    if objective.objective_type == ObjectiveType::CollectItem
-                        && objective.target == item_id
-                    {
-                        if let Some(result) = self.update_single_objective(
-                            player_state, &quest_id, &objective.id, count
-                        ) {
-                            results.push(result);
-                        }
-                    }
-                }
-            }
-        }
-
-        results
-    }
-
     /// Update talk_to objectives for active quests
     async fn update_talk_objectives(
         &self,
@@ -0,0 +1,240 @@
+//! Faction reputation
+//!
+//! Factions are loaded from `data/factions/*.toml`. Every character has a
+//! standing with each faction (persisted in `character_reputation`), which
+//! maps to a named level. Levels are numbered relative to Neutral (0), so
+//! `ReputationRequirement { level: 1 }` means "at least Friendly".
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use tracing::{info, warn};
+
+// ============================================================================
+// Levels
+// ============================================================================
+
+/// Lowest and highest possible standing
+pub const REPUTATION_MIN: i32 = -42_000;
+pub const REPUTATION_MAX: i32 = 42_000;
+
+/// Minimum standing and name of each level, from Hated (-3) to Exalted (4)
+const LEVELS: &[(i32, &str)] = &[
+    (REPUTATION_MIN, "Hated"),
+    (-6_000, "Hostile"),
+    (-3_000, "Unfriendly"),
+    (0, "Neutral"),
+    (3_000, "Friendly"),
+    (9_000, "Honored"),
+    (21_000, "Revered"),
+    (REPUTATION_MAX, "Exalted"),
+];
+
+/// Index of Neutral in `LEVELS`
+const NEUTRAL_INDEX: i32 = 3;
+
+/// Merchants refuse to trade below this level (Hostile and Hated)
+pub const MIN_TRADE_LEVEL: i32 = -1;
+
+/// Reputation level (relative to Neutral) for a standing
+pub fn reputation_level(standing: i32) -> i32 {
+    let index = LEVELS.iter().rposition(|(min, _)| standing >= *min).unwrap_or(0);
+    index as i32 - NEUTRAL_INDEX
+}
+
+/// Display name of a reputation level
+pub fn level_name(level: i32) -> &'static str {
+    let index = (level + NEUTRAL_INDEX).clamp(0, LEVELS.len() as i32 - 1);
+    LEVELS[index as usize].1
+}
+
+/// Standing range `[min, next)` of a level; `next` is None at Exalted
+pub fn level_bounds(level: i32) -> (i32, Option<i32>) {
+    let index = (level + NEUTRAL_INDEX).clamp(0, LEVELS.len() as i32 - 1) as usize;
+    (LEVELS[index].0, LEVELS.get(index + 1).map(|(min, _)| *min))
+}
+
+/// Multiplier on merchant prices when buying: discounts from Friendly up,
+/// a markup at Unfriendly
+pub fn purchase_price_modifier(level: i32) -> f32 {
+    match level {
+        i32::MIN..=-1 => 1.10,
+        0 => 1.0,
+        1 => 0.95,
+        2 => 0.90,
+        3 => 0.85,
+        _ => 0.80,
+    }
+}
+
+/// Multiplier on what merchants pay when selling (mirror of the purchase modifier)
+pub fn sale_price_modifier(level: i32) -> f32 {
+    2.0 - purchase_price_modifier(level)
+}
+
+// ============================================================================
+// Factions
+// ============================================================================
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FactionDefinition {
+    pub id: String,
+    pub display_name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Standing every character starts with
+    #[serde(default)]
+    pub starting_standing: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct FactionFile {
+    #[serde(default)]
+    faction: Vec<FactionDefinition>,
+}
+
+/// A reputation change granted by a quest or kill
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReputationReward {
+    pub faction: String,
+    pub amount: i32,
+}
+
+/// Registry of all factions
+#[derive(Debug, Default)]
+pub struct FactionRegistry {
+    factions: HashMap<String, FactionDefinition>,
+}
+
+impl FactionRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load all faction definitions from a directory
+    pub fn load_from_directory(&mut self, path: &Path) -> Result<(), String> {
+        if !path.exists() {
+            warn!("Faction directory does not exist: {:?}", path);
+            return Ok(());
+        }
+
+        for entry in fs::read_dir(path).map_err(|e| e.to_string())? {
+            let entry = entry.map_err(|e| e.to_string())?;
+            let file_path = entry.path();
+
+            if file_path.extension().and_then(|s| s.to_str()) == Some("toml") {
+                let contents = fs::read_to_string(&file_path)
+                    .map_err(|e| format!("Failed to read {:?}: {}", file_path, e))?;
+                let file: FactionFile = toml::from_str(&contents)
+                    .map_err(|e| format!("Failed to parse {:?}: {}", file_path, e))?;
+
+                for faction in file.faction {
+                    if self.factions.contains_key(&faction.id) {
+                        warn!("Duplicate faction ID '{}' in {:?}, overwriting", faction.id, file_path);
+                    }
+                    self.factions.insert(faction.id.clone(), faction);
+                }
+            }
+        }
+
+        info!("Loaded {} faction definitions", self.factions.len());
+        Ok(())
+    }
+
+    pub fn get(&self, faction_id: &str) -> Option<&FactionDefinition> {
+        self.factions.get(faction_id)
+    }
+
+    /// All factions, sorted by display name
+    pub fn all(&self) -> Vec<&FactionDefinition> {
+        let mut factions: Vec<_> = self.factions.values().collect();
+        factions.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+        factions
+    }
+}
+
+// ============================================================================
+// Player Reputation
+// ============================================================================
+
+/// A character's standing with each faction they have interacted with.
+/// Factions missing from the map are at their starting standing.
+#[derive(Debug, Clone, Default)]
+pub struct PlayerReputation {
+    pub standings: HashMap<String, i32>,
+}
+
+impl PlayerReputation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn standing(&self, faction: &FactionDefinition) -> i32 {
+        self.standings.get(&faction.id).copied().unwrap_or(faction.starting_standing)
+    }
+
+    pub fn level(&self, faction: &FactionDefinition) -> i32 {
+        reputation_level(self.standing(faction))
+    }
+
+    /// Change standing with a faction, clamped to the valid range.
+    /// Returns (old standing, new standing).
+    pub fn adjust(&mut self, faction: &FactionDefinition, amount: i32) -> (i32, i32) {
+        let old = self.standing(faction);
+        let new = old.saturating_add(amount).clamp(REPUTATION_MIN, REPUTATION_MAX);
+        self.standings.insert(faction.id.clone(), new);
+        (old, new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn faction(starting_standing: i32) -> FactionDefinition {
+        FactionDefinition {
+            id: "villagers".to_string(),
+            display_name: "Villagers".to_string(),
+            description: String::new(),
+            starting_standing,
+        }
+    }
+
+    #[test]
+    fn test_reputation_levels() {
+        assert_eq!(reputation_level(0), 0);
+        assert_eq!(reputation_level(2_999), 0);
+        assert_eq!(reputation_level(3_000), 1);
+        assert_eq!(reputation_level(-1), -1);
+        assert_eq!(reputation_level(-6_000), -2);
+        assert_eq!(reputation_level(REPUTATION_MIN), -3);
+        assert_eq!(reputation_level(REPUTATION_MAX), 4);
+
+        assert_eq!(level_name(1), "Friendly");
+        assert_eq!(level_name(-3), "Hated");
+        assert_eq!(level_bounds(0), (0, Some(3_000)));
+        assert_eq!(level_bounds(4), (REPUTATION_MAX, None));
+    }
+
+    #[test]
+    fn test_adjust_clamps_and_uses_starting_standing() {
+        let villagers = faction(500);
+        let mut reputation = PlayerReputation::new();
+        assert_eq!(reputation.standing(&villagers), 500);
+
+        assert_eq!(reputation.adjust(&villagers, 2_500), (500, 3_000));
+        assert_eq!(reputation.level(&villagers), 1);
+
+        reputation.adjust(&villagers, -100_000);
+        assert_eq!(reputation.standing(&villagers), REPUTATION_MIN);
+    }
+
+    #[test]
+    fn test_price_modifiers() {
+        assert_eq!(purchase_price_modifier(0), 1.0);
+        assert!(purchase_price_modifier(4) < purchase_price_modifier(1));
+        assert!(sale_price_modifier(4) > 1.0);
+        assert!(sale_price_modifier(-1) < 1.0);
+    }
+}
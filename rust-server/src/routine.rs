@@ -0,0 +1,163 @@
+//! NPC patrol routes and daily schedules
+//!
+//! Spawns in chunk and interior JSON can give an NPC a looping `patrol` or an
+//! hourly `schedule` of waypoints; when a spawn has both, the schedule wins.
+
+use serde::{Deserialize, Serialize};
+
+use crate::behavior::hour_of_day;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Shortest stop at a waypoint, so NPCs don't re-plan every tick while standing still
+pub const MIN_WAYPOINT_PAUSE_MS: u64 = 1000;
+/// How long an NPC stops to face a player who talked to it
+pub const TALK_PAUSE_MS: u64 = 8000;
+
+// ============================================================================
+// Data
+// ============================================================================
+
+/// A stop on a route, local to the chunk or interior like the spawn itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Waypoint {
+    pub x: i32,
+    pub y: i32,
+    /// How long to stay once there
+    #[serde(default)]
+    pub wait_ms: u64,
+}
+
+impl Waypoint {
+    /// Shift from local to world coordinates
+    pub fn translate(&mut self, dx: i32, dy: i32) {
+        self.x += dx;
+        self.y += dy;
+    }
+}
+
+/// Waypoints followed from `hour` until the next entry starts; a single
+/// waypoint means "stand here"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleEntry {
+    /// Hour of the in-game day (0-23) this entry starts at
+    pub hour: u32,
+    pub waypoints: Vec<Waypoint>,
+}
+
+// ============================================================================
+// Routine
+// ============================================================================
+
+/// Where an NPC with a patrol or schedule should be heading
+#[derive(Debug, Clone)]
+pub struct Routine {
+    /// Sorted by starting hour
+    entries: Vec<ScheduleEntry>,
+    /// Entry the NPC is currently following
+    entry: usize,
+    /// Waypoint within that entry it is heading to
+    waypoint: usize,
+}
+
+impl Routine {
+    /// Build a routine from a spawn's patrol and schedule, or None if it has neither
+    pub fn new(patrol: &[Waypoint], schedule: &[ScheduleEntry]) -> Option<Self> {
+        let mut entries: Vec<ScheduleEntry> = schedule.iter()
+            .filter(|entry| !entry.waypoints.is_empty())
+            .cloned()
+            .collect();
+        if entries.is_empty() && !patrol.is_empty() {
+            entries.push(ScheduleEntry { hour: 0, waypoints: patrol.to_vec() });
+        }
+        if entries.is_empty() {
+            return None;
+        }
+        entries.sort_by_key(|entry| entry.hour);
+        Some(Self { entries, entry: 0, waypoint: 0 })
+    }
+
+    /// Index of the entry in effect: the latest one started today, or
+    /// yesterday's last entry before the first one starts
+    fn active_entry(&self, current_time: u64) -> usize {
+        let hour = hour_of_day(current_time);
+        self.entries.iter()
+            .rposition(|entry| entry.hour <= hour)
+            .unwrap_or(self.entries.len() - 1)
+    }
+
+    /// Waypoint to head for, restarting from the first one when the schedule moves on
+    pub fn current_waypoint(&mut self, current_time: u64) -> Waypoint {
+        let active = self.active_entry(current_time);
+        if active != self.entry {
+            self.entry = active;
+            self.waypoint = 0;
+        }
+        let waypoints = &self.entries[self.entry].waypoints;
+        waypoints[self.waypoint % waypoints.len()]
+    }
+
+    /// Move on to the next waypoint, looping back to the first
+    pub fn advance(&mut self) {
+        let len = self.entries[self.entry].waypoints.len();
+        self.waypoint = (self.waypoint + 1) % len;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::behavior::DAY_LENGTH_MS;
+
+    fn at_hour(hour: u64) -> u64 {
+        hour * DAY_LENGTH_MS / 24
+    }
+
+    fn point(x: i32, y: i32) -> Waypoint {
+        Waypoint { x, y, wait_ms: 0 }
+    }
+
+    #[test]
+    fn test_patrol_loops_through_waypoints() {
+        assert!(Routine::new(&[], &[]).is_none());
+
+        let mut routine = Routine::new(&[point(1, 1), point(5, 1), point(5, 5)], &[]).unwrap();
+        let mut visited = Vec::new();
+        for _ in 0..4 {
+            visited.push(routine.current_waypoint(at_hour(3)));
+            routine.advance();
+        }
+        assert_eq!(visited, vec![point(1, 1), point(5, 1), point(5, 5), point(1, 1)]);
+    }
+
+    #[test]
+    fn test_schedule_follows_the_clock() {
+        let stall = ScheduleEntry { hour: 8, waypoints: vec![point(10, 10)] };
+        let home = ScheduleEntry { hour: 20, waypoints: vec![point(2, 2)] };
+        // Entries are sorted and the schedule overrides the patrol
+        let mut routine = Routine::new(&[point(50, 50)], &[home, stall]).unwrap();
+
+        assert_eq!(routine.current_waypoint(at_hour(9)), point(10, 10));
+        assert_eq!(routine.current_waypoint(at_hour(21)), point(2, 2));
+        // Before the first entry of the day, the previous evening's entry still holds
+        assert_eq!(routine.current_waypoint(at_hour(3)), point(2, 2));
+        assert_eq!(routine.current_waypoint(DAY_LENGTH_MS + at_hour(8)), point(10, 10));
+    }
+
+    #[test]
+    fn test_new_schedule_entry_restarts_its_route() {
+        let rounds = ScheduleEntry { hour: 6, waypoints: vec![point(1, 0), point(2, 0), point(3, 0)] };
+        let bed = ScheduleEntry { hour: 22, waypoints: vec![point(0, 0)] };
+        let mut routine = Routine::new(&[], &[rounds, bed]).unwrap();
+
+        routine.current_waypoint(at_hour(7));
+        routine.advance();
+        assert_eq!(routine.current_waypoint(at_hour(7)), point(2, 0));
+        assert_eq!(routine.current_waypoint(at_hour(23)), point(0, 0));
+        routine.advance();
+        assert_eq!(routine.current_waypoint(DAY_LENGTH_MS + at_hour(6)), point(1, 0));
+    }
+}
@@ -0,0 +1,488 @@
+//! Offline drop-rate and XP-rate simulation
+//!
+//! `isometric-server simulate` runs kills against the real monster and loot
+//! data with the game's own combat and loot code, for tuning drops and XP.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::data::ItemRegistry;
+use crate::entity::{calculate_exp_reward, generate_loot_from_prototype, EntityPrototype, EntityRegistry};
+use crate::game::ATTACK_COOLDOWN_MS;
+use crate::item::GOLD_ITEM_ID;
+use crate::loot_table::LootTableRegistry;
+use crate::skills::{calculate_hit, calculate_max_hit, roll_damage, COMBAT_XP_PER_DAMAGE};
+
+const MS_PER_HOUR: f64 = 3_600_000.0;
+
+pub const USAGE: &str = "\
+Usage: isometric-server simulate [options]
+
+Options:
+  --level N          player combat level (default 1)
+  --monster-level N  monster level (default: the player level)
+  --kills N          kills simulated per monster (default 1000)
+  --gear a,b,...     equipped item IDs
+  --monster ID       only simulate this monster (repeatable)
+  --downtime-ms N    time between kills spent walking and looting (default 0)
+  --csv              print CSV instead of a table
+  --drops            with --csv, print drop distributions instead of the summary
+  --data DIR         data directory (default \"data\")";
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationConfig {
+    pub player_level: i32,
+    pub monster_level: i32,
+    pub kills: u32,
+    pub gear: Vec<String>,
+    /// Empty means every hostile monster
+    pub monsters: Vec<String>,
+    pub downtime_ms: u64,
+    pub csv: bool,
+    pub drops: bool,
+    pub data_dir: PathBuf,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            player_level: 1,
+            monster_level: 1,
+            kills: 1000,
+            gear: Vec::new(),
+            monsters: Vec::new(),
+            downtime_ms: 0,
+            csv: false,
+            drops: false,
+            data_dir: PathBuf::from("data"),
+        }
+    }
+}
+
+impl SimulationConfig {
+    /// Parse the arguments following `simulate`
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut config = Self::default();
+        let mut monster_level = None;
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--csv" => config.csv = true,
+                "--drops" => config.drops = true,
+                flag => {
+                    let value = args.next().ok_or_else(|| format!("Missing value for {}", flag))?;
+                    match flag {
+                        "--level" => config.player_level = parse_number(flag, value)?,
+                        "--monster-level" => monster_level = Some(parse_number(flag, value)?),
+                        "--kills" => config.kills = parse_number(flag, value)?,
+                        "--gear" => config.gear.extend(
+                            value.split(',').map(str::trim).filter(|id| !id.is_empty()).map(String::from),
+                        ),
+                        "--monster" => config.monsters.push(value.clone()),
+                        "--downtime-ms" => config.downtime_ms = parse_number(flag, value)?,
+                        "--data" => config.data_dir = PathBuf::from(value),
+                        _ => return Err(format!("Unknown option: {}", flag)),
+                    }
+                }
+            }
+        }
+
+        if config.player_level < 1 || config.kills == 0 {
+            return Err("--level and --kills must be at least 1".to_string());
+        }
+        config.monster_level = monster_level.unwrap_or(config.player_level).max(1);
+        Ok(config)
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("Invalid value for {}: {}", flag, value))
+}
+
+/// Equipment bonuses summed over the simulated gear
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Gear {
+    pub attack_bonus: i32,
+    pub strength_bonus: i32,
+    pub defence_bonus: i32,
+}
+
+impl Gear {
+    pub fn from_items(item_registry: &ItemRegistry, item_ids: &[String]) -> Result<Self, String> {
+        let mut gear = Self::default();
+        let mut slots = Vec::new();
+        for item_id in item_ids {
+            let equipment = item_registry
+                .get(item_id)
+                .ok_or_else(|| format!("Unknown item: {}", item_id))?
+                .equipment
+                .as_ref()
+                .ok_or_else(|| format!("Item is not equipment: {}", item_id))?;
+            if slots.contains(&equipment.slot_type) {
+                return Err(format!("More than one item for the {:?} slot: {}", equipment.slot_type, item_id));
+            }
+            slots.push(equipment.slot_type);
+            gear.attack_bonus += equipment.attack_bonus;
+            gear.strength_bonus += equipment.strength_bonus;
+            gear.defence_bonus += equipment.defence_bonus;
+        }
+        Ok(gear)
+    }
+}
+
+// ============================================================================
+// Simulation
+// ============================================================================
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DropStats {
+    /// Kills that dropped the item at least once
+    pub kills_with_drop: u32,
+    pub total_quantity: i64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MonsterReport {
+    pub prototype_id: String,
+    pub level: i32,
+    pub max_hp: i32,
+    pub kills: u32,
+    pub swings: u64,
+    pub hits: u64,
+    pub damage_taken: i64,
+    pub exp: i64,
+    pub gold: i64,
+    /// Item ID -> stats, gold excluded
+    pub drops: BTreeMap<String, DropStats>,
+}
+
+impl MonsterReport {
+    pub fn hit_rate(&self) -> f64 {
+        self.hits as f64 / self.swings.max(1) as f64
+    }
+
+    pub fn kill_ms(&self) -> f64 {
+        (self.swings * ATTACK_COOLDOWN_MS) as f64 / self.kills as f64
+    }
+
+    pub fn per_kill(&self, total: i64) -> f64 {
+        total as f64 / self.kills as f64
+    }
+
+    pub fn kills_per_hour(&self, downtime_ms: u64) -> f64 {
+        MS_PER_HOUR / (self.kill_ms() + downtime_ms as f64).max(1.0)
+    }
+}
+
+/// Simulate `config.kills` fights against one monster, always its normal variant.
+/// The player swings every attack cooldown from the first tick of the fight.
+pub fn simulate_monster(
+    prototype: &EntityPrototype,
+    loot_tables: &LootTableRegistry,
+    config: &SimulationConfig,
+    gear: &Gear,
+) -> MonsterReport {
+    let mut report = MonsterReport {
+        prototype_id: prototype.id.clone(),
+        level: config.monster_level,
+        max_hp: prototype.stats.max_hp,
+        kills: config.kills,
+        ..Default::default()
+    };
+    let max_hit = calculate_max_hit(config.player_level, gear.strength_bonus);
+    // Player kill XP is awarded as if the kill's EXP reward were damage dealt
+    let exp_per_kill = (calculate_exp_reward(prototype, config.monster_level) as f64 * COMBAT_XP_PER_DAMAGE) as i64;
+
+    for kill in 0..config.kills {
+        let mut hp = prototype.stats.max_hp;
+        let mut swings = 0u64;
+        while hp > 0 {
+            swings += 1;
+            if calculate_hit(config.player_level, gear.attack_bonus, config.monster_level, 0) {
+                report.hits += 1;
+                hp -= roll_damage(max_hit);
+            }
+        }
+        report.swings += swings;
+
+        // The monster swings back on its own cooldown for as long as the fight lasts
+        let monster_attacks = swings * ATTACK_COOLDOWN_MS / prototype.stats.attack_cooldown_ms.max(1);
+        for _ in 0..monster_attacks {
+            if calculate_hit(config.monster_level, 0, config.player_level, gear.defence_bonus) {
+                report.damage_taken += roll_damage(prototype.stats.damage) as i64;
+            }
+        }
+
+        report.exp += exp_per_kill;
+
+        let loot = generate_loot_from_prototype(
+            prototype, loot_tables, (0.0, 0.0), "simulator", kill as u64, config.monster_level, None,
+        );
+        let mut dropped = Vec::new();
+        for item in loot {
+            if item.item_id == GOLD_ITEM_ID {
+                report.gold += item.quantity as i64;
+                continue;
+            }
+            let stats = report.drops.entry(item.item_id.clone()).or_default();
+            stats.total_quantity += item.quantity as i64;
+            if !dropped.contains(&item.item_id) {
+                stats.kills_with_drop += 1;
+                dropped.push(item.item_id);
+            }
+        }
+    }
+
+    report
+}
+
+/// Hostile, non-pet monsters in ID order, or the ones named in the config
+fn select_monsters<'a>(
+    entity_registry: &'a EntityRegistry,
+    config: &SimulationConfig,
+) -> Result<Vec<&'a EntityPrototype>, String> {
+    if !config.monsters.is_empty() {
+        return config
+            .monsters
+            .iter()
+            .map(|id| entity_registry.get(id).ok_or_else(|| format!("Unknown monster: {}", id)))
+            .collect();
+    }
+    let mut monsters: Vec<&EntityPrototype> = entity_registry
+        .all()
+        .filter(|prototype| prototype.behaviors.hostile && !prototype.is_pet() && !prototype.is_npc())
+        .collect();
+    monsters.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(monsters)
+}
+
+// ============================================================================
+// Output
+// ============================================================================
+
+pub fn format_table(reports: &[MonsterReport], config: &SimulationConfig) -> String {
+    let mut out = format!(
+        "Player level {}, monster level {}, {} kills per monster, {}ms downtime\n\n",
+        config.player_level, config.monster_level, config.kills, config.downtime_ms
+    );
+    out.push_str(&format!(
+        "{:<20} {:>6} {:>6} {:>7} {:>8} {:>9} {:>9} {:>10} {:>9} {:>10}\n",
+        "monster", "hp", "hit%", "kill s", "dmg/kill", "xp/kill", "xp/hour", "gold/kill", "gold/hr", "kills/hr"
+    ));
+    for report in reports {
+        let kills_per_hour = report.kills_per_hour(config.downtime_ms);
+        out.push_str(&format!(
+            "{:<20} {:>6} {:>6.1} {:>7.1} {:>8.1} {:>9.0} {:>9.0} {:>10.1} {:>9.0} {:>10.0}\n",
+            report.prototype_id,
+            report.max_hp,
+            report.hit_rate() * 100.0,
+            report.kill_ms() / 1000.0,
+            report.per_kill(report.damage_taken),
+            report.per_kill(report.exp),
+            report.per_kill(report.exp) * kills_per_hour,
+            report.per_kill(report.gold),
+            report.per_kill(report.gold) * kills_per_hour,
+            kills_per_hour,
+        ));
+    }
+
+    out.push_str(&format!("\n{:<20} {:<24} {:>9} {:>9} {:>9}\n", "monster", "item", "drop %", "avg qty", "per hour"));
+    for report in reports {
+        let kills_per_hour = report.kills_per_hour(config.downtime_ms);
+        for (item_id, stats) in &report.drops {
+            out.push_str(&format!(
+                "{:<20} {:<24} {:>9.3} {:>9.2} {:>9.1}\n",
+                report.prototype_id,
+                item_id,
+                stats.kills_with_drop as f64 / report.kills as f64 * 100.0,
+                stats.total_quantity as f64 / stats.kills_with_drop.max(1) as f64,
+                report.per_kill(stats.total_quantity) * kills_per_hour,
+            ));
+        }
+    }
+    out
+}
+
+pub fn format_csv(reports: &[MonsterReport], config: &SimulationConfig) -> String {
+    let mut out = String::new();
+    if config.drops {
+        out.push_str("monster,item,drop_rate,avg_quantity,per_hour\n");
+        for report in reports {
+            let kills_per_hour = report.kills_per_hour(config.downtime_ms);
+            for (item_id, stats) in &report.drops {
+                out.push_str(&format!(
+                    "{},{},{:.5},{:.3},{:.2}\n",
+                    report.prototype_id,
+                    item_id,
+                    stats.kills_with_drop as f64 / report.kills as f64,
+                    stats.total_quantity as f64 / stats.kills_with_drop.max(1) as f64,
+                    report.per_kill(stats.total_quantity) * kills_per_hour,
+                ));
+            }
+        }
+        return out;
+    }
+
+    out.push_str("monster,level,max_hp,kills,hit_rate,kill_seconds,damage_taken_per_kill,xp_per_kill,xp_per_hour,gold_per_kill,gold_per_hour\n");
+    for report in reports {
+        let kills_per_hour = report.kills_per_hour(config.downtime_ms);
+        out.push_str(&format!(
+            "{},{},{},{},{:.4},{:.3},{:.2},{:.1},{:.0},{:.2},{:.0}\n",
+            report.prototype_id,
+            report.level,
+            report.max_hp,
+            report.kills,
+            report.hit_rate(),
+            report.kill_ms() / 1000.0,
+            report.per_kill(report.damage_taken),
+            report.per_kill(report.exp),
+            report.per_kill(report.exp) * kills_per_hour,
+            report.per_kill(report.gold),
+            report.per_kill(report.gold) * kills_per_hour,
+        ));
+    }
+    out
+}
+
+/// Entry point for `isometric-server simulate`
+pub fn run(args: &[String]) -> Result<(), String> {
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+    let config = SimulationConfig::parse(args)?;
+
+    let mut entity_registry = EntityRegistry::new();
+    entity_registry.load_from_directory(&config.data_dir)?;
+    let mut item_registry = ItemRegistry::new();
+    item_registry.load_from_directory(&config.data_dir)?;
+    let mut loot_tables = LootTableRegistry::new();
+    loot_tables.load_from_directory(&config.data_dir)?;
+
+    let gear = Gear::from_items(&item_registry, &config.gear)?;
+    let reports: Vec<MonsterReport> = select_monsters(&entity_registry, &config)?
+        .into_iter()
+        .map(|prototype| simulate_monster(prototype, &loot_tables, &config, &gear))
+        .collect();
+
+    if config.csv {
+        print!("{}", format_csv(&reports, &config));
+    } else {
+        print!("{}", format_table(&reports, &config));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    fn setup_data() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("entities/monsters")).unwrap();
+        fs::create_dir_all(dir.path().join("items")).unwrap();
+        fs::write(dir.path().join("entities/monsters/test.toml"), r#"
+            [dummy]
+            display_name = "Dummy"
+            sprite = "dummy"
+
+            [dummy.stats]
+            max_hp = 10
+            damage = 2
+            attack_cooldown_ms = 1400
+
+            [dummy.rewards]
+            exp_base = 5
+            gold_min = 1
+            gold_max = 3
+
+            [[dummy.loot]]
+            item_id = "bone"
+            drop_chance = 1.0
+            quantity_min = 2
+            quantity_max = 2
+
+            [dummy.behaviors]
+            hostile = true
+        "#).unwrap();
+        fs::write(dir.path().join("items/gear.toml"), r#"
+            [sword]
+            display_name = "Sword"
+            category = "equipment"
+            [sword.equipment]
+            slot_type = "weapon"
+            attack_bonus = 10
+            strength_bonus = 20
+
+            [dagger]
+            display_name = "Dagger"
+            category = "equipment"
+            [dagger.equipment]
+            slot_type = "weapon"
+            attack_bonus = 5
+
+            [bone]
+            display_name = "Bone"
+        "#).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let config = SimulationConfig::parse(&args("--level 30 --gear sword,shield --monster pig --csv")).unwrap();
+        assert_eq!(config.player_level, 30);
+        assert_eq!(config.monster_level, 30);
+        assert_eq!(config.gear, vec!["sword", "shield"]);
+        assert_eq!(config.monsters, vec!["pig"]);
+        assert!(config.csv);
+
+        assert_eq!(SimulationConfig::parse(&[]).unwrap(), SimulationConfig::default());
+        assert!(SimulationConfig::parse(&args("--kills")).is_err());
+        assert!(SimulationConfig::parse(&args("--kills lots")).is_err());
+        assert!(SimulationConfig::parse(&args("--speed 2")).is_err());
+    }
+
+    #[test]
+    fn test_gear_sums_bonuses_and_rejects_bad_items() {
+        let dir = setup_data();
+        let mut items = ItemRegistry::new();
+        items.load_from_directory(dir.path()).unwrap();
+
+        let gear = Gear::from_items(&items, &["sword".to_string()]).unwrap();
+        assert_eq!(gear, Gear { attack_bonus: 10, strength_bonus: 20, defence_bonus: 0 });
+        assert!(Gear::from_items(&items, &["sword".to_string(), "dagger".to_string()]).is_err());
+        assert!(Gear::from_items(&items, &["bone".to_string()]).is_err());
+        assert!(Gear::from_items(&items, &["missing".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_simulated_kills_report_rewards() {
+        let dir = setup_data();
+        let mut entities = EntityRegistry::new();
+        entities.load_from_directory(dir.path()).unwrap();
+        let config = SimulationConfig { player_level: 10, monster_level: 2, kills: 200, ..Default::default() };
+        let report = simulate_monster(entities.get("dummy").unwrap(), &LootTableRegistry::new(), &config, &Gear::default());
+
+        // 5 EXP x level 2, awarded as combat XP per "damage"
+        assert_eq!(report.per_kill(report.exp), 40.0);
+        // 1-3 gold x level 2
+        assert!((2.0..=6.0).contains(&report.per_kill(report.gold)));
+        assert_eq!(report.drops["bone"], DropStats { kills_with_drop: 200, total_quantity: 400 });
+        assert!(report.swings >= report.hits && report.hits > 0);
+        assert_eq!(report.kill_ms(), (report.swings * ATTACK_COOLDOWN_MS) as f64 / 200.0);
+
+        let csv = format_csv(&[report], &config);
+        assert!(csv.lines().nth(1).unwrap().starts_with("dummy,2,10,200,"));
+    }
+}
@@ -0,0 +1,261 @@
+//! Monster spawn regions
+//!
+//! Chunk JSON can list `spawnRegions` that fill up to `maxPopulation` at
+//! startup and replace dead monsters every `respawnMs` while below the cap.
+
+use rand::Rng;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Random tiles tried per spawn before giving up until the next cadence
+const MAX_TILE_ATTEMPTS: usize = 30;
+
+fn default_weight() -> u32 { 1 }
+fn default_respawn_ms() -> u64 { 30000 }
+fn default_level() -> i32 { 1 }
+
+// ============================================================================
+// Region Definition
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct RegionPoint {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeightedEntity {
+    pub entity_id: String,
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+}
+
+/// A rectangle or polygon of chunk-local tiles spawning weighted entities
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpawnRegion {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub x: i32,
+    #[serde(default)]
+    pub y: i32,
+    #[serde(default)]
+    pub width: i32,
+    #[serde(default)]
+    pub height: i32,
+    /// Tile corners of a polygon, used instead of the rectangle when it has at
+    /// least three
+    #[serde(default)]
+    pub polygon: Vec<RegionPoint>,
+    pub entities: Vec<WeightedEntity>,
+    pub max_population: usize,
+    #[serde(default = "default_respawn_ms")]
+    pub respawn_ms: u64,
+    #[serde(default = "default_level")]
+    pub min_level: i32,
+    #[serde(default = "default_level")]
+    pub max_level: i32,
+}
+
+impl SpawnRegion {
+    fn is_polygon(&self) -> bool {
+        self.polygon.len() >= 3
+    }
+
+    /// Shift from chunk-local to world coordinates
+    pub fn translate(&mut self, dx: i32, dy: i32) {
+        self.x += dx;
+        self.y += dy;
+        for point in &mut self.polygon {
+            point.x += dx;
+            point.y += dy;
+        }
+    }
+
+    /// Whether a tile lies inside the region (polygons test the tile's center)
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        if !self.is_polygon() {
+            return x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height;
+        }
+        // Even-odd ray cast from the tile center
+        let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+        let mut inside = false;
+        let mut j = self.polygon.len() - 1;
+        for (i, a) in self.polygon.iter().enumerate() {
+            let b = self.polygon[j];
+            let (ax, ay, bx, by) = (a.x as f32, a.y as f32, b.x as f32, b.y as f32);
+            if (ay > py) != (by > py) && px < (bx - ax) * (py - ay) / (by - ay) + ax {
+                inside = !inside;
+            }
+            j = i;
+        }
+        inside
+    }
+
+    /// Smallest rectangle holding the region, as (min_x, min_y, max_x, max_y) exclusive of the max
+    fn bounds(&self) -> (i32, i32, i32, i32) {
+        if !self.is_polygon() {
+            return (self.x, self.y, self.x + self.width, self.y + self.height);
+        }
+        let min_x = self.polygon.iter().map(|p| p.x).min().unwrap_or(0);
+        let min_y = self.polygon.iter().map(|p| p.y).min().unwrap_or(0);
+        let max_x = self.polygon.iter().map(|p| p.x).max().unwrap_or(0);
+        let max_y = self.polygon.iter().map(|p| p.y).max().unwrap_or(0);
+        (min_x, min_y, max_x, max_y)
+    }
+
+    /// Pick a prototype by weight
+    pub fn pick_entity(&self, rng: &mut impl Rng) -> Option<&str> {
+        let total: u32 = self.entities.iter().map(|e| e.weight).sum();
+        if total == 0 {
+            return None;
+        }
+        let mut roll = rng.gen_range(0..total);
+        for entity in &self.entities {
+            if roll < entity.weight {
+                return Some(&entity.entity_id);
+            }
+            roll -= entity.weight;
+        }
+        None
+    }
+
+    pub fn roll_level(&self, rng: &mut impl Rng) -> i32 {
+        rng.gen_range(self.min_level..=self.max_level.max(self.min_level))
+    }
+
+    /// A random tile inside the region that `is_free` accepts, if one turns up
+    pub fn random_tile(&self, rng: &mut impl Rng, is_free: impl Fn(i32, i32) -> bool) -> Option<(i32, i32)> {
+        let (min_x, min_y, max_x, max_y) = self.bounds();
+        if max_x <= min_x || max_y <= min_y {
+            return None;
+        }
+        (0..MAX_TILE_ATTEMPTS)
+            .map(|_| (rng.gen_range(min_x..max_x), rng.gen_range(min_y..max_y)))
+            .find(|&(x, y)| self.contains(x, y) && is_free(x, y))
+    }
+}
+
+// ============================================================================
+// Spawn Cadence
+// ============================================================================
+
+/// Tracks when each region may next spawn a monster
+#[derive(Debug, Default)]
+pub struct SpawnRegionManager {
+    regions: Vec<SpawnRegion>,
+    /// Region ID -> earliest time of its next spawn, set while it is below its cap
+    next_spawn_at: HashMap<String, u64>,
+    /// Whether the regions have had their initial fill
+    filled: bool,
+}
+
+impl SpawnRegionManager {
+    pub fn new(regions: Vec<SpawnRegion>) -> Self {
+        Self { regions, next_spawn_at: HashMap::new(), filled: false }
+    }
+
+    pub fn regions(&self) -> &[SpawnRegion] {
+        &self.regions
+    }
+
+    /// How many monsters each region should spawn now, given its living population.
+    /// The first call fills every region; after that each one tops up a monster per `respawn_ms`.
+    pub fn due_spawns(&mut self, populations: &HashMap<String, usize>, now: u64) -> Vec<(SpawnRegion, usize)> {
+        let initial_fill = !self.filled;
+        self.filled = true;
+
+        let mut due = Vec::new();
+        for region in &self.regions {
+            let population = populations.get(&region.id).copied().unwrap_or(0);
+            let missing = region.max_population.saturating_sub(population);
+            if missing == 0 {
+                self.next_spawn_at.remove(&region.id);
+                continue;
+            }
+            if initial_fill {
+                due.push((region.clone(), missing));
+                continue;
+            }
+            let next = *self.next_spawn_at.entry(region.id.clone()).or_insert(now + region.respawn_ms);
+            if now >= next {
+                self.next_spawn_at.insert(region.id.clone(), now + region.respawn_ms);
+                due.push((region.clone(), 1));
+            }
+        }
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn region(json: &str) -> SpawnRegion {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_rectangles_and_polygons_contain_tiles() {
+        let mut rect = region(r#"{ "id": "r", "x": 2, "y": 3, "width": 4, "height": 2,
+            "entities": [{ "entityId": "pig" }], "maxPopulation": 3 }"#);
+        assert!(rect.contains(2, 3) && rect.contains(5, 4));
+        assert!(!rect.contains(6, 4) && !rect.contains(2, 5));
+        rect.translate(32, 0);
+        assert!(rect.contains(34, 3));
+
+        // Right triangle with corners (0,0), (10,0), (0,10)
+        let triangle = region(r#"{ "id": "t", "polygon": [{ "x": 0, "y": 0 }, { "x": 10, "y": 0 }, { "x": 0, "y": 10 }],
+            "entities": [{ "entityId": "pig" }], "maxPopulation": 3 }"#);
+        assert!(triangle.contains(1, 1) && triangle.contains(0, 8));
+        assert!(!triangle.contains(8, 8) && !triangle.contains(-1, 2));
+    }
+
+    #[test]
+    fn test_random_picks_respect_weights_levels_and_free_tiles() {
+        let meadow = region(r#"{ "id": "m", "x": 0, "y": 0, "width": 10, "height": 10,
+            "entities": [{ "entityId": "pig", "weight": 3 }, { "entityId": "boar", "weight": 0 }],
+            "maxPopulation": 5, "minLevel": 2, "maxLevel": 4 }"#);
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..50 {
+            assert_eq!(meadow.pick_entity(&mut rng), Some("pig"));
+            assert!((2..=4).contains(&meadow.roll_level(&mut rng)));
+            let (x, y) = meadow.random_tile(&mut rng, |x, _| x >= 5).unwrap();
+            assert!(meadow.contains(x, y) && x >= 5);
+        }
+        assert_eq!(meadow.random_tile(&mut rng, |_, _| false), None);
+    }
+
+    #[test]
+    fn test_regions_fill_then_top_up_on_cadence() {
+        let meadow = region(r#"{ "id": "m", "x": 0, "y": 0, "width": 10, "height": 10,
+            "entities": [{ "entityId": "pig" }], "maxPopulation": 4, "respawnMs": 1000 }"#);
+        let mut manager = SpawnRegionManager::new(vec![meadow]);
+        let mut populations = HashMap::new();
+
+        let due = manager.due_spawns(&populations, 0);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].1, 4);
+
+        populations.insert("m".to_string(), 4);
+        assert!(manager.due_spawns(&populations, 100).is_empty());
+
+        // Two die: one replacement per respawn interval
+        populations.insert("m".to_string(), 2);
+        assert!(manager.due_spawns(&populations, 500).is_empty());
+        assert!(manager.due_spawns(&populations, 1400).is_empty());
+        assert_eq!(manager.due_spawns(&populations, 1500)[0].1, 1);
+        populations.insert("m".to_string(), 3);
+        assert!(manager.due_spawns(&populations, 2000).is_empty());
+        assert_eq!(manager.due_spawns(&populations, 2500)[0].1, 1);
+    }
+}
@@ -0,0 +1,263 @@
+//! Status effects applied to players by monster attacks
+//!
+//! Monster prototypes list `on_hit_effects`; each successful hit rolls every
+//! entry's `chance`, reduced by the target's equipment resistance to that
+//! effect. Poison, stun and slow refresh on reapplication, while bleed stacks.
+//! Cure items (`use_effect.type = "cure"`) remove effects early.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Most bleed stacks a player can carry at once
+pub const BLEED_MAX_STACKS: u32 = 5;
+
+// ============================================================================
+// Effect Definitions
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusKind {
+    /// Damage over time
+    Poison,
+    /// Can't move or attack
+    Stun,
+    /// Longer delay between tile moves
+    Slow,
+    /// Damage over time that stacks with every hit
+    Bleed,
+}
+
+impl StatusKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatusKind::Poison => "poison",
+            StatusKind::Stun => "stun",
+            StatusKind::Slow => "slow",
+            StatusKind::Bleed => "bleed",
+        }
+    }
+}
+
+/// An effect a monster's hit can apply (`[[monster.on_hit_effects]]`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct OnHitEffect {
+    pub effect: StatusKind,
+    /// Chance per successful hit (0.0 - 1.0)
+    #[serde(default = "default_chance")]
+    pub chance: f32,
+    pub duration_ms: u64,
+    /// Damage per interval (poison, and per stack for bleed)
+    #[serde(default)]
+    pub damage: i32,
+    #[serde(default = "default_interval_ms")]
+    pub interval_ms: u64,
+    /// Extra move delay in percent (slow)
+    #[serde(default = "default_slow_percent")]
+    pub slow_percent: u32,
+}
+
+fn default_chance() -> f32 { 1.0 }
+fn default_interval_ms() -> u64 { 1000 }
+fn default_slow_percent() -> u32 { 50 }
+
+/// Roll whether an effect lands, with resistance (percent) scaling the chance down
+pub fn roll_effect(chance: f32, resistance: i32) -> bool {
+    let resisted = resistance.clamp(0, 100) as f32 / 100.0;
+    let chance = chance.clamp(0.0, 1.0) * (1.0 - resisted);
+    chance > 0.0 && rand::thread_rng().gen_bool(chance as f64)
+}
+
+// ============================================================================
+// Active Effects
+// ============================================================================
+
+#[derive(Debug, Clone)]
+pub struct ActiveEffect {
+    pub kind: StatusKind,
+    /// NPC that applied the effect (credited for damage over time)
+    pub source_id: String,
+    pub expires_at: u64,
+    pub damage: i32,
+    pub interval_ms: u64,
+    pub next_tick_at: u64,
+    pub stacks: u32,
+    pub slow_percent: u32,
+}
+
+/// Damage over time dealt by one `StatusEffects::tick`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EffectDamage {
+    pub kind: StatusKind,
+    pub source_id: String,
+    pub damage: i32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StatusEffects {
+    effects: Vec<ActiveEffect>,
+}
+
+impl StatusEffects {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ActiveEffect> {
+        self.effects.iter()
+    }
+
+    pub fn has(&self, kind: StatusKind, current_time: u64) -> bool {
+        self.effects.iter().any(|e| e.kind == kind && current_time < e.expires_at)
+    }
+
+    pub fn is_stunned(&self, current_time: u64) -> bool {
+        self.has(StatusKind::Stun, current_time)
+    }
+
+    /// Ticks between tile moves, stretched by the strongest active slow
+    pub fn move_cooldown_ticks(&self, base_ticks: u64, current_time: u64) -> u64 {
+        let slow = self.effects.iter()
+            .filter(|e| e.kind == StatusKind::Slow && current_time < e.expires_at)
+            .map(|e| e.slow_percent as u64)
+            .max()
+            .unwrap_or(0);
+        base_ticks * (100 + slow) / 100
+    }
+
+    /// Apply an effect from a hit. Bleed adds a stack, everything else refreshes.
+    pub fn apply(&mut self, effect: &OnHitEffect, source_id: &str, current_time: u64) {
+        let expires_at = current_time + effect.duration_ms;
+        if let Some(active) = self.effects.iter_mut().find(|e| e.kind == effect.effect) {
+            if effect.effect == StatusKind::Bleed {
+                active.stacks = (active.stacks + 1).min(BLEED_MAX_STACKS);
+            }
+            active.source_id = source_id.to_string();
+            active.expires_at = active.expires_at.max(expires_at);
+            active.damage = active.damage.max(effect.damage);
+            active.slow_percent = active.slow_percent.max(effect.slow_percent);
+            return;
+        }
+        self.effects.push(ActiveEffect {
+            kind: effect.effect,
+            source_id: source_id.to_string(),
+            expires_at,
+            damage: effect.damage,
+            interval_ms: effect.interval_ms.max(1),
+            next_tick_at: current_time + effect.interval_ms.max(1),
+            stacks: 1,
+            slow_percent: effect.slow_percent,
+        });
+    }
+
+    /// Deal due damage over time and drop expired effects.
+    /// Returns the damage dealt and whether any effect wore off.
+    pub fn tick(&mut self, current_time: u64) -> (Vec<EffectDamage>, bool) {
+        let mut damage = Vec::new();
+        for effect in self.effects.iter_mut() {
+            while effect.damage > 0 && effect.next_tick_at <= current_time.min(effect.expires_at) {
+                damage.push(EffectDamage {
+                    kind: effect.kind,
+                    source_id: effect.source_id.clone(),
+                    damage: effect.damage * effect.stacks as i32,
+                });
+                effect.next_tick_at += effect.interval_ms;
+            }
+        }
+        let before = self.effects.len();
+        self.effects.retain(|e| current_time < e.expires_at);
+        (damage, self.effects.len() != before)
+    }
+
+    /// Remove the given effects (all of them when `kinds` is empty).
+    /// Returns whether anything was removed.
+    pub fn cure(&mut self, kinds: &[StatusKind]) -> bool {
+        let before = self.effects.len();
+        self.effects.retain(|e| !kinds.is_empty() && !kinds.contains(&e.kind));
+        self.effects.len() != before
+    }
+
+    pub fn clear(&mut self) {
+        self.effects.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn on_hit(effect: StatusKind, duration_ms: u64, damage: i32) -> OnHitEffect {
+        OnHitEffect {
+            effect,
+            chance: 1.0,
+            duration_ms,
+            damage,
+            interval_ms: 1000,
+            slow_percent: 100,
+        }
+    }
+
+    #[test]
+    fn test_poison_ticks_until_it_expires() {
+        let mut effects = StatusEffects::new();
+        effects.apply(&on_hit(StatusKind::Poison, 3000, 2), "spider", 0);
+
+        let (damage, expired) = effects.tick(1500);
+        assert_eq!(damage, vec![EffectDamage { kind: StatusKind::Poison, source_id: "spider".to_string(), damage: 2 }]);
+        assert!(!expired);
+
+        // Ticks at 2000 and 3000, then wears off
+        let (damage, expired) = effects.tick(5000);
+        assert_eq!(damage.len(), 2);
+        assert!(expired);
+        assert!(effects.is_empty());
+    }
+
+    #[test]
+    fn test_bleed_stacks_and_poison_refreshes() {
+        let mut effects = StatusEffects::new();
+        for _ in 0..(BLEED_MAX_STACKS + 2) {
+            effects.apply(&on_hit(StatusKind::Bleed, 4000, 1), "wolf", 0);
+        }
+        effects.apply(&on_hit(StatusKind::Poison, 2000, 1), "spider", 0);
+        effects.apply(&on_hit(StatusKind::Poison, 2000, 1), "spider", 1000);
+
+        let bleed = effects.iter().find(|e| e.kind == StatusKind::Bleed).unwrap();
+        assert_eq!(bleed.stacks, BLEED_MAX_STACKS);
+        let poison = effects.iter().find(|e| e.kind == StatusKind::Poison).unwrap();
+        assert_eq!((poison.stacks, poison.expires_at), (1, 3000));
+
+        let (damage, _) = effects.tick(1000);
+        assert!(damage.contains(&EffectDamage { kind: StatusKind::Bleed, source_id: "wolf".to_string(), damage: 5 }));
+    }
+
+    #[test]
+    fn test_stun_slow_and_cure() {
+        let mut effects = StatusEffects::new();
+        effects.apply(&on_hit(StatusKind::Stun, 1000, 0), "golem", 0);
+        effects.apply(&on_hit(StatusKind::Slow, 5000, 0), "golem", 0);
+
+        assert!(effects.is_stunned(500));
+        assert!(!effects.is_stunned(1000));
+        assert_eq!(effects.move_cooldown_ticks(5, 500), 10);
+
+        assert!(effects.cure(&[StatusKind::Slow]));
+        assert_eq!(effects.move_cooldown_ticks(5, 500), 5);
+        assert!(effects.cure(&[]));
+        assert!(effects.is_empty());
+    }
+
+    #[test]
+    fn test_full_resistance_blocks_effects() {
+        assert!(!roll_effect(1.0, 100));
+        assert!(roll_effect(1.0, 0));
+    }
+}
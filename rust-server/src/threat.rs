@@ -0,0 +1,144 @@
+//! NPC threat tables
+//!
+//! Hostile NPCs chase whoever tops their threat table, switching only once
+//! someone passes the current target by `SWITCH_THRESHOLD`.
+
+use std::collections::HashMap;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Threat per point of damage dealt (misses still count as one point)
+pub const DAMAGE_THREAT: f32 = 1.0;
+/// Threat per HP healed, for every NPC already engaged with the healer
+pub const HEAL_THREAT: f32 = 0.5;
+/// Threat per second for standing next to an NPC, falling off to nothing past its aggro range
+pub const PROXIMITY_THREAT_PER_SEC: f32 = 1.0;
+/// How far another player has to pass the current target's threat to pull aggro
+pub const SWITCH_THRESHOLD: f32 = 1.1;
+
+// ============================================================================
+// Threat Table
+// ============================================================================
+
+#[derive(Debug, Clone, Default)]
+pub struct ThreatTable {
+    threat: HashMap<String, f32>,
+}
+
+impl ThreatTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.threat.is_empty()
+    }
+
+    pub fn contains(&self, player_id: &str) -> bool {
+        self.threat.contains_key(player_id)
+    }
+
+    pub fn get(&self, player_id: &str) -> f32 {
+        self.threat.get(player_id).copied().unwrap_or(0.0)
+    }
+
+    pub fn add(&mut self, player_id: &str, amount: f32) {
+        if amount <= 0.0 {
+            return;
+        }
+        *self.threat.entry(player_id.to_string()).or_insert(0.0) += amount;
+    }
+
+    /// Overwrite a player's threat (zero or less drops them from the table)
+    pub fn set(&mut self, player_id: &str, amount: f32) {
+        if amount <= 0.0 {
+            self.threat.remove(player_id);
+        } else {
+            self.threat.insert(player_id.to_string(), amount);
+        }
+    }
+
+    pub fn remove(&mut self, player_id: &str) {
+        self.threat.remove(player_id);
+    }
+
+    pub fn retain(&mut self, mut keep: impl FnMut(&str) -> bool) {
+        self.threat.retain(|id, _| keep(id));
+    }
+
+    /// Forget everyone, for when the NPC leashes back home or dies
+    pub fn clear(&mut self) {
+        self.threat.clear();
+    }
+
+    /// Entries by threat, highest first (ties broken by player ID)
+    pub fn sorted(&self) -> Vec<(String, f32)> {
+        let mut entries: Vec<(String, f32)> = self.threat.iter()
+            .map(|(id, threat)| (id.clone(), *threat))
+            .collect();
+        entries.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        entries
+    }
+
+    /// Who the NPC should be fighting. Sticks with `current` unless someone
+    /// else has passed its threat by `SWITCH_THRESHOLD`.
+    pub fn pick_target(&self, current: Option<&str>) -> Option<String> {
+        let (top_id, top_threat) = self.sorted().into_iter().next()?;
+        match current {
+            Some(current) if self.contains(current) && top_threat <= self.get(current) * SWITCH_THRESHOLD => {
+                Some(current.to_string())
+            }
+            _ => Some(top_id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highest_threat_is_picked_first() {
+        let mut table = ThreatTable::new();
+        assert_eq!(table.pick_target(None), None);
+
+        table.add("alice", 5.0);
+        table.add("bob", 8.0);
+        table.add("bob", 0.0);
+        assert_eq!(table.pick_target(None), Some("bob".to_string()));
+        assert_eq!(table.sorted()[1], ("alice".to_string(), 5.0));
+    }
+
+    #[test]
+    fn test_switching_needs_to_pass_the_threshold() {
+        let mut table = ThreatTable::new();
+        table.add("tank", 100.0);
+        table.add("mage", 105.0);
+        assert_eq!(table.pick_target(Some("tank")), Some("tank".to_string()));
+
+        table.add("mage", 10.0);
+        assert_eq!(table.pick_target(Some("tank")), Some("mage".to_string()));
+
+        // A current target that dropped off the table is replaced outright
+        table.remove("tank");
+        table.add("rogue", 1.0);
+        assert_eq!(table.pick_target(Some("tank")), Some("mage".to_string()));
+    }
+
+    #[test]
+    fn test_set_and_retain_drop_players() {
+        let mut table = ThreatTable::new();
+        table.add("alice", 5.0);
+        table.add("bob", 5.0);
+        table.add("carol", 5.0);
+
+        table.set("alice", 0.0);
+        table.retain(|id| id != "bob");
+        assert_eq!(table.sorted(), vec![("carol".to_string(), 5.0)]);
+
+        table.clear();
+        assert!(table.is_empty());
+    }
+}
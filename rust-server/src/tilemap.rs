@@ -115,39 +115,42 @@ impl Tilemap {
         (center_x, center_y)
     }
 
-    /// Check if there's a clear line of sight between two points (Bresenham's line)
+    /// Check if there's a clear line of sight between two points
     /// Returns true if no solid tiles block the path
     pub fn has_line_of_sight(&self, x0: i32, y0: i32, x1: i32, y1: i32) -> bool {
-        let dx = (x1 - x0).abs();
-        let dy = -(y1 - y0).abs();
-        let sx = if x0 < x1 { 1 } else { -1 };
-        let sy = if y0 < y1 { 1 } else { -1 };
-        let mut err = dx + dy;
-
-        let mut x = x0;
-        let mut y = y0;
-
-        loop {
-            // Don't check start position (attacker's tile)
-            if (x != x0 || y != y0) && !self.is_tile_walkable(x, y) {
-                return false;
-            }
-
-            if x == x1 && y == y1 {
-                return true;
-            }
+        trace_line((x0, y0), (x1, y1), |_, (x, y)| self.is_tile_walkable(x, y))
+    }
+}
 
-            let e2 = 2 * err;
-            if e2 >= dy {
-                if x == x1 { return true; }
-                err += dy;
-                x += sx;
-            }
-            if e2 <= dx {
-                if y == y1 { return true; }
-                err += dx;
-                y += sy;
-            }
+/// Walk a Bresenham line from `from` to `to`, asking `can_pass(previous, next)`
+/// for every step. Steps may be diagonal. Returns false at the first refused step.
+/// Shared by every line of sight check so tilemaps, NPCs and players agree.
+pub fn trace_line(
+    from: (i32, i32),
+    to: (i32, i32),
+    mut can_pass: impl FnMut((i32, i32), (i32, i32)) -> bool,
+) -> bool {
+    let dx = (to.0 - from.0).abs();
+    let dy = -(to.1 - from.1).abs();
+    let sx = if from.0 < to.0 { 1 } else { -1 };
+    let sy = if from.1 < to.1 { 1 } else { -1 };
+    let mut err = dx + dy;
+
+    let (mut x, mut y) = from;
+    while (x, y) != to {
+        let previous = (x, y);
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
+        }
+        if !can_pass(previous, (x, y)) {
+            return false;
         }
     }
+    true
 }
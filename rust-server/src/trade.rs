@@ -0,0 +1,344 @@
+//! Player-to-player trading
+//!
+//! A trade runs in two stages: both players build an offer (items + gold) and
+//! accept it, then both confirm the final offer. Any change to either offer
+//! resets both players back to the offering stage. The actual item swap is
+//! done by `GameRoom` under the players write lock.
+
+use std::collections::HashMap;
+use uuid::Uuid;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Maximum tile distance (Chebyshev) between trading players
+pub const TRADE_MAX_DISTANCE: i32 = 3;
+
+/// How long a trade request stays valid before it expires
+pub const TRADE_REQUEST_TIMEOUT_MS: u64 = 30_000;
+
+/// Maximum number of distinct item stacks a player can put in one offer
+pub const TRADE_MAX_OFFER_ITEMS: usize = 12;
+
+// ============================================================================
+// Trade Session
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeStage {
+    /// Players are building their offers
+    Offering,
+    /// Both players accepted, waiting for final confirmation
+    Confirming,
+}
+
+impl TradeStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TradeStage::Offering => "offering",
+            TradeStage::Confirming => "confirming",
+        }
+    }
+}
+
+/// One side of a trade
+#[derive(Debug, Clone, Default)]
+pub struct TradeOffer {
+    /// Offered stacks as (item_id, quantity)
+    pub items: Vec<(String, i32)>,
+    pub gold: i32,
+    pub accepted: bool,
+    pub confirmed: bool,
+}
+
+impl TradeOffer {
+    /// Total quantity of an item already in this offer
+    pub fn offered_quantity(&self, item_id: &str) -> i32 {
+        self.items
+            .iter()
+            .filter(|(id, _)| id == item_id)
+            .map(|(_, qty)| *qty)
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TradeSession {
+    pub id: String,
+    pub player_a: String,
+    pub player_b: String,
+    pub offer_a: TradeOffer,
+    pub offer_b: TradeOffer,
+    pub stage: TradeStage,
+}
+
+impl TradeSession {
+    pub fn new(player_a: &str, player_b: &str) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            player_a: player_a.to_string(),
+            player_b: player_b.to_string(),
+            offer_a: TradeOffer::default(),
+            offer_b: TradeOffer::default(),
+            stage: TradeStage::Offering,
+        }
+    }
+
+    /// Get (my offer, their offer) from the given player's perspective
+    pub fn offers_for(&self, player_id: &str) -> Option<(&TradeOffer, &TradeOffer)> {
+        if player_id == self.player_a {
+            Some((&self.offer_a, &self.offer_b))
+        } else if player_id == self.player_b {
+            Some((&self.offer_b, &self.offer_a))
+        } else {
+            None
+        }
+    }
+
+    pub fn offer_of(&self, player_id: &str) -> Option<&TradeOffer> {
+        self.offers_for(player_id).map(|(mine, _)| mine)
+    }
+
+    /// Modify a player's offer. Resets acceptance on both sides so nobody
+    /// can be tricked by a last-second change.
+    pub fn modify_offer<F: FnOnce(&mut TradeOffer)>(&mut self, player_id: &str, f: F) -> bool {
+        let offer = if player_id == self.player_a {
+            &mut self.offer_a
+        } else if player_id == self.player_b {
+            &mut self.offer_b
+        } else {
+            return false;
+        };
+        f(offer);
+        self.reset_acceptance();
+        true
+    }
+
+    fn reset_acceptance(&mut self) {
+        self.stage = TradeStage::Offering;
+        for offer in [&mut self.offer_a, &mut self.offer_b] {
+            offer.accepted = false;
+            offer.confirmed = false;
+        }
+    }
+
+    /// Accept the current offers. Moves to the confirmation stage once both
+    /// players have accepted.
+    pub fn accept(&mut self, player_id: &str) -> bool {
+        if self.stage != TradeStage::Offering {
+            return false;
+        }
+        if player_id == self.player_a {
+            self.offer_a.accepted = true;
+        } else if player_id == self.player_b {
+            self.offer_b.accepted = true;
+        } else {
+            return false;
+        }
+        if self.offer_a.accepted && self.offer_b.accepted {
+            self.stage = TradeStage::Confirming;
+        }
+        true
+    }
+
+    /// Confirm the final offers. Returns true once both players confirmed
+    /// and the swap should be executed.
+    pub fn confirm(&mut self, player_id: &str) -> bool {
+        if self.stage != TradeStage::Confirming {
+            return false;
+        }
+        if player_id == self.player_a {
+            self.offer_a.confirmed = true;
+        } else if player_id == self.player_b {
+            self.offer_b.confirmed = true;
+        } else {
+            return false;
+        }
+        self.is_ready()
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.stage == TradeStage::Confirming && self.offer_a.confirmed && self.offer_b.confirmed
+    }
+}
+
+/// Result of a successfully executed trade, used for the persistent trade log
+#[derive(Debug, Clone)]
+pub struct CompletedTrade {
+    pub trade_id: String,
+    pub player_a: String,
+    pub player_b: String,
+    pub name_a: String,
+    pub name_b: String,
+    pub items_a: Vec<(String, i32)>,
+    pub items_b: Vec<(String, i32)>,
+    pub gold_a: i32,
+    pub gold_b: i32,
+}
+
+// ============================================================================
+// Trade Manager
+// ============================================================================
+
+/// Pending trade request from one player to another
+#[derive(Debug, Clone)]
+pub struct TradeRequest {
+    pub target_id: String,
+    pub created_at: u64,
+}
+
+/// Tracks open trade requests and active trade sessions for a room
+#[derive(Debug, Default)]
+pub struct TradeManager {
+    /// Requester ID -> outgoing request
+    requests: HashMap<String, TradeRequest>,
+    /// Trade ID -> session
+    sessions: HashMap<String, TradeSession>,
+    /// Player ID -> trade ID
+    player_trades: HashMap<String, String>,
+}
+
+impl TradeManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_trading(&self, player_id: &str) -> bool {
+        self.player_trades.contains_key(player_id)
+    }
+
+    /// Record a trade request, replacing any earlier request from the same player
+    pub fn add_request(&mut self, requester_id: &str, target_id: &str, current_time: u64) {
+        self.requests.insert(requester_id.to_string(), TradeRequest {
+            target_id: target_id.to_string(),
+            created_at: current_time,
+        });
+    }
+
+    /// Take a pending request from `requester_id` to `target_id` if it is still valid
+    pub fn take_request(&mut self, requester_id: &str, target_id: &str, current_time: u64) -> Option<TradeRequest> {
+        let valid = self.requests.get(requester_id).is_some_and(|req| {
+            req.target_id == target_id
+                && current_time.saturating_sub(req.created_at) <= TRADE_REQUEST_TIMEOUT_MS
+        });
+        if valid {
+            self.requests.remove(requester_id)
+        } else {
+            None
+        }
+    }
+
+    /// Open a session between two players. Returns None if either is already trading.
+    pub fn open(&mut self, player_a: &str, player_b: &str) -> Option<&TradeSession> {
+        if self.is_trading(player_a) || self.is_trading(player_b) || player_a == player_b {
+            return None;
+        }
+        // Any other outgoing requests are void once a trade starts
+        self.requests.remove(player_a);
+        self.requests.remove(player_b);
+
+        let session = TradeSession::new(player_a, player_b);
+        let trade_id = session.id.clone();
+        self.player_trades.insert(player_a.to_string(), trade_id.clone());
+        self.player_trades.insert(player_b.to_string(), trade_id.clone());
+        self.sessions.insert(trade_id.clone(), session);
+        self.sessions.get(&trade_id)
+    }
+
+    pub fn session_for_mut(&mut self, player_id: &str) -> Option<&mut TradeSession> {
+        let trade_id = self.player_trades.get(player_id)?;
+        self.sessions.get_mut(trade_id)
+    }
+
+    /// Close the session a player is in, returning it
+    pub fn close_for(&mut self, player_id: &str) -> Option<TradeSession> {
+        let trade_id = self.player_trades.get(player_id)?.clone();
+        let session = self.sessions.remove(&trade_id)?;
+        self.player_trades.remove(&session.player_a);
+        self.player_trades.remove(&session.player_b);
+        Some(session)
+    }
+
+    /// Drop any requests involving a player (used on disconnect)
+    pub fn clear_requests_for(&mut self, player_id: &str) {
+        self.requests.remove(player_id);
+        self.requests.retain(|_, req| req.target_id != player_id);
+    }
+
+    /// Remove requests older than the timeout
+    pub fn expire_requests(&mut self, current_time: u64) {
+        self.requests
+            .retain(|_, req| current_time.saturating_sub(req.created_at) <= TRADE_REQUEST_TIMEOUT_MS);
+    }
+
+    /// All active sessions (for range/death validation each tick)
+    pub fn sessions(&self) -> impl Iterator<Item = &TradeSession> {
+        self.sessions.values()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_stage_confirmation() {
+        let mut session = TradeSession::new("a", "b");
+        assert!(session.accept("a"));
+        assert_eq!(session.stage, TradeStage::Offering);
+        assert!(!session.confirm("a"));
+
+        assert!(session.accept("b"));
+        assert_eq!(session.stage, TradeStage::Confirming);
+
+        assert!(!session.confirm("a"));
+        assert!(session.confirm("b"));
+        assert!(session.is_ready());
+    }
+
+    #[test]
+    fn test_offer_change_resets_acceptance() {
+        let mut session = TradeSession::new("a", "b");
+        session.accept("a");
+        session.accept("b");
+        session.confirm("a");
+
+        session.modify_offer("b", |offer| offer.gold = 50);
+
+        assert_eq!(session.stage, TradeStage::Offering);
+        assert!(!session.offer_a.accepted && !session.offer_a.confirmed);
+        assert!(!session.offer_b.accepted);
+        assert_eq!(session.offer_b.gold, 50);
+    }
+
+    #[test]
+    fn test_unknown_player_rejected() {
+        let mut session = TradeSession::new("a", "b");
+        assert!(!session.accept("c"));
+        assert!(!session.modify_offer("c", |offer| offer.gold = 10));
+        assert!(session.offers_for("c").is_none());
+    }
+
+    #[test]
+    fn test_manager_requests_and_sessions() {
+        let mut manager = TradeManager::new();
+        manager.add_request("a", "b", 1000);
+
+        // Wrong target or expired requests are rejected
+        assert!(manager.take_request("a", "c", 1000).is_none());
+        assert!(manager.take_request("a", "b", 1000 + TRADE_REQUEST_TIMEOUT_MS + 1).is_none());
+
+        manager.add_request("a", "b", 2000);
+        assert!(manager.take_request("a", "b", 2500).is_some());
+
+        assert!(manager.open("a", "b").is_some());
+        assert!(manager.is_trading("a") && manager.is_trading("b"));
+        // Can't open a second trade while busy
+        assert!(manager.open("a", "c").is_none());
+
+        let closed = manager.close_for("b").unwrap();
+        assert_eq!(closed.player_a, "a");
+        assert!(!manager.is_trading("a"));
+    }
+}
@@ -4,7 +4,10 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{info, warn};
 
-use crate::chunk::{world_to_local, Chunk, ChunkCoord, ChunkLayer, ChunkLayerType, EntitySpawn, MapObject, Portal, Wall, WallEdge, CHUNK_SIZE};
+use crate::pathfinding::NavGrid;
+use crate::routine::{ScheduleEntry, Waypoint};
+use crate::spawn_region::SpawnRegion;
+use crate::chunk::{world_to_local, Chunk, ChunkCoord, ChunkLayer, ChunkLayerType, EntitySpawn, MapObject, Portal, PvpZone, Wall, WallEdge, CHUNK_SIZE};
 
 /// World manager that handles loading and caching chunks
 pub struct World {
@@ -175,6 +178,7 @@ impl World {
                 let respawn = entity["respawn"].as_bool().unwrap_or(true);
                 let facing = entity["facing"].as_str().map(|s| s.to_string());
                 let unique_id = entity["uniqueId"].as_str().map(|s| s.to_string());
+                let (patrol, schedule) = self.parse_routine(entity.get("patrol"), entity.get("schedule"), coord);
 
                 chunk.entity_spawns.push(EntitySpawn {
                     entity_id,
@@ -185,6 +189,8 @@ impl World {
                     respawn_time_override: None,
                     facing,
                     unique_id,
+                    patrol,
+                    schedule,
                 });
             }
         }
@@ -245,6 +251,37 @@ impl World {
             .unwrap_or_default();
         chunk.portals = portals;
 
+        // Parse PvP zones (local coordinates in the file)
+        let pvp_zones: Vec<PvpZone> = value
+            .get("pvpZones")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        chunk.pvp_zones = pvp_zones.into_iter()
+            .map(|zone| PvpZone {
+                x: coord.x * CHUNK_SIZE as i32 + zone.x,
+                y: coord.y * CHUNK_SIZE as i32 + zone.y,
+                ..zone
+            })
+            .collect();
+
+        // Parse spawn regions (local coordinates in the file)
+        let spawn_regions: Vec<SpawnRegion> = value
+            .get("spawnRegions")
+            .and_then(|v| serde_json::from_value(v.clone())
+                .map_err(|e| warn!("Invalid spawn regions in chunk {:?}: {}", coord, e))
+                .ok())
+            .unwrap_or_default();
+        chunk.spawn_regions = spawn_regions.into_iter()
+            .enumerate()
+            .map(|(i, mut region)| {
+                region.translate(coord.x * CHUNK_SIZE as i32, coord.y * CHUNK_SIZE as i32);
+                if region.id.is_empty() {
+                    region.id = format!("region_{}_{}_{}", coord.x, coord.y, i);
+                }
+                region
+            })
+            .collect();
+
         Ok(chunk)
     }
 
@@ -359,6 +396,14 @@ impl World {
                                             chunk.set_collision(x + dx, y + dy, true);
                                         }
                                     }
+                                } else if obj_type == "pvp_zone" {
+                                    chunk.pvp_zones.push(PvpZone {
+                                        id: obj["name"].as_str().unwrap_or("").to_string(),
+                                        x: coord.x * CHUNK_SIZE as i32 + x as i32,
+                                        y: coord.y * CHUNK_SIZE as i32 + y as i32,
+                                        width: collision_width.max(1) as i32,
+                                        height: collision_height.max(1) as i32,
+                                    });
                                 } else if obj_type == "entity_spawn" || obj_type == "npc_spawn" {
                                     // Parse entity spawn from Tiled object properties
                                     if let Some(spawn) = self.parse_entity_spawn(obj, coord) {
@@ -407,6 +452,13 @@ impl World {
         let facing = self.get_property_string(properties, "facing");
         let unique_id = self.get_property_string(properties, "unique_id");
 
+        // Routes are JSON strings in Tiled properties
+        let patrol = self.get_property_string(properties, "patrol")
+            .and_then(|json| serde_json::from_str(&json).ok());
+        let schedule = self.get_property_string(properties, "schedule")
+            .and_then(|json| serde_json::from_str(&json).ok());
+        let (patrol, schedule) = self.parse_routine(patrol.as_ref(), schedule.as_ref(), chunk_coord);
+
         Some(EntitySpawn {
             entity_id,
             world_x,
@@ -416,9 +468,38 @@ impl World {
             respawn_time_override,
             facing,
             unique_id,
+            patrol,
+            schedule,
         })
     }
 
+    /// Parse an entity's patrol waypoints and schedule, converting chunk-local tiles to world coordinates
+    fn parse_routine(
+        &self,
+        patrol: Option<&serde_json::Value>,
+        schedule: Option<&serde_json::Value>,
+        chunk_coord: ChunkCoord,
+    ) -> (Vec<Waypoint>, Vec<ScheduleEntry>) {
+        let dx = chunk_coord.x * CHUNK_SIZE as i32;
+        let dy = chunk_coord.y * CHUNK_SIZE as i32;
+
+        let mut patrol: Vec<Waypoint> = patrol
+            .and_then(|v| serde_json::from_value(v.clone())
+                .map_err(|e| warn!("Invalid patrol in chunk {:?}: {}", chunk_coord, e))
+                .ok())
+            .unwrap_or_default();
+        let mut schedule: Vec<ScheduleEntry> = schedule
+            .and_then(|v| serde_json::from_value(v.clone())
+                .map_err(|e| warn!("Invalid schedule in chunk {:?}: {}", chunk_coord, e))
+                .ok())
+            .unwrap_or_default();
+
+        for waypoint in patrol.iter_mut().chain(schedule.iter_mut().flat_map(|entry| entry.waypoints.iter_mut())) {
+            waypoint.translate(dx, dy);
+        }
+        (patrol, schedule)
+    }
+
     /// Get a string property from Tiled properties array
     fn get_property_string(&self, props: Option<&Vec<serde_json::Value>>, name: &str) -> Option<String> {
         props?.iter()
@@ -452,42 +533,28 @@ impl World {
         }
     }
 
-    /// Check if there's a clear line of sight between two points (Bresenham's line)
-    /// Returns true if no solid tiles block the path
-    pub async fn has_line_of_sight(&self, x0: i32, y0: i32, x1: i32, y1: i32) -> bool {
-        let dx = (x1 - x0).abs();
-        let dy = -(y1 - y0).abs();
-        let sx = if x0 < x1 { 1 } else { -1 };
-        let sy = if y0 < y1 { 1 } else { -1 };
-        let mut err = dx + dy;
-
-        let mut x = x0;
-        let mut y = y0;
-
-        loop {
-            // Don't check start position (attacker's tile)
-            if (x != x0 || y != y0) && !self.is_tile_walkable(x, y).await {
-                return false;
-            }
+    /// Snapshot of the loaded chunks' collision and walls for NPC pathfinding
+    pub async fn nav_grid(&self) -> NavGrid {
+        let chunks = self.chunks.read().await;
+        NavGrid::new(chunks.values().cloned())
+    }
 
-            if x == x1 && y == y1 {
-                return true;
-            }
+    /// Check if a world position lies inside a PvP zone of its chunk
+    pub async fn is_pvp_zone(&self, world_x: i32, world_y: i32) -> bool {
+        let coord = ChunkCoord::from_world(world_x, world_y);
 
-            let e2 = 2 * err;
-            if e2 >= dy {
-                if x == x1 { return true; }
-                err += dy;
-                x += sx;
-            }
-            if e2 <= dx {
-                if y == y1 { return true; }
-                err += dx;
-                y += sy;
-            }
+        match self.get_or_load_chunk(coord).await {
+            Some(chunk) => chunk.pvp_zones.iter().any(|zone| zone.contains(world_x, world_y)),
+            None => false,
         }
     }
 
+    /// Check if there's a clear line of sight between two points, blocked by
+    /// collision and wall edges the same way as NPC sight
+    pub async fn has_line_of_sight(&self, x0: i32, y0: i32, x1: i32, y1: i32) -> bool {
+        self.nav_grid().await.has_line_of_sight(x0, y0, x1, y1)
+    }
+
     /// Get chunks in a radius around a center coordinate
     pub fn get_chunks_in_radius(&self, center: ChunkCoord, radius: i32) -> Vec<ChunkCoord> {
         center.in_radius(radius)
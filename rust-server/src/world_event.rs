@@ -0,0 +1,548 @@
+//! World events
+//!
+//! Scheduled or random events from `data/events/*.toml` spawn NPC waves, reward
+//! contributors by damage dealt when every wave dies, and fail when their
+//! duration runs out first.
+
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use tracing::{info, warn};
+
+use crate::quest::definition::{RawReward, Reward};
+
+const HOUR_MS: u64 = 60 * 60 * 1000;
+
+// ============================================================================
+// Definitions
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorldEventKind {
+    /// Waves of monsters attacking a location
+    Invasion,
+    /// A single powerful monster with a bounty on its head
+    Elite,
+    /// A burst of resource-carrying monsters
+    Surge,
+}
+
+impl WorldEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WorldEventKind::Invasion => "invasion",
+            WorldEventKind::Elite => "elite",
+            WorldEventKind::Surge => "surge",
+        }
+    }
+}
+
+/// When an event starts
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EventTrigger {
+    /// Starts at the listed hours of the day (UTC)
+    Scheduled { hours: Vec<u32> },
+    /// Rolled once per check interval with the given chance (0.0 - 1.0)
+    Random { chance: f64 },
+}
+
+/// A group of NPCs spawned together
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventWave {
+    pub prototype: String,
+    #[serde(default = "default_count")]
+    pub count: u32,
+    #[serde(default = "default_level")]
+    pub level: i32,
+    /// Seconds after the event starts before this wave spawns
+    #[serde(default)]
+    pub delay_secs: u64,
+    /// Display name override (e.g. for elites)
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default = "default_hp_multiplier")]
+    pub hp_multiplier: f32,
+}
+
+fn default_count() -> u32 {
+    1
+}
+
+fn default_level() -> i32 {
+    1
+}
+
+fn default_hp_multiplier() -> f32 {
+    1.0
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct EventLocation {
+    pub x: i32,
+    pub y: i32,
+    /// NPCs spawn within this many tiles of the location
+    #[serde(default = "default_radius")]
+    pub radius: i32,
+}
+
+fn default_radius() -> i32 {
+    4
+}
+
+/// Global event configuration
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct WorldEventSettings {
+    /// How often random events are rolled
+    pub check_interval_secs: u64,
+    /// Maximum number of events running at once
+    pub max_active: usize,
+}
+
+impl Default for WorldEventSettings {
+    fn default() -> Self {
+        Self {
+            check_interval_secs: 60,
+            max_active: 2,
+        }
+    }
+}
+
+// ============================================================================
+// Raw TOML Structures
+// ============================================================================
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RawWorldEvent {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub kind: WorldEventKind,
+    pub trigger: EventTrigger,
+    pub location: EventLocation,
+    pub waves: Vec<EventWave>,
+    #[serde(default = "default_duration")]
+    pub duration_secs: u64,
+    #[serde(default)]
+    pub cooldown_secs: u64,
+    /// Damage a player must deal to the event's NPCs to be rewarded
+    #[serde(default = "default_min_contribution")]
+    pub min_contribution: i32,
+    /// Extra gold for the player who dealt the most damage
+    #[serde(default)]
+    pub top_contributor_gold: i32,
+    /// Announcement text when the event starts (defaults to a generic one)
+    #[serde(default)]
+    pub announcement: Option<String>,
+    #[serde(default)]
+    pub rewards: RawReward,
+}
+
+fn default_duration() -> u64 {
+    600
+}
+
+fn default_min_contribution() -> i32 {
+    1
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RawWorldEventFile {
+    pub settings: Option<WorldEventSettings>,
+    #[serde(default)]
+    pub events: HashMap<String, RawWorldEvent>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WorldEventDefinition {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub kind: WorldEventKind,
+    pub trigger: EventTrigger,
+    pub location: EventLocation,
+    pub waves: Vec<EventWave>,
+    pub duration_ms: u64,
+    pub cooldown_ms: u64,
+    pub min_contribution: i32,
+    pub top_contributor_gold: i32,
+    pub announcement: String,
+    pub rewards: Reward,
+}
+
+impl WorldEventDefinition {
+    fn from_raw(id: String, raw: RawWorldEvent) -> Self {
+        let announcement = raw.announcement.unwrap_or_else(|| {
+            format!("{} has begun near ({}, {})!", raw.name, raw.location.x, raw.location.y)
+        });
+        Self {
+            id,
+            name: raw.name,
+            description: raw.description,
+            kind: raw.kind,
+            trigger: raw.trigger,
+            location: raw.location,
+            waves: raw.waves,
+            duration_ms: raw.duration_secs * 1000,
+            cooldown_ms: raw.cooldown_secs * 1000,
+            min_contribution: raw.min_contribution.max(1),
+            top_contributor_gold: raw.top_contributor_gold,
+            announcement,
+            rewards: Reward::from_raw(&raw.rewards),
+        }
+    }
+
+    /// Total number of NPCs across all waves
+    pub fn total_npcs(&self) -> u32 {
+        self.waves.iter().map(|w| w.count).sum()
+    }
+
+    /// Start time of the next scheduled occurrence after `now`
+    pub fn next_scheduled(&self, now: u64) -> Option<u64> {
+        let EventTrigger::Scheduled { hours } = &self.trigger else {
+            return None;
+        };
+        let current_hour = now / HOUR_MS;
+        (1..=24)
+            .map(|offset| current_hour + offset)
+            .find(|hour| hours.contains(&((hour % 24) as u32)))
+            .map(|hour| hour * HOUR_MS)
+    }
+}
+
+/// Registry of world event definitions
+#[derive(Debug, Default)]
+pub struct WorldEventRegistry {
+    settings: WorldEventSettings,
+    events: HashMap<String, WorldEventDefinition>,
+}
+
+impl WorldEventRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load settings and events from every TOML file in a directory
+    pub fn load_from_directory(&mut self, path: &Path) -> Result<(), String> {
+        if !path.exists() {
+            warn!("World event directory does not exist: {:?}", path);
+            return Ok(());
+        }
+
+        for entry in fs::read_dir(path).map_err(|e| e.to_string())? {
+            let entry = entry.map_err(|e| e.to_string())?;
+            let file_path = entry.path();
+
+            if file_path.extension().and_then(|s| s.to_str()) == Some("toml") {
+                let contents = fs::read_to_string(&file_path)
+                    .map_err(|e| format!("Failed to read {:?}: {}", file_path, e))?;
+                let file: RawWorldEventFile = toml::from_str(&contents)
+                    .map_err(|e| format!("Failed to parse {:?}: {}", file_path, e))?;
+                self.add_file(file, &file_path);
+            }
+        }
+
+        info!("Loaded {} world event definitions", self.events.len());
+        Ok(())
+    }
+
+    fn add_file(&mut self, file: RawWorldEventFile, path: &Path) {
+        if let Some(settings) = file.settings {
+            self.settings = settings;
+        }
+        for (id, raw) in file.events {
+            if raw.waves.is_empty() {
+                warn!("World event '{}' in {:?} has no waves, skipping", id, path);
+                continue;
+            }
+            if self.events.contains_key(&id) {
+                warn!("Duplicate world event ID '{}' in {:?}, overwriting", id, path);
+            }
+            self.events.insert(id.clone(), WorldEventDefinition::from_raw(id, raw));
+        }
+    }
+
+    pub fn settings(&self) -> &WorldEventSettings {
+        &self.settings
+    }
+
+    pub fn get(&self, id: &str) -> Option<&WorldEventDefinition> {
+        self.events.get(id)
+    }
+
+    pub fn all(&self) -> impl Iterator<Item = &WorldEventDefinition> {
+        self.events.values()
+    }
+}
+
+// ============================================================================
+// Runtime State
+// ============================================================================
+
+/// A running world event
+#[derive(Debug, Clone)]
+pub struct ActiveWorldEvent {
+    pub event_id: String,
+    pub started_at: u64,
+    pub ends_at: u64,
+    /// Index of the next wave to spawn
+    pub next_wave: usize,
+    /// Event NPCs that are still in the world
+    pub npc_ids: HashSet<String>,
+    pub killed: u32,
+    /// Player ID -> damage dealt to this event's NPCs
+    pub contributions: HashMap<String, i32>,
+}
+
+impl ActiveWorldEvent {
+    fn new(definition: &WorldEventDefinition, now: u64) -> Self {
+        Self {
+            event_id: definition.id.clone(),
+            started_at: now,
+            ends_at: now + definition.duration_ms,
+            next_wave: 0,
+            npc_ids: HashSet::new(),
+            killed: 0,
+            contributions: HashMap::new(),
+        }
+    }
+
+    /// Waves whose delay has passed and that have not been spawned yet
+    pub fn take_due_waves(&mut self, definition: &WorldEventDefinition, now: u64) -> Vec<EventWave> {
+        let mut due = Vec::new();
+        while let Some(wave) = definition.waves.get(self.next_wave) {
+            if self.started_at + wave.delay_secs * 1000 > now {
+                break;
+            }
+            due.push(wave.clone());
+            self.next_wave += 1;
+        }
+        due
+    }
+
+    pub fn record_contribution(&mut self, player_id: &str, amount: i32) {
+        *self.contributions.entry(player_id.to_string()).or_insert(0) += amount;
+    }
+
+    /// Remove a dead NPC from the event, returning false if it was not part of it
+    pub fn npc_killed(&mut self, npc_id: &str) -> bool {
+        if self.npc_ids.remove(npc_id) {
+            self.killed += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Every wave has spawned and been killed
+    pub fn is_cleared(&self, definition: &WorldEventDefinition) -> bool {
+        self.next_wave >= definition.waves.len() && self.npc_ids.is_empty()
+    }
+
+    pub fn is_expired(&self, now: u64) -> bool {
+        now >= self.ends_at
+    }
+
+    /// Players who contributed enough to be rewarded, highest contribution first
+    pub fn eligible_contributors(&self, min_contribution: i32) -> Vec<(String, i32)> {
+        let mut eligible: Vec<(String, i32)> = self.contributions.iter()
+            .filter(|(_, amount)| **amount >= min_contribution)
+            .map(|(id, amount)| (id.clone(), *amount))
+            .collect();
+        eligible.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        eligible
+    }
+}
+
+/// Tracks running events, cooldowns and schedule state
+#[derive(Debug, Default)]
+pub struct WorldEventManager {
+    active: HashMap<String, ActiveWorldEvent>,
+    /// Event ID -> time it may start again
+    cooldowns: HashMap<String, u64>,
+    /// Event ID -> hour index (hours since the epoch) it last started on schedule
+    last_scheduled_hour: HashMap<String, u64>,
+    last_random_check: u64,
+}
+
+impl WorldEventManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// IDs of events that should start now. `roll` returns a value in 0.0..1.0
+    /// and is called once per random event each check interval.
+    pub fn due_events(
+        &mut self,
+        registry: &WorldEventRegistry,
+        now: u64,
+        mut roll: impl FnMut() -> f64,
+    ) -> Vec<String> {
+        let check_random = now.saturating_sub(self.last_random_check)
+            >= registry.settings().check_interval_secs * 1000;
+        if check_random {
+            self.last_random_check = now;
+        }
+        let hour = now / HOUR_MS;
+
+        let mut definitions: Vec<&WorldEventDefinition> = registry.all().collect();
+        definitions.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let mut due = Vec::new();
+        for definition in definitions {
+            if self.active.len() + due.len() >= registry.settings().max_active {
+                break;
+            }
+            if !self.can_start(&definition.id, now) {
+                continue;
+            }
+            let starts = match &definition.trigger {
+                EventTrigger::Scheduled { hours } => {
+                    hours.contains(&((hour % 24) as u32))
+                        && self.last_scheduled_hour.get(&definition.id) != Some(&hour)
+                }
+                EventTrigger::Random { chance } => check_random && roll() < *chance,
+            };
+            if starts {
+                if matches!(definition.trigger, EventTrigger::Scheduled { .. }) {
+                    self.last_scheduled_hour.insert(definition.id.clone(), hour);
+                }
+                due.push(definition.id.clone());
+            }
+        }
+        due
+    }
+
+    /// Whether an event is neither running nor cooling down
+    pub fn can_start(&self, event_id: &str, now: u64) -> bool {
+        !self.active.contains_key(event_id)
+            && self.cooldowns.get(event_id).is_none_or(|ready_at| now >= *ready_at)
+    }
+
+    pub fn start(&mut self, definition: &WorldEventDefinition, now: u64) {
+        self.active.insert(definition.id.clone(), ActiveWorldEvent::new(definition, now));
+    }
+
+    /// Stop an event and start its cooldown
+    pub fn finish(&mut self, definition: &WorldEventDefinition, now: u64) -> Option<ActiveWorldEvent> {
+        let event = self.active.remove(&definition.id)?;
+        self.cooldowns.insert(definition.id.clone(), now + definition.cooldown_ms);
+        Some(event)
+    }
+
+    pub fn active(&self) -> impl Iterator<Item = &ActiveWorldEvent> {
+        self.active.values()
+    }
+
+    pub fn active_mut(&mut self) -> impl Iterator<Item = &mut ActiveWorldEvent> {
+        self.active.values_mut()
+    }
+
+    pub fn get_mut(&mut self, event_id: &str) -> Option<&mut ActiveWorldEvent> {
+        self.active.get_mut(event_id)
+    }
+
+    /// The running event an NPC belongs to
+    pub fn event_for_npc(&mut self, npc_id: &str) -> Option<&mut ActiveWorldEvent> {
+        self.active.values_mut().find(|e| e.npc_ids.contains(npc_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> WorldEventRegistry {
+        let toml = r#"
+            [settings]
+            check_interval_secs = 60
+            max_active = 2
+
+            [events.pig_invasion]
+            name = "Swine Invasion"
+            kind = "invasion"
+            trigger = { type = "scheduled", hours = [12] }
+            location = { x = 10, y = 10 }
+            duration_secs = 300
+            cooldown_secs = 600
+            min_contribution = 10
+            waves = [
+                { prototype = "corrupted_pig", count = 3 },
+                { prototype = "corrupted_pig", count = 2, delay_secs = 60 },
+            ]
+            rewards = { gold = 100 }
+
+            [events.elite_reaper]
+            name = "Bounty: Reaper"
+            kind = "elite"
+            trigger = { type = "random", chance = 0.5 }
+            location = { x = 30, y = 30, radius = 2 }
+            waves = [{ prototype = "reaper", level = 10, name = "Elder Reaper", hp_multiplier = 3.0 }]
+        "#;
+        let file: RawWorldEventFile = toml::from_str(toml).unwrap();
+        let mut registry = WorldEventRegistry::new();
+        registry.add_file(file, Path::new("test.toml"));
+        registry
+    }
+
+    #[test]
+    fn test_scheduled_and_random_triggers() {
+        let registry = registry();
+        let mut manager = WorldEventManager::new();
+        let noon = 12 * HOUR_MS;
+
+        // Scheduled fires once per matching hour; random depends on the roll
+        assert_eq!(manager.due_events(&registry, noon, || 0.9), vec!["pig_invasion".to_string()]);
+        assert!(manager.due_events(&registry, noon + 1000, || 0.0).is_empty());
+        assert_eq!(manager.due_events(&registry, noon + 61_000, || 0.1), vec!["elite_reaper".to_string()]);
+
+        // Running or cooling down events don't start again
+        let definition = registry.get("pig_invasion").unwrap();
+        manager.start(definition, noon);
+        assert!(!manager.can_start("pig_invasion", noon));
+        manager.finish(definition, noon + 1000);
+        assert!(!manager.can_start("pig_invasion", noon + 2000));
+        assert!(manager.can_start("pig_invasion", noon + 601_000));
+
+        assert_eq!(definition.next_scheduled(noon), Some(noon + 24 * HOUR_MS));
+        assert_eq!(definition.next_scheduled(noon - 1), Some(noon));
+    }
+
+    #[test]
+    fn test_waves_and_completion() {
+        let registry = registry();
+        let definition = registry.get("pig_invasion").unwrap();
+        let mut event = ActiveWorldEvent::new(definition, 0);
+
+        let waves = event.take_due_waves(definition, 0);
+        assert_eq!(waves.len(), 1);
+        assert_eq!(waves[0].count, 3);
+        assert!(event.take_due_waves(definition, 30_000).is_empty());
+
+        event.npc_ids.insert("a".to_string());
+        assert!(event.npc_killed("a"));
+        assert!(!event.npc_killed("a"));
+        assert!(!event.is_cleared(definition));
+
+        assert_eq!(event.take_due_waves(definition, 60_000).len(), 1);
+        assert!(event.is_cleared(definition));
+        assert!(event.is_expired(300_000));
+    }
+
+    #[test]
+    fn test_eligible_contributors() {
+        let registry = registry();
+        let definition = registry.get("pig_invasion").unwrap();
+        let mut event = ActiveWorldEvent::new(definition, 0);
+        event.record_contribution("alice", 5);
+        event.record_contribution("bob", 12);
+        event.record_contribution("alice", 20);
+        event.record_contribution("carol", 3);
+
+        let eligible = event.eligible_contributors(definition.min_contribution);
+        assert_eq!(eligible, vec![("alice".to_string(), 25), ("bob".to_string(), 12)]);
+    }
+}
@@ -0,0 +1,738 @@
+//! World scripting
+//!
+//! Scripts in `data/scripts/world/` share one sandboxed Lua state, each in its
+//! own environment, and queue `ScriptAction`s through the global `world` table
+//! (see `data/scripts/README.md` for the hooks and API).
+
+use mlua::{Function, Lua, Result as LuaResult, Table, Value};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::behavior::hour_of_day;
+use crate::lua_sandbox;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// `quest_id` of dialogues opened by world scripts, followed by the script ID
+pub const SCRIPT_DIALOGUE_PREFIX: &str = "script:";
+/// Shortest repeat interval for `world.every`
+const MIN_TIMER_INTERVAL_MS: u64 = 250;
+
+// ============================================================================
+// Scripts
+// ============================================================================
+
+/// World script sources, keyed by path relative to the world script directory
+/// without the `.lua` extension. Reloading bumps `version` so game rooms know
+/// to rebuild their Lua state.
+#[derive(Debug)]
+pub struct WorldScripts {
+    dir: PathBuf,
+    scripts: RwLock<HashMap<String, String>>,
+    version: AtomicU64,
+}
+
+impl WorldScripts {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            dir: data_dir.join("scripts").join("world"),
+            scripts: RwLock::new(HashMap::new()),
+            version: AtomicU64::new(0),
+        }
+    }
+
+    /// (Re)load every `.lua` file under the world script directory
+    pub fn reload(&self) -> Result<usize, String> {
+        let mut scripts = HashMap::new();
+        if self.dir.exists() {
+            load_dir(&self.dir, &self.dir, &mut scripts)?;
+        } else {
+            warn!("World script directory does not exist: {:?}", self.dir);
+        }
+        let count = scripts.len();
+        *self.scripts.write().map_err(|e| e.to_string())? = scripts;
+        self.version.fetch_add(1, Ordering::SeqCst);
+        Ok(count)
+    }
+
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::SeqCst)
+    }
+
+    /// All scripts as (ID, source), sorted by ID so load order is stable
+    pub fn snapshot(&self) -> Vec<(String, String)> {
+        let Ok(scripts) = self.scripts.read() else {
+            return Vec::new();
+        };
+        let mut snapshot: Vec<(String, String)> = scripts.iter()
+            .map(|(id, source)| (id.clone(), source.clone()))
+            .collect();
+        snapshot.sort_by(|a, b| a.0.cmp(&b.0));
+        snapshot
+    }
+
+    /// Reload the scripts whenever a `.lua` file in the directory changes
+    pub fn start_file_watcher(self: &Arc<Self>) -> Result<(), String> {
+        use notify::{Config, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+        use std::time::Duration;
+
+        if !self.dir.exists() {
+            return Err(format!("World script directory does not exist: {:?}", self.dir));
+        }
+
+        let (notify_tx, notify_rx) = std::sync::mpsc::channel();
+        let mut watcher = RecommendedWatcher::new(
+            move |res: Result<notify::Event, notify::Error>| {
+                if let Ok(event) = res {
+                    let _ = notify_tx.send(event);
+                }
+            },
+            Config::default().with_poll_interval(Duration::from_secs(1)),
+        ).map_err(|e| format!("Failed to create file watcher: {}", e))?;
+        watcher.watch(&self.dir, RecursiveMode::Recursive)
+            .map_err(|e| format!("Failed to watch {:?}: {}", self.dir, e))?;
+
+        let scripts = Arc::clone(self);
+        std::thread::spawn(move || {
+            // The watcher stops when dropped, so it lives as long as this thread
+            let _watcher = watcher;
+            while let Ok(event) = notify_rx.recv() {
+                if !matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_) | EventKind::Remove(_)) {
+                    continue;
+                }
+                if !event.paths.iter().any(|p| p.extension().and_then(|e| e.to_str()) == Some("lua")) {
+                    continue;
+                }
+                match scripts.reload() {
+                    Ok(count) => info!("World scripts hot-reloaded ({} scripts)", count),
+                    Err(e) => error!("World script hot-reload failed: {}", e),
+                }
+            }
+        });
+
+        info!("World script hot-reload watcher started for {:?}", self.dir);
+        Ok(())
+    }
+}
+
+fn load_dir(root: &Path, dir: &Path, scripts: &mut HashMap<String, String>) -> Result<(), String> {
+    for entry in fs::read_dir(dir).map_err(|e| e.to_string())? {
+        let path: PathBuf = entry.map_err(|e| e.to_string())?.path();
+        if path.is_dir() {
+            load_dir(root, &path, scripts)?;
+        } else if path.extension().and_then(|s| s.to_str()) == Some("lua") {
+            let source = fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+            let id = path.strip_prefix(root)
+                .map_err(|e| e.to_string())?
+                .with_extension("")
+                .to_string_lossy()
+                .replace('\\', "/");
+            scripts.insert(id, source);
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Hooks and Actions
+// ============================================================================
+
+/// Something that happened in the world that scripts may react to
+#[derive(Debug, Clone, Copy)]
+pub enum ScriptHook<'a> {
+    NpcDeath { npc_id: &'a str, prototype_id: &'a str, killer_id: &'a str },
+    ItemUse { player_id: &'a str, item_id: &'a str },
+    InteractObject { player_id: &'a str, npc_id: &'a str, prototype_id: &'a str },
+    /// Only goes to the script that opened the dialogue
+    DialogueChoice { script: &'a str, player_id: &'a str, choice_id: &'a str },
+}
+
+/// Something a world script asked for
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptAction {
+    SpawnNpc { npc_id: String, prototype: String, x: i32, y: i32, level: i32, script: String },
+    GiveItem { player_id: String, item_id: String, quantity: i32 },
+    /// Broadcast to every player
+    Announce { text: String },
+    /// System message to one player
+    Message { player_id: String, text: String },
+    /// Choices are (ID, text)
+    ShowDialogue { script: String, player_id: String, speaker: String, text: String, choices: Vec<(String, String)> },
+}
+
+/// What running a hook produced
+#[derive(Debug, Default)]
+pub struct ScriptOutput {
+    pub actions: Vec<ScriptAction>,
+    /// Whether a script took over the interaction (`on_interact_object` returned true)
+    pub handled: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FlagValue {
+    Bool(bool),
+    Number(f64),
+    Text(String),
+}
+
+// ============================================================================
+// Host
+// ============================================================================
+
+struct Timer {
+    id: u64,
+    script: String,
+    due: u64,
+    /// Repeat interval for `world.every`
+    interval: Option<u64>,
+    callback: Function,
+}
+
+#[derive(Debug, Clone)]
+struct TriggerRegion {
+    script: String,
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
+impl TriggerRegion {
+    fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+}
+
+/// State the `world` API reads and writes, kept as the Lua state's app data
+#[derive(Default)]
+struct HostState {
+    /// Script whose code is running, so timers and dialogues know their owner
+    current_script: String,
+    now: u64,
+    actions: Vec<ScriptAction>,
+    flags: HashMap<String, FlagValue>,
+    timers: Vec<Timer>,
+    next_timer_id: u64,
+    regions: HashMap<String, TriggerRegion>,
+    /// Overworld player ID -> tile, as of the last update
+    positions: HashMap<String, (i32, i32)>,
+}
+
+/// Runs the world scripts for a game room
+pub struct WorldScriptHost {
+    lua: Lua,
+    /// Script ID -> environment holding its globals, in load order
+    scripts: Vec<(String, Table)>,
+    /// `WorldScripts` version these were loaded from
+    version: u64,
+    /// Player ID -> regions they were standing in at the last update
+    player_regions: HashMap<String, HashSet<String>>,
+}
+
+impl WorldScriptHost {
+    /// Load scripts into a fresh sandbox. Scripts that fail to load are logged and skipped.
+    pub fn new(scripts: &[(String, String)], version: u64, now: u64) -> LuaResult<Self> {
+        Self::with_flags(scripts, version, now, HashMap::new())
+    }
+
+    fn with_flags(scripts: &[(String, String)], version: u64, now: u64, flags: HashMap<String, FlagValue>) -> LuaResult<Self> {
+        let lua = lua_sandbox::new_sandbox()?;
+        lua.set_app_data(HostState { now, flags, ..HostState::default() });
+        lua.globals().set("world", create_world_api(&lua)?)?;
+
+        let mut host = Self {
+            lua,
+            scripts: Vec::new(),
+            version,
+            player_regions: HashMap::new(),
+        };
+        for (id, source) in scripts {
+            match host.load_script(id, source) {
+                Ok(env) => host.scripts.push((id.clone(), env)),
+                Err(e) => error!("Failed to load world script {}: {}", id, e),
+            }
+        }
+        info!("Loaded {} world scripts", host.scripts.len());
+        Ok(host)
+    }
+
+    fn load_script(&self, id: &str, source: &str) -> LuaResult<Table> {
+        let env = self.lua.create_table()?;
+        let meta = self.lua.create_table()?;
+        meta.set("__index", self.lua.globals())?;
+        env.set_metatable(Some(meta));
+        self.begin_call(id);
+        self.lua.load(source)
+            .set_name(id)
+            .set_environment(env.clone())
+            .exec()?;
+        Ok(env)
+    }
+
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Swap in new script sources, keeping flags and who is standing in which region
+    pub fn reload(&mut self, scripts: &[(String, String)], version: u64, now: u64) {
+        let flags = self.lua.app_data_mut::<HostState>()
+            .map(|mut state| std::mem::take(&mut state.flags))
+            .unwrap_or_default();
+        match Self::with_flags(scripts, version, now, flags) {
+            Ok(host) => {
+                let player_regions = std::mem::take(&mut self.player_regions);
+                *self = host;
+                self.player_regions = player_regions;
+            }
+            Err(e) => error!("Failed to rebuild world script state: {}", e),
+        }
+    }
+
+    /// Run a hook in every script that defines it
+    pub fn run_hook(&mut self, hook: ScriptHook, now: u64) -> ScriptOutput {
+        self.set_now(now);
+        let mut handled = false;
+        match hook {
+            ScriptHook::NpcDeath { npc_id, prototype_id, killer_id } => {
+                self.call_all("on_npc_death", (npc_id, prototype_id, killer_id));
+            }
+            ScriptHook::ItemUse { player_id, item_id } => {
+                self.call_all("on_item_use", (player_id, item_id));
+            }
+            ScriptHook::InteractObject { player_id, npc_id, prototype_id } => {
+                handled = self.call_all("on_interact_object", (player_id, npc_id, prototype_id))
+                    .iter()
+                    .any(|result| matches!(result, Value::Boolean(true)));
+            }
+            ScriptHook::DialogueChoice { script, player_id, choice_id } => {
+                if let Some(function) = self.hook_function(script, "on_dialogue_choice") {
+                    self.call(script, function, (player_id, choice_id));
+                }
+            }
+        }
+        ScriptOutput { actions: self.take_actions(), handled }
+    }
+
+    /// Fire due timers and region hooks for players at (ID, x, y)
+    pub fn update(&mut self, now: u64, players: &[(String, i32, i32)]) -> ScriptOutput {
+        self.set_now(now);
+        if let Some(mut state) = self.lua.app_data_mut::<HostState>() {
+            state.positions = players.iter().map(|(id, x, y)| (id.clone(), (*x, *y))).collect();
+        }
+
+        let due: Vec<(String, Function)> = match self.lua.app_data_mut::<HostState>() {
+            Some(mut state) => {
+                let (due, pending): (Vec<Timer>, Vec<Timer>) = std::mem::take(&mut state.timers)
+                    .into_iter()
+                    .partition(|timer| timer.due <= now);
+                state.timers = pending;
+                let mut fired = Vec::new();
+                for timer in due {
+                    fired.push((timer.script.clone(), timer.callback.clone()));
+                    if let Some(interval) = timer.interval {
+                        state.timers.push(Timer { due: now + interval, ..timer });
+                    }
+                }
+                fired
+            }
+            None => Vec::new(),
+        };
+        for (script, callback) in due {
+            self.call(&script, callback, ());
+        }
+
+        let regions: HashMap<String, TriggerRegion> = self.lua.app_data_ref::<HostState>()
+            .map(|state| state.regions.clone())
+            .unwrap_or_default();
+        let mut entered = Vec::new();
+        let mut standing_in = HashMap::new();
+        for (player_id, x, y) in players {
+            let inside: HashSet<String> = regions.iter()
+                .filter(|(_, region)| region.contains(*x, *y))
+                .map(|(id, _)| id.clone())
+                .collect();
+            let before = self.player_regions.get(player_id);
+            for region_id in &inside {
+                if before.is_none_or(|regions| !regions.contains(region_id)) {
+                    entered.push((player_id.clone(), region_id.clone()));
+                }
+            }
+            standing_in.insert(player_id.clone(), inside);
+        }
+        // Players who left the room are forgotten, so coming back counts as entering
+        self.player_regions = standing_in;
+
+        for (player_id, region_id) in entered {
+            let Some(region) = regions.get(&region_id) else {
+                continue;
+            };
+            if let Some(function) = self.hook_function(&region.script, "on_player_enter_region") {
+                self.call(&region.script, function, (player_id.as_str(), region_id.as_str()));
+            }
+        }
+
+        ScriptOutput { actions: self.take_actions(), handled: false }
+    }
+
+    fn set_now(&self, now: u64) {
+        if let Some(mut state) = self.lua.app_data_mut::<HostState>() {
+            state.now = now;
+        }
+    }
+
+    fn begin_call(&self, script: &str) {
+        if let Some(mut state) = self.lua.app_data_mut::<HostState>() {
+            state.current_script = script.to_string();
+        }
+        lua_sandbox::reset_budget(&self.lua);
+    }
+
+    fn take_actions(&self) -> Vec<ScriptAction> {
+        self.lua.app_data_mut::<HostState>()
+            .map(|mut state| std::mem::take(&mut state.actions))
+            .unwrap_or_default()
+    }
+
+    fn hook_function(&self, script: &str, hook: &str) -> Option<Function> {
+        self.scripts.iter()
+            .find(|(id, _)| id == script)
+            .and_then(|(_, env)| env.raw_get::<Option<Function>>(hook).ok().flatten())
+    }
+
+    /// Call a hook in every script that defines it, returning what each one returned
+    fn call_all(&self, hook: &str, args: impl mlua::IntoLuaMulti + Clone) -> Vec<Value> {
+        let hooks: Vec<(String, Function)> = self.scripts.iter()
+            .filter_map(|(id, env)| {
+                let function = env.raw_get::<Option<Function>>(hook).ok().flatten()?;
+                Some((id.clone(), function))
+            })
+            .collect();
+        hooks.into_iter()
+            .filter_map(|(script, function)| self.call(&script, function, args.clone()))
+            .collect()
+    }
+
+    fn call(&self, script: &str, function: Function, args: impl mlua::IntoLuaMulti) -> Option<Value> {
+        self.begin_call(script);
+        match function.call::<Value>(args) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                error!("World script {} error: {}", script, e);
+                None
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Lua API
+// ============================================================================
+
+fn push_action(lua: &Lua, action: impl FnOnce(&str) -> ScriptAction) {
+    if let Some(mut state) = lua.app_data_mut::<HostState>() {
+        let action = action(&state.current_script);
+        state.actions.push(action);
+    }
+}
+
+fn add_timer(lua: &Lua, delay_ms: u64, interval: Option<u64>, callback: Function) -> u64 {
+    let Some(mut state) = lua.app_data_mut::<HostState>() else {
+        return 0;
+    };
+    state.next_timer_id += 1;
+    let timer = Timer {
+        id: state.next_timer_id,
+        script: state.current_script.clone(),
+        due: state.now + delay_ms,
+        interval,
+        callback,
+    };
+    state.timers.push(timer);
+    state.next_timer_id
+}
+
+fn create_world_api(lua: &Lua) -> LuaResult<Table> {
+    let world = lua.create_table()?;
+
+    // world.spawn_npc(prototype, x, y, [level]) -> npc_id
+    world.set("spawn_npc", lua.create_function(|lua, (prototype, x, y, level): (String, i32, i32, Option<i32>)| {
+        let npc_id = format!("script_npc_{}", Uuid::new_v4());
+        let id = npc_id.clone();
+        push_action(lua, |script| ScriptAction::SpawnNpc {
+            npc_id: id,
+            prototype,
+            x,
+            y,
+            level: level.unwrap_or(1).max(1),
+            script: script.to_string(),
+        });
+        Ok(npc_id)
+    })?)?;
+
+    // world.give_item(player_id, item_id, [quantity])
+    world.set("give_item", lua.create_function(|lua, (player_id, item_id, quantity): (String, String, Option<i32>)| {
+        let quantity = quantity.unwrap_or(1);
+        if quantity > 0 {
+            push_action(lua, |_| ScriptAction::GiveItem { player_id, item_id, quantity });
+        }
+        Ok(())
+    })?)?;
+
+    // world.announce(text)
+    world.set("announce", lua.create_function(|lua, text: String| {
+        push_action(lua, |_| ScriptAction::Announce { text });
+        Ok(())
+    })?)?;
+
+    // world.message(player_id, text)
+    world.set("message", lua.create_function(|lua, (player_id, text): (String, String)| {
+        push_action(lua, |_| ScriptAction::Message { player_id, text });
+        Ok(())
+    })?)?;
+
+    // world.show_dialogue(player_id, { speaker, text, choices = {{ id, text }} })
+    world.set("show_dialogue", lua.create_function(|lua, (player_id, options): (String, Table)| {
+        let speaker: String = options.get::<Option<String>>("speaker")?.unwrap_or_default();
+        let text: String = options.get::<Option<String>>("text")?.unwrap_or_default();
+        let choices = match options.get::<Option<Table>>("choices")? {
+            Some(choices) => choices.sequence_values::<Table>()
+                .filter_map(|choice| choice.ok())
+                .filter_map(|choice| Some((choice.get::<String>("id").ok()?, choice.get::<String>("text").ok()?)))
+                .collect(),
+            None => Vec::new(),
+        };
+        push_action(lua, |script| ScriptAction::ShowDialogue {
+            script: script.to_string(),
+            player_id,
+            speaker,
+            text,
+            choices,
+        });
+        Ok(())
+    })?)?;
+
+    // world.get_flag(name) -> value or nil
+    world.set("get_flag", lua.create_function(|lua, name: String| {
+        let flag = lua.app_data_ref::<HostState>().and_then(|state| state.flags.get(&name).cloned());
+        Ok(match flag {
+            Some(FlagValue::Bool(value)) => Value::Boolean(value),
+            Some(FlagValue::Number(value)) => Value::Number(value),
+            Some(FlagValue::Text(value)) => Value::String(lua.create_string(&value)?),
+            None => Value::Nil,
+        })
+    })?)?;
+
+    // world.set_flag(name, value) (nil clears it)
+    world.set("set_flag", lua.create_function(|lua, (name, value): (String, Value)| {
+        let value = match value {
+            Value::Nil => None,
+            Value::Boolean(value) => Some(FlagValue::Bool(value)),
+            Value::Integer(value) => Some(FlagValue::Number(value as f64)),
+            Value::Number(value) => Some(FlagValue::Number(value)),
+            Value::String(value) => Some(FlagValue::Text(value.to_str()?.to_string())),
+            other => {
+                return Err(mlua::Error::RuntimeError(format!(
+                    "flag '{}' must be a boolean, number or string, not {}", name, other.type_name()
+                )));
+            }
+        };
+        if let Some(mut state) = lua.app_data_mut::<HostState>() {
+            match value {
+                Some(value) => state.flags.insert(name, value),
+                None => state.flags.remove(&name),
+            };
+        }
+        Ok(())
+    })?)?;
+
+    // world.after(ms, fn) -> timer_id
+    world.set("after", lua.create_function(|lua, (delay_ms, callback): (u64, Function)| {
+        Ok(add_timer(lua, delay_ms, None, callback))
+    })?)?;
+
+    // world.every(ms, fn) -> timer_id
+    world.set("every", lua.create_function(|lua, (interval_ms, callback): (u64, Function)| {
+        let interval_ms = interval_ms.max(MIN_TIMER_INTERVAL_MS);
+        Ok(add_timer(lua, interval_ms, Some(interval_ms), callback))
+    })?)?;
+
+    // world.cancel(timer_id)
+    world.set("cancel", lua.create_function(|lua, timer_id: u64| {
+        if let Some(mut state) = lua.app_data_mut::<HostState>() {
+            state.timers.retain(|timer| timer.id != timer_id);
+        }
+        Ok(())
+    })?)?;
+
+    // world.define_region(id, x, y, width, height)
+    world.set("define_region", lua.create_function(|lua, (id, x, y, width, height): (String, i32, i32, i32, i32)| {
+        if let Some(mut state) = lua.app_data_mut::<HostState>() {
+            let script = state.current_script.clone();
+            state.regions.insert(id, TriggerRegion { script, x, y, width, height });
+        }
+        Ok(())
+    })?)?;
+
+    // world.player_position(player_id) -> x, y or nil
+    world.set("player_position", lua.create_function(|lua, player_id: String| {
+        Ok(lua.app_data_ref::<HostState>()
+            .and_then(|state| state.positions.get(&player_id).copied())
+            .map_or((None, None), |(x, y)| (Some(x), Some(y))))
+    })?)?;
+
+    // world.hour() -> in-game hour (0-23)
+    world.set("hour", lua.create_function(|lua, ()| {
+        Ok(lua.app_data_ref::<HostState>().map(|state| hour_of_day(state.now)).unwrap_or(0))
+    })?)?;
+
+    // world.log(text)
+    world.set("log", lua.create_function(|lua, text: String| {
+        let script = lua.app_data_ref::<HostState>()
+            .map(|state| state.current_script.clone())
+            .unwrap_or_default();
+        info!("[world script {}] {}", script, text);
+        Ok(())
+    })?)?;
+
+    Ok(world)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn host(scripts: &[(&str, &str)]) -> WorldScriptHost {
+        let scripts: Vec<(String, String)> = scripts.iter()
+            .map(|(id, source)| (id.to_string(), source.to_string()))
+            .collect();
+        WorldScriptHost::new(&scripts, 1, 0).unwrap()
+    }
+
+    #[test]
+    fn test_hooks_run_in_every_script_and_queue_actions() {
+        let mut host = host(&[
+            ("a", r#"
+                function on_npc_death(npc_id, prototype_id, killer_id)
+                    world.give_item(killer_id, "bone", 2)
+                end
+            "#),
+            ("b", r#"
+                function on_npc_death(npc_id, prototype_id, killer_id)
+                    if prototype_id == "pig" then
+                        world.announce("A pig fell")
+                    end
+                end
+                function on_interact_object(player_id, npc_id, prototype_id)
+                    world.show_dialogue(player_id, { speaker = "Lever", text = "Pull it?",
+                        choices = {{ id = "pull", text = "Pull" }} })
+                    return prototype_id == "lever"
+                end
+            "#),
+        ]);
+
+        let output = host.run_hook(ScriptHook::NpcDeath { npc_id: "npc_1", prototype_id: "pig", killer_id: "p1" }, 0);
+        assert_eq!(output.actions, vec![
+            ScriptAction::GiveItem { player_id: "p1".to_string(), item_id: "bone".to_string(), quantity: 2 },
+            ScriptAction::Announce { text: "A pig fell".to_string() },
+        ]);
+
+        let output = host.run_hook(ScriptHook::InteractObject { player_id: "p1", npc_id: "npc_2", prototype_id: "lever" }, 0);
+        assert!(output.handled);
+        assert_eq!(output.actions, vec![ScriptAction::ShowDialogue {
+            script: "b".to_string(),
+            player_id: "p1".to_string(),
+            speaker: "Lever".to_string(),
+            text: "Pull it?".to_string(),
+            choices: vec![("pull".to_string(), "Pull".to_string())],
+        }]);
+        assert!(!host.run_hook(ScriptHook::InteractObject { player_id: "p1", npc_id: "npc_3", prototype_id: "pig" }, 0).handled);
+    }
+
+    #[test]
+    fn test_flags_are_shared_and_survive_reload() {
+        let mut host = host(&[
+            ("writer", r#"function on_item_use(player_id, item_id) world.set_flag("used_" .. item_id, true) end"#),
+            ("reader", r#"
+                function on_dialogue_choice(player_id, choice_id)
+                    if world.get_flag("used_key") then world.message(player_id, choice_id) end
+                end
+            "#),
+        ]);
+        host.run_hook(ScriptHook::ItemUse { player_id: "p1", item_id: "key" }, 0);
+        assert_eq!(host.lua.app_data_ref::<HostState>().unwrap().flags.get("used_key").cloned(), Some(FlagValue::Bool(true)));
+
+        let sources = vec![("reader".to_string(), r#"
+            function on_dialogue_choice(player_id, choice_id)
+                if world.get_flag("used_key") then world.message(player_id, "still " .. choice_id) end
+            end
+        "#.to_string())];
+        host.reload(&sources, 2, 0);
+        assert_eq!(host.version(), 2);
+        let output = host.run_hook(ScriptHook::DialogueChoice { script: "reader", player_id: "p1", choice_id: "open" }, 0);
+        assert_eq!(output.actions, vec![ScriptAction::Message { player_id: "p1".to_string(), text: "still open".to_string() }]);
+    }
+
+    #[test]
+    fn test_timers_and_regions_fire_on_update() {
+        let mut host = host(&[("shrine", r#"
+            world.define_region("altar", 10, 10, 2, 2)
+            ticks = 0
+            local repeating = world.every(1000, function()
+                ticks = ticks + 1
+                world.announce("tick " .. ticks)
+            end)
+            world.after(1500, function() world.cancel(repeating) end)
+
+            function on_player_enter_region(player_id, region_id)
+                local x, y = world.player_position(player_id)
+                world.message(player_id, "entered " .. region_id .. " at " .. x .. "," .. y)
+            end
+        "#)]);
+        let announcements = |output: &ScriptOutput| output.actions.iter()
+            .filter(|a| matches!(a, ScriptAction::Announce { .. }))
+            .count();
+
+        assert_eq!(announcements(&host.update(999, &[])), 0);
+        assert_eq!(announcements(&host.update(1000, &[])), 1);
+        // The one-shot timer cancels the repeating one before it fires again
+        assert_eq!(announcements(&host.update(1500, &[])), 0);
+        assert_eq!(announcements(&host.update(5000, &[])), 0);
+
+        let entered = |text: &str| vec![ScriptAction::Message { player_id: "p1".to_string(), text: text.to_string() }];
+        assert_eq!(host.update(6000, &[("p1".to_string(), 11, 11)]).actions, entered("entered altar at 11,11"));
+        assert!(host.update(6100, &[("p1".to_string(), 10, 11)]).actions.is_empty());
+        assert!(host.update(6200, &[("p1".to_string(), 12, 11)]).actions.is_empty());
+        assert_eq!(host.update(6300, &[("p1".to_string(), 11, 10)]).actions, entered("entered altar at 11,10"));
+    }
+
+    #[test]
+    fn test_sandbox_and_runaway_scripts() {
+        let mut host = host(&[
+            ("broken", "this is not lua"),
+            ("probe", r#"
+                function on_item_use(player_id, item_id)
+                    if os == nil and io == nil and load == nil and require == nil then
+                        world.message(player_id, "sandboxed")
+                    end
+                end
+            "#),
+            ("spin", r#"function on_npc_death() while true do end end"#),
+        ]);
+        assert_eq!(host.scripts.len(), 2);
+
+        let output = host.run_hook(ScriptHook::ItemUse { player_id: "p1", item_id: "x" }, 0);
+        assert_eq!(output.actions, vec![ScriptAction::Message { player_id: "p1".to_string(), text: "sandboxed".to_string() }]);
+
+        // The budget stops the loop, and the next call gets a fresh one
+        assert!(host.run_hook(ScriptHook::NpcDeath { npc_id: "n", prototype_id: "pig", killer_id: "p1" }, 0).actions.is_empty());
+        assert_eq!(host.run_hook(ScriptHook::ItemUse { player_id: "p1", item_id: "x" }, 0).actions.len(), 1);
+    }
+}
@@ -5,6 +5,9 @@ import { Dashboard } from './pages/Dashboard'
 import { OnlinePlayers } from './pages/OnlinePlayers'
 import { Leaderboards } from './pages/Leaderboards'
 import { ItemRegistry } from './pages/ItemRegistry'
+import { Guilds } from './pages/Guilds'
+import { Achievements } from './pages/Achievements'
+import { SpawnRegions } from './pages/SpawnRegions'
 
 const queryClient = new QueryClient({
   defaultOptions: { queries: { refetchInterval: 30000 } },
@@ -19,6 +22,9 @@ export default function App() {
             <Route path="/" element={<Dashboard />} />
             <Route path="/players" element={<OnlinePlayers />} />
             <Route path="/leaderboards" element={<Leaderboards />} />
+            <Route path="/guilds" element={<Guilds />} />
+            <Route path="/achievements" element={<Achievements />} />
+            <Route path="/spawn-regions" element={<SpawnRegions />} />
             <Route path="/items" element={<ItemRegistry />} />
             <Route path="*" element={<Navigate to="/" replace />} />
           </Route>
@@ -23,6 +23,35 @@ export interface LeaderboardEntry {
   played_time: number
 }
 
+export interface GuildEntry {
+  name: string
+  tag: string
+  leader: string
+  member_count: number
+  total_level: number
+  average_combat_level: number
+  created_at: string | null
+}
+
+export interface AchievementUnlock {
+  character_name: string
+  achievement_id: string
+  achievement_name: string
+  description: string
+  title: string | null
+  unlocked_at: number
+}
+
+export interface SpawnRegionEntry {
+  id: string
+  entities: string[]
+  population: number
+  max_population: number
+  min_level: number
+  max_level: number
+  respawn_ms: number
+}
+
 export interface Equipment {
   slot_type: string
   attack_level_required: number
@@ -58,4 +87,9 @@ export const api = {
   leaderboard: (sort = 'combat_level', limit = 50) =>
     get<LeaderboardEntry[]>(`/leaderboard?sort=${sort}&limit=${limit}`),
   items: () => get<Item[]>('/items'),
+  guilds: (sort = 'total_level', limit = 50) =>
+    get<GuildEntry[]>(`/guilds?sort=${sort}&limit=${limit}`),
+  achievements: (limit = 50) =>
+    get<AchievementUnlock[]>(`/achievements?limit=${limit}`),
+  spawnRegions: () => get<SpawnRegionEntry[]>('/spawn-regions'),
 }
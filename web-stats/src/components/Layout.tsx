@@ -39,6 +39,39 @@ const navItems = [
       </svg>
     ),
   },
+  {
+    to: '/guilds',
+    label: 'Guilds',
+    icon: (
+      <svg width="18" height="18" viewBox="0 0 18 18" fill="none" stroke="currentColor" strokeWidth="1.5" strokeLinecap="round" strokeLinejoin="round">
+        <path d="M4 1v16" />
+        <path d="M4 2h10l-2.5 3.5L14 9H4" />
+      </svg>
+    ),
+  },
+  {
+    to: '/achievements',
+    label: 'Achievements',
+    icon: (
+      <svg width="18" height="18" viewBox="0 0 18 18" fill="none" stroke="currentColor" strokeWidth="1.5" strokeLinecap="round" strokeLinejoin="round">
+        <path d="M5 1h8v5a4 4 0 0 1-8 0z" />
+        <path d="M5 3H2v1.5A2.5 2.5 0 0 0 4.5 7H5" />
+        <path d="M13 3h3v1.5A2.5 2.5 0 0 1 13.5 7H13" />
+        <line x1="9" y1="10" x2="9" y2="14" />
+        <path d="M5.5 17h7l-1-3h-5z" />
+      </svg>
+    ),
+  },
+  {
+    to: '/spawn-regions',
+    label: 'Spawn Regions',
+    icon: (
+      <svg width="18" height="18" viewBox="0 0 18 18" fill="none" stroke="currentColor" strokeWidth="1.5" strokeLinecap="round" strokeLinejoin="round">
+        <path d="M2 5l5-3 4 2 5-2v11l-5 2-4-2-5 3z" />
+        <circle cx="9" cy="9" r="1.5" />
+      </svg>
+    ),
+  },
   {
     to: '/items',
     label: 'Item Registry',
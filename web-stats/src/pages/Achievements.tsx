@@ -0,0 +1,71 @@
+import { useQuery } from '@tanstack/react-query'
+import { api } from '../api'
+
+function timeAgo(ms: number) {
+  const seconds = Math.max(0, Math.floor((Date.now() - ms) / 1000))
+  if (seconds < 60) return 'just now'
+  if (seconds < 3600) return `${Math.floor(seconds / 60)}m ago`
+  if (seconds < 86400) return `${Math.floor(seconds / 3600)}h ago`
+  return `${Math.floor(seconds / 86400)}d ago`
+}
+
+export function Achievements() {
+  const { data, isLoading } = useQuery({
+    queryKey: ['achievements'],
+    queryFn: () => api.achievements(50),
+  })
+
+  return (
+    <div className="space-y-6">
+      <h1 className="text-2xl font-bold text-[#e2e4e9]">Recent Achievements</h1>
+
+      <div className="bg-[#1a1d28] rounded-lg border border-[#2a2d38] overflow-x-auto">
+        <table className="w-full">
+          <thead>
+            <tr className="bg-[#141722]">
+              <th className="px-4 py-3 text-left text-xs uppercase tracking-wider text-[#8b8fa3]">Player</th>
+              <th className="px-4 py-3 text-left text-xs uppercase tracking-wider text-[#8b8fa3]">Achievement</th>
+              <th className="px-4 py-3 text-left text-xs uppercase tracking-wider text-[#8b8fa3]">Title</th>
+              <th className="px-4 py-3 text-left text-xs uppercase tracking-wider text-[#8b8fa3]">Unlocked</th>
+            </tr>
+          </thead>
+          <tbody>
+            {isLoading ? (
+              Array.from({ length: 8 }).map((_, i) => (
+                <tr key={i} className="border-b border-[#2a2d38]">
+                  <td className="px-4 py-3"><div className="h-4 w-24 rounded bg-[#2a2d38] animate-pulse" /></td>
+                  <td className="px-4 py-3"><div className="h-4 w-40 rounded bg-[#2a2d38] animate-pulse" /></td>
+                  <td className="px-4 py-3"><div className="h-4 w-16 rounded bg-[#2a2d38] animate-pulse" /></td>
+                  <td className="px-4 py-3"><div className="h-4 w-16 rounded bg-[#2a2d38] animate-pulse" /></td>
+                </tr>
+              ))
+            ) : !data || data.length === 0 ? (
+              <tr>
+                <td colSpan={4} className="px-4 py-12 text-center text-[#8b8fa3]">
+                  No achievements unlocked yet
+                </td>
+              </tr>
+            ) : (
+              data.map(entry => (
+                <tr
+                  key={`${entry.character_name}-${entry.achievement_id}`}
+                  className="border-b border-[#2a2d38] hover:bg-[#141722] transition-colors"
+                >
+                  <td className="px-4 py-3 text-[#e2e4e9]">{entry.character_name}</td>
+                  <td className="px-4 py-3">
+                    <div className="text-[#c9a84c]">{entry.achievement_name}</div>
+                    <div className="text-xs text-[#8b8fa3]">{entry.description}</div>
+                  </td>
+                  <td className="px-4 py-3 text-[#8b8fa3]">{entry.title ?? '—'}</td>
+                  <td className="px-4 py-3 font-mono text-sm text-[#8b8fa3]" title={new Date(entry.unlocked_at).toLocaleString()}>
+                    {timeAgo(entry.unlocked_at)}
+                  </td>
+                </tr>
+              ))
+            )}
+          </tbody>
+        </table>
+      </div>
+    </div>
+  )
+}
@@ -0,0 +1,125 @@
+import { useState, useMemo } from 'react'
+import { useQuery } from '@tanstack/react-query'
+import { api, type GuildEntry } from '../api'
+
+const TABS = [
+  { label: 'Total Level', sort: 'total_level' },
+  { label: 'Members', sort: 'member_count' },
+  { label: 'Avg Combat', sort: 'average_combat_level' },
+]
+
+export function Guilds() {
+  const [activeTab, setActiveTab] = useState(0)
+  const [search, setSearch] = useState('')
+
+  const tab = TABS[activeTab]
+
+  const { data, isLoading } = useQuery({
+    queryKey: ['guilds', tab.sort],
+    queryFn: () => api.guilds(tab.sort, 100),
+  })
+
+  const filtered = useMemo(() => {
+    if (!data) return [] as { rank: number; entry: GuildEntry }[]
+    const ranked = data.map((entry, i) => ({ rank: i + 1, entry }))
+    if (!search) return ranked
+    const q = search.toLowerCase()
+    return ranked.filter(r => r.entry.name.toLowerCase().includes(q) || r.entry.tag.toLowerCase().includes(q))
+  }, [data, search])
+
+  const rankStyle = (rank: number) => {
+    if (rank === 1) return 'border-l-4 border-[#c9a84c]'
+    if (rank === 2) return 'border-l-4 border-[#a8a8a8]'
+    if (rank === 3) return 'border-l-4 border-[#b87333]'
+    return ''
+  }
+
+  const rankColor = (rank: number) => {
+    if (rank === 1) return 'text-[#c9a84c]'
+    if (rank === 2) return 'text-[#a8a8a8]'
+    if (rank === 3) return 'text-[#b87333]'
+    return 'text-[#5a5e72]'
+  }
+
+  return (
+    <div className="space-y-6">
+      <h1 className="text-2xl font-bold text-[#e2e4e9]">Guilds</h1>
+
+      {/* Tabs */}
+      <div className="flex flex-wrap gap-2">
+        {TABS.map((t, i) => (
+          <button
+            key={t.sort}
+            onClick={() => setActiveTab(i)}
+            className={`rounded-full px-4 py-2 text-sm font-medium transition-colors ${
+              i === activeTab
+                ? 'bg-[#c9a84c] text-[#0f1117]'
+                : 'bg-[#1a1d28] text-[#8b8fa3] hover:text-[#e2e4e9]'
+            }`}
+          >
+            {t.label}
+          </button>
+        ))}
+      </div>
+
+      {/* Search */}
+      <input
+        type="text"
+        placeholder="Search guild or tag..."
+        value={search}
+        onChange={e => setSearch(e.target.value)}
+        className="w-full max-w-sm rounded-lg border border-[#2a2d38] bg-[#141722] px-4 py-2 text-sm text-[#e2e4e9] placeholder-[#5a5e72] outline-none focus:border-[#c9a84c] transition-colors"
+      />
+
+      {/* Table */}
+      <div className="bg-[#1a1d28] rounded-lg border border-[#2a2d38] overflow-x-auto">
+        <table className="w-full">
+          <thead>
+            <tr className="bg-[#141722]">
+              <th className="px-4 py-3 text-left text-xs uppercase tracking-wider text-[#8b8fa3] w-16">Rank</th>
+              <th className="px-4 py-3 text-left text-xs uppercase tracking-wider text-[#8b8fa3]">Guild</th>
+              <th className="px-4 py-3 text-left text-xs uppercase tracking-wider text-[#8b8fa3]">Leader</th>
+              <th className="px-4 py-3 text-left text-xs uppercase tracking-wider text-[#8b8fa3]">Members</th>
+              <th className="px-4 py-3 text-left text-xs uppercase tracking-wider text-[#8b8fa3]">Total Level</th>
+              <th className="px-4 py-3 text-left text-xs uppercase tracking-wider text-[#8b8fa3]">Avg Combat</th>
+            </tr>
+          </thead>
+          <tbody>
+            {isLoading ? (
+              Array.from({ length: 8 }).map((_, i) => (
+                <tr key={i} className="border-b border-[#2a2d38]">
+                  <td className="px-4 py-3"><div className="h-4 w-8 rounded bg-[#2a2d38] animate-pulse" /></td>
+                  <td className="px-4 py-3"><div className="h-4 w-32 rounded bg-[#2a2d38] animate-pulse" /></td>
+                  <td className="px-4 py-3"><div className="h-4 w-20 rounded bg-[#2a2d38] animate-pulse" /></td>
+                  <td className="px-4 py-3"><div className="h-4 w-10 rounded bg-[#2a2d38] animate-pulse" /></td>
+                  <td className="px-4 py-3"><div className="h-4 w-12 rounded bg-[#2a2d38] animate-pulse" /></td>
+                  <td className="px-4 py-3"><div className="h-4 w-12 rounded bg-[#2a2d38] animate-pulse" /></td>
+                </tr>
+              ))
+            ) : filtered.length === 0 ? (
+              <tr>
+                <td colSpan={6} className="px-4 py-12 text-center text-[#8b8fa3]">
+                  No guilds found
+                </td>
+              </tr>
+            ) : (
+              filtered.map(({ rank, entry }) => (
+                <tr key={entry.tag} className={`border-b border-[#2a2d38] hover:bg-[#141722] transition-colors ${rankStyle(rank)}`}>
+                  <td className={`px-4 py-3 font-mono font-bold ${rankColor(rank)}`}>{rank}</td>
+                  <td className="px-4 py-3 text-[#e2e4e9]">
+                    <span className="mr-2 font-mono text-[#c9a84c]">&lt;{entry.tag}&gt;</span>
+                    {entry.name}
+                  </td>
+                  <td className="px-4 py-3 text-[#8b8fa3]">{entry.leader}</td>
+                  <td className="px-4 py-3 font-mono text-[#e2e4e9]">{entry.member_count}</td>
+                  <td className="px-4 py-3 font-mono text-[#e2e4e9]">{entry.total_level}</td>
+                  <td className="px-4 py-3 font-mono text-[#e2e4e9]">{entry.average_combat_level.toFixed(1)}</td>
+                </tr>
+              ))
+            )}
+          </tbody>
+        </table>
+      </div>
+    </div>
+  )
+}
@@ -0,0 +1,97 @@
+import { useState, useMemo } from 'react'
+import { useQuery } from '@tanstack/react-query'
+import { api } from '../api'
+
+export function SpawnRegions() {
+  const [search, setSearch] = useState('')
+
+  const { data, isLoading } = useQuery({
+    queryKey: ['spawnRegions'],
+    queryFn: () => api.spawnRegions(),
+    refetchInterval: 15000,
+  })
+
+  const filtered = useMemo(() => {
+    if (!data) return []
+    if (!search) return data
+    const q = search.toLowerCase()
+    return data.filter(r => r.id.toLowerCase().includes(q) || r.entities.some(e => e.toLowerCase().includes(q)))
+  }, [data, search])
+
+  const fillColor = (population: number, max: number) => {
+    if (population >= max) return 'bg-[#4ade80]'
+    if (population * 2 >= max) return 'bg-[#c9a84c]'
+    return 'bg-[#f87171]'
+  }
+
+  return (
+    <div className="space-y-6">
+      <h1 className="text-2xl font-bold text-[#e2e4e9]">Spawn Regions</h1>
+
+      {/* Search */}
+      <input
+        type="text"
+        placeholder="Search region or monster..."
+        value={search}
+        onChange={e => setSearch(e.target.value)}
+        className="w-full max-w-sm rounded-lg border border-[#2a2d38] bg-[#141722] px-4 py-2 text-sm text-[#e2e4e9] placeholder-[#5a5e72] outline-none focus:border-[#c9a84c] transition-colors"
+      />
+
+      {/* Table */}
+      <div className="bg-[#1a1d28] rounded-lg border border-[#2a2d38] overflow-x-auto">
+        <table className="w-full">
+          <thead>
+            <tr className="bg-[#141722]">
+              <th className="px-4 py-3 text-left text-xs uppercase tracking-wider text-[#8b8fa3]">Region</th>
+              <th className="px-4 py-3 text-left text-xs uppercase tracking-wider text-[#8b8fa3]">Monsters</th>
+              <th className="px-4 py-3 text-left text-xs uppercase tracking-wider text-[#8b8fa3]">Population</th>
+              <th className="px-4 py-3 text-left text-xs uppercase tracking-wider text-[#8b8fa3]">Levels</th>
+              <th className="px-4 py-3 text-left text-xs uppercase tracking-wider text-[#8b8fa3]">Respawn</th>
+            </tr>
+          </thead>
+          <tbody>
+            {isLoading ? (
+              Array.from({ length: 5 }).map((_, i) => (
+                <tr key={i} className="border-b border-[#2a2d38]">
+                  <td className="px-4 py-3"><div className="h-4 w-32 rounded bg-[#2a2d38] animate-pulse" /></td>
+                  <td className="px-4 py-3"><div className="h-4 w-24 rounded bg-[#2a2d38] animate-pulse" /></td>
+                  <td className="px-4 py-3"><div className="h-4 w-28 rounded bg-[#2a2d38] animate-pulse" /></td>
+                  <td className="px-4 py-3"><div className="h-4 w-12 rounded bg-[#2a2d38] animate-pulse" /></td>
+                  <td className="px-4 py-3"><div className="h-4 w-12 rounded bg-[#2a2d38] animate-pulse" /></td>
+                </tr>
+              ))
+            ) : filtered.length === 0 ? (
+              <tr>
+                <td colSpan={5} className="px-4 py-12 text-center text-[#8b8fa3]">
+                  No spawn regions found
+                </td>
+              </tr>
+            ) : (
+              filtered.map(region => (
+                <tr key={region.id} className="border-b border-[#2a2d38] hover:bg-[#141722] transition-colors">
+                  <td className="px-4 py-3 font-mono text-[#e2e4e9]">{region.id}</td>
+                  <td className="px-4 py-3 text-[#8b8fa3]">{region.entities.join(', ')}</td>
+                  <td className="px-4 py-3">
+                    <div className="flex items-center gap-3">
+                      <div className="h-2 w-24 rounded-full bg-[#2a2d38] overflow-hidden">
+                        <div
+                          className={`h-full ${fillColor(region.population, region.max_population)}`}
+                          style={{ width: `${Math.min(100, (region.population / Math.max(1, region.max_population)) * 100)}%` }}
+                        />
+                      </div>
+                      <span className="font-mono text-sm text-[#e2e4e9]">{region.population}/{region.max_population}</span>
+                    </div>
+                  </td>
+                  <td className="px-4 py-3 font-mono text-[#e2e4e9]">
+                    {region.min_level === region.max_level ? region.min_level : `${region.min_level}-${region.max_level}`}
+                  </td>
+                  <td className="px-4 py-3 font-mono text-[#8b8fa3]">{(region.respawn_ms / 1000).toFixed(0)}s</td>
+                </tr>
+              ))
+            )}
+          </tbody>
+        </table>
+      </div>
+    </div>
+  )
+}